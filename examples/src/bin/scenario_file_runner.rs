@@ -0,0 +1,41 @@
+use runner_examples::scenario_file::{self, LoadedScenario};
+use testing_framework_core::scenario::Deployer as _;
+use testing_framework_runner_compose::ComposeDeployer;
+use tracing::{info, warn};
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+
+    let Some(path) = std::env::args().nth(1) else {
+        warn!("usage: scenario_file_runner <scenario.yaml|scenario.toml>");
+        std::process::exit(2);
+    };
+
+    if let Err(err) = run_scenario_file(&path).await {
+        warn!("scenario {path} failed: {err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run_scenario_file(path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let scenario = scenario_file::from_file(path)?;
+    let deployer = ComposeDeployer::new();
+    info!(
+        path,
+        node_control = scenario.requires_node_control(),
+        "deploying scenario from file"
+    );
+
+    match scenario {
+        LoadedScenario::Plain(mut plan) => {
+            let runner = deployer.deploy(&plan).await?;
+            runner.run(&mut plan).await?;
+        }
+        LoadedScenario::NodeControl(mut plan) => {
+            let runner = deployer.deploy(&plan).await?;
+            runner.run(&mut plan).await?;
+        }
+    }
+    Ok(())
+}
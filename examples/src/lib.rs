@@ -1,7 +1,7 @@
 use testing_framework_core::scenario::Metrics;
 pub use testing_framework_workflows::{
     builder::{ChaosBuilderExt, ScenarioBuilderExt},
-    expectations, util, workloads,
+    expectations, scenario_file, util, workloads,
 };
 
 /// Metrics are currently disabled in this branch; return a stub handle.
@@ -45,7 +45,7 @@ host_identifier_default="${role}-$(hostname -i)"
 
 export CFG_FILE_PATH="/config.yaml" \
       CFG_SERVER_ADDR="${CFG_SERVER_ADDR:-http://cfgsync:${NOMOS_CFGSYNC_PORT:-4400}}" \
-       CFG_HOST_IP=$(hostname -i) \
+       CFG_HOST_IP="$(hostname -i)" \
        CFG_HOST_KIND="${CFG_HOST_KIND:-$role}" \
        CFG_HOST_IDENTIFIER="${CFG_HOST_IDENTIFIER:-$host_identifier_default}" \
        NOMOS_KZGRS_PARAMS_PATH="${NOMOS_KZGRS_PARAMS_PATH:-${KZG_CONTAINER_PATH}}" \
@@ -53,14 +53,21 @@ export CFG_FILE_PATH="/config.yaml" \
        LOG_LEVEL="${LOG_LEVEL:-INFO}" \
        POL_PROOF_DEV_MODE="${POL_PROOF_DEV_MODE:-true}"
 
-# Ensure recovery directory exists to avoid early crashes in services that
-# persist state.
-mkdir -p /recovery
+# Nodes placed in a network region delay their traffic towards other regions.
+# Applied on every start since the qdiscs do not survive container restarts.
+if [ -n "${NOMOS_EGRESS_SHAPING:-}" ]; then
+  sh -c "${NOMOS_EGRESS_SHAPING}"
+fi
 
-# cfgsync-server can start a little after the container; retry until it is
-# reachable instead of exiting immediately and crash-looping.
+# Ensure storage directories exist to avoid early crashes in services that
+# persist state. Paths in the generated config are relative to the working
+# directory, which compose bind-mounts from the host.
+mkdir -p ./recovery ./blobs
+
+# cfgsync-client retries with backoff until CFG_RETRY_DEADLINE_SECS elapses;
+# restart it a few times on top of that instead of crash-looping the container.
 attempt=0
-max_attempts=30
+max_attempts=3
 sleep_seconds=3
 until /usr/bin/cfgsync-client; do
   attempt=$((attempt + 1))
@@ -72,4 +79,13 @@ until /usr/bin/cfgsync-client; do
   sleep "$sleep_seconds"
 done
 
+# Late-joining nodes fetch their config with the rest of the cluster but only
+# start once the runner creates the join marker.
+if [ -n "${NOMOS_JOIN_MARKER:-}" ]; then
+  echo "waiting for ${NOMOS_JOIN_MARKER} before starting ${role}"
+  until [ -e "${NOMOS_JOIN_MARKER}" ]; do
+    sleep 1
+  done
+fi
+
 exec "${bin_path}" /config.yaml
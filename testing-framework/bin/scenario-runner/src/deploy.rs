@@ -0,0 +1,178 @@
+use clap::ValueEnum;
+use testing_framework_core::{
+    errors::{ClassifiedError, ErrorKind},
+    scenario::{Deployer, DryRunReport, DynError, Scenario, ScenarioError},
+};
+use testing_framework_runner_compose::ComposeDeployer;
+use testing_framework_runner_external::ExternalDeployer;
+use testing_framework_runner_k8s::K8sDeployer;
+use testing_framework_runner_local::LocalDeployer;
+use testing_framework_runner_process::ProcessDeployer;
+use testing_framework_workflows::scenario_file::LoadedScenario;
+use thiserror::Error;
+
+/// Backend used to deploy a scenario. Each deployer reads its usual
+/// environment (`NOMOS_TESTNET_IMAGE`, `NOMOS_NODE_BIN`,
+/// `NOMOS_EXTERNAL_VALIDATORS`, ...).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RunnerKind {
+    /// In-process nodes; no node control, so chaos scenarios are rejected.
+    Local,
+    /// Native child processes.
+    Process,
+    /// Docker Compose stack.
+    Compose,
+    /// Kubernetes via Helm.
+    K8s,
+    /// An already running cluster; no node control, so chaos scenarios are
+    /// rejected.
+    External,
+}
+
+/// Failures deploying or running a scenario.
+#[derive(Debug, Error)]
+pub enum DeployError {
+    #[error(
+        "the {runner} runner has no node control; run this scenario with process, compose or k8s"
+    )]
+    NodeControlUnsupported { runner: &'static str },
+    #[error("deployment failed ({kind}): {source}")]
+    Deploy {
+        kind: ErrorKind,
+        #[source]
+        source: DynError,
+    },
+    #[error("dry run failed ({kind}): {source}")]
+    DryRun {
+        kind: ErrorKind,
+        #[source]
+        source: DynError,
+    },
+    #[error(transparent)]
+    Run(#[from] ScenarioError),
+}
+
+impl ClassifiedError for DeployError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NodeControlUnsupported { .. } => ErrorKind::Configuration,
+            Self::Deploy { kind, .. } | Self::DryRun { kind, .. } => *kind,
+            Self::Run(source) => source.kind(),
+        }
+    }
+}
+
+/// Deploy `scenario` with the chosen backend and run it to completion.
+pub async fn run_scenario(runner: RunnerKind, scenario: LoadedScenario) -> Result<(), DeployError> {
+    match (runner, scenario) {
+        (RunnerKind::Local, LoadedScenario::Plain(mut scenario)) => {
+            execute(&LocalDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::Local, LoadedScenario::NodeControl(_)) => {
+            Err(DeployError::NodeControlUnsupported { runner: "local" })
+        }
+        (RunnerKind::Process, LoadedScenario::Plain(mut scenario)) => {
+            execute(&ProcessDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::Process, LoadedScenario::NodeControl(mut scenario)) => {
+            execute(&ProcessDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::Compose, LoadedScenario::Plain(mut scenario)) => {
+            execute(&ComposeDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::Compose, LoadedScenario::NodeControl(mut scenario)) => {
+            execute(&ComposeDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::K8s, LoadedScenario::Plain(mut scenario)) => {
+            execute(&K8sDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::K8s, LoadedScenario::NodeControl(mut scenario)) => {
+            execute(&K8sDeployer::new(), &mut scenario).await
+        }
+        (RunnerKind::External, LoadedScenario::Plain(mut scenario)) => {
+            let deployer = ExternalDeployer::from_env().map_err(|err| DeployError::Deploy {
+                kind: err.kind(),
+                source: err.into(),
+            })?;
+            execute(&deployer, &mut scenario).await
+        }
+        (RunnerKind::External, LoadedScenario::NodeControl(_)) => {
+            Err(DeployError::NodeControlUnsupported { runner: "external" })
+        }
+    }
+}
+
+/// Describe what deploying `scenario` with the chosen backend would bring up,
+/// rendering the backend's artifacts without starting anything.
+pub fn dry_run_scenario(
+    runner: RunnerKind,
+    scenario: LoadedScenario,
+) -> Result<DryRunReport, DeployError> {
+    match (runner, scenario) {
+        (RunnerKind::Local, LoadedScenario::Plain(scenario)) => {
+            preview(&LocalDeployer::new(), &scenario)
+        }
+        (RunnerKind::Local, LoadedScenario::NodeControl(_)) => {
+            Err(DeployError::NodeControlUnsupported { runner: "local" })
+        }
+        (RunnerKind::Process, LoadedScenario::Plain(scenario)) => {
+            preview(&ProcessDeployer::new(), &scenario)
+        }
+        (RunnerKind::Process, LoadedScenario::NodeControl(scenario)) => {
+            preview(&ProcessDeployer::new(), &scenario)
+        }
+        (RunnerKind::Compose, LoadedScenario::Plain(scenario)) => {
+            preview(&ComposeDeployer::new(), &scenario)
+        }
+        (RunnerKind::Compose, LoadedScenario::NodeControl(scenario)) => {
+            preview(&ComposeDeployer::new(), &scenario)
+        }
+        (RunnerKind::K8s, LoadedScenario::Plain(scenario)) => {
+            preview(&K8sDeployer::new(), &scenario)
+        }
+        (RunnerKind::K8s, LoadedScenario::NodeControl(scenario)) => {
+            preview(&K8sDeployer::new(), &scenario)
+        }
+        (RunnerKind::External, LoadedScenario::Plain(scenario)) => {
+            let deployer = ExternalDeployer::from_env().map_err(|err| DeployError::DryRun {
+                kind: err.kind(),
+                source: err.into(),
+            })?;
+            preview(&deployer, &scenario)
+        }
+        (RunnerKind::External, LoadedScenario::NodeControl(_)) => {
+            Err(DeployError::NodeControlUnsupported { runner: "external" })
+        }
+    }
+}
+
+fn preview<Caps, D>(deployer: &D, scenario: &Scenario<Caps>) -> Result<DryRunReport, DeployError>
+where
+    D: Deployer<Caps>,
+    D::Error: Into<DynError>,
+{
+    deployer
+        .dry_run(scenario)
+        .map_err(|err| DeployError::DryRun {
+            kind: err.kind(),
+            source: err.into(),
+        })
+}
+
+async fn execute<Caps, D>(deployer: &D, scenario: &mut Scenario<Caps>) -> Result<(), DeployError>
+where
+    Caps: Send + Sync,
+    D: Deployer<Caps>,
+    D::Error: Into<DynError>,
+{
+    let runner = deployer
+        .deploy(scenario)
+        .await
+        .map_err(|err| DeployError::Deploy {
+            kind: err.kind(),
+            source: err.into(),
+        })?;
+    // Dropping the handle tears the deployment down.
+    drop(runner.run_with_signal_handling(scenario).await?);
+    Ok(())
+}
@@ -0,0 +1,128 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use testing_framework_workflows::scenario_file::is_scenario_file;
+use thiserror::Error;
+
+/// Failures locating scenario files.
+#[derive(Debug, Error)]
+pub enum LibraryError {
+    #[error("no scenario `{name}` in {dir}")]
+    UnknownScenario { name: String, dir: PathBuf },
+    #[error("failed to read scenario directory {dir}: {source}")]
+    ReadDir {
+        dir: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Directory of scenario files, addressed by file stem.
+#[derive(Clone, Debug)]
+pub struct ScenarioLibrary {
+    dir: PathBuf,
+}
+
+impl ScenarioLibrary {
+    #[must_use]
+    pub const fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Scenario files in the directory, sorted by path.
+    pub fn entries(&self) -> Result<Vec<PathBuf>, LibraryError> {
+        let read_dir_err = |source| LibraryError::ReadDir {
+            dir: self.dir.clone(),
+            source,
+        };
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(&self.dir).map_err(read_dir_err)? {
+            let path = entry.map_err(read_dir_err)?.path();
+            if path.is_file() && is_scenario_file(&path) {
+                paths.push(path);
+            }
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    /// Resolves `scenario` as a path first, then as the stem of a file in
+    /// the directory (`smoke` finds `smoke.yaml`).
+    pub fn resolve(&self, scenario: &str) -> Result<PathBuf, LibraryError> {
+        let path = Path::new(scenario);
+        if path.is_file() {
+            return Ok(path.to_path_buf());
+        }
+
+        self.entries()?
+            .into_iter()
+            .find(|entry| name_of(entry) == scenario)
+            .ok_or_else(|| LibraryError::UnknownScenario {
+                name: scenario.to_owned(),
+                dir: self.dir.clone(),
+            })
+    }
+}
+
+/// Scenario name derived from its file stem.
+#[must_use]
+pub fn name_of(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library() -> (tempfile::TempDir, ScenarioLibrary) {
+        let dir = tempfile::tempdir().expect("temp dir");
+        for file in ["soak.toml", "smoke.yaml", "chaos.yml", "README.md"] {
+            fs::write(dir.path().join(file), "").expect("write scenario");
+        }
+        fs::create_dir(dir.path().join("nested.yaml")).expect("create dir");
+        let library = ScenarioLibrary::new(dir.path().to_path_buf());
+        (dir, library)
+    }
+
+    #[test]
+    fn entries_are_sorted_scenario_files() {
+        let (_dir, library) = library();
+
+        let names: Vec<String> = library
+            .entries()
+            .expect("entries")
+            .iter()
+            .map(|path| name_of(path))
+            .collect();
+        assert_eq!(names, ["chaos", "smoke", "soak"]);
+    }
+
+    #[test]
+    fn resolves_by_path_then_stem() {
+        let (dir, library) = library();
+        let smoke = dir.path().join("smoke.yaml");
+
+        assert_eq!(library.resolve("smoke").expect("by stem"), smoke);
+        let by_path = smoke.to_str().expect("utf-8 path");
+        assert_eq!(library.resolve(by_path).expect("by path"), smoke);
+        assert!(matches!(
+            library.resolve("README"),
+            Err(LibraryError::UnknownScenario { name, .. }) if name == "README"
+        ));
+    }
+
+    #[test]
+    fn missing_directory_is_reported() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let library = ScenarioLibrary::new(dir.path().join("scenarios"));
+
+        assert!(matches!(
+            library.resolve("smoke"),
+            Err(LibraryError::ReadDir { .. })
+        ));
+    }
+}
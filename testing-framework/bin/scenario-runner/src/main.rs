@@ -0,0 +1,260 @@
+//! Command-line front end over the scenario file library: run a scenario
+//! against any deployer, list and validate scenario files, and summarise run
+//! reports without writing a dedicated test binary per scenario.
+
+mod deploy;
+mod library;
+mod summary;
+
+use std::{
+    fs,
+    num::NonZeroU64,
+    path::{Path, PathBuf},
+    process,
+};
+
+use clap::{Args, Parser, Subcommand};
+use testing_framework_core::{
+    errors::{ClassifiedError as _, ErrorKind},
+    scenario::{ReportError, ReportFormat, RunReport, ScenarioError},
+};
+use testing_framework_workflows::scenario_file::{self, LoadedScenario, ScenarioFileError};
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::{
+    deploy::{DeployError, RunnerKind, dry_run_scenario, run_scenario},
+    library::{LibraryError, ScenarioLibrary},
+};
+
+/// Directory reports are written to when neither the scenario nor the
+/// command line names a destination.
+const DEFAULT_REPORT_DIR: &str = "target/scenario-reports";
+
+/// Exit status of a run that failed for a retryable reason (`EX_TEMPFAIL`),
+/// so CI can retry infrastructure hiccups and timeouts but not real failures.
+const EXIT_RETRYABLE: i32 = 75;
+
+#[derive(Parser, Debug)]
+#[command(about = "Run, list and validate declarative Nomos scenarios")]
+struct Cli {
+    /// Directory scenario names are resolved against.
+    #[arg(
+        long,
+        global = true,
+        env = "NOMOS_SCENARIO_DIR",
+        default_value = "examples/scenarios"
+    )]
+    scenarios: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Deploy and run a scenario; exits non-zero when it fails, with 75 when
+    /// the failure is retryable (infrastructure or timeout) and with 130 or
+    /// 143 after SIGINT or SIGTERM.
+    Run(RunArgs),
+    /// List the scenarios in the scenario directory.
+    List,
+    /// Check scenario files without deploying anything. Checks the whole
+    /// scenario directory when no scenario is given.
+    Validate {
+        /// Scenario files or names.
+        scenarios: Vec<String>,
+    },
+    /// Summarise a JSON run report.
+    Report {
+        path: PathBuf,
+        /// Also render the report as an HTML page at this path.
+        #[arg(long)]
+        html: Option<PathBuf>,
+    },
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    /// Scenario file, or the name of a file in the scenario directory.
+    scenario: String,
+    /// Backend used to deploy the scenario.
+    #[arg(long, value_enum, env = "NOMOS_RUNNER", default_value = "compose")]
+    runner: RunnerKind,
+    /// Where to write the run report; overrides the scenario's `report`.
+    #[arg(long, env = "NOMOS_RUN_REPORT")]
+    report: Option<PathBuf>,
+    /// Override the scenario's seed.
+    #[arg(long, env = "NOMOS_SCENARIO_SEED")]
+    seed: Option<u64>,
+    /// Override the scenario's run length, in seconds.
+    #[arg(long)]
+    duration_secs: Option<NonZeroU64>,
+    /// Render the deployment and check the scenario without deploying it,
+    /// printing the nodes, images and workload plans it would run.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Failures of a scenario-runner command.
+#[derive(Debug, Error)]
+enum CliError {
+    #[error(transparent)]
+    Library(#[from] LibraryError),
+    #[error(transparent)]
+    ScenarioFile(#[from] ScenarioFileError),
+    #[error(transparent)]
+    Deploy(#[from] DeployError),
+    #[error(transparent)]
+    Report(#[from] ReportError),
+    #[error("{invalid} of {total} scenario files are invalid")]
+    InvalidScenarios { invalid: usize, total: usize },
+    #[error("scenario failed")]
+    ScenarioFailed,
+    #[error("{failed} dry-run checks failed")]
+    DryRunFailed { failed: usize },
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+    let cli = Cli::parse();
+    let library = ScenarioLibrary::new(cli.scenarios);
+
+    let result = match cli.command {
+        Command::Run(args) => run(&library, args).await,
+        Command::List => list(&library),
+        Command::Validate { scenarios } => validate(&library, &scenarios),
+        Command::Report { path, html } => report(&path, html),
+    };
+    if let Err(err) = result {
+        eprintln!("error: {err}");
+        if let CliError::Deploy(DeployError::Run(ScenarioError::Interrupted(signal))) = &err {
+            process::exit(signal.exit_code());
+        }
+        let kind = err.kind();
+        if let Some(kind) = kind {
+            eprintln!("failure kind: {kind}");
+        }
+        let retryable = kind.is_some_and(ErrorKind::is_retryable);
+        process::exit(if retryable { EXIT_RETRYABLE } else { 1 });
+    }
+}
+
+impl CliError {
+    /// Classification of failed runs; scenario file and CLI errors have none.
+    fn kind(&self) -> Option<ErrorKind> {
+        match self {
+            Self::Deploy(err) => Some(err.kind()),
+            _ => None,
+        }
+    }
+}
+
+async fn run(library: &ScenarioLibrary, args: RunArgs) -> Result<(), CliError> {
+    let path = library.resolve(&args.scenario)?;
+    let mut spec = scenario_file::load_spec(&path)?;
+    if let Some(seed) = args.seed {
+        spec.seed = Some(seed);
+    }
+    if let Some(secs) = args.duration_secs {
+        spec.duration_secs = Some(secs);
+        spec.run_blocks = None;
+    }
+    let report_path = args
+        .report
+        .or_else(|| spec.report.clone())
+        .unwrap_or_else(|| {
+            PathBuf::from(DEFAULT_REPORT_DIR).join(format!("{}.json", library::name_of(&path)))
+        });
+    spec.report = Some(report_path.clone());
+    spec.validate()?;
+    if args.dry_run {
+        return dry_run(&path, args.runner, spec.build()?);
+    }
+    // A report left by an earlier run must not be summarised if this one
+    // fails to deploy.
+    let _ = fs::remove_file(&report_path);
+
+    info!(
+        scenario = %path.display(),
+        runner = ?args.runner,
+        report = %report_path.display(),
+        "running scenario"
+    );
+    let outcome = run_scenario(args.runner, spec.build()?).await;
+
+    // The runner persists the report whether or not the run passed.
+    if ReportFormat::from_path(&report_path) == ReportFormat::Json {
+        match RunReport::read(&report_path) {
+            Ok(report) => summary::print(&report),
+            Err(err) => warn!(error = %err, "run report unavailable"),
+        }
+    }
+    outcome?;
+    Ok(())
+}
+
+fn dry_run(path: &Path, runner: RunnerKind, scenario: LoadedScenario) -> Result<(), CliError> {
+    info!(scenario = %path.display(), runner = ?runner, "dry-running scenario");
+    let report = dry_run_scenario(runner, scenario)?;
+    println!("{report}");
+    let failed = report.failures().count();
+    if failed > 0 {
+        return Err(CliError::DryRunFailed { failed });
+    }
+    Ok(())
+}
+
+fn list(library: &ScenarioLibrary) -> Result<(), CliError> {
+    for path in library.entries()? {
+        match scenario_file::load_spec(&path) {
+            Ok(spec) => println!("{}", summary::describe(&library::name_of(&path), &spec)),
+            Err(err) => println!("{}\tinvalid: {err}", library::name_of(&path)),
+        }
+    }
+    Ok(())
+}
+
+fn validate(library: &ScenarioLibrary, scenarios: &[String]) -> Result<(), CliError> {
+    let paths = if scenarios.is_empty() {
+        library.entries()?
+    } else {
+        scenarios
+            .iter()
+            .map(|scenario| library.resolve(scenario))
+            .collect::<Result<_, _>>()?
+    };
+
+    let mut invalid = 0;
+    for path in &paths {
+        match scenario_file::load_spec(path).and_then(|spec| spec.validate()) {
+            Ok(()) => println!("ok\t{}", path.display()),
+            Err(err) => {
+                invalid += 1;
+                println!("invalid\t{}: {err}", path.display());
+            }
+        }
+    }
+
+    if invalid > 0 {
+        return Err(CliError::InvalidScenarios {
+            invalid,
+            total: paths.len(),
+        });
+    }
+    Ok(())
+}
+
+fn report(path: &Path, html: Option<PathBuf>) -> Result<(), CliError> {
+    let report = RunReport::read(path)?;
+    summary::print(&report);
+    if let Some(html) = html {
+        report.write(&html, ReportFormat::Html)?;
+        println!("html report written to {}", html.display());
+    }
+    if report.passed() {
+        Ok(())
+    } else {
+        Err(CliError::ScenarioFailed)
+    }
+}
@@ -0,0 +1,83 @@
+use testing_framework_core::scenario::{RunReport, Severity};
+use testing_framework_workflows::scenario_file::ScenarioSpec;
+
+/// Print the verdict, expectation outcomes and workload counters of a run.
+pub fn print(report: &RunReport) {
+    let verdict = if report.passed() { "PASSED" } else { "FAILED" };
+    let warnings = report.warnings().count();
+    if warnings > 0 {
+        println!(
+            "{verdict} with {warnings} warning(s) after {:.1}s",
+            report.elapsed_secs
+        );
+    } else {
+        println!("{verdict} after {:.1}s", report.elapsed_secs);
+    }
+
+    for outcome in &report.expectations {
+        let status = match (outcome.passed, outcome.severity) {
+            (true, _) => "pass",
+            (false, Severity::Soft) => "WARN",
+            (false, Severity::Hard) => "FAIL",
+        };
+        match &outcome.error {
+            Some(error) => println!("  {status}\t{}: {error}", outcome.name),
+            None => println!("  {status}\t{}", outcome.name),
+        }
+    }
+    for (workload, counters) in &report.workloads {
+        let counters = counters
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        println!("  {workload}\t{counters}");
+    }
+    let failed_checkpoints = report
+        .checkpoints
+        .iter()
+        .filter(|checkpoint| !checkpoint.passed)
+        .count();
+    if failed_checkpoints > 0 {
+        println!("  {failed_checkpoints} checkpoint(s) failed");
+    }
+    if !report.node_events.is_empty() {
+        println!("  {} node event(s)", report.node_events.len());
+    }
+    if !report.chaos_timeline.is_empty() {
+        println!("  {} chaos timeline fault(s)", report.chaos_timeline.len());
+    }
+}
+
+/// One-line description of a scenario file for listings.
+#[must_use]
+pub fn describe(name: &str, spec: &ScenarioSpec) -> String {
+    let length = match (spec.duration_secs, spec.run_blocks) {
+        (Some(secs), _) => format!("{secs}s"),
+        (None, Some(blocks)) => format!("{blocks} blocks"),
+        (None, None) => "default length".to_owned(),
+    };
+    let mut features = Vec::new();
+    if spec.workloads.transactions.is_some() {
+        features.push("transactions");
+    }
+    if spec.workloads.da.is_some() {
+        features.push("da");
+    }
+    features.extend(
+        spec.workloads
+            .registered
+            .iter()
+            .map(|component| component.name.as_str()),
+    );
+    if spec.requires_node_control() {
+        features.push("node-control");
+    }
+
+    format!(
+        "{name}\t{}v/{}e\t{length}\t{}",
+        spec.topology.validators,
+        spec.topology.executors,
+        features.join(",")
+    )
+}
@@ -1,4 +1,10 @@
-use std::{env, net::Ipv4Addr, ops::Mul as _, sync::LazyLock, time::Duration};
+use std::{
+    env,
+    net::{IpAddr, Ipv4Addr},
+    ops::Mul as _,
+    sync::LazyLock,
+    time::Duration,
+};
 
 use nomos_core::sdp::ProviderId;
 use nomos_libp2p::{Multiaddr, PeerId, multiaddr};
@@ -25,6 +31,18 @@ pub fn node_address_from_port(port: u16) -> Multiaddr {
     multiaddr(Ipv4Addr::LOCALHOST, port)
 }
 
+/// QUIC multiaddr for `ip`, using `/ip4/` or `/ip6/` as appropriate.
+#[must_use]
+pub fn quic_multiaddr(ip: IpAddr, port: u16) -> Multiaddr {
+    let family = match ip {
+        IpAddr::V4(_) => "ip4",
+        IpAddr::V6(_) => "ip6",
+    };
+    format!("/{family}/{ip}/udp/{port}/quic-v1")
+        .parse()
+        .expect("valid quic multiaddr")
+}
+
 #[must_use]
 pub fn secret_key_to_peer_id(node_key: nomos_libp2p::ed25519::SecretKey) -> PeerId {
     PeerId::from_public_key(
@@ -121,7 +121,7 @@ pub(crate) fn da_verifier_config(
         tx_verifier_settings: (),
         network_adapter_settings: (),
         storage_adapter_settings: VerifierStorageAdapterSettings {
-            blob_storage_directory: "./".into(),
+            blob_storage_directory: "./blobs".into(),
         },
         mempool_trigger_settings: MempoolPublishTriggerConfig {
             publish_threshold: NonNegativeF64::try_from(0.8).unwrap(),
@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::net::{Ipv4Addr, SocketAddr};
 
 use nomos_utils::net::get_available_tcp_port;
 
@@ -10,14 +10,25 @@ pub struct GeneralApiConfig {
 
 #[must_use]
 pub fn create_api_configs(ids: &[[u8; 32]]) -> Vec<GeneralApiConfig> {
-    ids.iter()
-        .map(|_| GeneralApiConfig {
-            address: format!("127.0.0.1:{}", get_available_tcp_port().unwrap())
-                .parse()
-                .unwrap(),
-            testing_http_address: format!("127.0.0.1:{}", get_available_tcp_port().unwrap())
-                .parse()
-                .unwrap(),
+    let free_port = || get_available_tcp_port().unwrap();
+    let api_ports = ids.iter().map(|_| free_port()).collect::<Vec<_>>();
+    let testing_ports = ids.iter().map(|_| free_port()).collect::<Vec<_>>();
+    create_api_configs_with_ports(&api_ports, &testing_ports)
+}
+
+/// API configs on loopback with the given API and testing HTTP ports, in node
+/// order.
+#[must_use]
+pub fn create_api_configs_with_ports(
+    api_ports: &[u16],
+    testing_ports: &[u16],
+) -> Vec<GeneralApiConfig> {
+    api_ports
+        .iter()
+        .zip(testing_ports)
+        .map(|(api, testing)| GeneralApiConfig {
+            address: SocketAddr::from((Ipv4Addr::LOCALHOST, *api)),
+            testing_http_address: SocketAddr::from((Ipv4Addr::LOCALHOST, *testing)),
         })
         .collect()
 }
@@ -1,14 +1,16 @@
 use core::time::Duration;
-use std::{num::NonZeroU64, str::FromStr as _};
+use std::num::NonZeroU64;
 
 use key_management_system_service::keys::{Ed25519Key, UnsecuredEd25519Key, ZkKey};
 use nomos_blend_service::{
     core::backends::libp2p::Libp2pBlendBackendSettings as Libp2pCoreBlendBackendSettings,
     edge::backends::libp2p::Libp2pBlendBackendSettings as Libp2pEdgeBlendBackendSettings,
 };
-use nomos_libp2p::{Multiaddr, protocol_name::StreamProtocol};
+use nomos_libp2p::protocol_name::StreamProtocol;
 use num_bigint::BigUint;
 
+use crate::{quic_multiaddr, topology::configs::network::IpStack};
+
 #[derive(Clone)]
 pub struct GeneralBlendConfig {
     pub backend_core: Libp2pCoreBlendBackendSettings,
@@ -22,11 +24,14 @@ pub struct GeneralBlendConfig {
 ///
 /// # Panics
 ///
-/// Panics if the provided port strings cannot be parsed into valid `Multiaddr`s
-/// or if any of the numeric blend parameters are zero, which would make the
+/// Panics if any of the numeric blend parameters are zero, which would make the
 /// libp2p configuration invalid.
 #[must_use]
-pub fn create_blend_configs(ids: &[[u8; 32]], ports: &[u16]) -> Vec<GeneralBlendConfig> {
+pub fn create_blend_configs(
+    ids: &[[u8; 32]],
+    ports: &[u16],
+    ip_stack: IpStack,
+) -> Vec<GeneralBlendConfig> {
     ids.iter()
         .zip(ports)
         .map(|(id, port)| {
@@ -39,10 +44,7 @@ pub fn create_blend_configs(ids: &[[u8; 32]], ports: &[u16]) -> Vec<GeneralBlend
                 ZkKey::from(BigUint::from_bytes_le(private_key.public_key().as_bytes()));
             GeneralBlendConfig {
                 backend_core: Libp2pCoreBlendBackendSettings {
-                    listening_address: Multiaddr::from_str(&format!(
-                        "/ip4/127.0.0.1/udp/{port}/quic-v1",
-                    ))
-                    .unwrap(),
+                    listening_address: quic_multiaddr(ip_stack.loopback(), *port),
                     core_peering_degree: 1..=3,
                     minimum_messages_coefficient: NonZeroU64::try_from(1)
                         .expect("Minimum messages coefficient cannot be zero."),
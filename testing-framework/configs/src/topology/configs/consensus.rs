@@ -25,15 +25,25 @@ use nomos_node::{SignedMantleTx, Transaction as _};
 use nomos_utils::math::NonNegativeF64;
 use num_bigint::BigUint;
 
-use super::wallet::{WalletAccount, WalletConfig};
+use super::{
+    genesis::{GenesisCustomization, GenesisInscription},
+    wallet::{WalletAccount, WalletConfig},
+};
 
 #[derive(Clone)]
 pub struct ConsensusParams {
     pub n_participants: usize,
     pub security_param: NonZero<u32>,
     pub active_slot_coeff: f64,
+    /// Blocks per SDP session of the DA and blend services.
+    pub session_duration: u64,
+    /// Extra genesis notes, declarations and inscriptions.
+    pub genesis: GenesisCustomization,
 }
 
+/// Default SDP session length in blocks.
+pub const DEFAULT_SESSION_DURATION: u64 = 1000;
+
 impl ConsensusParams {
     #[must_use]
     pub const fn default_for_participants(n_participants: usize) -> Self {
@@ -46,6 +56,8 @@ impl ConsensusParams {
             security_param: NonZero::new(10).unwrap(),
             // a block should be produced (on average) every slot
             active_slot_coeff: 0.9,
+            session_duration: DEFAULT_SESSION_DURATION,
+            genesis: GenesisCustomization::new(),
         }
     }
 }
@@ -82,6 +94,9 @@ pub struct GeneralConsensusConfig {
     pub blend_notes: Vec<ServiceNote>,
     pub da_notes: Vec<ServiceNote>,
     pub wallet_accounts: Vec<WalletAccount>,
+    /// Genesis declarations of providers that run no node, to be signed
+    /// into the genesis transaction with the nodes' own.
+    pub extra_providers: Vec<ProviderInfo>,
 }
 
 #[derive(Clone)]
@@ -134,11 +149,13 @@ pub fn create_consensus_configs(
 
     let utxos = create_utxos_for_leader_and_services(
         ids,
+        &consensus_params.genesis,
         &mut leader_keys,
         &mut blend_notes,
         &mut da_notes,
     );
     let utxos = append_wallet_utxos(utxos, wallet);
+    let (utxos, extra_providers) = append_extra_declarations(utxos, &consensus_params.genesis);
     let genesis_tx = create_genesis_tx(&utxos);
     let ledger_config = nomos_ledger::Config {
         epoch_config: EpochConfig {
@@ -160,7 +177,7 @@ pub fn create_consensus_configs(
                             inactivity_period: 20,
                             retention_period: 100,
                             timestamp: 0,
-                            session_duration: 1000,
+                            session_duration: consensus_params.session_duration,
                         },
                     ),
                     (
@@ -170,7 +187,7 @@ pub fn create_consensus_configs(
                             inactivity_period: 20,
                             retention_period: 100,
                             timestamp: 0,
-                            session_duration: 1000,
+                            session_duration: consensus_params.session_duration,
                         },
                     ),
                 ]
@@ -201,12 +218,14 @@ pub fn create_consensus_configs(
             da_notes: da_notes.clone(),
             blend_notes: blend_notes.clone(),
             wallet_accounts: wallet.accounts.clone(),
+            extra_providers: extra_providers.clone(),
         })
         .collect()
 }
 
 fn create_utxos_for_leader_and_services(
     ids: &[[u8; 32]],
+    genesis: &GenesisCustomization,
     leader_keys: &mut Vec<(ZkPublicKey, UnsecuredZkKey)>,
     blend_notes: &mut Vec<ServiceNote>,
     da_notes: &mut Vec<ServiceNote>,
@@ -228,7 +247,9 @@ fn create_utxos_for_leader_and_services(
     let mut output_index = 0;
 
     // Create notes for leader, Blend and DA declarations.
-    for &id in ids {
+    for (index, &id) in ids.iter().enumerate() {
+        let service_note_value = genesis.provider_note_value(index);
+
         let sk_leader_data = derive_key_material(b"ld", &id);
         let sk_leader = UnsecuredZkKey::from(BigUint::from_bytes_le(&sk_leader_data));
         let pk_leader = sk_leader.to_public_key();
@@ -243,7 +264,7 @@ fn create_utxos_for_leader_and_services(
         let sk_da_data = derive_key_material(b"da", &id);
         let sk_da = ZkKey::from(BigUint::from_bytes_le(&sk_da_data));
         let pk_da = sk_da.to_public_key();
-        let note_da = Note::new(1, pk_da);
+        let note_da = Note::new(service_note_value, pk_da);
         da_notes.push(ServiceNote {
             pk: pk_da,
             sk: sk_da,
@@ -260,7 +281,7 @@ fn create_utxos_for_leader_and_services(
         let sk_blend_data = derive_key_material(b"bn", &id);
         let sk_blend = ZkKey::from(BigUint::from_bytes_le(&sk_blend_data));
         let pk_blend = sk_blend.to_public_key();
-        let note_blend = Note::new(1, pk_blend);
+        let note_blend = Note::new(service_note_value, pk_blend);
         blend_notes.push(ServiceNote {
             pk: pk_blend,
             sk: sk_blend,
@@ -290,10 +311,68 @@ fn append_wallet_utxos(mut utxos: Vec<Utxo>, wallet: &WalletConfig) -> Vec<Utxo>
     utxos
 }
 
+/// Append the notes locked by the extra genesis declarations and the keys of
+/// their providers. Keys are derived from the declaration's position.
+fn append_extra_declarations(
+    mut utxos: Vec<Utxo>,
+    genesis: &GenesisCustomization,
+) -> (Vec<Utxo>, Vec<ProviderInfo>) {
+    let mut providers = Vec::with_capacity(genesis.declarations.len());
+    for (index, declaration) in genesis.declarations.iter().enumerate() {
+        let index_bytes = (index as u64).to_le_bytes();
+
+        let mut provider_seed = [0; 32];
+        provider_seed[..2].copy_from_slice(b"xp");
+        provider_seed[2..10].copy_from_slice(&index_bytes);
+
+        let mut zk_seed = [0; 16];
+        zk_seed[..2].copy_from_slice(b"xz");
+        zk_seed[2..10].copy_from_slice(&index_bytes);
+
+        let mut note_seed = [0; 16];
+        note_seed[..2].copy_from_slice(b"xn");
+        note_seed[2..10].copy_from_slice(&index_bytes);
+
+        let sk = ZkKey::from(BigUint::from_bytes_le(&note_seed));
+        let pk = sk.to_public_key();
+        let note = Note::new(declaration.note_value, pk);
+        providers.push(ProviderInfo {
+            service_type: declaration.service_type,
+            provider_sk: Ed25519Key::from_bytes(&provider_seed),
+            zk_sk: ZkKey::from(BigUint::from_bytes_le(&zk_seed)),
+            locator: Locator(declaration.locator.clone()),
+            note: ServiceNote {
+                pk,
+                sk,
+                note,
+                output_index: utxos.len(),
+            },
+        });
+        utxos.push(Utxo {
+            note,
+            tx_hash: BigUint::from(0u8).into(),
+            output_index: 0,
+        });
+    }
+
+    (utxos, providers)
+}
+
 #[must_use]
 pub fn create_genesis_tx_with_declarations(
     ledger_tx: LedgerTx,
     providers: Vec<ProviderInfo>,
+) -> GenesisTx {
+    create_custom_genesis_tx(ledger_tx, providers, &[])
+}
+
+#[must_use]
+/// Genesis transaction declaring `providers` and writing `inscriptions`
+/// after the genesis inscription.
+pub fn create_custom_genesis_tx(
+    ledger_tx: LedgerTx,
+    providers: Vec<ProviderInfo>,
+    inscriptions: &[GenesisInscription],
 ) -> GenesisTx {
     let inscription = InscriptionOp {
         channel_id: ChannelId::from([0; 32]),
@@ -305,6 +384,14 @@ pub fn create_genesis_tx_with_declarations(
     let ledger_tx_hash = ledger_tx.hash();
 
     let mut ops = vec![Op::ChannelInscribe(inscription)];
+    ops.extend(inscriptions.iter().map(|inscription| {
+        Op::ChannelInscribe(InscriptionOp {
+            channel_id: ChannelId::from(inscription.channel_id),
+            inscription: inscription.data.clone(),
+            parent: MsgId::root(),
+            signer: Ed25519PublicKey::from_bytes(&[0; 32]).unwrap(),
+        })
+    }));
 
     for provider in &providers {
         let utxo = Utxo {
@@ -330,7 +417,9 @@ pub fn create_genesis_tx_with_declarations(
     };
 
     let mantle_tx_hash = mantle_tx.hash();
-    let mut ops_proofs = vec![OpProof::NoProof];
+    let mut ops_proofs: Vec<_> = std::iter::repeat_with(|| OpProof::NoProof)
+        .take(1 + inscriptions.len())
+        .collect();
 
     for provider in providers {
         let zk_sig =
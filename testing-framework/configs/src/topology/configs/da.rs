@@ -3,7 +3,6 @@ use std::{
     env,
     path::{Path, PathBuf},
     process,
-    str::FromStr as _,
     sync::LazyLock,
     time::Duration,
 };
@@ -20,7 +19,7 @@ use rand::random;
 use subnetworks_assignations::{MembershipCreator as _, MembershipHandler as _};
 use tracing::warn;
 
-use crate::secret_key_to_peer_id;
+use crate::{quic_multiaddr, secret_key_to_peer_id, topology::configs::network::IpStack};
 
 pub static GLOBAL_PARAMS_PATH: LazyLock<String> = LazyLock::new(resolve_global_params_path);
 
@@ -160,6 +159,7 @@ pub fn create_da_configs(
     ids: &[[u8; 32]],
     da_params: &DaParams,
     ports: &[u16],
+    ip_stack: IpStack,
 ) -> Vec<GeneralDaConfig> {
     // Let the subnetwork size track the participant count so tiny local topologies
     // can form a membership.
@@ -177,10 +177,7 @@ pub fn create_da_configs(
         let peer_id = secret_key_to_peer_id(node_key);
         peer_ids.push(peer_id);
 
-        let listening_address =
-            Multiaddr::from_str(&format!("/ip4/127.0.0.1/udp/{}/quic-v1", ports[i],))
-                .expect("Failed to create multiaddr");
-        listening_addresses.push(listening_address);
+        listening_addresses.push(quic_multiaddr(ip_stack.loopback(), ports[i]));
     }
 
     let membership = {
@@ -0,0 +1,73 @@
+use std::collections::BTreeMap;
+
+use nomos_core::sdp::ServiceType;
+use nomos_libp2p::Multiaddr;
+use serde::{Deserialize, Serialize};
+
+/// Genesis content added on top of the default provider notes and wallet
+/// accounts, e.g. to start with under-staked providers or pre-seeded
+/// channels.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisCustomization {
+    /// Inscriptions written after the genesis inscription.
+    #[serde(default)]
+    pub inscriptions: Vec<GenesisInscription>,
+    /// Value of the notes locked by a node's DA and blend declarations,
+    /// keyed by node index in role order (validators, then executors).
+    /// Nodes not listed lock a note of 1.
+    #[serde(default)]
+    pub provider_note_values: BTreeMap<usize, u64>,
+    /// Declarations of providers that run no node. Their keys are derived
+    /// from their position in this list.
+    #[serde(default)]
+    pub declarations: Vec<GenesisDeclaration>,
+}
+
+impl GenesisCustomization {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            inscriptions: Vec::new(),
+            provider_note_values: BTreeMap::new(),
+            declarations: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Value of the notes backing node `index`'s declarations.
+    pub fn provider_note_value(&self, index: usize) -> u64 {
+        self.provider_note_values
+            .get(&index)
+            .copied()
+            .unwrap_or(DEFAULT_PROVIDER_NOTE_VALUE)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inscriptions.is_empty()
+            && self.provider_note_values.is_empty()
+            && self.declarations.is_empty()
+    }
+}
+
+/// Value of the note each node locks per declaration unless overridden.
+pub const DEFAULT_PROVIDER_NOTE_VALUE: u64 = 1;
+
+/// Inscription included in genesis.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisInscription {
+    pub channel_id: [u8; 32],
+    pub data: Vec<u8>,
+}
+
+/// SDP declaration of a provider outside the cluster, backed by a note of
+/// `note_value`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GenesisDeclaration {
+    pub service_type: ServiceType,
+    pub locator: Multiaddr,
+    pub note_value: u64,
+}
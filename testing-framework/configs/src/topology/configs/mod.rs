@@ -3,6 +3,7 @@ pub mod blend;
 pub mod bootstrap;
 pub mod consensus;
 pub mod da;
+pub mod genesis;
 pub mod network;
 pub mod time;
 pub mod tracing;
@@ -91,9 +92,14 @@ pub fn create_general_configs_with_blend_core_subset(
     let bootstrap_config =
         bootstrap::create_bootstrap_configs(&ids, SHORT_PROLONGED_BOOTSTRAP_PERIOD);
     let network_configs = network::create_network_configs(&ids, network_params);
-    let da_configs = da::create_da_configs(&ids, &DaParams::default(), &da_ports);
+    let da_configs = da::create_da_configs(
+        &ids,
+        &DaParams::default(),
+        &da_ports,
+        network_params.ip_stack,
+    );
     let api_configs = api::create_api_configs(&ids);
-    let blend_configs = blend::create_blend_configs(&ids, &blend_ports);
+    let blend_configs = blend::create_blend_configs(&ids, &blend_ports, network_params.ip_stack);
     let tracing_configs = tracing::create_tracing_configs(&ids);
     let time_config = time::default_time_config();
 
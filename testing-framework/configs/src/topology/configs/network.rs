@@ -1,14 +1,20 @@
-use std::{env, time::Duration};
+use std::{
+    env,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    time::Duration,
+};
 
 use nomos_libp2p::{
     IdentifySettings, KademliaSettings, Multiaddr, NatSettings, ed25519, gossipsub,
 };
 use nomos_node::config::network::serde::{BackendSettings, Config, SwarmConfig};
 use nomos_utils::net::get_available_udp_port;
+use serde::{Deserialize, Serialize};
 
-use crate::node_address_from_port;
+use crate::quic_multiaddr;
 
-#[derive(Default, Clone)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Libp2pNetworkLayout {
     #[default]
     Star,
@@ -16,9 +22,57 @@ pub enum Libp2pNetworkLayout {
     Full,
 }
 
+/// IP families nodes listen on and advertise.
+///
+/// The libp2p swarm host in `nomos-node` is IPv4-typed, so the swarm keeps
+/// listening on, and being dialed at, IPv4. DA and blend listeners and their
+/// advertised addresses (SDP locators) follow this setting.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IpStack {
+    #[default]
+    V4,
+    V6,
+    /// Local runs dial peers over both families; cfgsync-driven runs listen
+    /// on the family of the address each host announces.
+    DualStack,
+}
+
+impl IpStack {
+    /// Value passed to nodes as `CFG_IP_STACK`.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::V4 => "v4",
+            Self::V6 => "v6",
+            Self::DualStack => "dual_stack",
+        }
+    }
+
+    /// Loopback address used for locally-run nodes.
+    #[must_use]
+    pub const fn loopback(self) -> IpAddr {
+        match self {
+            Self::V4 | Self::DualStack => IpAddr::V4(Ipv4Addr::LOCALHOST),
+            Self::V6 => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        }
+    }
+
+    /// Wildcard address listeners bind to when the node IP is not known in
+    /// advance. Under `DualStack` the wildcard follows `advertised`'s family.
+    #[must_use]
+    pub const fn unspecified_for(self, advertised: IpAddr) -> IpAddr {
+        match (self, advertised) {
+            (Self::V4, _) | (Self::DualStack, IpAddr::V4(_)) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            (Self::V6, _) | (Self::DualStack, IpAddr::V6(_)) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct NetworkParams {
     pub libp2p_network_layout: Libp2pNetworkLayout,
+    pub ip_stack: IpStack,
 }
 
 pub type GeneralNetworkConfig = Config;
@@ -36,16 +90,18 @@ fn default_swarm_config() -> SwarmConfig {
     }
 }
 
+/// Address a local swarm listening on `port` is dialed at.
+fn local_swarm_address(port: u16) -> Multiaddr {
+    quic_multiaddr(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+}
+
 fn nat_settings(port: u16) -> NatSettings {
     if env::var("NOMOS_USE_AUTONAT").is_ok() {
         return NatSettings::default();
     }
 
-    let addr: Multiaddr = format!("/ip4/127.0.0.1/udp/{port}/quic-v1")
-        .parse()
-        .expect("failed to build loopback multiaddr");
     NatSettings::Static {
-        external_address: addr,
+        external_address: local_swarm_address(port),
     }
 }
 
@@ -53,15 +109,30 @@ fn nat_settings(port: u16) -> NatSettings {
 pub fn create_network_configs(
     ids: &[[u8; 32]],
     network_params: &NetworkParams,
+) -> Vec<GeneralNetworkConfig> {
+    let ports = ids
+        .iter()
+        .map(|_| get_available_udp_port().unwrap())
+        .collect::<Vec<_>>();
+    create_network_configs_with_ports(ids, network_params, &ports)
+}
+
+/// Like [`create_network_configs`], with the swarms listening on `ports` in
+/// node order.
+#[must_use]
+pub fn create_network_configs_with_ports(
+    ids: &[[u8; 32]],
+    network_params: &NetworkParams,
+    ports: &[u16],
 ) -> Vec<GeneralNetworkConfig> {
     let swarm_configs: Vec<SwarmConfig> = ids
         .iter()
-        .map(|id| {
+        .zip(ports)
+        .map(|(id, &port)| {
             let mut node_key_bytes = *id;
             let node_key = ed25519::SecretKey::try_from_bytes(&mut node_key_bytes)
                 .expect("Failed to generate secret key from bytes");
 
-            let port = get_available_udp_port().unwrap();
             SwarmConfig {
                 node_key,
                 port,
@@ -98,7 +169,7 @@ fn initial_peers_by_network_layout(
         Libp2pNetworkLayout::Star => {
             // First node is the hub - has no initial peers
             all_initial_peers.push(vec![]);
-            let first_addr = node_address_from_port(swarm_configs[0].port);
+            let first_addr = local_swarm_address(swarm_configs[0].port);
 
             // All other nodes connect to the first node
             for _ in 1..swarm_configs.len() {
@@ -111,7 +182,7 @@ fn initial_peers_by_network_layout(
 
             // Each subsequent node connects to the previous one
             for i in 1..swarm_configs.len() {
-                let prev_addr = node_address_from_port(swarm_configs[i - 1].port);
+                let prev_addr = local_swarm_address(swarm_configs[i - 1].port);
                 all_initial_peers.push(vec![prev_addr]);
             }
         }
@@ -120,7 +191,7 @@ fn initial_peers_by_network_layout(
             for i in 0..swarm_configs.len() {
                 let mut peers = vec![];
                 for swarm_config in swarm_configs.iter().take(i) {
-                    peers.push(node_address_from_port(swarm_config.port));
+                    peers.push(local_swarm_address(swarm_config.port));
                 }
                 all_initial_peers.push(peers);
             }
@@ -1,10 +1,165 @@
-use std::num::NonZeroUsize;
+use std::{fmt, num::NonZeroUsize};
 
 use key_management_system_service::keys::{ZkKey, ZkPublicKey};
 use num_bigint::BigUint;
+use thiserror::Error;
+
+/// How genesis funds are split across wallet accounts.
+///
+/// Skewed distributions are deterministic: account `0` always receives the
+/// largest share, and every account holds at least one token.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TokenDistribution {
+    /// Every account holds the same amount (up to rounding).
+    Uniform { users: NonZeroUsize },
+    /// Amounts follow a Pareto distribution with shape `alpha`; smaller
+    /// values concentrate more funds in the first accounts.
+    Pareto { users: NonZeroUsize, alpha: f64 },
+    /// The account ranked `i` (from 1) receives funds proportional to
+    /// `1 / i^exponent`.
+    Zipf { users: NonZeroUsize, exponent: f64 },
+    /// Explicit per-account amounts, which must add up to the total funds.
+    Explicit(Vec<u64>),
+}
+
+impl TokenDistribution {
+    #[must_use]
+    pub const fn uniform(users: NonZeroUsize) -> Self {
+        Self::Uniform { users }
+    }
+
+    #[must_use]
+    pub const fn pareto(users: NonZeroUsize, alpha: f64) -> Self {
+        Self::Pareto { users, alpha }
+    }
+
+    #[must_use]
+    pub const fn zipf(users: NonZeroUsize, exponent: f64) -> Self {
+        Self::Zipf { users, exponent }
+    }
+
+    #[must_use]
+    pub const fn explicit(amounts: Vec<u64>) -> Self {
+        Self::Explicit(amounts)
+    }
+
+    /// Number of accounts the distribution funds.
+    #[must_use]
+    pub fn users(&self) -> usize {
+        match self {
+            Self::Uniform { users } | Self::Pareto { users, .. } | Self::Zipf { users, .. } => {
+                users.get()
+            }
+            Self::Explicit(amounts) => amounts.len(),
+        }
+    }
+
+    /// Split `total_funds` into per-account amounts.
+    pub fn amounts(&self, total_funds: u64) -> Result<Vec<u64>, WalletConfigError> {
+        let users = self.users();
+        if users == 0 {
+            return Err(WalletConfigError::NoAccounts);
+        }
+        if let Self::Explicit(amounts) = self {
+            if amounts.contains(&0) {
+                return Err(WalletConfigError::EmptyAccount);
+            }
+            let sum = amounts
+                .iter()
+                .try_fold(0u64, |sum, amount| sum.checked_add(*amount));
+            return match sum {
+                Some(sum) if sum == total_funds => Ok(amounts.clone()),
+                _ => Err(WalletConfigError::ExplicitTotalMismatch {
+                    expected: total_funds,
+                    actual: sum,
+                }),
+            };
+        }
+        if total_funds < users as u64 {
+            return Err(WalletConfigError::InsufficientFunds { total_funds, users });
+        }
+
+        let weights = match *self {
+            Self::Uniform { .. } => vec![1.0; users],
+            Self::Pareto { alpha, .. } => {
+                ensure_positive("pareto alpha", alpha)?;
+                // Quantiles of the Pareto distribution at evenly spaced
+                // points, largest first.
+                (0..users)
+                    .map(|rank| {
+                        let tail = (rank as f64 + 0.5) / users as f64;
+                        tail.powf(-1.0 / alpha)
+                    })
+                    .collect()
+            }
+            Self::Zipf { exponent, .. } => {
+                ensure_positive("zipf exponent", exponent)?;
+                (1..=users)
+                    .map(|rank| (rank as f64).powf(-exponent))
+                    .collect()
+            }
+            Self::Explicit(_) => unreachable!("explicit amounts handled above"),
+        };
+
+        Ok(split_by_weights(total_funds, &weights))
+    }
+}
+
+/// Invalid wallet funding requests.
+#[derive(Debug, Error)]
+pub enum WalletConfigError {
+    #[error("wallet distribution must fund at least one account")]
+    NoAccounts,
+    #[error("wallet funds must allocate at least 1 token per user ({total_funds} for {users})")]
+    InsufficientFunds { total_funds: u64, users: usize },
+    #[error("explicit wallet amounts must all be positive")]
+    EmptyAccount,
+    #[error("explicit wallet amounts add up to {actual:?}, expected {expected}")]
+    ExplicitTotalMismatch { expected: u64, actual: Option<u64> },
+    #[error("{name} must be a positive finite number, got {value}")]
+    InvalidParameter { name: &'static str, value: f64 },
+    #[error("user {user} holds {amount} tokens, too few for {children} derived accounts")]
+    InsufficientChildFunds {
+        user: usize,
+        amount: u64,
+        children: usize,
+    },
+}
+
+fn ensure_positive(name: &'static str, value: f64) -> Result<(), WalletConfigError> {
+    if value.is_finite() && value > 0.0 {
+        Ok(())
+    } else {
+        Err(WalletConfigError::InvalidParameter { name, value })
+    }
+}
+
+/// Gives every account one token and splits the rest proportionally to
+/// `weights`. Cumulative rounding keeps the sum exactly `total_funds`.
+fn split_by_weights(total_funds: u64, weights: &[f64]) -> Vec<u64> {
+    let users = weights.len() as u64;
+    let rest = total_funds - users;
+    let weight_sum: f64 = weights.iter().sum();
+
+    let mut amounts = Vec::with_capacity(weights.len());
+    let mut cumulative_weight = 0.0;
+    let mut allocated = 0u64;
+    for (index, weight) in weights.iter().enumerate() {
+        cumulative_weight += weight;
+        let cumulative = if index + 1 == weights.len() {
+            rest
+        } else {
+            ((rest as f64 * cumulative_weight / weight_sum) as u64).clamp(allocated, rest)
+        };
+        amounts.push(1 + cumulative - allocated);
+        allocated = cumulative;
+    }
+    amounts
+}
 
 /// Collection of wallet accounts that should be funded at genesis.
 #[derive(Clone, Default, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct WalletConfig {
     pub accounts: Vec<WalletAccount>,
 }
@@ -18,7 +173,6 @@ impl WalletConfig {
     #[must_use]
     pub fn uniform(total_funds: u64, users: NonZeroUsize) -> Self {
         let user_count = users.get() as u64;
-        assert!(user_count > 0, "wallet user count must be non-zero");
         assert!(
             total_funds >= user_count,
             "wallet funds must allocate at least 1 token per user"
@@ -41,14 +195,103 @@ impl WalletConfig {
 
         Self { accounts }
     }
+
+    /// Fund deterministic accounts with `total_funds` split according to
+    /// `distribution`.
+    pub fn distributed(
+        total_funds: u64,
+        distribution: &TokenDistribution,
+    ) -> Result<Self, WalletConfigError> {
+        if let TokenDistribution::Uniform { users } = distribution {
+            if total_funds < users.get() as u64 {
+                return Err(WalletConfigError::InsufficientFunds {
+                    total_funds,
+                    users: users.get(),
+                });
+            }
+            return Ok(Self::uniform(total_funds, *users));
+        }
+
+        let accounts = distribution
+            .amounts(total_funds)?
+            .into_iter()
+            .enumerate()
+            .map(|(idx, amount)| WalletAccount::deterministic(idx as u64, amount))
+            .collect();
+        Ok(Self { accounts })
+    }
+
+    /// Fund `children` accounts derived from `seed` for every user. Users are
+    /// funded according to `distribution` and split their share evenly
+    /// across their children, so workloads can take fresh accounts of one
+    /// user instead of competing for a single note.
+    pub fn derived(
+        total_funds: u64,
+        distribution: &TokenDistribution,
+        children: NonZeroUsize,
+        seed: u64,
+    ) -> Result<Self, WalletConfigError> {
+        let mut accounts = Vec::new();
+        for (user, amount) in distribution.amounts(total_funds)?.into_iter().enumerate() {
+            if amount < children.get() as u64 {
+                return Err(WalletConfigError::InsufficientChildFunds {
+                    user,
+                    amount,
+                    children: children.get(),
+                });
+            }
+            let base = amount / children.get() as u64;
+            let remainder = amount % children.get() as u64;
+            accounts.extend((0..children.get()).map(|child| {
+                let value = base + u64::from((child as u64) < remainder);
+                let path = AccountPath::new(user as u32, child as u32);
+                WalletAccount::derived(seed, path, value)
+            }));
+        }
+        Ok(Self { accounts })
+    }
+
+    /// Sum of the funds held by every account.
+    #[must_use]
+    pub fn total_funds(&self) -> u64 {
+        self.accounts
+            .iter()
+            .fold(0u64, |sum, account| sum.saturating_add(account.value))
+    }
+}
+
+/// Position of a derived account: the user it belongs to and its index
+/// among that user's children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountPath {
+    pub user: u32,
+    pub child: u32,
+}
+
+impl AccountPath {
+    #[must_use]
+    pub const fn new(user: u32, child: u32) -> Self {
+        Self { user, child }
+    }
+}
+
+impl fmt::Display for AccountPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.user, self.child)
+    }
 }
 
 /// Wallet account that holds funds in the genesis state.
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct WalletAccount {
     pub label: String,
     pub secret_key: ZkKey,
     pub value: u64,
+    /// Set for accounts derived with [`WalletAccount::derived`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<AccountPath>,
 }
 
 impl WalletAccount {
@@ -59,6 +302,7 @@ impl WalletAccount {
             label: label.into(),
             secret_key,
             value,
+            path: None,
         }
     }
 
@@ -72,8 +316,167 @@ impl WalletAccount {
         Self::new(format!("wallet-user-{index}"), secret_key, value)
     }
 
+    /// The account at `path` under `seed`. Keys are a deterministic function
+    /// of both, so every node and workload derives the same accounts, and
+    /// they never collide with [`Self::deterministic`] ones. Test keys only.
+    #[must_use]
+    pub fn derived(seed: u64, path: AccountPath, value: u64) -> Self {
+        let mut key = [0u8; 32];
+        key[..2].copy_from_slice(b"wd");
+        key[2..10].copy_from_slice(&seed.to_le_bytes());
+        key[10..14].copy_from_slice(&path.user.to_le_bytes());
+        key[14..18].copy_from_slice(&path.child.to_le_bytes());
+
+        let secret_key = ZkKey::from(BigUint::from_bytes_le(&key));
+        let mut account = Self::new(format!("wallet-user-{path}"), secret_key, value);
+        account.path = Some(path);
+        account
+    }
+
     #[must_use]
     pub fn public_key(&self) -> ZkPublicKey {
         self.secret_key.to_public_key()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn non_zero(value: usize) -> NonZeroUsize {
+        NonZeroUsize::new(value).expect("non-zero")
+    }
+
+    #[test]
+    fn split_keeps_one_token_per_account_and_the_exact_total() {
+        assert_eq!(split_by_weights(10, &[1.0, 1.0, 1.0]), vec![3, 3, 4]);
+        assert_eq!(split_by_weights(5, &[1.0; 5]), vec![1; 5]);
+        assert_eq!(
+            split_by_weights(100, &[1.0, 0.5, 1.0 / 3.0]),
+            vec![53, 28, 19]
+        );
+    }
+
+    #[test]
+    fn skewed_distributions_favour_the_first_accounts() {
+        let zipf = TokenDistribution::zipf(non_zero(3), 1.0)
+            .amounts(100)
+            .expect("valid zipf");
+        assert_eq!(zipf, vec![53, 28, 19]);
+
+        let pareto = TokenDistribution::pareto(non_zero(4), 1.5)
+            .amounts(1000)
+            .expect("valid pareto");
+        assert_eq!(pareto.iter().sum::<u64>(), 1000);
+        assert!(
+            pareto.windows(2).all(|pair| pair[0] >= pair[1]),
+            "{pareto:?}"
+        );
+        assert!(pareto.iter().all(|amount| *amount >= 1), "{pareto:?}");
+    }
+
+    #[test]
+    fn invalid_distributions_are_rejected() {
+        assert!(matches!(
+            TokenDistribution::uniform(non_zero(4)).amounts(3),
+            Err(WalletConfigError::InsufficientFunds {
+                total_funds: 3,
+                users: 4
+            })
+        ));
+        assert!(matches!(
+            TokenDistribution::pareto(non_zero(2), 0.0).amounts(10),
+            Err(WalletConfigError::InvalidParameter {
+                name: "pareto alpha",
+                ..
+            })
+        ));
+        assert!(matches!(
+            TokenDistribution::zipf(non_zero(2), f64::NAN).amounts(10),
+            Err(WalletConfigError::InvalidParameter {
+                name: "zipf exponent",
+                ..
+            })
+        ));
+        assert!(matches!(
+            TokenDistribution::explicit(Vec::new()).amounts(10),
+            Err(WalletConfigError::NoAccounts)
+        ));
+        assert!(matches!(
+            TokenDistribution::explicit(vec![10, 0]).amounts(10),
+            Err(WalletConfigError::EmptyAccount)
+        ));
+        assert!(matches!(
+            TokenDistribution::explicit(vec![6, 3]).amounts(10),
+            Err(WalletConfigError::ExplicitTotalMismatch {
+                expected: 10,
+                actual: Some(9)
+            })
+        ));
+        assert!(matches!(
+            TokenDistribution::explicit(vec![u64::MAX, 1]).amounts(10),
+            Err(WalletConfigError::ExplicitTotalMismatch { actual: None, .. })
+        ));
+        assert_eq!(
+            TokenDistribution::explicit(vec![7, 3]).amounts(10).unwrap(),
+            vec![7, 3]
+        );
+    }
+
+    #[test]
+    fn derived_accounts_split_each_share_with_remainder_first() {
+        let distribution = TokenDistribution::explicit(vec![7, 3]);
+
+        let config =
+            WalletConfig::derived(10, &distribution, non_zero(3), 1).expect("enough funds");
+
+        let split = config
+            .accounts
+            .iter()
+            .map(|account| (account.path.expect("derived"), account.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            split,
+            [
+                (AccountPath::new(0, 0), 3),
+                (AccountPath::new(0, 1), 2),
+                (AccountPath::new(0, 2), 2),
+                (AccountPath::new(1, 0), 1),
+                (AccountPath::new(1, 1), 1),
+                (AccountPath::new(1, 2), 1),
+            ]
+        );
+        assert_eq!(config.total_funds(), 10);
+        assert_eq!(config.accounts[0].label, "wallet-user-0/0");
+    }
+
+    #[test]
+    fn derived_accounts_need_a_token_per_child() {
+        let distribution = TokenDistribution::explicit(vec![8, 2]);
+
+        let err = WalletConfig::derived(10, &distribution, non_zero(3), 1).unwrap_err();
+
+        assert!(matches!(
+            err,
+            WalletConfigError::InsufficientChildFunds {
+                user: 1,
+                amount: 2,
+                children: 3,
+            }
+        ));
+    }
+
+    #[test]
+    fn derived_keys_depend_on_seed_and_path() {
+        let path = AccountPath::new(0, 1);
+        let key = |seed, path| WalletAccount::derived(seed, path, 1).public_key();
+
+        assert_eq!(key(1, path), key(1, path));
+        assert_ne!(key(1, path), key(2, path));
+        assert_ne!(key(1, path), key(1, AccountPath::new(1, 0)));
+        assert_ne!(
+            key(1, path),
+            WalletAccount::deterministic(0, 1).public_key()
+        );
+    }
+}
@@ -0,0 +1,483 @@
+//! Per-run output directories.
+//!
+//! Every deployment writes its files (node data dirs, rendered compose or
+//! Helm assets, cfgsync configs, collected logs) below one run directory
+//! owned by [`RunArtifacts`]. Run directories live side by side under a
+//! base directory and are pruned according to a [`RetentionPolicy`] when a
+//! later run starts.
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        LazyLock,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use thiserror::Error;
+use tracing::{debug, info, warn};
+
+use crate::scenario::Profile;
+
+/// Base directory for run directories. Defaults to `nomos-artifacts` below
+/// the current directory, so CI can upload it with a single path.
+pub const ARTIFACTS_DIR_ENV: &str = "NOMOS_ARTIFACTS_DIR";
+/// Remove run directories older than this many seconds when a run starts.
+pub const ARTIFACTS_MAX_AGE_ENV: &str = "NOMOS_ARTIFACTS_MAX_AGE_SECS";
+/// Remove the oldest run directories until the base directory holds at most
+/// this many bytes.
+pub const ARTIFACTS_MAX_BYTES_ENV: &str = "NOMOS_ARTIFACTS_MAX_BYTES";
+/// When set, run directories are kept after the run instead of removed.
+pub const KEEP_LOGS_ENV: &str = "NOMOS_TESTS_KEEP_LOGS";
+
+const DEFAULT_ARTIFACTS_DIR: &str = "nomos-artifacts";
+const RUN_DIR_PREFIX: &str = "run-";
+/// File in a run directory naming the process that owns it while it runs.
+const LOCK_FILE: &str = "run.pid";
+
+static KEEP_RUN_ARTIFACTS: LazyLock<bool> = LazyLock::new(|| env::var(KEEP_LOGS_ENV).is_ok());
+static RUN_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+tokio::task_local! {
+    static NODE_DIR_ROOT: PathBuf;
+}
+
+/// Failures preparing run directories.
+#[derive(Debug, Error)]
+pub enum ArtifactsError {
+    #[error("failed to create artifacts directory {path}: {source}")]
+    Create {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("no artifacts directory for run {run_id} at {path}")]
+    MissingRun { run_id: String, path: PathBuf },
+    #[error("failed to resolve the current directory: {source}")]
+    CurrentDir {
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// How many finished run directories to keep around.
+///
+/// Only directories created by [`RunArtifacts`] are considered; anything
+/// else under the base directory is left alone, and so are the directories
+/// of runs whose process is still alive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    max_age: Option<Duration>,
+    max_total_bytes: Option<u64>,
+}
+
+impl RetentionPolicy {
+    /// Keep every run directory.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            max_age: None,
+            max_total_bytes: None,
+        }
+    }
+
+    /// Read limits from [`ARTIFACTS_MAX_AGE_ENV`] and
+    /// [`ARTIFACTS_MAX_BYTES_ENV`]; unset or invalid values mean no limit.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let read = |key| env::var(key).ok().and_then(|raw| raw.parse::<u64>().ok());
+        Self {
+            max_age: read(ARTIFACTS_MAX_AGE_ENV).map(Duration::from_secs),
+            max_total_bytes: read(ARTIFACTS_MAX_BYTES_ENV),
+        }
+    }
+
+    #[must_use]
+    /// Whether the policy never removes anything.
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_age.is_none() && self.max_total_bytes.is_none()
+    }
+
+    #[must_use]
+    pub const fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    #[must_use]
+    pub const fn with_max_total_bytes(mut self, bytes: u64) -> Self {
+        self.max_total_bytes = Some(bytes);
+        self
+    }
+
+    /// Remove run directories under `base` that exceed the limits, oldest
+    /// first. `current` and directories of live runs are never removed.
+    /// Returns the removed directories.
+    pub fn prune(&self, base: &Path, current: Option<&Path>) -> Vec<PathBuf> {
+        if self.is_unlimited() {
+            return Vec::new();
+        }
+
+        let mut runs = list_run_dirs(base);
+        runs.retain(|run| {
+            if current == Some(run.path.as_path()) {
+                return false;
+            }
+            let live = owned_by_live_process(&run.path);
+            if live {
+                debug!(path = %run.path.display(), "skipping run artifacts of a live run");
+            }
+            !live
+        });
+        runs.sort_by_key(|run| run.modified);
+
+        let now = SystemTime::now();
+        let mut removed = Vec::new();
+        let mut kept = Vec::new();
+        for run in runs {
+            let age = now.duration_since(run.modified).unwrap_or_default();
+            if self.max_age.is_some_and(|max_age| age > max_age) {
+                remove_run_dir(&run.path, &mut removed);
+            } else {
+                kept.push(run);
+            }
+        }
+
+        if let Some(max_bytes) = self.max_total_bytes {
+            let mut total: u64 = kept.iter().map(|run| run.bytes).sum();
+            for run in kept {
+                if total <= max_bytes {
+                    break;
+                }
+                total = total.saturating_sub(run.bytes);
+                remove_run_dir(&run.path, &mut removed);
+            }
+        }
+
+        removed
+    }
+}
+
+struct RunDir {
+    path: PathBuf,
+    modified: SystemTime,
+    bytes: u64,
+}
+
+fn list_run_dirs(base: &Path) -> Vec<RunDir> {
+    let Ok(entries) = fs::read_dir(base) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with(RUN_DIR_PREFIX))
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok().filter(fs::Metadata::is_dir)?;
+            let path = entry.path();
+            Some(RunDir {
+                bytes: dir_size(&path),
+                modified: metadata.modified().unwrap_or(UNIX_EPOCH),
+                path,
+            })
+        })
+        .collect()
+}
+
+fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(Result::ok)
+        .map(|entry| match entry.metadata() {
+            Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+/// Whether the lock file of the run directory at `path` names a process
+/// that is still running.
+fn owned_by_live_process(path: &Path) -> bool {
+    fs::read_to_string(path.join(LOCK_FILE))
+        .ok()
+        .and_then(|raw| raw.trim().parse::<u32>().ok())
+        .is_some_and(|pid| pid == process::id() || process_alive(pid))
+}
+
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> bool {
+    Path::new("/proc").join(pid.to_string()).exists()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_alive(pid: u32) -> bool {
+    process::Command::new("kill")
+        .arg("-0")
+        .arg(pid.to_string())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Mark the run directory at `root` as owned by this process.
+fn write_lock(root: &Path) {
+    if let Err(err) = fs::write(root.join(LOCK_FILE), process::id().to_string()) {
+        warn!(root = %root.display(), error = %err, "failed to write run artifacts lock");
+    }
+}
+
+fn remove_run_dir(path: &Path, removed: &mut Vec<PathBuf>) {
+    match fs::remove_dir_all(path) {
+        Ok(()) => {
+            debug!(path = %path.display(), "pruned run artifacts");
+            removed.push(path.to_path_buf());
+        }
+        Err(err) => warn!(path = %path.display(), error = %err, "failed to prune run artifacts"),
+    }
+}
+
+/// Owns the output directory of one run.
+///
+/// Runners create one per deployment and attach it to the
+/// [`RunContext`](crate::scenario::RunContext), so workloads and
+/// expectations can place their own files next to the node data and logs.
+/// The directory is removed when the last handle is dropped, unless
+/// [`Self::keep`] was called, [`KEEP_LOGS_ENV`] is set, or the thread is
+/// panicking. While a handle exists the directory holds a lock file naming
+/// this process, so concurrent runs never prune it.
+#[derive(Debug)]
+pub struct RunArtifacts {
+    run_id: String,
+    root: PathBuf,
+    keep: AtomicBool,
+}
+
+impl RunArtifacts {
+    /// Create a run directory under [`Self::base_dir`], pruning older runs
+    /// with [`RetentionPolicy::from_env`].
+    pub fn create() -> Result<Self, ArtifactsError> {
+        Self::create_in(Self::base_dir()?, RetentionPolicy::from_env())
+    }
+
+    /// Create a run directory following `profile`'s retention and keep
+    /// settings, or [`Self::create`] without a profile.
+    pub fn create_for(profile: Option<Profile>) -> Result<Self, ArtifactsError> {
+        let Some(profile) = profile else {
+            return Self::create();
+        };
+        let artifacts = Self::create_in(Self::base_dir()?, profile.retention())?;
+        if profile.keep_artifacts() {
+            artifacts.keep();
+        }
+        Ok(artifacts)
+    }
+
+    /// Create a run directory under `base`, pruning older runs with
+    /// `policy`.
+    pub fn create_in(
+        base: impl Into<PathBuf>,
+        policy: RetentionPolicy,
+    ) -> Result<Self, ArtifactsError> {
+        let base = base.into();
+        let run_id = next_run_id();
+        let root = base.join(format!("{RUN_DIR_PREFIX}{run_id}"));
+        fs::create_dir_all(&root).map_err(|source| ArtifactsError::Create {
+            path: root.clone(),
+            source,
+        })?;
+        write_lock(&root);
+
+        let pruned = policy.prune(&base, Some(&root));
+        if !pruned.is_empty() {
+            info!(base = %base.display(), pruned = pruned.len(), "pruned old run artifacts");
+        }
+        info!(run_id, root = %root.display(), "run artifacts directory created");
+
+        Ok(Self {
+            run_id,
+            root,
+            keep: AtomicBool::new(false),
+        })
+    }
+
+    /// Re-open the directory of an earlier run, e.g. one whose process died
+    /// before cleaning up. Like a fresh directory, it is removed when the
+    /// last handle is dropped unless kept.
+    pub fn open(run_id: &str) -> Result<Self, ArtifactsError> {
+        let root = Self::base_dir()?.join(format!("{RUN_DIR_PREFIX}{run_id}"));
+        if !root.is_dir() {
+            return Err(ArtifactsError::MissingRun {
+                run_id: run_id.to_owned(),
+                path: root,
+            });
+        }
+        write_lock(&root);
+        info!(run_id, root = %root.display(), "run artifacts directory reopened");
+
+        Ok(Self {
+            run_id: run_id.to_owned(),
+            root,
+            keep: AtomicBool::new(false),
+        })
+    }
+
+    /// Directory holding every run directory: [`ARTIFACTS_DIR_ENV`] if set,
+    /// otherwise `nomos-artifacts` below the current directory.
+    pub fn base_dir() -> Result<PathBuf, ArtifactsError> {
+        match env::var_os(ARTIFACTS_DIR_ENV) {
+            Some(dir) => Ok(PathBuf::from(dir)),
+            None => env::current_dir()
+                .map(|cwd| cwd.join(DEFAULT_ARTIFACTS_DIR))
+                .map_err(|source| ArtifactsError::CurrentDir { source }),
+        }
+    }
+
+    #[must_use]
+    /// Identifier unique to this run, also used in the directory name.
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Subdirectory `name` of the run directory, created if missing.
+    pub fn dir(&self, name: &str) -> Result<PathBuf, ArtifactsError> {
+        let path = self.root.join(name);
+        fs::create_dir_all(&path).map_err(|source| ArtifactsError::Create {
+            path: path.clone(),
+            source,
+        })?;
+        Ok(path)
+    }
+
+    #[must_use]
+    /// Path of a file `name` in the run directory; the file is not created.
+    pub fn file(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+
+    /// Keep the run directory after the run, e.g. because a runner was asked
+    /// to preserve its state.
+    pub fn keep(&self) {
+        self.keep.store(true, Ordering::Relaxed);
+    }
+
+    /// Run `future` with locally spawned nodes placing their data
+    /// directories under this run's `nodes` directory.
+    pub async fn scope_nodes<F: Future>(&self, future: F) -> Result<F::Output, ArtifactsError> {
+        let nodes = self.dir("nodes")?;
+        Ok(NODE_DIR_ROOT.scope(nodes, future).await)
+    }
+
+    fn should_keep(&self) -> bool {
+        self.keep.load(Ordering::Relaxed) || *KEEP_RUN_ARTIFACTS || std::thread::panicking()
+    }
+}
+
+impl Drop for RunArtifacts {
+    fn drop(&mut self) {
+        if self.should_keep() {
+            // Kept directories become prunable once the run is over.
+            let _ = fs::remove_file(self.root.join(LOCK_FILE));
+            info!(run_id = self.run_id, root = %self.root.display(), "keeping run artifacts");
+            return;
+        }
+        if let Err(err) = fs::remove_dir_all(&self.root) {
+            warn!(root = %self.root.display(), error = %err, "failed to remove run artifacts");
+        }
+    }
+}
+
+/// Directory new local node data dirs are created in: the enclosing
+/// [`RunArtifacts::scope_nodes`] if any, otherwise the current directory.
+pub(crate) fn node_dir_root() -> io::Result<PathBuf> {
+    NODE_DIR_ROOT
+        .try_with(Clone::clone)
+        .or_else(|_| env::current_dir())
+}
+
+fn next_run_id() -> String {
+    let started = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let sequence = RUN_COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("{started}-{}-{sequence}", process::id())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_dir(base: &Path, name: &str, owner: Option<u32>) -> PathBuf {
+        let path = base.join(name);
+        fs::create_dir_all(&path).expect("create run dir");
+        fs::write(path.join("node.log"), "log line").expect("write log");
+        if let Some(pid) = owner {
+            fs::write(path.join(LOCK_FILE), pid.to_string()).expect("write lock");
+        }
+        path
+    }
+
+    #[test]
+    fn prune_skips_current_live_and_foreign_dirs() {
+        let base = tempfile::tempdir().expect("temp dir");
+        let finished = run_dir(base.path(), "run-finished", None);
+        let crashed = run_dir(base.path(), "run-crashed", Some(u32::MAX));
+        let live = run_dir(base.path(), "run-live", Some(process::id()));
+        let current = run_dir(base.path(), "run-current", None);
+        let foreign = run_dir(base.path(), "cache", None);
+
+        let mut removed = RetentionPolicy::unlimited()
+            .with_max_total_bytes(0)
+            .prune(base.path(), Some(&current));
+
+        removed.sort();
+        assert_eq!(removed, vec![crashed, finished]);
+        assert!(live.is_dir());
+        assert!(current.is_dir());
+        assert!(foreign.is_dir());
+    }
+
+    #[test]
+    fn unlimited_policy_keeps_everything() {
+        let base = tempfile::tempdir().expect("temp dir");
+        let finished = run_dir(base.path(), "run-finished", None);
+
+        assert!(
+            RetentionPolicy::unlimited()
+                .prune(base.path(), None)
+                .is_empty()
+        );
+        assert!(finished.is_dir());
+    }
+
+    #[test]
+    fn kept_run_drops_its_lock() {
+        let base = tempfile::tempdir().expect("temp dir");
+        let artifacts =
+            RunArtifacts::create_in(base.path(), RetentionPolicy::unlimited()).expect("create");
+        let root = artifacts.root().to_path_buf();
+        assert!(owned_by_live_process(&root));
+
+        artifacts.keep();
+        drop(artifacts);
+
+        assert!(root.is_dir());
+        assert!(!owned_by_live_process(&root));
+    }
+}
@@ -33,6 +33,15 @@ pub const DEFAULT_PROMETHEUS_HTTP_PROBE_TIMEOUT: Duration = Duration::from_secs(
 /// Default Prometheus service name.
 pub const DEFAULT_PROMETHEUS_SERVICE_NAME: &str = "prometheus";
 
+/// Tempo HTTP query port.
+pub const DEFAULT_TEMPO_HTTP_PORT: u16 = 3200;
+
+/// Tempo OTLP gRPC receiver port.
+pub const DEFAULT_TEMPO_OTLP_PORT: u16 = 4317;
+
+/// Default Tempo service name.
+pub const DEFAULT_TEMPO_SERVICE_NAME: &str = "tempo";
+
 /// Default API port used by nodes.
 pub const DEFAULT_API_PORT: u16 = 18080;
 
@@ -48,6 +57,23 @@ pub const DEFAULT_DA_NETWORK_PORT: u16 = 3300;
 /// Default blend network port.
 pub const DEFAULT_BLEND_NETWORK_PORT: u16 = 4401;
 
+/// Environment variable pointing a node container at the file it waits for
+/// before starting; set only for late-joining nodes.
+pub const JOIN_MARKER_ENV: &str = "NOMOS_JOIN_MARKER";
+
+/// Container path of the join marker created when a late node should start.
+pub const JOIN_MARKER_PATH: &str = "/tmp/nomos-join";
+
+/// Subdirectory of a node's data directory holding its RocksDB database.
+pub const NODE_DB_DIR: &str = "db";
+
+/// Subdirectory of a node's data directory holding DA blob files.
+pub const NODE_BLOBS_DIR: &str = "blobs";
+
+/// Working directory of node containers; the node's data directory on the
+/// host is bind-mounted here so relative storage paths land on the host.
+pub const NODE_STATE_CONTAINER_DIR: &str = "/state";
+
 /// Resolve cfgsync port from `NOMOS_CFGSYNC_PORT`, falling back to the default.
 pub fn cfgsync_port() -> u16 {
     env::var("NOMOS_CFGSYNC_PORT")
@@ -0,0 +1,281 @@
+//! Runner-independent classification of framework errors.
+//!
+//! Every deployer error and the errors the runner surfaces implement
+//! [`ClassifiedError`], so CI wrappers and suites can decide whether a failed
+//! run is worth retrying without matching on each runner's error enum.
+
+use std::{error::Error, fmt};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::{
+    artifacts::ArtifactsError,
+    nodes::{HttpOptionsError, WaitForHeightError},
+    scenario::{
+        AttachDeployerError, DynError, FaultProxyError, MetricsError, ScenarioError,
+        http_probe::HttpReadinessError,
+    },
+    topology::readiness::ReadinessError,
+};
+
+/// Broad cause of a failed deployment or run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The host or backend misbehaved: docker, kubernetes, ports, files.
+    Infrastructure,
+    /// A node crashed, rejected a request, or a workload or expectation
+    /// failed against it.
+    NodeFailure,
+    /// Something did not happen within its deadline.
+    Timeout,
+    /// The scenario, topology or environment is invalid; retrying cannot
+    /// help.
+    Configuration,
+}
+
+impl ErrorKind {
+    #[must_use]
+    /// Whether a failure of this kind may go away on a fresh attempt.
+    /// Infrastructure hiccups and timeouts are retryable; node failures and
+    /// configuration errors are not.
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::Infrastructure | Self::Timeout)
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Infrastructure => "infrastructure",
+            Self::NodeFailure => "node_failure",
+            Self::Timeout => "timeout",
+            Self::Configuration => "configuration",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error that knows its [`ErrorKind`].
+pub trait ClassifiedError: Error {
+    fn kind(&self) -> ErrorKind;
+
+    fn is_retryable(&self) -> bool {
+        self.kind().is_retryable()
+    }
+}
+
+/// Tags an arbitrary error with a kind, e.g. from a workload, so
+/// [`classify`] can recover it through a [`DynError`].
+#[derive(Debug, Error)]
+#[error("{source}")]
+pub struct Classified {
+    kind: ErrorKind,
+    #[source]
+    source: DynError,
+}
+
+impl Classified {
+    #[must_use]
+    pub fn new(kind: ErrorKind, source: impl Into<DynError>) -> Self {
+        Self {
+            kind,
+            source: source.into(),
+        }
+    }
+}
+
+impl ClassifiedError for Classified {
+    fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
+
+/// Kind of the first classified error in `err`'s source chain, looking
+/// through [`Classified`] and the core error types.
+#[must_use]
+pub fn classify(err: &(dyn Error + 'static)) -> Option<ErrorKind> {
+    let mut current = Some(err);
+    while let Some(err) = current {
+        if let Some(kind) = known_kind(err) {
+            return Some(kind);
+        }
+        current = err.source();
+    }
+    None
+}
+
+fn known_kind(err: &(dyn Error + 'static)) -> Option<ErrorKind> {
+    if let Some(err) = err.downcast_ref::<Classified>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<ScenarioError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<ReadinessError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<HttpReadinessError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<WaitForHeightError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<HttpOptionsError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<ArtifactsError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<MetricsError>() {
+        return Some(err.kind());
+    }
+    if let Some(err) = err.downcast_ref::<FaultProxyError>() {
+        return Some(err.kind());
+    }
+    None
+}
+
+/// Kind of a type-erased failure, defaulting to `fallback` when nothing in
+/// its chain is classified.
+fn classify_dyn(err: &DynError, fallback: ErrorKind) -> ErrorKind {
+    classify(err.as_ref()).unwrap_or(fallback)
+}
+
+impl ClassifiedError for ScenarioError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Workload(source)
+            | Self::ExpectationCapture(source)
+            | Self::Expectations(source)
+            | Self::Shutdown(source)
+            | Self::Checkpoint { source, .. } => classify_dyn(source, ErrorKind::NodeFailure),
+            Self::BlockTarget { .. } => ErrorKind::Timeout,
+            // The node binaries were built without what the scenario needs.
+            Self::MissingFeatures(_) => ErrorKind::Configuration,
+            // Raised through `RunAbort` by deployer-side monitors, e.g. the
+            // compose watchdog when a node container exits.
+            Self::Aborted(_) => ErrorKind::NodeFailure,
+            // The environment stopped the run; the nodes are not to blame.
+            Self::Interrupted(_) => ErrorKind::Infrastructure,
+        }
+    }
+}
+
+impl ClassifiedError for ReadinessError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Timeout { .. } => ErrorKind::Timeout,
+            Self::HttpClient { .. } => ErrorKind::Configuration,
+        }
+    }
+}
+
+impl ClassifiedError for HttpReadinessError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Timeout
+    }
+}
+
+impl ClassifiedError for WaitForHeightError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Behind { .. } => ErrorKind::Timeout,
+            Self::Unreachable { .. } => ErrorKind::NodeFailure,
+        }
+    }
+}
+
+impl ClassifiedError for HttpOptionsError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Configuration
+    }
+}
+
+impl ClassifiedError for ArtifactsError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::MissingRun { .. } => ErrorKind::Configuration,
+            Self::Create { .. } | Self::CurrentDir { .. } => ErrorKind::Infrastructure,
+        }
+    }
+}
+
+impl ClassifiedError for MetricsError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Infrastructure
+    }
+}
+
+impl ClassifiedError for FaultProxyError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::UnsupportedUrl { .. } => ErrorKind::Configuration,
+            Self::Bind { .. } | Self::Client { .. } => ErrorKind::Infrastructure,
+        }
+    }
+}
+
+impl ClassifiedError for AttachDeployerError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NodeCountMismatch { .. }
+            | Self::LateJoinUnsupported { .. }
+            | Self::HttpClient { .. } => ErrorKind::Configuration,
+            Self::Readiness { source } => source.kind(),
+            Self::Telemetry(source) => source.kind(),
+            Self::BlockFeed { source } => classify_dyn(source, ErrorKind::NodeFailure),
+            Self::ApiFaults(source) => source.kind(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+    use crate::scenario::ShutdownSignal;
+
+    /// Unclassified error wrapping another, like a runner-specific variant.
+    #[derive(Debug, Error)]
+    #[error("wrapped: {0}")]
+    struct Wrapper(#[source] Classified);
+
+    #[test]
+    fn classify_finds_the_first_kind_in_the_chain() {
+        let wrapped = Wrapper(Classified::new(ErrorKind::Timeout, "too slow"));
+
+        assert_eq!(classify(&wrapped), Some(ErrorKind::Timeout));
+        assert_eq!(classify(&io::Error::other("disk full")), None);
+    }
+
+    #[test]
+    fn scenario_failures_defer_to_classified_sources() {
+        let workload = ScenarioError::Workload(
+            Classified::new(ErrorKind::Infrastructure, "docker went away").into(),
+        );
+        assert_eq!(classify(&workload), Some(ErrorKind::Infrastructure));
+        assert!(workload.is_retryable());
+
+        let unclassified = ScenarioError::Expectations("tx inclusion 3/10".into());
+        assert_eq!(classify(&unclassified), Some(ErrorKind::NodeFailure));
+        assert!(!unclassified.is_retryable());
+    }
+
+    #[test]
+    fn run_stops_are_classified_by_who_stopped_it() {
+        let aborted = ScenarioError::Aborted("validator-0 exited".to_owned());
+        let interrupted = ScenarioError::Interrupted(ShutdownSignal::Terminate);
+        let missing = ScenarioError::MissingFeatures("- testing-http".into());
+
+        assert_eq!(aborted.kind(), ErrorKind::NodeFailure);
+        assert_eq!(interrupted.kind(), ErrorKind::Infrastructure);
+        assert_eq!(missing.kind(), ErrorKind::Configuration);
+    }
+}
@@ -1,5 +1,8 @@
+pub mod artifacts;
 pub mod constants;
+pub mod errors;
 pub mod nodes;
+pub mod ports;
 pub mod scenario;
 pub mod topology;
 
@@ -13,7 +16,9 @@ pub use testing_framework_config::{
 static IS_SLOW_TEST_ENV: LazyLock<bool> =
     LazyLock::new(|| env::var("SLOW_TEST_ENV").is_ok_and(|s| s == "true"));
 
-/// In slow test environments like Codecov, use 2x timeout.
+/// In slow test environments like Codecov, use 2x timeout. Profiles scale
+/// [`scenario::ScenarioTimings`] instead, which callers pass through here, so
+/// they are not applied a second time.
 #[must_use]
 pub fn adjust_timeout(d: Duration) -> Duration {
     if *IS_SLOW_TEST_ENV { d.mul(2) } else { d }
@@ -1,8 +1,13 @@
-use std::net::SocketAddr;
+use std::{collections::HashSet, net::SocketAddr};
 
 use chain_service::CryptarchiaInfo;
 use common_http_client::CommonHttpClient;
+use futures::StreamExt as _;
 use hex;
+use kzgrs_backend::common::{
+    ShareIndex,
+    share::{DaShare, DaSharesCommitments},
+};
 use nomos_core::{block::Block, da::BlobId, mantle::SignedMantleTx, sdp::SessionNumber};
 use nomos_da_network_core::swarm::{BalancerStats, MonitorStats};
 use nomos_da_network_service::MembershipResponse;
@@ -12,12 +17,15 @@ use nomos_http_api_common::paths::{
     NETWORK_INFO, STORAGE_BLOCK,
 };
 use nomos_network::backends::libp2p::Libp2pInfo;
-use nomos_node::{HeaderId, api::testing::handlers::HistoricSamplingRequest};
-use reqwest::{Client, RequestBuilder, Response, Url};
+use nomos_node::HeaderId;
+pub use nomos_node::api::testing::handlers::HistoricSamplingRequest;
+use reqwest::{Client, RequestBuilder, Response, StatusCode, Url};
 use serde::{Serialize, de::DeserializeOwned};
 use serde_json::Value;
 use tracing::error;
 
+use super::{HistoricSamplingBatch, HttpOptions, HttpOptionsError, SampledChunk};
+
 pub const DA_GET_TESTING_ENDPOINT_ERROR: &str = "Failed to connect to testing endpoint. The binary was likely built without the 'testing' \
      feature. Try: cargo build --workspace --all-features";
 
@@ -28,6 +36,8 @@ pub struct ApiClient {
     pub(crate) testing_url: Option<Url>,
     client: Client,
     pub(crate) http_client: CommonHttpClient,
+    /// Options `client` was built from.
+    options: HttpOptions,
 }
 
 impl ApiClient {
@@ -44,15 +54,45 @@ impl ApiClient {
     #[must_use]
     /// Construct from prebuilt URLs.
     pub fn from_urls(base_url: Url, testing_url: Option<Url>) -> Self {
-        let client = Client::new();
+        Self::from_urls_with_client(base_url, testing_url, Client::new(), HttpOptions::default())
+    }
+
+    /// Construct from prebuilt URLs, e.g. `https://` ingress endpoints, with
+    /// the TLS and auth settings in `options`.
+    pub fn from_urls_with_options(
+        base_url: Url,
+        testing_url: Option<Url>,
+        options: &HttpOptions,
+    ) -> Result<Self, HttpOptionsError> {
+        Ok(Self::from_urls_with_client(
+            base_url,
+            testing_url,
+            options.client()?,
+            options.clone(),
+        ))
+    }
+
+    pub(crate) fn from_urls_with_client(
+        base_url: Url,
+        testing_url: Option<Url>,
+        client: Client,
+        options: HttpOptions,
+    ) -> Self {
         Self {
             base_url,
             testing_url,
             http_client: CommonHttpClient::new_with_client(client.clone(), None),
             client,
+            options,
         }
     }
 
+    #[must_use]
+    /// Transport settings the client was built with.
+    pub const fn http_options(&self) -> &HttpOptions {
+        &self.options
+    }
+
     #[must_use]
     /// Testing URL, when built with testing features.
     pub fn testing_url(&self) -> Option<Url> {
@@ -185,6 +225,20 @@ impl ApiClient {
             .await
     }
 
+    /// Status the testing API answers a membership query with, or `None`
+    /// when the client has no testing address. Unlike the typed testing
+    /// calls it never panics, so it can check the API is served at all.
+    pub async fn testing_api_status(&self) -> Option<reqwest::Result<StatusCode>> {
+        let testing_url = self.testing_url.as_ref()?;
+        let response = self
+            .client
+            .post(Self::join_url(testing_url, DA_GET_MEMBERSHIP))
+            .json(&SessionNumber::from(0u64))
+            .send()
+            .await;
+        Some(response.map(|response| response.status()))
+    }
+
     /// Block a peer via the DA testing API.
     pub async fn block_peer(&self, peer_id: &str) -> reqwest::Result<bool> {
         self.post_json_decode(DA_BLOCK_PEER, &peer_id).await
@@ -275,6 +329,88 @@ impl ApiClient {
             .await
     }
 
+    /// Historic sampling over a large blob set, split into chunks sampled
+    /// with bounded concurrency and retried according to `batch`. Returns one
+    /// result per chunk, in no particular order.
+    pub async fn da_historic_sampling_batch(
+        &self,
+        requests: &[HistoricSamplingRequest<BlobId>],
+        batch: &HistoricSamplingBatch,
+    ) -> Vec<SampledChunk> {
+        let chunks = requests.iter().flat_map(|request| {
+            request
+                .blob_ids
+                .chunks(batch.chunk_size())
+                .map(|blob_ids| HistoricSamplingRequest {
+                    block_id: request.block_id,
+                    blob_ids: blob_ids.to_vec(),
+                })
+        });
+        futures::stream::iter(chunks)
+            .map(|request| self.sample_chunk(request, batch))
+            .buffer_unordered(batch.concurrency())
+            .collect()
+            .await
+    }
+
+    async fn sample_chunk(
+        &self,
+        request: HistoricSamplingRequest<BlobId>,
+        batch: &HistoricSamplingBatch,
+    ) -> SampledChunk {
+        let mut failure = None;
+        let mut attempts = 0;
+        while attempts < batch.attempts() {
+            if attempts > 0 {
+                tokio::time::sleep(batch.retry_delay()).await;
+            }
+            attempts += 1;
+            failure = match self.da_historic_sampling(&request).await {
+                Ok(true) => None,
+                Ok(false) => Some("node reported sampling failure".to_owned()),
+                Err(err) => Some(format!("request failed: {err}")),
+            };
+            if failure.is_none() {
+                break;
+            }
+        }
+        SampledChunk {
+            block_id: request.block_id,
+            blob_ids: request.blob_ids,
+            attempts,
+            failure,
+        }
+    }
+
+    /// Indices of the shares of `blob_id` this node stores.
+    pub async fn da_share_indices(
+        &self,
+        blob_id: BlobId,
+    ) -> Result<HashSet<ShareIndex>, common_http_client::Error> {
+        let shares = self
+            .http_client
+            .get_shares::<DaShare>(
+                self.base_url.clone(),
+                blob_id,
+                HashSet::new(),
+                HashSet::new(),
+                true,
+            )
+            .await?;
+        Ok(shares.map(|share| share.share_idx).collect().await)
+    }
+
+    /// Row commitments this node stores for `blob_id`, or `None` when it holds
+    /// none.
+    pub async fn da_commitments(
+        &self,
+        blob_id: BlobId,
+    ) -> Result<Option<DaSharesCommitments>, common_http_client::Error> {
+        self.http_client
+            .get_commitments::<DaShare>(self.base_url.clone(), blob_id)
+            .await
+    }
+
     /// Submit a mantle transaction through the base API.
     pub async fn submit_transaction(&self, tx: &SignedMantleTx) -> reqwest::Result<()> {
         let res = self.post_json_response(MEMPOOL_ADD_TX, tx).await?;
@@ -321,3 +457,113 @@ impl ApiClient {
         base.join(trimmed).expect("valid relative path")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroUsize,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::Duration,
+    };
+
+    use tokio::{
+        io::{AsyncReadExt as _, AsyncWriteExt as _},
+        net::TcpListener,
+    };
+
+    use super::*;
+
+    fn request(block: u8, blobs: u8) -> HistoricSamplingRequest<BlobId> {
+        HistoricSamplingRequest {
+            block_id: HeaderId::from([block; 32]),
+            blob_ids: (0..blobs).map(|n| ([n; 32], 0)).collect(),
+        }
+    }
+
+    fn batch(chunk_size: usize, attempts: usize) -> HistoricSamplingBatch {
+        HistoricSamplingBatch::default()
+            .with_chunk_size(NonZeroUsize::new(chunk_size).expect("non-zero"))
+            .with_retry(
+                NonZeroUsize::new(attempts).expect("non-zero"),
+                Duration::from_millis(1),
+            )
+    }
+
+    /// Answers the sampling route with `false` for the first `failures`
+    /// requests and `true` afterwards.
+    async fn sampling_node(failures: usize) -> (SocketAddr, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("bind sampling node");
+        let addr = listener.local_addr().expect("local addr");
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = vec![0; 64 * 1024];
+                let _ = stream.read(&mut buf).await;
+                let answer = if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    "false"
+                } else {
+                    "true"
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{answer}",
+                    answer.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (addr, requests)
+    }
+
+    #[tokio::test]
+    async fn batch_splits_requests_into_chunks_and_retries_each() {
+        // Nothing listens on port 1, so every attempt fails.
+        let addr = SocketAddr::from(([127, 0, 0, 1], 1));
+        let client = ApiClient::new(addr, Some(addr));
+
+        let chunks = client
+            .da_historic_sampling_batch(&[request(1, 5), request(2, 2)], &batch(2, 3))
+            .await;
+
+        assert_eq!(chunks.len(), 4);
+        assert!(chunks.iter().all(|chunk| chunk.blob_ids.len() <= 2));
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| chunk.blob_ids.len())
+                .sum::<usize>(),
+            7
+        );
+        assert_eq!(
+            chunks
+                .iter()
+                .filter(|chunk| chunk.block_id == HeaderId::from([1; 32]))
+                .count(),
+            3
+        );
+        for chunk in &chunks {
+            assert_eq!(chunk.attempts, 3);
+            assert!(!chunk.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn chunk_retry_stops_once_sampled() {
+        let (addr, requests) = sampling_node(1).await;
+        let client = ApiClient::new(addr, Some(addr));
+
+        let chunks = client
+            .da_historic_sampling_batch(&[request(1, 3)], &batch(16, 3))
+            .await;
+
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].is_ok(), "{:?}", chunks[0].failure);
+        assert_eq!(chunks[0].attempts, 2);
+        assert_eq!(requests.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,5 +1,7 @@
 use std::{fs, io, path::Path};
 
+use crate::constants::NODE_BLOBS_DIR;
+
 /// Ensure recovery-related directories and placeholder files exist under the
 /// given base dir.
 pub fn ensure_recovery_paths(base_dir: &Path) -> io::Result<()> {
@@ -21,3 +23,8 @@ pub fn ensure_recovery_paths(base_dir: &Path) -> io::Result<()> {
 
     Ok(())
 }
+
+/// Ensure the DA blob directory exists under the given base dir.
+pub fn ensure_storage_paths(base_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(base_dir.join(NODE_BLOBS_DIR))
+}
@@ -1,24 +1,50 @@
 use std::{
+    io,
     net::SocketAddr,
     path::{Path, PathBuf},
     process::{Child, Command, Stdio},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use nomos_tracing_service::LoggerLayer;
 use reqwest::Url;
 use serde::Serialize;
 use tempfile::TempDir;
+use thiserror::Error;
 use tokio::time;
 use tracing::{debug, info};
 
-use super::lifecycle::monitor::is_running;
-use crate::nodes::{
-    ApiClient,
-    common::{config::paths::ensure_recovery_paths, lifecycle::spawn::configure_logging},
-    create_tempdir, persist_tempdir,
+use super::lifecycle::{kill::kill_child, monitor::is_running};
+use crate::{
+    nodes::{
+        ApiClient,
+        common::{
+            config::paths::{ensure_recovery_paths, ensure_storage_paths},
+            lifecycle::spawn::configure_logging,
+        },
+        create_tempdir, persist_tempdir,
+    },
+    scenario::NodeExit,
 };
 
+/// How long a process gets to be reaped once killed with `SIGKILL`.
+const KILL_EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a spawned node gets to answer `consensus_info`.
+const READINESS_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Failures bringing up a node process.
+#[derive(Debug, Error)]
+pub enum SpawnNodeError {
+    #[error("failed to spawn {}: {source}", binary.display())]
+    Spawn {
+        binary: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("node did not become ready within {timeout:?}")]
+    Readiness { timeout: Duration },
+}
+
 /// Minimal interface to apply common node setup.
 pub trait NodeConfigCommon {
     fn set_logger(&mut self, logger: LoggerLayer);
@@ -64,6 +90,51 @@ impl<T> NodeHandle<T> {
         &self.config
     }
 
+    /// Working directory of the node, which also receives its log files
+    /// unless `NOMOS_LOG_DIR` redirects them.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        self.tempdir.path()
+    }
+
+    /// Send `SIGTERM` and wait up to `deadline` for the process to exit,
+    /// killing it if it is still running by then.
+    pub async fn terminate(&mut self, deadline: Duration) -> io::Result<NodeExit> {
+        let started = Instant::now();
+        let status = tokio::process::Command::new("kill")
+            .arg("-TERM")
+            .arg(self.child.id().to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await?;
+        if !status.success() {
+            return Err(io::Error::other(format!(
+                "kill -TERM {} exited with {status}",
+                self.child.id()
+            )));
+        }
+
+        let timed_out = !self.wait_for_exit(deadline).await;
+        if timed_out {
+            debug!(pid = self.child.id(), "process ignored SIGTERM; killing it");
+            kill_child(&mut self.child);
+            self.wait_for_exit(KILL_EXIT_TIMEOUT).await;
+        }
+        let status = self.child.try_wait()?.ok_or_else(|| {
+            io::Error::other(format!(
+                "process {} still running after SIGKILL",
+                self.child.id()
+            ))
+        })?;
+
+        Ok(NodeExit {
+            elapsed: started.elapsed(),
+            exit_code: status.code(),
+            timed_out,
+        })
+    }
+
     /// Returns true if the process exited within the timeout, false otherwise.
     pub async fn wait_for_exit(&mut self, timeout: Duration) -> bool {
         time::timeout(timeout, async {
@@ -77,6 +148,48 @@ impl<T> NodeHandle<T> {
         .await
         .is_ok()
     }
+
+    /// Kill the process and start `binary_path` again in the same directory
+    /// with the `config_filename` written by [`spawn_node`], so the node comes
+    /// back with its database and blobs. Waits for readiness like
+    /// [`spawn_node`].
+    pub async fn respawn(
+        &mut self,
+        binary_path: PathBuf,
+        config_filename: &str,
+    ) -> Result<(), SpawnNodeError> {
+        kill_child(&mut self.child);
+        self.wait_for_exit(KILL_EXIT_TIMEOUT).await;
+
+        let config_path = self.tempdir.path().join(config_filename);
+        self.child = start_process(binary_path, self.tempdir.path(), &config_path)?;
+        self.wait_ready().await
+    }
+
+    /// Wait for `consensus_info` to answer. On timeout the directory is
+    /// persisted to aid debugging and the process is killed.
+    async fn wait_ready(&mut self) -> Result<(), SpawnNodeError> {
+        let ready = time::timeout(READINESS_TIMEOUT, async {
+            loop {
+                if self.api.consensus_info().await.is_ok() {
+                    break;
+                }
+                time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+
+        if ready.is_err() {
+            let _ = persist_tempdir(&mut self.tempdir, "nomos-node");
+            kill_child(&mut self.child);
+            return Err(SpawnNodeError::Readiness {
+                timeout: READINESS_TIMEOUT,
+            });
+        }
+
+        info!("node readiness confirmed via consensus_info");
+        Ok(())
+    }
 }
 
 /// Apply common setup (recovery paths, logging, data dirs) and return a ready
@@ -93,6 +206,7 @@ pub fn prepare_node_config<T: NodeConfigCommon>(
     // Ensure recovery files/dirs exist so services that persist state do not fail
     // on startup.
     let _ = ensure_recovery_paths(dir.path());
+    let _ = ensure_storage_paths(dir.path());
 
     if enable_logging {
         configure_logging(dir.path(), log_prefix, |file_cfg| {
@@ -115,7 +229,7 @@ pub async fn spawn_node<C>(
     config_filename: &str,
     binary_path: PathBuf,
     enable_logging: bool,
-) -> Result<NodeHandle<C>, tokio::time::error::Elapsed>
+) -> Result<NodeHandle<C>, SpawnNodeError>
 where
     C: NodeConfigCommon + Serialize,
 {
@@ -126,36 +240,28 @@ where
     })
     .expect("failed to write node config");
 
+    let child = start_process(binary_path, dir.path(), &config_path)?;
+    let mut handle = NodeHandle::new(child, dir, config, ApiClient::new(addr, testing_addr));
+    handle.wait_ready().await?;
+    Ok(handle)
+}
+
+fn start_process(
+    binary_path: PathBuf,
+    dir: &Path,
+    config_path: &Path,
+) -> Result<Child, SpawnNodeError> {
     debug!(config_file = %config_path.display(), binary = %binary_path.display(), "spawning node process");
 
-    let child = Command::new(binary_path)
-        .arg(&config_path)
-        .current_dir(dir.path())
+    Command::new(&binary_path)
+        .arg(config_path)
+        .current_dir(dir)
         .stdin(Stdio::null())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
-        .expect("failed to spawn node process");
-
-    let mut handle = NodeHandle::new(child, dir, config, ApiClient::new(addr, testing_addr));
-
-    // Wait for readiness via consensus_info
-    let ready = time::timeout(Duration::from_secs(60), async {
-        loop {
-            if handle.api.consensus_info().await.is_ok() {
-                break;
-            }
-            time::sleep(Duration::from_millis(100)).await;
-        }
-    })
-    .await;
-
-    if let Err(err) = ready {
-        // Persist tempdir to aid debugging if readiness fails.
-        let _ = persist_tempdir(&mut handle.tempdir, "nomos-node");
-        return Err(err);
-    }
-
-    info!("node readiness confirmed via consensus_info");
-    Ok(handle)
+        .map_err(|source| SpawnNodeError::Spawn {
+            binary: binary_path,
+            source,
+        })
 }
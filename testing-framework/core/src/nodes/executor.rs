@@ -1,4 +1,4 @@
-use std::{ops::Deref, path::PathBuf};
+use std::{io, ops::Deref, path::PathBuf, time::Duration};
 
 use nomos_executor::config::Config;
 use nomos_tracing_service::LoggerLayer;
@@ -8,17 +8,20 @@ use tracing::{debug, info};
 use super::{persist_tempdir, should_persist_tempdir};
 use crate::{
     IS_DEBUG_TRACING,
+    constants::{NODE_BLOBS_DIR, NODE_DB_DIR},
     nodes::{
         LOGS_PREFIX,
         common::{
             binary::{BinaryConfig, BinaryResolver},
             lifecycle::{kill::kill_child, monitor::is_running},
-            node::{NodeConfigCommon, NodeHandle, spawn_node},
+            node::{NodeConfigCommon, NodeHandle, SpawnNodeError, spawn_node},
         },
     },
+    scenario::NodeExit,
 };
 
 const BIN_PATH: &str = "target/debug/nomos-executor";
+const CONFIG_FILENAME: &str = "executor.yaml";
 
 fn binary_path() -> PathBuf {
     let cfg = BinaryConfig {
@@ -57,19 +60,37 @@ impl Drop for Executor {
 
 impl Executor {
     pub async fn spawn(config: Config) -> Self {
+        Self::try_spawn(config)
+            .await
+            .expect("executor failed to start")
+    }
+
+    /// Like [`Self::spawn`], but returns an error instead of panicking when
+    /// the executor cannot be started or does not become ready. In the
+    /// latter case the process is killed.
+    pub async fn try_spawn(config: Config) -> Result<Self, SpawnNodeError> {
         let handle = spawn_node(
             config,
             LOGS_PREFIX,
-            "executor.yaml",
+            CONFIG_FILENAME,
             binary_path(),
             !*IS_DEBUG_TRACING,
         )
-        .await
-        .expect("executor did not become ready");
+        .await?;
 
         info!("executor spawned and ready");
 
-        Self { handle }
+        Ok(Self { handle })
+    }
+
+    /// Kill the executor process and start it again from its existing
+    /// directory and config, so it keeps its database and blobs.
+    pub async fn respawn(&mut self) -> Result<(), SpawnNodeError> {
+        self.handle.respawn(binary_path(), CONFIG_FILENAME).await?;
+
+        info!("executor respawned and ready");
+
+        Ok(())
     }
 
     /// Check if the executor process is still running
@@ -77,6 +98,12 @@ impl Executor {
         is_running(&mut self.handle.child)
     }
 
+    /// Send `SIGTERM` to the executor process and wait up to `deadline` for it
+    /// to exit, killing it otherwise.
+    pub async fn terminate(&mut self, deadline: Duration) -> io::Result<NodeExit> {
+        self.handle.terminate(deadline).await
+    }
+
     /// Wait for the executor process to exit, with a timeout.
     pub async fn wait_for_exit(&mut self, timeout: std::time::Duration) -> bool {
         self.handle.wait_for_exit(timeout).await
@@ -89,13 +116,10 @@ impl NodeConfigCommon for Config {
     }
 
     fn set_paths(&mut self, base: &std::path::Path) {
-        self.storage.db_path = base.join("db");
-        base.clone_into(
-            &mut self
-                .da_verifier
-                .storage_adapter_settings
-                .blob_storage_directory,
-        );
+        self.storage.db_path = base.join(NODE_DB_DIR);
+        self.da_verifier
+            .storage_adapter_settings
+            .blob_storage_directory = base.join(NODE_BLOBS_DIR);
     }
 
     fn addresses(&self) -> (std::net::SocketAddr, Option<std::net::SocketAddr>) {
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use chain_service::CryptarchiaInfo;
+use reqwest::Url;
+use thiserror::Error;
+use tokio::time::{Instant, sleep};
+use tracing::debug;
+
+use super::ApiClient;
+
+/// Delay between `consensus_info` polls, doubling from `initial` up to `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeightBackoff {
+    pub initial: Duration,
+    pub max: Duration,
+}
+
+impl Default for HeightBackoff {
+    fn default() -> Self {
+        Self {
+            initial: Duration::from_millis(250),
+            max: Duration::from_secs(2),
+        }
+    }
+}
+
+/// A node did not reach the requested height in time.
+#[derive(Debug, Error)]
+pub enum WaitForHeightError {
+    #[error(
+        "node {url} did not reach height {target} within {timeout:?} (last seen at height {current})"
+    )]
+    Behind {
+        url: Url,
+        target: u64,
+        current: u64,
+        timeout: Duration,
+    },
+    #[error(
+        "node {url} did not reach height {target} within {timeout:?}; consensus_info failed: {source}"
+    )]
+    Unreachable {
+        url: Url,
+        target: u64,
+        timeout: Duration,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+/// Polls `consensus_info` until the node reports at least `height`, with the
+/// default [`HeightBackoff`]. Request failures are retried until `timeout`.
+pub async fn wait_for_height(
+    client: &ApiClient,
+    height: u64,
+    timeout: Duration,
+) -> Result<CryptarchiaInfo, WaitForHeightError> {
+    wait_for_height_with_backoff(client, height, timeout, HeightBackoff::default()).await
+}
+
+/// [`wait_for_height`] with a custom poll backoff.
+pub async fn wait_for_height_with_backoff(
+    client: &ApiClient,
+    height: u64,
+    timeout: Duration,
+    backoff: HeightBackoff,
+) -> Result<CryptarchiaInfo, WaitForHeightError> {
+    let deadline = Instant::now() + timeout;
+    let mut delay = backoff.initial;
+    loop {
+        let last = match client.consensus_info().await {
+            Ok(info) if info.height >= height => return Ok(info),
+            Ok(info) => {
+                debug!(url = %client.base_url, height = info.height, target = height, "waiting for height");
+                Ok(info.height)
+            }
+            Err(err) => {
+                debug!(url = %client.base_url, error = %err, "consensus_info failed while waiting for height");
+                Err(err)
+            }
+        };
+
+        let now = Instant::now();
+        if now >= deadline {
+            let url = client.base_url.clone();
+            return Err(match last {
+                Ok(current) => WaitForHeightError::Behind {
+                    url,
+                    target: height,
+                    current,
+                    timeout,
+                },
+                Err(source) => WaitForHeightError::Unreachable {
+                    url,
+                    target: height,
+                    timeout,
+                    source,
+                },
+            });
+        }
+        sleep(delay.min(deadline - now)).await;
+        delay = delay.saturating_mul(2).min(backoff.max);
+    }
+}
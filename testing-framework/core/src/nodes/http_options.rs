@@ -0,0 +1,251 @@
+use std::{fmt, fs, io, path::PathBuf};
+
+use reqwest::{
+    Certificate, Client, ClientBuilder,
+    header::{
+        AUTHORIZATION, HeaderMap, HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue,
+    },
+};
+use thiserror::Error;
+
+/// Transport settings for the HTTP clients talking to node APIs: readiness
+/// probes and [`super::ApiClient`].
+///
+/// The defaults match a plain `http://` endpoint without authentication.
+/// Headers, including the bearer token, are sent with every request, so
+/// `https://` URLs behind an authenticating ingress work the same way as
+/// local ports.
+#[derive(Clone, Default)]
+pub struct HttpOptions {
+    bearer_token: Option<String>,
+    headers: Vec<(String, String)>,
+    ca_certificate: Option<PathBuf>,
+    accept_invalid_certs: bool,
+}
+
+/// Failures building an HTTP client from [`HttpOptions`].
+#[derive(Debug, Error)]
+pub enum HttpOptionsError {
+    #[error("invalid header name {name:?}: {source}")]
+    HeaderName {
+        name: String,
+        #[source]
+        source: InvalidHeaderName,
+    },
+    #[error("invalid value for header {name:?}: {source}")]
+    HeaderValue {
+        name: String,
+        #[source]
+        source: InvalidHeaderValue,
+    },
+    #[error("failed to read CA certificate {path}: {source}")]
+    ReadCertificate {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid CA certificate {path}: {source}")]
+    Certificate {
+        path: PathBuf,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("failed to build HTTP client: {source}")]
+    Client {
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+impl HttpOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Send `Authorization: Bearer <token>` with every request.
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_token = Some(token.into());
+        self
+    }
+
+    #[must_use]
+    /// Send an extra header with every request, e.g. an API key expected by
+    /// the ingress.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    #[must_use]
+    /// Trust a PEM encoded CA certificate in addition to the system roots,
+    /// for clusters serving a private CA.
+    pub fn with_ca_certificate(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_certificate = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    /// Skip TLS certificate verification. Only meant for throwaway clusters
+    /// with self-signed certificates.
+    pub const fn danger_accept_invalid_certs(mut self, enabled: bool) -> Self {
+        self.accept_invalid_certs = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Whether these options change anything over a default client.
+    pub fn is_default(&self) -> bool {
+        self.bearer_token.is_none()
+            && self.headers.is_empty()
+            && self.ca_certificate.is_none()
+            && !self.accept_invalid_certs
+    }
+
+    /// Build a client applying these options.
+    pub fn client(&self) -> Result<Client, HttpOptionsError> {
+        if self.is_default() {
+            return Ok(Client::new());
+        }
+        self.client_builder()?
+            .build()
+            .map_err(|source| HttpOptionsError::Client { source })
+    }
+
+    /// A client builder with these options applied, for callers adding
+    /// their own settings.
+    pub fn client_builder(&self) -> Result<ClientBuilder, HttpOptionsError> {
+        let mut builder = Client::builder()
+            .default_headers(self.headers()?)
+            .danger_accept_invalid_certs(self.accept_invalid_certs);
+        if let Some(path) = &self.ca_certificate {
+            let pem = fs::read(path).map_err(|source| HttpOptionsError::ReadCertificate {
+                path: path.clone(),
+                source,
+            })?;
+            let certificate =
+                Certificate::from_pem(&pem).map_err(|source| HttpOptionsError::Certificate {
+                    path: path.clone(),
+                    source,
+                })?;
+            builder = builder.add_root_certificate(certificate);
+        }
+        Ok(builder)
+    }
+
+    fn headers(&self) -> Result<HeaderMap, HttpOptionsError> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let header = HeaderName::try_from(name.as_str()).map_err(|source| {
+                HttpOptionsError::HeaderName {
+                    name: name.clone(),
+                    source,
+                }
+            })?;
+            let mut value = HeaderValue::try_from(value.as_str()).map_err(|source| {
+                HttpOptionsError::HeaderValue {
+                    name: name.clone(),
+                    source,
+                }
+            })?;
+            value.set_sensitive(true);
+            headers.append(header, value);
+        }
+        if let Some(token) = &self.bearer_token {
+            let mut value = HeaderValue::try_from(format!("Bearer {token}")).map_err(|source| {
+                HttpOptionsError::HeaderValue {
+                    name: AUTHORIZATION.to_string(),
+                    source,
+                }
+            })?;
+            value.set_sensitive(true);
+            headers.insert(AUTHORIZATION, value);
+        }
+        Ok(headers)
+    }
+}
+
+impl fmt::Debug for HttpOptions {
+    // Header values and the token are credentials; keep them out of logs.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpOptions")
+            .field(
+                "bearer_token",
+                &self.bearer_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field(
+                "headers",
+                &self
+                    .headers
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .field("ca_certificate", &self.ca_certificate)
+            .field("accept_invalid_certs", &self.accept_invalid_certs)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn headers_carry_the_token_and_extra_headers_as_sensitive() {
+        let options = HttpOptions::new()
+            .with_header("x-api-key", "secret")
+            .with_bearer_token("token");
+
+        let headers = options.headers().expect("valid headers");
+
+        assert_eq!(headers["x-api-key"], "secret");
+        assert_eq!(headers[AUTHORIZATION], "Bearer token");
+        assert!(headers.values().all(HeaderValue::is_sensitive));
+        assert!(!options.is_default());
+        assert!(HttpOptions::new().is_default());
+    }
+
+    #[test]
+    fn invalid_headers_are_rejected() {
+        let name = HttpOptions::new().with_header("bad header", "value");
+        let value = HttpOptions::new().with_header("x-api-key", "line\nbreak");
+
+        assert!(matches!(
+            name.client(),
+            Err(HttpOptionsError::HeaderName { name, .. }) if name == "bad header"
+        ));
+        assert!(matches!(
+            value.client(),
+            Err(HttpOptionsError::HeaderValue { name, .. }) if name == "x-api-key"
+        ));
+    }
+
+    #[test]
+    fn missing_ca_certificate_is_reported() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("ca.pem");
+
+        let result = HttpOptions::new().with_ca_certificate(&path).client();
+
+        assert!(matches!(
+            result,
+            Err(HttpOptionsError::ReadCertificate { path: missing, .. }) if missing == path
+        ));
+    }
+
+    #[test]
+    fn debug_output_redacts_credentials() {
+        let options = HttpOptions::new()
+            .with_header("x-api-key", "secret")
+            .with_bearer_token("t0ken");
+
+        let debug = format!("{options:?}");
+
+        assert!(debug.contains("x-api-key"), "{debug}");
+        assert!(!debug.contains("secret"), "{debug}");
+        assert!(!debug.contains("t0ken"), "{debug}");
+        assert!(debug.contains("<redacted>"), "{debug}");
+    }
+}
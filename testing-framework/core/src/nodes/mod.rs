@@ -1,22 +1,33 @@
 mod api_client;
 pub mod common;
 pub mod executor;
+mod height;
+mod http_options;
+mod sampling;
+mod session;
 pub mod validator;
 
 use std::sync::LazyLock;
 
-pub use api_client::ApiClient;
+pub use api_client::{ApiClient, HistoricSamplingRequest};
+pub use height::{
+    HeightBackoff, WaitForHeightError, wait_for_height, wait_for_height_with_backoff,
+};
+pub use http_options::{HttpOptions, HttpOptionsError};
+pub use sampling::{HistoricSamplingBatch, SampledChunk};
+pub use session::{current_session, session_at, session_start, wait_for_session};
 use tempfile::TempDir;
 
 pub(crate) const LOGS_PREFIX: &str = "__logs";
 static KEEP_NODE_TEMPDIRS: LazyLock<bool> =
-    LazyLock::new(|| std::env::var("NOMOS_TESTS_KEEP_LOGS").is_ok());
+    LazyLock::new(|| std::env::var(crate::artifacts::KEEP_LOGS_ENV).is_ok());
 
 pub(crate) fn create_tempdir() -> std::io::Result<TempDir> {
-    // It's easier to use the current location instead of OS-default tempfile
-    // location because Github Actions can easily access files in the current
-    // location using wildcard to upload them as artifacts.
-    TempDir::new_in(std::env::current_dir()?)
+    // It's easier to use the current location (or the run's artifacts
+    // directory) instead of OS-default tempfile location because Github
+    // Actions can easily access files there using wildcard to upload them as
+    // artifacts.
+    TempDir::new_in(crate::artifacts::node_dir_root()?)
 }
 
 fn persist_tempdir(tempdir: &mut TempDir, label: &str) -> std::io::Result<()> {
@@ -0,0 +1,98 @@
+use std::{num::NonZeroUsize, time::Duration};
+
+use nomos_core::{da::BlobId, sdp::SessionNumber};
+use nomos_node::HeaderId;
+
+const DEFAULT_CHUNK_SIZE: usize = 16;
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_ATTEMPTS: usize = 3;
+const DEFAULT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// How [`ApiClient::da_historic_sampling_batch`](super::ApiClient::da_historic_sampling_batch)
+/// splits a large blob set into historic sampling requests.
+///
+/// Blobs are grouped per block, each group is cut into chunks of at most
+/// `chunk_size` blobs, and up to `concurrency` chunk requests are in flight
+/// at once. A chunk the node fails to sample, or whose request fails, is
+/// retried up to `attempts` times in total, `retry_delay` apart.
+#[derive(Clone, Copy, Debug)]
+pub struct HistoricSamplingBatch {
+    chunk_size: NonZeroUsize,
+    concurrency: NonZeroUsize,
+    attempts: NonZeroUsize,
+    retry_delay: Duration,
+}
+
+impl Default for HistoricSamplingBatch {
+    fn default() -> Self {
+        Self {
+            chunk_size: NonZeroUsize::new(DEFAULT_CHUNK_SIZE).expect("non-zero"),
+            concurrency: NonZeroUsize::new(DEFAULT_CONCURRENCY).expect("non-zero"),
+            attempts: NonZeroUsize::new(DEFAULT_ATTEMPTS).expect("non-zero"),
+            retry_delay: DEFAULT_RETRY_DELAY,
+        }
+    }
+}
+
+impl HistoricSamplingBatch {
+    #[must_use]
+    /// Maximum number of blobs sent in one request.
+    pub const fn with_chunk_size(mut self, chunk_size: NonZeroUsize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    #[must_use]
+    /// Maximum number of requests in flight against the node.
+    pub const fn with_concurrency(mut self, concurrency: NonZeroUsize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    #[must_use]
+    /// Attempts per chunk, including the first one, and the delay between
+    /// them.
+    pub const fn with_retry(mut self, attempts: NonZeroUsize, retry_delay: Duration) -> Self {
+        self.attempts = attempts;
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    #[must_use]
+    pub const fn chunk_size(&self) -> usize {
+        self.chunk_size.get()
+    }
+
+    #[must_use]
+    pub const fn concurrency(&self) -> usize {
+        self.concurrency.get()
+    }
+
+    #[must_use]
+    pub const fn attempts(&self) -> usize {
+        self.attempts.get()
+    }
+
+    #[must_use]
+    pub const fn retry_delay(&self) -> Duration {
+        self.retry_delay
+    }
+}
+
+/// Outcome of one chunk of a batched historic sampling call.
+#[derive(Clone, Debug)]
+pub struct SampledChunk {
+    pub block_id: HeaderId,
+    pub blob_ids: Vec<(BlobId, SessionNumber)>,
+    /// Attempts made, including the successful one.
+    pub attempts: usize,
+    /// Why the last attempt failed, `None` when the node sampled the chunk.
+    pub failure: Option<String>,
+}
+
+impl SampledChunk {
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.failure.is_none()
+    }
+}
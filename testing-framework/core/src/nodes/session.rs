@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+use chain_service::CryptarchiaInfo;
+use nomos_core::sdp::SessionNumber;
+
+use super::{ApiClient, WaitForHeightError, wait_for_height};
+
+/// SDP session block `height` belongs to, for sessions of `duration` blocks.
+#[must_use]
+pub fn session_at(height: u64, duration: u64) -> SessionNumber {
+    SessionNumber::from(height / duration.max(1))
+}
+
+/// Height of the first block of `session`.
+#[must_use]
+pub fn session_start(session: SessionNumber, duration: u64) -> u64 {
+    u64::from(session).saturating_mul(duration.max(1))
+}
+
+/// Session the node's tip is in.
+pub async fn current_session(client: &ApiClient, duration: u64) -> reqwest::Result<SessionNumber> {
+    let info = client.consensus_info().await?;
+    Ok(session_at(info.height, duration))
+}
+
+/// Polls `consensus_info` until the node's tip enters `session`, retrying
+/// failed requests until `timeout`.
+pub async fn wait_for_session(
+    client: &ApiClient,
+    session: SessionNumber,
+    duration: u64,
+    timeout: Duration,
+) -> Result<CryptarchiaInfo, WaitForHeightError> {
+    wait_for_height(client, session_start(session, duration), timeout).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_at_groups_heights_by_duration() {
+        assert_eq!(session_at(0, 10), SessionNumber::from(0u64));
+        assert_eq!(session_at(9, 10), SessionNumber::from(0u64));
+        assert_eq!(session_at(10, 10), SessionNumber::from(1u64));
+        assert_eq!(session_at(25, 10), SessionNumber::from(2u64));
+    }
+
+    #[test]
+    fn zero_duration_counts_every_block_as_a_session() {
+        assert_eq!(session_at(7, 0), SessionNumber::from(7u64));
+        assert_eq!(session_start(SessionNumber::from(7u64), 0), 7);
+    }
+
+    #[test]
+    fn session_start_is_the_first_height_of_its_session() {
+        for session in 0..5u64 {
+            let start = session_start(SessionNumber::from(session), 10);
+            assert_eq!(start, session * 10);
+            assert_eq!(session_at(start, 10), SessionNumber::from(session));
+            assert_eq!(
+                session_at(start.saturating_sub(1), 10),
+                SessionNumber::from(session.saturating_sub(1))
+            );
+        }
+        assert_eq!(session_start(SessionNumber::from(u64::MAX), 2), u64::MAX);
+    }
+}
@@ -1,25 +1,27 @@
-use std::{ops::Deref, path::PathBuf, time::Duration};
+use std::{io, ops::Deref, path::PathBuf, time::Duration};
 
 use nomos_node::Config;
 use nomos_tracing_service::LoggerLayer;
 pub use testing_framework_config::nodes::validator::create_validator_config;
-use tokio::time::error::Elapsed;
 use tracing::{debug, info};
 
 use super::{persist_tempdir, should_persist_tempdir};
 use crate::{
     IS_DEBUG_TRACING,
+    constants::{NODE_BLOBS_DIR, NODE_DB_DIR},
     nodes::{
         LOGS_PREFIX,
         common::{
             binary::{BinaryConfig, BinaryResolver},
             lifecycle::{kill::kill_child, monitor::is_running},
-            node::{NodeConfigCommon, NodeHandle, spawn_node},
+            node::{NodeConfigCommon, NodeHandle, SpawnNodeError, spawn_node},
         },
     },
+    scenario::NodeExit,
 };
 
 const BIN_PATH: &str = "target/debug/nomos-node";
+const CONFIG_FILENAME: &str = "validator.yaml";
 
 fn binary_path() -> PathBuf {
     let cfg = BinaryConfig {
@@ -67,17 +69,23 @@ impl Validator {
         is_running(&mut self.handle.child)
     }
 
+    /// Send `SIGTERM` to the validator process and wait up to `deadline` for it
+    /// to exit, killing it otherwise.
+    pub async fn terminate(&mut self, deadline: Duration) -> io::Result<NodeExit> {
+        self.handle.terminate(deadline).await
+    }
+
     /// Wait for the validator process to exit, with a timeout
     /// Returns true if the process exited within the timeout, false otherwise
     pub async fn wait_for_exit(&mut self, timeout: Duration) -> bool {
         self.handle.wait_for_exit(timeout).await
     }
 
-    pub async fn spawn(config: Config) -> Result<Self, Elapsed> {
+    pub async fn spawn(config: Config) -> Result<Self, SpawnNodeError> {
         let handle = spawn_node(
             config,
             LOGS_PREFIX,
-            "validator.yaml",
+            CONFIG_FILENAME,
             binary_path(),
             !*IS_DEBUG_TRACING,
         )
@@ -87,6 +95,16 @@ impl Validator {
 
         Ok(Self { handle })
     }
+
+    /// Kill the validator process and start it again from its existing
+    /// directory and config, so it keeps its database and blobs.
+    pub async fn respawn(&mut self) -> Result<(), SpawnNodeError> {
+        self.handle.respawn(binary_path(), CONFIG_FILENAME).await?;
+
+        info!("validator respawned and ready");
+
+        Ok(())
+    }
 }
 
 impl NodeConfigCommon for Config {
@@ -95,13 +113,10 @@ impl NodeConfigCommon for Config {
     }
 
     fn set_paths(&mut self, base: &std::path::Path) {
-        self.storage.db_path = base.join("db");
-        base.clone_into(
-            &mut self
-                .da_verifier
-                .storage_adapter_settings
-                .blob_storage_directory,
-        );
+        self.storage.db_path = base.join(NODE_DB_DIR);
+        self.da_verifier
+            .storage_adapter_settings
+            .blob_storage_directory = base.join(NODE_BLOBS_DIR);
     }
 
     fn addresses(&self) -> (std::net::SocketAddr, Option<std::net::SocketAddr>) {
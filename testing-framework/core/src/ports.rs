@@ -0,0 +1,435 @@
+use std::{
+    collections::HashSet,
+    env, fmt, io,
+    net::{Ipv4Addr, TcpListener, UdpSocket},
+    ops::RangeInclusive,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use thiserror::Error;
+use tracing::{debug, warn};
+
+/// Restricts [`PortAllocator::global`] to an inclusive range, e.g.
+/// `NOMOS_PORT_RANGE=20000-29999`. Ports are then handed out in order, so
+/// runs are reproducible and concurrent jobs on one host can be given
+/// disjoint ranges.
+pub const PORT_RANGE_ENV: &str = "NOMOS_PORT_RANGE";
+
+/// Attempts at drawing an OS-assigned port that was not handed out before.
+const EPHEMERAL_ATTEMPTS: usize = 64;
+
+static GLOBAL: LazyLock<PortAllocator> = LazyLock::new(|| {
+    let Ok(raw) = env::var(PORT_RANGE_ENV) else {
+        return PortAllocator::new();
+    };
+    match parse_range(&raw) {
+        Some(range) => PortAllocator::with_range(range),
+        None => {
+            warn!(
+                value = raw,
+                "ignoring invalid {PORT_RANGE_ENV}, expected `<start>-<end>`"
+            );
+            PortAllocator::new()
+        }
+    }
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PortProtocol {
+    Tcp,
+    Udp,
+}
+
+impl fmt::Display for PortProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Tcp => "tcp",
+            Self::Udp => "udp",
+        })
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum PortError {
+    #[error("failed to bind {protocol} port {port}: {source}")]
+    Bind {
+        protocol: PortProtocol,
+        port: u16,
+        #[source]
+        source: io::Error,
+    },
+    #[error("no free {protocol} port left to reserve")]
+    Exhausted { protocol: PortProtocol },
+}
+
+/// A port held by an open socket until it is handed to its user.
+///
+/// Release the socket as late as possible: [`Self::release`] right before
+/// the process or container that listens on the port starts, or
+/// [`Self::into_tcp_listener`] to keep listening in this process.
+#[derive(Debug)]
+pub struct PortReservation {
+    port: u16,
+    protocol: PortProtocol,
+    guard: Option<Guard>,
+}
+
+#[derive(Debug)]
+enum Guard {
+    Tcp(TcpListener),
+    Udp(UdpSocket),
+}
+
+impl PortReservation {
+    /// A port that is not backed by a socket, e.g. a fixed default that
+    /// could not be bound.
+    #[must_use]
+    pub const fn unguarded(protocol: PortProtocol, port: u16) -> Self {
+        Self {
+            port,
+            protocol,
+            guard: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    #[must_use]
+    pub const fn protocol(&self) -> PortProtocol {
+        self.protocol
+    }
+
+    /// Close the socket holding the port and return it for handoff.
+    #[must_use]
+    pub fn release(self) -> u16 {
+        self.port
+    }
+
+    /// The bound listener of a TCP reservation, for serving the port from
+    /// this process.
+    #[must_use]
+    pub fn into_tcp_listener(self) -> Option<TcpListener> {
+        match self.guard {
+            Some(Guard::Tcp(listener)) => Some(listener),
+            _ => None,
+        }
+    }
+}
+
+/// Reservations held together until their ports are handed off, e.g. every
+/// port of a generated topology until its nodes are spawned. Clones share the
+/// reservations, which are released by [`Self::release`] or once the last
+/// clone is dropped.
+#[derive(Clone, Debug, Default)]
+pub struct HeldPorts {
+    reservations: Arc<Mutex<Vec<PortReservation>>>,
+}
+
+impl HeldPorts {
+    /// Keep `reservation` until the held ports are released.
+    pub fn hold(&self, reservation: PortReservation) -> u16 {
+        let port = reservation.port();
+        self.reservations
+            .lock()
+            .expect("held ports lock poisoned")
+            .push(reservation);
+        port
+    }
+
+    /// Close every held socket.
+    pub fn release(&self) {
+        let released =
+            std::mem::take(&mut *self.reservations.lock().expect("held ports lock poisoned"));
+        if !released.is_empty() {
+            debug!(ports = released.len(), "released held ports");
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.reservations
+            .lock()
+            .expect("held ports lock poisoned")
+            .len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Hands out ports that are free on the host and unique within the process.
+///
+/// Every reservation binds the port on all IPv4 interfaces, so ports taken
+/// by other processes (or published by Docker) are skipped. Released ports
+/// stay claimed, so two topologies generated by concurrent tests never share
+/// a port even when the OS would recycle it. Without a range the OS picks the
+/// ports and they stay claimed for the rest of the process; with one they are
+/// tried in order from its start and claims are dropped each time the range
+/// wraps around, so a long-lived process does not run out of ports.
+#[derive(Debug)]
+pub struct PortAllocator {
+    range: Option<RangeInclusive<u16>>,
+    state: Mutex<AllocatorState>,
+}
+
+#[derive(Debug, Default)]
+struct AllocatorState {
+    cursor: u16,
+    claimed: HashSet<(PortProtocol, u16)>,
+}
+
+impl Default for PortAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PortAllocator {
+    /// The process-wide allocator, configured by [`PORT_RANGE_ENV`].
+    #[must_use]
+    pub fn global() -> &'static Self {
+        &GLOBAL
+    }
+
+    /// An allocator handing out OS-assigned ports.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            range: None,
+            state: Mutex::new(AllocatorState::default()),
+        }
+    }
+
+    /// An allocator handing out ports of `range` in order.
+    #[must_use]
+    pub fn with_range(range: RangeInclusive<u16>) -> Self {
+        let cursor = *range.start();
+        Self {
+            range: Some(range),
+            state: Mutex::new(AllocatorState {
+                cursor,
+                claimed: HashSet::new(),
+            }),
+        }
+    }
+
+    /// Reserve a free port.
+    pub fn reserve(&self, protocol: PortProtocol) -> Result<PortReservation, PortError> {
+        let mut state = self.state.lock().expect("port allocator lock poisoned");
+        let reservation = match &self.range {
+            Some(range) => reserve_in_range(&mut state, range, protocol),
+            None => reserve_ephemeral(&state, protocol),
+        }?;
+        state.claimed.insert((protocol, reservation.port));
+        debug!(%protocol, port = reservation.port, "reserved port");
+        Ok(reservation)
+    }
+
+    /// Reserve `count` distinct free ports.
+    pub fn reserve_many(
+        &self,
+        protocol: PortProtocol,
+        count: usize,
+    ) -> Result<Vec<PortReservation>, PortError> {
+        (0..count).map(|_| self.reserve(protocol)).collect()
+    }
+
+    /// Reserve `port` itself, inside the allocator's range or not, failing
+    /// when it is in use. Meant for well-known ports a run asks for by number,
+    /// so a port handed out before is not refused once it is free again.
+    pub fn reserve_exact(
+        &self,
+        protocol: PortProtocol,
+        port: u16,
+    ) -> Result<PortReservation, PortError> {
+        let mut state = self.state.lock().expect("port allocator lock poisoned");
+        let reservation = bind(protocol, port)?;
+        state.claimed.insert((protocol, port));
+        debug!(%protocol, port, "reserved port");
+        Ok(reservation)
+    }
+}
+
+fn reserve_in_range(
+    state: &mut AllocatorState,
+    range: &RangeInclusive<u16>,
+    protocol: PortProtocol,
+) -> Result<PortReservation, PortError> {
+    let len = usize::from(range.end() - range.start()) + 1;
+    for _ in 0..len {
+        let port = state.cursor;
+        state.cursor = if port >= *range.end() {
+            state
+                .claimed
+                .retain(|(_, claimed)| !range.contains(claimed));
+            *range.start()
+        } else {
+            port + 1
+        };
+        if state.claimed.contains(&(protocol, port)) {
+            continue;
+        }
+        if let Ok(reservation) = bind(protocol, port) {
+            return Ok(reservation);
+        }
+    }
+    Err(PortError::Exhausted { protocol })
+}
+
+fn reserve_ephemeral(
+    state: &AllocatorState,
+    protocol: PortProtocol,
+) -> Result<PortReservation, PortError> {
+    // Hold recycled ports until a fresh one turns up, so the OS cannot hand
+    // the same one back on the next attempt.
+    let mut recycled = Vec::new();
+    for _ in 0..EPHEMERAL_ATTEMPTS {
+        let reservation = bind(protocol, 0)?;
+        if !state.claimed.contains(&(protocol, reservation.port)) {
+            return Ok(reservation);
+        }
+        recycled.push(reservation);
+    }
+    Err(PortError::Exhausted { protocol })
+}
+
+fn bind(protocol: PortProtocol, port: u16) -> Result<PortReservation, PortError> {
+    let error = |source| PortError::Bind {
+        protocol,
+        port,
+        source,
+    };
+    let (guard, port) = match protocol {
+        PortProtocol::Tcp => {
+            let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).map_err(error)?;
+            let port = listener.local_addr().map_err(error)?.port();
+            (Guard::Tcp(listener), port)
+        }
+        PortProtocol::Udp => {
+            let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)).map_err(error)?;
+            let port = socket.local_addr().map_err(error)?.port();
+            (Guard::Udp(socket), port)
+        }
+    };
+    Ok(PortReservation {
+        port,
+        protocol,
+        guard: Some(guard),
+    })
+}
+
+fn parse_range(raw: &str) -> Option<RangeInclusive<u16>> {
+    let (start, end) = raw.trim().split_once('-')?;
+    let start: u16 = start.trim().parse().ok()?;
+    let end: u16 = end.trim().parse().ok()?;
+    (start > 0 && start <= end).then_some(start..=end)
+}
+
+/// A free TCP port from the global allocator, released for immediate
+/// handoff. Prefer holding the reservation in [`HeldPorts`] until the port's
+/// user starts.
+///
+/// # Panics
+///
+/// When no port can be reserved.
+#[must_use]
+pub fn allocate_tcp_port() -> u16 {
+    allocate(PortProtocol::Tcp)
+}
+
+/// A free UDP port from the global allocator, released for immediate
+/// handoff.
+///
+/// # Panics
+///
+/// When no port can be reserved.
+#[must_use]
+pub fn allocate_udp_port() -> u16 {
+    allocate(PortProtocol::Udp)
+}
+
+fn allocate(protocol: PortProtocol) -> u16 {
+    PortAllocator::global()
+        .reserve(protocol)
+        .unwrap_or_else(|err| panic!("failed to allocate a port: {err}"))
+        .release()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A range of `len` ports from one reserved by the OS, most likely free.
+    fn free_range(len: u16) -> RangeInclusive<u16> {
+        let probe = bind(PortProtocol::Tcp, 0).expect("probe port");
+        let start = probe.port().clamp(1024, u16::MAX - len);
+        start..=start + len - 1
+    }
+
+    #[test]
+    fn parses_inclusive_ranges() {
+        assert_eq!(parse_range("20000-29999"), Some(20000..=29999));
+        assert_eq!(parse_range(" 7 - 7 "), Some(7..=7));
+        assert_eq!(parse_range("0-10"), None);
+        assert_eq!(parse_range("10-9"), None);
+        assert_eq!(parse_range("10"), None);
+        assert_eq!(parse_range("10-70000"), None);
+    }
+
+    #[test]
+    fn range_hands_out_ports_in_order_and_wraps() {
+        let range = free_range(3);
+        let allocator = PortAllocator::with_range(range.clone());
+        let start = *range.start();
+
+        let ports = (0..3)
+            .map(|_| {
+                allocator
+                    .reserve(PortProtocol::Tcp)
+                    .expect("port in range")
+                    .release()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ports, vec![start, start + 1, start + 2]);
+
+        // Wrapping drops the claims, so the released ports come around again.
+        let again = allocator.reserve(PortProtocol::Tcp).expect("wrapped port");
+        assert_eq!(again.port(), start);
+    }
+
+    #[test]
+    fn held_range_ports_are_not_handed_out_twice() {
+        let range = free_range(2);
+        let allocator = PortAllocator::with_range(range);
+        let held = HeldPorts::default();
+
+        held.hold(allocator.reserve(PortProtocol::Udp).expect("first port"));
+        held.hold(allocator.reserve(PortProtocol::Udp).expect("second port"));
+        assert!(matches!(
+            allocator.reserve(PortProtocol::Udp),
+            Err(PortError::Exhausted { .. })
+        ));
+
+        held.release();
+        assert!(held.is_empty());
+        assert!(allocator.reserve(PortProtocol::Udp).is_ok());
+    }
+
+    #[test]
+    fn released_ephemeral_ports_stay_claimed() {
+        let allocator = PortAllocator::new();
+        let mut ports = HashSet::new();
+        for _ in 0..32 {
+            let port = allocator
+                .reserve(PortProtocol::Tcp)
+                .expect("ephemeral port")
+                .release();
+            assert!(ports.insert(port), "port {port} handed out twice");
+        }
+    }
+}
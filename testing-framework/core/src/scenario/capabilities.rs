@@ -1,6 +1,10 @@
+use std::{fmt, time::Duration};
+
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 use super::DynError;
+use crate::{nodes::ApiClient, topology::generation::NodeRole};
 
 /// Marker type used by scenario builders to request node control support.
 #[derive(Clone, Copy, Debug, Default)]
@@ -20,10 +24,246 @@ impl RequiresNodeControl for NodeControlCapability {
     const REQUIRED: bool = true;
 }
 
+/// Identifies a single node in the generated topology by role and index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NodeTarget {
+    Validator(usize),
+    Executor(usize),
+}
+
+impl NodeTarget {
+    /// Parses a label in the `validator-<index>` / `executor-<index>` form
+    /// produced by [`Display`](fmt::Display).
+    #[must_use]
+    pub fn from_label(label: &str) -> Option<Self> {
+        let (role, index) = label.rsplit_once('-')?;
+        let index = index.parse().ok()?;
+        match role {
+            "validator" => Some(Self::Validator(index)),
+            "executor" => Some(Self::Executor(index)),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub const fn role(self) -> NodeRole {
+        match self {
+            Self::Validator(_) => NodeRole::Validator,
+            Self::Executor(_) => NodeRole::Executor,
+        }
+    }
+
+    #[must_use]
+    pub const fn index(self) -> usize {
+        match self {
+            Self::Validator(index) | Self::Executor(index) => index,
+        }
+    }
+}
+
+impl fmt::Display for NodeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Validator(index) => write!(f, "validator-{index}"),
+            Self::Executor(index) => write!(f, "executor-{index}"),
+        }
+    }
+}
+
+/// Traffic shaping applied to a node's network interface.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkShaping {
+    /// Added one-way delay for outgoing packets.
+    pub latency: Duration,
+    /// Random variation applied on top of `latency`.
+    pub jitter: Duration,
+    /// Percentage of outgoing packets to drop (0.0 - 100.0).
+    pub loss_percent: f64,
+}
+
+impl NetworkShaping {
+    /// Returns `true` when the profile does not alter traffic.
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        self.latency.is_zero() && self.jitter.is_zero() && self.loss_percent <= 0.0
+    }
+}
+
+/// How a node exited after [`NodeControlHandle::terminate_node`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeExit {
+    /// Time from sending `SIGTERM` until the node exited (or was killed).
+    pub elapsed: Duration,
+    /// Exit code, `None` when the process was ended by a signal.
+    pub exit_code: Option<i32>,
+    /// The node was still running at the deadline and had to be killed.
+    pub timed_out: bool,
+}
+
+/// Output of a command run with [`NodeControlHandle::exec`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecOutput {
+    /// Exit code, `None` when the runner could not determine it.
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+impl ExecOutput {
+    #[must_use]
+    pub const fn success(&self) -> bool {
+        matches!(self.exit_code, Some(0))
+    }
+}
+
+/// Node started by [`NodeControlHandle::add_node`] while the scenario runs.
+#[derive(Clone)]
+pub struct AddedNode {
+    pub target: NodeTarget,
+    pub client: ApiClient,
+}
+
 /// Interface exposed by runners that can restart nodes at runtime.
 #[async_trait]
 pub trait NodeControlHandle: Send + Sync {
     async fn restart_validator(&self, index: usize) -> Result<(), DynError>;
 
     async fn restart_executor(&self, index: usize) -> Result<(), DynError>;
+
+    /// Stop a validator and keep it offline until
+    /// [`NodeControlHandle::start_validator`] is called.
+    async fn stop_validator(&self, index: usize) -> Result<(), DynError> {
+        let _ = index;
+        Err("stopping nodes is not supported by this runner".into())
+    }
+
+    /// Start a validator previously stopped with
+    /// [`NodeControlHandle::stop_validator`], keeping its state.
+    async fn start_validator(&self, index: usize) -> Result<(), DynError> {
+        let _ = index;
+        Err("starting nodes is not supported by this runner".into())
+    }
+
+    /// Stop an executor and keep it offline until
+    /// [`NodeControlHandle::start_executor`] is called.
+    async fn stop_executor(&self, index: usize) -> Result<(), DynError> {
+        let _ = index;
+        Err("stopping nodes is not supported by this runner".into())
+    }
+
+    /// Start an executor previously stopped with
+    /// [`NodeControlHandle::stop_executor`], keeping its state.
+    async fn start_executor(&self, index: usize) -> Result<(), DynError> {
+        let _ = index;
+        Err("starting nodes is not supported by this runner".into())
+    }
+
+    /// Kill a validator with `SIGKILL`, leaving it down until
+    /// [`NodeControlHandle::start_validator`] is called. Unlike
+    /// [`NodeControlHandle::stop_validator`] the node gets no chance to flush
+    /// or close its storage.
+    async fn kill_validator(&self, index: usize) -> Result<(), DynError> {
+        let _ = index;
+        Err("killing nodes is not supported by this runner".into())
+    }
+
+    /// Kill an executor with `SIGKILL`, leaving it down until
+    /// [`NodeControlHandle::start_executor`] is called.
+    async fn kill_executor(&self, index: usize) -> Result<(), DynError> {
+        let _ = index;
+        Err("killing nodes is not supported by this runner".into())
+    }
+
+    /// Send `SIGTERM` to a node and wait up to `deadline` for it to exit,
+    /// killing it if it does not. The node stays down for the rest of the
+    /// run.
+    async fn terminate_node(
+        &self,
+        target: NodeTarget,
+        deadline: Duration,
+    ) -> Result<NodeExit, DynError> {
+        let _ = (target, deadline);
+        Err("terminating nodes is not supported by this runner".into())
+    }
+
+    /// Split the network so nodes can only reach peers within their own group.
+    ///
+    /// Nodes not listed in any group keep their connectivity untouched.
+    /// Runners without network control return an error.
+    async fn partition(&self, groups: &[Vec<NodeTarget>]) -> Result<(), DynError> {
+        let _ = groups;
+        Err("network partitions are not supported by this runner".into())
+    }
+
+    /// Undo a previous [`NodeControlHandle::partition`] call.
+    async fn heal_partition(&self) -> Result<(), DynError> {
+        Err("network partitions are not supported by this runner".into())
+    }
+
+    /// Freeze every process of a node (`docker pause`) without stopping it.
+    /// The node keeps its connections and state but neither sends nor
+    /// answers anything until [`NodeControlHandle::unpause_node`].
+    async fn pause_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        let _ = target;
+        Err("pausing nodes is not supported by this runner".into())
+    }
+
+    /// Resume a node frozen with [`NodeControlHandle::pause_node`].
+    async fn unpause_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        let _ = target;
+        Err("pausing nodes is not supported by this runner".into())
+    }
+
+    /// Apply latency, jitter and packet loss to a node's outgoing traffic,
+    /// replacing any shaping previously applied to it.
+    async fn shape_network(
+        &self,
+        target: NodeTarget,
+        shaping: NetworkShaping,
+    ) -> Result<(), DynError> {
+        let _ = (target, shaping);
+        Err("network shaping is not supported by this runner".into())
+    }
+
+    /// Remove any traffic shaping applied to a node.
+    async fn clear_network_shaping(&self, target: NodeTarget) -> Result<(), DynError> {
+        let _ = target;
+        Err("network shaping is not supported by this runner".into())
+    }
+
+    /// Start a node the deployer held back because the topology declared it
+    /// as late-joining (see
+    /// [`TopologyBuilder::with_late_join`](crate::topology::config::TopologyBuilder::with_late_join)).
+    async fn join_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        let _ = target;
+        Err("late-joining nodes are not supported by this runner".into())
+    }
+
+    /// Start an extra node of `role` next to the deployed topology, taking
+    /// the next free index of its role.
+    ///
+    /// The node gets a config minted by cfgsync from the running cluster's
+    /// genesis. It is not part of genesis, so it syncs and relays but does
+    /// not lead blocks or serve DA subnetworks. It is not added to
+    /// [`RunContext::node_clients`](super::RunContext::node_clients); use the
+    /// returned client instead.
+    async fn add_node(&self, role: NodeRole) -> Result<AddedNode, DynError> {
+        let _ = role;
+        Err("adding nodes is not supported by this runner".into())
+    }
+
+    /// Stop a node and remove it from the cluster for the rest of the run.
+    async fn remove_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        let _ = target;
+        Err("removing nodes is not supported by this runner".into())
+    }
+
+    /// Run `command` inside the node's container, without a shell, and
+    /// capture its output. A command that runs but exits non-zero is not an
+    /// error; check [`ExecOutput::success`]. Wrap the command in
+    /// `["sh", "-c", ...]` for pipes or redirections.
+    async fn exec(&self, target: NodeTarget, command: &[&str]) -> Result<ExecOutput, DynError> {
+        let _ = (target, command);
+        Err("executing commands in nodes is not supported by this runner".into())
+    }
 }
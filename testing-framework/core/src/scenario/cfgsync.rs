@@ -2,33 +2,54 @@ use std::{fs::File, num::NonZero, path::Path, time::Duration};
 
 use anyhow::{Context as _, Result};
 use nomos_da_network_core::swarm::ReplicationConfig;
-use nomos_tracing_service::TracingSettings;
+use nomos_tracing::tracing::otlp::OtlpTracingConfig;
+use nomos_tracing_service::{MetricsLayer, TracingLayer, TracingSettings};
 use nomos_utils::bounded_duration::{MinimalBoundedDuration, SECOND};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use tracing::debug;
 
 use crate::{
-    constants::kzg_container_path,
-    topology::{configs::wallet::WalletConfig, generation::GeneratedTopology},
+    constants::{cfgsync_port, kzg_container_path},
+    scenario::NodeTarget,
+    topology::{
+        configs::{
+            consensus::DEFAULT_SESSION_DURATION, genesis::GenesisCustomization, network::IpStack,
+            wallet::WalletConfig,
+        },
+        generation::GeneratedTopology,
+    },
 };
 
+/// Registration window of cfgsync configs built without a template.
+const SNAPSHOT_CFGSYNC_TIMEOUT_SECS: u64 = 300;
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CfgSyncConfig {
     pub port: u16,
     pub n_hosts: usize,
+    #[serde(default)]
+    pub expected_hosts: Option<Vec<String>>,
     pub timeout: u64,
+    #[serde(default)]
+    pub accept_late_hosts: bool,
     pub security_param: NonZero<u32>,
     pub active_slot_coeff: f64,
+    #[serde(default = "default_session_duration")]
+    pub session_duration: u64,
     #[serde(default)]
     pub wallet: WalletConfig,
     #[serde(default)]
+    pub genesis: GenesisCustomization,
+    #[serde(default)]
     pub ids: Option<Vec<[u8; 32]>>,
     #[serde(default)]
     pub da_ports: Option<Vec<u16>>,
     #[serde(default)]
     pub blend_ports: Option<Vec<u16>>,
+    #[serde(default)]
+    pub ip_stack: IpStack,
     pub subnetwork_size: usize,
     pub dispersal_factor: usize,
     pub num_samples: u16,
@@ -71,29 +92,127 @@ pub fn render_cfgsync_yaml(cfg: &CfgSyncConfig) -> Result<String> {
     serde_yaml::to_string(&serializable).context("rendering cfgsync yaml")
 }
 
+/// Export every node span over OTLP to `endpoint`. cfgsync replaces the
+/// service name with each node's identifier.
+pub fn enable_otlp_tracing(cfg: &mut CfgSyncConfig, endpoint: &str) -> Result<()> {
+    let endpoint = endpoint
+        .parse()
+        .with_context(|| format!("invalid OTLP endpoint {endpoint}"))?;
+    cfg.tracing_settings.tracing = TracingLayer::Otlp(OtlpTracingConfig {
+        endpoint,
+        sample_ratio: 1.0,
+        service_name: "node".to_owned(),
+    });
+    Ok(())
+}
+
+const fn default_session_duration() -> u64 {
+    DEFAULT_SESSION_DURATION
+}
+
+/// Stop nodes pushing OTLP metrics, for stacks without a collector.
+pub fn disable_otlp_metrics(cfg: &mut CfgSyncConfig) {
+    cfg.tracing_settings.metrics = MetricsLayer::None;
+}
+
+/// Push OTLP metrics to `host`, keeping the template's scheme, port and path;
+/// for stacks whose collector is not reachable as `prometheus`.
+pub fn set_otlp_metrics_host(cfg: &mut CfgSyncConfig, host: &str) -> Result<()> {
+    if let MetricsLayer::Otlp(config) = &mut cfg.tracing_settings.metrics {
+        config
+            .endpoint
+            .set_host(Some(host))
+            .with_context(|| format!("invalid OTLP metrics host {host}"))?;
+    }
+    Ok(())
+}
+
+/// Cfgsync config serving `topology` without a template, e.g. for exported
+/// snapshots. Tracing follows the first node's settings.
+#[must_use]
+pub fn cfgsync_config_for_topology(topology: &GeneratedTopology) -> CfgSyncConfig {
+    let consensus = &topology.config().consensus_params;
+    let da = &topology.config().da_params;
+    let mut cfg = CfgSyncConfig {
+        port: cfgsync_port(),
+        n_hosts: 0,
+        expected_hosts: None,
+        timeout: SNAPSHOT_CFGSYNC_TIMEOUT_SECS,
+        accept_late_hosts: false,
+        security_param: consensus.security_param,
+        active_slot_coeff: consensus.active_slot_coeff,
+        session_duration: consensus.session_duration,
+        wallet: WalletConfig::default(),
+        genesis: GenesisCustomization::default(),
+        ids: None,
+        da_ports: None,
+        blend_ports: None,
+        ip_stack: IpStack::default(),
+        subnetwork_size: da.subnetwork_size,
+        dispersal_factor: da.dispersal_factor,
+        num_samples: da.num_samples,
+        num_subnets: da.num_subnets,
+        old_blobs_check_interval: da.old_blobs_check_interval,
+        blobs_validity_duration: da.blobs_validity_duration,
+        global_params_path: da.global_params_path.clone(),
+        min_dispersal_peers: da.policy_settings.min_dispersal_peers,
+        min_replication_peers: da.policy_settings.min_replication_peers,
+        monitor_failure_time_window: da.monitor_settings.failure_time_window,
+        balancer_interval: da.balancer_interval,
+        replication_settings: da.replication_settings,
+        retry_shares_limit: da.retry_shares_limit,
+        retry_commitments_limit: da.retry_commitments_limit,
+        tracing_settings: topology
+            .nodes()
+            .next()
+            .map(|node| node.general.tracing_config.tracing_settings.clone())
+            .unwrap_or_default(),
+    };
+    apply_topology_overrides(&mut cfg, topology, false, false);
+    cfg
+}
+
+/// Point `cfg` at `topology`. `node_control` is whether the scenario may add
+/// or restart nodes while it runs.
 pub fn apply_topology_overrides(
     cfg: &mut CfgSyncConfig,
     topology: &GeneratedTopology,
     use_kzg_mount: bool,
+    node_control: bool,
 ) {
     debug!(
         validators = topology.validators().len(),
         executors = topology.executors().len(),
         use_kzg_mount,
+        node_control,
         "applying topology overrides to cfgsync config"
     );
     let hosts = topology.validators().len() + topology.executors().len();
     cfg.n_hosts = hosts;
+    cfg.expected_hosts = Some(
+        (0..topology.validators().len())
+            .map(NodeTarget::Validator)
+            .chain((0..topology.executors().len()).map(NodeTarget::Executor))
+            .map(|target| target.to_string())
+            .collect(),
+    );
+    // Serves late-joining nodes, nodes added through node control, and nodes
+    // whose entrypoint runs cfgsync again after a restart. Without either,
+    // every host registers in the initial round.
+    cfg.accept_late_hosts = node_control || !topology.late_joins().is_empty();
 
     let consensus = &topology.config().consensus_params;
     cfg.security_param = consensus.security_param;
     cfg.active_slot_coeff = consensus.active_slot_coeff;
+    cfg.session_duration = consensus.session_duration;
+    cfg.genesis = consensus.genesis.clone();
 
     let config = topology.config();
     cfg.wallet = config.wallet_config.clone();
     cfg.ids = Some(topology.nodes().map(|node| node.id).collect());
     cfg.da_ports = Some(topology.nodes().map(|node| node.da_port).collect());
     cfg.blend_ports = Some(topology.nodes().map(|node| node.blend_port).collect());
+    cfg.ip_stack = config.network_params.ip_stack;
 
     let da = &config.da_params;
     cfg.subnetwork_size = da.subnetwork_size;
@@ -123,16 +242,24 @@ pub fn apply_topology_overrides(
 struct SerializableCfgSyncConfig {
     port: u16,
     n_hosts: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_hosts: Option<Vec<String>>,
     timeout: u64,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    accept_late_hosts: bool,
     security_param: NonZero<u32>,
     active_slot_coeff: f64,
+    session_duration: u64,
     wallet: WalletConfig,
+    #[serde(skip_serializing_if = "GenesisCustomization::is_empty")]
+    genesis: GenesisCustomization,
     #[serde(skip_serializing_if = "Option::is_none")]
     ids: Option<Vec<[u8; 32]>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     da_ports: Option<Vec<u16>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     blend_ports: Option<Vec<u16>>,
+    ip_stack: IpStack,
     subnetwork_size: usize,
     dispersal_factor: usize,
     num_samples: u16,
@@ -159,13 +286,18 @@ impl From<&CfgSyncConfig> for SerializableCfgSyncConfig {
         Self {
             port: cfg.port,
             n_hosts: cfg.n_hosts,
+            expected_hosts: cfg.expected_hosts.clone(),
             timeout: cfg.timeout,
+            accept_late_hosts: cfg.accept_late_hosts,
             security_param: cfg.security_param,
             active_slot_coeff: cfg.active_slot_coeff,
+            session_duration: cfg.session_duration,
             wallet: cfg.wallet.clone(),
+            genesis: cfg.genesis.clone(),
             ids: cfg.ids.clone(),
             da_ports: cfg.da_ports.clone(),
             blend_ports: cfg.blend_ports.clone(),
+            ip_stack: cfg.ip_stack,
             subnetwork_size: cfg.subnetwork_size,
             dispersal_factor: cfg.dispersal_factor,
             num_samples: cfg.num_samples,
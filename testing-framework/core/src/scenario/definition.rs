@@ -1,18 +1,39 @@
-use std::{num::NonZeroUsize, sync::Arc, time::Duration};
+use std::{
+    num::{NonZeroU32, NonZeroU64, NonZeroUsize},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
+use nomos_core::sdp::ServiceType;
+use nomos_libp2p::Multiaddr;
 use tracing::{debug, info};
 
 use super::{
-    NodeControlCapability, expectation::Expectation, runtime::context::RunMetrics,
+    ApiFaults, CheckpointPolicy, NodeControlCapability, NodeEnv, NodeFeature, NodeTarget, Profile,
+    ScenarioTimings, TagFilter,
+    expectation::{Expectation, SoftExpectation},
+    runtime::context::RunMetrics,
     workload::Workload,
 };
 use crate::topology::{
-    config::{TopologyBuilder, TopologyConfig},
-    configs::{network::Libp2pNetworkLayout, wallet::WalletConfig},
-    generation::GeneratedTopology,
+    config::{
+        ConsensusPreset, LateJoin, NetworkRegions, NodeResources, TopologyBuilder, TopologyConfig,
+        TopologyResources,
+    },
+    configs::{
+        consensus::ConsensusParams,
+        da::DaParams,
+        genesis::{GenesisDeclaration, GenesisInscription},
+        network::{IpStack, Libp2pNetworkLayout},
+        wallet::{TokenDistribution, WalletConfig},
+    },
+    generation::{GeneratedTopology, NodeRole},
 };
 
 const DEFAULT_FUNDS_PER_WALLET: u64 = 100;
+/// Key space of [`Builder::derived_wallets`] accounts.
+const DERIVED_WALLET_SEED: u64 = 0;
 const MIN_EXPECTATION_BLOCKS: u32 = 2;
 const MIN_EXPECTATION_FALLBACK_SECS: u64 = 10;
 
@@ -23,7 +44,18 @@ pub struct Scenario<Caps = ()> {
     workloads: Vec<Arc<dyn Workload>>,
     expectations: Vec<Box<dyn Expectation>>,
     duration: Duration,
+    run_blocks: Option<NonZeroU64>,
     capabilities: Caps,
+    report_path: Option<PathBuf>,
+    block_record_path: Option<PathBuf>,
+    timings: ScenarioTimings,
+    node_env: NodeEnv,
+    checkpoints: CheckpointPolicy,
+    api_faults: Option<ApiFaults>,
+    transaction_readiness: bool,
+    graceful_shutdown: Option<Duration>,
+    required_features: Vec<NodeFeature>,
+    profile: Option<Profile>,
 }
 
 impl<Caps> Scenario<Caps> {
@@ -32,14 +64,36 @@ impl<Caps> Scenario<Caps> {
         workloads: Vec<Arc<dyn Workload>>,
         expectations: Vec<Box<dyn Expectation>>,
         duration: Duration,
+        run_blocks: Option<NonZeroU64>,
         capabilities: Caps,
+        report_path: Option<PathBuf>,
+        block_record_path: Option<PathBuf>,
+        timings: ScenarioTimings,
+        node_env: NodeEnv,
+        checkpoints: CheckpointPolicy,
+        api_faults: Option<ApiFaults>,
+        transaction_readiness: bool,
+        graceful_shutdown: Option<Duration>,
+        required_features: Vec<NodeFeature>,
+        profile: Option<Profile>,
     ) -> Self {
         Self {
             topology,
             workloads,
             expectations,
             duration,
+            run_blocks,
             capabilities,
+            report_path,
+            block_record_path,
+            timings,
+            node_env,
+            checkpoints,
+            api_faults,
+            transaction_readiness,
+            graceful_shutdown,
+            required_features,
+            profile,
         }
     }
 
@@ -68,19 +122,113 @@ impl<Caps> Scenario<Caps> {
         self.duration
     }
 
+    #[must_use]
+    /// Number of blocks that ends the run, when it is block-bounded rather
+    /// than timed. [`Self::duration`] then holds the estimated run time.
+    pub const fn run_blocks(&self) -> Option<NonZeroU64> {
+        self.run_blocks
+    }
+
     #[must_use]
     pub const fn capabilities(&self) -> &Caps {
         &self.capabilities
     }
+
+    #[must_use]
+    /// Seed used for topology generation and workload randomness, if set.
+    pub const fn seed(&self) -> Option<u64> {
+        self.topology.seed()
+    }
+
+    #[must_use]
+    /// Where the run report is written once the scenario finishes, if
+    /// configured.
+    pub fn report_path(&self) -> Option<&Path> {
+        self.report_path.as_deref()
+    }
+
+    #[must_use]
+    /// Where the runner records the block feed during the run, if configured.
+    pub fn block_record_path(&self) -> Option<&Path> {
+        self.block_record_path.as_deref()
+    }
+
+    #[must_use]
+    /// Timeouts deployers apply while bringing the cluster up and probing it.
+    pub const fn timings(&self) -> &ScenarioTimings {
+        &self.timings
+    }
+
+    #[must_use]
+    /// Extra per-node environment variables for deployers that support them.
+    pub const fn node_env(&self) -> &NodeEnv {
+        &self.node_env
+    }
+
+    #[must_use]
+    /// When periodic expectations are checked during the run.
+    pub const fn checkpoints(&self) -> &CheckpointPolicy {
+        &self.checkpoints
+    }
+
+    #[must_use]
+    /// Faults deployers inject into the run's node API traffic, if any.
+    pub const fn api_faults(&self) -> Option<ApiFaults> {
+        self.api_faults
+    }
+
+    #[must_use]
+    /// Whether deployers wait for mempool and wallet readiness before
+    /// starting workloads.
+    pub const fn transaction_readiness(&self) -> bool {
+        self.transaction_readiness
+    }
+
+    #[must_use]
+    /// Deadline each node gets to exit after `SIGTERM` at the end of the run,
+    /// when the graceful shutdown assertion is enabled.
+    pub const fn graceful_shutdown(&self) -> Option<Duration> {
+        self.graceful_shutdown
+    }
+
+    #[must_use]
+    /// Node features the runner probes for before starting workloads: those
+    /// declared on the builder plus those of every workload and expectation.
+    pub fn required_features(&self) -> &[NodeFeature] {
+        &self.required_features
+    }
+
+    #[must_use]
+    /// Environment profile the scenario was built with, if any; deployers
+    /// fall back to it for settings they were not given explicitly.
+    pub const fn profile(&self) -> Option<Profile> {
+        self.profile
+    }
 }
 
 /// Builder used by callers to describe the desired scenario.
 pub struct Builder<Caps = ()> {
     topology: TopologyBuilder,
+    generated: Option<GeneratedTopology>,
     workloads: Vec<Arc<dyn Workload>>,
     expectations: Vec<Box<dyn Expectation>>,
+    /// Tags of each expectation, including those of the workload that
+    /// attached it.
+    expectation_tags: Vec<Vec<String>>,
     duration: Duration,
+    run_blocks: Option<NonZeroU64>,
     capabilities: Caps,
+    report_path: Option<PathBuf>,
+    block_record_path: Option<PathBuf>,
+    timings: ScenarioTimings,
+    node_env: NodeEnv,
+    checkpoints: CheckpointPolicy,
+    tag_filter: Option<TagFilter>,
+    api_faults: Option<ApiFaults>,
+    transaction_readiness: bool,
+    graceful_shutdown: Option<Duration>,
+    required_features: Vec<NodeFeature>,
+    profile: Option<Profile>,
 }
 
 pub type ScenarioBuilder = Builder<()>;
@@ -91,6 +239,13 @@ pub struct TopologyConfigurator<Caps> {
     validators: usize,
     executors: usize,
     network_star: bool,
+    ip_stack: IpStack,
+    late_joins: Vec<LateJoin>,
+    resources: TopologyResources,
+    regions: NetworkRegions,
+    provider_note_values: Vec<(NodeTarget, u64)>,
+    consensus_overrides: Vec<Box<dyn FnOnce(&mut ConsensusParams)>>,
+    da_overrides: Vec<Box<dyn FnOnce(&mut DaParams)>>,
 }
 
 impl<Caps: Default> Builder<Caps> {
@@ -99,13 +254,33 @@ impl<Caps: Default> Builder<Caps> {
     pub fn new(topology: TopologyBuilder) -> Self {
         Self {
             topology,
+            generated: None,
             workloads: Vec::new(),
             expectations: Vec::new(),
+            expectation_tags: Vec::new(),
             duration: Duration::ZERO,
+            run_blocks: None,
             capabilities: Caps::default(),
+            report_path: None,
+            block_record_path: None,
+            timings: ScenarioTimings::default(),
+            node_env: NodeEnv::default(),
+            checkpoints: CheckpointPolicy::default(),
+            tag_filter: None,
+            api_faults: None,
+            transaction_readiness: false,
+            graceful_shutdown: None,
+            required_features: Vec::new(),
+            profile: None,
         }
     }
 
+    #[must_use]
+    /// Start a builder with `profile`'s topology size and settings.
+    pub fn from_profile(profile: Profile) -> Self {
+        Self::with_node_counts(profile.validators(), profile.executors()).with_profile(profile)
+    }
+
     #[must_use]
     pub fn with_node_counts(validators: usize, executors: usize) -> Self {
         Self::new(TopologyBuilder::new(TopologyConfig::with_node_numbers(
@@ -119,6 +294,17 @@ impl<Caps: Default> Builder<Caps> {
         TopologyConfigurator::new(Self::new(TopologyBuilder::new(TopologyConfig::empty())))
     }
 
+    /// Start a builder that reuses an already generated topology, e.g. to run
+    /// a second scenario against a cluster deployed from it (see
+    /// [`crate::scenario::AttachDeployer`]). Topology and seed overrides on
+    /// the returned builder are ignored.
+    #[must_use]
+    pub fn topology_from_generated(generated: GeneratedTopology) -> Self {
+        let mut builder = Self::new(TopologyBuilder::new(generated.config().clone()));
+        builder.generated = Some(generated);
+        builder
+    }
+
     /// Configure topology via a closure and return the scenario builder.
     #[must_use]
     pub fn topology_with(
@@ -135,18 +321,46 @@ impl<Caps> Builder<Caps> {
     pub fn with_capabilities<NewCaps>(self, capabilities: NewCaps) -> Builder<NewCaps> {
         let Self {
             topology,
+            generated,
             workloads,
             expectations,
+            expectation_tags,
             duration,
+            run_blocks,
+            report_path,
+            block_record_path,
+            timings,
+            node_env,
+            checkpoints,
+            tag_filter,
+            api_faults,
+            transaction_readiness,
+            graceful_shutdown,
+            required_features,
+            profile,
             ..
         } = self;
 
         Builder {
             topology,
+            generated,
             workloads,
             expectations,
+            expectation_tags,
             duration,
+            run_blocks,
             capabilities,
+            report_path,
+            block_record_path,
+            timings,
+            node_env,
+            checkpoints,
+            tag_filter,
+            api_faults,
+            transaction_readiness,
+            graceful_shutdown,
+            required_features,
+            profile,
         }
     }
 
@@ -161,25 +375,54 @@ impl<Caps> Builder<Caps> {
     }
 
     #[must_use]
-    pub fn with_workload<W>(mut self, workload: W) -> Self
+    pub fn with_workload<W>(self, workload: W) -> Self
     where
         W: Workload + 'static,
     {
-        self.expectations.extend(workload.expectations());
-        self.workloads.push(Arc::new(workload));
+        self.with_boxed_workload(Box::new(workload))
+    }
+
+    #[must_use]
+    /// Add a workload whose type is only known at runtime, e.g. one built
+    /// from a [`super::ComponentRegistry`].
+    pub fn with_boxed_workload(mut self, workload: Box<dyn Workload>) -> Self {
+        for expectation in workload.expectations() {
+            let mut tags = owned_tags(expectation.tags());
+            tags.extend(owned_tags(workload.tags()));
+            self.expectation_tags.push(tags);
+            self.expectations.push(expectation);
+        }
+        self.workloads.push(Arc::from(workload));
         self
     }
 
     #[must_use]
     /// Add a standalone expectation not tied to a workload.
-    pub fn with_expectation<E>(mut self, expectation: E) -> Self
+    pub fn with_expectation<E>(self, expectation: E) -> Self
     where
         E: Expectation + 'static,
     {
-        self.expectations.push(Box::new(expectation));
+        self.with_boxed_expectation(Box::new(expectation))
+    }
+
+    #[must_use]
+    /// Boxed counterpart of [`Self::with_expectation`].
+    pub fn with_boxed_expectation(mut self, expectation: Box<dyn Expectation>) -> Self {
+        self.expectation_tags.push(owned_tags(expectation.tags()));
+        self.expectations.push(expectation);
         self
     }
 
+    #[must_use]
+    /// Add an expectation whose failures are reported as warnings instead of
+    /// failing the run.
+    pub fn with_soft_expectation<E>(self, expectation: E) -> Self
+    where
+        E: Expectation + 'static,
+    {
+        self.with_expectation(SoftExpectation::new(expectation))
+    }
+
     #[must_use]
     /// Configure the intended run duration.
     pub const fn with_run_duration(mut self, duration: Duration) -> Self {
@@ -187,6 +430,139 @@ impl<Caps> Builder<Caps> {
         self
     }
 
+    #[must_use]
+    /// End the run once `blocks` new blocks have been observed on the block
+    /// feed instead of after a fixed duration (panics on zero). Overrides
+    /// [`Self::with_run_duration`]; workloads are sized for the estimated
+    /// time those blocks take.
+    pub const fn run_for_blocks(mut self, blocks: u64) -> Self {
+        match NonZeroU64::new(blocks) {
+            Some(value) => self.run_blocks = Some(value),
+            None => panic!("run block count must be non-zero"),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Make the run reproducible: node IDs (and derived keys), workload
+    /// payloads and chaos scheduling are all drawn from RNGs seeded with
+    /// `seed`. Ports are still allocated from whatever is free on the host.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.topology = self.topology.with_seed(seed);
+        self
+    }
+
+    #[must_use]
+    /// Write a structured run report to `path` when the run completes. Paths
+    /// ending in `.html` produce an HTML page; anything else is JSON.
+    pub fn with_report_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.report_path = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    /// Record every block the feed observes during the run to `path` as JSON
+    /// lines, for replaying through [`super::ReplayBlockFeed`] later.
+    pub fn with_block_recording(mut self, path: impl Into<PathBuf>) -> Self {
+        self.block_record_path = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    /// Apply `profile`'s timeouts, transaction readiness and checkpoint
+    /// interval, and let deployers follow its artifact and observability
+    /// settings. Later builder calls override what the profile set.
+    pub fn with_profile(mut self, profile: Profile) -> Self {
+        self.timings = profile.timings();
+        self.transaction_readiness = profile.transaction_readiness();
+        if let Some(interval) = profile.checkpoint_interval() {
+            self.checkpoints = self.checkpoints.with_interval(interval);
+        }
+        self.profile = Some(profile);
+        self
+    }
+
+    #[must_use]
+    /// [`Self::with_profile`] with the profile named by
+    /// [`super::PROFILE_ENV`], if set.
+    pub fn with_env_profile(self) -> Self {
+        match Profile::from_env() {
+            Some(profile) => self.with_profile(profile),
+            None => self,
+        }
+    }
+
+    #[must_use]
+    /// Override the timeouts deployers use for this scenario, e.g. to scale
+    /// them up on slow CI runners.
+    pub const fn with_timings(mut self, timings: ScenarioTimings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    #[must_use]
+    /// Set an environment variable on a single node, e.g. to enable an
+    /// experimental node feature there. See [`NodeEnv`].
+    pub fn with_node_env(
+        mut self,
+        target: NodeTarget,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.node_env = self.node_env.with_var(target, key, value);
+        self
+    }
+
+    #[must_use]
+    /// Soak mode: check expectations implementing
+    /// [`super::PeriodicExpectation`] every `interval` while workloads run
+    /// (panics on zero). Outcomes land in the run report.
+    pub const fn with_checkpoint_interval(mut self, interval: Duration) -> Self {
+        self.checkpoints = self.checkpoints.with_interval(interval);
+        self
+    }
+
+    #[must_use]
+    /// Stop the run at the first failing checkpoint rather than waiting for
+    /// the final evaluation.
+    pub const fn abort_on_checkpoint_failure(mut self) -> Self {
+        self.checkpoints = self.checkpoints.abort_on_failure();
+        self
+    }
+
+    #[must_use]
+    /// Only keep workloads and expectations whose tags match `filter`.
+    /// Without one, the filter is read from [`super::TAG_FILTER_ENV`].
+    pub fn with_tag_filter(mut self, filter: TagFilter) -> Self {
+        self.tag_filter = Some(filter);
+        self
+    }
+
+    #[must_use]
+    /// Fail the run before workloads start unless the cluster offers
+    /// `features`, on top of those workloads and expectations declare.
+    pub fn require_features(mut self, features: impl IntoIterator<Item = NodeFeature>) -> Self {
+        self.required_features.extend(features);
+        self
+    }
+
+    #[must_use]
+    /// Route the run's node API clients, block feed included, through
+    /// proxies injecting `faults`.
+    pub const fn with_api_faults(mut self, faults: ApiFaults) -> Self {
+        self.api_faults = Some(faults);
+        self
+    }
+
+    #[must_use]
+    /// Have deployers wait until every node's mempool accepts requests and
+    /// its chain is past genesis, so transaction workloads don't fail their
+    /// first submissions.
+    pub const fn with_transaction_readiness(mut self, enabled: bool) -> Self {
+        self.transaction_readiness = enabled;
+        self
+    }
+
     #[must_use]
     /// Transform the topology builder.
     pub fn map_topology(mut self, f: impl FnOnce(TopologyBuilder) -> TopologyBuilder) -> Self {
@@ -211,34 +587,99 @@ impl<Caps> Builder<Caps> {
         self.with_wallet_config(wallet)
     }
 
+    #[must_use]
+    /// Seed `users` wallets with `accounts_per_user` derived accounts each,
+    /// so workloads can claim fresh accounts of a user from the
+    /// [`super::WalletPool`] instead of sharing its notes.
+    pub fn derived_wallets(self, users: usize, accounts_per_user: usize) -> Self {
+        let user_count = NonZeroUsize::new(users).expect("wallet user count must be non-zero");
+        let children = NonZeroUsize::new(accounts_per_user)
+            .expect("wallet accounts per user must be non-zero");
+        let total_funds = DEFAULT_FUNDS_PER_WALLET
+            .checked_mul(users as u64)
+            .and_then(|funds| funds.checked_mul(accounts_per_user as u64))
+            .expect("wallet count exceeds capacity");
+        let wallet = WalletConfig::derived(
+            total_funds,
+            &TokenDistribution::uniform(user_count),
+            children,
+            DERIVED_WALLET_SEED,
+        )
+        .expect("uniform funds cover every derived account");
+        self.with_wallet_config(wallet)
+    }
+
     #[must_use]
     /// Finalize the scenario, computing run metrics and initializing
     /// components.
     pub fn build(self) -> Scenario<Caps> {
         let Self {
             topology,
-            mut workloads,
-            mut expectations,
+            generated,
+            workloads,
+            expectations,
+            expectation_tags,
             duration,
+            run_blocks,
             capabilities,
-            ..
+            report_path,
+            block_record_path,
+            timings,
+            node_env,
+            checkpoints,
+            tag_filter,
+            api_faults,
+            transaction_readiness,
+            graceful_shutdown,
+            required_features,
+            profile,
         } = self;
 
-        let generated = topology.build();
+        let (mut workloads, mut expectations) = match tag_filter.or_else(TagFilter::from_env) {
+            Some(filter) => apply_tag_filter(&filter, workloads, expectations, expectation_tags),
+            None => (workloads, expectations),
+        };
+        let generated = generated.unwrap_or_else(|| topology.build());
+        let duration = run_blocks.map_or(duration, |blocks| {
+            estimated_duration_for_blocks(&generated, blocks)
+        });
         let duration = enforce_min_duration(&generated, duration);
         let run_metrics = RunMetrics::from_topology(&generated, duration);
         initialize_components(&generated, &run_metrics, &mut workloads, &mut expectations);
+        let required_features =
+            collect_required_features(required_features, &workloads, &expectations);
 
         info!(
             validators = generated.validators().len(),
             executors = generated.executors().len(),
             duration_secs = duration.as_secs(),
+            run_blocks = run_blocks.map(NonZeroU64::get),
+            seed = ?generated.seed(),
             workloads = workloads.len(),
             expectations = expectations.len(),
+            checkpoint_interval_secs = checkpoints.interval().map(|interval| interval.as_secs()),
+            profile = profile.map(Profile::name),
             "scenario built"
         );
 
-        Scenario::new(generated, workloads, expectations, duration, capabilities)
+        Scenario::new(
+            generated,
+            workloads,
+            expectations,
+            duration,
+            run_blocks,
+            capabilities,
+            report_path,
+            block_record_path,
+            timings,
+            node_env,
+            checkpoints,
+            api_faults,
+            transaction_readiness,
+            graceful_shutdown,
+            required_features,
+            profile,
+        )
     }
 }
 
@@ -249,6 +690,13 @@ impl<Caps> TopologyConfigurator<Caps> {
             validators: 0,
             executors: 0,
             network_star: false,
+            ip_stack: IpStack::V4,
+            late_joins: Vec::new(),
+            resources: TopologyResources::new(),
+            regions: NetworkRegions::new(),
+            provider_note_values: Vec::new(),
+            consensus_overrides: Vec::new(),
+            da_overrides: Vec::new(),
         }
     }
 
@@ -273,6 +721,141 @@ impl<Caps> TopologyConfigurator<Caps> {
         self
     }
 
+    /// Run nodes on IPv4, IPv6 or both.
+    #[must_use]
+    pub fn ip_stack(mut self, ip_stack: IpStack) -> Self {
+        self.ip_stack = ip_stack;
+        self
+    }
+
+    /// Hold `target` back and start it `delay` into the run; see
+    /// [`LateJoin`].
+    #[must_use]
+    pub fn late_join(mut self, target: NodeTarget, delay: Duration) -> Self {
+        self.late_joins.retain(|join| join.target != target);
+        self.late_joins.push(LateJoin { target, delay });
+        self
+    }
+
+    /// Hold validator `index` back and start it `delay` into the run.
+    #[must_use]
+    pub fn late_join_validator(self, index: usize, delay: Duration) -> Self {
+        self.late_join(NodeTarget::Validator(index), delay)
+    }
+
+    /// Container requests/limits for every `role` node; applied by the k8s
+    /// runner.
+    #[must_use]
+    pub fn resources(mut self, role: NodeRole, resources: NodeResources) -> Self {
+        self.resources.set_role(role, resources);
+        self
+    }
+
+    /// Container requests/limits for a single node, overriding its role's.
+    #[must_use]
+    pub fn node_resources(mut self, target: NodeTarget, resources: NodeResources) -> Self {
+        self.resources.set_node(target, resources);
+        self
+    }
+
+    /// Group nodes into network regions with latency between them; applied
+    /// by the compose runner.
+    #[must_use]
+    pub fn regions(mut self, regions: NetworkRegions) -> Self {
+        self.regions = regions;
+        self
+    }
+
+    /// Start from a consensus preset; later consensus settings still apply
+    /// on top of it.
+    #[must_use]
+    pub fn consensus_preset(self, preset: ConsensusPreset) -> Self {
+        self.consensus_params(move |params| preset.apply(params))
+    }
+
+    /// Blocks a node waits for before treating a block as final (panics on
+    /// zero).
+    #[must_use]
+    pub fn security_param(self, blocks: u32) -> Self {
+        let blocks = NonZeroU32::new(blocks).expect("security parameter must be non-zero");
+        self.consensus_params(move |params| params.security_param = blocks)
+    }
+
+    /// Blocks per SDP session of the DA and blend services (panics on zero).
+    /// Short sessions let a run observe membership rotation.
+    #[must_use]
+    pub fn session_duration(self, blocks: u64) -> Self {
+        assert!(blocks > 0, "session duration must be non-zero");
+        self.consensus_params(move |params| params.session_duration = blocks)
+    }
+
+    /// Probability of a slot having a leader, i.e. how densely blocks are
+    /// produced (panics outside `(0, 1]`).
+    #[must_use]
+    pub fn active_slot_coeff(self, coeff: f64) -> Self {
+        assert!(
+            coeff > 0.0 && coeff <= 1.0,
+            "active slot coefficient must be in (0, 1], got {coeff}"
+        );
+        self.consensus_params(move |params| params.active_slot_coeff = coeff)
+    }
+
+    /// Edit the consensus parameters derived from the node counts.
+    #[must_use]
+    pub fn consensus_params(mut self, f: impl FnOnce(&mut ConsensusParams) + 'static) -> Self {
+        self.consensus_overrides.push(Box::new(f));
+        self
+    }
+
+    /// Write an inscription of `data` on `channel_id` into genesis.
+    #[must_use]
+    pub fn genesis_inscription(self, channel_id: [u8; 32], data: Vec<u8>) -> Self {
+        self.consensus_params(move |params| {
+            params
+                .genesis
+                .inscriptions
+                .push(GenesisInscription { channel_id, data });
+        })
+    }
+
+    /// Declare a `service_type` provider at `locator` in genesis, backed by a
+    /// note of `note_value`; no node runs for it.
+    #[must_use]
+    pub fn genesis_declaration(
+        self,
+        service_type: ServiceType,
+        locator: Multiaddr,
+        note_value: u64,
+    ) -> Self {
+        self.consensus_params(move |params| {
+            params.genesis.declarations.push(GenesisDeclaration {
+                service_type,
+                locator,
+                note_value,
+            });
+        })
+    }
+
+    /// Value of the notes backing `target`'s DA and blend declarations in
+    /// genesis, e.g. `0` to start it under-staked.
+    #[must_use]
+    pub fn provider_note_value(mut self, target: NodeTarget, value: u64) -> Self {
+        self.provider_note_values
+            .retain(|(node, _)| *node != target);
+        self.provider_note_values.push((target, value));
+        self
+    }
+
+    /// Edit the DA parameters derived from the node counts, e.g. to shorten
+    /// `blobs_validity_duration` for pruning runs. Subnet and dispersal
+    /// settings are sized for the node counts; changing them can leave the
+    /// network unable to disperse.
+    #[must_use]
+    pub fn da_params(mut self, f: impl FnOnce(&mut DaParams) + 'static) -> Self {
+        self.da_overrides.push(Box::new(f));
+        self
+    }
+
     /// Finalize and return the underlying scenario builder.
     #[must_use]
     pub fn apply(self) -> Builder<Caps> {
@@ -286,9 +869,24 @@ impl<Caps> TopologyConfigurator<Caps> {
         if self.network_star {
             config.network_params.libp2p_network_layout = Libp2pNetworkLayout::Star;
         }
+        config.network_params.ip_stack = self.ip_stack;
+        config.late_joins = self.late_joins;
+        config.resources = self.resources;
+        config.regions = self.regions;
+        for edit in self.consensus_overrides {
+            edit(&mut config.consensus_params);
+        }
+        for edit in self.da_overrides {
+            edit(&mut config.da_params);
+        }
+
+        let mut topology = TopologyBuilder::new(config);
+        for (target, value) in self.provider_note_values {
+            topology = topology.with_provider_note_value(target, value);
+        }
 
         let mut builder = self.builder;
-        builder.topology = TopologyBuilder::new(config);
+        builder.topology = topology;
         builder
     }
 }
@@ -300,6 +898,17 @@ impl Builder<()> {
     }
 }
 
+impl Builder<NodeControlCapability> {
+    #[must_use]
+    /// Once expectations are evaluated, stop every node with `SIGTERM` and
+    /// fail the run if one does not exit within `deadline` or logs a panic
+    /// while shutting down.
+    pub const fn with_graceful_shutdown(mut self, deadline: Duration) -> Self {
+        self.graceful_shutdown = Some(deadline);
+        self
+    }
+}
+
 fn initialize_components(
     descriptors: &GeneratedTopology,
     run_metrics: &RunMetrics,
@@ -326,6 +935,71 @@ fn initialize_workloads(
     }
 }
 
+fn owned_tags(tags: &[&str]) -> Vec<String> {
+    tags.iter().map(|tag| (*tag).to_owned()).collect()
+}
+
+fn collect_required_features(
+    declared: Vec<NodeFeature>,
+    workloads: &[Arc<dyn Workload>],
+    expectations: &[Box<dyn Expectation>],
+) -> Vec<NodeFeature> {
+    let mut features = Vec::new();
+    let all = declared
+        .into_iter()
+        .chain(
+            workloads
+                .iter()
+                .flat_map(|workload| workload.required_features().to_vec()),
+        )
+        .chain(
+            expectations
+                .iter()
+                .flat_map(|expectation| expectation.required_features().to_vec()),
+        );
+    for feature in all {
+        if !features.contains(&feature) {
+            features.push(feature);
+        }
+    }
+    features
+}
+
+fn apply_tag_filter(
+    filter: &TagFilter,
+    workloads: Vec<Arc<dyn Workload>>,
+    expectations: Vec<Box<dyn Expectation>>,
+    expectation_tags: Vec<Vec<String>>,
+) -> (Vec<Arc<dyn Workload>>, Vec<Box<dyn Expectation>>) {
+    let workloads = workloads
+        .into_iter()
+        .filter(|workload| {
+            let keep = filter.matches(workload.tags());
+            if !keep {
+                info!(workload = workload.name(), tags = ?workload.tags(), "workload filtered out by tags");
+            }
+            keep
+        })
+        .collect();
+    let expectations = expectations
+        .into_iter()
+        .zip(expectation_tags)
+        .filter_map(|(expectation, tags)| {
+            if filter.matches(&tags) {
+                Some(expectation)
+            } else {
+                info!(
+                    expectation = expectation.name(),
+                    ?tags,
+                    "expectation filtered out by tags"
+                );
+                None
+            }
+        })
+        .collect();
+    (workloads, expectations)
+}
+
 fn initialize_expectations(
     descriptors: &GeneratedTopology,
     run_metrics: &RunMetrics,
@@ -342,6 +1016,21 @@ fn initialize_expectations(
     }
 }
 
+/// Expected time to produce `blocks` blocks: one block every
+/// `slot / active_slot_coeff` on average.
+fn estimated_duration_for_blocks(descriptors: &GeneratedTopology, blocks: NonZeroU64) -> Duration {
+    let Some(slot) = descriptors.slot_duration() else {
+        return Duration::from_secs(MIN_EXPECTATION_FALLBACK_SECS)
+            .saturating_mul(u32::try_from(blocks.get()).unwrap_or(u32::MAX));
+    };
+    let active_slot_coeff = descriptors
+        .config()
+        .consensus_params
+        .active_slot_coeff
+        .clamp(f64::EPSILON, 1.0);
+    slot.mul_f64(blocks.get() as f64 / active_slot_coeff)
+}
+
 fn enforce_min_duration(descriptors: &GeneratedTopology, requested: Duration) -> Duration {
     let min_duration = descriptors.slot_duration().map_or_else(
         || Duration::from_secs(MIN_EXPECTATION_FALLBACK_SECS),
@@ -0,0 +1,572 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    fs,
+    num::NonZeroU64,
+    path::Path,
+};
+
+use serde::Serialize;
+
+use super::{
+    NodeFeature, NodeTarget, ReportError, Scenario,
+    cfgsync::{cfgsync_config_for_topology, render_cfgsync_yaml},
+    runtime::context::RunMetrics,
+};
+use crate::topology::{
+    generation::{GeneratedNodeConfig, NodeRole},
+    snapshot::SnapshotPorts,
+};
+
+/// Verdict of a single dry-run check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Pass,
+    /// The scenario deploys, but likely not the way it was meant to.
+    Warn,
+    /// The scenario would fail to deploy or to run.
+    Fail,
+}
+
+/// A configuration check run by [`Scenario::dry_run`] or a deployer.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunCheck {
+    pub name: String,
+    pub status: CheckStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+/// A node the scenario would deploy.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunNode {
+    /// Node label, e.g. `validator-0`.
+    pub label: String,
+    pub role: NodeRole,
+    pub ports: SnapshotPorts,
+    /// Delay after which the node is started, for late joiners.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub late_join_after_secs: Option<f64>,
+    /// Extra environment variables set on the node.
+    pub env_vars: usize,
+}
+
+/// A workload or expectation the scenario would run.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunComponent {
+    pub name: String,
+    pub tags: Vec<String>,
+    /// What the workload would do over the run, see
+    /// [`super::Workload::plan`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<String>,
+}
+
+/// A file a deployer would render for the run.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunArtifact {
+    /// File name, e.g. `docker-compose.yml`.
+    pub name: String,
+    pub contents: String,
+}
+
+/// What a scenario would deploy and run, produced without starting any node.
+///
+/// [`Scenario::dry_run`] fills in the topology, the run plan and the checks
+/// that need nothing but the scenario; deployers extend it through
+/// [`super::Deployer::dry_run`] with the artifacts they render, the images
+/// they would pull and their own checks.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunReport {
+    /// Deployer that extended the report, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployer: Option<String>,
+    pub validators: usize,
+    pub executors: usize,
+    pub seed: Option<u64>,
+    pub profile: Option<&'static str>,
+    pub duration_secs: f64,
+    pub run_blocks: Option<u64>,
+    pub expected_blocks: u64,
+    pub checkpoint_interval_secs: Option<f64>,
+    pub required_features: Vec<NodeFeature>,
+    pub nodes: Vec<DryRunNode>,
+    pub workloads: Vec<DryRunComponent>,
+    pub expectations: Vec<DryRunComponent>,
+    pub images: Vec<String>,
+    pub artifacts: Vec<DryRunArtifact>,
+    pub checks: Vec<DryRunCheck>,
+}
+
+impl DryRunReport {
+    /// Returns `true` when no check failed. Warnings do not fail a dry run.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    /// Checks that failed.
+    pub fn failures(&self) -> impl Iterator<Item = &DryRunCheck> {
+        self.checks
+            .iter()
+            .filter(|check| check.status == CheckStatus::Fail)
+    }
+
+    /// Record a check; an `Err` detail fails it.
+    pub fn record_check(&mut self, name: impl Into<String>, result: Result<(), String>) {
+        let (status, detail) = match result {
+            Ok(()) => (CheckStatus::Pass, None),
+            Err(detail) => (CheckStatus::Fail, Some(detail)),
+        };
+        self.push_check(name.into(), status, detail);
+    }
+
+    /// Record a check that passed with a caveat.
+    pub fn record_warning(&mut self, name: impl Into<String>, detail: impl Into<String>) {
+        self.push_check(name.into(), CheckStatus::Warn, Some(detail.into()));
+    }
+
+    /// Record a file the deployer would render.
+    pub fn record_artifact(&mut self, name: impl Into<String>, contents: impl Into<String>) {
+        self.artifacts.push(DryRunArtifact {
+            name: name.into(),
+            contents: contents.into(),
+        });
+    }
+
+    /// Record the file a deployer rendered at `path` as artifact `name`. A
+    /// file that cannot be read is recorded as an `artifacts` warning.
+    pub fn record_file(&mut self, name: impl Into<String>, path: &Path) {
+        let name = name.into();
+        match fs::read_to_string(path) {
+            Ok(contents) => self.record_artifact(name, contents),
+            Err(err) => self.record_warning(
+                "artifacts",
+                format!(
+                    "failed to read rendered {name} at {}: {err}",
+                    path.display()
+                ),
+            ),
+        }
+    }
+
+    /// Record an image the deployer would run; duplicates are dropped.
+    pub fn record_image(&mut self, image: impl Into<String>) {
+        let image = image.into();
+        if !self.images.contains(&image) {
+            self.images.push(image);
+        }
+    }
+
+    #[must_use]
+    /// Name the deployer that extended the report.
+    pub fn with_deployer(mut self, deployer: impl Into<String>) -> Self {
+        self.deployer = Some(deployer.into());
+        self
+    }
+
+    /// Serialize the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, ReportError> {
+        serde_json::to_string_pretty(self).map_err(|source| ReportError::Serialize { source })
+    }
+
+    fn push_check(&mut self, name: String, status: CheckStatus, detail: Option<String>) {
+        self.checks.push(DryRunCheck {
+            name,
+            status,
+            detail,
+        });
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::new();
+        let deployer = self.deployer.as_deref().unwrap_or("scenario");
+        let _ = writeln!(
+            out,
+            "dry run ({deployer}): {} validators, {} executors, {:.0}s (~{} blocks)",
+            self.validators, self.executors, self.duration_secs, self.expected_blocks
+        );
+        if let Some(seed) = self.seed {
+            let _ = writeln!(out, "seed: {seed}");
+        }
+        if let Some(profile) = self.profile {
+            let _ = writeln!(out, "profile: {profile}");
+        }
+        if !self.required_features.is_empty() {
+            let features = self
+                .required_features
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            let _ = writeln!(out, "required features: {}", features.join(", "));
+        }
+
+        out.push_str("nodes:\n");
+        for node in &self.nodes {
+            let ports = node.ports;
+            let _ = write!(
+                out,
+                "  {}: api {}, testing {}, network {}, da {}, blend {}",
+                node.label, ports.api, ports.testing_http, ports.network, ports.da, ports.blend
+            );
+            if let Some(delay) = node.late_join_after_secs {
+                let _ = write!(out, ", joins after {delay:.0}s");
+            }
+            out.push('\n');
+        }
+
+        for (heading, components) in [
+            ("workloads", &self.workloads),
+            ("expectations", &self.expectations),
+        ] {
+            let _ = writeln!(out, "{heading}:");
+            for component in components {
+                let _ = write!(out, "  {}", component.name);
+                if !component.tags.is_empty() {
+                    let _ = write!(out, " [{}]", component.tags.join(", "));
+                }
+                if let Some(plan) = &component.plan {
+                    let _ = write!(out, ": {plan}");
+                }
+                out.push('\n');
+            }
+        }
+
+        if !self.images.is_empty() {
+            let _ = writeln!(out, "images: {}", self.images.join(", "));
+        }
+        if !self.artifacts.is_empty() {
+            out.push_str("artifacts:\n");
+            for artifact in &self.artifacts {
+                let _ = writeln!(
+                    out,
+                    "  {} ({} bytes)",
+                    artifact.name,
+                    artifact.contents.len()
+                );
+            }
+        }
+
+        out.push_str("checks:\n");
+        for check in &self.checks {
+            let status = match check.status {
+                CheckStatus::Pass => "ok",
+                CheckStatus::Warn => "warn",
+                CheckStatus::Fail => "FAIL",
+            };
+            let _ = write!(out, "  [{status}] {}", check.name);
+            if let Some(detail) = &check.detail {
+                let _ = write!(out, ": {detail}");
+            }
+            out.push('\n');
+        }
+        f.write_str(out.trim_end())
+    }
+}
+
+impl<Caps> Scenario<Caps> {
+    /// Describe what the scenario would deploy and run, and check its
+    /// configuration, without starting anything.
+    ///
+    /// Workloads and expectations were already initialized against the
+    /// topology when the scenario was built. The checks here cover what
+    /// only shows once the run starts: env overrides for missing nodes, late
+    /// joins after the run ends, port clashes and the cfgsync config. Use
+    /// [`super::Deployer::dry_run`] to also render the deployer's artifacts.
+    #[must_use]
+    pub fn dry_run(&self) -> DryRunReport {
+        let topology = self.topology();
+        let run_metrics = RunMetrics::from_topology(topology, self.duration());
+
+        let nodes = topology
+            .nodes()
+            .map(|node| {
+                let target = node_target(node);
+                DryRunNode {
+                    label: target.to_string(),
+                    role: node.role(),
+                    ports: SnapshotPorts {
+                        api: node.api_port(),
+                        testing_http: node.testing_http_port(),
+                        network: node.network_port(),
+                        da: node.da_port,
+                        blend: node.blend_port,
+                    },
+                    late_join_after_secs: topology
+                        .late_joins()
+                        .iter()
+                        .find(|join| join.target == target)
+                        .map(|join| join.delay.as_secs_f64()),
+                    env_vars: self.node_env().vars_for(target).count(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let workloads = self
+            .workloads()
+            .iter()
+            .map(|workload| DryRunComponent {
+                name: workload.name().to_owned(),
+                tags: owned_tags(workload.tags()),
+                plan: workload.plan(&run_metrics),
+            })
+            .collect();
+        let expectations = self
+            .expectations()
+            .iter()
+            .map(|expectation| DryRunComponent {
+                name: expectation.name().to_owned(),
+                tags: owned_tags(expectation.tags()),
+                plan: None,
+            })
+            .collect();
+
+        let mut report = DryRunReport {
+            deployer: None,
+            validators: topology.validators().len(),
+            executors: topology.executors().len(),
+            seed: self.seed(),
+            profile: self.profile().map(|profile| profile.name()),
+            duration_secs: self.duration().as_secs_f64(),
+            run_blocks: self.run_blocks().map(NonZeroU64::get),
+            expected_blocks: run_metrics.expected_consensus_blocks(),
+            checkpoint_interval_secs: self
+                .checkpoints()
+                .interval()
+                .map(|interval| interval.as_secs_f64()),
+            required_features: self.required_features().to_vec(),
+            nodes,
+            workloads,
+            expectations,
+            images: Vec::new(),
+            artifacts: Vec::new(),
+            checks: Vec::new(),
+        };
+        self.run_checks(&mut report);
+        report
+    }
+
+    fn run_checks(&self, report: &mut DryRunReport) {
+        let topology = self.topology();
+
+        let unknown = self
+            .node_env()
+            .targets()
+            .filter(|target| {
+                let count = match target.role() {
+                    NodeRole::Validator => topology.validators().len(),
+                    NodeRole::Executor => topology.executors().len(),
+                };
+                target.index() >= count
+            })
+            .map(|target| target.to_string())
+            .collect::<Vec<_>>();
+        report.record_check(
+            "node_env_targets",
+            if unknown.is_empty() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "env overrides target nodes outside the topology: {}",
+                    unknown.join(", ")
+                ))
+            },
+        );
+
+        let mut owners = BTreeMap::<u16, Vec<String>>::new();
+        for node in &report.nodes {
+            let ports = node.ports;
+            for port in [
+                ports.api,
+                ports.testing_http,
+                ports.network,
+                ports.da,
+                ports.blend,
+            ] {
+                owners.entry(port).or_default().push(node.label.clone());
+            }
+        }
+        let clashes = owners
+            .into_iter()
+            .filter(|(_, labels)| labels.len() > 1)
+            .map(|(port, labels)| format!("{port} ({})", labels.join(", ")))
+            .collect::<Vec<_>>();
+        report.record_check(
+            "port_allocation",
+            if clashes.is_empty() {
+                Ok(())
+            } else {
+                Err(format!(
+                    "ports bound more than once: {}",
+                    clashes.join("; ")
+                ))
+            },
+        );
+
+        let cfgsync = render_cfgsync_yaml(&cfgsync_config_for_topology(topology))
+            .map(|_| ())
+            .map_err(|err| format!("failed to render cfgsync config: {err}"));
+        report.record_check("cfgsync_config", cfgsync);
+
+        report.record_check(
+            "expected_blocks",
+            if report.expected_blocks > 0 {
+                Ok(())
+            } else {
+                Err(format!(
+                    "no block is expected within {:.0}s",
+                    report.duration_secs
+                ))
+            },
+        );
+
+        let late = topology
+            .late_joins()
+            .iter()
+            .filter(|join| join.delay >= self.duration())
+            .map(|join| join.target.to_string())
+            .collect::<Vec<_>>();
+        if late.is_empty() {
+            report.record_check("late_joins", Ok(()));
+        } else {
+            report.record_warning(
+                "late_joins",
+                format!("would join only after the run ends: {}", late.join(", ")),
+            );
+        }
+
+        if let Some(interval) = self.checkpoints().interval()
+            && interval >= self.duration()
+        {
+            report.record_warning(
+                "checkpoint_interval",
+                format!(
+                    "interval of {:.0}s is not shorter than the run, no checkpoint would run",
+                    interval.as_secs_f64()
+                ),
+            );
+        }
+
+        if self.expectations().is_empty() {
+            report.record_warning("expectations", "the scenario asserts nothing");
+        }
+    }
+}
+
+const fn node_target(node: &GeneratedNodeConfig) -> NodeTarget {
+    match node.role() {
+        NodeRole::Validator => NodeTarget::Validator(node.index()),
+        NodeRole::Executor => NodeTarget::Executor(node.index()),
+    }
+}
+
+fn owned_tags(tags: &[&'static str]) -> Vec<String> {
+    tags.iter().map(|tag| (*tag).to_owned()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::scenario::{Builder, ScenarioBuilder};
+
+    fn check<'a>(report: &'a DryRunReport, name: &str) -> &'a DryRunCheck {
+        report
+            .checks
+            .iter()
+            .find(|check| check.name == name)
+            .unwrap_or_else(|| panic!("check {name} was recorded"))
+    }
+
+    fn small() -> Builder<()> {
+        ScenarioBuilder::topology_with(|t| t.validators(2).executors(1))
+            .with_run_duration(Duration::from_secs(120))
+    }
+
+    #[test]
+    fn valid_scenario_passes_with_warnings_only() {
+        let report = small().build().dry_run();
+
+        assert!(report.passed(), "{report}");
+        assert_eq!(report.nodes.len(), 3);
+        for name in [
+            "node_env_targets",
+            "port_allocation",
+            "cfgsync_config",
+            "expected_blocks",
+            "late_joins",
+        ] {
+            assert_eq!(check(&report, name).status, CheckStatus::Pass, "{name}");
+        }
+        assert_eq!(check(&report, "expectations").status, CheckStatus::Warn);
+    }
+
+    #[test]
+    fn env_overrides_for_missing_nodes_fail() {
+        let report = small()
+            .with_node_env(NodeTarget::Executor(3), "RUST_LOG", "debug")
+            .build()
+            .dry_run();
+
+        assert!(!report.passed());
+        let failed = check(&report, "node_env_targets");
+        assert_eq!(failed.status, CheckStatus::Fail);
+        assert!(
+            failed
+                .detail
+                .as_deref()
+                .is_some_and(|detail| detail.contains("executor-3")),
+            "{failed:?}"
+        );
+        assert_eq!(report.failures().count(), 1);
+    }
+
+    #[test]
+    fn late_joins_and_checkpoints_past_the_run_warn() {
+        let report = ScenarioBuilder::topology_with(|t| {
+            t.validators(2)
+                .executors(1)
+                .late_join_validator(1, Duration::from_secs(3600))
+        })
+        .with_run_duration(Duration::from_secs(120))
+        .with_checkpoint_interval(Duration::from_secs(3600))
+        .build()
+        .dry_run();
+
+        assert!(report.passed(), "warnings do not fail a dry run");
+        let late = check(&report, "late_joins");
+        assert_eq!(late.status, CheckStatus::Warn);
+        assert!(
+            late.detail
+                .as_deref()
+                .is_some_and(|detail| detail.contains("validator-1"))
+        );
+        assert_eq!(
+            check(&report, "checkpoint_interval").status,
+            CheckStatus::Warn
+        );
+    }
+
+    #[test]
+    fn rendered_files_become_artifacts() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("values.yaml");
+        fs::write(&path, "replicas: 1\n").expect("write rendered file");
+        let mut report = small().build().dry_run();
+
+        report.record_file("values.yaml", &path);
+        report.record_file("cfgsync.yaml", &dir.path().join("missing.yaml"));
+
+        assert_eq!(report.artifacts.len(), 1);
+        assert_eq!(report.artifacts[0].name, "values.yaml");
+        assert_eq!(report.artifacts[0].contents, "replicas: 1\n");
+        let unreadable = check(&report, "artifacts");
+        assert_eq!(unreadable.status, CheckStatus::Warn);
+        assert!(report.passed());
+    }
+}
@@ -1,13 +1,41 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
-use super::{DynError, RunContext, runtime::context::RunMetrics};
+use super::{DynError, NodeFeature, RunContext, runtime::context::RunMetrics};
 use crate::topology::generation::GeneratedTopology;
 
+/// How a failing expectation affects the run verdict.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// A failure fails the run.
+    #[default]
+    Hard,
+    /// A failure is reported as a warning; the run still passes. Useful while
+    /// burning in flaky assertions.
+    Soft,
+}
+
 #[async_trait]
 /// Defines a check evaluated during or after a scenario run.
 pub trait Expectation: Send + Sync {
     fn name(&self) -> &str;
 
+    /// Whether a failure fails the run or is only reported as a warning.
+    fn severity(&self) -> Severity {
+        Severity::Hard
+    }
+
+    /// Labels used by [`super::TagFilter`] to select expectations at runtime.
+    fn tags(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Node features the expectation relies on; see [`NodeFeature`].
+    fn required_features(&self) -> &[NodeFeature] {
+        &[]
+    }
+
     fn init(
         &mut self,
         _descriptors: &GeneratedTopology,
@@ -21,4 +49,73 @@ pub trait Expectation: Send + Sync {
     }
 
     async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError>;
+
+    /// Expose the checkpoint hook of expectations that implement
+    /// [`PeriodicExpectation`]. Implementors return `Some(self)`.
+    fn as_periodic(&mut self) -> Option<&mut dyn PeriodicExpectation> {
+        None
+    }
+}
+
+#[async_trait]
+/// An expectation that can also be checked while workloads are still running.
+///
+/// When the scenario sets a checkpoint interval, the runner calls
+/// [`Self::checkpoint`] on every tick and records the outcome in the run
+/// report. The final [`Expectation::evaluate`] still decides the verdict
+/// unless the scenario aborts on checkpoint failures.
+pub trait PeriodicExpectation: Send + Sync {
+    async fn checkpoint(&mut self, ctx: &RunContext) -> Result<(), DynError>;
+}
+
+/// Wraps an expectation so its failures only produce warnings, see
+/// [`Severity::Soft`].
+pub struct SoftExpectation<E> {
+    inner: E,
+}
+
+impl<E> SoftExpectation<E> {
+    #[must_use]
+    pub const fn new(inner: E) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<E: Expectation> Expectation for SoftExpectation<E> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Soft
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        self.inner.tags()
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        self.inner.required_features()
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        self.inner.init(descriptors, run_metrics)
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.inner.start_capture(ctx).await
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.inner.evaluate(ctx).await
+    }
+
+    fn as_periodic(&mut self) -> Option<&mut dyn PeriodicExpectation> {
+        self.inner.as_periodic()
+    }
 }
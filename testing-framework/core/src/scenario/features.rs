@@ -0,0 +1,36 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+/// Optional node functionality a workload or expectation depends on.
+///
+/// The runner probes the features a scenario requires once the deployer
+/// handed over the ready cluster, before any workload starts, so a node
+/// binary built without them fails the run up front instead of panicking
+/// midway.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum NodeFeature {
+    /// The testing HTTP API on every node, served by binaries built with the
+    /// `testing` feature.
+    TestingHttp,
+    /// At least one executor deployed from the start and serving its
+    /// dispersal endpoint, to disperse blobs through.
+    ExecutorDispersal,
+}
+
+impl NodeFeature {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::TestingHttp => "testing-http",
+            Self::ExecutorDispersal => "executor-dispersal",
+        }
+    }
+}
+
+impl fmt::Display for NodeFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
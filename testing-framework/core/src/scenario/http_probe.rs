@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use futures::future::try_join_all;
 use nomos_http_api_common::paths;
-use reqwest::Client as ReqwestClient;
+use reqwest::{Client as ReqwestClient, Url};
 use thiserror::Error;
 use tokio::time::{sleep, timeout};
 use tracing::{debug, info};
@@ -92,11 +92,12 @@ pub async fn wait_for_http_ports_with_host(
 
     let client = ReqwestClient::new();
     let probes = ports.iter().copied().map(|port| {
-        wait_for_single_port(
+        let url = format!("http://{host}:{port}{}", paths::CRYPTARCHIA_INFO);
+        wait_for_single_url(
             client.clone(),
+            url,
             port,
             role,
-            host,
             timeout_duration,
             poll_interval,
         )
@@ -105,15 +106,56 @@ pub async fn wait_for_http_ports_with_host(
     try_join_all(probes).await.map(|_| ())
 }
 
-async fn wait_for_single_port(
+/// Wait for HTTP readiness on full base URLs, e.g. `https://` ingress
+/// endpoints. Build `client` from [`crate::nodes::HttpOptions`] to send auth
+/// headers or trust a private CA.
+pub async fn wait_for_http_urls(
+    base_urls: &[Url],
+    role: NodeRole,
+    client: &ReqwestClient,
+    timeout_duration: Duration,
+    poll_interval: Duration,
+) -> Result<(), HttpReadinessError> {
+    if base_urls.is_empty() {
+        return Ok(());
+    }
+
+    info!(
+        role = role.label(),
+        urls = ?base_urls.iter().map(Url::as_str).collect::<Vec<_>>(),
+        timeout_secs = timeout_duration.as_secs_f32(),
+        poll_ms = poll_interval.as_millis(),
+        "waiting for HTTP readiness"
+    );
+
+    let probes = base_urls.iter().map(|base| {
+        let url = format!(
+            "{}{}",
+            base.as_str().trim_end_matches('/'),
+            paths::CRYPTARCHIA_INFO
+        );
+        let port = base.port_or_known_default().unwrap_or_default();
+        wait_for_single_url(
+            client.clone(),
+            url,
+            port,
+            role,
+            timeout_duration,
+            poll_interval,
+        )
+    });
+
+    try_join_all(probes).await.map(|_| ())
+}
+
+async fn wait_for_single_url(
     client: ReqwestClient,
+    url: String,
     port: u16,
     role: NodeRole,
-    host: &str,
     timeout_duration: Duration,
     poll_interval: Duration,
 ) -> Result<(), HttpReadinessError> {
-    let url = format!("http://{host}:{port}{}", paths::CRYPTARCHIA_INFO);
     debug!(role = role.label(), %url, "probing HTTP endpoint");
     let probe = async {
         loop {
@@ -3,23 +3,56 @@
 mod capabilities;
 pub mod cfgsync;
 mod definition;
+mod dry_run;
 mod expectation;
+mod features;
 pub mod http_probe;
+mod node_env;
+mod profile;
+mod registry;
+mod report;
 mod runtime;
+mod tags;
+mod timings;
 mod workload;
 
 pub type DynError = Box<dyn std::error::Error + Send + Sync + 'static>;
 
-pub use capabilities::{NodeControlCapability, NodeControlHandle, RequiresNodeControl};
+pub use capabilities::{
+    AddedNode, ExecOutput, NetworkShaping, NodeControlCapability, NodeControlHandle, NodeExit,
+    NodeTarget, RequiresNodeControl,
+};
 pub use definition::{Builder, Scenario, ScenarioBuilder, TopologyConfigurator};
-pub use expectation::Expectation;
+pub use dry_run::{
+    CheckStatus, DryRunArtifact, DryRunCheck, DryRunComponent, DryRunNode, DryRunReport,
+};
+pub use expectation::{Expectation, PeriodicExpectation, Severity, SoftExpectation};
+pub use features::NodeFeature;
+pub use node_env::NodeEnv;
+pub use profile::{POL_PROOF_DEV_MODE_ENV, PROFILE_ENV, Profile, pol_proof_dev_mode};
+pub use registry::{ComponentInfo, ComponentKind, ComponentRegistry, RegistryError};
+pub use report::{
+    ChaosFaultRecord, CheckpointOutcome, DeploymentPhaseTiming, ExpectationOutcome, NodeEvent,
+    NodeEventKind, ReadinessTiming, ReportError, ReportFormat, RunReport, RunReporter,
+};
 pub use runtime::{
-    BlockFeed, BlockFeedTask, BlockRecord, BlockStats, CleanupGuard, Deployer, NodeClients,
-    RunContext, RunHandle, RunMetrics, Runner, ScenarioError,
+    ApiFaultProxies, ApiFaultStats, ApiFaults, AttachDeployer, AttachDeployerError, AttachedNode,
+    BlockFeed, BlockFeedRecorder, BlockFeedTask, BlockRecord, BlockRecordError, BlockStats,
+    CheckpointPolicy, CleanupGuard, ConsensusSample, ConsensusThroughput, DaStatsSample, Deployer,
+    DeploymentPhase, DeploymentTimeline, DispersalStats, ExecutorDispersal, FailoverReason,
+    FaultProxyError, FeedGap, HeightDelta, LatencyPercentiles, LogLocation, METRICS_JOB_ENV,
+    METRICS_OTLP_ENV, METRICS_PUSHGATEWAY_ENV, MetricsExportError, MetricsExportTarget,
+    NodeClients, NodeLogSource, NodeLogs, NodeRecovery, NodeStorage, PublishedBlob, PublishedBlobs,
+    RecoverySample, ReplayBlockFeed, RunAbort, RunCancellation, RunContext, RunHandle, RunMetrics,
+    RunMetricsExporter, Runner, SamplingSweep, ScenarioError, ShutdownSignal, SpanSummary,
+    StallDetector, StallEvent, StatsSampler, StorageArea, StorageInspector, StoredFile,
+    SweepFailure, TempoEndpoint, TraceSummary, TracesError, TxLatency, WalletPool,
     metrics::{
         CONSENSUS_PROCESSED_BLOCKS, CONSENSUS_TRANSACTIONS_TOTAL, Metrics, MetricsError,
-        PrometheusEndpoint, PrometheusInstantSample,
+        NODE_LABEL, PrometheusEndpoint, PrometheusInstantSample, node_selector, role_selector,
     },
-    spawn_block_feed,
+    sample_published_blobs, spawn_block_feed, spawn_failover_block_feed,
 };
+pub use tags::{TAG_FILTER_ENV, TagFilter};
+pub use timings::{ScenarioTimings, TIMEOUT_SCALE_ENV};
 pub use workload::Workload;
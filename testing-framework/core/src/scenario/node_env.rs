@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use super::NodeTarget;
+
+/// Extra environment variables for individual nodes, e.g. to toggle
+/// experimental node features on part of the cluster.
+///
+/// Applied by the compose and k8s deployers on top of the variables they set
+/// themselves; a key set here replaces the deployer's value. Other deployers
+/// ignore it.
+#[derive(Clone, Debug, Default)]
+pub struct NodeEnv {
+    vars: HashMap<NodeTarget, Vec<(String, String)>>,
+}
+
+impl NodeEnv {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Set `key` to `value` for `target`, replacing an earlier value.
+    pub fn with_var(
+        mut self,
+        target: NodeTarget,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        let key = key.into();
+        let vars = self.vars.entry(target).or_default();
+        vars.retain(|(existing, _)| *existing != key);
+        vars.push((key, value.into()));
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// Variables set for `target`, in insertion order.
+    pub fn vars_for(&self, target: NodeTarget) -> impl Iterator<Item = (&str, &str)> {
+        self.vars
+            .get(&target)
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Nodes with at least one variable.
+    pub fn targets(&self) -> impl Iterator<Item = NodeTarget> + '_ {
+        self.vars.keys().copied()
+    }
+}
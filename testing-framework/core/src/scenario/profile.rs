@@ -0,0 +1,239 @@
+use std::{env, fmt, str::FromStr, time::Duration};
+
+use tracing::warn;
+
+use super::{ScenarioTimings, timings::scale_from_env};
+use crate::artifacts::{KEEP_LOGS_ENV, RetentionPolicy};
+
+/// Names the [`Profile`] applied by [`super::Builder::with_env_profile`] and
+/// by scenario files, e.g. `NOMOS_TEST_PROFILE=ci`.
+pub const PROFILE_ENV: &str = "NOMOS_TEST_PROFILE";
+/// Passed through to nodes started by the compose and k8s deployers.
+pub const POL_PROOF_DEV_MODE_ENV: &str = "POL_PROOF_DEV_MODE";
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+const LOCAL_DEV_MAX_ARTIFACT_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+const SOAK_CHECKPOINT_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Defaults for the environment a scenario runs in.
+///
+/// A profile bundles the timeout scale, transaction readiness, what happens
+/// to run artifacts, whether deployers start Prometheus and Grafana, and the
+/// topology size of [`super::Builder::from_profile`]. Select one with
+/// [`super::Builder::with_profile`] or, through
+/// [`super::Builder::with_env_profile`], [`PROFILE_ENV`]; settings made on the
+/// builder or deployer afterwards take precedence, and the environment
+/// variables that used to control each setting (`NOMOS_TIMEOUT_SCALE`,
+/// `NOMOS_TESTS_KEEP_LOGS`, `NOMOS_ARTIFACTS_MAX_*`) still override the
+/// profile's value. Every profile runs nodes with dev-mode proofs of
+/// leadership; see [`pol_proof_dev_mode`].
+///
+/// | | `LocalDev` | `Ci` | `NightlySoak` |
+/// |---|---|---|---|
+/// | timeout scale | 1.0 | 2.0 | 1.5 |
+/// | transaction readiness | off | on | on |
+/// | keep run artifacts | yes | on panic only | yes |
+/// | artifact retention | 1 day, 5 GiB | unlimited | 7 days |
+/// | Prometheus / Grafana | on | off | on |
+/// | validators / executors | 1 / 1 | 2 / 1 | 4 / 2 |
+/// | checkpoint interval | - | - | 5 min |
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// Iterating on a developer machine: small topology, artifacts kept for
+    /// inspection and pruned after a day.
+    LocalDev,
+    /// Ephemeral CI runners: generous timeouts, no observability stack and
+    /// artifacts only kept when the run panics.
+    Ci,
+    /// Long scheduled runs: larger topology, periodic checkpoints and a week
+    /// of kept artifacts.
+    NightlySoak,
+}
+
+impl Profile {
+    /// Profile named by [`PROFILE_ENV`], if set to a known name.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let value = env::var(PROFILE_ENV).ok()?;
+        value
+            .parse()
+            .inspect_err(|err| warn!(%err, "ignoring {PROFILE_ENV}"))
+            .ok()
+    }
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::LocalDev => "local-dev",
+            Self::Ci => "ci",
+            Self::NightlySoak => "nightly-soak",
+        }
+    }
+
+    #[must_use]
+    /// Multiplier for every timeout of [`Self::timings`].
+    pub const fn timeout_scale(self) -> f64 {
+        match self {
+            Self::LocalDev => 1.0,
+            Self::Ci => 2.0,
+            Self::NightlySoak => 1.5,
+        }
+    }
+
+    #[must_use]
+    /// Deployer timeouts scaled by [`Self::timeout_scale`], or by
+    /// `NOMOS_TIMEOUT_SCALE` when set.
+    pub fn timings(self) -> ScenarioTimings {
+        ScenarioTimings::new().with_scale(scale_from_env().unwrap_or(self.timeout_scale()))
+    }
+
+    #[must_use]
+    /// Whether deployers wait for mempools to accept transactions; see
+    /// [`super::Builder::with_transaction_readiness`].
+    pub const fn transaction_readiness(self) -> bool {
+        !matches!(self, Self::LocalDev)
+    }
+
+    #[must_use]
+    /// Whether run artifacts outlive a successful run. Setting
+    /// `NOMOS_TESTS_KEEP_LOGS` always keeps them.
+    pub fn keep_artifacts(self) -> bool {
+        self.keep_artifacts_with(env::var_os(KEEP_LOGS_ENV).is_some())
+    }
+
+    const fn keep_artifacts_with(self, keep_logs: bool) -> bool {
+        !matches!(self, Self::Ci) || keep_logs
+    }
+
+    #[must_use]
+    /// How older run directories are pruned when a run starts. Limits set
+    /// through the `NOMOS_ARTIFACTS_MAX_*` variables replace the profile's.
+    pub fn retention(self) -> RetentionPolicy {
+        self.retention_with(RetentionPolicy::from_env())
+    }
+
+    fn retention_with(self, from_env: RetentionPolicy) -> RetentionPolicy {
+        if !from_env.is_unlimited() {
+            return from_env;
+        }
+        match self {
+            Self::LocalDev => RetentionPolicy::unlimited()
+                .with_max_age(DAY)
+                .with_max_total_bytes(LOCAL_DEV_MAX_ARTIFACT_BYTES),
+            Self::Ci => RetentionPolicy::unlimited(),
+            Self::NightlySoak => RetentionPolicy::unlimited().with_max_age(DAY * 7),
+        }
+    }
+
+    #[must_use]
+    /// Whether deployers that can run Prometheus and Grafana do so.
+    pub const fn observability(self) -> bool {
+        !matches!(self, Self::Ci)
+    }
+
+    #[must_use]
+    pub const fn validators(self) -> usize {
+        match self {
+            Self::LocalDev => 1,
+            Self::Ci => 2,
+            Self::NightlySoak => 4,
+        }
+    }
+
+    #[must_use]
+    pub const fn executors(self) -> usize {
+        match self {
+            Self::LocalDev | Self::Ci => 1,
+            Self::NightlySoak => 2,
+        }
+    }
+
+    #[must_use]
+    /// Soak-mode checkpoint interval; see
+    /// [`super::Builder::with_checkpoint_interval`].
+    pub const fn checkpoint_interval(self) -> Option<Duration> {
+        match self {
+            Self::LocalDev | Self::Ci => None,
+            Self::NightlySoak => Some(SOAK_CHECKPOINT_INTERVAL),
+        }
+    }
+}
+
+/// Whether deployed nodes use dev-mode proofs of leadership: on unless
+/// [`POL_PROOF_DEV_MODE_ENV`] is set to anything but `true`.
+#[must_use]
+pub fn pol_proof_dev_mode() -> bool {
+    !env::var(POL_PROOF_DEV_MODE_ENV).is_ok_and(|value| value != "true")
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Profile {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "local" | "local-dev" | "dev" => Ok(Self::LocalDev),
+            "ci" => Ok(Self::Ci),
+            "soak" | "nightly" | "nightly-soak" => Ok(Self::NightlySoak),
+            other => Err(format!(
+                "unknown profile `{other}`, expected local-dev, ci or nightly-soak"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_and_aliases() {
+        for (value, profile) in [
+            ("local-dev", Profile::LocalDev),
+            ("dev", Profile::LocalDev),
+            (" CI ", Profile::Ci),
+            ("Nightly", Profile::NightlySoak),
+            ("nightly-soak", Profile::NightlySoak),
+        ] {
+            assert_eq!(value.parse::<Profile>(), Ok(profile), "{value}");
+        }
+        for profile in [Profile::LocalDev, Profile::Ci, Profile::NightlySoak] {
+            assert_eq!(profile.name().parse::<Profile>(), Ok(profile));
+        }
+
+        let err = "staging".parse::<Profile>().unwrap_err();
+        assert!(err.contains("`staging`"), "{err}");
+    }
+
+    #[test]
+    fn retention_limits_from_env_replace_the_profile() {
+        let from_env = RetentionPolicy::unlimited().with_max_total_bytes(1024);
+
+        assert_eq!(Profile::Ci.retention_with(from_env), from_env);
+        assert_eq!(Profile::LocalDev.retention_with(from_env), from_env);
+        assert_eq!(
+            Profile::LocalDev.retention_with(RetentionPolicy::unlimited()),
+            RetentionPolicy::unlimited()
+                .with_max_age(DAY)
+                .with_max_total_bytes(LOCAL_DEV_MAX_ARTIFACT_BYTES)
+        );
+        assert!(
+            Profile::Ci
+                .retention_with(RetentionPolicy::unlimited())
+                .is_unlimited()
+        );
+    }
+
+    #[test]
+    fn ci_keeps_artifacts_only_when_asked() {
+        assert!(!Profile::Ci.keep_artifacts_with(false));
+        assert!(Profile::Ci.keep_artifacts_with(true));
+        assert!(Profile::LocalDev.keep_artifacts_with(false));
+        assert!(Profile::NightlySoak.keep_artifacts_with(false));
+    }
+}
@@ -0,0 +1,362 @@
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+use serde::{Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use thiserror::Error;
+
+use super::{DynError, Expectation, Workload};
+
+type WorkloadFactory = Arc<dyn Fn(Value) -> Result<Box<dyn Workload>, RegistryError> + Send + Sync>;
+type ExpectationFactory =
+    Arc<dyn Fn(Value) -> Result<Box<dyn Expectation>, RegistryError> + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    Workload,
+    Expectation,
+}
+
+impl fmt::Display for ComponentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Workload => "workload",
+            Self::Expectation => "expectation",
+        })
+    }
+}
+
+/// What a registered component is and how it is configured.
+#[derive(Clone, Debug, Serialize)]
+pub struct ComponentInfo {
+    pub name: &'static str,
+    pub kind: ComponentKind,
+    pub description: &'static str,
+    /// Whether the scenario needs [`super::NodeControlCapability`], e.g. for
+    /// chaos workloads.
+    pub requires_node_control: bool,
+    /// The configuration the component is built with when none is given;
+    /// every field can be overridden by name.
+    pub default_config: Value,
+}
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("unknown {kind} `{name}`")]
+    Unknown { kind: ComponentKind, name: String },
+    #[error("invalid config for {kind} `{name}`: {source}")]
+    Config {
+        kind: ComponentKind,
+        name: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to build {kind} `{name}`: {source}")]
+    Build {
+        kind: ComponentKind,
+        name: String,
+        #[source]
+        source: DynError,
+    },
+}
+
+/// Workloads and expectations constructible by name from a serde config.
+///
+/// Lets the YAML scenario loader and the daemon API assemble scenarios from
+/// components they do not know at compile time. Each component registers a
+/// config type; a config is deserialized into it (a missing config counts as
+/// an empty object, so defaults apply) and handed to the component's build
+/// function. Clones share the registered factories.
+#[derive(Clone, Default)]
+pub struct ComponentRegistry {
+    workloads: BTreeMap<&'static str, (ComponentInfo, WorkloadFactory)>,
+    expectations: BTreeMap<&'static str, (ComponentInfo, ExpectationFactory)>,
+}
+
+impl fmt::Debug for ComponentRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ComponentRegistry")
+            .field("workloads", &self.workloads.keys().collect::<Vec<_>>())
+            .field(
+                "expectations",
+                &self.expectations.keys().collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl ComponentRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a workload built from config `C`.
+    ///
+    /// # Panics
+    ///
+    /// When a workload with the same name is already registered.
+    pub fn register_workload<C, W>(
+        &mut self,
+        name: &'static str,
+        description: &'static str,
+        build: impl Fn(C) -> Result<W, DynError> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: DeserializeOwned + Serialize + Default + 'static,
+        W: Workload + 'static,
+    {
+        self.insert_workload::<C, W>(name, description, false, build)
+    }
+
+    /// Register a workload that needs node control, such as a chaos
+    /// workload. Scenarios using it must be built with
+    /// [`super::NodeControlCapability`].
+    ///
+    /// # Panics
+    ///
+    /// When a workload with the same name is already registered.
+    pub fn register_node_control_workload<C, W>(
+        &mut self,
+        name: &'static str,
+        description: &'static str,
+        build: impl Fn(C) -> Result<W, DynError> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: DeserializeOwned + Serialize + Default + 'static,
+        W: Workload + 'static,
+    {
+        self.insert_workload::<C, W>(name, description, true, build)
+    }
+
+    /// Register a standalone expectation built from config `C`.
+    ///
+    /// # Panics
+    ///
+    /// When an expectation with the same name is already registered.
+    pub fn register_expectation<C, E>(
+        &mut self,
+        name: &'static str,
+        description: &'static str,
+        build: impl Fn(C) -> Result<E, DynError> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: DeserializeOwned + Serialize + Default + 'static,
+        E: Expectation + 'static,
+    {
+        let kind = ComponentKind::Expectation;
+        let info = component_info::<C>(name, kind, description, false);
+        let factory: ExpectationFactory = Arc::new(move |config| {
+            let config = parse_config::<C>(kind, name, config)?;
+            build(config)
+                .map(|expectation| Box::new(expectation) as Box<dyn Expectation>)
+                .map_err(|source| build_error(kind, name, source))
+        });
+        assert!(
+            self.expectations.insert(name, (info, factory)).is_none(),
+            "expectation `{name}` registered twice"
+        );
+        self
+    }
+
+    /// Build the workload registered as `name` from `config`.
+    pub fn workload(&self, name: &str, config: Value) -> Result<Box<dyn Workload>, RegistryError> {
+        let (_, factory) = self
+            .workloads
+            .get(name)
+            .ok_or_else(|| unknown(ComponentKind::Workload, name))?;
+        factory(config)
+    }
+
+    /// Build the expectation registered as `name` from `config`.
+    pub fn expectation(
+        &self,
+        name: &str,
+        config: Value,
+    ) -> Result<Box<dyn Expectation>, RegistryError> {
+        let (_, factory) = self
+            .expectations
+            .get(name)
+            .ok_or_else(|| unknown(ComponentKind::Expectation, name))?;
+        factory(config)
+    }
+
+    #[must_use]
+    pub fn workload_info(&self, name: &str) -> Option<&ComponentInfo> {
+        self.workloads.get(name).map(|(info, _)| info)
+    }
+
+    #[must_use]
+    pub fn expectation_info(&self, name: &str) -> Option<&ComponentInfo> {
+        self.expectations.get(name).map(|(info, _)| info)
+    }
+
+    /// Every registered component, workloads first, each group sorted by
+    /// name.
+    pub fn components(&self) -> impl Iterator<Item = &ComponentInfo> {
+        self.workloads
+            .values()
+            .map(|(info, _)| info)
+            .chain(self.expectations.values().map(|(info, _)| info))
+    }
+
+    fn insert_workload<C, W>(
+        &mut self,
+        name: &'static str,
+        description: &'static str,
+        requires_node_control: bool,
+        build: impl Fn(C) -> Result<W, DynError> + Send + Sync + 'static,
+    ) -> &mut Self
+    where
+        C: DeserializeOwned + Serialize + Default + 'static,
+        W: Workload + 'static,
+    {
+        let kind = ComponentKind::Workload;
+        let info = component_info::<C>(name, kind, description, requires_node_control);
+        let factory: WorkloadFactory = Arc::new(move |config| {
+            let config = parse_config::<C>(kind, name, config)?;
+            build(config)
+                .map(|workload| Box::new(workload) as Box<dyn Workload>)
+                .map_err(|source| build_error(kind, name, source))
+        });
+        assert!(
+            self.workloads.insert(name, (info, factory)).is_none(),
+            "workload `{name}` registered twice"
+        );
+        self
+    }
+}
+
+fn component_info<C>(
+    name: &'static str,
+    kind: ComponentKind,
+    description: &'static str,
+    requires_node_control: bool,
+) -> ComponentInfo
+where
+    C: Serialize + Default,
+{
+    ComponentInfo {
+        name,
+        kind,
+        description,
+        requires_node_control,
+        default_config: serde_json::to_value(C::default()).unwrap_or(Value::Null),
+    }
+}
+
+fn parse_config<C: DeserializeOwned>(
+    kind: ComponentKind,
+    name: &str,
+    config: Value,
+) -> Result<C, RegistryError> {
+    let config = match config {
+        Value::Null => Value::Object(serde_json::Map::new()),
+        config => config,
+    };
+    serde_json::from_value(config).map_err(|source| RegistryError::Config {
+        kind,
+        name: name.to_owned(),
+        source,
+    })
+}
+
+fn build_error(kind: ComponentKind, name: &str, source: DynError) -> RegistryError {
+    RegistryError::Build {
+        kind,
+        name: name.to_owned(),
+        source,
+    }
+}
+
+fn unknown(kind: ComponentKind, name: &str) -> RegistryError {
+    RegistryError::Unknown {
+        kind,
+        name: name.to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    #[serde(default, deny_unknown_fields)]
+    struct RateConfig {
+        rate: u64,
+        label: Option<String>,
+    }
+
+    impl Default for RateConfig {
+        fn default() -> Self {
+            Self {
+                rate: 1,
+                label: None,
+            }
+        }
+    }
+
+    fn parse(config: Value) -> Result<RateConfig, RegistryError> {
+        parse_config(ComponentKind::Workload, "rate", config)
+    }
+
+    #[test]
+    fn missing_config_uses_defaults() {
+        assert_eq!(
+            parse(Value::Null).expect("null config"),
+            RateConfig::default()
+        );
+        assert_eq!(
+            parse(json!({})).expect("empty config"),
+            RateConfig::default()
+        );
+        assert_eq!(
+            parse(json!({ "label": "burst" })).expect("partial config"),
+            RateConfig {
+                rate: 1,
+                label: Some("burst".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_config_names_the_component() {
+        let err = parse(json!({ "rte": 5 })).expect_err("misspelled field");
+        assert!(matches!(
+            err,
+            RegistryError::Config {
+                kind: ComponentKind::Workload,
+                ..
+            }
+        ));
+        let message = err.to_string();
+        assert!(
+            message.starts_with("invalid config for workload `rate`"),
+            "{message}"
+        );
+        assert!(message.contains("unknown field `rte`"), "{message}");
+
+        assert!(parse(json!([5])).is_err());
+    }
+
+    #[test]
+    fn unknown_components_are_reported() {
+        let registry = ComponentRegistry::new();
+
+        let err = registry
+            .workload("missing", Value::Null)
+            .err()
+            .expect("nothing is registered");
+        assert_eq!(err.to_string(), "unknown workload `missing`");
+        let err = registry
+            .expectation("missing", Value::Null)
+            .err()
+            .expect("nothing is registered");
+        assert_eq!(err.to_string(), "unknown expectation `missing`");
+        assert!(registry.workload_info("missing").is_none());
+        assert_eq!(registry.components().count(), 0);
+    }
+}
@@ -0,0 +1,608 @@
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write as _},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{DeploymentTimeline, DynError, NodeTarget, Severity};
+
+/// Output format for a persisted [`RunReport`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Json,
+    Html,
+}
+
+impl ReportFormat {
+    /// Picks the format from the file extension, defaulting to JSON.
+    #[must_use]
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("html") || ext.eq_ignore_ascii_case("htm") => {
+                Self::Html
+            }
+            _ => Self::Json,
+        }
+    }
+}
+
+/// Failures persisting a run report.
+#[derive(Debug, Error)]
+pub enum ReportError {
+    #[error("failed to serialize run report: {source}")]
+    Serialize {
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to parse run report {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to access run report {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Kind of runtime action applied to a node, or an exit the deployer
+/// observed without one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeEventKind {
+    Restart,
+    Stop,
+    Kill,
+    Start,
+    Partition,
+    HealPartition,
+    NetworkShaping,
+    ClearNetworkShaping,
+    /// The node process exited without being asked to.
+    Exit,
+    /// A late-joining node was started.
+    Join,
+    /// A node was frozen in place.
+    Pause,
+    /// A paused node was resumed.
+    Unpause,
+    /// A node was added to the running cluster.
+    Add,
+    /// A node was removed from the running cluster.
+    Remove,
+    /// A node's disk was filled up.
+    DiskFill,
+    /// Space taken by a disk fill was released.
+    DiskRelease,
+    /// The node's container failed its healthcheck without being stopped
+    /// or paused.
+    Unhealthy,
+}
+
+/// A node control action observed during the run.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NodeEvent {
+    pub node: String,
+    pub kind: NodeEventKind,
+    /// Seconds since the run context was created.
+    pub at_secs: f64,
+}
+
+/// A fault applied by a chaos timeline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChaosFaultRecord {
+    /// Kind of fault, e.g. `restart` or `partition`.
+    pub fault: String,
+    /// Nodes the fault was applied to.
+    pub nodes: Vec<String>,
+    /// Offset into the timeline the fault was declared at.
+    pub scheduled_secs: f64,
+    /// Seconds since the run context was created.
+    pub at_secs: f64,
+    /// How long the fault was held; `None` for instant faults and faults
+    /// kept until the end of the run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<f64>,
+}
+
+/// Time spent waiting for a deployer readiness check.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReadinessTiming {
+    pub check: String,
+    pub duration_ms: u64,
+}
+
+/// A deployer bring-up phase, see
+/// [`DeploymentTimeline`](super::DeploymentTimeline).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DeploymentPhaseTiming {
+    pub phase: String,
+    /// Milliseconds from the start of the deployment until the phase started.
+    pub start_ms: u64,
+    pub duration_ms: u64,
+}
+
+/// Final verdict of a single expectation.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ExpectationOutcome {
+    pub name: String,
+    pub passed: bool,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of checking a periodic expectation while the run was in progress.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CheckpointOutcome {
+    pub name: String,
+    /// Seconds since the run context was created.
+    pub at_secs: f64,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Structured summary of a scenario run.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RunReport {
+    pub generated_at_unix_secs: u64,
+    pub elapsed_secs: f64,
+    pub readiness: Vec<ReadinessTiming>,
+    #[serde(default)]
+    pub deployment: Vec<DeploymentPhaseTiming>,
+    /// Counters keyed by workload name, then counter name.
+    pub workloads: BTreeMap<String, BTreeMap<String, u64>>,
+    pub expectations: Vec<ExpectationOutcome>,
+    pub checkpoints: Vec<CheckpointOutcome>,
+    pub node_events: Vec<NodeEvent>,
+    #[serde(default)]
+    pub chaos_timeline: Vec<ChaosFaultRecord>,
+    /// Error that ended the run, e.g. a workload failure, an abort or an
+    /// interrupt; `None` when the run completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RunReport {
+    /// Returns `true` when the run completed and every hard expectation
+    /// passed. Soft failures and checkpoint failures (unless the scenario
+    /// aborts on them) do not fail the run.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.error.is_none()
+            && self
+                .expectations
+                .iter()
+                .all(|outcome| outcome.passed || outcome.severity == Severity::Soft)
+    }
+
+    /// Failed soft expectations, reported as warnings.
+    pub fn warnings(&self) -> impl Iterator<Item = &ExpectationOutcome> {
+        self.expectations
+            .iter()
+            .filter(|outcome| !outcome.passed && outcome.severity == Severity::Soft)
+    }
+
+    /// Serialize the report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, ReportError> {
+        serde_json::to_string_pretty(self).map_err(|source| ReportError::Serialize { source })
+    }
+
+    /// Render the report as a standalone HTML page.
+    #[must_use]
+    pub fn to_html(&self) -> String {
+        let mut html = String::from(
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Scenario run report</title>\
+             <style>body{font-family:sans-serif}table{border-collapse:collapse;margin-bottom:1em}\
+             td,th{border:1px solid #999;padding:4px 8px}.pass{color:#070}.fail{color:#a00}.warn{color:#b60}</style>\
+             </head><body>\n",
+        );
+        let verdict = if self.passed() { "pass" } else { "fail" };
+        let _ = writeln!(
+            html,
+            "<h1>Scenario run report</h1><p class=\"{verdict}\">{}</p><p>elapsed: {:.1}s</p>",
+            verdict.to_uppercase(),
+            self.elapsed_secs
+        );
+        if let Some(error) = &self.error {
+            let _ = writeln!(html, "<p class=\"fail\">error: {}</p>", escape_html(error));
+        }
+
+        html.push_str(
+            "<h2>Expectations</h2><table><tr><th>name</th><th>result</th><th>error</th></tr>\n",
+        );
+        for outcome in &self.expectations {
+            let class = match (outcome.passed, outcome.severity) {
+                (true, _) => "pass",
+                (false, Severity::Soft) => "warn",
+                (false, Severity::Hard) => "fail",
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td class=\"{class}\">{class}</td><td>{}</td></tr>",
+                escape_html(&outcome.name),
+                escape_html(outcome.error.as_deref().unwrap_or("")),
+            );
+        }
+        html.push_str("</table>\n");
+
+        if !self.checkpoints.is_empty() {
+            html.push_str(
+                "<h2>Checkpoints</h2><table><tr><th>at (s)</th><th>name</th><th>result</th><th>error</th></tr>\n",
+            );
+            for outcome in &self.checkpoints {
+                let class = if outcome.passed { "pass" } else { "fail" };
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{:.1}</td><td>{}</td><td class=\"{class}\">{class}</td><td>{}</td></tr>",
+                    outcome.at_secs,
+                    escape_html(&outcome.name),
+                    escape_html(outcome.error.as_deref().unwrap_or("")),
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str(
+            "<h2>Workloads</h2><table><tr><th>workload</th><th>counter</th><th>value</th></tr>\n",
+        );
+        for (workload, counters) in &self.workloads {
+            for (counter, value) in counters {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{value}</td></tr>",
+                    escape_html(workload),
+                    escape_html(counter),
+                );
+            }
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h2>Readiness</h2><table><tr><th>check</th><th>duration (ms)</th></tr>\n");
+        for timing in &self.readiness {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                escape_html(&timing.check),
+                timing.duration_ms
+            );
+        }
+        html.push_str("</table>\n");
+
+        if !self.deployment.is_empty() {
+            html.push_str(
+                "<h2>Deployment</h2><table><tr><th>phase</th><th>start (ms)</th><th>duration (ms)</th></tr>\n",
+            );
+            for phase in &self.deployment {
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape_html(&phase.phase),
+                    phase.start_ms,
+                    phase.duration_ms
+                );
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str(
+            "<h2>Node events</h2><table><tr><th>at (s)</th><th>node</th><th>event</th></tr>\n",
+        );
+        for event in &self.node_events {
+            let _ = writeln!(
+                html,
+                "<tr><td>{:.1}</td><td>{}</td><td>{:?}</td></tr>",
+                event.at_secs,
+                escape_html(&event.node),
+                event.kind
+            );
+        }
+        html.push_str("</table>\n");
+
+        if !self.chaos_timeline.is_empty() {
+            html.push_str(
+                "<h2>Chaos timeline</h2><table><tr><th>scheduled (s)</th><th>at (s)</th><th>fault</th><th>nodes</th><th>duration (s)</th></tr>\n",
+            );
+            for record in &self.chaos_timeline {
+                let duration = record
+                    .duration_secs
+                    .map_or_else(String::new, |secs| format!("{secs:.1}"));
+                let _ = writeln!(
+                    html,
+                    "<tr><td>{:.1}</td><td>{:.1}</td><td>{}</td><td>{}</td><td>{duration}</td></tr>",
+                    record.scheduled_secs,
+                    record.at_secs,
+                    escape_html(&record.fault),
+                    escape_html(&record.nodes.join(", ")),
+                );
+            }
+            html.push_str("</table>\n");
+        }
+        html.push_str("</body></html>\n");
+        html
+    }
+
+    /// Load a report previously written as JSON.
+    pub fn read(path: &Path) -> Result<Self, ReportError> {
+        let contents = fs::read_to_string(path).map_err(|source| ReportError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        serde_json::from_str(&contents).map_err(|source| ReportError::Parse {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Persist the report at `path`, creating parent directories as needed.
+    pub fn write(&self, path: &Path, format: ReportFormat) -> Result<(), ReportError> {
+        let contents = match format {
+            ReportFormat::Json => self.to_json()?,
+            ReportFormat::Html => self.to_html(),
+        };
+        let io_err = |source| ReportError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(io_err)?;
+        }
+        fs::write(path, contents).map_err(io_err)
+    }
+}
+
+/// Shared, cloneable collector that deployers, workloads and the runner feed
+/// while a scenario executes.
+#[derive(Clone)]
+pub struct RunReporter {
+    started: Instant,
+    report: Arc<Mutex<RunReport>>,
+}
+
+impl Default for RunReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunReporter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            report: Arc::new(Mutex::new(RunReport::default())),
+        }
+    }
+
+    fn with_report(&self, f: impl FnOnce(&mut RunReport)) {
+        let mut report = self.report.lock().expect("run report lock poisoned");
+        f(&mut report);
+    }
+
+    /// Record how long a readiness check took.
+    pub fn record_readiness(&self, check: impl Into<String>, duration: Duration) {
+        let timing = ReadinessTiming {
+            check: check.into(),
+            duration_ms: millis(duration),
+        };
+        self.with_report(|report| report.readiness.push(timing));
+    }
+
+    /// Record the deployer's bring-up phases.
+    pub fn record_deployment(&self, timeline: &DeploymentTimeline) {
+        let phases = timeline
+            .phases()
+            .iter()
+            .map(|phase| DeploymentPhaseTiming {
+                phase: phase.name.clone(),
+                start_ms: millis(phase.start),
+                duration_ms: millis(phase.duration),
+            })
+            .collect();
+        self.with_report(|report| report.deployment = phases);
+    }
+
+    /// Add `amount` to a named workload counter.
+    pub fn increment(&self, workload: &str, counter: &str, amount: u64) {
+        self.with_report(|report| {
+            let value = report
+                .workloads
+                .entry(workload.to_owned())
+                .or_default()
+                .entry(counter.to_owned())
+                .or_default();
+            *value = value.saturating_add(amount);
+        });
+    }
+
+    /// Overwrite a named workload counter.
+    pub fn set_counter(&self, workload: &str, counter: &str, value: u64) {
+        self.with_report(|report| {
+            report
+                .workloads
+                .entry(workload.to_owned())
+                .or_default()
+                .insert(counter.to_owned(), value);
+        });
+    }
+
+    /// Record a node control action.
+    pub fn record_node_event(&self, node: NodeTarget, kind: NodeEventKind) {
+        let event = NodeEvent {
+            node: node.to_string(),
+            kind,
+            at_secs: self.started.elapsed().as_secs_f64(),
+        };
+        self.with_report(|report| report.node_events.push(event));
+    }
+
+    /// Record a fault a chaos timeline applied `scheduled` into the timeline.
+    pub fn record_chaos_fault(
+        &self,
+        fault: &str,
+        nodes: &[NodeTarget],
+        scheduled: Duration,
+        duration: Option<Duration>,
+    ) {
+        let record = ChaosFaultRecord {
+            fault: fault.to_owned(),
+            nodes: nodes.iter().map(ToString::to_string).collect(),
+            scheduled_secs: scheduled.as_secs_f64(),
+            at_secs: self.started.elapsed().as_secs_f64(),
+            duration_secs: duration.map(|duration| duration.as_secs_f64()),
+        };
+        self.with_report(|report| report.chaos_timeline.push(record));
+    }
+
+    /// Record the verdict of an expectation.
+    pub fn record_expectation(
+        &self,
+        name: &str,
+        severity: Severity,
+        result: &Result<(), DynError>,
+    ) {
+        let outcome = ExpectationOutcome {
+            name: name.to_owned(),
+            passed: result.is_ok(),
+            severity,
+            error: result.as_ref().err().map(ToString::to_string),
+        };
+        self.with_report(|report| report.expectations.push(outcome));
+    }
+
+    /// Record the outcome of a periodic expectation checkpoint.
+    pub fn record_checkpoint(&self, name: &str, result: &Result<(), DynError>) {
+        let outcome = CheckpointOutcome {
+            name: name.to_owned(),
+            at_secs: self.started.elapsed().as_secs_f64(),
+            passed: result.is_ok(),
+            error: result.as_ref().err().map(ToString::to_string),
+        };
+        self.with_report(|report| report.checkpoints.push(outcome));
+    }
+
+    /// Record the error that ended the run. Only the first one is kept.
+    pub fn record_error(&self, error: &impl fmt::Display) {
+        let message = error.to_string();
+        self.with_report(|report| {
+            report.error.get_or_insert(message);
+        });
+    }
+
+    /// Snapshot the report collected so far.
+    #[must_use]
+    pub fn snapshot(&self) -> RunReport {
+        let mut report = self
+            .report
+            .lock()
+            .expect("run report lock poisoned")
+            .clone();
+        report.elapsed_secs = self.started.elapsed().as_secs_f64();
+        report.generated_at_unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        report
+    }
+}
+
+fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn escape_html(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_report_round_trips() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("reports/run.json");
+        let reporter = RunReporter::new();
+        reporter.set_counter("tx", "submitted", 12);
+        reporter.record_expectation("consensus liveness", Severity::Hard, &Ok(()));
+        reporter.record_expectation("da pruning", Severity::Soft, &Err("not pruned".into()));
+
+        reporter
+            .snapshot()
+            .write(&path, ReportFormat::Json)
+            .expect("write report");
+        let report = RunReport::read(&path).expect("read report");
+
+        assert_eq!(report.workloads["tx"]["submitted"], 12);
+        assert_eq!(report.expectations.len(), 2);
+        assert_eq!(report.expectations[1].error.as_deref(), Some("not pruned"));
+        assert!(report.passed());
+        assert_eq!(report.warnings().count(), 1);
+    }
+
+    #[test]
+    fn reports_without_newer_sections_still_load() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("run.json");
+        fs::write(
+            &path,
+            r#"{"generated_at_unix_secs":1,"elapsed_secs":2.5,"readiness":[],"workloads":{},
+            "expectations":[],"checkpoints":[],"node_events":[]}"#,
+        )
+        .expect("write report");
+
+        let report = RunReport::read(&path).expect("read report");
+        assert!(report.deployment.is_empty());
+        assert!(report.chaos_timeline.is_empty());
+        assert!(report.error.is_none());
+    }
+
+    #[test]
+    fn run_error_fails_the_report() {
+        let reporter = RunReporter::new();
+        reporter.record_expectation("consensus liveness", Severity::Hard, &Ok(()));
+        reporter.record_error(&"workload failure: boom");
+        reporter.record_error(&"run interrupted by SIGINT");
+
+        let report = reporter.snapshot();
+
+        assert!(!report.passed());
+        assert_eq!(report.error.as_deref(), Some("workload failure: boom"));
+        assert!(report.to_html().contains("error: workload failure: boom"));
+    }
+
+    #[test]
+    fn read_reports_missing_and_malformed_files() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let missing = dir.path().join("missing.json");
+        assert!(matches!(
+            RunReport::read(&missing),
+            Err(ReportError::Io { path, .. }) if path == missing
+        ));
+
+        let html = dir.path().join("run.html");
+        RunReport::default()
+            .write(&html, ReportFormat::Html)
+            .expect("write report");
+        assert!(matches!(
+            RunReport::read(&html),
+            Err(ReportError::Parse { path, .. }) if path == html
+        ));
+    }
+}
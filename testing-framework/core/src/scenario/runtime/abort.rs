@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use tokio::sync::watch;
+
+/// Lets deployer-side monitors stop a run before its duration elapses.
+///
+/// The runner watches the signal while workloads execute and fails the run
+/// with [`super::ScenarioError::Aborted`] once it fires. Only the first reason
+/// is kept. Clones share the same signal.
+#[derive(Clone)]
+pub struct RunAbort {
+    sender: Arc<watch::Sender<Option<String>>>,
+}
+
+impl Default for RunAbort {
+    fn default() -> Self {
+        let (sender, _) = watch::channel(None);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+}
+
+impl RunAbort {
+    /// Request the run to stop with `reason`.
+    pub fn abort(&self, reason: impl Into<String>) {
+        let reason = reason.into();
+        self.sender.send_if_modified(|current| {
+            if current.is_some() {
+                return false;
+            }
+            *current = Some(reason);
+            true
+        });
+    }
+
+    /// Reason the run was aborted with, if it was.
+    #[must_use]
+    pub fn reason(&self) -> Option<String> {
+        self.sender.borrow().clone()
+    }
+
+    /// Resolves with the abort reason once the run is aborted.
+    pub async fn aborted(&self) -> String {
+        let mut receiver = self.sender.subscribe();
+        let reason = receiver
+            .wait_for(Option::is_some)
+            .await
+            .expect("abort sender is kept alive by self");
+        reason.clone().unwrap_or_default()
+    }
+}
@@ -0,0 +1,521 @@
+use std::{
+    io,
+    net::Ipv4Addr,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use rand::{Rng as _, thread_rng};
+use reqwest::{Url, url::Host};
+use serde::Serialize;
+use thiserror::Error;
+use tokio::{
+    io::{AsyncReadExt as _, AsyncWriteExt as _, copy_bidirectional},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+    time::sleep,
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    nodes::{ApiClient, HttpOptionsError},
+    scenario::NodeClients,
+};
+
+/// Request heads larger than this are forwarded without waiting for the end
+/// of the head.
+const MAX_REQUEST_HEAD: usize = 64 * 1024;
+const INJECTED_ERROR_RESPONSE: &[u8] =
+    b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+
+/// Faults injected into the node API traffic of workloads, expectations and
+/// the block feed.
+///
+/// Each request rolls independently: its connection is reset, else it is
+/// answered with a `500` without reaching the node, else it is forwarded,
+/// optionally after `latency`. Only `http://` node URLs can be proxied.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ApiFaults {
+    latency: Duration,
+    latency_rate: f64,
+    error_rate: f64,
+    reset_rate: f64,
+}
+
+enum Fault {
+    Reset,
+    Error,
+    Delay(Duration),
+    Pass,
+}
+
+impl ApiFaults {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            latency_rate: 0.0,
+            error_rate: 0.0,
+            reset_rate: 0.0,
+        }
+    }
+
+    #[must_use]
+    /// Delay a `rate` fraction of forwarded requests by `latency` (panics
+    /// unless `rate` is in `[0, 1]`).
+    pub fn with_latency(mut self, latency: Duration, rate: f64) -> Self {
+        self.latency = latency;
+        self.latency_rate = checked_rate(rate, "latency");
+        self
+    }
+
+    #[must_use]
+    /// Answer a `rate` fraction of requests with `500 Internal Server Error`
+    /// (panics unless `rate` is in `[0, 1]`).
+    pub fn with_error_rate(mut self, rate: f64) -> Self {
+        self.error_rate = checked_rate(rate, "error");
+        self
+    }
+
+    #[must_use]
+    /// Close the connection of a `rate` fraction of requests without
+    /// answering (panics unless `rate` is in `[0, 1]`).
+    pub fn with_reset_rate(mut self, rate: f64) -> Self {
+        self.reset_rate = checked_rate(rate, "reset");
+        self
+    }
+
+    #[must_use]
+    /// Whether no request would ever be affected.
+    pub fn is_noop(&self) -> bool {
+        self.reset_rate == 0.0
+            && self.error_rate == 0.0
+            && (self.latency.is_zero() || self.latency_rate == 0.0)
+    }
+
+    fn roll(&self) -> Fault {
+        let mut rng = thread_rng();
+        if rng.gen_bool(self.reset_rate) {
+            Fault::Reset
+        } else if rng.gen_bool(self.error_rate) {
+            Fault::Error
+        } else if !self.latency.is_zero() && rng.gen_bool(self.latency_rate) {
+            Fault::Delay(self.latency)
+        } else {
+            Fault::Pass
+        }
+    }
+}
+
+fn checked_rate(rate: f64, fault: &str) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&rate),
+        "{fault} rate must be in [0, 1], got {rate}"
+    );
+    rate
+}
+
+/// What the fault proxies did with the requests they received.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct ApiFaultStats {
+    /// Requests passed on to a node, delayed ones included.
+    pub forwarded: u64,
+    pub delayed: u64,
+    pub errors: u64,
+    pub resets: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    forwarded: AtomicU64,
+    delayed: AtomicU64,
+    errors: AtomicU64,
+    resets: AtomicU64,
+}
+
+/// Failures starting the fault proxies.
+#[derive(Debug, Error)]
+pub enum FaultProxyError {
+    #[error("cannot proxy {url}: only http:// node URLs are supported")]
+    UnsupportedUrl { url: Url },
+    #[error("failed to bind fault proxy for {url}: {source}")]
+    Bind {
+        url: Url,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to build proxied HTTP client: {source}")]
+    Client {
+        #[source]
+        source: HttpOptionsError,
+    },
+}
+
+/// Local proxies sitting between the run's API clients and the nodes.
+/// Dropping the handle stops accepting connections.
+pub struct ApiFaultProxies {
+    faults: ApiFaults,
+    counters: Arc<Counters>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl ApiFaultProxies {
+    /// Route `clients` through fault proxies when `faults` would affect any
+    /// request; otherwise hand them back unchanged.
+    pub async fn wrap(
+        clients: NodeClients,
+        faults: Option<ApiFaults>,
+    ) -> Result<(NodeClients, Option<Self>), FaultProxyError> {
+        let Some(faults) = faults.filter(|faults| !faults.is_noop()) else {
+            return Ok((clients, None));
+        };
+        let (clients, proxies) = Self::spawn(&clients, faults).await?;
+        Ok((clients, Some(proxies)))
+    }
+
+    /// Start a proxy for every API and testing endpoint of `clients` and
+    /// return clients going through them. The returned clients keep the
+    /// headers and auth of the originals.
+    pub async fn spawn(
+        clients: &NodeClients,
+        faults: ApiFaults,
+    ) -> Result<(NodeClients, Self), FaultProxyError> {
+        let mut proxies = Self {
+            faults,
+            counters: Arc::default(),
+            tasks: Vec::new(),
+        };
+
+        let mut validators = Vec::new();
+        for client in clients.validator_clients() {
+            validators.push(proxies.proxy(client).await?);
+        }
+        let mut executors = Vec::new();
+        for client in clients.executor_clients() {
+            executors.push(proxies.proxy(client).await?);
+        }
+        info!(
+            proxies = proxies.tasks.len(),
+            faults = ?faults,
+            "node API fault proxies started"
+        );
+        Ok((NodeClients::new(validators, executors), proxies))
+    }
+
+    #[must_use]
+    pub const fn faults(&self) -> &ApiFaults {
+        &self.faults
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> ApiFaultStats {
+        ApiFaultStats {
+            forwarded: self.counters.forwarded.load(Ordering::Relaxed),
+            delayed: self.counters.delayed.load(Ordering::Relaxed),
+            errors: self.counters.errors.load(Ordering::Relaxed),
+            resets: self.counters.resets.load(Ordering::Relaxed),
+        }
+    }
+
+    async fn proxy(&mut self, client: &ApiClient) -> Result<ApiClient, FaultProxyError> {
+        let options = client.http_options();
+        // One request per connection, so every request rolls its own fault.
+        let http = options
+            .client_builder()
+            .and_then(|builder| {
+                builder
+                    .pool_max_idle_per_host(0)
+                    .build()
+                    .map_err(|source| HttpOptionsError::Client { source })
+            })
+            .map_err(|source| FaultProxyError::Client { source })?;
+        let base_url = self.listen(&client.base_url).await?;
+        let testing_url = match &client.testing_url {
+            Some(url) => Some(self.listen(url).await?),
+            None => None,
+        };
+        Ok(ApiClient::from_urls_with_client(
+            base_url,
+            testing_url,
+            http,
+            options.clone(),
+        ))
+    }
+
+    async fn listen(&mut self, upstream: &Url) -> Result<Url, FaultProxyError> {
+        let unsupported = || FaultProxyError::UnsupportedUrl {
+            url: upstream.clone(),
+        };
+        if upstream.scheme() != "http" {
+            return Err(unsupported());
+        }
+        let host = upstream_host(upstream).ok_or_else(unsupported)?;
+        let port = upstream.port_or_known_default().ok_or_else(unsupported)?;
+
+        let bind_error = |source| FaultProxyError::Bind {
+            url: upstream.clone(),
+            source,
+        };
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .map_err(bind_error)?;
+        let local = listener.local_addr().map_err(bind_error)?;
+
+        let mut url = upstream.clone();
+        url.set_host(Some("127.0.0.1"))
+            .expect("http URLs accept an IP host");
+        url.set_port(Some(local.port()))
+            .expect("http URLs accept a port");
+        debug!(%upstream, proxy = %url, "node API fault proxy listening");
+
+        let upstream = Arc::new(Upstream { host, port });
+        self.tasks.push(tokio::spawn(serve(
+            listener,
+            upstream,
+            self.faults,
+            Arc::clone(&self.counters),
+        )));
+        Ok(url)
+    }
+}
+
+impl Drop for ApiFaultProxies {
+    fn drop(&mut self) {
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+struct Upstream {
+    host: String,
+    port: u16,
+}
+
+/// Host of `url` as a socket address host, i.e. IPv6 addresses without the
+/// brackets of their URL form.
+fn upstream_host(url: &Url) -> Option<String> {
+    Some(match url.host()? {
+        Host::Domain(domain) => domain.to_owned(),
+        Host::Ipv4(addr) => addr.to_string(),
+        Host::Ipv6(addr) => addr.to_string(),
+    })
+}
+
+async fn serve(
+    listener: TcpListener,
+    upstream: Arc<Upstream>,
+    faults: ApiFaults,
+    counters: Arc<Counters>,
+) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle(
+                    stream,
+                    Arc::clone(&upstream),
+                    faults,
+                    Arc::clone(&counters),
+                ));
+            }
+            Err(err) => warn!(error = %err, "fault proxy failed to accept a connection"),
+        }
+    }
+}
+
+async fn handle(
+    mut client: TcpStream,
+    upstream: Arc<Upstream>,
+    faults: ApiFaults,
+    counters: Arc<Counters>,
+) {
+    // Wait for the request so faults hit requests, not idle connections.
+    let Ok(head) = read_request_head(&mut client).await else {
+        return;
+    };
+
+    match faults.roll() {
+        Fault::Reset => {
+            counters.resets.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        Fault::Error => {
+            counters.errors.fetch_add(1, Ordering::Relaxed);
+            let _ = client.write_all(INJECTED_ERROR_RESPONSE).await;
+            let _ = client.shutdown().await;
+            return;
+        }
+        Fault::Delay(latency) => {
+            counters.delayed.fetch_add(1, Ordering::Relaxed);
+            sleep(latency).await;
+        }
+        Fault::Pass => {}
+    }
+
+    counters.forwarded.fetch_add(1, Ordering::Relaxed);
+    let mut node = match TcpStream::connect((upstream.host.as_str(), upstream.port)).await {
+        Ok(node) => node,
+        Err(err) => {
+            debug!(host = %upstream.host, port = upstream.port, error = %err, "fault proxy could not reach node");
+            return;
+        }
+    };
+    if node.write_all(&head).await.is_err() {
+        return;
+    }
+    let _ = copy_bidirectional(&mut client, &mut node).await;
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    let mut chunk = [0u8; 4096];
+    while head.len() < MAX_REQUEST_HEAD && !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&chunk[..read]);
+    }
+    if head.is_empty() {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(head)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use crate::nodes::HttpOptions;
+
+    /// Answers every request with an empty `200` and reports each request
+    /// head it received.
+    async fn node() -> (SocketAddr, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+            .await
+            .expect("bind node");
+        let addr = listener.local_addr().expect("node address");
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let Ok(head) = read_request_head(&mut stream).await else {
+                    continue;
+                };
+                let _ = sender.send(String::from_utf8_lossy(&head).into_owned());
+                let _ = stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await;
+            }
+        });
+        (addr, receiver)
+    }
+
+    fn clients(client: ApiClient) -> NodeClients {
+        NodeClients::new(vec![client], Vec::new())
+    }
+
+    #[test]
+    #[should_panic(expected = "error rate must be in [0, 1]")]
+    fn rates_outside_unit_interval_are_rejected() {
+        let _ = ApiFaults::new().with_error_rate(1.5);
+    }
+
+    #[test]
+    fn latency_without_rate_is_a_noop() {
+        assert!(ApiFaults::new().is_noop());
+        assert!(
+            ApiFaults::new()
+                .with_latency(Duration::from_secs(1), 0.0)
+                .is_noop()
+        );
+        assert!(!ApiFaults::new().with_reset_rate(0.1).is_noop());
+    }
+
+    #[test]
+    fn upstream_host_strips_ipv6_brackets() {
+        let url = |raw: &str| Url::parse(raw).expect("valid url");
+
+        assert_eq!(
+            upstream_host(&url("http://[::1]:8080/")).as_deref(),
+            Some("::1")
+        );
+        assert_eq!(
+            upstream_host(&url("http://127.0.0.1:8080/")).as_deref(),
+            Some("127.0.0.1")
+        );
+        assert_eq!(
+            upstream_host(&url("http://node-0:8080/")).as_deref(),
+            Some("node-0")
+        );
+    }
+
+    #[tokio::test]
+    async fn noop_faults_leave_clients_unproxied() {
+        let client = ApiClient::new(SocketAddr::from(([127, 0, 0, 1], 1)), None);
+        let (wrapped, proxies) =
+            ApiFaultProxies::wrap(clients(client.clone()), Some(ApiFaults::new()))
+                .await
+                .expect("no proxies needed");
+
+        assert!(proxies.is_none());
+        assert_eq!(wrapped.validator_clients()[0].base_url(), client.base_url());
+    }
+
+    #[tokio::test]
+    async fn injected_errors_never_reach_the_node() {
+        let (addr, mut requests) = node().await;
+        let faults = ApiFaults::new().with_error_rate(1.0);
+        let (proxied, proxies) =
+            ApiFaultProxies::spawn(&clients(ApiClient::new(addr, None)), faults)
+                .await
+                .expect("proxies start");
+
+        let response = proxied.validator_clients()[0]
+            .get_response("/cryptarchia/info")
+            .await
+            .expect("proxy answers");
+
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(proxies.stats().errors, 1);
+        assert_eq!(proxies.stats().forwarded, 0);
+        assert!(requests.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn forwarded_requests_keep_client_headers() {
+        let (addr, mut requests) = node().await;
+        let base_url = Url::parse(&format!("http://{addr}/")).expect("node url");
+        let client = ApiClient::from_urls_with_options(
+            base_url,
+            None,
+            &HttpOptions::new().with_bearer_token("secret"),
+        )
+        .expect("client builds");
+        let faults = ApiFaults::new().with_latency(Duration::from_millis(1), 1.0);
+        let (proxied, proxies) = ApiFaultProxies::spawn(&clients(client), faults)
+            .await
+            .expect("proxies start");
+
+        let response = proxied.validator_clients()[0]
+            .get_response("/cryptarchia/info")
+            .await
+            .expect("node answers");
+
+        assert!(response.status().is_success());
+        let head = requests.recv().await.expect("node saw the request");
+        assert!(
+            head.to_ascii_lowercase()
+                .contains("authorization: bearer secret"),
+            "{head}"
+        );
+        let stats = proxies.stats();
+        assert_eq!((stats.forwarded, stats.delayed), (1, 1));
+    }
+}
@@ -0,0 +1,421 @@
+use std::time::Instant;
+
+use async_trait::async_trait;
+use reqwest::Url;
+use thiserror::Error;
+use tracing::{debug, info};
+
+use super::{
+    api_faults::{ApiFaultProxies, FaultProxyError},
+    block_feed::spawn_failover_block_feed,
+    context::{CleanupGuard, RunContext},
+    deployer::Deployer,
+    metrics::{Metrics, MetricsError},
+    node_clients::NodeClients,
+    runner::Runner,
+    timeline::DeploymentTimeline,
+};
+use crate::{
+    nodes::{ApiClient, HttpOptions, HttpOptionsError},
+    scenario::{DynError, Scenario},
+    topology::readiness::{
+        ApiReadiness, ReadinessCheck as _, ReadinessConfig, ReadinessError,
+        wait_transaction_readiness,
+    },
+};
+
+/// API endpoints of a node that is already running.
+#[derive(Clone, Debug)]
+pub struct AttachedNode {
+    pub base_url: Url,
+    pub testing_url: Option<Url>,
+}
+
+impl AttachedNode {
+    #[must_use]
+    pub const fn new(base_url: Url, testing_url: Option<Url>) -> Self {
+        Self {
+            base_url,
+            testing_url,
+        }
+    }
+}
+
+/// Errors surfaced by the attach deployer.
+#[derive(Debug, Error)]
+pub enum AttachDeployerError {
+    #[error("scenario topology has {expected} {role}s but {actual} endpoints were given")]
+    NodeCountMismatch {
+        role: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("late-joining nodes cannot be started on an attached cluster ({count} declared)")]
+    LateJoinUnsupported { count: usize },
+    #[error("readiness probe failed: {source}")]
+    Readiness {
+        #[source]
+        source: ReadinessError,
+    },
+    #[error(transparent)]
+    Telemetry(#[from] MetricsError),
+    #[error("failed to build node API clients: {source}")]
+    HttpClient {
+        #[source]
+        source: HttpOptionsError,
+    },
+    #[error("failed to initialize block feed: {source}")]
+    BlockFeed {
+        #[source]
+        source: DynError,
+    },
+    #[error(transparent)]
+    ApiFaults(#[from] FaultProxyError),
+}
+
+/// Runs a scenario against nodes that are already running instead of
+/// deploying new ones.
+///
+/// Endpoints are given in role order, so `validator-1` resolves to the second
+/// validator endpoint; the scenario's topology may declare fewer nodes than
+/// there are endpoints but not more, since its extra targets would resolve to
+/// no node. Its generated configs are never deployed, so workloads
+/// relying on generated keys only work against a cluster started from that
+/// topology: pair with [`crate::scenario::Builder::topology_from_generated`]
+/// for those. Nothing is torn down when the run ends, and node control is not
+/// available.
+#[derive(Clone, Debug, Default)]
+pub struct AttachDeployer {
+    validators: Vec<AttachedNode>,
+    executors: Vec<AttachedNode>,
+    readiness_checks: bool,
+    topology_readiness: bool,
+    readiness: ReadinessConfig,
+    prometheus_url: Option<Url>,
+    http: HttpOptions,
+}
+
+impl AttachDeployer {
+    #[must_use]
+    /// Construct with no endpoints; readiness checks are disabled because the
+    /// cluster is assumed to be up already.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Add the next validator's endpoints.
+    pub fn with_validator(mut self, base_url: Url, testing_url: Option<Url>) -> Self {
+        self.validators
+            .push(AttachedNode::new(base_url, testing_url));
+        self
+    }
+
+    #[must_use]
+    /// Add the next executor's endpoints.
+    pub fn with_executor(mut self, base_url: Url, testing_url: Option<Url>) -> Self {
+        self.executors
+            .push(AttachedNode::new(base_url, testing_url));
+        self
+    }
+
+    #[must_use]
+    /// Wait until every node answers its API before handing control to
+    /// workloads.
+    pub const fn with_readiness(mut self, enabled: bool) -> Self {
+        self.readiness_checks = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Also probe network and membership readiness against the scenario's
+    /// topology. Only meaningful when the cluster was started from that
+    /// topology, and requires the endpoints to match its node counts
+    /// exactly.
+    pub const fn with_topology_readiness(mut self, enabled: bool) -> Self {
+        self.topology_readiness = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for each readiness check.
+    pub const fn with_readiness_config(mut self, readiness: ReadinessConfig) -> Self {
+        self.readiness = readiness;
+        self
+    }
+
+    #[must_use]
+    /// Query the Prometheus instance scraping the cluster for telemetry.
+    pub fn with_prometheus(mut self, url: Url) -> Self {
+        self.prometheus_url = Some(url);
+        self
+    }
+
+    #[must_use]
+    /// TLS and auth settings for every request to the attached nodes, e.g.
+    /// a bearer token for an authenticating ingress.
+    pub fn with_http_options(mut self, http: HttpOptions) -> Self {
+        self.http = http;
+        self
+    }
+
+    fn check_topology(&self, scenario: &Scenario) -> Result<(), AttachDeployerError> {
+        let topology = scenario.topology();
+        let late_joins = topology.late_joins().len();
+        if late_joins > 0 {
+            return Err(AttachDeployerError::LateJoinUnsupported { count: late_joins });
+        }
+        for (role, expected, actual) in [
+            (
+                "validator",
+                topology.validators().len(),
+                self.validators.len(),
+            ),
+            ("executor", topology.executors().len(), self.executors.len()),
+        ] {
+            let mismatch = if self.topology_readiness {
+                expected != actual
+            } else {
+                expected > actual
+            };
+            if mismatch {
+                return Err(AttachDeployerError::NodeCountMismatch {
+                    role,
+                    expected,
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn node_clients(&self) -> Result<NodeClients, HttpOptionsError> {
+        let clients = |nodes: &[AttachedNode]| {
+            nodes
+                .iter()
+                .map(|node| {
+                    ApiClient::from_urls_with_options(
+                        node.base_url.clone(),
+                        node.testing_url.clone(),
+                        &self.http,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(NodeClients::new(
+            clients(&self.validators)?,
+            clients(&self.executors)?,
+        ))
+    }
+
+    async fn wait_for_api_readiness(
+        node_clients: &NodeClients,
+        readiness: ReadinessConfig,
+    ) -> Result<(), ReadinessError> {
+        let clients = node_clients
+            .targeted_clients()
+            .map(|(target, client)| (target.to_string(), client.clone()))
+            .collect::<Vec<_>>();
+        ApiReadiness {
+            clients: &clients,
+            config: readiness,
+        }
+        .wait()
+        .await
+    }
+
+    async fn wait_for_topology_readiness(
+        &self,
+        scenario: &Scenario,
+        readiness: ReadinessConfig,
+    ) -> Result<(), ReadinessError> {
+        let base_urls =
+            |nodes: &[AttachedNode]| nodes.iter().map(|n| n.base_url.clone()).collect::<Vec<_>>();
+        let testing_urls = |nodes: &[AttachedNode]| {
+            nodes
+                .iter()
+                .map(|n| n.testing_url.clone())
+                .collect::<Option<Vec<_>>>()
+        };
+        let validator_testing = testing_urls(&self.validators);
+        let executor_testing = testing_urls(&self.executors);
+
+        info!("waiting for attached cluster readiness");
+        scenario
+            .topology()
+            .wait_remote_readiness_with_options(
+                &base_urls(&self.validators),
+                &base_urls(&self.executors),
+                validator_testing.as_deref(),
+                executor_testing.as_deref(),
+                readiness,
+                &self.http,
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl Deployer for AttachDeployer {
+    type Error = AttachDeployerError;
+
+    async fn deploy(&self, scenario: &Scenario) -> Result<Runner, Self::Error> {
+        self.check_topology(scenario)?;
+        info!(
+            validators = self.validators.len(),
+            executors = self.executors.len(),
+            readiness_checks = self.readiness_checks,
+            topology_readiness = self.topology_readiness,
+            "attaching to running cluster"
+        );
+
+        let telemetry = self
+            .prometheus_url
+            .clone()
+            .map_or_else(|| Ok(Metrics::empty()), Metrics::from_prometheus)?;
+        let node_clients = self
+            .node_clients()
+            .map_err(|source| AttachDeployerError::HttpClient { source })?;
+        let readiness = self.readiness.or_timings(scenario.timings());
+
+        let startup = Instant::now();
+        let mut timeline = DeploymentTimeline::new();
+        if self.readiness_checks {
+            timeline
+                .measure(
+                    "readiness",
+                    Self::wait_for_api_readiness(&node_clients, readiness),
+                )
+                .await
+                .map_err(|source| AttachDeployerError::Readiness { source })?;
+        }
+        if self.topology_readiness {
+            timeline
+                .measure(
+                    "topology_readiness",
+                    self.wait_for_topology_readiness(scenario, readiness),
+                )
+                .await
+                .map_err(|source| AttachDeployerError::Readiness { source })?;
+        }
+        if scenario.transaction_readiness() {
+            timeline
+                .measure(
+                    "transaction_readiness",
+                    wait_transaction_readiness(&node_clients, scenario.topology(), readiness),
+                )
+                .await
+                .map_err(|source| AttachDeployerError::Readiness { source })?;
+        }
+        let startup_elapsed = startup.elapsed();
+
+        let (node_clients, api_faults) =
+            ApiFaultProxies::wrap(node_clients, scenario.api_faults()).await?;
+        let block_source = node_clients.random_validator().cloned().ok_or_else(|| {
+            AttachDeployerError::BlockFeed {
+                source: "block feed requires at least one validator".into(),
+            }
+        })?;
+        debug!(url = %block_source.base_url(), "attaching block feed");
+        let (block_feed, block_feed_guard) = timeline
+            .measure(
+                "block_feed",
+                spawn_failover_block_feed(block_source, node_clients.validator_clients().to_vec()),
+            )
+            .await
+            .map_err(|source| AttachDeployerError::BlockFeed {
+                source: source.into(),
+            })?;
+
+        let context = RunContext::new(
+            scenario.topology().clone(),
+            None,
+            node_clients,
+            scenario.duration(),
+            telemetry,
+            block_feed,
+            None,
+        )
+        .with_deployment_timeline(timeline)
+        .with_api_faults(api_faults);
+        context
+            .report()
+            .record_readiness("attached_cluster", startup_elapsed);
+
+        info!("attached cluster ready; handing control to scenario runner");
+        let cleanup: Box<dyn CleanupGuard> = Box::new(block_feed_guard);
+        Ok(Runner::new(context, Some(cleanup)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::scenario::ScenarioBuilder;
+
+    fn url(port: u16) -> Url {
+        Url::parse(&format!("http://127.0.0.1:{port}/")).expect("valid url")
+    }
+
+    fn attached(validators: u16, executors: u16) -> AttachDeployer {
+        let deployer = (0..validators).fold(AttachDeployer::new(), |deployer, port| {
+            deployer.with_validator(url(8000 + port), None)
+        });
+        (0..executors).fold(deployer, |deployer, port| {
+            deployer.with_executor(url(9000 + port), None)
+        })
+    }
+
+    fn scenario(validators: usize, executors: usize) -> Scenario {
+        ScenarioBuilder::topology_with(|t| t.validators(validators).executors(executors)).build()
+    }
+
+    #[test]
+    fn topology_may_declare_fewer_nodes_than_endpoints() {
+        let deployer = attached(3, 1);
+
+        assert!(deployer.check_topology(&scenario(1, 0)).is_ok());
+        assert!(deployer.check_topology(&scenario(2, 1)).is_ok());
+        let err = deployer
+            .check_topology(&scenario(4, 1))
+            .expect_err("validator-3 would resolve to no endpoint");
+        assert!(matches!(
+            err,
+            AttachDeployerError::NodeCountMismatch {
+                role: "validator",
+                expected: 4,
+                actual: 3,
+            }
+        ));
+    }
+
+    #[test]
+    fn topology_readiness_requires_matching_counts() {
+        let deployer = attached(3, 1).with_topology_readiness(true);
+
+        assert!(deployer.check_topology(&scenario(3, 1)).is_ok());
+        assert!(matches!(
+            deployer.check_topology(&scenario(2, 1)),
+            Err(AttachDeployerError::NodeCountMismatch {
+                role: "validator",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn late_joiners_are_rejected() {
+        let scenario = ScenarioBuilder::topology_with(|t| {
+            t.validators(2)
+                .late_join_validator(1, Duration::from_secs(30))
+        })
+        .build();
+
+        assert!(matches!(
+            attached(2, 0).check_topology(&scenario),
+            Err(AttachDeployerError::LateJoinUnsupported { count: 1 })
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     sync::{
-        Arc,
+        Arc, Mutex,
         atomic::{AtomicU64, Ordering},
     },
     time::Duration,
@@ -11,13 +11,26 @@ use anyhow::{Context as _, Result};
 use nomos_core::{block::Block, mantle::SignedMantleTx};
 use nomos_http_api_common::paths::STORAGE_BLOCK;
 use nomos_node::HeaderId;
-use tokio::{sync::broadcast, task::JoinHandle, time::sleep};
-use tracing::{debug, error};
+use reqwest::Url;
+use serde::Serialize;
+use tokio::{
+    sync::broadcast,
+    task::JoinHandle,
+    time::{Instant, sleep, timeout},
+};
+use tracing::{debug, error, info, warn};
 
-use super::context::CleanupGuard;
+use super::{context::CleanupGuard, da_sampling::PublishedBlobs};
 use crate::nodes::ApiClient;
 
 const POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Consecutive failed polls after which the feed switches to another source.
+const MAX_SOURCE_FAILURES: u32 = 3;
+/// Time without a new block after which the feed asks the other sources
+/// whether the chain moved on without its source.
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+/// Time a candidate source may take to answer a consensus info request.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
 
 /// Broadcasts observed blocks to subscribers while tracking simple stats.
 #[derive(Clone)]
@@ -35,6 +48,42 @@ struct BlockFeedInner {
 pub struct BlockRecord {
     pub header: HeaderId,
     pub block: Arc<Block<SignedMantleTx>>,
+    /// Set on the blocks caught up right after the feed switched sources.
+    /// They arrive late by up to the gap's duration.
+    pub gap: Option<Arc<FeedGap>>,
+}
+
+/// Why the feed abandoned its source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailoverReason {
+    /// The source failed several polls in a row.
+    Unreachable,
+    /// The source stopped producing blocks while another node moved past it.
+    Stalled,
+}
+
+impl FailoverReason {
+    /// Whether a source at `candidate_height` can take over from a feed that
+    /// reached `feed_height`: any answering source replaces an unreachable
+    /// one, but a stall only moves to a source that is ahead.
+    const fn accepts(self, candidate_height: u64, feed_height: u64) -> bool {
+        match self {
+            Self::Unreachable => true,
+            Self::Stalled => candidate_height > feed_height,
+        }
+    }
+}
+
+/// A switch of the feed from one source validator to another.
+#[derive(Clone, Debug)]
+pub struct FeedGap {
+    pub from: Url,
+    pub to: Url,
+    pub reason: FailoverReason,
+    /// Time since the previous source last delivered a new block.
+    pub duration: Duration,
+    /// Height the feed had reached on the previous source.
+    pub height: u64,
 }
 
 /// Join handle for the background block feed task.
@@ -53,13 +102,28 @@ impl BlockFeed {
         Arc::clone(&self.inner.stats)
     }
 
-    fn ingest(&self, header: HeaderId, block: Block<SignedMantleTx>) {
-        self.inner.stats.record_block(&block);
-        let record = Arc::new(BlockRecord {
+    /// Feed without a scanner; blocks only arrive through
+    /// [`Self::publish`].
+    pub(super) fn detached() -> Self {
+        let (sender, _) = broadcast::channel(1024);
+        Self {
+            inner: Arc::new(BlockFeedInner {
+                sender,
+                stats: Arc::new(BlockStats::default()),
+            }),
+        }
+    }
+
+    fn ingest(&self, header: HeaderId, block: Block<SignedMantleTx>, gap: Option<Arc<FeedGap>>) {
+        self.publish(Arc::new(BlockRecord {
             header,
             block: Arc::new(block),
-        });
+            gap,
+        }));
+    }
 
+    pub(super) fn publish(&self, record: Arc<BlockRecord>) {
+        self.inner.stats.record_block(&record);
         let _ = self.inner.sender.send(record);
     }
 }
@@ -75,15 +139,29 @@ impl BlockFeedTask {
 /// Spawn a background task to poll blocks from the given client and broadcast
 /// them.
 pub async fn spawn_block_feed(client: ApiClient) -> Result<(BlockFeed, BlockFeedTask)> {
-    let (sender, _) = broadcast::channel(1024);
-    let feed = BlockFeed {
-        inner: Arc::new(BlockFeedInner {
-            sender,
-            stats: Arc::new(BlockStats::default()),
-        }),
-    };
-
-    let mut scanner = BlockScanner::new(client, feed.clone());
+    spawn_failover_block_feed(client, Vec::new()).await
+}
+
+/// Like [`spawn_block_feed`], but switch to one of `fallbacks` when `primary`
+/// stops answering or stalls behind the rest of the cluster. Blocks caught up
+/// after a switch carry a [`FeedGap`], which is also kept in
+/// [`BlockStats::gaps`].
+pub async fn spawn_failover_block_feed(
+    primary: ApiClient,
+    fallbacks: impl IntoIterator<Item = ApiClient>,
+) -> Result<(BlockFeed, BlockFeedTask)> {
+    let feed = BlockFeed::detached();
+
+    let mut sources = vec![primary];
+    for client in fallbacks {
+        if sources
+            .iter()
+            .all(|source| source.base_url() != client.base_url())
+        {
+            sources.push(client);
+        }
+    }
+    let mut scanner = BlockScanner::new(sources, feed.clone());
     scanner.catch_up().await?;
 
     let handle = tokio::spawn(async move { scanner.run().await });
@@ -92,31 +170,116 @@ pub async fn spawn_block_feed(client: ApiClient) -> Result<(BlockFeed, BlockFeed
 }
 
 struct BlockScanner {
-    client: ApiClient,
+    sources: Vec<ApiClient>,
+    active: usize,
     feed: BlockFeed,
     seen: HashSet<HeaderId>,
+    height: u64,
+    failures: u32,
+    last_progress: Instant,
+    /// Earliest time the sources are probed again for a stall: pushed back
+    /// on progress and after a probe that found no source ahead.
+    next_stall_probe: Instant,
+    gap: Option<Arc<FeedGap>>,
 }
 
 impl BlockScanner {
-    fn new(client: ApiClient, feed: BlockFeed) -> Self {
+    fn new(sources: Vec<ApiClient>, feed: BlockFeed) -> Self {
         Self {
-            client,
+            sources,
+            active: 0,
             feed,
             seen: HashSet::new(),
+            height: 0,
+            failures: 0,
+            last_progress: Instant::now(),
+            next_stall_probe: Instant::now() + STALL_TIMEOUT,
+            gap: None,
         }
     }
 
+    fn client(&self) -> &ApiClient {
+        &self.sources[self.active]
+    }
+
     async fn run(&mut self) {
         loop {
-            if let Err(err) = self.catch_up().await {
-                error!(error = %err, error_debug = ?err, "block feed catch up failed");
+            match self.catch_up().await {
+                Ok(()) => {
+                    self.failures = 0;
+                    if Instant::now() >= self.next_stall_probe {
+                        self.fail_over(FailoverReason::Stalled).await;
+                    }
+                }
+                Err(err) => {
+                    error!(
+                        source = %self.client().base_url(),
+                        error = %err,
+                        error_debug = ?err,
+                        "block feed catch up failed"
+                    );
+                    self.failures += 1;
+                    if self.failures >= MAX_SOURCE_FAILURES {
+                        self.fail_over(FailoverReason::Unreachable).await;
+                    }
+                }
             }
             sleep(POLL_INTERVAL).await;
         }
     }
 
+    /// Switch to the next source that answers and, for a stall, is ahead of
+    /// the feed. Keeps the current source when none qualifies, and waits for
+    /// another stall timeout or failure streak before probing again, so a
+    /// cluster-wide stall does not re-probe every source on each poll.
+    async fn fail_over(&mut self, reason: FailoverReason) {
+        let count = self.sources.len();
+        for offset in 1..count {
+            let candidate = (self.active + offset) % count;
+            let Ok(Ok(info)) =
+                timeout(PROBE_TIMEOUT, self.sources[candidate].consensus_info()).await
+            else {
+                continue;
+            };
+            if reason.accepts(info.height, self.height) {
+                self.switch_to(candidate, reason);
+                return;
+            }
+        }
+
+        debug!(?reason, "no other block feed source available");
+        match reason {
+            FailoverReason::Unreachable => self.failures = 0,
+            FailoverReason::Stalled => self.next_stall_probe = Instant::now() + STALL_TIMEOUT,
+        }
+    }
+
+    fn switch_to(&mut self, candidate: usize, reason: FailoverReason) {
+        let gap = FeedGap {
+            from: self.client().base_url().clone(),
+            to: self.sources[candidate].base_url().clone(),
+            reason,
+            duration: self.last_progress.elapsed(),
+            height: self.height,
+        };
+        warn!(
+            from = %gap.from,
+            to = %gap.to,
+            ?reason,
+            gap = ?gap.duration,
+            height = gap.height,
+            "block feed switching source"
+        );
+        self.feed.inner.stats.record_gap(gap.clone());
+        self.gap = Some(Arc::new(gap));
+        self.active = candidate;
+        self.failures = 0;
+        self.last_progress = Instant::now();
+        self.next_stall_probe = self.last_progress + STALL_TIMEOUT;
+    }
+
     async fn catch_up(&mut self) -> Result<()> {
-        let info = self.client.consensus_info().await?;
+        let info = self.client().consensus_info().await?;
         let tip = info.tip;
         let mut remaining_height = info.height;
         let mut stack = Vec::new();
@@ -132,12 +295,14 @@ impl BlockScanner {
                 break;
             }
 
-            let block = match self.client.storage_block(&cursor).await {
+            let block = match self.client().storage_block(&cursor).await {
                 Ok(block) => block,
                 Err(err) => {
                     if err.is_decode() {
-                        if let Ok(resp) =
-                            self.client.post_json_response(STORAGE_BLOCK, &cursor).await
+                        if let Ok(resp) = self
+                            .client()
+                            .post_json_response(STORAGE_BLOCK, &cursor)
+                            .await
                         {
                             if let Ok(body) = resp.text().await {
                                 error!(header = ?cursor, %body, "failed to decode block response");
@@ -161,11 +326,23 @@ impl BlockScanner {
         }
 
         let mut processed = 0usize;
+        let gap = self.gap.take();
         while let Some((header, block)) = stack.pop() {
-            self.feed.ingest(header, block);
+            self.feed.ingest(header, block, gap.clone());
             self.seen.insert(header);
             processed += 1;
         }
+        if processed == 0 {
+            // Nothing new on the new source yet; mark its first batch instead.
+            self.gap = gap;
+        } else {
+            self.last_progress = Instant::now();
+            self.next_stall_probe = self.last_progress + STALL_TIMEOUT;
+            if let Some(gap) = gap {
+                info!(source = %gap.to, processed, "block feed caught up after switching source");
+            }
+        }
+        self.height = self.height.max(info.height);
 
         debug!(processed, "block feed processed catch up batch");
         Ok(())
@@ -182,16 +359,220 @@ impl CleanupGuard for BlockFeedTask {
 #[derive(Default)]
 pub struct BlockStats {
     total_transactions: AtomicU64,
+    /// Number of blocks seen per transaction count.
+    txs_per_block: Mutex<BTreeMap<u64, u64>>,
+    published_blobs: PublishedBlobs,
+    gaps: Mutex<Vec<FeedGap>>,
+}
+
+/// Consensus throughput derived from the blocks the feed observed.
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct ConsensusThroughput {
+    pub blocks: u64,
+    pub transactions: u64,
+    /// Blocks without any transaction.
+    pub empty_blocks: u64,
+    /// Nearest-rank median of transactions per block.
+    pub p50_txs_per_block: u64,
+    pub p95_txs_per_block: u64,
+    pub max_txs_per_block: u64,
+}
+
+impl ConsensusThroughput {
+    /// Throughput of the blocks counted per transaction count in
+    /// `distribution`.
+    fn from_distribution(distribution: &BTreeMap<u64, u64>) -> Self {
+        let blocks = distribution.values().sum();
+        let percentile = |percentile: f64| {
+            let rank = (percentile / 100.0 * blocks as f64).ceil().max(1.0) as u64;
+            let mut seen = 0;
+            distribution
+                .iter()
+                .find(|(_, count)| {
+                    seen += **count;
+                    seen >= rank
+                })
+                .map_or(0, |(transactions, _)| *transactions)
+        };
+        Self {
+            blocks,
+            transactions: distribution
+                .iter()
+                .map(|(transactions, count)| transactions * count)
+                .sum(),
+            empty_blocks: distribution.get(&0).copied().unwrap_or(0),
+            p50_txs_per_block: percentile(50.0),
+            p95_txs_per_block: percentile(95.0),
+            max_txs_per_block: distribution.keys().next_back().copied().unwrap_or(0),
+        }
+    }
+
+    #[must_use]
+    /// Average transactions per block, `0.0` before the first block.
+    pub fn mean_txs_per_block(&self) -> f64 {
+        if self.blocks == 0 {
+            return 0.0;
+        }
+        self.transactions as f64 / self.blocks as f64
+    }
+
+    #[must_use]
+    /// Fraction of blocks without transactions, `0.0` before the first block.
+    pub fn empty_block_ratio(&self) -> f64 {
+        if self.blocks == 0 {
+            return 0.0;
+        }
+        self.empty_blocks as f64 / self.blocks as f64
+    }
 }
 
 impl BlockStats {
-    fn record_block(&self, block: &Block<SignedMantleTx>) {
+    fn record_block(&self, record: &BlockRecord) {
+        let transactions = record.block.transactions().len() as u64;
         self.total_transactions
-            .fetch_add(block.transactions().len() as u64, Ordering::Relaxed);
+            .fetch_add(transactions, Ordering::Relaxed);
+        *self
+            .txs_per_block
+            .lock()
+            .expect("block stats lock poisoned")
+            .entry(transactions)
+            .or_default() += 1;
+        self.published_blobs.observe_block(record);
+    }
+
+    fn record_gap(&self, gap: FeedGap) {
+        self.gaps
+            .lock()
+            .expect("block stats lock poisoned")
+            .push(gap);
+    }
+
+    #[must_use]
+    /// Every switch of the feed to another source, in order.
+    pub fn gaps(&self) -> Vec<FeedGap> {
+        self.gaps.lock().expect("block stats lock poisoned").clone()
+    }
+
+    #[must_use]
+    /// DA blobs published in the observed blocks.
+    pub const fn published_blobs(&self) -> &PublishedBlobs {
+        &self.published_blobs
     }
 
     #[must_use]
     pub fn total_transactions(&self) -> u64 {
         self.total_transactions.load(Ordering::Relaxed)
     }
+
+    #[must_use]
+    pub fn blocks(&self) -> u64 {
+        self.txs_per_block
+            .lock()
+            .expect("block stats lock poisoned")
+            .values()
+            .sum()
+    }
+
+    #[must_use]
+    /// Number of blocks seen for each transaction count, ordered by count.
+    pub fn txs_per_block(&self) -> BTreeMap<u64, u64> {
+        self.txs_per_block
+            .lock()
+            .expect("block stats lock poisoned")
+            .clone()
+    }
+
+    #[must_use]
+    pub fn throughput(&self) -> ConsensusThroughput {
+        ConsensusThroughput::from_distribution(&self.txs_per_block())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    fn client(port: u16) -> ApiClient {
+        ApiClient::new(SocketAddr::from(([127, 0, 0, 1], port)), None)
+    }
+
+    #[test]
+    fn throughput_uses_nearest_rank_percentiles() {
+        // 20 blocks: 10 empty, 9 with 3 transactions and 1 with 40.
+        let distribution = BTreeMap::from([(0, 10), (3, 9), (40, 1)]);
+
+        let throughput = ConsensusThroughput::from_distribution(&distribution);
+
+        assert_eq!(throughput.blocks, 20);
+        assert_eq!(throughput.transactions, 67);
+        assert_eq!(throughput.empty_blocks, 10);
+        assert_eq!(throughput.p50_txs_per_block, 0);
+        assert_eq!(throughput.p95_txs_per_block, 3);
+        assert_eq!(throughput.max_txs_per_block, 40);
+        assert!((throughput.mean_txs_per_block() - 3.35).abs() < 1e-9);
+        assert!((throughput.empty_block_ratio() - 0.5).abs() < f64::EPSILON);
+
+        let single = ConsensusThroughput::from_distribution(&BTreeMap::from([(5, 1)]));
+        assert_eq!(single.p50_txs_per_block, 5);
+        assert_eq!(single.p95_txs_per_block, 5);
+    }
+
+    #[test]
+    fn throughput_of_no_blocks_is_zero() {
+        let throughput = ConsensusThroughput::from_distribution(&BTreeMap::new());
+
+        assert_eq!(throughput.blocks, 0);
+        assert_eq!(throughput.p50_txs_per_block, 0);
+        assert_eq!(throughput.max_txs_per_block, 0);
+        assert!(throughput.mean_txs_per_block().abs() < f64::EPSILON);
+        assert_eq!(BlockStats::default().throughput().blocks, 0);
+    }
+
+    #[test]
+    fn stalled_feed_only_moves_to_sources_ahead() {
+        assert!(FailoverReason::Stalled.accepts(11, 10));
+        assert!(!FailoverReason::Stalled.accepts(10, 10));
+        assert!(FailoverReason::Unreachable.accepts(3, 10));
+    }
+
+    #[test]
+    fn switching_source_records_gap_for_next_batch() {
+        let feed = BlockFeed::detached();
+        let mut scanner = BlockScanner::new(vec![client(1), client(2)], feed.clone());
+        scanner.height = 12;
+        scanner.failures = MAX_SOURCE_FAILURES;
+
+        scanner.switch_to(1, FailoverReason::Unreachable);
+
+        assert_eq!(scanner.active, 1);
+        assert_eq!(scanner.failures, 0);
+        let gaps = feed.stats().gaps();
+        assert_eq!(gaps.len(), 1);
+        assert_eq!(gaps[0].from, *client(1).base_url());
+        assert_eq!(gaps[0].to, *client(2).base_url());
+        assert_eq!(gaps[0].reason, FailoverReason::Unreachable);
+        assert_eq!(gaps[0].height, 12);
+        let pending = scanner.gap.as_ref().expect("next batch carries the gap");
+        assert_eq!(pending.to, gaps[0].to);
+    }
+
+    #[tokio::test]
+    async fn fruitless_probe_backs_off() {
+        // Nothing listens on these ports, so no source qualifies.
+        let feed = BlockFeed::detached();
+        let mut scanner = BlockScanner::new(vec![client(1), client(2)], feed.clone());
+        scanner.next_stall_probe = Instant::now();
+
+        scanner.fail_over(FailoverReason::Stalled).await;
+        assert_eq!(scanner.active, 0);
+        assert!(scanner.next_stall_probe > Instant::now() + STALL_TIMEOUT / 2);
+
+        scanner.failures = MAX_SOURCE_FAILURES;
+        scanner.fail_over(FailoverReason::Unreachable).await;
+        assert_eq!(scanner.active, 0);
+        assert_eq!(scanner.failures, 0);
+        assert!(feed.stats().gaps().is_empty());
+    }
 }
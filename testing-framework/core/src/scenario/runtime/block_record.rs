@@ -0,0 +1,318 @@
+use std::{
+    fs::{self, File},
+    io::{self, BufRead as _, BufReader},
+    path::{Path, PathBuf},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use nomos_core::{block::Block, mantle::SignedMantleTx};
+use nomos_node::HeaderId;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use thiserror::Error;
+use tokio::{
+    fs::File as AsyncFile,
+    io::AsyncWriteExt as _,
+    sync::broadcast::{self, error::RecvError},
+    task::{JoinHandle, yield_now},
+    time::sleep,
+};
+use tracing::{debug, warn};
+
+use super::block_feed::{BlockFeed, BlockRecord};
+
+/// Failures recording or loading a block feed file.
+#[derive(Debug, Error)]
+pub enum BlockRecordError {
+    #[error("failed to open block record file {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid block record at {path}:{line}: {source}")]
+    Decode {
+        path: PathBuf,
+        line: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+/// One line of a block record file.
+#[derive(Serialize)]
+struct StoredRecordRef<'a> {
+    header: &'a HeaderId,
+    block: &'a Block<SignedMantleTx>,
+}
+
+#[derive(Deserialize)]
+struct StoredRecord {
+    header: HeaderId,
+    block: Block<SignedMantleTx>,
+}
+
+/// Writes every [`BlockRecord`] a feed broadcasts to a JSON lines file.
+///
+/// Only blocks broadcast after [`Self::spawn`] are written. Each line is
+/// flushed as it is written, so the file stays readable if the run is
+/// aborted. Recording stops when the recorder is dropped.
+pub struct BlockFeedRecorder {
+    path: PathBuf,
+    recorded: Arc<AtomicU64>,
+    handle: JoinHandle<()>,
+}
+
+impl BlockFeedRecorder {
+    /// Subscribe to `feed` and record into `path`, creating parent
+    /// directories and truncating an existing file.
+    pub fn spawn(feed: &BlockFeed, path: impl Into<PathBuf>) -> Result<Self, BlockRecordError> {
+        let path = path.into();
+        let io_err = |source| BlockRecordError::Io {
+            path: path.clone(),
+            source,
+        };
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent).map_err(io_err)?;
+        }
+        let file = File::create(&path).map_err(io_err)?;
+
+        let recorded = Arc::new(AtomicU64::new(0));
+        let handle = tokio::spawn(record_lines(
+            feed.subscribe(),
+            AsyncFile::from_std(file),
+            path.clone(),
+            Arc::clone(&recorded),
+            |record: &Arc<BlockRecord>| {
+                serde_json::to_string(&StoredRecordRef {
+                    header: &record.header,
+                    block: &record.block,
+                })
+            },
+        ));
+        Ok(Self {
+            path,
+            recorded,
+            handle,
+        })
+    }
+
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[must_use]
+    /// Number of blocks written so far.
+    pub fn recorded(&self) -> u64 {
+        self.recorded.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for BlockFeedRecorder {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// Write every value broadcast on `receiver` to `file` as one JSON line,
+/// flushing after each line, until the channel closes.
+async fn record_lines<T: Clone>(
+    mut receiver: broadcast::Receiver<T>,
+    mut file: AsyncFile,
+    path: PathBuf,
+    recorded: Arc<AtomicU64>,
+    encode: impl Fn(&T) -> serde_json::Result<String>,
+) {
+    loop {
+        let record = match receiver.recv().await {
+            Ok(record) => record,
+            Err(RecvError::Lagged(skipped)) => {
+                warn!(skipped, path = %path.display(), "block recorder lagged; blocks missing from record");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+
+        let written = match encode(&record) {
+            Ok(mut line) => {
+                line.push('\n');
+                write_line(&mut file, &line).await
+            }
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = written {
+            warn!(error = %err, path = %path.display(), "failed to record block; stopping recorder");
+            return;
+        }
+        recorded.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+async fn write_line(file: &mut AsyncFile, line: &str) -> io::Result<()> {
+    file.write_all(line.as_bytes()).await?;
+    file.flush().await
+}
+
+/// Decode every non-blank line of the JSON lines file at `path`.
+fn read_lines<T: DeserializeOwned>(path: &Path) -> Result<Vec<T>, BlockRecordError> {
+    let io_err = |source| BlockRecordError::Io {
+        path: path.to_path_buf(),
+        source,
+    };
+    let reader = BufReader::new(File::open(path).map_err(io_err)?);
+
+    let mut values = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(io_err)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value = serde_json::from_str(&line).map_err(|source| BlockRecordError::Decode {
+            path: path.to_path_buf(),
+            line: index + 1,
+            source,
+        })?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Drives a [`BlockFeed`] from a file written by [`BlockFeedRecorder`]
+/// instead of a live cluster.
+///
+/// Hand [`Self::feed`] to whatever subscribes (typically a [`RunContext`]
+/// built for the test), let expectations subscribe in `start_capture`, then
+/// call [`Self::replay`] to broadcast the recorded blocks in order.
+///
+/// [`RunContext`]: super::RunContext
+pub struct ReplayBlockFeed {
+    feed: BlockFeed,
+    records: Vec<Arc<BlockRecord>>,
+}
+
+impl ReplayBlockFeed {
+    /// Load every record from a JSON lines file; blank lines are skipped.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, BlockRecordError> {
+        let path = path.as_ref();
+        let records = read_lines::<StoredRecord>(path)?
+            .into_iter()
+            .map(|stored| BlockRecord {
+                header: stored.header,
+                block: Arc::new(stored.block),
+                gap: None,
+            })
+            .collect::<Vec<_>>();
+        debug!(path = %path.display(), blocks = records.len(), "loaded block record");
+        Ok(Self::from_records(records))
+    }
+
+    #[must_use]
+    pub fn from_records(records: Vec<BlockRecord>) -> Self {
+        Self {
+            feed: BlockFeed::detached(),
+            records: records.into_iter().map(Arc::new).collect(),
+        }
+    }
+
+    #[must_use]
+    /// Feed the recorded blocks are broadcast on.
+    pub fn feed(&self) -> BlockFeed {
+        self.feed.clone()
+    }
+
+    #[must_use]
+    pub fn records(&self) -> &[Arc<BlockRecord>] {
+        &self.records
+    }
+
+    /// Broadcast every recorded block, yielding between blocks so
+    /// subscribers keep up.
+    pub async fn replay(&self) {
+        for record in &self.records {
+            self.feed.publish(Arc::clone(record));
+            yield_now().await;
+        }
+    }
+
+    /// Broadcast every recorded block, waiting `interval` between blocks,
+    /// e.g. to emulate slot timing for expectations that poll.
+    pub async fn replay_with_interval(&self, interval: Duration) {
+        for record in &self.records {
+            self.feed.publish(Arc::clone(record));
+            sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct Line {
+        header: HeaderId,
+        transactions: u64,
+    }
+
+    fn line(n: u8) -> Line {
+        Line {
+            header: HeaderId::from([n; 32]),
+            transactions: u64::from(n) * 10,
+        }
+    }
+
+    #[tokio::test]
+    async fn recorded_lines_read_back_in_order() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("blocks.jsonl");
+        let file = AsyncFile::from_std(File::create(&path).expect("create record"));
+        let (sender, receiver) = broadcast::channel(8);
+        let recorded = Arc::new(AtomicU64::new(0));
+        let recorder = tokio::spawn(record_lines(
+            receiver,
+            file,
+            path.clone(),
+            Arc::clone(&recorded),
+            serde_json::to_string,
+        ));
+
+        for n in 1..=3 {
+            sender.send(line(n)).expect("recorder subscribed");
+        }
+        drop(sender);
+        recorder.await.expect("recorder task");
+
+        assert_eq!(recorded.load(Ordering::Relaxed), 3);
+        let lines: Vec<Line> = read_lines(&path).expect("read record");
+        assert_eq!(lines, vec![line(1), line(2), line(3)]);
+    }
+
+    #[test]
+    fn read_skips_blank_lines_and_reports_bad_line_numbers() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("blocks.jsonl");
+        let first = serde_json::to_string(&line(1)).expect("encode");
+        fs::write(&path, format!("{first}\n\n{first}\n")).expect("write record");
+        assert_eq!(
+            read_lines::<Line>(&path).expect("read record"),
+            vec![line(1), line(1)]
+        );
+
+        fs::write(&path, format!("{first}\n\nnot json\n")).expect("write record");
+        let err = read_lines::<Line>(&path).expect_err("third line is invalid");
+        assert!(matches!(err, BlockRecordError::Decode { line: 3, .. }));
+
+        let missing = dir.path().join("missing.jsonl");
+        assert!(matches!(
+            ReplayBlockFeed::load(&missing),
+            Err(BlockRecordError::Io { .. })
+        ));
+    }
+}
@@ -0,0 +1,54 @@
+use std::{future::Future, sync::Arc};
+
+use tokio::sync::watch;
+
+/// Tells workloads the run's workload phase is over.
+///
+/// The runner cancels it once the run duration (or block target) and the
+/// cooldown have elapsed, then waits briefly for workloads to return before
+/// aborting the ones still running. Open-ended workloads, such as traffic
+/// generators or chaos loops, watch it instead of planning a fixed amount of
+/// work. Clones share the same signal.
+#[derive(Clone)]
+pub struct RunCancellation {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl Default for RunCancellation {
+    fn default() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+}
+
+impl RunCancellation {
+    /// Ask workloads to stop.
+    pub fn cancel(&self) {
+        self.sender.send_replace(true);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    /// Resolves once workloads are asked to stop.
+    pub async fn cancelled(&self) {
+        let mut receiver = self.sender.subscribe();
+        receiver
+            .wait_for(|cancelled| *cancelled)
+            .await
+            .expect("cancellation sender is kept alive by self");
+    }
+
+    /// Drive `future` until it completes or the run is cancelled, whichever
+    /// comes first; `None` means the run was cancelled.
+    pub async fn until_cancelled<F: Future>(&self, future: F) -> Option<F::Output> {
+        tokio::select! {
+            output = future => Some(output),
+            () = self.cancelled() => None,
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use tokio::time::{Instant, MissedTickBehavior, interval_at};
+use tracing::{info, warn};
+
+use super::{context::RunContext, deployer::ScenarioError};
+use crate::scenario::{Expectation, Severity};
+
+/// How often periodic expectations are checked while a scenario runs.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CheckpointPolicy {
+    interval: Option<Duration>,
+    abort_on_failure: bool,
+}
+
+impl CheckpointPolicy {
+    #[must_use]
+    /// Check periodic expectations every `interval` (panics on zero).
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "checkpoint interval must be non-zero");
+        self.interval = Some(interval);
+        self
+    }
+
+    #[must_use]
+    /// Fail the run at the first failing checkpoint instead of only recording
+    /// it.
+    pub const fn abort_on_failure(mut self) -> Self {
+        self.abort_on_failure = true;
+        self
+    }
+
+    #[must_use]
+    pub const fn interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    #[must_use]
+    pub const fn aborts_on_failure(&self) -> bool {
+        self.abort_on_failure
+    }
+}
+
+/// Checks every periodic expectation on each tick of `interval`, recording
+/// the outcomes. Only returns when a checkpoint fails and the policy aborts on
+/// failure; callers race it against the workload driver.
+pub(crate) async fn run_checkpoints(
+    expectations: &mut [Box<dyn Expectation>],
+    context: &RunContext,
+    interval: Duration,
+    abort_on_failure: bool,
+) -> ScenarioError {
+    let mut ticker = interval_at(Instant::now() + interval, interval);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut round = 0u64;
+
+    loop {
+        ticker.tick().await;
+        round += 1;
+        for expectation in expectations.iter_mut() {
+            let name = expectation.name().to_owned();
+            let severity = expectation.severity();
+            let Some(periodic) = expectation.as_periodic() else {
+                continue;
+            };
+
+            let result = periodic.checkpoint(context).await;
+            context.report().record_checkpoint(&name, &result);
+            match result {
+                Ok(()) => info!(expectation = %name, round, "checkpoint passed"),
+                Err(source) => {
+                    warn!(expectation = %name, round, error = %source, "checkpoint failed");
+                    if abort_on_failure && severity == Severity::Hard {
+                        return ScenarioError::Checkpoint { name, source };
+                    }
+                }
+            }
+        }
+    }
+}
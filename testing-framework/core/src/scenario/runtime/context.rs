@@ -1,9 +1,27 @@
-use std::{sync::Arc, time::Duration};
-
-use super::{block_feed::BlockFeed, metrics::Metrics, node_clients::ClusterClient};
+use std::{path::Path, sync::Arc, time::Duration};
+
+use rand::rngs::StdRng;
+
+use super::{
+    abort::RunAbort,
+    api_faults::{ApiFaultProxies, ApiFaultStats},
+    block_feed::{BlockFeed, BlockStats, ConsensusThroughput},
+    cancel::RunCancellation,
+    dispersal::{DispersalStats, ExecutorDispersal},
+    logs::NodeLogs,
+    metrics::Metrics,
+    node_clients::ClusterClient,
+    recovery::NodeRecovery,
+    storage::NodeStorage,
+    timeline::DeploymentTimeline,
+    traces::TempoEndpoint,
+    tx_latency::{LatencyPercentiles, TxLatency},
+    wallet_pool::WalletPool,
+};
 use crate::{
+    artifacts::RunArtifacts,
     nodes::ApiClient,
-    scenario::{NodeClients, NodeControlHandle},
+    scenario::{NodeClients, NodeControlHandle, RunReporter},
     topology::{
         configs::wallet::WalletAccount, deployment::Topology, generation::GeneratedTopology,
     },
@@ -15,9 +33,22 @@ pub struct RunContext {
     cluster: Option<Topology>,
     node_clients: NodeClients,
     metrics: RunMetrics,
+    tx_latency: TxLatency,
+    dispersal: DispersalStats,
+    recovery: NodeRecovery,
     telemetry: Metrics,
     block_feed: BlockFeed,
     node_control: Option<Arc<dyn NodeControlHandle>>,
+    report: RunReporter,
+    node_logs: NodeLogs,
+    node_storage: NodeStorage,
+    traces: Option<TempoEndpoint>,
+    abort: RunAbort,
+    cancellation: RunCancellation,
+    api_faults: Option<ApiFaultProxies>,
+    deployment: DeploymentTimeline,
+    // Last so node data dirs inside it are released before it is removed.
+    artifacts: Option<Arc<RunArtifacts>>,
 }
 
 impl RunContext {
@@ -41,12 +72,69 @@ impl RunContext {
             cluster,
             node_clients,
             metrics,
+            tx_latency: TxLatency::default(),
+            dispersal: DispersalStats::default(),
+            recovery: NodeRecovery::default(),
             telemetry,
             block_feed,
             node_control,
+            report: RunReporter::new(),
+            node_logs: NodeLogs::new(),
+            node_storage: NodeStorage::new(),
+            traces: None,
+            abort: RunAbort::default(),
+            cancellation: RunCancellation::default(),
+            api_faults: None,
+            deployment: DeploymentTimeline::new(),
+            artifacts: None,
         }
     }
 
+    /// Attach the locations where the deployer collects node logs.
+    #[must_use]
+    pub fn with_node_logs(mut self, node_logs: NodeLogs) -> Self {
+        self.node_logs = node_logs;
+        self
+    }
+
+    /// Attach the host paths of the nodes' data directories.
+    #[must_use]
+    pub fn with_node_storage(mut self, node_storage: NodeStorage) -> Self {
+        self.node_storage = node_storage;
+        self
+    }
+
+    /// Attach the trace backend the nodes export spans to.
+    #[must_use]
+    pub fn with_traces(mut self, traces: TempoEndpoint) -> Self {
+        self.traces = Some(traces);
+        self
+    }
+
+    /// Keep the node API fault proxies the run's clients go through alive
+    /// for the run.
+    #[must_use]
+    pub fn with_api_faults(mut self, proxies: Option<ApiFaultProxies>) -> Self {
+        self.api_faults = proxies;
+        self
+    }
+
+    /// Attach the deployer's bring-up phases; they are also added to the
+    /// run report.
+    #[must_use]
+    pub fn with_deployment_timeline(mut self, timeline: DeploymentTimeline) -> Self {
+        self.report.record_deployment(&timeline);
+        self.deployment = timeline;
+        self
+    }
+
+    /// Attach the directory the deployer writes this run's files to.
+    #[must_use]
+    pub fn with_artifacts(mut self, artifacts: Arc<RunArtifacts>) -> Self {
+        self.artifacts = Some(artifacts);
+        self
+    }
+
     #[must_use]
     pub const fn descriptors(&self) -> &GeneratedTopology {
         &self.descriptors
@@ -77,11 +165,43 @@ impl RunContext {
         self.descriptors.wallet_accounts()
     }
 
+    /// Claim fresh wallet accounts no other workload uses.
+    #[must_use]
+    pub const fn wallet_pool(&self) -> &WalletPool {
+        self.descriptors.wallet_pool()
+    }
+
+    /// RNG for the named stream; workloads use one stream each so a seeded
+    /// scenario replays the same random choices.
+    #[must_use]
+    pub fn rng(&self, stream: &str) -> StdRng {
+        self.descriptors.rng(stream)
+    }
+
+    /// Signal deployers use to stop the run early, e.g. when a node dies.
+    #[must_use]
+    pub const fn abort_signal(&self) -> &RunAbort {
+        &self.abort
+    }
+
+    #[must_use]
+    /// Signal the runner fires when workloads should stop; open-ended
+    /// workloads return once it fires.
+    pub const fn cancellation(&self) -> &RunCancellation {
+        &self.cancellation
+    }
+
     #[must_use]
     pub const fn telemetry(&self) -> &Metrics {
         &self.telemetry
     }
 
+    /// Trace backend collecting node spans, when the deployer runs one.
+    #[must_use]
+    pub const fn traces(&self) -> Option<&TempoEndpoint> {
+        self.traces.as_ref()
+    }
+
     #[must_use]
     pub const fn run_duration(&self) -> Duration {
         self.metrics.run_duration()
@@ -97,11 +217,103 @@ impl RunContext {
         self.metrics
     }
 
+    #[must_use]
+    /// Transaction inclusion latencies tracked during the run.
+    pub const fn tx_latency(&self) -> &TxLatency {
+        &self.tx_latency
+    }
+
+    #[must_use]
+    /// Inclusion latency percentiles observed so far, if any transaction
+    /// was included.
+    pub fn tx_latency_percentiles(&self) -> Option<LatencyPercentiles> {
+        self.tx_latency.percentiles()
+    }
+
+    #[must_use]
+    /// Block counters fed by the run's block feed.
+    pub fn block_stats(&self) -> Arc<BlockStats> {
+        self.block_feed.stats()
+    }
+
+    #[must_use]
+    /// Blocks observed so far, how many transactions they carried and how
+    /// many were empty.
+    pub fn consensus_throughput(&self) -> ConsensusThroughput {
+        self.block_feed.stats().throughput()
+    }
+
+    #[must_use]
+    /// Blob publish latencies and failures tracked during the run.
+    pub const fn dispersal(&self) -> &DispersalStats {
+        &self.dispersal
+    }
+
+    #[must_use]
+    /// Publish latencies and failures per executor observed so far.
+    pub fn dispersal_summary(&self) -> Vec<ExecutorDispersal> {
+        self.dispersal.summary()
+    }
+
+    #[must_use]
+    /// Tracker of nodes restarted by chaos workloads and how long they took
+    /// to recover.
+    pub const fn recovery(&self) -> &NodeRecovery {
+        &self.recovery
+    }
+
+    #[must_use]
+    /// Requests affected by node API fault injection so far, when the
+    /// scenario configured it.
+    pub fn api_fault_stats(&self) -> Option<ApiFaultStats> {
+        self.api_faults.as_ref().map(ApiFaultProxies::stats)
+    }
+
     #[must_use]
     pub fn node_control(&self) -> Option<Arc<dyn NodeControlHandle>> {
         self.node_control.clone()
     }
 
+    /// Collector for the structured run report; deployers, workloads and
+    /// expectations record their outcomes here.
+    #[must_use]
+    pub const fn report(&self) -> &RunReporter {
+        &self.report
+    }
+
+    /// Phases the deployer went through bringing the cluster up; empty when
+    /// the deployer does not record them.
+    #[must_use]
+    pub const fn deployment_timeline(&self) -> &DeploymentTimeline {
+        &self.deployment
+    }
+
+    /// Per-node log locations; empty when the deployer does not expose logs.
+    #[must_use]
+    pub const fn node_logs(&self) -> &NodeLogs {
+        &self.node_logs
+    }
+
+    /// Per-node storage inspectors; empty when the deployer cannot reach the
+    /// nodes' data directories.
+    #[must_use]
+    pub const fn node_storage(&self) -> &NodeStorage {
+        &self.node_storage
+    }
+
+    /// Directory holding every node's log files, when they share one.
+    #[must_use]
+    pub fn log_dir(&self) -> Option<&Path> {
+        self.node_logs.common_dir()
+    }
+
+    /// This run's output directory, when the deployer manages one.
+    /// Workloads can create subdirectories in it for their own files.
+    #[must_use]
+    pub const fn artifacts(&self) -> Option<&Arc<RunArtifacts>> {
+        self.artifacts.as_ref()
+    }
+
     #[must_use]
     pub const fn cluster_client(&self) -> ClusterClient<'_> {
         self.node_clients.cluster_client()
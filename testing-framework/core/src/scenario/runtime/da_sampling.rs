@@ -0,0 +1,294 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use futures::future::join_all;
+use nomos_core::{
+    da::BlobId,
+    mantle::{AuthenticatedMantleTx as _, ops::Op},
+    sdp::SessionNumber,
+};
+use nomos_node::HeaderId;
+use rand::{Rng, seq::SliceRandom as _};
+
+use super::{block_feed::BlockRecord, context::RunContext};
+use crate::{
+    nodes::{HistoricSamplingBatch, HistoricSamplingRequest},
+    topology::generation::NodeRole,
+};
+
+/// Blocks built on top of a blob's block before it is sampled, so a short
+/// fork cannot drop it from the chain.
+pub const CONFIRMATION_DEPTH: u64 = 2;
+/// Most recent blobs kept for sampling; older ones are dropped first.
+const MAX_PUBLISHED_BLOBS: usize = 4096;
+
+/// A DA blob whose channel operation landed in a block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublishedBlob {
+    pub blob_id: BlobId,
+    pub session: SessionNumber,
+    pub block_id: HeaderId,
+}
+
+/// Blobs published on chain during the run, collected from the block feed.
+/// Only the most recent blobs are kept. Clones share the same set.
+#[derive(Clone, Default)]
+pub struct PublishedBlobs {
+    inner: Arc<Mutex<Landed>>,
+}
+
+#[derive(Default)]
+struct Landed {
+    /// Blocks observed so far.
+    blocks: u64,
+    blobs: VecDeque<LandedBlob>,
+}
+
+struct LandedBlob {
+    blob: PublishedBlob,
+    /// Value of [`Landed::blocks`] once the blob's block was observed.
+    height: u64,
+    at: Instant,
+}
+
+impl PublishedBlobs {
+    /// Record the blobs of every channel blob operation in `record`.
+    pub fn observe_block(&self, record: &BlockRecord) {
+        let landed = record
+            .block
+            .transactions()
+            .flat_map(|tx| tx.mantle_tx().ops.iter())
+            .filter_map(|op| match op {
+                Op::ChannelBlob(blob) => Some(PublishedBlob {
+                    blob_id: blob.blob,
+                    session: blob.session,
+                    block_id: record.header,
+                }),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.land(landed, Instant::now());
+    }
+
+    fn land(&self, blobs: Vec<PublishedBlob>, at: Instant) {
+        let mut landed = self.inner.lock().expect("published blobs lock poisoned");
+        landed.blocks += 1;
+        let height = landed.blocks;
+        landed.blobs.extend(
+            blobs
+                .into_iter()
+                .map(|blob| LandedBlob { blob, height, at }),
+        );
+        let excess = landed.blobs.len().saturating_sub(MAX_PUBLISHED_BLOBS);
+        landed.blobs.drain(..excess);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .expect("published blobs lock poisoned")
+            .blobs
+            .len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    /// The kept blobs, in the order the blocks were observed.
+    pub fn snapshot(&self) -> Vec<PublishedBlob> {
+        self.inner
+            .lock()
+            .expect("published blobs lock poisoned")
+            .blobs
+            .iter()
+            .map(|landed| landed.blob)
+            .collect()
+    }
+
+    #[must_use]
+    /// Up to `count` distinct blobs picked at random among those at least
+    /// [`CONFIRMATION_DEPTH`] blocks deep that landed less than `validity`
+    /// ago, so nodes are still expected to serve them.
+    pub fn choose(
+        &self,
+        count: usize,
+        validity: Duration,
+        rng: &mut impl Rng,
+    ) -> Vec<PublishedBlob> {
+        self.choose_at(Instant::now(), count, validity, rng)
+    }
+
+    fn choose_at(
+        &self,
+        now: Instant,
+        count: usize,
+        validity: Duration,
+        rng: &mut impl Rng,
+    ) -> Vec<PublishedBlob> {
+        let landed = self.inner.lock().expect("published blobs lock poisoned");
+        let sampleable = landed
+            .blobs
+            .iter()
+            .filter(|blob| {
+                blob.height + CONFIRMATION_DEPTH <= landed.blocks
+                    && now.saturating_duration_since(blob.at) < validity
+            })
+            .collect::<Vec<_>>();
+        sampleable
+            .choose_multiple(rng, count)
+            .map(|landed| landed.blob)
+            .collect()
+    }
+}
+
+/// A node that failed to sample part of a [`SamplingSweep`].
+#[derive(Clone, Debug)]
+pub struct SweepFailure {
+    pub node: String,
+    pub block_id: HeaderId,
+    pub blobs: usize,
+    pub reason: String,
+}
+
+/// Result of [`sample_published_blobs`].
+#[derive(Clone, Debug, Default)]
+pub struct SamplingSweep {
+    /// Blobs drawn from the published set.
+    pub blobs: usize,
+    /// Blob samples checked, one per blob and node.
+    pub checks: u64,
+    pub failures: Vec<SweepFailure>,
+}
+
+impl SamplingSweep {
+    #[must_use]
+    pub const fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Historic-sample up to `count` random confirmed, unexpired blobs of the
+/// run's published set on every validator, batching requests per node as
+/// configured by `batch`.
+pub async fn sample_published_blobs(
+    ctx: &RunContext,
+    count: usize,
+    rng: &mut impl Rng,
+    batch: &HistoricSamplingBatch,
+) -> SamplingSweep {
+    let validity = ctx.descriptors().config().da_params.blobs_validity_duration;
+    let sample = ctx
+        .block_stats()
+        .published_blobs()
+        .choose(count, validity, rng);
+    if sample.is_empty() {
+        return SamplingSweep::default();
+    }
+
+    let mut by_block: HashMap<HeaderId, Vec<(BlobId, SessionNumber)>> = HashMap::new();
+    for blob in &sample {
+        by_block
+            .entry(blob.block_id)
+            .or_default()
+            .push((blob.blob_id, blob.session));
+    }
+    let requests = by_block
+        .into_iter()
+        .map(|(block_id, blob_ids)| HistoricSamplingRequest { block_id, blob_ids })
+        .collect::<Vec<_>>();
+
+    let nodes = ctx
+        .node_clients()
+        .targeted_clients()
+        .filter(|(target, _)| target.role() == NodeRole::Validator)
+        .collect::<Vec<_>>();
+    let results = join_all(
+        nodes
+            .iter()
+            .map(|(_, client)| client.da_historic_sampling_batch(&requests, batch)),
+    )
+    .await;
+
+    let failures = nodes
+        .iter()
+        .zip(results)
+        .flat_map(|((target, _), chunks)| {
+            chunks.into_iter().filter_map(move |chunk| {
+                chunk.failure.map(|reason| SweepFailure {
+                    node: target.to_string(),
+                    block_id: chunk.block_id,
+                    blobs: chunk.blob_ids.len(),
+                    reason,
+                })
+            })
+        })
+        .collect();
+    SamplingSweep {
+        blobs: sample.len(),
+        checks: (sample.len() * nodes.len()) as u64,
+        failures,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+
+    fn blob(n: u8) -> PublishedBlob {
+        PublishedBlob {
+            blob_id: [n; 32],
+            session: 0,
+            block_id: HeaderId::from([n; 32]),
+        }
+    }
+
+    #[test]
+    fn only_confirmed_unexpired_blobs_are_chosen() {
+        let blobs = PublishedBlobs::default();
+        let start = Instant::now();
+        let validity = Duration::from_secs(60);
+        let mut rng = StdRng::seed_from_u64(7);
+
+        blobs.land(vec![blob(1)], start);
+        blobs.land(vec![blob(2)], start + Duration::from_secs(30));
+        blobs.land(Vec::new(), start + Duration::from_secs(31));
+        assert_eq!(blobs.len(), 2);
+
+        let now = start + Duration::from_secs(31);
+        assert_eq!(blobs.choose_at(now, 10, validity, &mut rng), vec![blob(1)]);
+
+        blobs.land(Vec::new(), start + Duration::from_secs(32));
+        let mut chosen = blobs.choose_at(now, 10, validity, &mut rng);
+        chosen.sort_by_key(|blob| blob.blob_id);
+        assert_eq!(chosen, vec![blob(1), blob(2)]);
+
+        let later = start + Duration::from_secs(61);
+        assert_eq!(
+            blobs.choose_at(later, 10, validity, &mut rng),
+            vec![blob(2)]
+        );
+    }
+
+    #[test]
+    fn keeps_the_most_recent_blobs() {
+        let blobs = PublishedBlobs::default();
+        let now = Instant::now();
+        for n in 0..3 {
+            blobs.land(vec![blob(n); MAX_PUBLISHED_BLOBS / 2], now);
+        }
+
+        assert_eq!(blobs.len(), MAX_PUBLISHED_BLOBS);
+        let snapshot = blobs.snapshot();
+        assert_eq!(snapshot[0], blob(1));
+        assert_eq!(snapshot[MAX_PUBLISHED_BLOBS - 1], blob(2));
+    }
+}
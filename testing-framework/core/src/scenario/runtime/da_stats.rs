@@ -0,0 +1,153 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use nomos_da_network_core::swarm::{BalancerStats, MonitorStats};
+use tokio::{
+    task::JoinHandle,
+    time::{Instant, MissedTickBehavior, interval},
+};
+use tracing::debug;
+
+use super::node_clients::NodeClients;
+use crate::{nodes::ApiClient, topology::readiness::connected_subnetworks};
+
+/// One poll of a node's DA balancer and monitor stats. Failed requests leave
+/// the corresponding field empty.
+#[derive(Clone)]
+pub struct DaStatsSample {
+    pub node: String,
+    /// Time since the sampler started.
+    pub at: Duration,
+    pub balancer: Option<Arc<BalancerStats>>,
+    pub monitor: Option<Arc<MonitorStats>>,
+}
+
+impl DaStatsSample {
+    #[must_use]
+    /// Subnetworks with at least one inbound or outbound peer, if the
+    /// balancer stats were available.
+    pub fn connected_subnetworks(&self) -> Option<usize> {
+        self.balancer.as_deref().map(connected_subnetworks)
+    }
+}
+
+/// Polls `balancer_stats`/`monitor_stats` on every node at a fixed interval
+/// and keeps the time series in memory.
+///
+/// Expectations typically spawn one in `start_capture` and inspect it in
+/// `evaluate`. Clones share the same series; polling stops once the last
+/// clone is dropped or [`Self::stop`] is called.
+#[derive(Clone)]
+pub struct StatsSampler {
+    inner: Arc<SamplerInner>,
+}
+
+struct SamplerInner {
+    samples: Arc<Mutex<Vec<DaStatsSample>>>,
+    task: JoinHandle<()>,
+}
+
+impl Drop for SamplerInner {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl StatsSampler {
+    #[must_use]
+    /// Start polling every node in `clients` each `every` (panics on zero).
+    pub fn spawn(clients: &NodeClients, every: Duration) -> Self {
+        assert!(!every.is_zero(), "stats sampling interval must be non-zero");
+        let nodes: Vec<(String, ApiClient)> = clients
+            .labeled_clients()
+            .map(|(label, _, client)| (label, client.clone()))
+            .collect();
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        let task = tokio::spawn(sample_loop(nodes, every, Arc::clone(&samples)));
+        Self {
+            inner: Arc::new(SamplerInner { samples, task }),
+        }
+    }
+
+    /// Stop polling; the samples collected so far stay available.
+    pub fn stop(&self) {
+        self.inner.task.abort();
+    }
+
+    #[must_use]
+    /// Every sample collected so far, oldest first.
+    pub fn samples(&self) -> Vec<DaStatsSample> {
+        self.inner
+            .samples
+            .lock()
+            .expect("stats sampler lock poisoned")
+            .clone()
+    }
+
+    #[must_use]
+    /// Samples of a single node, oldest first.
+    pub fn samples_for(&self, node: &str) -> Vec<DaStatsSample> {
+        self.inner
+            .samples
+            .lock()
+            .expect("stats sampler lock poisoned")
+            .iter()
+            .filter(|sample| sample.node == node)
+            .cloned()
+            .collect()
+    }
+
+    #[must_use]
+    /// Longest stretch during which `node` had fewer than `threshold`
+    /// connected subnetworks, measured between samples. Polls whose balancer
+    /// request failed are skipped.
+    pub fn longest_below(&self, node: &str, threshold: usize) -> Duration {
+        let mut longest = Duration::ZERO;
+        let mut since: Option<Duration> = None;
+        for sample in self.samples_for(node) {
+            let Some(connected) = sample.connected_subnetworks() else {
+                continue;
+            };
+            if connected < threshold {
+                let start = *since.get_or_insert(sample.at);
+                longest = longest.max(sample.at.saturating_sub(start));
+            } else if let Some(start) = since.take() {
+                longest = longest.max(sample.at.saturating_sub(start));
+            }
+        }
+        longest
+    }
+}
+
+async fn sample_loop(
+    nodes: Vec<(String, ApiClient)>,
+    every: Duration,
+    samples: Arc<Mutex<Vec<DaStatsSample>>>,
+) {
+    let started = Instant::now();
+    let mut ticker = interval(every);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        for (node, client) in &nodes {
+            let balancer = client.balancer_stats().await;
+            let monitor = client.monitor_stats().await;
+            if let Err(err) = &balancer {
+                debug!(node, %err, "balancer stats request failed");
+            }
+            let sample = DaStatsSample {
+                node: node.clone(),
+                at: started.elapsed(),
+                balancer: balancer.ok().map(Arc::new),
+                monitor: monitor.ok().map(Arc::new),
+            };
+            samples
+                .lock()
+                .expect("stats sampler lock poisoned")
+                .push(sample);
+        }
+    }
+}
@@ -1,23 +1,58 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 
-use super::runner::Runner;
-use crate::scenario::{DynError, Scenario};
+use super::{runner::Runner, shutdown::ShutdownSignal};
+use crate::{
+    errors::ClassifiedError,
+    scenario::{DryRunReport, DynError, Scenario},
+};
 
 /// Error returned when executing workloads or expectations.
 #[derive(Debug, thiserror::Error)]
 pub enum ScenarioError {
+    #[error("required node features unavailable:\n{0}")]
+    MissingFeatures(#[source] DynError),
     #[error("workload failure: {0}")]
     Workload(#[source] DynError),
     #[error("expectation capture failed: {0}")]
     ExpectationCapture(#[source] DynError),
     #[error("expectations failed:\n{0}")]
     Expectations(#[source] DynError),
+    #[error("observed only {observed}/{target} blocks within {limit:?}")]
+    BlockTarget {
+        target: u64,
+        observed: u64,
+        limit: Duration,
+    },
+    #[error("run aborted: {0}")]
+    Aborted(String),
+    #[error("graceful shutdown failed:\n{0}")]
+    Shutdown(#[source] DynError),
+    #[error("checkpoint of {name} failed: {source}")]
+    Checkpoint {
+        name: String,
+        #[source]
+        source: DynError,
+    },
+    #[error("run interrupted by {0}")]
+    Interrupted(ShutdownSignal),
 }
 
 /// Deploys a scenario into a target environment and returns a `Runner`.
+///
+/// Deployment errors are classified so callers can decide on retries without
+/// knowing the backend.
 #[async_trait]
 pub trait Deployer<Caps = ()>: Send + Sync {
-    type Error;
+    type Error: ClassifiedError;
 
     async fn deploy(&self, scenario: &Scenario<Caps>) -> Result<Runner, Self::Error>;
+
+    /// Describe what [`Self::deploy`] would bring up without touching the
+    /// target environment. Deployers that render artifacts extend the
+    /// scenario's [`Scenario::dry_run`] report with them.
+    fn dry_run(&self, scenario: &Scenario<Caps>) -> Result<DryRunReport, Self::Error> {
+        Ok(scenario.dry_run())
+    }
 }
@@ -0,0 +1,138 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::Serialize;
+
+use super::tx_latency::nearest_rank;
+
+/// Blob publish latencies and failures per executor, recorded by DA
+/// workloads. Clones share the same samples.
+#[derive(Clone, Default)]
+pub struct DispersalStats {
+    executors: Arc<Mutex<BTreeMap<String, ExecutorSamples>>>,
+}
+
+#[derive(Default)]
+struct ExecutorSamples {
+    latencies: Vec<Duration>,
+    failures: u64,
+    bytes: u64,
+}
+
+/// Publish outcomes of one executor.
+#[derive(Clone, Debug, Serialize)]
+pub struct ExecutorDispersal {
+    pub executor: String,
+    pub published: u64,
+    pub failed: u64,
+    pub bytes: u64,
+    pub p50: Option<Duration>,
+    pub p95: Option<Duration>,
+    pub max: Option<Duration>,
+}
+
+impl ExecutorDispersal {
+    #[must_use]
+    /// Fraction of publish attempts that failed.
+    pub fn failure_ratio(&self) -> f64 {
+        let attempts = self.published + self.failed;
+        if attempts == 0 {
+            return 0.0;
+        }
+        self.failed as f64 / attempts as f64
+    }
+}
+
+impl DispersalStats {
+    /// Record a blob of `bytes` that `executor` accepted after `latency`.
+    pub fn record_publish(&self, executor: &str, bytes: u64, latency: Duration) {
+        let mut executors = self
+            .executors
+            .lock()
+            .expect("dispersal stats lock poisoned");
+        let samples = executors.entry(executor.to_owned()).or_default();
+        samples.latencies.push(latency);
+        samples.bytes += bytes;
+    }
+
+    /// Record a publish attempt `executor` rejected or failed to answer.
+    pub fn record_failure(&self, executor: &str) {
+        self.executors
+            .lock()
+            .expect("dispersal stats lock poisoned")
+            .entry(executor.to_owned())
+            .or_default()
+            .failures += 1;
+    }
+
+    #[must_use]
+    /// Per-executor summary, ordered by executor.
+    pub fn summary(&self) -> Vec<ExecutorDispersal> {
+        self.executors
+            .lock()
+            .expect("dispersal stats lock poisoned")
+            .iter()
+            .map(|(executor, samples)| {
+                let mut latencies = samples.latencies.clone();
+                latencies.sort_unstable();
+                ExecutorDispersal {
+                    executor: executor.clone(),
+                    published: latencies.len() as u64,
+                    failed: samples.failures,
+                    bytes: samples.bytes,
+                    p50: nearest_rank(&latencies, 50.0),
+                    p95: nearest_rank(&latencies, 95.0),
+                    max: latencies.last().copied(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary_is_per_executor_with_latency_percentiles() {
+        let stats = DispersalStats::default();
+        for millis in 1..=20 {
+            stats.record_publish("executor-1", 100, Duration::from_millis(millis));
+        }
+        stats.record_failure("executor-1");
+        stats.record_failure("executor-0");
+
+        let summary = stats.summary();
+
+        assert_eq!(summary.len(), 2);
+        let failing = &summary[0];
+        assert_eq!(failing.executor, "executor-0");
+        assert_eq!(failing.published, 0);
+        assert_eq!(failing.failed, 1);
+        assert_eq!(failing.p50, None);
+        assert!((failing.failure_ratio() - 1.0).abs() < f64::EPSILON);
+
+        let busy = &summary[1];
+        assert_eq!(busy.executor, "executor-1");
+        assert_eq!(busy.published, 20);
+        assert_eq!(busy.bytes, 2000);
+        assert_eq!(busy.p50, Some(Duration::from_millis(10)));
+        assert_eq!(busy.p95, Some(Duration::from_millis(19)));
+        assert_eq!(busy.max, Some(Duration::from_millis(20)));
+        assert!((busy.failure_ratio() - 1.0 / 21.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn clones_share_samples() {
+        let stats = DispersalStats::default();
+        stats
+            .clone()
+            .record_publish("executor-0", 1, Duration::from_millis(5));
+
+        assert_eq!(stats.summary()[0].published, 1);
+        assert!(DispersalStats::default().summary().is_empty());
+    }
+}
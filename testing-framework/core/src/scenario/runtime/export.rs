@@ -0,0 +1,492 @@
+use std::{
+    env,
+    fmt::Write as _,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use reqwest::{Client, Url};
+use serde::Serialize;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use crate::scenario::{NodeEventKind, RunReport};
+
+/// Environment variable holding a Prometheus pushgateway base URL, e.g.
+/// `http://pushgateway:9091/`.
+pub const METRICS_PUSHGATEWAY_ENV: &str = "NOMOS_METRICS_PUSHGATEWAY";
+/// Environment variable holding an OTLP/HTTP collector base URL, e.g.
+/// `http://otel-collector:4318/`.
+pub const METRICS_OTLP_ENV: &str = "NOMOS_METRICS_OTLP_ENDPOINT";
+/// Environment variable naming the job (pushgateway) or service (OTLP) the
+/// metrics are grouped under.
+pub const METRICS_JOB_ENV: &str = "NOMOS_METRICS_JOB";
+
+const DEFAULT_JOB: &str = "nomos-scenario";
+/// How long a push may take; a hung backend must not hold up the run's end.
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Failures exporting run metrics.
+#[derive(Debug, Error)]
+pub enum MetricsExportError {
+    #[error("invalid metrics export url {url:?}: {reason}")]
+    Url { url: String, reason: String },
+    #[error("failed to push run metrics to {url}: {source}")]
+    Push {
+        url: Url,
+        #[source]
+        source: reqwest::Error,
+    },
+}
+
+/// Backend receiving the scenario-level metrics.
+#[derive(Clone, Debug)]
+pub enum MetricsExportTarget {
+    /// Prometheus pushgateway, fed the text exposition format.
+    Pushgateway(Url),
+    /// OTLP collector, fed JSON-encoded metrics over HTTP.
+    Otlp(Url),
+}
+
+/// Pushes a summary of a finished run to a metrics backend so CI trend
+/// dashboards can be built across runs.
+#[derive(Clone, Debug)]
+pub struct RunMetricsExporter {
+    target: MetricsExportTarget,
+    job: String,
+    instance: Option<String>,
+    client: Client,
+}
+
+/// A single exported value with its labels.
+struct Sample {
+    name: &'static str,
+    help: &'static str,
+    labels: Vec<(&'static str, String)>,
+    value: f64,
+}
+
+impl RunMetricsExporter {
+    #[must_use]
+    pub fn new(target: MetricsExportTarget, job: impl Into<String>) -> Self {
+        Self {
+            target,
+            job: job.into(),
+            instance: None,
+            client: Client::new(),
+        }
+    }
+
+    #[must_use]
+    /// Identify the run the metrics belong to, e.g. by its run id. Pushes are
+    /// grouped by job and instance, so runs sharing a job keep their own
+    /// series instead of replacing each other's; without an instance the
+    /// report's generation time is used.
+    pub fn with_instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Exporter configured by [`METRICS_PUSHGATEWAY_ENV`] or
+    /// [`METRICS_OTLP_ENV`] (the pushgateway wins if both are set), grouped
+    /// under [`METRICS_JOB_ENV`].
+    pub fn from_env() -> Result<Option<Self>, MetricsExportError> {
+        let job = env::var(METRICS_JOB_ENV)
+            .ok()
+            .filter(|job| !job.is_empty())
+            .unwrap_or_else(|| DEFAULT_JOB.to_owned());
+        let parse = |raw: String| {
+            Url::parse(&raw).map_err(|err| MetricsExportError::Url {
+                reason: err.to_string(),
+                url: raw,
+            })
+        };
+
+        if let Some(raw) = non_empty_env(METRICS_PUSHGATEWAY_ENV) {
+            return Ok(Some(Self::new(
+                MetricsExportTarget::Pushgateway(parse(raw)?),
+                job,
+            )));
+        }
+        if let Some(raw) = non_empty_env(METRICS_OTLP_ENV) {
+            return Ok(Some(Self::new(MetricsExportTarget::Otlp(parse(raw)?), job)));
+        }
+        Ok(None)
+    }
+
+    #[must_use]
+    pub const fn target(&self) -> &MetricsExportTarget {
+        &self.target
+    }
+
+    /// Push run duration, verdicts, workload counters (transactions, blobs,
+    /// ...), node event counts, readiness timings and deployment phase
+    /// durations from `report`.
+    pub async fn export(&self, report: &RunReport) -> Result<(), MetricsExportError> {
+        let samples = samples(report);
+        let instance = self
+            .instance
+            .clone()
+            .unwrap_or_else(|| report.generated_at_unix_secs.to_string());
+        match &self.target {
+            MetricsExportTarget::Pushgateway(base) => {
+                let url = pushgateway_url(base, &self.job, &instance)?;
+                let body = exposition_text(&samples);
+                self.send(url, |request| {
+                    request
+                        .header("content-type", "text/plain; version=0.0.4")
+                        .body(body)
+                })
+                .await
+            }
+            MetricsExportTarget::Otlp(base) => {
+                let url = join_url(base, "v1/metrics")?;
+                let body = otlp_payload(&self.job, &instance, &samples);
+                self.send(url, |request| request.json(&body)).await
+            }
+        }
+    }
+
+    async fn send(
+        &self,
+        url: Url,
+        build: impl FnOnce(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    ) -> Result<(), MetricsExportError> {
+        build(self.client.post(url.clone()).timeout(PUSH_TIMEOUT))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map(|_| ())
+            .map_err(|source| MetricsExportError::Push { url, source })
+    }
+}
+
+fn join_url(base: &Url, path: &str) -> Result<Url, MetricsExportError> {
+    let base = if base.path().ends_with('/') {
+        base.clone()
+    } else {
+        let mut base = base.clone();
+        base.set_path(&format!("{}/", base.path()));
+        base
+    };
+    base.join(path).map_err(|err| MetricsExportError::Url {
+        url: format!("{base}{path}"),
+        reason: err.to_string(),
+    })
+}
+
+/// Pushgateway grouping URL for `job` and `instance`, with both values
+/// escaped as path segments.
+fn pushgateway_url(base: &Url, job: &str, instance: &str) -> Result<Url, MetricsExportError> {
+    let mut url = base.clone();
+    url.path_segments_mut()
+        .map_err(|()| MetricsExportError::Url {
+            url: base.to_string(),
+            reason: "cannot be a base url".to_owned(),
+        })?
+        .pop_if_empty()
+        .extend(["metrics", "job", job, "instance", instance]);
+    Ok(url)
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key).ok().filter(|value| !value.trim().is_empty())
+}
+
+fn samples(report: &RunReport) -> Vec<Sample> {
+    let mut samples = vec![
+        Sample {
+            name: "nomos_scenario_run_duration_seconds",
+            help: "Wall-clock duration of the scenario run.",
+            labels: Vec::new(),
+            value: report.elapsed_secs,
+        },
+        Sample {
+            name: "nomos_scenario_passed",
+            help: "1 if every hard expectation passed, 0 otherwise.",
+            labels: Vec::new(),
+            value: f64::from(u8::from(report.passed())),
+        },
+    ];
+
+    samples.extend(report.expectations.iter().map(|outcome| Sample {
+        name: "nomos_scenario_expectation_passed",
+        help: "1 if the expectation passed, 0 otherwise.",
+        labels: vec![
+            ("expectation", outcome.name.clone()),
+            ("severity", snake_case_label(outcome.severity)),
+        ],
+        value: f64::from(u8::from(outcome.passed)),
+    }));
+
+    for (workload, counters) in &report.workloads {
+        samples.extend(counters.iter().map(|(counter, value)| Sample {
+            name: "nomos_scenario_workload_counter",
+            help: "Final value of a workload counter.",
+            labels: vec![("workload", workload.clone()), ("counter", counter.clone())],
+            value: *value as f64,
+        }));
+    }
+
+    let mut node_events = Vec::<(NodeEventKind, u32)>::new();
+    for event in &report.node_events {
+        match node_events.iter_mut().find(|(kind, _)| *kind == event.kind) {
+            Some((_, count)) => *count += 1,
+            None => node_events.push((event.kind, 1)),
+        }
+    }
+    samples.extend(node_events.into_iter().map(|(kind, count)| Sample {
+        name: "nomos_scenario_node_events",
+        help: "Node control actions observed during the run, by kind.",
+        labels: vec![("kind", snake_case_label(kind))],
+        value: f64::from(count),
+    }));
+
+    samples.extend(report.readiness.iter().map(|timing| Sample {
+        name: "nomos_scenario_readiness_seconds",
+        help: "Time spent waiting for a deployer readiness check.",
+        labels: vec![("check", timing.check.clone())],
+        value: timing.duration_ms as f64 / 1_000.0,
+    }));
+
+    // Retried phases appear several times; export their combined duration.
+    let mut phases = Vec::<(&str, u64)>::new();
+    for phase in &report.deployment {
+        match phases.iter_mut().find(|(name, _)| *name == phase.phase) {
+            Some((_, total)) => *total = total.saturating_add(phase.duration_ms),
+            None => phases.push((&phase.phase, phase.duration_ms)),
+        }
+    }
+    samples.extend(phases.into_iter().map(|(phase, duration_ms)| Sample {
+        name: "nomos_scenario_deployment_phase_seconds",
+        help: "Time a deployer spent in a cluster bring-up phase.",
+        labels: vec![("phase", phase.to_owned())],
+        value: duration_ms as f64 / 1_000.0,
+    }));
+
+    samples
+}
+
+/// Label value matching the variant's name in the run report.
+fn snake_case_label(variant: impl Serialize + std::fmt::Debug) -> String {
+    serde_json::to_value(&variant)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_owned))
+        .unwrap_or_else(|| format!("{variant:?}"))
+}
+
+/// Render samples in the Prometheus text exposition format, one `HELP`/`TYPE`
+/// header per metric family.
+fn exposition_text(samples: &[Sample]) -> String {
+    let mut body = String::new();
+    let mut last_family = None;
+    for sample in samples {
+        if last_family != Some(sample.name) {
+            let _ = writeln!(body, "# HELP {} {}", sample.name, sample.help);
+            let _ = writeln!(body, "# TYPE {} gauge", sample.name);
+            last_family = Some(sample.name);
+        }
+        body.push_str(sample.name);
+        if !sample.labels.is_empty() {
+            let labels = sample
+                .labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape_label(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(body, "{{{labels}}}");
+        }
+        let _ = writeln!(body, " {}", sample.value);
+    }
+    body
+}
+
+fn escape_label(raw: &str) -> String {
+    raw.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Build an OTLP `ExportMetricsServiceRequest` in its JSON encoding, with
+/// every sample as a gauge data point.
+fn otlp_payload(service: &str, instance: &str, samples: &[Sample]) -> Value {
+    let now_nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_nanos())
+        .to_string();
+
+    let mut metrics: Vec<Value> = Vec::new();
+    let mut last_family = None;
+    for sample in samples {
+        let point = json!({
+            "timeUnixNano": now_nanos,
+            "asDouble": sample.value,
+            "attributes": sample
+                .labels
+                .iter()
+                .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+                .collect::<Vec<_>>(),
+        });
+        if last_family == Some(sample.name) {
+            if let Some(points) = metrics
+                .last_mut()
+                .and_then(|metric| metric.pointer_mut("/gauge/dataPoints"))
+                .and_then(Value::as_array_mut)
+            {
+                points.push(point);
+                continue;
+            }
+        }
+        metrics.push(json!({
+            "name": sample.name,
+            "description": sample.help,
+            "gauge": { "dataPoints": [point] },
+        }));
+        last_family = Some(sample.name);
+    }
+
+    json!({
+        "resourceMetrics": [{
+            "resource": {
+                "attributes": [
+                    { "key": "service.name", "value": { "stringValue": service } },
+                    { "key": "service.instance.id", "value": { "stringValue": instance } },
+                ],
+            },
+            "scopeMetrics": [{
+                "scope": { "name": "testing-framework" },
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt as _, AsyncWriteExt as _},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::scenario::{RunReporter, Severity};
+
+    fn report() -> RunReport {
+        let reporter = RunReporter::new();
+        reporter.increment("tx", "submitted", 3);
+        reporter.record_expectation("liveness", Severity::Hard, &Ok(()));
+        reporter.record_expectation("fairness", Severity::Soft, &Err("skewed".into()));
+        reporter.record_readiness("network", Duration::from_millis(1_500));
+        reporter.snapshot()
+    }
+
+    #[test]
+    fn exposition_groups_families_and_escapes_labels() {
+        let samples = vec![
+            Sample {
+                name: "family_a",
+                help: "First.",
+                labels: vec![("label", "quoted \"value\"".to_owned())],
+                value: 1.0,
+            },
+            Sample {
+                name: "family_a",
+                help: "First.",
+                labels: vec![("label", "other".to_owned())],
+                value: 2.5,
+            },
+        ];
+
+        assert_eq!(
+            exposition_text(&samples),
+            "# HELP family_a First.\n# TYPE family_a gauge\n\
+             family_a{label=\"quoted \\\"value\\\"\"} 1\n\
+             family_a{label=\"other\"} 2.5\n"
+        );
+    }
+
+    #[test]
+    fn report_becomes_samples() {
+        let text = exposition_text(&samples(&report()));
+
+        assert!(text.contains("nomos_scenario_passed 1\n"), "{text}");
+        assert!(
+            text.contains(
+                "nomos_scenario_expectation_passed{expectation=\"fairness\",severity=\"soft\"} 0"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "nomos_scenario_workload_counter{workload=\"tx\",counter=\"submitted\"} 3"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.contains("nomos_scenario_readiness_seconds{check=\"network\"} 1.5"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn otlp_payload_carries_service_and_instance() {
+        let payload = otlp_payload("ci", "run-1", &samples(&report()));
+
+        let resource = &payload["resourceMetrics"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][1]["value"]["stringValue"],
+            "run-1"
+        );
+        let metrics = resource["scopeMetrics"][0]["metrics"]
+            .as_array()
+            .expect("metrics array");
+        let expectations = metrics
+            .iter()
+            .find(|metric| metric["name"] == "nomos_scenario_expectation_passed")
+            .expect("expectation family");
+        assert_eq!(
+            expectations["gauge"]["dataPoints"].as_array().map(Vec::len),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn pushgateway_groups_by_job_and_instance() {
+        let base = Url::parse("http://pushgateway:9091/prefix").expect("valid url");
+
+        let url = pushgateway_url(&base, "nightly", "run/7").expect("grouping url");
+        assert_eq!(
+            url.as_str(),
+            "http://pushgateway:9091/prefix/metrics/job/nightly/instance/run%2F7"
+        );
+    }
+
+    #[tokio::test]
+    async fn export_pushes_to_the_grouping_url() {
+        let listener = TcpListener::bind(("127.0.0.1", 0))
+            .await
+            .expect("bind pushgateway");
+        let addr = listener.local_addr().expect("local addr");
+        let received = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("push arrives");
+            let mut buf = vec![0; 64 * 1024];
+            let read = stream.read(&mut buf).await.expect("request read");
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await;
+            String::from_utf8_lossy(&buf[..read]).into_owned()
+        });
+
+        let base = Url::parse(&format!("http://{addr}/")).expect("valid url");
+        RunMetricsExporter::new(MetricsExportTarget::Pushgateway(base), "ci")
+            .with_instance("run-1")
+            .export(&report())
+            .await
+            .expect("push accepted");
+
+        let request = received.await.expect("server task");
+        assert!(
+            request.starts_with("POST /metrics/job/ci/instance/run-1 "),
+            "{request}"
+        );
+    }
+}
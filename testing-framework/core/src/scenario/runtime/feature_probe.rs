@@ -0,0 +1,156 @@
+use futures::future::join_all;
+use nomos_http_api_common::paths::DISPERSE_DATA;
+use reqwest::StatusCode;
+use tracing::info;
+
+use crate::{
+    nodes::ApiClient,
+    scenario::{DynError, NodeFeature, NodeTarget, runtime::context::RunContext},
+    topology::generation::NodeRole,
+};
+
+/// Checks that the cluster offers every feature in `required`, returning one
+/// line per node or feature found missing.
+pub(super) async fn probe_features(
+    required: &[NodeFeature],
+    context: &RunContext,
+) -> Result<(), DynError> {
+    if required.is_empty() {
+        return Ok(());
+    }
+
+    let mut missing = Vec::new();
+    for feature in required {
+        missing.extend(match feature {
+            NodeFeature::TestingHttp => probe_testing_http(context).await,
+            NodeFeature::ExecutorDispersal => probe_executor_dispersal(context).await,
+        });
+    }
+    if missing.is_empty() {
+        info!(features = ?required, "required node features available");
+        return Ok(());
+    }
+    Err(missing.join("\n").into())
+}
+
+/// Nodes running from the start; late joiners are not up yet when features
+/// are probed.
+fn initial_clients(context: &RunContext) -> Vec<(NodeTarget, &ApiClient)> {
+    let config = context.descriptors().config();
+    context
+        .node_clients()
+        .targeted_clients()
+        .filter(|(target, _)| !config.joins_late(*target))
+        .collect()
+}
+
+async fn probe_testing_http(context: &RunContext) -> Vec<String> {
+    let nodes = initial_clients(context);
+    let statuses = join_all(nodes.iter().map(|(_, client)| client.testing_api_status())).await;
+
+    nodes
+        .iter()
+        .zip(statuses)
+        .filter_map(|((target, _), status)| testing_http_issue(*target, status))
+        .collect()
+}
+
+fn testing_http_issue(
+    target: NodeTarget,
+    status: Option<reqwest::Result<StatusCode>>,
+) -> Option<String> {
+    let feature = NodeFeature::TestingHttp;
+    match status {
+        None => Some(format!(
+            "- {feature}: {target} exposes no testing API address"
+        )),
+        Some(Ok(StatusCode::NOT_FOUND)) => Some(format!(
+            "- {feature}: {target} does not serve the testing endpoints; build the node with the `testing` feature"
+        )),
+        Some(Ok(_)) => None,
+        Some(Err(err)) => Some(format!(
+            "- {feature}: {target} testing API unreachable ({err}); build the node with the `testing` feature"
+        )),
+    }
+}
+
+/// Asks every initial executor for its dispersal route. A `GET` is never
+/// dispersed: nodes serving the route answer with a method error, others
+/// with not found.
+async fn probe_executor_dispersal(context: &RunContext) -> Vec<String> {
+    let feature = NodeFeature::ExecutorDispersal;
+    let executors = initial_clients(context)
+        .into_iter()
+        .filter(|(target, _)| target.role() == NodeRole::Executor)
+        .collect::<Vec<_>>();
+    if executors.is_empty() {
+        return vec![format!(
+            "- {feature}: the topology has no executor running from the start"
+        )];
+    }
+
+    let answers = join_all(
+        executors
+            .iter()
+            .map(|(_, client)| client.get_response(DISPERSE_DATA)),
+    )
+    .await;
+    let issues = executors
+        .iter()
+        .zip(answers)
+        .filter_map(|((target, _), answer)| {
+            dispersal_issue(*target, answer.map(|response| response.status()))
+        })
+        .collect::<Vec<_>>();
+    if issues.len() < executors.len() {
+        return Vec::new();
+    }
+    issues
+}
+
+fn dispersal_issue(target: NodeTarget, status: reqwest::Result<StatusCode>) -> Option<String> {
+    let feature = NodeFeature::ExecutorDispersal;
+    match status {
+        Ok(StatusCode::NOT_FOUND) => Some(format!(
+            "- {feature}: {target} does not serve {DISPERSE_DATA}; deploy an executor binary"
+        )),
+        Ok(status) if status.is_server_error() => Some(format!(
+            "- {feature}: {target} dispersal endpoint failing ({status})"
+        )),
+        Ok(_) => None,
+        Err(err) => Some(format!("- {feature}: {target} API unreachable ({err})")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn testing_http_requires_served_endpoints() {
+        let target = NodeTarget::Validator(1);
+
+        assert!(testing_http_issue(target, Some(Ok(StatusCode::OK))).is_none());
+        assert!(testing_http_issue(target, Some(Ok(StatusCode::METHOD_NOT_ALLOWED))).is_none());
+        let missing = testing_http_issue(target, Some(Ok(StatusCode::NOT_FOUND)))
+            .expect("404 means no testing endpoints");
+        assert!(missing.contains("validator-1"), "{missing}");
+        assert!(
+            testing_http_issue(target, None)
+                .expect("no testing address")
+                .contains("no testing API address")
+        );
+    }
+
+    #[test]
+    fn dispersal_route_must_exist_and_not_fail() {
+        let target = NodeTarget::Executor(0);
+
+        assert!(dispersal_issue(target, Ok(StatusCode::METHOD_NOT_ALLOWED)).is_none());
+        assert!(dispersal_issue(target, Ok(StatusCode::UNPROCESSABLE_ENTITY)).is_none());
+        assert!(dispersal_issue(target, Ok(StatusCode::NOT_FOUND)).is_some());
+        let failing = dispersal_issue(target, Ok(StatusCode::SERVICE_UNAVAILABLE))
+            .expect("server errors count as missing");
+        assert!(failing.contains("executor-0"), "{failing}");
+    }
+}
@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use futures::future::join_all;
+use tracing::{info, warn};
+
+use crate::scenario::{
+    DynError, NodeEventKind, NodeExit, NodeTarget, runtime::context::RunContext,
+};
+
+/// Log line Rust's default panic hook prints.
+const PANIC_MARKER: &str = "panicked at";
+/// Exit code of a Rust process whose main thread panicked.
+const PANIC_EXIT_CODE: i32 = 101;
+/// Exit code of a container killed with `SIGKILL`, e.g. by the OOM killer.
+const SIGKILL_EXIT_CODE: i32 = 128 + 9;
+
+/// Stops every node with `SIGTERM` and checks each one exits within
+/// `deadline` without panicking, returning one line per misbehaving node.
+///
+/// Only log output written after the signal is inspected, so panics the run
+/// already tolerated do not count against shutdown.
+pub(super) async fn check_graceful_shutdown(
+    context: &RunContext,
+    deadline: Duration,
+) -> Result<(), DynError> {
+    let control = context
+        .node_control()
+        .ok_or("graceful shutdown check requires node control but the deployer provided none")?;
+    let targets = context
+        .node_clients()
+        .targeted_clients()
+        .map(|(target, _)| target)
+        .collect::<Vec<_>>();
+
+    info!(
+        nodes = targets.len(),
+        ?deadline,
+        "stopping nodes gracefully"
+    );
+    let checks = targets.into_iter().map(|target| {
+        let control = control.clone();
+        async move {
+            let label = target.to_string();
+            let logged_before = read_logs(context, &label).map_or(0, |logs| logs.len());
+            let exit = control.terminate_node(target, deadline).await;
+            context
+                .report()
+                .record_node_event(target, NodeEventKind::Stop);
+            let shutdown_logs = read_logs(context, &label)
+                .map(|logs| logs.get(logged_before..).unwrap_or(&logs).to_owned());
+            verdict(target, deadline, exit, shutdown_logs.as_deref())
+        }
+    });
+
+    let failures = join_all(checks)
+        .await
+        .into_iter()
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+    if failures.is_empty() {
+        info!("every node shut down gracefully");
+        return Ok(());
+    }
+
+    for failure in &failures {
+        warn!(%failure, "node did not shut down gracefully");
+    }
+    Err(failures.join("\n").into())
+}
+
+fn verdict(
+    target: NodeTarget,
+    deadline: Duration,
+    exit: Result<NodeExit, DynError>,
+    shutdown_logs: Option<&str>,
+) -> Result<(), String> {
+    let exit = exit.map_err(|err| format!("{target}: stopping failed: {err}"))?;
+    if exit.timed_out {
+        return Err(format!(
+            "{target}: still running {deadline:?} after SIGTERM and had to be killed"
+        ));
+    }
+    if exit.exit_code == Some(PANIC_EXIT_CODE) {
+        return Err(format!(
+            "{target}: exited with code {PANIC_EXIT_CODE} (panic) after {:?}",
+            exit.elapsed
+        ));
+    }
+    if exit.exit_code == Some(SIGKILL_EXIT_CODE) {
+        return Err(format!(
+            "{target}: was killed (exit code {SIGKILL_EXIT_CODE}, e.g. out of memory) after {:?}",
+            exit.elapsed
+        ));
+    }
+    if let Some(line) = shutdown_logs
+        .into_iter()
+        .flat_map(str::lines)
+        .find(|line| line.contains(PANIC_MARKER))
+    {
+        return Err(format!("{target}: panicked while shutting down: {line}"));
+    }
+    Ok(())
+}
+
+fn read_logs(context: &RunContext, node: &str) -> Option<String> {
+    let source = context.node_logs().get(node)?;
+    match source.read() {
+        Ok(logs) => Some(logs),
+        Err(err) => {
+            warn!(node, error = %err, "failed to read node logs for shutdown check");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARGET: NodeTarget = NodeTarget::Validator(1);
+    const DEADLINE: Duration = Duration::from_secs(10);
+
+    fn exited(exit_code: Option<i32>, timed_out: bool) -> Result<NodeExit, DynError> {
+        Ok(NodeExit {
+            elapsed: Duration::from_secs(2),
+            exit_code,
+            timed_out,
+        })
+    }
+
+    #[test]
+    fn clean_exits_pass() {
+        assert!(verdict(TARGET, DEADLINE, exited(Some(0), false), Some("bye\n")).is_ok());
+        // Ended by the signal itself rather than by a clean return.
+        assert!(verdict(TARGET, DEADLINE, exited(None, false), None).is_ok());
+        assert!(verdict(TARGET, DEADLINE, exited(Some(143), false), None).is_ok());
+    }
+
+    #[test]
+    fn kills_panics_and_failures_are_reported() {
+        let timed_out = verdict(TARGET, DEADLINE, exited(Some(137), true), None)
+            .expect_err("killed at the deadline");
+        assert!(timed_out.contains("still running 10s"), "{timed_out}");
+
+        let killed = verdict(TARGET, DEADLINE, exited(Some(137), false), None)
+            .expect_err("killed before the deadline");
+        assert!(killed.contains("out of memory"), "{killed}");
+
+        let panicked =
+            verdict(TARGET, DEADLINE, exited(Some(101), false), None).expect_err("panic exit code");
+        assert!(panicked.contains("(panic)"), "{panicked}");
+
+        let logged = verdict(
+            TARGET,
+            DEADLINE,
+            exited(Some(0), false),
+            Some("stopping\nthread 'main' panicked at src/main.rs:1:1\n"),
+        )
+        .expect_err("panic logged during shutdown");
+        assert!(
+            logged.starts_with("validator-1: panicked while shutting down"),
+            "{logged}"
+        );
+
+        let failed = verdict(TARGET, DEADLINE, Err("no such node".into()), None)
+            .expect_err("stop request failed");
+        assert!(failed.contains("stopping failed: no such node"), "{failed}");
+    }
+}
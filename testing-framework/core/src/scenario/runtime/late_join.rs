@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use tokio::time::{Instant, sleep_until};
+use tracing::info;
+
+use crate::scenario::{DynError, NodeEventKind, runtime::context::RunContext};
+
+/// Starts the topology's late-joining nodes once their delays elapse,
+/// measured from the moment workloads start.
+///
+/// Runs alongside the workloads and then stays pending so it never ends the
+/// run early. Fails when the deployer provided no node control, since there
+/// is then no way to start the held-back nodes.
+pub(super) async fn join_late_nodes(context: Arc<RunContext>) -> Result<(), DynError> {
+    let mut joins = context.descriptors().late_joins().to_vec();
+    if joins.is_empty() {
+        return Ok(());
+    }
+    let control = context
+        .node_control()
+        .ok_or("topology declares late-joining nodes but the deployer provided no node control")?;
+
+    joins.sort_by_key(|join| join.delay);
+    let start = Instant::now();
+    for join in joins {
+        sleep_until(start + join.delay).await;
+        info!(node = %join.target, delay = ?join.delay, "starting late-joining node");
+        control
+            .join_node(join.target)
+            .await
+            .map_err(|err| format!("starting late-joining {} failed: {err}", join.target))?;
+        context
+            .report()
+            .record_node_event(join.target, NodeEventKind::Join);
+    }
+
+    context.cancellation().cancelled().await;
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where a node writes its log output.
+#[derive(Clone, Debug)]
+pub enum LogLocation {
+    /// A single file receiving all output.
+    File(PathBuf),
+    /// Files inside `path` whose name starts with `prefix` (rolling
+    /// appenders create one per period).
+    Directory { path: PathBuf, prefix: String },
+}
+
+/// Log location of a single node, tagged with its identity
+/// (`validator-0`, `executor-1`, ...).
+#[derive(Clone, Debug)]
+pub struct NodeLogSource {
+    pub node: String,
+    pub location: LogLocation,
+}
+
+impl NodeLogSource {
+    /// Read the node's logs collected so far. Directory sources concatenate
+    /// matching files in name order.
+    pub fn read(&self) -> io::Result<String> {
+        match &self.location {
+            LogLocation::File(path) => fs::read_to_string(path),
+            LogLocation::Directory { path, prefix } => {
+                let mut files = fs::read_dir(path)?
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|file| {
+                        file.is_file()
+                            && file
+                                .file_name()
+                                .and_then(|name| name.to_str())
+                                .is_some_and(|name| name.starts_with(prefix.as_str()))
+                    })
+                    .collect::<Vec<_>>();
+                files.sort();
+
+                let mut contents = String::new();
+                for file in files {
+                    contents.push_str(&String::from_utf8_lossy(&fs::read(file)?));
+                }
+                Ok(contents)
+            }
+        }
+    }
+}
+
+/// Node log locations exposed by the deployer so expectations can inspect
+/// what the nodes printed during the run.
+#[derive(Clone, Debug, Default)]
+pub struct NodeLogs {
+    sources: Vec<NodeLogSource>,
+}
+
+impl NodeLogs {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Register a node whose logs are written to a single file.
+    pub fn with_file(mut self, node: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.sources.push(NodeLogSource {
+            node: node.into(),
+            location: LogLocation::File(path.into()),
+        });
+        self
+    }
+
+    #[must_use]
+    /// Register a node whose logs are files in `dir` starting with `prefix`.
+    pub fn with_directory(
+        mut self,
+        node: impl Into<String>,
+        dir: impl Into<PathBuf>,
+        prefix: impl Into<String>,
+    ) -> Self {
+        self.sources.push(NodeLogSource {
+            node: node.into(),
+            location: LogLocation::Directory {
+                path: dir.into(),
+                prefix: prefix.into(),
+            },
+        });
+        self
+    }
+
+    #[must_use]
+    pub fn sources(&self) -> &[NodeLogSource] {
+        &self.sources
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Looks up the log source of a node by identity.
+    #[must_use]
+    pub fn get(&self, node: &str) -> Option<&NodeLogSource> {
+        self.sources.iter().find(|source| source.node == node)
+    }
+
+    /// Directory containing every registered log file, when they share one.
+    #[must_use]
+    pub fn common_dir(&self) -> Option<&Path> {
+        let mut dirs = self.sources.iter().map(|source| match &source.location {
+            LogLocation::File(path) => path.parent(),
+            LogLocation::Directory { path, .. } => Some(path.as_path()),
+        });
+        let first = dirs.next()??;
+        dirs.all(|dir| dir == Some(first)).then_some(first)
+    }
+}
@@ -4,11 +4,36 @@ use prometheus_http_query::{Client as PrometheusClient, response::Data as Promet
 use reqwest::Url;
 use tracing::warn;
 
+use crate::{scenario::NodeTarget, topology::generation::NodeRole};
+
 pub const CONSENSUS_PROCESSED_BLOCKS: &str = "consensus_processed_blocks";
 pub const CONSENSUS_TRANSACTIONS_TOTAL: &str = "consensus_transactions_total";
 const CONSENSUS_TRANSACTIONS_VALIDATOR_QUERY: &str =
     r#"sum(consensus_transactions_total{job=~"validator-.*"})"#;
 
+/// Label carrying the node identifier (`validator-0`). Nodes push their
+/// metrics over OTLP with the identifier cfgsync hands them as service name,
+/// which Prometheus' OTLP receiver exposes as `job`.
+pub const NODE_LABEL: &str = "job";
+
+/// Label matcher selecting the series of a single node, e.g.
+/// `job="validator-0"`, for use inside a PromQL selector.
+#[must_use]
+pub fn node_selector(target: NodeTarget) -> String {
+    format!("{NODE_LABEL}=\"{target}\"")
+}
+
+/// Label matcher selecting the series of every node of `role`, e.g.
+/// `job=~"executor-[0-9]+"`.
+#[must_use]
+pub fn role_selector(role: NodeRole) -> String {
+    let prefix = match role {
+        NodeRole::Validator => "validator",
+        NodeRole::Executor => "executor",
+    };
+    format!("{NODE_LABEL}=~\"{prefix}-[0-9]+\"")
+}
+
 /// Telemetry handles available during a run.
 #[derive(Clone, Default)]
 pub struct Metrics {
@@ -202,3 +227,20 @@ impl PrometheusEndpoint {
             .map(|values| values.into_iter().sum())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selectors_match_otlp_job_names() {
+        assert_eq!(
+            node_selector(NodeTarget::Executor(1)),
+            r#"job="executor-1""#
+        );
+        assert_eq!(
+            role_selector(NodeRole::Validator),
+            r#"job=~"validator-[0-9]+""#
+        );
+    }
+}
@@ -1,12 +1,61 @@
+mod abort;
+mod api_faults;
+mod attach;
 mod block_feed;
+mod block_record;
+mod cancel;
+mod checkpoint;
 pub mod context;
+mod da_sampling;
+mod da_stats;
 mod deployer;
+mod dispersal;
+mod export;
+mod feature_probe;
+mod graceful_shutdown;
+mod late_join;
+mod logs;
 pub mod metrics;
 mod node_clients;
+mod recovery;
 mod runner;
+mod shutdown;
+mod stall;
+mod storage;
+mod timeline;
+mod traces;
+mod tx_latency;
+mod wallet_pool;
 
-pub use block_feed::{BlockFeed, BlockFeedTask, BlockRecord, BlockStats, spawn_block_feed};
+pub use abort::RunAbort;
+pub use api_faults::{ApiFaultProxies, ApiFaultStats, ApiFaults, FaultProxyError};
+pub use attach::{AttachDeployer, AttachDeployerError, AttachedNode};
+pub use block_feed::{
+    BlockFeed, BlockFeedTask, BlockRecord, BlockStats, ConsensusThroughput, FailoverReason,
+    FeedGap, spawn_block_feed, spawn_failover_block_feed,
+};
+pub use block_record::{BlockFeedRecorder, BlockRecordError, ReplayBlockFeed};
+pub use cancel::RunCancellation;
+pub use checkpoint::CheckpointPolicy;
 pub use context::{CleanupGuard, RunContext, RunHandle, RunMetrics};
+pub use da_sampling::{
+    PublishedBlob, PublishedBlobs, SamplingSweep, SweepFailure, sample_published_blobs,
+};
+pub use da_stats::{DaStatsSample, StatsSampler};
 pub use deployer::{Deployer, ScenarioError};
+pub use dispersal::{DispersalStats, ExecutorDispersal};
+pub use export::{
+    METRICS_JOB_ENV, METRICS_OTLP_ENV, METRICS_PUSHGATEWAY_ENV, MetricsExportError,
+    MetricsExportTarget, RunMetricsExporter,
+};
+pub use logs::{LogLocation, NodeLogSource, NodeLogs};
 pub use node_clients::NodeClients;
+pub use recovery::{NodeRecovery, RecoverySample};
 pub use runner::Runner;
+pub use shutdown::ShutdownSignal;
+pub use stall::{ConsensusSample, HeightDelta, StallDetector, StallEvent};
+pub use storage::{NodeStorage, StorageArea, StorageInspector, StoredFile};
+pub use timeline::{DeploymentPhase, DeploymentTimeline};
+pub use traces::{SpanSummary, TempoEndpoint, TraceSummary, TracesError};
+pub use tx_latency::{LatencyPercentiles, TxLatency};
+pub use wallet_pool::WalletPool;
@@ -4,8 +4,11 @@ use rand::{Rng as _, seq::SliceRandom as _, thread_rng};
 
 use crate::{
     nodes::ApiClient,
-    scenario::DynError,
-    topology::{deployment::Topology, generation::GeneratedTopology},
+    scenario::{DynError, NodeTarget},
+    topology::{
+        deployment::Topology,
+        generation::{GeneratedTopology, NodeRole},
+    },
 };
 
 /// Collection of API clients for the validator and executor set.
@@ -53,6 +56,45 @@ impl NodeClients {
         &self.executors
     }
 
+    #[must_use]
+    /// Client for a single node, if it is part of the topology.
+    pub fn client(&self, target: NodeTarget) -> Option<&ApiClient> {
+        match target {
+            NodeTarget::Validator(index) => self.validators.get(index),
+            NodeTarget::Executor(index) => self.executors.get(index),
+        }
+    }
+
+    #[must_use]
+    /// Client for the node with the given label (`validator-2`,
+    /// `executor-0`), matching the labels used in reports and logs.
+    pub fn by_label(&self, label: &str) -> Option<&ApiClient> {
+        NodeTarget::from_label(label).and_then(|target| self.client(target))
+    }
+
+    /// Iterator over every client with the node it belongs to, validators
+    /// first.
+    pub fn targeted_clients(&self) -> impl Iterator<Item = (NodeTarget, &ApiClient)> {
+        let validators = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, client)| (NodeTarget::Validator(index), client));
+        let executors = self
+            .executors
+            .iter()
+            .enumerate()
+            .map(|(index, client)| (NodeTarget::Executor(index), client));
+        validators.chain(executors)
+    }
+
+    /// Iterator over `(label, role, client)` for every node, for diagnostics
+    /// that should name the node they talk about.
+    pub fn labeled_clients(&self) -> impl Iterator<Item = (String, NodeRole, &ApiClient)> {
+        self.targeted_clients()
+            .map(|(target, client)| (target.to_string(), target.role(), client))
+    }
+
     #[must_use]
     /// Choose a random validator client if present.
     pub fn random_validator(&self) -> Option<&ApiClient> {
@@ -64,6 +106,24 @@ impl NodeClients {
         self.validators.get(idx)
     }
 
+    #[must_use]
+    /// Choose a random validator running from the start, skipping validators
+    /// the topology declares as late-joining.
+    pub fn random_initial_validator(&self, descriptors: &GeneratedTopology) -> Option<&ApiClient> {
+        let initial = self
+            .validators
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| {
+                !descriptors
+                    .config()
+                    .joins_late(NodeTarget::Validator(*index))
+            })
+            .map(|(_, client)| client)
+            .collect::<Vec<_>>();
+        initial.choose(&mut thread_rng()).copied()
+    }
+
     #[must_use]
     /// Choose a random executor client if present.
     pub fn random_executor(&self) -> Option<&ApiClient> {
@@ -0,0 +1,324 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures::future::join_all;
+use tokio::time::{Instant, sleep, timeout};
+use tracing::{debug, info};
+
+use super::node_clients::NodeClients;
+use crate::{
+    nodes::ApiClient,
+    scenario::{DynError, NodeControlHandle, NodeTarget},
+};
+
+/// Time between two probes of a restarted node.
+const PROBE_INTERVAL: Duration = Duration::from_millis(500);
+/// Time a single consensus info request may take.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How long nodes restarted during a run took to recover: to serve their API
+/// again and to catch up with the rest of the cluster.
+///
+/// Chaos workloads restart nodes through [`Self::restart`], which tracks the
+/// node in the background until it recovered or the tracking timeout
+/// elapsed. Clones share the samples.
+#[derive(Clone, Default)]
+pub struct NodeRecovery {
+    restarts: Arc<Mutex<Vec<Restart>>>,
+}
+
+struct Restart {
+    node: NodeTarget,
+    issued: Instant,
+    ready: Option<Instant>,
+    caught_up: Option<Instant>,
+    settled: bool,
+}
+
+/// Recovery of a single restart. Durations are measured from the moment the
+/// restart was issued.
+#[derive(Clone, Copy, Debug)]
+pub struct RecoverySample {
+    pub node: NodeTarget,
+    /// Until the node answered a consensus info request, if it did.
+    pub ready_after: Option<Duration>,
+    /// Until the node reached the height the cluster's tip had when the node
+    /// became ready, if it did.
+    pub caught_up_after: Option<Duration>,
+    /// Tracking is over: the node caught up or the timeout elapsed.
+    pub settled: bool,
+}
+
+impl NodeRecovery {
+    /// Restart `node` through `control` and track its recovery for up to
+    /// `tracking_timeout`, probing it and the cluster's tip through
+    /// `clients`.
+    pub async fn restart(
+        &self,
+        control: &dyn NodeControlHandle,
+        node: NodeTarget,
+        clients: &NodeClients,
+        tracking_timeout: Duration,
+    ) -> Result<(), DynError> {
+        let issued = Instant::now();
+        match node {
+            NodeTarget::Validator(index) => control.restart_validator(index).await,
+            NodeTarget::Executor(index) => control.restart_executor(index).await,
+        }?;
+
+        let id = self.begin(node, issued);
+        let Some(client) = clients.client(node).cloned() else {
+            debug!(%node, "no API client for restarted node; recovery not tracked");
+            self.update(id, |restart| restart.settled = true);
+            return Ok(());
+        };
+        let recovery = self.clone();
+        let clients = clients.clone();
+        tokio::spawn(async move {
+            recovery
+                .track(id, node, client, &clients, issued + tracking_timeout)
+                .await;
+        });
+        Ok(())
+    }
+
+    #[must_use]
+    /// Every restart issued so far, in order.
+    pub fn samples(&self) -> Vec<RecoverySample> {
+        self.restarts
+            .lock()
+            .expect("node recovery lock poisoned")
+            .iter()
+            .map(|restart| RecoverySample {
+                node: restart.node,
+                ready_after: restart.ready.map(|at| at - restart.issued),
+                caught_up_after: restart.caught_up.map(|at| at - restart.issued),
+                settled: restart.settled,
+            })
+            .collect()
+    }
+
+    #[must_use]
+    /// Longest time a restarted node took to catch up, among those that
+    /// did.
+    pub fn slowest(&self) -> Option<Duration> {
+        self.samples()
+            .iter()
+            .filter_map(|sample| sample.caught_up_after)
+            .max()
+    }
+
+    fn begin(&self, node: NodeTarget, issued: Instant) -> usize {
+        let mut restarts = self.restarts.lock().expect("node recovery lock poisoned");
+        restarts.push(Restart {
+            node,
+            issued,
+            ready: None,
+            caught_up: None,
+            settled: false,
+        });
+        restarts.len() - 1
+    }
+
+    async fn track(
+        &self,
+        id: usize,
+        node: NodeTarget,
+        client: ApiClient,
+        clients: &NodeClients,
+        deadline: Instant,
+    ) {
+        self.track_with(
+            id,
+            node,
+            deadline,
+            || probe_height(&client),
+            || cluster_tip(clients, node),
+        )
+        .await;
+    }
+
+    /// Probe the restarted node's height with `probe` until it reaches the
+    /// height `tip` reports once the node first answers.
+    async fn track_with<P, T>(
+        &self,
+        id: usize,
+        node: NodeTarget,
+        deadline: Instant,
+        probe: impl Fn() -> P,
+        tip: impl Fn() -> T,
+    ) where
+        P: Future<Output = Option<u64>>,
+        T: Future<Output = Option<u64>>,
+    {
+        let mut target_height = None;
+        while Instant::now() < deadline {
+            if let Some(height) = probe().await {
+                let target = match target_height {
+                    Some(target) => target,
+                    None => {
+                        self.update(id, |restart| restart.ready = Some(Instant::now()));
+                        let target = tip().await.unwrap_or(height);
+                        target_height = Some(target);
+                        target
+                    }
+                };
+                if height >= target {
+                    self.update(id, |restart| {
+                        restart.caught_up = Some(Instant::now());
+                        restart.settled = true;
+                    });
+                    info!(%node, height, "restarted node recovered");
+                    return;
+                }
+            }
+            sleep(PROBE_INTERVAL).await;
+        }
+        debug!(%node, "restarted node did not recover before tracking ended");
+        self.update(id, |restart| restart.settled = true);
+    }
+
+    fn update(&self, id: usize, f: impl FnOnce(&mut Restart)) {
+        if let Some(restart) = self
+            .restarts
+            .lock()
+            .expect("node recovery lock poisoned")
+            .get_mut(id)
+        {
+            f(restart);
+        }
+    }
+}
+
+/// Height of `client`, or `None` when it did not answer in time.
+async fn probe_height(client: &ApiClient) -> Option<u64> {
+    match timeout(PROBE_TIMEOUT, client.consensus_info()).await {
+        Ok(Ok(info)) => Some(info.height),
+        _ => None,
+    }
+}
+
+/// Highest height reported by any node other than `node`, asking all of
+/// them at once.
+async fn cluster_tip(clients: &NodeClients, node: NodeTarget) -> Option<u64> {
+    join_all(
+        clients
+            .targeted_clients()
+            .filter(|(target, _)| *target != node)
+            .map(|(_, client)| probe_height(client)),
+    )
+    .await
+    .into_iter()
+    .flatten()
+    .max()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    const NODE: NodeTarget = NodeTarget::Validator(1);
+
+    fn track_until(recovery: &NodeRecovery, tracking: Duration) -> (usize, Instant) {
+        let issued = Instant::now();
+        (recovery.begin(NODE, issued), issued + tracking)
+    }
+
+    #[tokio::test]
+    async fn node_at_the_tip_recovers_on_first_answer() {
+        let recovery = NodeRecovery::default();
+        let (id, deadline) = track_until(&recovery, Duration::from_secs(5));
+
+        recovery
+            .track_with(
+                id,
+                NODE,
+                deadline,
+                || async { Some(7) },
+                || async { Some(7) },
+            )
+            .await;
+
+        let samples = recovery.samples();
+        let [sample] = samples.as_slice() else {
+            panic!("one restart was tracked");
+        };
+        assert_eq!(sample.node, NODE);
+        assert!(sample.settled);
+        assert!(sample.ready_after.is_some());
+        assert!(sample.caught_up_after >= sample.ready_after);
+        assert_eq!(recovery.slowest(), sample.caught_up_after);
+    }
+
+    #[tokio::test]
+    async fn node_catches_up_to_the_tip_seen_when_ready() {
+        let recovery = NodeRecovery::default();
+        let (id, deadline) = track_until(&recovery, Duration::from_secs(10));
+        let height = AtomicU64::new(3);
+        let tips = AtomicU64::new(5);
+
+        recovery
+            .track_with(
+                id,
+                NODE,
+                deadline,
+                || {
+                    let height = height.fetch_add(1, Ordering::SeqCst);
+                    async move { Some(height) }
+                },
+                // Later tips are ignored: the target is fixed once ready.
+                || {
+                    let tip = tips.fetch_add(10, Ordering::SeqCst);
+                    async move { Some(tip) }
+                },
+            )
+            .await;
+
+        let sample = recovery.samples()[0];
+        assert!(sample.settled);
+        assert_eq!(height.load(Ordering::SeqCst), 6, "probed at 3, 4 and 5");
+        assert!(sample.caught_up_after.expect("caught up") >= PROBE_INTERVAL * 2);
+    }
+
+    #[tokio::test]
+    async fn tracking_ends_at_the_deadline() {
+        let recovery = NodeRecovery::default();
+
+        let (down, deadline) = track_until(&recovery, Duration::from_millis(1200));
+        recovery
+            .track_with(
+                down,
+                NODE,
+                deadline,
+                || async { None },
+                || async { Some(9) },
+            )
+            .await;
+        let (behind, deadline) = track_until(&recovery, Duration::from_millis(1200));
+        recovery
+            .track_with(
+                behind,
+                NODE,
+                deadline,
+                || async { Some(2) },
+                || async { Some(9) },
+            )
+            .await;
+
+        let samples = recovery.samples();
+        assert!(samples.iter().all(|sample| sample.settled));
+        assert!(samples[0].ready_after.is_none());
+        assert!(samples[1].ready_after.is_some());
+        assert!(
+            samples
+                .iter()
+                .all(|sample| sample.caught_up_after.is_none())
+        );
+        assert_eq!(recovery.slowest(), None);
+    }
+}
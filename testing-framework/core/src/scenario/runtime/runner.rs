@@ -1,19 +1,39 @@
-use std::{any::Any, panic::AssertUnwindSafe, sync::Arc, time::Duration};
+use std::{any::Any, num::NonZeroU64, panic::AssertUnwindSafe, sync::Arc, time::Duration};
 
 use futures::FutureExt as _;
 use tokio::{
-    task::JoinSet,
+    sync::broadcast,
+    task::{AbortHandle, JoinSet},
     time::{sleep, timeout},
 };
-
-use super::deployer::ScenarioError;
+use tracing::{info, warn};
+
+use super::{
+    block_record::BlockFeedRecorder,
+    checkpoint,
+    deployer::ScenarioError,
+    export::RunMetricsExporter,
+    feature_probe, graceful_shutdown, late_join,
+    shutdown::{ShutdownSignal, shutdown_signal},
+};
 use crate::scenario::{
-    DynError, Expectation, Scenario,
-    runtime::context::{CleanupGuard, RunContext, RunHandle},
+    DynError, Expectation, Scenario, Severity,
+    report::ReportFormat,
+    runtime::{
+        cancel::RunCancellation,
+        context::{CleanupGuard, RunContext, RunHandle},
+    },
 };
 
 type WorkloadOutcome = Result<(), DynError>;
 
+/// Block-bounded runs fail if the target is not reached within this multiple
+/// of the estimated run time.
+const BLOCK_TARGET_TIMEOUT_FACTOR: u32 = 5;
+/// Time workloads observing cancellation get to return before they are
+/// aborted.
+const WORKLOAD_CANCEL_GRACE: Duration = Duration::from_secs(5);
+
 /// Represents a fully prepared environment capable of executing a scenario.
 pub struct Runner {
     context: Arc<RunContext>,
@@ -57,28 +77,208 @@ impl Runner {
         Caps: Send + Sync,
     {
         let context = self.context();
+        let recorder = Self::start_block_recording(scenario, context.as_ref());
+        let result = Self::execute(&context, scenario).await;
+        drop(recorder);
+        self.finish(result)
+    }
+
+    /// Like [`Self::run`], but a SIGINT or SIGTERM received meanwhile aborts
+    /// the run: the report is written, the cleanup guard torn down (compose
+    /// stack, cfgsync server, port-forwards) and
+    /// [`ScenarioError::Interrupted`] returned.
+    ///
+    /// Tokio never uninstalls the signal handlers, so later signals in the
+    /// same process no longer terminate it. Only call this from binaries that
+    /// exit once the run is over, e.g. with [`ShutdownSignal::exit_code`].
+    pub async fn run_with_signal_handling<Caps>(
+        mut self,
+        scenario: &mut Scenario<Caps>,
+    ) -> Result<RunHandle, ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        let context = self.context();
+        let recorder = Self::start_block_recording(scenario, context.as_ref());
+
+        let signal = {
+            let execution = Self::execute(&context, scenario);
+            tokio::pin!(execution);
+            tokio::select! {
+                result = &mut execution => {
+                    drop(recorder);
+                    return self.finish(result);
+                }
+                signal = shutdown_signal() => signal,
+            }
+        };
+
+        drop(recorder);
+        Err(self.interrupt(scenario, signal))
+    }
+
+    fn finish(mut self, result: Result<(), ScenarioError>) -> Result<RunHandle, ScenarioError> {
+        match result {
+            Ok(()) => Ok(self.into_run_handle()),
+            Err(error) => {
+                self.cleanup();
+                Err(error)
+            }
+        }
+    }
+
+    async fn execute<Caps>(
+        context: &Arc<RunContext>,
+        scenario: &mut Scenario<Caps>,
+    ) -> Result<(), ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        if let Err(source) =
+            feature_probe::probe_features(scenario.required_features(), context.as_ref()).await
+        {
+            let error = ScenarioError::MissingFeatures(source);
+            Self::persist_failure(scenario, context.as_ref(), &error);
+            return Err(error);
+        }
         if let Err(error) =
             Self::prepare_expectations(scenario.expectations_mut(), context.as_ref()).await
         {
-            self.cleanup();
+            Self::persist_failure(scenario, context.as_ref(), &error);
             return Err(error);
         }
 
-        if let Err(error) = Self::run_workloads(&context, scenario).await {
-            self.cleanup();
+        if let Err(error) = Self::run_workloads(context, scenario).await {
+            Self::persist_failure(scenario, context.as_ref(), &error);
+            Self::export_metrics(context.as_ref()).await;
             return Err(error);
         }
 
-        Self::settle_before_expectations(&context).await;
+        Self::settle_before_expectations(context).await;
 
-        if let Err(error) =
-            Self::run_expectations(scenario.expectations_mut(), context.as_ref()).await
-        {
-            self.cleanup();
-            return Err(error);
+        let mut outcome =
+            Self::run_expectations(scenario.expectations_mut(), context.as_ref()).await;
+        if let Some(deadline) = scenario.graceful_shutdown() {
+            let shutdown = Self::assert_graceful_shutdown(context.as_ref(), deadline).await;
+            outcome = outcome.and(shutdown);
+        }
+        match &outcome {
+            Ok(()) => Self::persist_report(scenario, context.as_ref()),
+            Err(error) => Self::persist_failure(scenario, context.as_ref(), error),
         }
+        Self::export_metrics(context.as_ref()).await;
+        outcome
+    }
 
-        Ok(self.into_run_handle())
+    /// Tears the deployment down after a signal.
+    fn interrupt<Caps>(
+        mut self,
+        scenario: &Scenario<Caps>,
+        signal: ShutdownSignal,
+    ) -> ScenarioError {
+        warn!(%signal, "run interrupted; cleaning up");
+        let context = self.context();
+        context
+            .abort_signal()
+            .abort(format!("interrupted by {signal}"));
+        let error = ScenarioError::Interrupted(signal);
+        Self::persist_failure(scenario, context.as_ref(), &error);
+        self.cleanup();
+        info!(%signal, "cleanup finished after interrupt");
+        error
+    }
+
+    /// Records `error` as the outcome of the run, then writes the report.
+    fn persist_failure<Caps>(
+        scenario: &Scenario<Caps>,
+        context: &RunContext,
+        error: &ScenarioError,
+    ) {
+        context.report().record_error(error);
+        Self::persist_report(scenario, context);
+    }
+
+    /// Writes the run report if the scenario configured a destination. A
+    /// failure to persist the report never fails the run itself.
+    fn persist_report<Caps>(scenario: &Scenario<Caps>, context: &RunContext) {
+        if let Some(stats) = context.api_fault_stats() {
+            for (counter, value) in [
+                ("forwarded", stats.forwarded),
+                ("delayed", stats.delayed),
+                ("errors", stats.errors),
+                ("resets", stats.resets),
+            ] {
+                context.report().set_counter("api_faults", counter, value);
+            }
+        }
+        let throughput = context.consensus_throughput();
+        if throughput.blocks > 0 {
+            for (counter, value) in [
+                ("blocks", throughput.blocks),
+                ("transactions", throughput.transactions),
+                ("empty_blocks", throughput.empty_blocks),
+                ("p50_txs_per_block", throughput.p50_txs_per_block),
+                ("p95_txs_per_block", throughput.p95_txs_per_block),
+                ("max_txs_per_block", throughput.max_txs_per_block),
+            ] {
+                context.report().set_counter("consensus", counter, value);
+            }
+        }
+        let failovers = context.block_stats().gaps().len();
+        if failovers > 0 {
+            context
+                .report()
+                .set_counter("consensus", "feed_failovers", failovers as u64);
+        }
+
+        let Some(path) = scenario.report_path() else {
+            return;
+        };
+        let report = context.report().snapshot();
+        match report.write(path, ReportFormat::from_path(path)) {
+            Ok(()) => info!(path = %path.display(), "run report written"),
+            Err(err) => warn!(path = %path.display(), error = %err, "failed to write run report"),
+        }
+    }
+
+    /// Pushes scenario-level metrics to the backend configured by the
+    /// environment, if any. Like the report, an export failure never fails
+    /// the run.
+    async fn export_metrics(context: &RunContext) {
+        let exporter = match RunMetricsExporter::from_env() {
+            Ok(Some(exporter)) => match context.artifacts() {
+                Some(artifacts) => exporter.with_instance(artifacts.run_id()),
+                None => exporter,
+            },
+            Ok(None) => return,
+            Err(err) => {
+                warn!(error = %err, "invalid run metrics export configuration");
+                return;
+            }
+        };
+        match exporter.export(&context.report().snapshot()).await {
+            Ok(()) => info!(target = ?exporter.target(), "run metrics exported"),
+            Err(err) => warn!(error = %err, "failed to export run metrics"),
+        }
+    }
+
+    /// Starts recording the block feed if the scenario configured a
+    /// destination. Like the report, a recording failure never fails the run.
+    fn start_block_recording<Caps>(
+        scenario: &Scenario<Caps>,
+        context: &RunContext,
+    ) -> Option<BlockFeedRecorder> {
+        let path = scenario.block_record_path()?;
+        match BlockFeedRecorder::spawn(&context.block_feed(), path) {
+            Ok(recorder) => {
+                info!(path = %path.display(), "recording block feed");
+                Some(recorder)
+            }
+            Err(err) => {
+                warn!(error = %err, "failed to start block feed recording");
+                None
+            }
+        }
     }
 
     async fn prepare_expectations(
@@ -93,17 +293,53 @@ impl Runner {
         Ok(())
     }
 
-    /// Spawns every workload, waits until the configured duration elapses (or a
-    /// workload fails), and then aborts the remaining tasks.
+    /// Spawns every workload, waits until the configured duration elapses or
+    /// the target block count is observed (or a workload fails), and then
+    /// aborts the remaining tasks. Periodic expectations are checkpointed
+    /// alongside when the scenario configures an interval.
     async fn run_workloads<Caps>(
         context: &Arc<RunContext>,
-        scenario: &Scenario<Caps>,
+        scenario: &mut Scenario<Caps>,
     ) -> Result<(), ScenarioError>
     where
         Caps: Send + Sync,
     {
-        let mut workloads = Self::spawn_workloads(scenario, context);
-        let _ = Self::drive_until_timer(&mut workloads, scenario.duration()).await?;
+        let (mut workloads, unobserved) = Self::spawn_workloads(scenario, context);
+        let run_blocks = scenario.run_blocks();
+        let duration = scenario.duration();
+        let policy = *scenario.checkpoints();
+
+        let drive = Self::drive_workloads(&mut workloads, context, run_blocks, duration);
+        if let Some(interval) = policy.interval() {
+            let checkpoints = checkpoint::run_checkpoints(
+                scenario.expectations_mut(),
+                context.as_ref(),
+                interval,
+                policy.aborts_on_failure(),
+            );
+            tokio::select! {
+                result = drive => result?,
+                error = checkpoints => return Err(error),
+            }
+        } else {
+            drive.await?;
+        }
+
+        Self::drain_workloads(&mut workloads, &unobserved, context.cancellation()).await
+    }
+
+    async fn drive_workloads(
+        workloads: &mut JoinSet<WorkloadOutcome>,
+        context: &Arc<RunContext>,
+        run_blocks: Option<NonZeroU64>,
+        duration: Duration,
+    ) -> Result<(), ScenarioError> {
+        if let Some(target) = run_blocks {
+            let limit = duration.saturating_mul(BLOCK_TARGET_TIMEOUT_FACTOR);
+            Self::drive_until_blocks(workloads, context, target.get(), limit).await?;
+        } else {
+            let _ = Self::drive_until_timer(workloads, context, duration).await?;
+        }
 
         // Keep workloads running during the cooldown window so that late
         // inclusions (especially DA parent-linked ops) still have a chance to
@@ -114,12 +350,12 @@ impl Runner {
                 if workloads.is_empty() {
                     sleep(cooldown).await;
                 } else {
-                    let _ = Self::drive_until_timer(&mut workloads, cooldown).await?;
+                    let _ = Self::drive_until_timer(workloads, context, cooldown).await?;
                 }
             }
         }
 
-        Self::drain_workloads(&mut workloads).await
+        Ok(())
     }
 
     async fn settle_before_expectations(context: &Arc<RunContext>) {
@@ -146,8 +382,23 @@ impl Runner {
     ) -> Result<(), ScenarioError> {
         let mut failures: Vec<(String, DynError)> = Vec::new();
         for expectation in expectations {
-            if let Err(source) = expectation.evaluate(context).await {
-                failures.push((expectation.name().to_owned(), source));
+            let result = expectation.evaluate(context).await;
+            let severity = expectation.severity();
+            context
+                .report()
+                .record_expectation(expectation.name(), severity, &result);
+            match (result, severity) {
+                (Ok(()), _) => {}
+                (Err(source), Severity::Soft) => {
+                    warn!(
+                        expectation = expectation.name(),
+                        error = %source,
+                        "soft expectation failed; reporting as warning"
+                    );
+                }
+                (Err(source), Severity::Hard) => {
+                    failures.push((expectation.name().to_owned(), source));
+                }
             }
         }
 
@@ -164,6 +415,19 @@ impl Runner {
         Err(ScenarioError::Expectations(summary.into()))
     }
 
+    /// Stops every node gracefully and records the verdict alongside the
+    /// expectations.
+    async fn assert_graceful_shutdown(
+        context: &RunContext,
+        deadline: Duration,
+    ) -> Result<(), ScenarioError> {
+        let result = graceful_shutdown::check_graceful_shutdown(context, deadline).await;
+        context
+            .report()
+            .record_expectation("graceful_shutdown", Severity::Hard, &result);
+        result.map_err(ScenarioError::Shutdown)
+    }
+
     fn cooldown_duration(context: &RunContext) -> Option<Duration> {
         let metrics = context.run_metrics();
         let needs_stabilization = context.node_control().is_some();
@@ -197,21 +461,24 @@ impl Runner {
         }
     }
 
-    /// Spawns each workload inside its own task and returns the join set for
-    /// cooperative management.
+    /// Spawns each workload inside its own task, plus the task starting
+    /// late-joining nodes, and returns the join set together with the abort
+    /// handles of the workloads that do not observe cancellation.
     fn spawn_workloads<Caps>(
         scenario: &Scenario<Caps>,
         context: &Arc<RunContext>,
-    ) -> JoinSet<WorkloadOutcome>
+    ) -> (JoinSet<WorkloadOutcome>, Vec<AbortHandle>)
     where
         Caps: Send + Sync,
     {
         let mut workloads = JoinSet::new();
+        let mut unobserved = Vec::new();
         for workload in scenario.workloads() {
             let workload = Arc::clone(workload);
             let ctx = Arc::clone(context);
+            let observes_cancellation = workload.observes_cancellation();
 
-            workloads.spawn(async move {
+            let handle = workloads.spawn(async move {
                 let outcome = AssertUnwindSafe(async { workload.start(ctx.as_ref()).await })
                     .catch_unwind()
                     .await;
@@ -220,14 +487,23 @@ impl Runner {
                     Err(format!("workload panicked: {}", panic_message(panic)).into())
                 })
             });
+            if !observes_cancellation {
+                unobserved.push(handle);
+            }
+        }
+
+        if !scenario.topology().late_joins().is_empty() {
+            workloads.spawn(late_join::join_late_nodes(Arc::clone(context)));
         }
 
-        workloads
+        (workloads, unobserved)
     }
 
-    /// Polls workload tasks until the timeout fires or one reports an error.
+    /// Polls workload tasks until the timeout fires, one reports an error or
+    /// the run is aborted.
     async fn drive_until_timer(
         workloads: &mut JoinSet<WorkloadOutcome>,
+        context: &RunContext,
         duration: Duration,
     ) -> Result<bool, ScenarioError> {
         let run_future = async {
@@ -237,19 +513,83 @@ impl Runner {
             Ok(())
         };
 
-        timeout(duration, run_future)
-            .await
-            .map_or(Ok(true), |result| {
+        tokio::select! {
+            reason = context.abort_signal().aborted() => Err(ScenarioError::Aborted(reason)),
+            result = timeout(duration, run_future) => result.map_or(Ok(true), |result| {
                 result?;
                 Ok(false)
-            })
+            }),
+        }
+    }
+
+    /// Polls workload tasks until `target` blocks have been observed on the
+    /// block feed, failing if a workload errors, the run is aborted or `limit`
+    /// elapses first.
+    async fn drive_until_blocks(
+        workloads: &mut JoinSet<WorkloadOutcome>,
+        context: &RunContext,
+        target: u64,
+        limit: Duration,
+    ) -> Result<(), ScenarioError> {
+        let mut receiver = context.block_feed().subscribe();
+        let deadline = sleep(limit);
+        tokio::pin!(deadline);
+
+        let mut observed = 0u64;
+        while observed < target {
+            tokio::select! {
+                () = &mut deadline => {
+                    return Err(ScenarioError::BlockTarget { target, observed, limit });
+                }
+                reason = context.abort_signal().aborted() => {
+                    return Err(ScenarioError::Aborted(reason));
+                }
+                block = receiver.recv() => match block {
+                    Ok(_) => observed += 1,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => observed += skipped,
+                    Err(broadcast::error::RecvError::Closed) => {
+                        return Err(ScenarioError::Workload(
+                            format!("block feed closed after {observed}/{target} blocks").into(),
+                        ));
+                    }
+                },
+                Some(result) = workloads.join_next(), if !workloads.is_empty() => {
+                    Self::map_join_result(result)?;
+                }
+            }
+        }
+
+        info!(blocks = observed, "block target reached");
+        Ok(())
     }
 
-    /// Aborts and drains any remaining workload tasks so we do not leak work
-    /// across scenario runs.
+    /// Cancels the remaining workload tasks, aborts those in `unobserved`
+    /// right away, gives the others `WORKLOAD_CANCEL_GRACE` to return and
+    /// aborts the rest so we do not leak work across scenario runs.
     async fn drain_workloads(
         workloads: &mut JoinSet<WorkloadOutcome>,
+        unobserved: &[AbortHandle],
+        cancellation: &RunCancellation,
     ) -> Result<(), ScenarioError> {
+        cancellation.cancel();
+        for handle in unobserved {
+            handle.abort();
+        }
+        let finished = timeout(WORKLOAD_CANCEL_GRACE, async {
+            while let Some(result) = workloads.join_next().await {
+                Self::map_join_result(result)?;
+            }
+            Ok(())
+        })
+        .await;
+        if let Ok(result) = finished {
+            return result;
+        }
+
+        warn!(
+            remaining = workloads.len(),
+            "workloads still running after cancellation; aborting them"
+        );
         workloads.abort_all();
 
         while let Some(result) = workloads.join_next().await {
@@ -292,3 +632,82 @@ impl Drop for Runner {
         self.cleanup();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::future;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::scenario::{
+        NodeClients, RunReport, ScenarioBuilder, Workload,
+        runtime::{block_feed::BlockFeed, metrics::Metrics},
+    };
+
+    struct FailingWorkload;
+
+    #[async_trait]
+    impl Workload for FailingWorkload {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn start(&self, _ctx: &RunContext) -> Result<(), DynError> {
+            Err("submission rejected".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn drain_aborts_unobserved_workloads_without_grace() {
+        let cancellation = RunCancellation::default();
+        let mut workloads = JoinSet::new();
+        let ignoring = workloads.spawn(future::pending::<WorkloadOutcome>());
+        let observer = cancellation.clone();
+        workloads.spawn(async move {
+            observer.cancelled().await;
+            Ok(())
+        });
+
+        let started = std::time::Instant::now();
+        Runner::drain_workloads(&mut workloads, &[ignoring], &cancellation)
+            .await
+            .expect("cancelled workloads drain cleanly");
+
+        assert!(started.elapsed() < WORKLOAD_CANCEL_GRACE);
+        assert!(workloads.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_workload_produces_failing_report() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("run.json");
+        let mut scenario = ScenarioBuilder::topology_with(|t| t.validators(1))
+            .with_workload(FailingWorkload)
+            .with_report_path(&path)
+            .build();
+        let context = Arc::new(RunContext::new(
+            scenario.topology().clone(),
+            None,
+            NodeClients::new(Vec::new(), Vec::new()),
+            scenario.duration(),
+            Metrics::empty(),
+            BlockFeed::detached(),
+            None,
+        ));
+
+        let result = Runner::execute(&context, &mut scenario).await;
+
+        assert!(matches!(result, Err(ScenarioError::Workload(_))));
+        let report = RunReport::read(&path).expect("read report");
+        assert!(!report.passed());
+        assert!(
+            report
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("submission rejected")),
+            "{:?}",
+            report.error
+        );
+    }
+}
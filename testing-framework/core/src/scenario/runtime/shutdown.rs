@@ -0,0 +1,66 @@
+use std::fmt;
+
+use tracing::warn;
+
+/// Process signal that interrupted a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShutdownSignal {
+    /// SIGINT, e.g. Ctrl-C.
+    Interrupt,
+    /// SIGTERM, e.g. a CI job being cancelled.
+    Terminate,
+}
+
+impl ShutdownSignal {
+    /// Status a binary should exit with after an interrupted run, following
+    /// the shell convention of 128 plus the signal number.
+    #[must_use]
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Interrupt => 130,
+            Self::Terminate => 143,
+        }
+    }
+}
+
+impl fmt::Display for ShutdownSignal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Interrupt => f.write_str("SIGINT"),
+            Self::Terminate => f.write_str("SIGTERM"),
+        }
+    }
+}
+
+/// Resolves once the process receives SIGINT or SIGTERM. Never resolves if
+/// the handlers cannot be installed, leaving the default behaviour in place.
+pub(crate) async fn shutdown_signal() -> ShutdownSignal {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let (mut interrupt, mut terminate) = match (
+            signal(SignalKind::interrupt()),
+            signal(SignalKind::terminate()),
+        ) {
+            (Ok(interrupt), Ok(terminate)) => (interrupt, terminate),
+            (Err(err), _) | (_, Err(err)) => {
+                warn!(error = %err, "failed to install signal handlers");
+                return std::future::pending().await;
+            }
+        };
+        tokio::select! {
+            _ = interrupt.recv() => ShutdownSignal::Interrupt,
+            _ = terminate.recv() => ShutdownSignal::Terminate,
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            warn!(error = %err, "failed to install signal handlers");
+            return std::future::pending().await;
+        }
+        ShutdownSignal::Interrupt
+    }
+}
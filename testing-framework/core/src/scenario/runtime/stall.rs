@@ -0,0 +1,562 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use tokio::{
+    sync::broadcast,
+    task::JoinHandle,
+    time::{Instant, MissedTickBehavior, interval},
+};
+use tracing::{debug, warn};
+
+use super::{context::RunContext, node_clients::NodeClients};
+use crate::{nodes::ApiClient, scenario::RunReporter};
+
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+/// Poll interval used when the topology does not declare a slot duration.
+const FALLBACK_SLOT_DURATION: Duration = Duration::from_secs(1);
+/// Most recent samples kept across all nodes; older ones are dropped first.
+const MAX_SAMPLES: usize = 10_000;
+
+/// One `consensus_info` poll of a node; `height` is `None` when the request
+/// failed.
+#[derive(Clone, Debug)]
+pub struct ConsensusSample {
+    pub node: String,
+    /// Time since the detector started.
+    pub at: Duration,
+    pub height: Option<u64>,
+}
+
+/// Height change of a node between two successful polls.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeightDelta {
+    /// Time of the later poll since the detector started.
+    pub at: Duration,
+    /// Time between the two polls.
+    pub elapsed: Duration,
+    pub from: u64,
+    pub to: u64,
+}
+
+impl HeightDelta {
+    #[must_use]
+    /// Blocks gained between the polls; zero when the node reorged to a
+    /// lower height.
+    pub const fn blocks(&self) -> u64 {
+        self.to.saturating_sub(self.from)
+    }
+
+    #[must_use]
+    pub const fn progressed(&self) -> bool {
+        self.to > self.from
+    }
+}
+
+/// A change in a node's progress reported by [`StallDetector`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StallEvent {
+    /// `node` stayed at `height` (or could not be reached) for longer than
+    /// the detector's threshold, starting at `since`.
+    Stalled {
+        node: String,
+        height: u64,
+        since: Duration,
+        at: Duration,
+    },
+    /// `node` moved past the height it stalled at after `stalled_for`.
+    Resumed {
+        node: String,
+        height: u64,
+        stalled_for: Duration,
+        at: Duration,
+    },
+}
+
+impl StallEvent {
+    #[must_use]
+    pub fn node(&self) -> &str {
+        match self {
+            Self::Stalled { node, .. } | Self::Resumed { node, .. } => node,
+        }
+    }
+}
+
+/// Polls `consensus_info` on every node at a fixed interval and reports
+/// nodes whose height stops advancing.
+///
+/// A node is stalled once its height has not increased for longer than the
+/// threshold; failed polls count as no progress, so a node unreachable from
+/// the first poll stalls at height zero. Each stall produces one
+/// [`StallEvent::Stalled`] and, if the node advances again, one
+/// [`StallEvent::Resumed`]. Events and the most recent samples are kept in
+/// memory and broadcast to [`Self::subscribe`]rs. Clones share the same state;
+/// polling stops once the last clone is dropped or [`Self::stop`] is called.
+#[derive(Clone)]
+pub struct StallDetector {
+    inner: Arc<DetectorInner>,
+}
+
+struct DetectorInner {
+    state: Arc<Mutex<DetectorState>>,
+    sender: broadcast::Sender<StallEvent>,
+    threshold: Duration,
+    task: JoinHandle<()>,
+}
+
+impl Drop for DetectorInner {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[derive(Default)]
+struct DetectorState {
+    samples: VecDeque<ConsensusSample>,
+    /// Time of the latest sample, kept once older samples are dropped.
+    latest: Duration,
+    events: Vec<StallEvent>,
+    progress: HashMap<String, NodeProgress>,
+}
+
+/// Last height a node advanced to and whether it is currently stalled.
+#[derive(Clone, Copy)]
+struct NodeProgress {
+    height: u64,
+    since: Duration,
+    stalled: bool,
+}
+
+impl StallDetector {
+    #[must_use]
+    /// Start polling every node in `clients` each `every`, reporting nodes
+    /// without progress for longer than `threshold` (panics on a zero
+    /// interval).
+    pub fn spawn(clients: &NodeClients, every: Duration, threshold: Duration) -> Self {
+        assert!(!every.is_zero(), "stall detector interval must be non-zero");
+        let nodes: Vec<(String, ApiClient)> = clients
+            .labeled_clients()
+            .map(|(label, _, client)| (label, client.clone()))
+            .collect();
+        let state = Arc::new(Mutex::new(DetectorState::default()));
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        let task = tokio::spawn(poll_loop(
+            nodes,
+            every,
+            threshold,
+            Arc::clone(&state),
+            sender.clone(),
+        ));
+        Self {
+            inner: Arc::new(DetectorInner {
+                state,
+                sender,
+                threshold,
+                task,
+            }),
+        }
+    }
+
+    #[must_use]
+    /// Poll the run's nodes once per slot and report nodes that make no
+    /// progress for longer than `stall_blocks` expected block intervals.
+    pub fn for_run(ctx: &RunContext, stall_blocks: u64) -> Self {
+        let slot = ctx
+            .descriptors()
+            .slot_duration()
+            .unwrap_or(FALLBACK_SLOT_DURATION);
+        let active_slot_coeff = ctx
+            .descriptors()
+            .config()
+            .consensus_params
+            .active_slot_coeff;
+        Self::spawn(
+            ctx.node_clients(),
+            slot,
+            stall_threshold(slot, active_slot_coeff, stall_blocks),
+        )
+    }
+
+    /// Stop polling; samples and events collected so far stay available.
+    pub fn stop(&self) {
+        self.inner.task.abort();
+    }
+
+    #[must_use]
+    pub fn threshold(&self) -> Duration {
+        self.inner.threshold
+    }
+
+    #[must_use]
+    /// Stall events emitted from now on. Events emitted earlier are available
+    /// through [`Self::events`].
+    pub fn subscribe(&self) -> broadcast::Receiver<StallEvent> {
+        self.inner.sender.subscribe()
+    }
+
+    #[must_use]
+    /// Every event emitted so far, oldest first.
+    pub fn events(&self) -> Vec<StallEvent> {
+        self.lock().events.clone()
+    }
+
+    #[must_use]
+    /// Number of stalls detected so far.
+    pub fn stall_count(&self) -> usize {
+        self.lock()
+            .events
+            .iter()
+            .filter(|event| matches!(event, StallEvent::Stalled { .. }))
+            .count()
+    }
+
+    #[must_use]
+    /// Nodes stalled as of the latest poll, sorted by label.
+    pub fn stalled_nodes(&self) -> Vec<String> {
+        let mut nodes: Vec<String> = self
+            .lock()
+            .progress
+            .iter()
+            .filter(|(_, progress)| progress.stalled)
+            .map(|(node, _)| node.clone())
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[must_use]
+    /// Longest stretch any node went without progress, including stalls
+    /// still ongoing at the latest poll.
+    pub fn longest_stall(&self) -> Duration {
+        self.lock().longest_stall()
+    }
+
+    #[must_use]
+    /// Kept samples of a single node, oldest first. Only the most recent
+    /// samples of the run are kept.
+    pub fn samples_for(&self, node: &str) -> Vec<ConsensusSample> {
+        self.lock().samples_for(node)
+    }
+
+    #[must_use]
+    /// Height changes of a node between consecutive successful polls among
+    /// the kept samples.
+    pub fn deltas_for(&self, node: &str) -> Vec<HeightDelta> {
+        self.lock().deltas_for(node)
+    }
+
+    /// Record the stall count and the longest stall as `workload` counters in
+    /// the run report.
+    pub fn report_to(&self, reporter: &RunReporter, workload: &str) {
+        reporter.set_counter(workload, "stalls", self.stall_count() as u64);
+        reporter.set_counter(
+            workload,
+            "longest_stall_ms",
+            u64::try_from(self.longest_stall().as_millis()).unwrap_or(u64::MAX),
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DetectorState> {
+        self.inner
+            .state
+            .lock()
+            .expect("stall detector lock poisoned")
+    }
+}
+
+/// Time `blocks` expected block intervals take. A block is expected every
+/// `slot / active_slot_coeff`; coefficients outside `(0, 1)` count one block
+/// per slot.
+fn stall_threshold(slot: Duration, active_slot_coeff: f64, blocks: u64) -> Duration {
+    let slots = slot.saturating_mul(u32::try_from(blocks).unwrap_or(u32::MAX));
+    if active_slot_coeff > 0.0 && active_slot_coeff < 1.0 {
+        Duration::try_from_secs_f64(slots.as_secs_f64() / active_slot_coeff)
+            .unwrap_or(Duration::MAX)
+    } else {
+        slots
+    }
+}
+
+async fn poll_loop(
+    nodes: Vec<(String, ApiClient)>,
+    every: Duration,
+    threshold: Duration,
+    state: Arc<Mutex<DetectorState>>,
+    sender: broadcast::Sender<StallEvent>,
+) {
+    let started = Instant::now();
+    let mut ticker = interval(every);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+        for (node, client) in &nodes {
+            let height = match client.consensus_info().await {
+                Ok(info) => Some(info.height),
+                Err(err) => {
+                    debug!(node, %err, "consensus_info request failed");
+                    None
+                }
+            };
+            let sample = ConsensusSample {
+                node: node.clone(),
+                at: started.elapsed(),
+                height,
+            };
+            let event = state
+                .lock()
+                .expect("stall detector lock poisoned")
+                .observe(sample, threshold);
+            if let Some(event) = event {
+                if matches!(event, StallEvent::Stalled { .. }) {
+                    warn!(?event, "consensus stall detected");
+                }
+                let _ = sender.send(event);
+            }
+        }
+    }
+}
+
+impl DetectorState {
+    fn observe(&mut self, sample: ConsensusSample, threshold: Duration) -> Option<StallEvent> {
+        let at = sample.at;
+        let node = sample.node.clone();
+        let height = sample.height;
+        self.latest = self.latest.max(at);
+        self.samples.push_back(sample);
+        if self.samples.len() > MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+
+        let Some(progress) = self.progress.get_mut(&node) else {
+            self.progress.insert(
+                node,
+                NodeProgress {
+                    height: height.unwrap_or_default(),
+                    since: at,
+                    stalled: false,
+                },
+            );
+            return None;
+        };
+
+        let event = match height {
+            Some(height) if height > progress.height => {
+                let resumed = progress.stalled.then(|| StallEvent::Resumed {
+                    node,
+                    height,
+                    stalled_for: at.saturating_sub(progress.since),
+                    at,
+                });
+                *progress = NodeProgress {
+                    height,
+                    since: at,
+                    stalled: false,
+                };
+                resumed
+            }
+            _ if !progress.stalled && at.saturating_sub(progress.since) > threshold => {
+                progress.stalled = true;
+                Some(StallEvent::Stalled {
+                    node,
+                    height: progress.height,
+                    since: progress.since,
+                    at,
+                })
+            }
+            _ => None,
+        };
+        if let Some(event) = &event {
+            self.events.push(event.clone());
+        }
+        event
+    }
+
+    fn longest_stall(&self) -> Duration {
+        let resumed = self.events.iter().filter_map(|event| match event {
+            StallEvent::Resumed { stalled_for, .. } => Some(*stalled_for),
+            StallEvent::Stalled { .. } => None,
+        });
+        let ongoing = self
+            .progress
+            .values()
+            .filter(|progress| progress.stalled)
+            .map(|progress| self.latest.saturating_sub(progress.since));
+        resumed.chain(ongoing).max().unwrap_or_default()
+    }
+
+    fn samples_for(&self, node: &str) -> Vec<ConsensusSample> {
+        self.samples
+            .iter()
+            .filter(|sample| sample.node == node)
+            .cloned()
+            .collect()
+    }
+
+    fn deltas_for(&self, node: &str) -> Vec<HeightDelta> {
+        let polls: Vec<(Duration, u64)> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.node == node)
+            .filter_map(|sample| sample.height.map(|height| (sample.at, height)))
+            .collect();
+        polls
+            .windows(2)
+            .map(|pair| HeightDelta {
+                at: pair[1].0,
+                elapsed: pair[1].0.saturating_sub(pair[0].0),
+                from: pair[0].1,
+                to: pair[1].1,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const THRESHOLD: Duration = Duration::from_secs(3);
+
+    fn sample(node: &str, secs: u64, height: Option<u64>) -> ConsensusSample {
+        ConsensusSample {
+            node: node.to_owned(),
+            at: Duration::from_secs(secs),
+            height,
+        }
+    }
+
+    fn observe_all(
+        state: &mut DetectorState,
+        samples: impl IntoIterator<Item = ConsensusSample>,
+    ) -> Vec<StallEvent> {
+        samples
+            .into_iter()
+            .filter_map(|sample| state.observe(sample, THRESHOLD))
+            .collect()
+    }
+
+    #[test]
+    fn reports_stall_and_resume() {
+        let mut state = DetectorState::default();
+        let events = observe_all(
+            &mut state,
+            [
+                sample("validator-0", 0, Some(1)),
+                sample("validator-0", 2, Some(2)),
+                sample("validator-0", 4, Some(2)),
+                sample("validator-0", 6, None),
+                sample("validator-0", 7, Some(2)),
+                sample("validator-0", 9, Some(3)),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                StallEvent::Stalled {
+                    node: "validator-0".to_owned(),
+                    height: 2,
+                    since: Duration::from_secs(2),
+                    at: Duration::from_secs(6),
+                },
+                StallEvent::Resumed {
+                    node: "validator-0".to_owned(),
+                    height: 3,
+                    stalled_for: Duration::from_secs(7),
+                    at: Duration::from_secs(9),
+                },
+            ]
+        );
+        assert_eq!(state.longest_stall(), Duration::from_secs(7));
+    }
+
+    #[test]
+    fn node_unreachable_from_the_start_stalls() {
+        let mut state = DetectorState::default();
+        let events = observe_all(
+            &mut state,
+            [
+                sample("executor-0", 0, None),
+                sample("executor-0", 2, None),
+                sample("executor-0", 4, None),
+                sample("executor-0", 5, None),
+            ],
+        );
+
+        assert_eq!(
+            events,
+            vec![StallEvent::Stalled {
+                node: "executor-0".to_owned(),
+                height: 0,
+                since: Duration::ZERO,
+                at: Duration::from_secs(4),
+            }]
+        );
+        assert_eq!(state.longest_stall(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn deltas_skip_failed_polls() {
+        let mut state = DetectorState::default();
+        observe_all(
+            &mut state,
+            [
+                sample("validator-0", 0, Some(5)),
+                sample("validator-1", 1, Some(9)),
+                sample("validator-0", 2, None),
+                sample("validator-0", 3, Some(7)),
+                sample("validator-0", 4, Some(6)),
+            ],
+        );
+
+        let deltas = state.deltas_for("validator-0");
+        assert_eq!(
+            deltas,
+            vec![
+                HeightDelta {
+                    at: Duration::from_secs(3),
+                    elapsed: Duration::from_secs(3),
+                    from: 5,
+                    to: 7,
+                },
+                HeightDelta {
+                    at: Duration::from_secs(4),
+                    elapsed: Duration::from_secs(1),
+                    from: 7,
+                    to: 6,
+                },
+            ]
+        );
+        assert_eq!(deltas[0].blocks(), 2);
+        assert!(!deltas[1].progressed());
+        assert_eq!(deltas[1].blocks(), 0);
+        assert!(state.deltas_for("validator-1").is_empty());
+    }
+
+    #[test]
+    fn keeps_the_most_recent_samples() {
+        let mut state = DetectorState::default();
+        let total = MAX_SAMPLES as u64 + 5;
+        observe_all(
+            &mut state,
+            (0..total).map(|secs| sample("validator-0", secs, Some(secs))),
+        );
+
+        let samples = state.samples_for("validator-0");
+        assert_eq!(samples.len(), MAX_SAMPLES);
+        assert_eq!(samples[0].at, Duration::from_secs(5));
+        assert_eq!(state.latest, Duration::from_secs(total - 1));
+    }
+
+    #[test]
+    fn threshold_scales_with_active_slot_coeff() {
+        let slot = Duration::from_secs(2);
+
+        assert_eq!(stall_threshold(slot, 0.5, 5), Duration::from_secs(20));
+        assert_eq!(stall_threshold(slot, 1.0, 5), Duration::from_secs(10));
+        assert_eq!(stall_threshold(slot, 0.0, 5), Duration::from_secs(10));
+    }
+}
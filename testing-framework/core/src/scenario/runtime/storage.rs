@@ -0,0 +1,214 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::constants::{NODE_BLOBS_DIR, NODE_DB_DIR};
+
+/// Part of a node's data directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageArea {
+    /// RocksDB database holding blocks and chain state.
+    Database,
+    /// DA shares and commitments written by the verifier.
+    Blobs,
+}
+
+impl StorageArea {
+    const fn dir_name(self) -> &'static str {
+        match self {
+            Self::Database => NODE_DB_DIR,
+            Self::Blobs => NODE_BLOBS_DIR,
+        }
+    }
+}
+
+/// A file found in a node's storage.
+#[derive(Clone, Debug)]
+pub struct StoredFile {
+    pub path: PathBuf,
+    pub bytes: u64,
+    pub modified: SystemTime,
+}
+
+impl StoredFile {
+    #[must_use]
+    /// Time since the file was last written, zero if its timestamp lies in
+    /// the future.
+    pub fn age(&self) -> Duration {
+        SystemTime::now()
+            .duration_since(self.modified)
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn has_extension(&self, extension: &str) -> bool {
+        self.path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext == extension)
+    }
+}
+
+/// Read access to a node's data directory on the host, for expectations
+/// asserting storage invariants such as pruned blob files.
+///
+/// The node keeps writing while the run goes on, so listings are snapshots;
+/// files may disappear between listing and reading them.
+#[derive(Clone, Debug)]
+pub struct StorageInspector {
+    node: String,
+    root: PathBuf,
+}
+
+impl StorageInspector {
+    #[must_use]
+    pub fn new(node: impl Into<String>, root: impl Into<PathBuf>) -> Self {
+        Self {
+            node: node.into(),
+            root: root.into(),
+        }
+    }
+
+    #[must_use]
+    /// Identity of the node (`validator-0`, `executor-1`, ...).
+    pub fn node(&self) -> &str {
+        &self.node
+    }
+
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    #[must_use]
+    pub fn path(&self, area: StorageArea) -> PathBuf {
+        self.root.join(area.dir_name())
+    }
+
+    /// Every file below `area`, recursively. A missing directory (e.g. no
+    /// blob stored yet) yields an empty list.
+    pub fn files(&self, area: StorageArea) -> io::Result<Vec<StoredFile>> {
+        let mut files = Vec::new();
+        match collect_files(&self.path(area), &mut files) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+            Ok(()) => Ok(files),
+        }
+    }
+
+    /// Total size in bytes of the files below `area`.
+    pub fn usage(&self, area: StorageArea) -> io::Result<u64> {
+        Ok(self.files(area)?.iter().map(|file| file.bytes).sum())
+    }
+
+    /// Files below `area` not written for longer than `age`, e.g. blobs
+    /// that should have been pruned.
+    pub fn files_older_than(
+        &self,
+        area: StorageArea,
+        age: Duration,
+    ) -> io::Result<Vec<StoredFile>> {
+        let mut files = self.files(area)?;
+        files.retain(|file| file.age() > age);
+        Ok(files)
+    }
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<StoredFile>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            // Removed between listing and stat, e.g. by compaction.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        if metadata.is_dir() {
+            collect_files(&entry.path(), files)?;
+        } else {
+            files.push(StoredFile {
+                path: entry.path(),
+                bytes: metadata.len(),
+                modified: metadata.modified()?,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Node data directories exposed by the deployer. Empty when the nodes'
+/// storage is not reachable from the host (k8s, attached clusters).
+#[derive(Clone, Debug, Default)]
+pub struct NodeStorage {
+    nodes: Vec<StorageInspector>,
+}
+
+impl NodeStorage {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    #[must_use]
+    /// Register the data directory of `node`.
+    pub fn with_node(mut self, node: impl Into<String>, root: impl Into<PathBuf>) -> Self {
+        self.nodes.push(StorageInspector::new(node, root));
+        self
+    }
+
+    #[must_use]
+    pub fn inspectors(&self) -> &[StorageInspector] {
+        &self.nodes
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Looks up the storage of a node by identity.
+    #[must_use]
+    pub fn get(&self, node: &str) -> Option<&StorageInspector> {
+        self.nodes.iter().find(|inspector| inspector.node == node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inspector_lists_files_per_area() {
+        let root = std::env::temp_dir().join(format!("storage-inspector-{}", std::process::id()));
+        let blobs = root.join(NODE_BLOBS_DIR).join("shares");
+        fs::create_dir_all(&blobs).expect("blob dir created");
+        fs::write(blobs.join("a.blob"), [0u8; 10]).expect("blob written");
+        fs::write(blobs.join("b.blob"), [0u8; 5]).expect("blob written");
+
+        let storage = NodeStorage::new().with_node("validator-0", &root);
+        let inspector = storage.get("validator-0").expect("node registered");
+        assert!(storage.get("validator-1").is_none());
+
+        let mut files = inspector.files(StorageArea::Blobs).expect("blobs listed");
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|file| file.has_extension("blob")));
+        assert_eq!(inspector.usage(StorageArea::Blobs).expect("usage"), 15);
+        assert!(
+            inspector
+                .files_older_than(StorageArea::Blobs, Duration::from_secs(3600))
+                .expect("blobs listed")
+                .is_empty()
+        );
+        assert!(
+            inspector
+                .files(StorageArea::Database)
+                .expect("missing area is empty")
+                .is_empty()
+        );
+
+        fs::remove_dir_all(&root).expect("temp dir removed");
+    }
+}
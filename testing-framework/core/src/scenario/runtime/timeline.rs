@@ -0,0 +1,90 @@
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// A step of bringing the cluster up, such as building images or waiting
+/// for readiness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeploymentPhase {
+    pub name: String,
+    /// Time from the start of the deployment until the phase started.
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+impl DeploymentPhase {
+    #[must_use]
+    /// Time from the start of the deployment until the phase ended.
+    pub fn end(&self) -> Duration {
+        self.start + self.duration
+    }
+}
+
+/// Bring-up phases a deployer went through, in the order they started.
+///
+/// Deployers record one entry per phase (a phase retried on failure is
+/// recorded once per attempt) and attach the timeline to the
+/// [`RunContext`](super::RunContext), which also copies it into the run
+/// report, so expectations and report consumers can flag slow bring-ups.
+#[derive(Clone, Debug)]
+pub struct DeploymentTimeline {
+    started: Instant,
+    phases: Vec<DeploymentPhase>,
+}
+
+impl Default for DeploymentTimeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DeploymentTimeline {
+    /// Start a timeline; phase starts are measured from now.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    /// Record phase `name` as running from `started` until now.
+    pub fn record(&mut self, name: impl Into<String>, started: Instant) {
+        self.phases.push(DeploymentPhase {
+            name: name.into(),
+            start: started.saturating_duration_since(self.started),
+            duration: started.elapsed(),
+        });
+    }
+
+    /// Run `future` as phase `name`. The phase is recorded whether the
+    /// future succeeds or not.
+    pub async fn measure<F: Future>(&mut self, name: impl Into<String>, future: F) -> F::Output {
+        let started = Instant::now();
+        let output = future.await;
+        self.record(name, started);
+        output
+    }
+
+    #[must_use]
+    pub fn phases(&self) -> &[DeploymentPhase] {
+        &self.phases
+    }
+
+    /// Last recorded phase called `name`.
+    #[must_use]
+    pub fn phase(&self, name: &str) -> Option<&DeploymentPhase> {
+        self.phases.iter().rev().find(|phase| phase.name == name)
+    }
+
+    /// Time from the start of the deployment until the last phase ended.
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.phases
+            .iter()
+            .map(DeploymentPhase::end)
+            .max()
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,186 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Spans returned per matching trace; Tempo's default is 3.
+const SPANS_PER_TRACE: usize = 100;
+
+/// Failures querying the trace backend.
+#[derive(Debug, Error)]
+pub enum TracesError {
+    #[error("trace search `{query}` failed: {source}")]
+    Search {
+        query: String,
+        #[source]
+        source: reqwest::Error,
+    },
+    #[error("unexpected value {value:?} in tempo response")]
+    Response { value: String },
+}
+
+/// Trace matching a TraceQL search.
+#[derive(Clone, Debug)]
+pub struct TraceSummary {
+    pub trace_id: String,
+    pub root_service: String,
+    pub root_name: String,
+    pub duration: Duration,
+    /// Spans of the trace matched by the query, not the whole trace.
+    pub spans: Vec<SpanSummary>,
+}
+
+/// Span matched by a TraceQL search.
+#[derive(Clone, Debug)]
+pub struct SpanSummary {
+    pub span_id: String,
+    pub name: String,
+    pub duration: Duration,
+}
+
+/// Client for the Tempo instance collecting node traces, queried with
+/// TraceQL (e.g. `{ name = "dispersal" && duration > 500ms }`).
+///
+/// Nodes export spans in batches, so spans ended in the last few seconds of
+/// a run may not be searchable yet.
+#[derive(Clone, Debug)]
+pub struct TempoEndpoint {
+    base_url: Url,
+    client: Client,
+}
+
+impl TempoEndpoint {
+    #[must_use]
+    pub fn new(base_url: Url) -> Self {
+        Self {
+            base_url,
+            client: Client::new(),
+        }
+    }
+
+    #[must_use]
+    pub const fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Up to `limit` traces started after `since` matching the TraceQL
+    /// `query`.
+    pub async fn search(
+        &self,
+        query: &str,
+        since: SystemTime,
+        limit: usize,
+    ) -> Result<Vec<TraceSummary>, TracesError> {
+        let mut url = self.base_url.clone();
+        url.set_path(&format!(
+            "{}/api/search",
+            self.base_url.path().trim_end_matches('/')
+        ));
+        let start = since
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let end = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            + 1;
+        let search_error = |source| TracesError::Search {
+            query: query.to_owned(),
+            source,
+        };
+
+        let response: SearchResponse = self
+            .client
+            .get(url)
+            .query(&[
+                ("q", query.to_owned()),
+                ("limit", limit.to_string()),
+                ("spss", SPANS_PER_TRACE.to_string()),
+                ("start", start.to_string()),
+                ("end", end.to_string()),
+            ])
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(search_error)?
+            .json()
+            .await
+            .map_err(search_error)?;
+
+        response.traces.into_iter().map(TryInto::try_into).collect()
+    }
+}
+
+#[derive(Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    traces: Vec<RawTrace>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawTrace {
+    #[serde(rename = "traceID")]
+    trace_id: String,
+    #[serde(default)]
+    root_service_name: String,
+    #[serde(default)]
+    root_trace_name: String,
+    #[serde(default)]
+    duration_ms: u64,
+    #[serde(default)]
+    span_sets: Vec<RawSpanSet>,
+}
+
+#[derive(Deserialize)]
+struct RawSpanSet {
+    #[serde(default)]
+    spans: Vec<RawSpan>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawSpan {
+    #[serde(rename = "spanID")]
+    span_id: String,
+    #[serde(default)]
+    name: String,
+    // Tempo encodes 64-bit integers as strings.
+    #[serde(default)]
+    duration_nanos: Option<String>,
+}
+
+impl TryFrom<RawTrace> for TraceSummary {
+    type Error = TracesError;
+
+    fn try_from(raw: RawTrace) -> Result<Self, Self::Error> {
+        let spans = raw
+            .span_sets
+            .into_iter()
+            .flat_map(|set| set.spans)
+            .map(|span| {
+                let nanos = match span.duration_nanos {
+                    Some(raw) => raw
+                        .parse::<u64>()
+                        .map_err(|_| TracesError::Response { value: raw })?,
+                    None => 0,
+                };
+                Ok(SpanSummary {
+                    span_id: span.span_id,
+                    name: span.name,
+                    duration: Duration::from_nanos(nanos),
+                })
+            })
+            .collect::<Result<_, TracesError>>()?;
+
+        Ok(Self {
+            trace_id: raw.trace_id,
+            root_service: raw.root_service_name,
+            root_name: raw.root_trace_name,
+            duration: Duration::from_millis(raw.duration_ms),
+            spans,
+        })
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use nomos_core::mantle::{Transaction as _, TxHash};
+use serde::Serialize;
+
+use super::block_feed::BlockRecord;
+
+/// Submissions tracked at once. Older ones are forgotten so transactions
+/// that never land cannot grow the tracker for the whole run.
+const MAX_TRACKED_SUBMISSIONS: usize = 65_536;
+
+/// Submission-to-inclusion latency of transactions submitted during a run.
+///
+/// Workloads record when a transaction was accepted by a node; block feed
+/// consumers record when it shows up in a block. Clones share the same
+/// samples. Only the latest [`MAX_TRACKED_SUBMISSIONS`] submissions are
+/// tracked.
+#[derive(Clone, Default)]
+pub struct TxLatency {
+    state: Arc<Mutex<LatencyState>>,
+}
+
+#[derive(Default)]
+struct LatencyState {
+    pending: HashMap<TxHash, Instant>,
+    /// Submission order of `pending`; may still hold hashes included since.
+    order: VecDeque<TxHash>,
+    samples: Vec<Duration>,
+}
+
+/// Summary of the inclusion latencies observed so far.
+#[derive(Clone, Copy, Debug, Serialize)]
+pub struct LatencyPercentiles {
+    pub samples: usize,
+    pub p50: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl TxLatency {
+    /// Remember when `tx` was submitted.
+    pub fn record_submission(&self, tx: TxHash) {
+        let mut state = self.state.lock().expect("tx latency lock poisoned");
+        if state.pending.contains_key(&tx) {
+            return;
+        }
+        if state.order.len() >= MAX_TRACKED_SUBMISSIONS
+            && let Some(oldest) = state.order.pop_front()
+        {
+            state.pending.remove(&oldest);
+        }
+        state.pending.insert(tx, Instant::now());
+        state.order.push_back(tx);
+    }
+
+    /// Record the inclusion of `tx`, returning its latency when its
+    /// submission was tracked. Later inclusions of the same transaction are
+    /// ignored.
+    pub fn record_inclusion(&self, tx: &TxHash) -> Option<Duration> {
+        let mut state = self.state.lock().expect("tx latency lock poisoned");
+        let latency = state.pending.remove(tx)?.elapsed();
+        state.samples.push(latency);
+        Some(latency)
+    }
+
+    /// Record the inclusion of every transaction in `record`. Blocks the feed
+    /// caught up after switching sources arrived late, so their
+    /// transactions are dropped instead of sampled.
+    pub fn observe_block(&self, record: &BlockRecord) {
+        if record.gap.is_some() {
+            let mut state = self.state.lock().expect("tx latency lock poisoned");
+            for tx in record.block.transactions() {
+                state.pending.remove(&tx.hash());
+            }
+            return;
+        }
+        for tx in record.block.transactions() {
+            self.record_inclusion(&tx.hash());
+        }
+    }
+
+    #[must_use]
+    /// Submitted transactions not seen in a block yet.
+    pub fn pending(&self) -> usize {
+        self.state
+            .lock()
+            .expect("tx latency lock poisoned")
+            .pending
+            .len()
+    }
+
+    #[must_use]
+    /// Nearest-rank percentile (`0.0..=100.0`) of the observed latencies.
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        let mut samples = self
+            .state
+            .lock()
+            .expect("tx latency lock poisoned")
+            .samples
+            .clone();
+        samples.sort_unstable();
+        nearest_rank(&samples, percentile)
+    }
+
+    #[must_use]
+    pub fn percentiles(&self) -> Option<LatencyPercentiles> {
+        let mut samples = self
+            .state
+            .lock()
+            .expect("tx latency lock poisoned")
+            .samples
+            .clone();
+        samples.sort_unstable();
+        Some(LatencyPercentiles {
+            samples: samples.len(),
+            p50: nearest_rank(&samples, 50.0)?,
+            p95: nearest_rank(&samples, 95.0)?,
+            p99: nearest_rank(&samples, 99.0)?,
+            max: *samples.last()?,
+        })
+    }
+}
+
+pub(super) fn nearest_rank(sorted: &[Duration], percentile: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted.get(rank.saturating_sub(1)).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::nearest_rank;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn nearest_rank_picks_the_smallest_sample_covering_the_percentile() {
+        let sorted = millis(&[10, 20, 30, 40, 50, 60, 70, 80, 90, 100]);
+
+        assert_eq!(nearest_rank(&sorted, 50.0), Some(Duration::from_millis(50)));
+        assert_eq!(
+            nearest_rank(&sorted, 95.0),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            nearest_rank(&sorted, 91.0),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(nearest_rank(&sorted, 90.0), Some(Duration::from_millis(90)));
+        assert_eq!(
+            nearest_rank(&sorted, 100.0),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn nearest_rank_clamps_out_of_range_percentiles() {
+        let sorted = millis(&[10, 20, 30]);
+
+        assert_eq!(nearest_rank(&sorted, 0.0), Some(Duration::from_millis(10)));
+        assert_eq!(nearest_rank(&sorted, -5.0), Some(Duration::from_millis(10)));
+        assert_eq!(
+            nearest_rank(&sorted, 250.0),
+            Some(Duration::from_millis(30))
+        );
+    }
+
+    #[test]
+    fn nearest_rank_of_no_samples_is_none() {
+        assert_eq!(nearest_rank(&[], 50.0), None);
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::{Arc, Mutex};
+
+use crate::topology::configs::wallet::WalletAccount;
+
+/// Hands the topology's wallet accounts out to workloads, each at most once.
+///
+/// A genesis account owns a single note, so two workloads spending from the
+/// same account race for it and one of their transactions is dropped.
+/// Workloads that need accounts of their own claim them here, during `init`
+/// or while the run is going, and get accounts nobody else claimed. Clones
+/// share the claims, so the pool reached through the scenario's topology and
+/// through [`super::RunContext::wallet_pool`] is the same.
+#[derive(Clone, Debug, Default)]
+pub struct WalletPool {
+    accounts: Arc<[WalletAccount]>,
+    claimed: Arc<Mutex<Vec<bool>>>,
+}
+
+impl WalletPool {
+    #[must_use]
+    pub fn new(accounts: Vec<WalletAccount>) -> Self {
+        let claimed = vec![false; accounts.len()];
+        Self {
+            accounts: accounts.into(),
+            claimed: Arc::new(Mutex::new(claimed)),
+        }
+    }
+
+    /// Claim up to `count` unclaimed accounts, in wallet order. Returns fewer
+    /// when the pool runs dry.
+    #[must_use]
+    pub fn claim(&self, count: usize) -> Vec<WalletAccount> {
+        self.claim_where(count, |_| true)
+    }
+
+    /// Claim one fresh account, if any is left.
+    #[must_use]
+    pub fn claim_one(&self) -> Option<WalletAccount> {
+        self.claim(1).pop()
+    }
+
+    /// Claim up to `count` unclaimed accounts derived for `user`, see
+    /// [`WalletAccount::derived`].
+    #[must_use]
+    pub fn claim_children(&self, user: u32, count: usize) -> Vec<WalletAccount> {
+        self.claim_where(count, |account| {
+            account.path.is_some_and(|path| path.user == user)
+        })
+    }
+
+    /// Accounts nobody claimed yet.
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.claimed
+            .lock()
+            .expect("wallet pool lock poisoned")
+            .iter()
+            .filter(|claimed| !**claimed)
+            .count()
+    }
+
+    /// Every account of the wallet, claimed or not.
+    #[must_use]
+    pub fn accounts(&self) -> &[WalletAccount] {
+        &self.accounts
+    }
+
+    /// Claim up to `count` unclaimed accounts accepted by `matches`, in
+    /// wallet order, for workloads that can only use some of the accounts.
+    #[must_use]
+    pub fn claim_where(
+        &self,
+        count: usize,
+        matches: impl Fn(&WalletAccount) -> bool,
+    ) -> Vec<WalletAccount> {
+        let mut claimed = self.claimed.lock().expect("wallet pool lock poisoned");
+        let mut taken = Vec::new();
+        for (index, account) in self.accounts.iter().enumerate() {
+            if taken.len() == count {
+                break;
+            }
+            if !claimed[index] && matches(account) {
+                claimed[index] = true;
+                taken.push(account.clone());
+            }
+        }
+        tracing::debug!(
+            requested = count,
+            claimed = taken.len(),
+            "claimed wallet accounts"
+        );
+        taken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::*;
+    use crate::topology::configs::wallet::{TokenDistribution, WalletConfig};
+
+    fn labels(accounts: &[WalletAccount]) -> Vec<&str> {
+        accounts
+            .iter()
+            .map(|account| account.label.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn accounts_are_handed_out_once() {
+        let users = NonZeroUsize::new(3).expect("non-zero");
+        let pool = WalletPool::new(WalletConfig::uniform(30, users).accounts);
+        let other = pool.clone();
+
+        assert_eq!(labels(&pool.claim(2)), ["wallet-user-0", "wallet-user-1"]);
+        assert_eq!(labels(&other.claim(5)), ["wallet-user-2"]);
+        assert!(pool.claim_one().is_none());
+        assert_eq!(pool.available(), 0);
+        assert_eq!(pool.accounts().len(), 3);
+    }
+
+    #[test]
+    fn claims_skip_accounts_that_do_not_match() {
+        let users = NonZeroUsize::new(2).expect("non-zero");
+        let children = NonZeroUsize::new(2).expect("non-zero");
+        let config = WalletConfig::derived(40, &TokenDistribution::uniform(users), children, 7)
+            .expect("enough funds");
+        let pool = WalletPool::new(config.accounts);
+
+        assert_eq!(
+            labels(&pool.claim_children(1, 3)),
+            ["wallet-user-1/0", "wallet-user-1/1"]
+        );
+        let second = pool.accounts()[1].public_key();
+        assert_eq!(
+            labels(&pool.claim_where(2, |account| account.public_key() != second)),
+            ["wallet-user-0/0"]
+        );
+        assert_eq!(pool.available(), 1);
+    }
+}
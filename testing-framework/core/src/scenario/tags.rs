@@ -0,0 +1,113 @@
+use std::env;
+
+/// Environment variable holding a [`TagFilter`] expression, e.g.
+/// `da,chaos,!slow`.
+pub const TAG_FILTER_ENV: &str = "NOMOS_SCENARIO_TAGS";
+
+/// Selects which tagged workloads and expectations of a scenario run.
+///
+/// An expression is a comma separated list of tags; tags prefixed with `!`
+/// are excluded. An item runs when it carries none of the excluded tags and,
+/// if any tags are included, at least one of them. Expectations attached by a
+/// workload also carry that workload's tags, so they are dropped along with
+/// it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagFilter {
+    include: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl TagFilter {
+    /// Parse a filter expression; empty entries are ignored.
+    #[must_use]
+    pub fn parse(expression: &str) -> Self {
+        let mut filter = Self::default();
+        for tag in expression.split(',').map(str::trim) {
+            match tag.strip_prefix('!').map(str::trim) {
+                Some(excluded) if !excluded.is_empty() => filter.exclude.push(excluded.to_owned()),
+                Some(_) => {}
+                None if !tag.is_empty() => filter.include.push(tag.to_owned()),
+                None => {}
+            }
+        }
+        filter
+    }
+
+    /// Filter read from [`TAG_FILTER_ENV`], if set and non-empty.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        env::var(TAG_FILTER_ENV)
+            .ok()
+            .map(|value| Self::parse(&value))
+            .filter(|filter| !filter.is_empty())
+    }
+
+    /// Only run items tagged `tag` (or another included tag).
+    #[must_use]
+    pub fn include(mut self, tag: impl Into<String>) -> Self {
+        self.include.push(tag.into());
+        self
+    }
+
+    /// Skip items tagged `tag`.
+    #[must_use]
+    pub fn exclude(mut self, tag: impl Into<String>) -> Self {
+        self.exclude.push(tag.into());
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Whether an item with `tags` should run.
+    #[must_use]
+    pub fn matches<S: AsRef<str>>(&self, tags: &[S]) -> bool {
+        let has = |wanted: &String| tags.iter().any(|tag| tag.as_ref() == wanted);
+        if self.exclude.iter().any(has) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(has)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_splits_includes_and_excludes() {
+        let filter = TagFilter::parse(" da, chaos ,! slow,,!");
+
+        assert_eq!(
+            filter,
+            TagFilter::default()
+                .include("da")
+                .include("chaos")
+                .exclude("slow")
+        );
+        assert!(TagFilter::parse(" , ! ").is_empty());
+    }
+
+    #[test]
+    fn exclusions_win_over_inclusions() {
+        let filter = TagFilter::parse("da,!slow");
+
+        assert!(filter.matches(&["da"]));
+        assert!(filter.matches(&["da", "chaos"]));
+        assert!(!filter.matches(&["da", "slow"]));
+        assert!(!filter.matches(&["chaos"]));
+        assert!(!filter.matches::<&str>(&[]));
+    }
+
+    #[test]
+    fn exclusions_alone_keep_everything_else() {
+        let filter = TagFilter::parse("!slow");
+
+        assert!(filter.matches::<&str>(&[]));
+        assert!(filter.matches(&["chaos"]));
+        assert!(!filter.matches(&["chaos", "slow"]));
+        assert!(TagFilter::default().matches(&["slow"]));
+    }
+}
@@ -0,0 +1,302 @@
+use std::{env, time::Duration};
+
+use crate::{
+    constants::{
+        DEFAULT_HTTP_POLL_INTERVAL, DEFAULT_K8S_DEPLOYMENT_TIMEOUT,
+        DEFAULT_NODE_HTTP_PROBE_TIMEOUT, DEFAULT_NODE_HTTP_TIMEOUT,
+        DEFAULT_PROMETHEUS_HTTP_PROBE_TIMEOUT, DEFAULT_PROMETHEUS_HTTP_TIMEOUT,
+    },
+    topology::readiness::DEFAULT_READINESS_TIMEOUT,
+};
+
+/// Multiplier applied to every timeout in [`ScenarioTimings`], e.g. `2.5` on
+/// slow CI runners.
+pub const TIMEOUT_SCALE_ENV: &str = "NOMOS_TIMEOUT_SCALE";
+
+const DEFAULT_BRING_UP_TIMEOUT: Duration = DEFAULT_K8S_DEPLOYMENT_TIMEOUT;
+const DEFAULT_IMAGE_BUILD_TIMEOUT: Duration = Duration::from_secs(600);
+const DEFAULT_CFGSYNC_START_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Timeouts deployers use while bringing a cluster up and probing it.
+///
+/// Attach to a scenario with [`super::Builder::with_timings`]; deployers read
+/// it from the scenario instead of their own constants. Every getter applies
+/// the global scale, which defaults to [`TIMEOUT_SCALE_ENV`] (or `1.0`).
+/// `SLOW_TEST_ENV` keeps doubling the timeouts that honoured it before.
+/// Readiness settings given directly to a deployer take precedence over
+/// these.
+///
+/// Bring-up, node HTTP and HTTP poll settings left unset fall back to the
+/// runner's own defaults when it supplies them via [`Self::or_defaults`],
+/// and to the shared defaults otherwise.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScenarioTimings {
+    scale: f64,
+    readiness: Duration,
+    readiness_poll_interval: Option<Duration>,
+    bring_up: Option<Duration>,
+    node_http: Option<Duration>,
+    node_http_probe: Duration,
+    http_poll_interval: Option<Duration>,
+    prometheus_http: Duration,
+    prometheus_http_probe: Duration,
+    image_build: Duration,
+    cfgsync_start: Duration,
+}
+
+impl Default for ScenarioTimings {
+    fn default() -> Self {
+        Self::new().with_scale(scale_from_env().unwrap_or(1.0))
+    }
+}
+
+impl ScenarioTimings {
+    /// Built-in defaults without reading [`TIMEOUT_SCALE_ENV`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            scale: 1.0,
+            readiness: DEFAULT_READINESS_TIMEOUT,
+            readiness_poll_interval: None,
+            bring_up: None,
+            node_http: None,
+            node_http_probe: DEFAULT_NODE_HTTP_PROBE_TIMEOUT,
+            http_poll_interval: None,
+            prometheus_http: DEFAULT_PROMETHEUS_HTTP_TIMEOUT,
+            prometheus_http_probe: DEFAULT_PROMETHEUS_HTTP_PROBE_TIMEOUT,
+            image_build: DEFAULT_IMAGE_BUILD_TIMEOUT,
+            cfgsync_start: DEFAULT_CFGSYNC_START_TIMEOUT,
+        }
+    }
+
+    #[must_use]
+    /// Multiply every timeout by `scale`. Poll intervals are not scaled.
+    pub fn with_scale(mut self, scale: f64) -> Self {
+        assert!(
+            scale.is_finite() && scale > 0.0,
+            "timeout scale must be positive"
+        );
+        self.scale = scale;
+        self
+    }
+
+    #[must_use]
+    /// Time each network/membership readiness check may take.
+    pub const fn with_readiness(mut self, timeout: Duration) -> Self {
+        self.readiness = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Delay between readiness polls; unset keeps each check's own default.
+    pub const fn with_readiness_poll_interval(mut self, interval: Duration) -> Self {
+        self.readiness_poll_interval = Some(interval);
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for `docker compose up` or a k8s rollout.
+    pub const fn with_bring_up(mut self, timeout: Duration) -> Self {
+        self.bring_up = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for node HTTP endpoints to answer after bring-up.
+    pub const fn with_node_http(mut self, timeout: Duration) -> Self {
+        self.node_http = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for a quick probe before falling back (e.g. NodePort
+    /// before port-forwarding).
+    pub const fn with_node_http_probe(mut self, timeout: Duration) -> Self {
+        self.node_http_probe = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Delay between HTTP endpoint probes.
+    pub const fn with_http_poll_interval(mut self, interval: Duration) -> Self {
+        self.http_poll_interval = Some(interval);
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for Prometheus to become ready.
+    pub const fn with_prometheus_http(mut self, timeout: Duration) -> Self {
+        self.prometheus_http = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for a quick Prometheus probe before falling back.
+    pub const fn with_prometheus_http_probe(mut self, timeout: Duration) -> Self {
+        self.prometheus_http_probe = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for building a missing node image.
+    pub const fn with_image_build(mut self, timeout: Duration) -> Self {
+        self.image_build = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for the cfgsync server to start.
+    pub const fn with_cfgsync_start(mut self, timeout: Duration) -> Self {
+        self.cfgsync_start = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Fill the bring-up, node HTTP and HTTP poll settings left unset with
+    /// those set on `defaults`, typically a runner's built-in timings. The
+    /// scale and every other setting are kept.
+    pub const fn or_defaults(mut self, defaults: &Self) -> Self {
+        if self.bring_up.is_none() {
+            self.bring_up = defaults.bring_up;
+        }
+        if self.node_http.is_none() {
+            self.node_http = defaults.node_http;
+        }
+        if self.http_poll_interval.is_none() {
+            self.http_poll_interval = defaults.http_poll_interval;
+        }
+        self
+    }
+
+    #[must_use]
+    pub const fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    #[must_use]
+    pub fn readiness(&self) -> Duration {
+        self.scaled(self.readiness)
+    }
+
+    #[must_use]
+    pub const fn readiness_poll_interval(&self) -> Option<Duration> {
+        self.readiness_poll_interval
+    }
+
+    #[must_use]
+    pub fn bring_up(&self) -> Duration {
+        self.scaled(self.bring_up.unwrap_or(DEFAULT_BRING_UP_TIMEOUT))
+    }
+
+    #[must_use]
+    pub fn node_http(&self) -> Duration {
+        self.scaled(self.node_http.unwrap_or(DEFAULT_NODE_HTTP_TIMEOUT))
+    }
+
+    #[must_use]
+    pub fn node_http_probe(&self) -> Duration {
+        self.scaled(self.node_http_probe)
+    }
+
+    #[must_use]
+    pub fn http_poll_interval(&self) -> Duration {
+        self.http_poll_interval
+            .unwrap_or(DEFAULT_HTTP_POLL_INTERVAL)
+    }
+
+    #[must_use]
+    pub fn prometheus_http(&self) -> Duration {
+        self.scaled(self.prometheus_http)
+    }
+
+    #[must_use]
+    pub fn prometheus_http_probe(&self) -> Duration {
+        self.scaled(self.prometheus_http_probe)
+    }
+
+    #[must_use]
+    pub fn image_build(&self) -> Duration {
+        self.scaled(self.image_build)
+    }
+
+    #[must_use]
+    pub fn cfgsync_start(&self) -> Duration {
+        self.scaled(self.cfgsync_start)
+    }
+
+    fn scaled(&self, timeout: Duration) -> Duration {
+        timeout.mul_f64(self.scale)
+    }
+}
+
+/// Scale set through [`TIMEOUT_SCALE_ENV`], if valid.
+pub(super) fn scale_from_env() -> Option<f64> {
+    env::var(TIMEOUT_SCALE_ENV)
+        .ok()
+        .and_then(|value| parse_scale(&value))
+}
+
+fn parse_scale(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|scale| scale.is_finite() && *scale > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{DEFAULT_BRING_UP_TIMEOUT, ScenarioTimings, parse_scale};
+    use crate::constants::{DEFAULT_HTTP_POLL_INTERVAL, DEFAULT_NODE_HTTP_TIMEOUT};
+
+    #[test]
+    fn parse_scale_accepts_positive_finite_values_only() {
+        assert_eq!(parse_scale("2.5"), Some(2.5));
+        assert_eq!(parse_scale(" 3 "), Some(3.0));
+        assert_eq!(parse_scale("0"), None);
+        assert_eq!(parse_scale("-1"), None);
+        assert_eq!(parse_scale("inf"), None);
+        assert_eq!(parse_scale("NaN"), None);
+        assert_eq!(parse_scale("fast"), None);
+    }
+
+    #[test]
+    fn scale_applies_to_timeouts_but_not_poll_intervals() {
+        let timings = ScenarioTimings::new()
+            .with_http_poll_interval(Duration::from_millis(500))
+            .with_scale(2.0);
+
+        assert_eq!(timings.bring_up(), DEFAULT_BRING_UP_TIMEOUT * 2);
+        assert_eq!(timings.node_http(), DEFAULT_NODE_HTTP_TIMEOUT * 2);
+        assert_eq!(timings.http_poll_interval(), Duration::from_millis(500));
+    }
+
+    #[test]
+    #[should_panic(expected = "timeout scale must be positive")]
+    fn with_scale_rejects_zero() {
+        let _ = ScenarioTimings::new().with_scale(0.0);
+    }
+
+    #[test]
+    fn runner_defaults_only_fill_unset_settings() {
+        let runner = ScenarioTimings::new()
+            .with_bring_up(Duration::from_secs(120))
+            .with_node_http(Duration::from_secs(180))
+            .with_http_poll_interval(Duration::from_millis(250));
+
+        let timings = ScenarioTimings::new()
+            .with_bring_up(Duration::from_secs(30))
+            .with_scale(1.5)
+            .or_defaults(&runner);
+
+        assert_eq!(timings.bring_up(), Duration::from_secs(45));
+        assert_eq!(timings.node_http(), Duration::from_secs(270));
+        assert_eq!(timings.http_poll_interval(), Duration::from_millis(250));
+        assert_eq!(
+            ScenarioTimings::new().http_poll_interval(),
+            DEFAULT_HTTP_POLL_INTERVAL
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use super::{DynError, Expectation, RunContext, runtime::context::RunMetrics};
+use super::{DynError, Expectation, NodeFeature, RunContext, runtime::context::RunMetrics};
 use crate::topology::generation::GeneratedTopology;
 
 #[async_trait]
@@ -8,6 +8,18 @@ use crate::topology::generation::GeneratedTopology;
 pub trait Workload: Send + Sync {
     fn name(&self) -> &str;
 
+    /// Labels such as `"da"` or `"chaos"` used by [`super::TagFilter`] to
+    /// select workloads at runtime.
+    fn tags(&self) -> &[&'static str] {
+        &[]
+    }
+
+    /// Node features the workload relies on, e.g. the testing API; the run
+    /// fails before workloads start when the cluster lacks one.
+    fn required_features(&self) -> &[NodeFeature] {
+        &[]
+    }
+
     fn expectations(&self) -> Vec<Box<dyn Expectation>> {
         Vec::new()
     }
@@ -20,5 +32,24 @@ pub trait Workload: Send + Sync {
         Ok(())
     }
 
+    /// One-line summary of what the workload would do over a run with
+    /// `run_metrics`, e.g. how many transactions it would submit. Reported
+    /// by [`super::Scenario::dry_run`]; called after `init`.
+    fn plan(&self, _run_metrics: &RunMetrics) -> Option<String> {
+        None
+    }
+
+    /// Whether [`Self::start`] returns on its own once
+    /// [`RunContext::cancellation`](super::RunContext::cancellation) fires.
+    /// Such workloads get a short grace period to wind down when the run
+    /// ends; the others are aborted right away.
+    fn observes_cancellation(&self) -> bool {
+        false
+    }
+
+    /// Run the workload. The run ends early once every workload returned.
+    /// Open-ended workloads instead keep going until the run ends, either
+    /// returning when cancelled (see [`Self::observes_cancellation`]) or
+    /// being aborted.
     async fn start(&self, ctx: &RunContext) -> Result<(), DynError>;
 }
@@ -1,30 +1,297 @@
-use std::time::Duration;
+use std::{collections::HashSet, num::NonZero, time::Duration};
 
 use nomos_core::{
     mantle::GenesisTx as _,
     sdp::{Locator, ServiceType},
 };
 use nomos_da_network_core::swarm::DAConnectionPolicySettings;
+use nomos_libp2p::Multiaddr;
+use serde::{Deserialize, Serialize};
 use testing_framework_config::topology::configs::{
-    api::create_api_configs,
+    api::create_api_configs_with_ports,
     blend::create_blend_configs,
     bootstrap::{SHORT_PROLONGED_BOOTSTRAP_PERIOD, create_bootstrap_configs},
     consensus::{
-        ConsensusParams, ProviderInfo, create_consensus_configs,
-        create_genesis_tx_with_declarations,
+        ConsensusParams, ProviderInfo, create_consensus_configs, create_custom_genesis_tx,
     },
     da::{DaParams, create_da_configs},
-    network::{Libp2pNetworkLayout, NetworkParams, create_network_configs},
+    genesis::{GenesisDeclaration, GenesisInscription},
+    network::{IpStack, Libp2pNetworkLayout, NetworkParams, create_network_configs_with_ports},
     tracing::create_tracing_configs,
     wallet::WalletConfig,
 };
 
-use crate::topology::{
-    configs::{GeneralConfig, time::default_time_config},
-    generation::{GeneratedNodeConfig, GeneratedTopology, NodeRole},
-    utils::{create_kms_configs, resolve_ids, resolve_ports},
+use crate::{
+    ports::HeldPorts,
+    scenario::{NetworkShaping, NodeTarget, WalletPool},
+    topology::{
+        configs::{GeneralConfig, time::default_time_config},
+        generation::{GeneratedNodeConfig, GeneratedTopology, NodeRole},
+        utils::{create_kms_configs, resolve_ids, resolve_ports, resolve_tcp_ports},
+    },
 };
 
+/// A node left out of the initial cluster and brought up `delay` after the
+/// run starts, to exercise syncing the chain from its peers.
+///
+/// Deployers supporting late joins fetch the node's config with the rest of
+/// the cluster but hold its process back; readiness checks skip it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LateJoin {
+    pub target: NodeTarget,
+    pub delay: Duration,
+}
+
+/// CPU and memory quantities in Kubernetes notation, e.g. `"500m"` and
+/// `"1Gi"`. Unset fields are left to the cluster defaults.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResourceQuantities {
+    pub cpu: Option<String>,
+    pub memory: Option<String>,
+}
+
+impl ResourceQuantities {
+    #[must_use]
+    pub const fn unset() -> Self {
+        Self {
+            cpu: None,
+            memory: None,
+        }
+    }
+
+    #[must_use]
+    pub fn new(cpu: impl Into<String>, memory: impl Into<String>) -> Self {
+        Self {
+            cpu: Some(cpu.into()),
+            memory: Some(memory.into()),
+        }
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.memory.is_none()
+    }
+}
+
+/// Resource requests and limits for a node's container. Only deployers
+/// scheduling containers (k8s) apply them.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeResources {
+    pub requests: ResourceQuantities,
+    pub limits: ResourceQuantities,
+}
+
+impl NodeResources {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            requests: ResourceQuantities::unset(),
+            limits: ResourceQuantities::unset(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_requests(mut self, cpu: impl Into<String>, memory: impl Into<String>) -> Self {
+        self.requests = ResourceQuantities::new(cpu, memory);
+        self
+    }
+
+    #[must_use]
+    pub fn with_limits(mut self, cpu: impl Into<String>, memory: impl Into<String>) -> Self {
+        self.limits = ResourceQuantities::new(cpu, memory);
+        self
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.requests.is_empty() && self.limits.is_empty()
+    }
+}
+
+/// Per-role container resources, with per-node overrides taking precedence.
+#[derive(Clone, Debug, Default)]
+pub struct TopologyResources {
+    pub validators: NodeResources,
+    pub executors: NodeResources,
+    pub nodes: Vec<(NodeTarget, NodeResources)>,
+}
+
+impl TopologyResources {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            validators: NodeResources::new(),
+            executors: NodeResources::new(),
+            nodes: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Resources applying to `target`: its override if any, otherwise its
+    /// role's.
+    pub fn for_node(&self, target: NodeTarget) -> &NodeResources {
+        self.nodes
+            .iter()
+            .find(|(node, _)| *node == target)
+            .map_or_else(
+                || match target.role() {
+                    NodeRole::Validator => &self.validators,
+                    NodeRole::Executor => &self.executors,
+                },
+                |(_, resources)| resources,
+            )
+    }
+
+    /// Set the resources of every `role` node without an override.
+    pub fn set_role(&mut self, role: NodeRole, resources: NodeResources) {
+        match role {
+            NodeRole::Validator => self.validators = resources,
+            NodeRole::Executor => self.executors = resources,
+        }
+    }
+
+    /// Override the resources of a single node.
+    pub fn set_node(&mut self, target: NodeTarget, resources: NodeResources) {
+        self.nodes.retain(|(node, _)| *node != target);
+        self.nodes.push((target, resources));
+    }
+}
+
+/// Named group of nodes in [`NetworkRegions`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NetworkRegion {
+    pub name: String,
+    pub nodes: Vec<NodeTarget>,
+}
+
+/// Shaping of the traffic between two regions, applied in both directions.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegionLink {
+    pub regions: (String, String),
+    pub shaping: NetworkShaping,
+}
+
+/// Nodes grouped into named regions (e.g. `"eu"`, `"us"`, `"asia"`) with
+/// latency added between them, to emulate a geo-distributed network. Only
+/// deployers emulating networks (compose) apply them; nodes outside every
+/// region, and traffic within a region, are not shaped.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NetworkRegions {
+    pub regions: Vec<NetworkRegion>,
+    pub links: Vec<RegionLink>,
+}
+
+impl NetworkRegions {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            regions: Vec::new(),
+            links: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Place `nodes` in region `name`.
+    pub fn region(
+        mut self,
+        name: impl Into<String>,
+        nodes: impl IntoIterator<Item = NodeTarget>,
+    ) -> Self {
+        self.regions.push(NetworkRegion {
+            name: name.into(),
+            nodes: nodes.into_iter().collect(),
+        });
+        self
+    }
+
+    #[must_use]
+    /// Delay traffic between regions `a` and `b` by `latency` each way.
+    pub fn latency(self, a: impl Into<String>, b: impl Into<String>, latency: Duration) -> Self {
+        self.link(
+            a,
+            b,
+            NetworkShaping {
+                latency,
+                ..NetworkShaping::default()
+            },
+        )
+    }
+
+    #[must_use]
+    /// Shape traffic between regions `a` and `b`, replacing an earlier link
+    /// between them.
+    pub fn link(
+        mut self,
+        a: impl Into<String>,
+        b: impl Into<String>,
+        shaping: NetworkShaping,
+    ) -> Self {
+        let regions = (a.into(), b.into());
+        self.links
+            .retain(|link| !link.connects(&regions.0, &regions.1));
+        self.links.push(RegionLink { regions, shaping });
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    #[must_use]
+    /// Region `target` belongs to, if any.
+    pub fn region_of(&self, target: NodeTarget) -> Option<&NetworkRegion> {
+        self.regions
+            .iter()
+            .find(|region| region.nodes.contains(&target))
+    }
+
+    #[must_use]
+    /// Shaping of the traffic between regions `a` and `b`, if linked.
+    pub fn shaping_between(&self, a: &str, b: &str) -> Option<NetworkShaping> {
+        self.links
+            .iter()
+            .find(|link| link.connects(a, b))
+            .map(|link| link.shaping)
+    }
+}
+
+impl RegionLink {
+    fn connects(&self, a: &str, b: &str) -> bool {
+        let (x, y) = (&self.regions.0, &self.regions.1);
+        (x == a && y == b) || (x == b && y == a)
+    }
+}
+
+/// Ready-made consensus settings for common run shapes. Presets keep the
+/// participant count and only change block production and finality.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConsensusPreset {
+    /// Security parameter 10, a block in 90% of slots.
+    #[default]
+    Standard,
+    /// Security parameter 3, a leader in every slot: many blocks and quick
+    /// finality for short smoke runs, at the cost of more forks.
+    Fast,
+    /// Security parameter 20, a block in half of the slots: longer gaps
+    /// between blocks and deeper finality, closer to a live network.
+    Sparse,
+}
+
+impl ConsensusPreset {
+    /// Overwrite the security parameter and active slot coefficient of
+    /// `params`.
+    pub fn apply(self, params: &mut ConsensusParams) {
+        let (security_param, active_slot_coeff) = match self {
+            Self::Standard => (10, 0.9),
+            Self::Fast => (3, 1.0),
+            Self::Sparse => (20, 0.5),
+        };
+        params.security_param = NonZero::new(security_param).expect("preset is non-zero");
+        params.active_slot_coeff = active_slot_coeff;
+    }
+}
+
 /// High-level topology settings used to generate node configs for a scenario.
 #[derive(Clone)]
 pub struct TopologyConfig {
@@ -34,6 +301,9 @@ pub struct TopologyConfig {
     pub da_params: DaParams,
     pub network_params: NetworkParams,
     pub wallet_config: WalletConfig,
+    pub late_joins: Vec<LateJoin>,
+    pub resources: TopologyResources,
+    pub regions: NetworkRegions,
 }
 
 impl TopologyConfig {
@@ -47,6 +317,9 @@ impl TopologyConfig {
             da_params: DaParams::default(),
             network_params: NetworkParams::default(),
             wallet_config: WalletConfig::default(),
+            late_joins: Vec::new(),
+            resources: TopologyResources::new(),
+            regions: NetworkRegions::new(),
         }
     }
 
@@ -60,6 +333,9 @@ impl TopologyConfig {
             da_params: DaParams::default(),
             network_params: NetworkParams::default(),
             wallet_config: WalletConfig::default(),
+            late_joins: Vec::new(),
+            resources: TopologyResources::new(),
+            regions: NetworkRegions::new(),
         }
     }
 
@@ -87,6 +363,9 @@ impl TopologyConfig {
             },
             network_params: NetworkParams::default(),
             wallet_config: WalletConfig::default(),
+            late_joins: Vec::new(),
+            resources: TopologyResources::new(),
+            regions: NetworkRegions::new(),
         }
     }
 
@@ -122,6 +401,9 @@ impl TopologyConfig {
             da_params,
             network_params: NetworkParams::default(),
             wallet_config: WalletConfig::default(),
+            late_joins: Vec::new(),
+            resources: TopologyResources::new(),
+            regions: NetworkRegions::new(),
         }
     }
 
@@ -153,6 +435,9 @@ impl TopologyConfig {
             },
             network_params: NetworkParams::default(),
             wallet_config: WalletConfig::default(),
+            late_joins: Vec::new(),
+            resources: TopologyResources::new(),
+            regions: NetworkRegions::new(),
         }
     }
 
@@ -160,6 +445,12 @@ impl TopologyConfig {
     pub const fn wallet(&self) -> &WalletConfig {
         &self.wallet_config
     }
+
+    #[must_use]
+    /// Whether `target` is held back until its [`LateJoin::delay`] elapses.
+    pub fn joins_late(&self, target: NodeTarget) -> bool {
+        self.late_joins.iter().any(|join| join.target == target)
+    }
 }
 
 /// Builder that produces `GeneratedTopology` instances from a `TopologyConfig`.
@@ -169,6 +460,11 @@ pub struct TopologyBuilder {
     ids: Option<Vec<[u8; 32]>>,
     da_ports: Option<Vec<u16>>,
     blend_ports: Option<Vec<u16>>,
+    network_ports: Option<Vec<u16>>,
+    api_ports: Option<Vec<u16>>,
+    testing_http_ports: Option<Vec<u16>>,
+    seed: Option<u64>,
+    provider_note_values: Vec<(NodeTarget, u64)>,
 }
 
 impl TopologyBuilder {
@@ -180,6 +476,11 @@ impl TopologyBuilder {
             ids: None,
             da_ports: None,
             blend_ports: None,
+            network_ports: None,
+            api_ports: None,
+            testing_http_ports: None,
+            seed: None,
+            provider_note_values: Vec::new(),
         }
     }
 
@@ -190,6 +491,14 @@ impl TopologyBuilder {
         self
     }
 
+    #[must_use]
+    /// Seed node ID generation (and everything derived from the IDs, such as
+    /// DA and blend keys) so the same topology is produced on every build.
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
     #[must_use]
     /// Override DA ports for nodes in order.
     pub fn with_da_ports(mut self, ports: Vec<u16>) -> Self {
@@ -204,6 +513,27 @@ impl TopologyBuilder {
         self
     }
 
+    #[must_use]
+    /// Override libp2p swarm ports for nodes in order.
+    pub fn with_network_ports(mut self, ports: Vec<u16>) -> Self {
+        self.network_ports = Some(ports);
+        self
+    }
+
+    #[must_use]
+    /// Override HTTP API ports for nodes in order.
+    pub fn with_api_ports(mut self, ports: Vec<u16>) -> Self {
+        self.api_ports = Some(ports);
+        self
+    }
+
+    #[must_use]
+    /// Override testing HTTP ports for nodes in order.
+    pub fn with_testing_http_ports(mut self, ports: Vec<u16>) -> Self {
+        self.testing_http_ports = Some(ports);
+        self
+    }
+
     #[must_use]
     pub const fn with_validator_count(mut self, validators: usize) -> Self {
         self.config.n_validators = validators;
@@ -232,36 +562,138 @@ impl TopologyBuilder {
         self
     }
 
+    #[must_use]
+    /// Select the IP families nodes listen on and advertise.
+    pub const fn with_ip_stack(mut self, ip_stack: IpStack) -> Self {
+        self.config.network_params.ip_stack = ip_stack;
+        self
+    }
+
+    #[must_use]
+    /// Start `target` `delay` after the run begins instead of with the rest
+    /// of the cluster (see [`LateJoin`]).
+    pub fn with_late_join(mut self, target: NodeTarget, delay: Duration) -> Self {
+        self.config.late_joins.retain(|join| join.target != target);
+        self.config.late_joins.push(LateJoin { target, delay });
+        self
+    }
+
+    #[must_use]
+    /// Container resources for every `role` node (see
+    /// [`TopologyResources`]).
+    pub fn with_role_resources(mut self, role: NodeRole, resources: NodeResources) -> Self {
+        self.config.resources.set_role(role, resources);
+        self
+    }
+
+    #[must_use]
+    /// Container resources for a single node, overriding its role's.
+    pub fn with_node_resources(mut self, target: NodeTarget, resources: NodeResources) -> Self {
+        self.config.resources.set_node(target, resources);
+        self
+    }
+
+    #[must_use]
+    /// Group nodes into network regions with latency between them; see
+    /// [`NetworkRegions`].
+    pub fn with_regions(mut self, regions: NetworkRegions) -> Self {
+        self.config.regions = regions;
+        self
+    }
+
     /// Override wallet configuration used in genesis.
     pub fn with_wallet_config(mut self, wallet: WalletConfig) -> Self {
         self.config.wallet_config = wallet;
         self
     }
 
+    #[must_use]
+    /// Write an inscription of `data` on `channel_id` into genesis.
+    pub fn with_genesis_inscription(mut self, channel_id: [u8; 32], data: Vec<u8>) -> Self {
+        self.config
+            .consensus_params
+            .genesis
+            .inscriptions
+            .push(GenesisInscription { channel_id, data });
+        self
+    }
+
+    #[must_use]
+    /// Declare a `service_type` provider at `locator` in genesis, backed by a
+    /// note of `note_value`. No node runs for it, so it never becomes active.
+    pub fn with_genesis_declaration(
+        mut self,
+        service_type: ServiceType,
+        locator: Multiaddr,
+        note_value: u64,
+    ) -> Self {
+        self.config
+            .consensus_params
+            .genesis
+            .declarations
+            .push(GenesisDeclaration {
+                service_type,
+                locator,
+                note_value,
+            });
+        self
+    }
+
+    #[must_use]
+    /// Value of the notes backing `target`'s DA and blend declarations in
+    /// genesis; values below the minimum stake (1) leave it under-staked.
+    pub fn with_provider_note_value(mut self, target: NodeTarget, value: u64) -> Self {
+        self.provider_note_values
+            .retain(|(node, _)| *node != target);
+        self.provider_note_values.push((target, value));
+        self
+    }
+
     #[must_use]
     /// Finalize and generate topology and node descriptors.
     pub fn build(self) -> GeneratedTopology {
         let Self {
-            config,
+            mut config,
             ids,
             da_ports,
             blend_ports,
+            network_ports,
+            api_ports,
+            testing_http_ports,
+            seed,
+            provider_note_values,
         } = self;
 
         let n_participants = config.n_validators + config.n_executors;
         assert!(n_participants > 0, "topology must have at least one node");
-
-        let ids = resolve_ids(ids, n_participants);
-        let da_ports = resolve_ports(da_ports, n_participants, "DA");
-        let blend_ports = resolve_ports(blend_ports, n_participants, "Blend");
+        dedup_late_joins(&mut config.late_joins);
+        validate_late_joins(&config);
+        validate_resources(&config);
+        validate_regions(&config);
+        apply_provider_note_values(&mut config, &provider_note_values);
+
+        let ids = resolve_ids(ids, n_participants, seed);
+        let held_ports = HeldPorts::default();
+        let da_ports = resolve_ports(da_ports, n_participants, "DA", &held_ports);
+        let blend_ports = resolve_ports(blend_ports, n_participants, "Blend", &held_ports);
+        let network_ports = resolve_ports(network_ports, n_participants, "network", &held_ports);
+        let api_ports = resolve_tcp_ports(api_ports, n_participants, "API", &held_ports);
+        let testing_http_ports = resolve_tcp_ports(
+            testing_http_ports,
+            n_participants,
+            "testing HTTP",
+            &held_ports,
+        );
 
         let mut consensus_configs =
             create_consensus_configs(&ids, &config.consensus_params, &config.wallet_config);
         let bootstrapping_config = create_bootstrap_configs(&ids, SHORT_PROLONGED_BOOTSTRAP_PERIOD);
-        let da_configs = create_da_configs(&ids, &config.da_params, &da_ports);
-        let network_configs = create_network_configs(&ids, &config.network_params);
-        let blend_configs = create_blend_configs(&ids, &blend_ports);
-        let api_configs = create_api_configs(&ids);
+        let ip_stack = config.network_params.ip_stack;
+        let da_configs = create_da_configs(&ids, &config.da_params, &da_ports, ip_stack);
+        let network_configs =
+            create_network_configs_with_ports(&ids, &config.network_params, &network_ports);
+        let blend_configs = create_blend_configs(&ids, &blend_ports, ip_stack);
+        let api_configs = create_api_configs_with_ports(&api_ports, &testing_http_ports);
         let tracing_configs = create_tracing_configs(&ids);
         let time_config = default_time_config();
 
@@ -289,12 +721,18 @@ impl TopologyBuilder {
                 }),
         );
 
+        providers.extend(consensus_configs[0].extra_providers.iter().cloned());
+
         let ledger_tx = consensus_configs[0]
             .genesis_tx
             .mantle_tx()
             .ledger_tx
             .clone();
-        let genesis_tx = create_genesis_tx_with_declarations(ledger_tx, providers);
+        let genesis_tx = create_custom_genesis_tx(
+            ledger_tx,
+            providers,
+            &config.consensus_params.genesis.inscriptions,
+        );
         for c in &mut consensus_configs {
             c.genesis_tx = genesis_tx.clone();
         }
@@ -344,9 +782,12 @@ impl TopologyBuilder {
         }
 
         GeneratedTopology {
+            wallet_pool: WalletPool::new(config.wallet_config.accounts.clone()),
             config,
             validators,
             executors,
+            seed,
+            held_ports,
         }
     }
 
@@ -355,3 +796,206 @@ impl TopologyBuilder {
         &self.config
     }
 }
+
+fn validate_regions(config: &TopologyConfig) {
+    let regions = &config.regions.regions;
+    for (index, region) in regions.iter().enumerate() {
+        assert!(
+            regions[..index]
+                .iter()
+                .all(|other| other.name != region.name),
+            "network region {} is declared twice",
+            region.name
+        );
+        for target in &region.nodes {
+            let count = match target {
+                NodeTarget::Validator(_) => config.n_validators,
+                NodeTarget::Executor(_) => config.n_executors,
+            };
+            assert!(
+                target.index() < count,
+                "network region {} lists {target}, which is not part of the topology",
+                region.name
+            );
+            assert!(
+                regions[..index]
+                    .iter()
+                    .all(|other| !other.nodes.contains(target)),
+                "{target} is placed in more than one network region"
+            );
+        }
+    }
+    for link in &config.regions.links {
+        let (a, b) = &link.regions;
+        for name in [a, b] {
+            assert!(
+                regions.iter().any(|region| &region.name == name),
+                "network region link names unknown region {name}"
+            );
+        }
+        assert!(a != b, "network region {a} cannot be linked to itself");
+    }
+}
+
+fn validate_resources(config: &TopologyConfig) {
+    for (target, _) in &config.resources.nodes {
+        let count = match target {
+            NodeTarget::Validator(_) => config.n_validators,
+            NodeTarget::Executor(_) => config.n_executors,
+        };
+        assert!(
+            target.index() < count,
+            "resources set for {target}, which is not part of the topology"
+        );
+    }
+}
+
+fn apply_provider_note_values(config: &mut TopologyConfig, values: &[(NodeTarget, u64)]) {
+    for &(target, value) in values {
+        let (count, offset) = match target {
+            NodeTarget::Validator(_) => (config.n_validators, 0),
+            NodeTarget::Executor(_) => (config.n_executors, config.n_validators),
+        };
+        assert!(
+            target.index() < count,
+            "genesis note value set for {target}, which is not part of the topology"
+        );
+        config
+            .consensus_params
+            .genesis
+            .provider_note_values
+            .insert(offset + target.index(), value);
+    }
+}
+
+/// Keep one late join per node; the last one wins, as with
+/// [`TopologyBuilder::with_late_join`].
+fn dedup_late_joins(joins: &mut Vec<LateJoin>) {
+    let mut seen = HashSet::new();
+    joins.reverse();
+    joins.retain(|join| seen.insert(join.target));
+    joins.reverse();
+}
+
+fn validate_late_joins(config: &TopologyConfig) {
+    for join in &config.late_joins {
+        let count = match join.target {
+            NodeTarget::Validator(_) => config.n_validators,
+            NodeTarget::Executor(_) => config.n_executors,
+        };
+        assert!(
+            join.target.index() < count,
+            "late-joining {} is not part of the topology",
+            join.target
+        );
+    }
+    let late_validators = config
+        .late_joins
+        .iter()
+        .filter(|join| matches!(join.target, NodeTarget::Validator(_)))
+        .count();
+    assert!(
+        late_validators < config.n_validators,
+        "at least one validator must run from the start"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use nomos_core::mantle::ops::{Op, channel::ChannelId};
+
+    use super::*;
+
+    fn da_note_values(topology: &GeneratedTopology) -> Vec<u64> {
+        topology.validators()[0]
+            .general
+            .consensus_config
+            .da_notes
+            .iter()
+            .map(|note| note.note.value)
+            .collect()
+    }
+
+    #[test]
+    fn provider_note_values_follow_role_order() {
+        let topology = TopologyBuilder::new(TopologyConfig::with_node_numbers(2, 1))
+            .with_provider_note_value(NodeTarget::Executor(0), 5)
+            .with_provider_note_value(NodeTarget::Validator(1), 3)
+            .with_provider_note_value(NodeTarget::Validator(1), 0)
+            .build();
+
+        let genesis = &topology.config().consensus_params.genesis;
+        assert_eq!(genesis.provider_note_value(0), 1);
+        assert_eq!(genesis.provider_note_value(1), 0);
+        assert_eq!(genesis.provider_note_value(2), 5);
+        assert_eq!(da_note_values(&topology), vec![1, 0, 5]);
+    }
+
+    #[test]
+    fn genesis_carries_extra_declarations_and_inscriptions() {
+        let locator: Multiaddr = "/ip4/10.0.0.9/udp/3000/quic-v1"
+            .parse()
+            .expect("valid multiaddr");
+        let topology = TopologyBuilder::new(TopologyConfig::with_node_numbers(1, 1))
+            .with_genesis_inscription([7; 32], b"hello".to_vec())
+            .with_genesis_declaration(ServiceType::DataAvailability, locator.clone(), 4)
+            .build();
+
+        let general = &topology.validators()[0].general.consensus_config;
+        let ops = &general.genesis_tx.mantle_tx().ops;
+        assert!(ops.iter().any(|op| matches!(
+            op,
+            Op::ChannelInscribe(inscription)
+                if inscription.channel_id == ChannelId::from([7; 32])
+                    && inscription.inscription == b"hello"
+        )));
+        assert!(ops.iter().any(|op| matches!(
+            op,
+            Op::SDPDeclare(declaration)
+                if declaration.service_type == ServiceType::DataAvailability
+                    && declaration.locators == vec![Locator(locator.clone())]
+        )));
+        assert_eq!(general.extra_providers.len(), 1);
+        assert_eq!(general.extra_providers[0].note.note.value, 4);
+
+        let executor = &topology.executors()[0].general.consensus_config;
+        assert_eq!(
+            executor.genesis_tx.mantle_tx().ops.len(),
+            ops.len(),
+            "every node starts from the same genesis"
+        );
+    }
+
+    #[test]
+    fn duplicate_late_joins_keep_the_last_delay() {
+        let mut config = TopologyConfig::with_node_numbers(2, 0);
+        config.late_joins = vec![
+            LateJoin {
+                target: NodeTarget::Validator(1),
+                delay: Duration::from_secs(10),
+            },
+            LateJoin {
+                target: NodeTarget::Validator(1),
+                delay: Duration::from_secs(20),
+            },
+        ];
+
+        let topology = TopologyBuilder::new(config).build();
+
+        assert_eq!(
+            topology.late_joins(),
+            [LateJoin {
+                target: NodeTarget::Validator(1),
+                delay: Duration::from_secs(20),
+            }]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not part of the topology")]
+    fn provider_note_value_outside_topology_panics() {
+        let _ = TopologyBuilder::new(TopologyConfig::with_node_numbers(1, 0))
+            .with_provider_note_value(NodeTarget::Executor(0), 2)
+            .build();
+    }
+}
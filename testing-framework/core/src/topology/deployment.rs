@@ -3,17 +3,20 @@ use std::collections::HashSet;
 use nomos_core::sdp::SessionNumber;
 
 use crate::{
+    IS_DEBUG_TRACING,
     nodes::{
+        LOGS_PREFIX,
         executor::{Executor, create_executor_config},
         validator::{Validator, create_validator_config},
     },
+    scenario::{NodeLogs, NodeStorage, NodeTarget},
     topology::{
         config::{TopologyBuilder, TopologyConfig},
         configs::GeneralConfig,
         generation::find_expected_peer_counts,
         readiness::{
             DaBalancerReadiness, MembershipReadiness, NetworkReadiness, ReadinessCheck,
-            ReadinessError,
+            ReadinessConfig, ReadinessError,
         },
         utils::multiaddr_port,
     },
@@ -28,6 +31,7 @@ pub struct Topology {
 impl Topology {
     pub async fn spawn(config: TopologyConfig) -> Self {
         let generated = TopologyBuilder::new(config.clone()).build();
+        generated.release_ports();
         let n_validators = config.n_validators;
         let n_executors = config.n_executors;
         let node_configs = generated
@@ -55,6 +59,7 @@ impl Topology {
             .with_da_ports(da_ports.to_vec())
             .with_blend_ports(blend_ports.to_vec())
             .build();
+        generated.release_ports();
 
         let node_configs = generated
             .nodes()
@@ -101,7 +106,62 @@ impl Topology {
         &self.executors
     }
 
+    /// Log locations of the spawned nodes. Empty when file logging is disabled
+    /// (debug tracing) or redirected into the shared `NOMOS_LOG_DIR`, where
+    /// nodes cannot be told apart.
+    #[must_use]
+    pub fn node_logs(&self) -> NodeLogs {
+        if *IS_DEBUG_TRACING || std::env::var_os("NOMOS_LOG_DIR").is_some() {
+            return NodeLogs::new();
+        }
+
+        let validators = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeTarget::Validator(index), node.dir()));
+        let executors = self
+            .executors
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeTarget::Executor(index), node.dir()));
+        validators
+            .chain(executors)
+            .fold(NodeLogs::new(), |logs, (target, dir)| {
+                logs.with_directory(target.to_string(), dir, LOGS_PREFIX)
+            })
+    }
+
+    /// Data directories of the local nodes, keyed like [`Self::node_logs`].
+    #[must_use]
+    pub fn node_storage(&self) -> NodeStorage {
+        let validators = self
+            .validators
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeTarget::Validator(index), node.dir()));
+        let executors = self
+            .executors
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeTarget::Executor(index), node.dir()));
+        validators
+            .chain(executors)
+            .fold(NodeStorage::new(), |storage, (target, dir)| {
+                storage.with_node(target.to_string(), dir)
+            })
+    }
+
     pub async fn wait_network_ready(&self) -> Result<(), ReadinessError> {
+        self.wait_network_ready_with(ReadinessConfig::default())
+            .await
+    }
+
+    /// Like [`Self::wait_network_ready`] with a custom timeout/poll interval.
+    pub async fn wait_network_ready_with(
+        &self,
+        config: ReadinessConfig,
+    ) -> Result<(), ReadinessError> {
         let listen_ports = self.node_listen_ports();
         if listen_ports.len() <= 1 {
             return Ok(());
@@ -115,6 +175,7 @@ impl Topology {
             topology: self,
             expected_peer_counts: &expected_peer_counts,
             labels: &labels,
+            config,
         };
 
         check.wait().await?;
@@ -122,6 +183,16 @@ impl Topology {
     }
 
     pub async fn wait_da_balancer_ready(&self) -> Result<(), ReadinessError> {
+        self.wait_da_balancer_ready_with(ReadinessConfig::default())
+            .await
+    }
+
+    /// Like [`Self::wait_da_balancer_ready`] with a custom timeout/poll
+    /// interval.
+    pub async fn wait_da_balancer_ready_with(
+        &self,
+        config: ReadinessConfig,
+    ) -> Result<(), ReadinessError> {
         if self.validators.is_empty() && self.executors.is_empty() {
             return Ok(());
         }
@@ -130,6 +201,7 @@ impl Topology {
         let check = DaBalancerReadiness {
             topology: self,
             labels: &labels,
+            config,
         };
 
         check.wait().await?;
@@ -137,7 +209,17 @@ impl Topology {
     }
 
     pub async fn wait_membership_ready(&self) -> Result<(), ReadinessError> {
-        self.wait_membership_ready_for_session(SessionNumber::from(0u64))
+        self.wait_membership_ready_with(ReadinessConfig::default())
+            .await
+    }
+
+    /// Like [`Self::wait_membership_ready`] with a custom timeout/poll
+    /// interval.
+    pub async fn wait_membership_ready_with(
+        &self,
+        config: ReadinessConfig,
+    ) -> Result<(), ReadinessError> {
+        self.wait_membership_assignations(SessionNumber::from(0u64), true, config)
             .await
     }
 
@@ -145,20 +227,23 @@ impl Topology {
         &self,
         session: SessionNumber,
     ) -> Result<(), ReadinessError> {
-        self.wait_membership_assignations(session, true).await
+        self.wait_membership_assignations(session, true, ReadinessConfig::default())
+            .await
     }
 
     pub async fn wait_membership_empty_for_session(
         &self,
         session: SessionNumber,
     ) -> Result<(), ReadinessError> {
-        self.wait_membership_assignations(session, false).await
+        self.wait_membership_assignations(session, false, ReadinessConfig::default())
+            .await
     }
 
     async fn wait_membership_assignations(
         &self,
         session: SessionNumber,
         expect_non_empty: bool,
+        config: ReadinessConfig,
     ) -> Result<(), ReadinessError> {
         let total_nodes = self.validators.len() + self.executors.len();
 
@@ -172,6 +257,7 @@ impl Topology {
             session,
             labels: &labels,
             expect_non_empty,
+            config,
         };
 
         check.wait().await?;
@@ -1,16 +1,28 @@
 use std::{collections::HashSet, time::Duration};
 
-use reqwest::{Client, Url};
-
-use crate::topology::{
-    config::TopologyConfig,
-    configs::{GeneralConfig, wallet::WalletAccount},
-    deployment::Topology,
-    readiness::{HttpMembershipReadiness, HttpNetworkReadiness, ReadinessCheck, ReadinessError},
+use rand::rngs::StdRng;
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    nodes::HttpOptions,
+    ports::HeldPorts,
+    scenario::{NodeTarget, WalletPool},
+    topology::{
+        config::{LateJoin, TopologyConfig},
+        configs::{GeneralConfig, wallet::WalletAccount},
+        deployment::Topology,
+        readiness::{
+            HttpMembershipReadiness, HttpNetworkReadiness, ReadinessCheck, ReadinessConfig,
+            ReadinessError,
+        },
+        utils::seeded_rng,
+    },
 };
 
 /// Node role within the generated topology.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum NodeRole {
     Validator,
     Executor,
@@ -63,6 +75,10 @@ pub struct GeneratedTopology {
     pub(crate) config: TopologyConfig,
     pub(crate) validators: Vec<GeneratedNodeConfig>,
     pub(crate) executors: Vec<GeneratedNodeConfig>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) wallet_pool: WalletPool,
+    /// Sockets holding the generated ports until the nodes are spawned.
+    pub(crate) held_ports: HeldPorts,
 }
 
 impl GeneratedTopology {
@@ -97,13 +113,61 @@ impl GeneratedTopology {
             .map(|node| node.general.time_config.slot_duration)
     }
 
+    #[must_use]
+    /// Scenario seed the topology was generated with, if any.
+    pub const fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    #[must_use]
+    /// RNG for the named stream, reproducible when the topology is seeded.
+    pub fn rng(&self, stream: &str) -> StdRng {
+        seeded_rng(self.seed, stream)
+    }
+
+    #[must_use]
+    /// Nodes started after the run begins rather than with the cluster.
+    pub fn late_joins(&self) -> &[LateJoin] {
+        &self.config.late_joins
+    }
+
+    #[must_use]
+    /// The nodes running from the start, i.e. without late joiners. Readiness
+    /// checks run against this view; node indices still refer to the full
+    /// topology.
+    pub fn initial_topology(&self) -> Self {
+        let mut initial = self.clone();
+        let config = &self.config;
+        initial
+            .validators
+            .retain(|node| !config.joins_late(NodeTarget::Validator(node.index)));
+        initial
+            .executors
+            .retain(|node| !config.joins_late(NodeTarget::Executor(node.index)));
+        initial
+    }
+
     #[must_use]
     /// Wallet accounts configured for this topology.
     pub fn wallet_accounts(&self) -> &[WalletAccount] {
         &self.config.wallet_config.accounts
     }
 
+    #[must_use]
+    /// Claims on the wallet accounts, shared by every workload of the run.
+    pub const fn wallet_pool(&self) -> &WalletPool {
+        &self.wallet_pool
+    }
+
+    /// Close the sockets holding the generated ports so the nodes can bind
+    /// them. Called right before nodes are spawned on this host; otherwise
+    /// the ports are released with the last clone of the topology.
+    pub fn release_ports(&self) {
+        self.held_ports.release();
+    }
+
     pub async fn spawn_local(&self) -> Topology {
+        self.release_ports();
         let configs = self
             .nodes()
             .map(|node| node.general.clone())
@@ -128,6 +192,29 @@ impl GeneratedTopology {
         executor_endpoints: &[Url],
         validator_membership_endpoints: Option<&[Url]>,
         executor_membership_endpoints: Option<&[Url]>,
+        readiness: ReadinessConfig,
+    ) -> Result<(), ReadinessError> {
+        self.wait_remote_readiness_with_options(
+            validator_endpoints,
+            executor_endpoints,
+            validator_membership_endpoints,
+            executor_membership_endpoints,
+            readiness,
+            &HttpOptions::default(),
+        )
+        .await
+    }
+
+    /// Like [`Self::wait_remote_readiness`], probing the endpoints with the
+    /// TLS and auth settings in `http`.
+    pub async fn wait_remote_readiness_with_options(
+        &self,
+        validator_endpoints: &[Url],
+        executor_endpoints: &[Url],
+        validator_membership_endpoints: Option<&[Url]>,
+        executor_membership_endpoints: Option<&[Url]>,
+        readiness: ReadinessConfig,
+        http: &HttpOptions,
     ) -> Result<(), ReadinessError> {
         let total_nodes = self.validators.len() + self.executors.len();
         if total_nodes == 0 {
@@ -150,7 +237,7 @@ impl GeneratedTopology {
         endpoints.extend_from_slice(executor_endpoints);
 
         let labels = self.labels();
-        let client = Client::new();
+        let client = http.client()?;
         let make_testing_base_url = |port: u16| -> Url {
             Url::parse(&format!("http://127.0.0.1:{port}/"))
                 .expect("failed to construct local testing base url")
@@ -168,6 +255,7 @@ impl GeneratedTopology {
                 endpoints: &endpoints,
                 expected_peer_counts: &expected_peer_counts,
                 labels: &labels,
+                config: readiness,
             };
 
             network_check.wait().await?;
@@ -210,6 +298,7 @@ impl GeneratedTopology {
             session: nomos_core::sdp::SessionNumber::from(0u64),
             labels: &labels,
             expect_non_empty: true,
+            config: readiness,
         };
 
         membership_check.wait().await
@@ -254,17 +343,16 @@ impl GeneratedTopology {
     fn labels(&self) -> Vec<String> {
         self.validators
             .iter()
-            .enumerate()
-            .map(|(idx, node)| {
+            .map(|node| {
                 format!(
-                    "validator#{idx}@{}",
-                    node.general.network_config.backend.swarm.port
+                    "validator#{}@{}",
+                    node.index, node.general.network_config.backend.swarm.port
                 )
             })
-            .chain(self.executors.iter().enumerate().map(|(idx, node)| {
+            .chain(self.executors.iter().map(|node| {
                 format!(
-                    "executor#{idx}@{}",
-                    node.general.network_config.backend.swarm.port
+                    "executor#{}@{}",
+                    node.index, node.general.network_config.backend.swarm.port
                 )
             }))
             .collect()
@@ -6,4 +6,5 @@ pub mod config;
 pub mod deployment;
 pub mod generation;
 pub mod readiness;
+pub mod snapshot;
 pub mod utils;
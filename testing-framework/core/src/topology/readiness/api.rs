@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use super::{ReadinessCheck, ReadinessConfig};
+use crate::nodes::ApiClient;
+
+/// Waits until every node answers consensus info queries. Used for clusters
+/// started outside the framework, where the peer and membership layouts the
+/// generated topology would predict do not apply.
+pub struct ApiReadiness<'a> {
+    pub(crate) clients: &'a [(String, ApiClient)],
+    pub(crate) config: ReadinessConfig,
+}
+
+#[async_trait::async_trait]
+impl<'a> ReadinessCheck<'a> for ApiReadiness<'a> {
+    type Data = Vec<(String, Result<u64, String>)>;
+
+    async fn collect(&'a self) -> Self::Data {
+        consensus_heights(self.clients).await
+    }
+
+    fn is_ready(&self, data: &Self::Data) -> bool {
+        data.iter().all(|(_, result)| result.is_ok())
+    }
+
+    fn timeout_message(&self, data: Self::Data) -> String {
+        format!("timed out waiting for node APIs: {}", failed_probes(data))
+    }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
+}
+
+/// Consensus height of each node, or why it could not be read.
+pub(super) async fn consensus_heights(
+    clients: &[(String, ApiClient)],
+) -> Vec<(String, Result<u64, String>)> {
+    let probes = clients.iter().map(|(label, client)| async move {
+        let result = client
+            .consensus_info()
+            .await
+            .map(|info| info.height)
+            .map_err(|err| err.to_string());
+        (label.clone(), result)
+    });
+    futures::future::join_all(probes).await
+}
+
+/// `label: error` for every failed probe, comma separated.
+pub(super) fn failed_probes<T>(data: Vec<(String, Result<T, String>)>) -> String {
+    data.into_iter()
+        .filter_map(|(label, result)| result.err().map(|err| format!("{label}: {err}")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -1,11 +1,16 @@
+use std::time::Duration;
+
 use nomos_da_network_core::swarm::BalancerStats;
 
-use super::ReadinessCheck;
+use super::{ReadinessCheck, ReadinessConfig};
 use crate::topology::deployment::Topology;
 
+const BALANCER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 pub struct DaBalancerReadiness<'a> {
     pub(crate) topology: &'a Topology,
     pub(crate) labels: &'a [String],
+    pub(crate) config: ReadinessConfig,
 }
 
 #[async_trait::async_trait]
@@ -54,12 +59,20 @@ impl<'a> ReadinessCheck<'a> for DaBalancerReadiness<'a> {
         format!("timed out waiting for DA balancer readiness: {summary}")
     }
 
-    fn poll_interval(&self) -> std::time::Duration {
-        std::time::Duration::from_secs(1)
+    fn poll_interval(&self) -> Duration {
+        self.config
+            .poll_interval()
+            .unwrap_or(BALANCER_POLL_INTERVAL)
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
     }
 }
 
-fn connected_subnetworks(stats: &BalancerStats) -> usize {
+/// Subnetworks with at least one inbound or outbound peer.
+#[must_use]
+pub fn connected_subnetworks(stats: &BalancerStats) -> usize {
     stats
         .values()
         .filter(|stat| stat.inbound > 0 || stat.outbound > 0)
@@ -1,8 +1,10 @@
+use std::time::Duration;
+
 use nomos_core::sdp::SessionNumber;
 use nomos_da_network_service::MembershipResponse;
 use reqwest::{Client, Url};
 
-use super::ReadinessCheck;
+use super::{ReadinessCheck, ReadinessConfig};
 use crate::topology::deployment::Topology;
 
 pub struct MembershipReadiness<'a> {
@@ -10,6 +12,7 @@ pub struct MembershipReadiness<'a> {
     pub(crate) session: SessionNumber,
     pub(crate) labels: &'a [String],
     pub(crate) expect_non_empty: bool,
+    pub(crate) config: ReadinessConfig,
 }
 
 #[async_trait::async_trait]
@@ -54,6 +57,14 @@ impl<'a> ReadinessCheck<'a> for MembershipReadiness<'a> {
         let summary = build_membership_summary(self.labels, &statuses, description);
         format!("timed out waiting for DA membership readiness ({description}): {summary}")
     }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
 }
 
 impl MembershipReadiness<'_> {
@@ -85,6 +96,7 @@ pub struct HttpMembershipReadiness<'a> {
     pub(crate) session: SessionNumber,
     pub(crate) labels: &'a [String],
     pub(crate) expect_non_empty: bool,
+    pub(crate) config: ReadinessConfig,
 }
 
 #[async_trait::async_trait]
@@ -115,6 +127,14 @@ impl<'a> ReadinessCheck<'a> for HttpMembershipReadiness<'a> {
         let summary = build_membership_summary(self.labels, &statuses, description);
         format!("timed out waiting for DA membership readiness ({description}): {summary}")
     }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
 }
 
 pub async fn fetch_membership(
@@ -1,21 +1,108 @@
+pub mod api;
 pub mod balancer;
 pub mod membership;
 pub mod network;
+pub mod transactions;
 
 use std::time::Duration;
 
-pub use balancer::DaBalancerReadiness;
+pub use api::ApiReadiness;
+pub use balancer::{DaBalancerReadiness, connected_subnetworks};
 pub use membership::{HttpMembershipReadiness, MembershipReadiness};
 pub use network::{HttpNetworkReadiness, NetworkReadiness};
 use thiserror::Error;
 use tokio::time::{sleep, timeout};
+pub use transactions::{MempoolReadiness, WalletReadiness, wait_transaction_readiness};
 
-use crate::adjust_timeout;
+use crate::{adjust_timeout, nodes::HttpOptionsError, scenario::ScenarioTimings};
+
+/// How long a readiness check may poll before giving up.
+pub const DEFAULT_READINESS_TIMEOUT: Duration = Duration::from_secs(60);
+/// Delay between polls for checks that do not pick their own interval.
+pub const DEFAULT_READINESS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Timeout and poll interval applied to readiness checks.
+///
+/// The timeout is scaled by [`adjust_timeout`] like other test timeouts. When
+/// no poll interval is set each check keeps its own default. Settings left
+/// unset can be filled from the scenario's timings with
+/// [`ReadinessConfig::or_timings`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReadinessConfig {
+    timeout: Option<Duration>,
+    poll_interval: Option<Duration>,
+}
+
+impl Default for ReadinessConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadinessConfig {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            timeout: None,
+            poll_interval: None,
+        }
+    }
+
+    #[must_use]
+    /// Override how long each check may take before failing.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Override how often each check re-queries the nodes.
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    #[must_use]
+    /// Fill the settings not overridden explicitly from `timings`.
+    pub fn or_timings(self, timings: &ScenarioTimings) -> Self {
+        Self {
+            timeout: self.timeout.or(Some(timings.readiness())),
+            poll_interval: self.poll_interval.or(timings.readiness_poll_interval()),
+        }
+    }
+
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        match self.timeout {
+            Some(timeout) => timeout,
+            None => DEFAULT_READINESS_TIMEOUT,
+        }
+    }
+
+    #[must_use]
+    pub const fn poll_interval(&self) -> Option<Duration> {
+        self.poll_interval
+    }
+
+    #[must_use]
+    /// The overridden poll interval, or [`DEFAULT_READINESS_POLL_INTERVAL`].
+    pub const fn poll_interval_or_default(&self) -> Duration {
+        match self.poll_interval {
+            Some(poll_interval) => poll_interval,
+            None => DEFAULT_READINESS_POLL_INTERVAL,
+        }
+    }
+}
 
 #[derive(Debug, Error)]
 pub enum ReadinessError {
     #[error("{message}")]
     Timeout { message: String },
+    #[error("failed to build readiness HTTP client: {source}")]
+    HttpClient {
+        #[from]
+        source: HttpOptionsError,
+    },
 }
 
 #[async_trait::async_trait]
@@ -29,11 +116,15 @@ pub trait ReadinessCheck<'a> {
     fn timeout_message(&self, data: Self::Data) -> String;
 
     fn poll_interval(&self) -> Duration {
-        Duration::from_millis(200)
+        DEFAULT_READINESS_POLL_INTERVAL
+    }
+
+    fn timeout(&self) -> Duration {
+        DEFAULT_READINESS_TIMEOUT
     }
 
     async fn wait(&'a self) -> Result<(), ReadinessError> {
-        let timeout_duration = adjust_timeout(Duration::from_secs(60));
+        let timeout_duration = adjust_timeout(self.timeout());
         let poll_interval = self.poll_interval();
         let mut data = self.collect().await;
 
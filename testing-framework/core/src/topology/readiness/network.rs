@@ -1,14 +1,17 @@
+use std::time::Duration;
+
 use nomos_network::backends::libp2p::Libp2pInfo;
 use reqwest::{Client, Url};
 use tracing::warn;
 
-use super::ReadinessCheck;
+use super::{ReadinessCheck, ReadinessConfig};
 use crate::topology::deployment::Topology;
 
 pub struct NetworkReadiness<'a> {
     pub(crate) topology: &'a Topology,
     pub(crate) expected_peer_counts: &'a [usize],
     pub(crate) labels: &'a [String],
+    pub(crate) config: ReadinessConfig,
 }
 
 #[async_trait::async_trait]
@@ -44,6 +47,14 @@ impl<'a> ReadinessCheck<'a> for NetworkReadiness<'a> {
         let summary = build_timeout_summary(self.labels, data, self.expected_peer_counts);
         format!("timed out waiting for network readiness: {summary}")
     }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
 }
 
 pub struct HttpNetworkReadiness<'a> {
@@ -51,6 +62,7 @@ pub struct HttpNetworkReadiness<'a> {
     pub(crate) endpoints: &'a [Url],
     pub(crate) expected_peer_counts: &'a [usize],
     pub(crate) labels: &'a [String],
+    pub(crate) config: ReadinessConfig,
 }
 
 #[async_trait::async_trait]
@@ -75,6 +87,14 @@ impl<'a> ReadinessCheck<'a> for HttpNetworkReadiness<'a> {
         let summary = build_timeout_summary(self.labels, data, self.expected_peer_counts);
         format!("timed out waiting for network readiness: {summary}")
     }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
 }
 
 async fn fetch_network_info(client: &Client, base: &Url) -> Libp2pInfo {
@@ -0,0 +1,199 @@
+use std::time::Duration;
+
+use tracing::info;
+
+use super::{
+    ReadinessCheck, ReadinessConfig, ReadinessError,
+    api::{consensus_heights, failed_probes},
+};
+use crate::{nodes::ApiClient, scenario::NodeClients, topology::generation::GeneratedTopology};
+
+/// Mempool whose metrics are probed; transactions are submitted to it.
+const MANTLE_POOL: &str = "mantle";
+/// Most blocks a node may trail the highest one by and count as caught up.
+const MAX_HEIGHT_SPREAD: u64 = 2;
+
+/// Waits until every node answers mempool metrics queries, i.e. its mempool
+/// service accepts requests.
+pub struct MempoolReadiness<'a> {
+    pub(crate) clients: &'a [(String, ApiClient)],
+    pub(crate) config: ReadinessConfig,
+}
+
+#[async_trait::async_trait]
+impl<'a> ReadinessCheck<'a> for MempoolReadiness<'a> {
+    type Data = Vec<(String, Result<(), String>)>;
+
+    async fn collect(&'a self) -> Self::Data {
+        let probes = self.clients.iter().map(|(label, client)| async move {
+            let result = client
+                .mempool_metrics(MANTLE_POOL)
+                .await
+                .map(|_| ())
+                .map_err(|err| err.to_string());
+            (label.clone(), result)
+        });
+        futures::future::join_all(probes).await
+    }
+
+    fn is_ready(&self, data: &Self::Data) -> bool {
+        data.iter().all(|(_, result)| result.is_ok())
+    }
+
+    fn timeout_message(&self, data: Self::Data) -> String {
+        format!(
+            "timed out waiting for mempool readiness: {}",
+            failed_probes(data)
+        )
+    }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
+}
+
+/// Waits until every node follows a chain past genesis and is within
+/// [`MAX_HEIGHT_SPREAD`] blocks of the highest one, so every node validates
+/// wallet transactions against the same, live ledger state whichever node
+/// they are submitted to.
+pub struct WalletReadiness<'a> {
+    pub(crate) clients: &'a [(String, ApiClient)],
+    pub(crate) config: ReadinessConfig,
+}
+
+#[async_trait::async_trait]
+impl<'a> ReadinessCheck<'a> for WalletReadiness<'a> {
+    type Data = Vec<(String, Result<u64, String>)>;
+
+    async fn collect(&'a self) -> Self::Data {
+        consensus_heights(self.clients).await
+    }
+
+    fn is_ready(&self, data: &Self::Data) -> bool {
+        let Some(heights) = data
+            .iter()
+            .map(|(_, result)| result.as_ref().ok().copied())
+            .collect::<Option<Vec<_>>>()
+        else {
+            return false;
+        };
+        let highest = heights.iter().copied().max().unwrap_or_default();
+        heights
+            .iter()
+            .all(|height| *height > 0 && highest - height <= MAX_HEIGHT_SPREAD)
+    }
+
+    fn timeout_message(&self, data: Self::Data) -> String {
+        let highest = data
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok().copied())
+            .max()
+            .unwrap_or_default();
+        let summary = data
+            .into_iter()
+            .map(|(label, result)| match result {
+                Ok(height) => format!("{label}: height={height}"),
+                Err(err) => format!("{label}: {err}"),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "timed out waiting for wallet readiness (every node past genesis and within \
+             {MAX_HEIGHT_SPREAD} blocks of height {highest}): {summary}"
+        )
+    }
+
+    fn poll_interval(&self) -> Duration {
+        self.config.poll_interval_or_default()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.config.timeout()
+    }
+}
+
+/// Run [`MempoolReadiness`] and [`WalletReadiness`] against the nodes of
+/// `clients` that start with the cluster.
+pub async fn wait_transaction_readiness(
+    clients: &NodeClients,
+    descriptors: &GeneratedTopology,
+    config: ReadinessConfig,
+) -> Result<(), ReadinessError> {
+    let clients: Vec<_> = clients
+        .targeted_clients()
+        .filter(|(target, _)| !descriptors.config().joins_late(*target))
+        .map(|(target, client)| (target.to_string(), client.clone()))
+        .collect();
+    if clients.is_empty() {
+        return Ok(());
+    }
+
+    info!(nodes = clients.len(), "waiting for mempool readiness");
+    MempoolReadiness {
+        clients: &clients,
+        config,
+    }
+    .wait()
+    .await?;
+
+    info!(nodes = clients.len(), "waiting for wallet readiness");
+    WalletReadiness {
+        clients: &clients,
+        config,
+    }
+    .wait()
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+    use crate::scenario::ScenarioBuilder;
+
+    fn wallet(data: &[(&str, Result<u64, &str>)]) -> (bool, String) {
+        let check = WalletReadiness {
+            clients: &[],
+            config: ReadinessConfig::new(),
+        };
+        let data = data
+            .iter()
+            .map(|(label, result)| ((*label).to_owned(), result.map_err(ToOwned::to_owned)))
+            .collect::<Vec<_>>();
+        (check.is_ready(&data), check.timeout_message(data))
+    }
+
+    #[test]
+    fn wallet_readiness_needs_caught_up_nodes_past_genesis() {
+        assert!(wallet(&[("validator-0", Ok(5)), ("validator-1", Ok(3))]).0);
+        assert!(!wallet(&[("validator-0", Ok(5)), ("validator-1", Ok(2))]).0);
+        assert!(!wallet(&[("validator-0", Ok(1)), ("validator-1", Ok(0))]).0);
+
+        let (ready, message) = wallet(&[("validator-0", Ok(4)), ("executor-0", Err("refused"))]);
+        assert!(!ready);
+        assert!(message.contains("height 4"), "{message}");
+        assert!(message.contains("executor-0: refused"), "{message}");
+    }
+
+    #[tokio::test]
+    async fn unreachable_nodes_time_out_on_the_mempool() {
+        let scenario = ScenarioBuilder::topology_with(|t| t.validators(1).executors(0)).build();
+        let refused: SocketAddr = "127.0.0.1:1".parse().expect("valid address");
+        let clients = NodeClients::new(vec![ApiClient::new(refused, None)], Vec::new());
+        let config = ReadinessConfig::new()
+            .with_timeout(Duration::from_millis(200))
+            .with_poll_interval(Duration::from_millis(50));
+
+        let err = wait_transaction_readiness(&clients, scenario.topology(), config)
+            .await
+            .expect_err("nothing answers");
+        let message = err.to_string();
+        assert!(message.contains("mempool readiness"), "{message}");
+        assert!(message.contains("validator-0"), "{message}");
+    }
+}
@@ -0,0 +1,487 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use testing_framework_config::topology::configs::network::Libp2pNetworkLayout;
+use thiserror::Error;
+use time::OffsetDateTime;
+use tracing::{debug, info};
+
+use crate::{
+    nodes::{
+        common::{
+            config::{
+                injection::inject_ibd_into_cryptarchia,
+                paths::{ensure_recovery_paths, ensure_storage_paths},
+            },
+            lifecycle::spawn::write_config_with_injection,
+            node::NodeConfigCommon,
+        },
+        executor::create_executor_config,
+        validator::create_validator_config,
+    },
+    scenario::{
+        NodeTarget,
+        cfgsync::{cfgsync_config_for_topology, load_cfgsync_template, render_cfgsync_yaml},
+    },
+    topology::{
+        config::{LateJoin, NodeResources, RegionLink, TopologyBuilder, TopologyConfig},
+        generation::{GeneratedNodeConfig, GeneratedTopology, NodeRole},
+    },
+};
+
+/// Manifest file written at the root of an exported snapshot.
+pub const SNAPSHOT_MANIFEST_FILE: &str = "manifest.json";
+/// Cfgsync server config written at the root of an exported snapshot.
+pub const SNAPSHOT_CFGSYNC_FILE: &str = "cfgsync.yaml";
+/// Bumped whenever the snapshot layout changes incompatibly.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Failures writing or loading a topology snapshot.
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("failed to access snapshot path {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to render snapshot file {path}: {source}")]
+    Render {
+        path: PathBuf,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("failed to parse snapshot file {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error("snapshot {path} has version {found}, expected {SNAPSHOT_VERSION}")]
+    UnsupportedVersion { path: PathBuf, found: u32 },
+    #[error("invalid snapshot {path}: {reason}")]
+    Invalid { path: PathBuf, reason: String },
+}
+
+/// Describes an exported topology: which file holds each node's config and
+/// the ports and role it was generated with.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub version: u32,
+    pub seed: Option<u64>,
+    pub validators: usize,
+    pub executors: usize,
+    /// Cfgsync config relative to the snapshot root.
+    pub cfgsync: PathBuf,
+    pub network_layout: Libp2pNetworkLayout,
+    /// Chain start time as a unix timestamp, split into whole seconds and
+    /// the nanoseconds past them.
+    pub chain_start_unix_secs: i64,
+    pub chain_start_nanos: u32,
+    pub slot_duration: Duration,
+    pub nodes: Vec<SnapshotNode>,
+    /// Shaping between the regions named by [`SnapshotNode::region`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub region_links: Vec<RegionLink>,
+}
+
+/// A single node in a [`SnapshotManifest`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SnapshotNode {
+    /// Node label, e.g. `validator-0`.
+    pub label: String,
+    pub role: NodeRole,
+    pub index: usize,
+    /// Hex-encoded node id.
+    pub id: String,
+    /// Node config, as passed to the node binary, relative to the snapshot
+    /// root.
+    pub config: PathBuf,
+    /// Directory the config's database and blob paths point into.
+    pub data_dir: PathBuf,
+    pub ports: SnapshotPorts,
+    /// Delay after which the node is started, for late joiners.
+    pub late_join_after_secs: Option<f64>,
+    /// Network region the node is placed in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Container resources, when any are set for the node or its role.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<NodeResources>,
+}
+
+/// Ports a snapshot node listens on.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct SnapshotPorts {
+    pub api: u16,
+    pub testing_http: u16,
+    pub network: u16,
+    pub da: u16,
+    pub blend: u16,
+}
+
+impl GeneratedTopology {
+    /// Write every node config (as the node binary expects it), a cfgsync
+    /// config and a [`SnapshotManifest`] into `dir`, so a node can be run by
+    /// hand (`nomos-node <dir>/validator-0/validator.yaml`) or handed to
+    /// external deployment tooling. Node data paths point into per-node
+    /// directories under `dir`.
+    pub fn export(&self, dir: impl AsRef<Path>) -> Result<SnapshotManifest, SnapshotError> {
+        let dir = dir.as_ref();
+        let Some(time) = self.nodes().next().map(|node| &node.general.time_config) else {
+            return Err(SnapshotError::Invalid {
+                path: dir.to_owned(),
+                reason: "topology has no nodes to export".to_owned(),
+            });
+        };
+        create_dir(dir)?;
+        let root = fs::canonicalize(dir).map_err(|source| SnapshotError::Io {
+            path: dir.to_owned(),
+            source,
+        })?;
+        info!(dir = %root.display(), nodes = self.nodes().count(), "exporting topology snapshot");
+
+        let nodes = self
+            .nodes()
+            .map(|node| self.export_node(&root, node))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let cfgsync_path = root.join(SNAPSHOT_CFGSYNC_FILE);
+        let cfgsync =
+            render_cfgsync_yaml(&cfgsync_config_for_topology(self)).map_err(|source| {
+                SnapshotError::Render {
+                    path: cfgsync_path.clone(),
+                    source,
+                }
+            })?;
+        write_file(&cfgsync_path, cfgsync)?;
+
+        let manifest = SnapshotManifest {
+            version: SNAPSHOT_VERSION,
+            seed: self.seed,
+            validators: self.validators.len(),
+            executors: self.executors.len(),
+            cfgsync: PathBuf::from(SNAPSHOT_CFGSYNC_FILE),
+            network_layout: self.config.network_params.libp2p_network_layout,
+            chain_start_unix_secs: time.chain_start_time.unix_timestamp(),
+            chain_start_nanos: time.chain_start_time.nanosecond(),
+            slot_duration: time.slot_duration,
+            nodes,
+            region_links: self.config.regions.links.clone(),
+        };
+        let manifest_path = root.join(SNAPSHOT_MANIFEST_FILE);
+        let rendered =
+            serde_json::to_string_pretty(&manifest).map_err(|source| SnapshotError::Render {
+                path: manifest_path.clone(),
+                source: source.into(),
+            })?;
+        write_file(&manifest_path, rendered)?;
+
+        Ok(manifest)
+    }
+
+    /// Rebuild the topology exported into `dir` by [`Self::export`]: ids,
+    /// ports, keys, genesis and chain start time are taken from the snapshot,
+    /// so the regenerated node configs match the exported ones byte for byte.
+    /// Node resources and network regions are restored per node. Topology
+    /// settings not carried by the snapshot (DA timeouts absent from the
+    /// cfgsync config) fall back to their defaults.
+    pub fn import(dir: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        let dir = dir.as_ref();
+        let manifest_path = dir.join(SNAPSHOT_MANIFEST_FILE);
+        let manifest: SnapshotManifest = serde_json::from_str(&read_file(&manifest_path)?)
+            .map_err(|source| SnapshotError::Parse {
+                path: manifest_path.clone(),
+                source: source.into(),
+            })?;
+        if manifest.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion {
+                path: manifest_path,
+                found: manifest.version,
+            });
+        }
+        let invalid = |reason: String| SnapshotError::Invalid {
+            path: manifest_path.clone(),
+            reason,
+        };
+        if manifest.nodes.len() != manifest.validators + manifest.executors {
+            return Err(invalid(format!(
+                "lists {} nodes for {} validators and {} executors",
+                manifest.nodes.len(),
+                manifest.validators,
+                manifest.executors
+            )));
+        }
+        info!(dir = %dir.display(), nodes = manifest.nodes.len(), "importing topology snapshot");
+
+        let cfgsync_path = dir.join(&manifest.cfgsync);
+        let cfgsync =
+            load_cfgsync_template(&cfgsync_path).map_err(|source| SnapshotError::Parse {
+                path: cfgsync_path,
+                source,
+            })?;
+
+        let mut config = TopologyConfig::with_node_numbers(manifest.validators, manifest.executors);
+        let consensus = &mut config.consensus_params;
+        consensus.security_param = cfgsync.security_param;
+        consensus.active_slot_coeff = cfgsync.active_slot_coeff;
+        consensus.session_duration = cfgsync.session_duration;
+        consensus.genesis = cfgsync.genesis;
+        let da = &mut config.da_params;
+        da.subnetwork_size = cfgsync.subnetwork_size;
+        da.dispersal_factor = cfgsync.dispersal_factor;
+        da.num_samples = cfgsync.num_samples;
+        da.num_subnets = cfgsync.num_subnets;
+        da.old_blobs_check_interval = cfgsync.old_blobs_check_interval;
+        da.blobs_validity_duration = cfgsync.blobs_validity_duration;
+        da.global_params_path = cfgsync.global_params_path;
+        da.policy_settings.min_dispersal_peers = cfgsync.min_dispersal_peers;
+        da.policy_settings.min_replication_peers = cfgsync.min_replication_peers;
+        da.monitor_settings.failure_time_window = cfgsync.monitor_failure_time_window;
+        da.balancer_interval = cfgsync.balancer_interval;
+        da.replication_settings = cfgsync.replication_settings;
+        da.retry_shares_limit = cfgsync.retry_shares_limit;
+        da.retry_commitments_limit = cfgsync.retry_commitments_limit;
+        config.wallet_config = cfgsync.wallet;
+        config.network_params.ip_stack = cfgsync.ip_stack;
+        config.network_params.libp2p_network_layout = manifest.network_layout;
+
+        let mut ids = Vec::with_capacity(manifest.nodes.len());
+        for node in &manifest.nodes {
+            let mut id = [0; 32];
+            hex::decode_to_slice(&node.id, &mut id)
+                .map_err(|err| invalid(format!("node {} has id {}: {err}", node.label, node.id)))?;
+            ids.push(id);
+
+            let target = match node.role {
+                NodeRole::Validator => NodeTarget::Validator(node.index),
+                NodeRole::Executor => NodeTarget::Executor(node.index),
+            };
+            if let Some(secs) = node.late_join_after_secs {
+                config.late_joins.push(LateJoin {
+                    target,
+                    delay: Duration::from_secs_f64(secs),
+                });
+            }
+            if let Some(resources) = &node.resources {
+                config.resources.set_node(target, resources.clone());
+            }
+            if let Some(name) = &node.region {
+                match config
+                    .regions
+                    .regions
+                    .iter_mut()
+                    .find(|region| &region.name == name)
+                {
+                    Some(region) => region.nodes.push(target),
+                    None => config.regions = config.regions.region(name, [target]),
+                }
+            }
+        }
+        config.regions.links = manifest.region_links.clone();
+
+        let ports = |port: fn(&SnapshotPorts) -> u16| -> Vec<u16> {
+            manifest
+                .nodes
+                .iter()
+                .map(|node| port(&node.ports))
+                .collect()
+        };
+        let mut builder = TopologyBuilder::new(config)
+            .with_ids(ids)
+            .with_da_ports(ports(|ports| ports.da))
+            .with_blend_ports(ports(|ports| ports.blend))
+            .with_network_ports(ports(|ports| ports.network))
+            .with_api_ports(ports(|ports| ports.api))
+            .with_testing_http_ports(ports(|ports| ports.testing_http));
+        if let Some(seed) = manifest.seed {
+            builder = builder.with_seed(seed);
+        }
+        let mut topology = builder.build();
+
+        let chain_start_time = OffsetDateTime::from_unix_timestamp(manifest.chain_start_unix_secs)
+            .and_then(|time| time.replace_nanosecond(manifest.chain_start_nanos))
+            .map_err(|err| invalid(format!("chain start time is out of range: {err}")))?;
+        for node in topology
+            .validators
+            .iter_mut()
+            .chain(topology.executors.iter_mut())
+        {
+            node.general.time_config.chain_start_time = chain_start_time;
+            node.general.time_config.slot_duration = manifest.slot_duration;
+        }
+
+        Ok(topology)
+    }
+
+    fn export_node(
+        &self,
+        root: &Path,
+        node: &GeneratedNodeConfig,
+    ) -> Result<SnapshotNode, SnapshotError> {
+        let target = match node.role {
+            NodeRole::Validator => NodeTarget::Validator(node.index),
+            NodeRole::Executor => NodeTarget::Executor(node.index),
+        };
+        let label = target.to_string();
+        let data_dir = root.join(&label);
+        create_dir(&data_dir)?;
+        ensure_recovery_paths(&data_dir)
+            .and_then(|()| ensure_storage_paths(&data_dir))
+            .map_err(|source| SnapshotError::Io {
+                path: data_dir.clone(),
+                source,
+            })?;
+
+        let config_file = match node.role {
+            NodeRole::Validator => {
+                let mut config = create_validator_config(node.general.clone());
+                config.set_paths(&data_dir);
+                write_node_config(&config, &data_dir, "validator.yaml")?
+            }
+            NodeRole::Executor => {
+                let mut config = create_executor_config(node.general.clone());
+                config.set_paths(&data_dir);
+                write_node_config(&config, &data_dir, "executor.yaml")?
+            }
+        };
+        debug!(node = %label, config = config_file, "exported node config");
+
+        let late_join = self
+            .late_joins()
+            .iter()
+            .find(|join| join.target == target)
+            .map(|join| join.delay.as_secs_f64());
+        let resources = self.config.resources.for_node(target);
+        Ok(SnapshotNode {
+            id: hex::encode(node.id),
+            role: node.role,
+            index: node.index,
+            config: PathBuf::from(&label).join(config_file),
+            data_dir: PathBuf::from(&label),
+            ports: SnapshotPorts {
+                api: node.api_port(),
+                testing_http: node.testing_http_port(),
+                network: node.network_port(),
+                da: node.da_port,
+                blend: node.blend_port,
+            },
+            late_join_after_secs: late_join,
+            region: self
+                .config
+                .regions
+                .region_of(target)
+                .map(|region| region.name.clone()),
+            resources: (resources != &NodeResources::default()).then(|| resources.clone()),
+            label,
+        })
+    }
+}
+
+/// Write `config` the way the node spawner does and return its file name.
+fn write_node_config<C: Serialize>(
+    config: &C,
+    dir: &Path,
+    file_name: &'static str,
+) -> Result<&'static str, SnapshotError> {
+    let path = dir.join(file_name);
+    write_config_with_injection(config, &path, inject_ibd_into_cryptarchia)
+        .map_err(|source| SnapshotError::Io { path, source })?;
+    Ok(file_name)
+}
+
+fn create_dir(path: &Path) -> Result<(), SnapshotError> {
+    fs::create_dir_all(path).map_err(|source| SnapshotError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn read_file(path: &Path) -> Result<String, SnapshotError> {
+    fs::read_to_string(path).map_err(|source| SnapshotError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+fn write_file(path: &Path, contents: String) -> Result<(), SnapshotError> {
+    fs::write(path, contents).map_err(|source| SnapshotError::Io {
+        path: path.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::topology::config::NetworkRegions;
+
+    fn read_tree(root: &Path, manifest: &SnapshotManifest) -> Vec<String> {
+        let root = root.display().to_string();
+        manifest
+            .nodes
+            .iter()
+            .map(|node| node.config.clone())
+            .chain([manifest.cfgsync.clone()])
+            .map(|file| {
+                read_file(&Path::new(&root).join(file))
+                    .expect("snapshot file readable")
+                    .replace(&root, "<root>")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn export_import_export_round_trips() {
+        let mut config = TopologyConfig::with_node_numbers(2, 1);
+        config.late_joins.push(LateJoin {
+            target: NodeTarget::Validator(1),
+            delay: Duration::from_secs(30),
+        });
+        config.resources.set_node(
+            NodeTarget::Executor(0),
+            NodeResources::new().with_requests("500m", "1Gi"),
+        );
+        config.regions = NetworkRegions::new()
+            .region("eu", [NodeTarget::Validator(0)])
+            .region("us", [NodeTarget::Validator(1), NodeTarget::Executor(0)])
+            .latency("eu", "us", Duration::from_millis(80));
+        let topology = TopologyBuilder::new(config).with_seed(7).build();
+
+        let (first, second) = (
+            TempDir::new().expect("tempdir"),
+            TempDir::new().expect("tempdir"),
+        );
+        let exported = topology.export(first.path()).expect("export");
+        let imported = GeneratedTopology::import(first.path()).expect("import");
+        let reexported = imported.export(second.path()).expect("re-export");
+
+        assert_eq!(
+            serde_json::to_value(&exported).expect("manifest serializes"),
+            serde_json::to_value(&reexported).expect("manifest serializes"),
+        );
+        let first_root = fs::canonicalize(first.path()).expect("canonical path");
+        let second_root = fs::canonicalize(second.path()).expect("canonical path");
+        assert_eq!(
+            read_tree(&first_root, &exported),
+            read_tree(&second_root, &reexported)
+        );
+
+        let restored = imported.config();
+        assert_eq!(
+            restored.resources.for_node(NodeTarget::Executor(0)),
+            topology
+                .config()
+                .resources
+                .for_node(NodeTarget::Executor(0))
+        );
+        assert_eq!(restored.regions, topology.config().regions);
+        assert_eq!(restored.late_joins, topology.late_joins());
+    }
+}
@@ -2,11 +2,11 @@ use std::{collections::HashMap, iter};
 
 use groth16::fr_to_bytes;
 use key_management_system_service::{backend::preload::PreloadKMSBackendSettings, keys::Key};
-use nomos_utils::net::get_available_udp_port;
-use rand::{Rng, thread_rng};
+use rand::{Rng, SeedableRng as _, rngs::StdRng};
 
-use crate::topology::configs::{
-    blend::GeneralBlendConfig, da::GeneralDaConfig, wallet::WalletAccount,
+use crate::{
+    ports::{HeldPorts, PortAllocator, PortProtocol},
+    topology::configs::{blend::GeneralBlendConfig, da::GeneralDaConfig, wallet::WalletAccount},
 };
 
 #[must_use]
@@ -52,12 +52,31 @@ pub fn create_kms_configs(
         .collect()
 }
 
-pub fn resolve_ids(ids: Option<Vec<[u8; 32]>>, count: usize) -> Vec<[u8; 32]> {
+/// RNG for a named stream of a scenario. With a seed, every stream is
+/// reproducible and independent of the others; without one it is seeded from
+/// OS entropy.
+#[must_use]
+pub fn seeded_rng(seed: Option<u64>, stream: &str) -> StdRng {
+    seed.map_or_else(StdRng::from_entropy, |seed| {
+        StdRng::seed_from_u64(seed ^ stream_hash(stream))
+    })
+}
+
+/// FNV-1a, used instead of `DefaultHasher` so stream seeds stay stable across
+/// Rust releases.
+fn stream_hash(stream: &str) -> u64 {
+    stream.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+pub fn resolve_ids(ids: Option<Vec<[u8; 32]>>, count: usize, seed: Option<u64>) -> Vec<[u8; 32]> {
     ids.map_or_else(
         || {
+            let mut rng = seeded_rng(seed, "node_ids");
             let mut generated = vec![[0; 32]; count];
             for id in &mut generated {
-                thread_rng().fill(id);
+                rng.fill(id);
             }
             generated
         },
@@ -73,12 +92,15 @@ pub fn resolve_ids(ids: Option<Vec<[u8; 32]>>, count: usize) -> Vec<[u8; 32]> {
     )
 }
 
-pub fn resolve_ports(ports: Option<Vec<u16>>, count: usize, label: &str) -> Vec<u16> {
-    let resolved = ports.unwrap_or_else(|| {
-        iter::repeat_with(|| get_available_udp_port().unwrap())
-            .take(count)
-            .collect()
-    });
+/// `ports`, or `count` free UDP ports whose reservations are kept in `held`
+/// until the nodes using them start.
+pub fn resolve_ports(
+    ports: Option<Vec<u16>>,
+    count: usize,
+    label: &str,
+    held: &HeldPorts,
+) -> Vec<u16> {
+    let resolved = ports.unwrap_or_else(|| reserve_held(PortProtocol::Udp, count, held));
     assert_eq!(
         resolved.len(),
         count,
@@ -88,6 +110,28 @@ pub fn resolve_ports(ports: Option<Vec<u16>>, count: usize, label: &str) -> Vec<
     resolved
 }
 
+/// Like [`resolve_ports`], picking free TCP ports for HTTP listeners.
+pub fn resolve_tcp_ports(
+    ports: Option<Vec<u16>>,
+    count: usize,
+    label: &str,
+    held: &HeldPorts,
+) -> Vec<u16> {
+    let ports = ports.unwrap_or_else(|| reserve_held(PortProtocol::Tcp, count, held));
+    resolve_ports(Some(ports), count, label, held)
+}
+
+fn reserve_held(protocol: PortProtocol, count: usize, held: &HeldPorts) -> Vec<u16> {
+    iter::repeat_with(|| {
+        let reservation = PortAllocator::global()
+            .reserve(protocol)
+            .unwrap_or_else(|err| panic!("failed to allocate a port: {err}"));
+        held.hold(reservation)
+    })
+    .take(count)
+    .collect()
+}
+
 pub fn multiaddr_port(addr: &nomos_libp2p::Multiaddr) -> Option<u16> {
     for protocol in addr {
         match protocol {
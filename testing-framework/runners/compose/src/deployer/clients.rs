@@ -1,5 +1,5 @@
 use testing_framework_core::{
-    scenario::{BlockFeed, BlockFeedTask, NodeClients},
+    scenario::{ApiFaultProxies, ApiFaults, BlockFeed, BlockFeedTask, NodeClients},
     topology::generation::GeneratedTopology,
 };
 use tracing::info;
@@ -39,12 +39,33 @@ impl ClientBuilder {
         }
     }
 
+    /// Route `node_clients` through fault proxies when the scenario injects
+    /// node API faults.
+    pub async fn apply_api_faults(
+        &self,
+        node_clients: NodeClients,
+        faults: Option<ApiFaults>,
+        environment: &mut StackEnvironment,
+    ) -> Result<(NodeClients, Option<ApiFaultProxies>), ComposeRunnerError> {
+        match ApiFaultProxies::wrap(node_clients, faults).await {
+            Ok(wrapped) => Ok(wrapped),
+            Err(err) => {
+                environment
+                    .fail("failed to start node api fault proxies")
+                    .await;
+                tracing::warn!(error = ?err, "fault proxy startup failed");
+                Err(err.into())
+            }
+        }
+    }
+
     pub async fn start_block_feed(
         &self,
         node_clients: &NodeClients,
+        descriptors: &GeneratedTopology,
         environment: &mut StackEnvironment,
     ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
-        match spawn_block_feed_with_retry(node_clients).await {
+        match spawn_block_feed_with_retry(node_clients, descriptors).await {
             Ok(pair) => {
                 info!("block feed connected to validator");
                 Ok(pair)
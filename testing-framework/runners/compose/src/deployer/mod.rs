@@ -4,17 +4,50 @@ pub mod ports;
 pub mod readiness;
 pub mod setup;
 
+use std::time::Duration;
+
 use async_trait::async_trait;
-use testing_framework_core::scenario::{
-    BlockFeedTask, CleanupGuard, Deployer, RequiresNodeControl, Runner, Scenario,
+use testing_framework_core::{
+    scenario::{
+        BlockFeedTask, CleanupGuard, Deployer, DryRunReport, NodeTarget, Profile,
+        RequiresNodeControl, Runner, Scenario, ScenarioTimings,
+    },
+    topology::readiness::ReadinessConfig,
+};
+use url::Url;
+
+use crate::{
+    descriptor::{Healthcheck, ImageOverrides},
+    errors::ComposeRunnerError,
+    infrastructure::{
+        environment::StackOptions, stagger::StaggeredStart, template::ComposeTemplate,
+    },
+    lifecycle::{cleanup::RunnerCleanup, watchdog::ContainerWatchdog},
 };
 
-use crate::{errors::ComposeRunnerError, lifecycle::cleanup::RunnerCleanup};
+/// Compose's own bring-up, node HTTP and HTTP poll defaults, used where the
+/// scenario's timings leave them unset.
+const COMPOSE_TIMINGS: ScenarioTimings = ScenarioTimings::new()
+    .with_bring_up(Duration::from_secs(120))
+    .with_node_http(Duration::from_secs(180))
+    .with_http_poll_interval(Duration::from_millis(250));
 
 /// Docker Compose-based deployer for Nomos test scenarios.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct ComposeDeployer {
     readiness_checks: bool,
+    readiness: ReadinessConfig,
+    collect_logs: bool,
+    compress_logs: bool,
+    observability: Option<bool>,
+    dashboards: bool,
+    tracing: bool,
+    fail_fast_on_exit: bool,
+    images: ImageOverrides,
+    external_cfgsync: Option<Url>,
+    template: ComposeTemplate,
+    staggered_start: Option<StaggeredStart>,
+    healthcheck: Option<Healthcheck>,
 }
 
 impl Default for ComposeDeployer {
@@ -25,9 +58,21 @@ impl Default for ComposeDeployer {
 
 impl ComposeDeployer {
     #[must_use]
-    pub const fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             readiness_checks: true,
+            readiness: ReadinessConfig::new(),
+            collect_logs: true,
+            compress_logs: false,
+            observability: None,
+            dashboards: true,
+            tracing: false,
+            fail_fast_on_exit: false,
+            images: ImageOverrides::default(),
+            external_cfgsync: None,
+            template: ComposeTemplate::Embedded,
+            staggered_start: None,
+            healthcheck: Some(Healthcheck::default()),
         }
     }
 
@@ -36,6 +81,201 @@ impl ComposeDeployer {
         self.readiness_checks = enabled;
         self
     }
+
+    #[must_use]
+    /// Time allowed for each network/membership readiness check. Defaults to
+    /// the scenario's readiness timing; raise it for large topologies.
+    pub const fn with_readiness_timeout(mut self, timeout: Duration) -> Self {
+        self.readiness = self.readiness.with_timeout(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Delay between readiness polls.
+    pub const fn with_readiness_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.readiness = self.readiness.with_poll_interval(poll_interval);
+        self
+    }
+
+    #[must_use]
+    /// Stream node container logs into `<workspace>/logs` during the run and
+    /// expose them through `RunContext::node_logs` (enabled by default).
+    pub const fn with_log_collection(mut self, enabled: bool) -> Self {
+        self.collect_logs = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Gzip collected log files when the run is torn down.
+    pub const fn with_log_compression(mut self, enabled: bool) -> Self {
+        self.compress_logs = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Run Prometheus and Grafana next to the nodes. Defaults to the
+    /// scenario profile's setting, or enabled without a profile. Disabling
+    /// them speeds up minimal CI runs and frees their host ports; nodes then
+    /// export no metrics and `RunContext::telemetry` is an empty handle whose
+    /// `is_configured` returns false.
+    pub const fn with_observability(mut self, enabled: bool) -> Self {
+        self.observability = Some(enabled);
+        self
+    }
+
+    #[must_use]
+    /// Provision a Grafana dashboard with block rate, mempool and DA panels
+    /// filtered to this scenario's nodes (enabled by default).
+    pub const fn with_dashboards(mut self, enabled: bool) -> Self {
+        self.dashboards = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Export node spans over OTLP to a Tempo service and expose it through
+    /// `RunContext::traces` for span-based expectations.
+    pub const fn with_tracing(mut self, enabled: bool) -> Self {
+        self.tracing = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Abort the run as soon as a node container exits without being stopped
+    /// through node control, failing with its last log lines. Unexpected
+    /// exits are always logged and recorded in the run report.
+    pub const fn with_fail_fast_on_exit(mut self, enabled: bool) -> Self {
+        self.fail_fast_on_exit = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Run every validator from `image` instead of `NOMOS_TESTNET_IMAGE`.
+    pub fn with_validators_image(mut self, image: impl Into<String>) -> Self {
+        self.images = self.images.with_validators(image);
+        self
+    }
+
+    #[must_use]
+    /// Run every executor from `image` instead of `NOMOS_TESTNET_IMAGE`.
+    pub fn with_executors_image(mut self, image: impl Into<String>) -> Self {
+        self.images = self.images.with_executors(image);
+        self
+    }
+
+    #[must_use]
+    /// Run validator `index` from `image`; combine with the role-wide
+    /// overrides to build mixed-version clusters for upgrade testing.
+    pub fn with_validator_image(mut self, index: usize, image: impl Into<String>) -> Self {
+        self.images = self.images.with_node(NodeTarget::Validator(index), image);
+        self
+    }
+
+    #[must_use]
+    /// Run executor `index` from `image`.
+    pub fn with_executor_image(mut self, index: usize, image: impl Into<String>) -> Self {
+        self.images = self.images.with_node(NodeTarget::Executor(index), image);
+        self
+    }
+
+    #[must_use]
+    /// Fetch node configs from the cfgsync server at `url` instead of
+    /// starting one, e.g. to share a server between stacks or use one run
+    /// with custom settings. The server must already be configured for the
+    /// scenario's topology; a loopback `url` is reached through the docker
+    /// host from the containers.
+    pub fn with_external_cfgsync(mut self, url: Url) -> Self {
+        self.external_cfgsync = Some(url);
+        self
+    }
+
+    #[must_use]
+    /// Render the stack from a custom Tera template instead of the embedded
+    /// one, e.g. [`ComposeTemplate::file`] or [`ComposeTemplate::source`].
+    /// The template sees the same descriptor as the default
+    /// (`assets/docker-compose.yml.tera`), which is a good starting point. A
+    /// copy is kept in the workspace; nodes added later, also after
+    /// [`Self::resume`], render from it.
+    pub fn with_template(mut self, template: ComposeTemplate) -> Self {
+        self.template = template;
+        self
+    }
+
+    #[must_use]
+    /// Start node containers one at a time, `interval` plus up to `jitter`
+    /// apart, instead of all at once. Other services start first and
+    /// readiness checks still run once every node is up.
+    pub const fn with_staggered_start(mut self, interval: Duration, jitter: Duration) -> Self {
+        self.staggered_start = Some(StaggeredStart::new(interval, jitter));
+        self
+    }
+
+    #[must_use]
+    /// Stagger the start with full control, e.g. over the batch size.
+    pub const fn with_staggered_start_plan(mut self, staggered: StaggeredStart) -> Self {
+        self.staggered_start = Some(staggered);
+        self
+    }
+
+    #[must_use]
+    /// Run a healthcheck probing the API of every node container (enabled
+    /// by default). Readiness waits for the containers to turn healthy and
+    /// the container watchdog records nodes that turn unhealthy mid-run.
+    pub const fn with_healthchecks(mut self, enabled: bool) -> Self {
+        self.healthcheck = if enabled {
+            Some(match self.healthcheck {
+                Some(healthcheck) => healthcheck,
+                None => Healthcheck::new(),
+            })
+        } else {
+            None
+        };
+        self
+    }
+
+    #[must_use]
+    /// Tune the interval, timeout, retries and start period of the node
+    /// healthchecks, enabling them.
+    pub const fn with_healthcheck(mut self, healthcheck: Healthcheck) -> Self {
+        self.healthcheck = Some(healthcheck);
+        self
+    }
+
+    /// Re-attach to the stack deployed by run `run_id` after the harness that
+    /// deployed it died, rebuilding node clients, log collection and the
+    /// cleanup guard from the state persisted in the run artifacts. The
+    /// scenario must generate the same topology, which needs a fixed seed.
+    pub async fn resume<Caps>(
+        &self,
+        scenario: &Scenario<Caps>,
+        run_id: &str,
+    ) -> Result<Runner, ComposeRunnerError>
+    where
+        Caps: RequiresNodeControl + Send + Sync,
+    {
+        orchestrator::DeploymentOrchestrator::new(self.clone())
+            .resume(scenario, run_id)
+            .await
+    }
+
+    fn stack_options<Caps: RequiresNodeControl>(&self, scenario: &Scenario<Caps>) -> StackOptions {
+        let profile = scenario.profile();
+        StackOptions {
+            images: self.images.clone(),
+            observability: self
+                .observability
+                .unwrap_or_else(|| profile.is_none_or(Profile::observability)),
+            dashboards: self.dashboards,
+            tracing: self.tracing,
+            node_control: Caps::REQUIRED,
+            timings: compose_timings(scenario),
+            node_env: scenario.node_env().clone(),
+            profile,
+            external_cfgsync: self.external_cfgsync.clone(),
+            template: self.template.clone(),
+            staggered_start: self.staggered_start,
+            healthcheck: self.healthcheck,
+        }
+    }
 }
 
 #[async_trait]
@@ -46,28 +286,43 @@ where
     type Error = ComposeRunnerError;
 
     async fn deploy(&self, scenario: &Scenario<Caps>) -> Result<Runner, Self::Error> {
-        orchestrator::DeploymentOrchestrator::new(*self)
+        orchestrator::DeploymentOrchestrator::new(self.clone())
             .deploy(scenario)
             .await
     }
+
+    fn dry_run(&self, scenario: &Scenario<Caps>) -> Result<DryRunReport, Self::Error> {
+        let mut report = scenario.dry_run().with_deployer("compose");
+        setup::DeploymentSetup::new(scenario.topology(), self.stack_options(scenario))
+            .dry_run(&mut report)?;
+        Ok(report)
+    }
 }
 
 pub(super) struct ComposeCleanupGuard {
     environment: RunnerCleanup,
     block_feed: Option<BlockFeedTask>,
+    watchdog: Option<ContainerWatchdog>,
 }
 
 impl ComposeCleanupGuard {
-    const fn new(environment: RunnerCleanup, block_feed: BlockFeedTask) -> Self {
+    const fn new(
+        environment: RunnerCleanup,
+        block_feed: BlockFeedTask,
+        watchdog: Option<ContainerWatchdog>,
+    ) -> Self {
         Self {
             environment,
             block_feed: Some(block_feed),
+            watchdog,
         }
     }
 }
 
 impl CleanupGuard for ComposeCleanupGuard {
     fn cleanup(mut self: Box<Self>) {
+        // Stop watching before teardown stops the containers.
+        drop(self.watchdog.take());
         if let Some(block_feed) = self.block_feed.take() {
             CleanupGuard::cleanup(Box::new(block_feed));
         }
@@ -78,13 +333,23 @@ impl CleanupGuard for ComposeCleanupGuard {
 pub(super) fn make_cleanup_guard(
     environment: RunnerCleanup,
     block_feed: BlockFeedTask,
+    watchdog: Option<ContainerWatchdog>,
 ) -> Box<dyn CleanupGuard> {
-    Box::new(ComposeCleanupGuard::new(environment, block_feed))
+    Box::new(ComposeCleanupGuard::new(environment, block_feed, watchdog))
+}
+
+/// The scenario's timings with compose defaults filled in.
+pub(crate) fn compose_timings<Caps>(scenario: &Scenario<Caps>) -> ScenarioTimings {
+    scenario.timings().or_defaults(&COMPOSE_TIMINGS)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, net::Ipv4Addr};
+    use std::{
+        collections::HashMap,
+        net::{IpAddr, Ipv4Addr, Ipv6Addr},
+        time::Duration,
+    };
 
     use cfgsync::{
         config::builder::create_node_configs,
@@ -99,12 +364,27 @@ mod tests {
     use nomos_ledger::LedgerState;
     use nomos_tracing_service::TracingSettings;
     use testing_framework_core::{
-        scenario::ScenarioBuilder,
-        topology::generation::{
-            GeneratedNodeConfig, GeneratedTopology, NodeRole as TopologyNodeRole,
+        scenario::{ScenarioBuilder, ScenarioTimings},
+        topology::{
+            configs::network::IpStack,
+            generation::{GeneratedNodeConfig, GeneratedTopology, NodeRole as TopologyNodeRole},
         },
     };
 
+    use super::compose_timings;
+
+    #[test]
+    fn compose_timings_fill_only_unset_settings() {
+        let scenario = ScenarioBuilder::topology_with(|t| t.validators(1).executors(1))
+            .with_timings(ScenarioTimings::new().with_node_http(Duration::from_secs(30)))
+            .build();
+
+        let timings = compose_timings(&scenario);
+        assert_eq!(timings.bring_up(), Duration::from_secs(120));
+        assert_eq!(timings.node_http(), Duration::from_secs(30));
+        assert_eq!(timings.http_poll_interval(), Duration::from_millis(250));
+    }
+
     #[test]
     fn cfgsync_prebuilt_configs_preserve_genesis() {
         let scenario = ScenarioBuilder::topology_with(|t| t.validators(1).executors(1)).build();
@@ -120,6 +400,7 @@ mod tests {
             Some(topology.nodes().map(|node| node.id).collect()),
             Some(topology.nodes().map(|node| node.da_port).collect()),
             Some(topology.nodes().map(|node| node.blend_port).collect()),
+            topology.config().network_params.ip_stack,
             hosts,
         );
         let configs_by_identifier: HashMap<_, _> = configs
@@ -172,6 +453,7 @@ mod tests {
             Some(topology.nodes().map(|node| node.id).collect()),
             Some(topology.nodes().map(|node| node.da_port).collect()),
             Some(topology.nodes().map(|node| node.blend_port).collect()),
+            topology.config().network_params.ip_stack,
             hosts,
         );
         let configs_by_identifier: HashMap<_, _> = configs
@@ -208,6 +490,7 @@ mod tests {
             Some(topology.nodes().map(|node| node.id).collect()),
             Some(topology.nodes().map(|node| node.da_port).collect()),
             Some(topology.nodes().map(|node| node.blend_port).collect()),
+            topology.config().network_params.ip_stack,
             hosts,
         );
 
@@ -224,6 +507,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cfgsync_ipv6_hosts_dial_swarms_where_they_listen() {
+        let scenario =
+            ScenarioBuilder::topology_with(|t| t.validators(2).executors(1).ip_stack(IpStack::V6))
+                .build();
+        let topology = scenario.topology().clone();
+        let tracing_settings = tracing_settings(&topology);
+        let hosts: Vec<Host> = topology
+            .nodes()
+            .map(|node| {
+                let identifier = identifier_for(node.role(), node.index());
+                let ip = IpAddr::V6(Ipv6Addr::new(
+                    0xfd00,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    0,
+                    10 + node.index() as u16,
+                ));
+                let swarm_ip = IpAddr::V4(Ipv4Addr::new(172, 23, 0, 10 + node.index() as u8));
+                let mut host = make_host(node.role(), ip, identifier).with_swarm_ip(swarm_ip);
+                host.network_port = node.network_port();
+                host.da_network_port = node.da_port;
+                host.blend_port = node.blend_port;
+                host
+            })
+            .collect();
+
+        let configs = create_node_configs(
+            &topology.config().consensus_params,
+            &topology.config().da_params,
+            &tracing_settings,
+            &topology.config().wallet_config,
+            Some(topology.nodes().map(|node| node.id).collect()),
+            Some(topology.nodes().map(|node| node.da_port).collect()),
+            Some(topology.nodes().map(|node| node.blend_port).collect()),
+            topology.config().network_params.ip_stack,
+            hosts,
+        );
+
+        for (host, config) in configs {
+            let identifier = &host.identifier;
+            assert!(
+                config
+                    .da_config
+                    .listening_address
+                    .to_string()
+                    .starts_with("/ip6/::/"),
+                "DA listener for {identifier} should bind the IPv6 wildcard"
+            );
+            assert_eq!(
+                config.network_config.backend.swarm.host,
+                Ipv4Addr::UNSPECIFIED,
+                "swarm for {identifier} should bind the IPv4 wildcard"
+            );
+            let peers = &config.network_config.backend.initial_peers;
+            assert!(
+                peers
+                    .iter()
+                    .all(|peer| peer.to_string().starts_with("/ip4/172.23.0.")),
+                "initial peers for {identifier} should be dialed over IPv4: {peers:?}"
+            );
+            assert_eq!(
+                config.api_config.address.ip(),
+                IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+                "API for {identifier} should listen on IPv6"
+            );
+        }
+    }
+
     fn hosts_from_topology(topology: &GeneratedTopology) -> Vec<Host> {
         topology.nodes().map(host_from_node).collect()
     }
@@ -237,7 +592,7 @@ mod tests {
 
     fn host_from_node(node: &GeneratedNodeConfig) -> Host {
         let identifier = identifier_for(node.role(), node.index());
-        let ip = Ipv4Addr::LOCALHOST;
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
         let mut host = make_host(node.role(), ip, identifier);
         host.network_port = node.network_port();
         host.da_network_port = node.da_port;
@@ -247,7 +602,7 @@ mod tests {
 
     fn docker_host(node: &GeneratedNodeConfig, octet: u8) -> Host {
         let identifier = identifier_for(node.role(), node.index());
-        let ip = Ipv4Addr::new(172, 23, 0, octet);
+        let ip = IpAddr::V4(Ipv4Addr::new(172, 23, 0, octet));
         let mut host = make_host(node.role(), ip, identifier);
         host.network_port = node.network_port().saturating_add(1000);
         host.da_network_port = node.da_port.saturating_add(1000);
@@ -274,14 +629,8 @@ mod tests {
         }
     }
 
-    fn make_host(role: TopologyNodeRole, ip: Ipv4Addr, identifier: String) -> Host {
-        let ports = PortOverrides {
-            network_port: None,
-            da_network_port: None,
-            blend_port: None,
-            api_port: None,
-            testing_http_port: None,
-        };
+    fn make_host(role: TopologyNodeRole, ip: IpAddr, identifier: String) -> Host {
+        let ports = PortOverrides::default();
         match role {
             TopologyNodeRole::Validator => Host::validator_from_ip(ip, identifier, ports),
             TopologyNodeRole::Executor => Host::executor_from_ip(ip, identifier, ports),
@@ -1,26 +1,34 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Instant};
 
-use testing_framework_core::scenario::{
-    NodeControlHandle, RequiresNodeControl, RunContext, Runner, Scenario,
+use testing_framework_core::{
+    artifacts::RunArtifacts,
+    scenario::{
+        DeploymentTimeline, Metrics, NodeControlHandle, NodeLogs, NodeTarget, RequiresNodeControl,
+        RunContext, Runner, Scenario, TempoEndpoint,
+    },
+    topology::generation::GeneratedTopology,
 };
-use tracing::info;
+use tracing::{info, warn};
+use url::Url;
 
 use super::{
     ComposeDeployer,
     clients::ClientBuilder,
-    make_cleanup_guard,
+    compose_timings, make_cleanup_guard,
     ports::PortManager,
     readiness::ReadinessChecker,
     setup::{DeploymentContext, DeploymentSetup},
 };
 use crate::{
-    docker::control::ComposeNodeControl,
-    errors::ComposeRunnerError,
+    docker::{control::ComposeNodeControl, ensure_docker_available, workspace::ComposeWorkspace},
+    errors::{ComposeRunnerError, StateError, WorkspaceError},
     infrastructure::{
-        environment::StackEnvironment,
+        environment::{StackEnvironment, resume_environment},
         ports::{HostPortMapping, compose_runner_host},
+        state::ComposeRunState,
+        template::ComposeTemplate,
     },
-    lifecycle::readiness::metrics_handle_from_port,
+    lifecycle::{readiness::metrics_handle_from_port, watchdog::ContainerWatchdog},
 };
 
 pub struct DeploymentOrchestrator {
@@ -28,7 +36,7 @@ pub struct DeploymentOrchestrator {
 }
 
 impl DeploymentOrchestrator {
-    pub const fn new(deployer: ComposeDeployer) -> Self {
+    pub fn new(deployer: ComposeDeployer) -> Self {
         Self { deployer }
     }
 
@@ -39,13 +47,20 @@ impl DeploymentOrchestrator {
     where
         Caps: RequiresNodeControl + Send + Sync,
     {
-        let setup = DeploymentSetup::new(scenario.topology());
-        setup.validate_environment().await?;
+        let options = self.deployer.stack_options(scenario);
+        let setup = DeploymentSetup::new(scenario.topology(), options);
+        let mut timeline = DeploymentTimeline::new();
+        timeline
+            .measure("environment_check", setup.validate_environment())
+            .await?;
 
         let DeploymentContext {
-            mut environment,
+            environment,
             descriptors,
-        } = setup.prepare_workspace().await?;
+        } = setup.prepare_workspace(&mut timeline).await?;
+        if let Err(err) = environment.persist_state(&descriptors) {
+            warn!(error = %err, "failed to persist compose deployment state; the run cannot be resumed");
+        }
 
         tracing::info!(
             validators = descriptors.validators().len(),
@@ -55,42 +70,151 @@ impl DeploymentOrchestrator {
             "compose deployment starting"
         );
 
+        self.attach(scenario, environment, descriptors, timeline)
+            .await
+    }
+
+    /// Re-attach to the stack deployed by run `run_id`, whose harness died
+    /// without tearing it down.
+    pub async fn resume<Caps>(
+        &self,
+        scenario: &Scenario<Caps>,
+        run_id: &str,
+    ) -> Result<Runner, ComposeRunnerError>
+    where
+        Caps: RequiresNodeControl + Send + Sync,
+    {
+        ensure_docker_available().await?;
+        let artifacts = RunArtifacts::open(run_id).map_err(|source| StateError::Artifacts {
+            run_id: run_id.to_owned(),
+            source,
+        })?;
+        let state = ComposeRunState::load(&artifacts)?;
+
+        let descriptors = scenario.topology().clone();
+        let validators = descriptors.validators().len();
+        let executors = descriptors.executors().len();
+        if state.validators != validators || state.executors != executors {
+            return Err(StateError::TopologyMismatch {
+                run_id: run_id.to_owned(),
+                validators: state.validators,
+                executors: state.executors,
+                expected_validators: validators,
+                expected_executors: executors,
+            }
+            .into());
+        }
+
+        let workspace =
+            ComposeWorkspace::reopen(Arc::new(artifacts)).map_err(WorkspaceError::new)?;
+        let options = self.deployer.stack_options(scenario);
+        let environment = resume_environment(state, workspace, &descriptors, &options)?;
+
+        self.attach(
+            scenario,
+            environment,
+            descriptors,
+            DeploymentTimeline::new(),
+        )
+        .await
+    }
+
+    /// Wait for a running stack and build the runner driving it; the
+    /// remaining bring-up phases are added to `timeline`.
+    async fn attach<Caps>(
+        &self,
+        scenario: &Scenario<Caps>,
+        mut environment: StackEnvironment,
+        descriptors: GeneratedTopology,
+        mut timeline: DeploymentTimeline,
+    ) -> Result<Runner, ComposeRunnerError>
+    where
+        Caps: RequiresNodeControl + Send + Sync,
+    {
+        let timings = compose_timings(scenario);
         let validator_count = descriptors.validators().len();
         let executor_count = descriptors.executors().len();
-        let host_ports = PortManager::prepare(&mut environment, &descriptors).await?;
+        let node_logs = self
+            .maybe_collect_logs(&mut environment, validator_count, executor_count)
+            .await?;
+        let host_ports = timeline
+            .measure(
+                "port_discovery",
+                PortManager::prepare(&mut environment, &descriptors),
+            )
+            .await?;
 
-        if self.deployer.readiness_checks {
-            ReadinessChecker::wait_all(&descriptors, &host_ports, &mut environment).await?;
+        let readiness_started = Instant::now();
+        let readiness_elapsed = if self.deployer.readiness_checks {
+            ReadinessChecker::wait_all(
+                &descriptors,
+                &host_ports,
+                self.deployer.readiness.or_timings(&timings),
+                &timings,
+                &mut environment,
+            )
+            .await?;
+            timeline.record("readiness", readiness_started);
+            Some(readiness_started.elapsed())
         } else {
             info!("readiness checks disabled; giving the stack a short grace period");
             crate::lifecycle::readiness::maybe_sleep_for_disabled_readiness(false).await;
-        }
+            None
+        };
 
         let host = compose_runner_host();
         let client_builder = ClientBuilder::new();
         let node_clients = client_builder
             .build_node_clients(&descriptors, &host_ports, &host, &mut environment)
             .await?;
-        let telemetry = metrics_handle_from_port(environment.prometheus_port(), &host)?;
-        let node_control = self.maybe_node_control::<Caps>(&environment);
+        if scenario.transaction_readiness() {
+            timeline
+                .measure(
+                    "transaction_readiness",
+                    ReadinessChecker::wait_transactions(
+                        &node_clients,
+                        &descriptors,
+                        self.deployer.readiness.or_timings(&timings),
+                        &mut environment,
+                    ),
+                )
+                .await?;
+        }
+        let (node_clients, api_faults) = client_builder
+            .apply_api_faults(node_clients, scenario.api_faults(), &mut environment)
+            .await?;
+        let telemetry = match environment.prometheus_port() {
+            Some(port) => {
+                info!(
+                    prometheus_url = %format!("http://{host}:{port}/"),
+                    "prometheus endpoint available on host"
+                );
+                metrics_handle_from_port(port, &host)?
+            }
+            None => {
+                info!("observability disabled; metrics queries are unavailable");
+                Metrics::empty()
+            }
+        };
+        let node_control = self.maybe_node_control::<Caps>(&environment, &descriptors);
 
-        info!(
-            prometheus_url = %format!("http://{}:{}/", host, environment.prometheus_port()),
-            "prometheus endpoint available on host"
-        );
-        info!(
-            grafana_url = %format!("http://{}:{}/", host, environment.grafana_port()),
-            "grafana dashboard available on host"
-        );
+        if let Some(port) = environment.grafana_port() {
+            info!(
+                grafana_url = %format!("http://{host}:{port}/"),
+                "grafana dashboard available on host"
+            );
+        }
         log_profiling_urls(&host, &host_ports);
 
         // Log profiling endpoints (profiling feature must be enabled in the binaries).
         log_profiling_urls(&host, &host_ports);
 
-        let (block_feed, block_feed_guard) = client_builder
-            .start_block_feed(&node_clients, &mut environment)
+        let (block_feed, block_feed_guard) = timeline
+            .measure(
+                "block_feed",
+                client_builder.start_block_feed(&node_clients, &descriptors, &mut environment),
+            )
             .await?;
-        let cleanup_guard = make_cleanup_guard(environment.into_cleanup(), block_feed_guard);
 
         let context = RunContext::new(
             descriptors,
@@ -101,12 +225,38 @@ impl DeploymentOrchestrator {
             block_feed,
             node_control,
         );
+        let services = node_targets(validator_count, executor_count)
+            .map(|target| target.to_string())
+            .collect::<Vec<_>>();
+        let context = context
+            .with_deployment_timeline(timeline)
+            .with_api_faults(api_faults)
+            .with_artifacts(Arc::clone(environment.artifacts()))
+            .with_node_storage(environment.node_storage(&services));
+        let context = match node_logs {
+            Some(node_logs) => context.with_node_logs(node_logs),
+            None => context,
+        };
+        let context = match tempo_endpoint(&environment, &host) {
+            Some(traces) => context.with_traces(traces),
+            None => context,
+        };
+        if let Some(elapsed) = readiness_elapsed {
+            context
+                .report()
+                .record_readiness("compose_cluster", elapsed);
+        }
+
+        let watchdog = self.start_watchdog(&environment, &context, validator_count, executor_count);
+        let cleanup_guard =
+            make_cleanup_guard(environment.into_cleanup(), block_feed_guard, watchdog);
 
         info!(
             validators = validator_count,
             executors = executor_count,
             duration_secs = scenario.duration().as_secs(),
             readiness_checks = self.deployer.readiness_checks,
+            run_id = environment.artifacts().run_id(),
             host,
             "compose deployment ready; handing control to scenario runner"
         );
@@ -114,22 +264,85 @@ impl DeploymentOrchestrator {
         Ok(Runner::new(context, Some(cleanup_guard)))
     }
 
+    async fn maybe_collect_logs(
+        &self,
+        environment: &mut StackEnvironment,
+        validators: usize,
+        executors: usize,
+    ) -> Result<Option<NodeLogs>, ComposeRunnerError> {
+        if !self.deployer.collect_logs {
+            return Ok(None);
+        }
+
+        let services = node_targets(validators, executors)
+            .map(|target| target.to_string())
+            .collect::<Vec<_>>();
+        match environment.start_log_collection(&services, self.deployer.compress_logs) {
+            Ok(node_logs) => Ok(Some(node_logs)),
+            Err(err) => {
+                environment.fail("failed to start log collection").await;
+                Err(err)
+            }
+        }
+    }
+
+    fn start_watchdog(
+        &self,
+        environment: &StackEnvironment,
+        context: &RunContext,
+        validators: usize,
+        executors: usize,
+    ) -> Option<ContainerWatchdog> {
+        let services = node_targets(validators, executors)
+            .map(|target| (target.to_string(), target))
+            .collect();
+        ContainerWatchdog::start(
+            environment.compose_path(),
+            environment.project_name(),
+            environment.root(),
+            services,
+            context.report().clone(),
+            self.deployer
+                .fail_fast_on_exit
+                .then(|| context.abort_signal().clone()),
+        )
+    }
+
+    /// Node control is also needed, regardless of `Caps`, to start
+    /// late-joining nodes.
     fn maybe_node_control<Caps>(
         &self,
         environment: &StackEnvironment,
+        descriptors: &GeneratedTopology,
     ) -> Option<Arc<dyn NodeControlHandle>>
     where
         Caps: RequiresNodeControl + Send + Sync,
     {
-        Caps::REQUIRED.then(|| {
-            Arc::new(ComposeNodeControl {
-                compose_file: environment.compose_path().to_path_buf(),
-                project_name: environment.project_name().to_owned(),
-            }) as Arc<dyn NodeControlHandle>
+        let needed = Caps::REQUIRED || !descriptors.late_joins().is_empty();
+        needed.then(|| {
+            Arc::new(
+                ComposeNodeControl::new(
+                    environment.compose_path().to_path_buf(),
+                    environment.project_name().to_owned(),
+                )
+                .with_region_shaping(environment.descriptor().region_shaped_services())
+                .with_scaling(
+                    environment.descriptor().clone(),
+                    ComposeTemplate::persisted(environment.root()),
+                    environment.root().to_path_buf(),
+                    compose_runner_host(),
+                ),
+            ) as Arc<dyn NodeControlHandle>
         })
     }
 }
 
+fn node_targets(validators: usize, executors: usize) -> impl Iterator<Item = NodeTarget> {
+    (0..validators)
+        .map(NodeTarget::Validator)
+        .chain((0..executors).map(NodeTarget::Executor))
+}
+
 fn log_profiling_urls(host: &str, ports: &HostPortMapping) {
     for (idx, node) in ports.validators.iter().enumerate() {
         tracing::info!(
@@ -152,3 +365,17 @@ fn log_profiling_urls(host: &str, ports: &HostPortMapping) {
         );
     }
 }
+
+fn tempo_endpoint(environment: &StackEnvironment, host: &str) -> Option<TempoEndpoint> {
+    let port = environment.tempo_port()?;
+    match Url::parse(&format!("http://{host}:{port}/")) {
+        Ok(url) => {
+            info!(tempo_url = %url, "tempo trace search available on host");
+            Some(TempoEndpoint::new(url))
+        }
+        Err(err) => {
+            warn!(error = %err, "invalid tempo url; traces unavailable");
+            None
+        }
+    }
+}
@@ -1,13 +1,23 @@
-use testing_framework_core::topology::generation::GeneratedTopology;
-use tracing::info;
+use cfgsync::status::SyncPhase;
+use testing_framework_core::{
+    scenario::{NodeClients, ScenarioTimings},
+    topology::{
+        generation::GeneratedTopology,
+        readiness::{ReadinessConfig, wait_transaction_readiness},
+    },
+};
+use tracing::{info, warn};
 
 use crate::{
-    errors::ComposeRunnerError,
+    errors::{ComposeRunnerError, StackReadinessError},
     infrastructure::{
         environment::StackEnvironment,
         ports::{HostPortMapping, ensure_remote_readiness_with_ports},
     },
-    lifecycle::readiness::{ensure_executors_ready_with_ports, ensure_validators_ready_with_ports},
+    lifecycle::readiness::{
+        ensure_executors_ready_with_ports, ensure_services_healthy,
+        ensure_validators_ready_with_ports,
+    },
 };
 
 pub struct ReadinessChecker;
@@ -16,39 +26,130 @@ impl ReadinessChecker {
     pub async fn wait_all(
         descriptors: &GeneratedTopology,
         host_ports: &HostPortMapping,
+        readiness: ReadinessConfig,
+        timings: &ScenarioTimings,
         environment: &mut StackEnvironment,
     ) -> Result<(), ComposeRunnerError> {
+        // Late-joining nodes are held back until the run starts.
+        let host_ports = &host_ports.initial(descriptors);
+        let descriptors = &descriptors.initial_topology();
         info!(
             ports = ?host_ports.validator_api_ports(),
             "waiting for validator HTTP endpoints"
         );
         if let Err(err) =
-            ensure_validators_ready_with_ports(&host_ports.validator_api_ports()).await
+            ensure_validators_ready_with_ports(&host_ports.validator_api_ports(), timings).await
         {
-            environment.fail("validator readiness failed").await;
             tracing::warn!(error = ?err, "validator readiness failed");
-            return Err(err.into());
+            return Err(fail_with_cfgsync_context(
+                environment,
+                "validator readiness failed",
+                err.into(),
+            )
+            .await);
         }
 
         info!(
             ports = ?host_ports.executor_api_ports(),
             "waiting for executor HTTP endpoints"
         );
-        if let Err(err) = ensure_executors_ready_with_ports(&host_ports.executor_api_ports()).await
+        if let Err(err) =
+            ensure_executors_ready_with_ports(&host_ports.executor_api_ports(), timings).await
         {
-            environment.fail("executor readiness failed").await;
             tracing::warn!(error = ?err, "executor readiness failed");
-            return Err(err.into());
+            return Err(fail_with_cfgsync_context(
+                environment,
+                "executor readiness failed",
+                err.into(),
+            )
+            .await);
+        }
+
+        let services = environment
+            .descriptor()
+            .validators()
+            .iter()
+            .chain(environment.descriptor().executors())
+            .filter(|node| node.has_healthcheck())
+            .map(|node| node.name().to_owned())
+            .collect::<Vec<_>>();
+        if !services.is_empty() {
+            info!(services = services.len(), "waiting for node healthchecks");
+        }
+        if let Err(err) = ensure_services_healthy(
+            environment.compose_path(),
+            environment.project_name(),
+            environment.root(),
+            &services,
+            timings,
+        )
+        .await
+        {
+            tracing::warn!(error = ?err, "node healthchecks failed");
+            return Err(fail_with_cfgsync_context(
+                environment,
+                "node healthchecks failed",
+                err.into(),
+            )
+            .await);
         }
 
         info!("waiting for remote service readiness");
-        if let Err(err) = ensure_remote_readiness_with_ports(descriptors, host_ports).await {
-            environment.fail("remote readiness probe failed").await;
+        if let Err(err) =
+            ensure_remote_readiness_with_ports(descriptors, host_ports, readiness).await
+        {
             tracing::warn!(error = ?err, "remote readiness probe failed");
-            return Err(err.into());
+            return Err(fail_with_cfgsync_context(
+                environment,
+                "remote readiness probe failed",
+                err.into(),
+            )
+            .await);
         }
 
         info!("compose readiness checks passed");
         Ok(())
     }
+
+    /// Wait until every node's mempool and wallet state accept transactions.
+    pub async fn wait_transactions(
+        node_clients: &NodeClients,
+        descriptors: &GeneratedTopology,
+        readiness: ReadinessConfig,
+        environment: &mut StackEnvironment,
+    ) -> Result<(), ComposeRunnerError> {
+        if let Err(source) = wait_transaction_readiness(node_clients, descriptors, readiness).await
+        {
+            warn!(error = ?source, "transaction readiness probe failed");
+            return Err(fail_with_cfgsync_context(
+                environment,
+                "transaction readiness probe failed",
+                StackReadinessError::Transactions { source }.into(),
+            )
+            .await);
+        }
+
+        Ok(())
+    }
+}
+
+/// Tear the stack down, attaching cfgsync progress to the error when nodes
+/// never received their configs.
+async fn fail_with_cfgsync_context(
+    environment: &mut StackEnvironment,
+    reason: &str,
+    err: ComposeRunnerError,
+) -> ComposeRunnerError {
+    let status = environment.cfgsync_status().await;
+    environment.fail(reason).await;
+
+    match status {
+        Some(status) if status.phase != SyncPhase::Completed => {
+            ComposeRunnerError::CfgsyncIncomplete {
+                cfgsync: status.summary(),
+                source: Box::new(err),
+            }
+        }
+        _ => err,
+    }
 }
@@ -1,16 +1,19 @@
-use std::{
-    env,
-    net::{Ipv4Addr, TcpListener as StdTcpListener},
-};
+use std::env;
 
-use testing_framework_core::topology::generation::GeneratedTopology;
-use tracing::{debug, info};
+use testing_framework_core::{
+    constants::cfgsync_port,
+    ports::{PortAllocator, PortProtocol, PortReservation},
+    scenario::{DeploymentTimeline, DryRunReport},
+    topology::generation::GeneratedTopology,
+};
+use tracing::{debug, info, warn};
 
 use crate::{
     docker::ensure_docker_available,
     errors::ComposeRunnerError,
     infrastructure::environment::{
-        PortReservation, StackEnvironment, ensure_supported_topology, prepare_environment,
+        StackEnvironment, StackOptions, configure_cfgsync, ensure_supported_topology,
+        prepare_environment, prepare_workspace_logged, write_compose_artifacts,
     },
 };
 
@@ -19,6 +22,7 @@ pub const DEFAULT_PROMETHEUS_PORT: u16 = 9090;
 
 pub struct DeploymentSetup {
     descriptors: GeneratedTopology,
+    options: StackOptions,
 }
 
 pub struct DeploymentContext {
@@ -27,9 +31,10 @@ pub struct DeploymentContext {
 }
 
 impl DeploymentSetup {
-    pub fn new(descriptors: &GeneratedTopology) -> Self {
+    pub fn new(descriptors: &GeneratedTopology, options: StackOptions) -> Self {
         Self {
             descriptors: descriptors.clone(),
+            options,
         }
     }
 
@@ -46,24 +51,40 @@ impl DeploymentSetup {
         Ok(())
     }
 
-    pub async fn prepare_workspace(self) -> Result<DeploymentContext, ComposeRunnerError> {
+    pub async fn prepare_workspace(
+        self,
+        timeline: &mut DeploymentTimeline,
+    ) -> Result<DeploymentContext, ComposeRunnerError> {
         let prometheus_env = env::var(PROMETHEUS_PORT_ENV)
             .ok()
             .and_then(|raw| raw.parse::<u16>().ok());
-        if prometheus_env.is_some() {
-            info!(port = prometheus_env, "using prometheus port from env");
-        }
-        let prometheus_port = prometheus_env
-            .and_then(|port| reserve_port(port))
-            .or_else(|| allocate_prometheus_port())
-            .unwrap_or_else(|| PortReservation::new(DEFAULT_PROMETHEUS_PORT, None));
-        debug!(
-            prometheus_port = prometheus_port.port(),
-            "selected prometheus port"
-        );
-        let environment =
-            prepare_environment(&self.descriptors, prometheus_port, prometheus_env.is_some())
-                .await?;
+        let prometheus_port = if self.options.observability {
+            if prometheus_env.is_some() {
+                info!(port = prometheus_env, "using prometheus port from env");
+            }
+            let reservation = prometheus_env
+                .and_then(reserve_port)
+                .or_else(allocate_prometheus_port)
+                .unwrap_or_else(|| {
+                    PortReservation::unguarded(PortProtocol::Tcp, DEFAULT_PROMETHEUS_PORT)
+                });
+            debug!(
+                prometheus_port = reservation.port(),
+                "selected prometheus port"
+            );
+            Some(reservation)
+        } else {
+            info!("observability disabled; skipping prometheus and grafana");
+            None
+        };
+        let environment = prepare_environment(
+            &self.descriptors,
+            &self.options,
+            prometheus_port,
+            self.options.observability && prometheus_env.is_some(),
+            timeline,
+        )
+        .await?;
 
         info!(
             compose_file = %environment.compose_path().display(),
@@ -77,14 +98,68 @@ impl DeploymentSetup {
             environment,
         })
     }
+
+    /// Render the cfgsync config and the compose file into a fresh workspace
+    /// without talking to Docker, recording them and whether they rendered
+    /// in `report`. The workspace is kept for inspection; ports allocated at
+    /// deploy time keep their defaults.
+    pub fn dry_run(&self, report: &mut DryRunReport) -> Result<(), ComposeRunnerError> {
+        report.record_check(
+            "compose_topology",
+            ensure_supported_topology(&self.descriptors).map_err(|err| err.to_string()),
+        );
+        let workspace = prepare_workspace_logged(self.options.profile)?;
+        let cfgsync_port = cfgsync_port();
+
+        if self.options.external_cfgsync.is_none() {
+            let cfgsync =
+                configure_cfgsync(&workspace, &self.descriptors, cfgsync_port, &self.options);
+            if cfgsync.is_ok() {
+                report.record_file("cfgsync.yaml", &workspace.cfgsync_path);
+            }
+            report.record_check("compose_cfgsync", cfgsync.map_err(|err| err.to_string()));
+        }
+
+        let prometheus_port = self
+            .options
+            .observability
+            .then_some(DEFAULT_PROMETHEUS_PORT);
+        match write_compose_artifacts(
+            &workspace,
+            &self.descriptors,
+            &self.options,
+            cfgsync_port,
+            prometheus_port,
+            0,
+        ) {
+            Ok((compose_path, descriptor)) => {
+                report.record_file("compose.generated.yml", &compose_path);
+                for node in descriptor.validators().iter().chain(descriptor.executors()) {
+                    report.record_image(node.image());
+                }
+                report.record_check("compose_file", Ok(()));
+            }
+            Err(err) => report.record_check("compose_file", Err(err.to_string())),
+        }
+
+        let root = workspace.workspace.keep();
+        info!(root = %root.display(), "compose dry run rendered");
+        Ok(())
+    }
 }
 
-fn allocate_prometheus_port() -> Option<PortReservation> {
-    reserve_port(DEFAULT_PROMETHEUS_PORT).or_else(|| reserve_port(0))
+/// The default Prometheus port when it is free, any free port otherwise.
+pub(crate) fn allocate_prometheus_port() -> Option<PortReservation> {
+    reserve_port(DEFAULT_PROMETHEUS_PORT).or_else(|| {
+        PortAllocator::global()
+            .reserve(PortProtocol::Tcp)
+            .inspect_err(|err| warn!(error = %err, "failed to reserve a prometheus port"))
+            .ok()
+    })
 }
 
 fn reserve_port(port: u16) -> Option<PortReservation> {
-    let listener = StdTcpListener::bind((Ipv4Addr::LOCALHOST, port)).ok()?;
-    let actual_port = listener.local_addr().ok()?.port();
-    Some(PortReservation::new(actual_port, Some(listener)))
+    PortAllocator::global()
+        .reserve_exact(PortProtocol::Tcp, port)
+        .ok()
 }
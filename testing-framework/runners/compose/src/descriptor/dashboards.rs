@@ -0,0 +1,177 @@
+use serde_json::{Value, json};
+use testing_framework_core::{
+    scenario::{CONSENSUS_PROCESSED_BLOCKS, CONSENSUS_TRANSACTIONS_TOTAL, NODE_LABEL, NodeTarget},
+    topology::generation::GeneratedTopology,
+};
+
+const DASHBOARD_UID: &str = "nomos-scenario";
+/// Uid of the Prometheus datasource provisioned from
+/// `stack/monitoring/grafana/datasources.yaml`.
+const DATASOURCE_UID: &str = "PBFA97CFB590B2093";
+const TX_MEMPOOL_PENDING: &str = "tx_mempool_pending_items";
+const DA_MEMPOOL_PENDING: &str = "da_mempool_pending_items";
+const PANEL_WIDTH: u64 = 12;
+const PANEL_HEIGHT: u64 = 8;
+
+/// Grafana dashboard scoped to the nodes of the running scenario.
+#[derive(Clone, Debug)]
+pub struct ScenarioDashboard {
+    nodes: Vec<String>,
+}
+
+impl ScenarioDashboard {
+    /// Dashboard covering every validator and executor in `topology`.
+    #[must_use]
+    pub fn from_topology(topology: &GeneratedTopology) -> Self {
+        let validators = (0..topology.validators().len()).map(NodeTarget::Validator);
+        let executors = (0..topology.executors().len()).map(NodeTarget::Executor);
+        Self {
+            nodes: validators
+                .chain(executors)
+                .map(|target| target.to_string())
+                .collect(),
+        }
+    }
+
+    /// Render the dashboard JSON consumed by Grafana's file provisioning.
+    #[must_use]
+    pub fn render(&self) -> Value {
+        let selector = format!("{NODE_LABEL}=~\"{}\"", self.nodes.join("|"));
+        let panels = [
+            (
+                "Block rate (blocks/s)",
+                format!("rate({CONSENSUS_PROCESSED_BLOCKS}{{{selector}}}[1m])"),
+            ),
+            (
+                "Processed blocks",
+                format!("{CONSENSUS_PROCESSED_BLOCKS}{{{selector}}}"),
+            ),
+            (
+                "Included transactions (tx/s)",
+                format!("rate({CONSENSUS_TRANSACTIONS_TOTAL}{{{selector}}}[1m])"),
+            ),
+            (
+                "Mempool pending transactions",
+                format!("{TX_MEMPOOL_PENDING}{{{selector}}}"),
+            ),
+            (
+                "DA mempool pending items",
+                format!("{DA_MEMPOOL_PENDING}{{{selector}}}"),
+            ),
+        ];
+
+        let panels = panels
+            .into_iter()
+            .enumerate()
+            .map(|(index, (title, expr))| timeseries_panel(index as u64, title, &expr))
+            .collect::<Vec<_>>();
+
+        json!({
+            "uid": DASHBOARD_UID,
+            "title": "Nomos scenario",
+            "tags": ["nomos", "scenario"],
+            "timezone": "browser",
+            "refresh": "5s",
+            "time": { "from": "now-15m", "to": "now" },
+            "schemaVersion": 39,
+            "editable": true,
+            "panels": panels,
+        })
+    }
+}
+
+fn timeseries_panel(index: u64, title: &str, expr: &str) -> Value {
+    json!({
+        "id": index + 1,
+        "type": "timeseries",
+        "title": title,
+        "datasource": { "type": "prometheus", "uid": DATASOURCE_UID },
+        "gridPos": {
+            "x": (index % 2) * PANEL_WIDTH,
+            "y": (index / 2) * PANEL_HEIGHT,
+            "w": PANEL_WIDTH,
+            "h": PANEL_HEIGHT,
+        },
+        "targets": [{
+            "refId": "A",
+            "expr": expr,
+            "legendFormat": format!("{{{{{NODE_LABEL}}}}}"),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use testing_framework_core::scenario::{Scenario, ScenarioBuilder};
+
+    use super::*;
+
+    const DATASOURCES: &str =
+        include_str!("../../../../assets/stack/monitoring/grafana/datasources.yaml");
+
+    fn dashboard() -> Value {
+        let scenario: Scenario<()> =
+            ScenarioBuilder::topology_with(|t| t.validators(2).executors(1)).build();
+        ScenarioDashboard::from_topology(scenario.topology()).render()
+    }
+
+    #[test]
+    fn panels_query_exported_metrics_of_topology_nodes() {
+        let dashboard = dashboard();
+        let exported = [
+            CONSENSUS_PROCESSED_BLOCKS,
+            CONSENSUS_TRANSACTIONS_TOTAL,
+            TX_MEMPOOL_PENDING,
+            DA_MEMPOOL_PENDING,
+        ];
+        let selector = r#"{job=~"validator-0|validator-1|executor-0"}"#;
+
+        let panels = dashboard["panels"].as_array().expect("panels array");
+        assert!(!panels.is_empty());
+        let mut queried = HashSet::new();
+        for panel in panels {
+            let expr = panel["targets"][0]["expr"].as_str().expect("panel expr");
+            let metric = exported
+                .iter()
+                .find(|metric| expr.contains(&format!("{metric}{selector}")))
+                .unwrap_or_else(|| panic!("{expr} does not select an exported node metric"));
+            queried.insert(*metric);
+        }
+        assert_eq!(queried.len(), exported.len());
+    }
+
+    #[test]
+    fn panels_use_the_provisioned_datasource() {
+        assert!(
+            DATASOURCES
+                .lines()
+                .any(|line| line.trim() == format!("uid: {DATASOURCE_UID}")),
+            "datasources.yaml does not pin uid {DATASOURCE_UID}"
+        );
+        for panel in dashboard()["panels"].as_array().expect("panels array") {
+            assert_eq!(panel["datasource"]["uid"], DATASOURCE_UID);
+        }
+    }
+
+    #[test]
+    fn panels_have_unique_ids_and_do_not_overlap() {
+        let dashboard = dashboard();
+        let panels = dashboard["panels"].as_array().expect("panels array");
+
+        let ids = panels
+            .iter()
+            .map(|panel| panel["id"].as_u64().expect("panel id"))
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), panels.len());
+        let cells = panels
+            .iter()
+            .map(|panel| {
+                let grid = &panel["gridPos"];
+                (grid["x"].as_u64(), grid["y"].as_u64())
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(cells.len(), panels.len());
+    }
+}
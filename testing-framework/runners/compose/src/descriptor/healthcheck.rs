@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use serde::Serialize;
+
+/// Endpoint probed by the healthcheck; it answers once the node's services
+/// are up.
+const PROBE_PATH: &str = "/cryptarchia/info";
+
+/// Docker healthcheck run in every node container.
+///
+/// The probe requests the node's cryptarchia info on its API port from inside
+/// the container and passes on a `200`. A container turns unhealthy after
+/// `retries` consecutive failures; failures during `start_period` do not
+/// count. Late-joining nodes pass while they are held back; releasing one
+/// restarts its container, so the start period applies again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Healthcheck {
+    interval: Duration,
+    timeout: Duration,
+    retries: u32,
+    start_period: Duration,
+}
+
+impl Default for Healthcheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Healthcheck {
+    #[must_use]
+    /// Probe every 5s with a 3s timeout; unhealthy after 6 failures once the
+    /// 60s start period is over.
+    pub const fn new() -> Self {
+        Self {
+            interval: Duration::from_secs(5),
+            timeout: Duration::from_secs(3),
+            retries: 6,
+            start_period: Duration::from_secs(60),
+        }
+    }
+
+    #[must_use]
+    /// Time between two probes.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Time a single probe may take before it counts as failed.
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Consecutive failed probes after which the container is unhealthy.
+    pub const fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    #[must_use]
+    /// Grace period after the container starts during which failed probes
+    /// are not counted.
+    pub const fn with_start_period(mut self, start_period: Duration) -> Self {
+        self.start_period = start_period;
+        self
+    }
+
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    #[must_use]
+    pub const fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    #[must_use]
+    pub const fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    #[must_use]
+    pub const fn start_period(&self) -> Duration {
+        self.start_period
+    }
+}
+
+/// Healthcheck block of a node service in the compose template.
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthcheckTemplate {
+    /// JSON array, which YAML reads as a flow sequence.
+    test: String,
+    interval: String,
+    timeout: String,
+    retries: u32,
+    start_period: String,
+    #[serde(skip)]
+    api_port: u16,
+}
+
+impl HealthcheckTemplate {
+    pub(super) fn new(healthcheck: &Healthcheck, api_port: u16) -> Self {
+        Self {
+            test: probe(api_port, None),
+            interval: compose_duration(healthcheck.interval),
+            timeout: compose_duration(healthcheck.timeout),
+            retries: healthcheck.retries,
+            start_period: compose_duration(healthcheck.start_period),
+            api_port,
+        }
+    }
+
+    /// Pass while `marker` is missing, i.e. while a late-joining node is
+    /// held back; `None` probes unconditionally.
+    pub(super) fn held_until(mut self, marker: Option<&str>) -> Self {
+        self.test = probe(self.api_port, marker);
+        self
+    }
+
+    #[cfg(test)]
+    pub fn test(&self) -> &str {
+        &self.test
+    }
+}
+
+/// The runtime image ships neither curl nor wget, so the request goes
+/// through bash's `/dev/tcp`. The script avoids `$`, which compose would
+/// interpolate.
+fn probe(api_port: u16, marker: Option<&str>) -> String {
+    let request = format!(
+        "exec 3<>/dev/tcp/127.0.0.1/{api_port} \
+         && printf 'GET {PROBE_PATH} HTTP/1.0\\r\\nHost: localhost\\r\\n\\r\\n' >&3 \
+         && head -n 1 <&3 | grep -q ' 200 '"
+    );
+    let script = match marker {
+        Some(marker) => format!("[ ! -e {marker} ] || {{ {request}; }}"),
+        None => request,
+    };
+    serde_json::to_string(&["CMD", "bash", "-c", script.as_str()])
+        .expect("healthcheck command serializes")
+}
+
+fn compose_duration(duration: Duration) -> String {
+    format!("{}ms", duration.as_millis())
+}
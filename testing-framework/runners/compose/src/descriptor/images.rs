@@ -0,0 +1,75 @@
+use std::collections::{BTreeSet, HashMap};
+
+use testing_framework_core::scenario::NodeTarget;
+
+/// Image overrides for mixed-version stacks.
+///
+/// Per-node overrides win over per-role ones; nodes without either use the
+/// image from `resolve_image` (`NOMOS_TESTNET_IMAGE`).
+#[derive(Clone, Debug, Default)]
+pub struct ImageOverrides {
+    validators: Option<String>,
+    executors: Option<String>,
+    nodes: HashMap<NodeTarget, String>,
+}
+
+impl ImageOverrides {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Run every validator from `image`.
+    pub fn with_validators(mut self, image: impl Into<String>) -> Self {
+        self.validators = Some(image.into());
+        self
+    }
+
+    #[must_use]
+    /// Run every executor from `image`.
+    pub fn with_executors(mut self, image: impl Into<String>) -> Self {
+        self.executors = Some(image.into());
+        self
+    }
+
+    #[must_use]
+    /// Run a single node from `image`.
+    pub fn with_node(mut self, target: NodeTarget, image: impl Into<String>) -> Self {
+        self.nodes.insert(target, image.into());
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_none() && self.executors.is_none() && self.nodes.is_empty()
+    }
+
+    /// Override for `target`, if any.
+    #[must_use]
+    pub fn image_for(&self, target: NodeTarget) -> Option<&str> {
+        self.nodes
+            .get(&target)
+            .or(match target {
+                NodeTarget::Validator(_) => self.validators.as_ref(),
+                NodeTarget::Executor(_) => self.executors.as_ref(),
+            })
+            .map(String::as_str)
+    }
+
+    /// Node targets with a per-node override.
+    pub fn node_targets(&self) -> impl Iterator<Item = NodeTarget> + '_ {
+        self.nodes.keys().copied()
+    }
+
+    /// Distinct override images, in a stable order.
+    #[must_use]
+    pub fn images(&self) -> BTreeSet<&str> {
+        self.validators
+            .iter()
+            .chain(self.executors.iter())
+            .chain(self.nodes.values())
+            .map(String::as_str)
+            .collect()
+    }
+}
@@ -1,13 +1,32 @@
 use serde::Serialize;
 use testing_framework_core::{
-    constants::{DEFAULT_CFGSYNC_PORT, DEFAULT_PROMETHEUS_HTTP_PORT, kzg_container_path},
-    topology::generation::{GeneratedNodeConfig, GeneratedTopology},
+    constants::{
+        DEFAULT_CFGSYNC_PORT, DEFAULT_PROMETHEUS_HTTP_PORT, DEFAULT_TEMPO_HTTP_PORT,
+        kzg_container_path,
+    },
+    scenario::{NodeEnv, NodeTarget, POL_PROOF_DEV_MODE_ENV, pol_proof_dev_mode},
+    topology::{
+        config::LateJoin,
+        configs::network::IpStack,
+        generation::{GeneratedNodeConfig, GeneratedTopology, NodeRole},
+    },
 };
 
-use crate::docker::platform::{host_gateway_entry, resolve_image};
+use self::regions::RegionPlan;
+use crate::docker::platform::{
+    host_gateway_entry, image_platform, published_bind_address, resolve_image,
+};
 
+mod dashboards;
+mod healthcheck;
+mod images;
 mod node;
+mod regions;
 
+pub use dashboards::ScenarioDashboard;
+pub use healthcheck::Healthcheck;
+pub use images::ImageOverrides;
+pub(crate) use node::STORAGE_DIR;
 pub use node::{EnvEntry, NodeDescriptor};
 
 /// Errors building a compose descriptor from the topology.
@@ -15,30 +34,117 @@ pub use node::{EnvEntry, NodeDescriptor};
 pub enum DescriptorBuildError {
     #[error("prometheus port is not configured for compose descriptor")]
     MissingPrometheusPort,
+    #[error("image override targets {target}, which is not part of the topology")]
+    UnknownImageTarget { target: NodeTarget },
+    #[error("environment override targets {target}, which is not part of the topology")]
+    UnknownEnvTarget { target: NodeTarget },
+    #[error("network regions need static IPv4 addresses and only support the IPv4 stack")]
+    RegionsRequireIpv4,
+    #[error("{count} network regions declared; the compose runner supports at most {max}")]
+    TooManyRegions { count: usize, max: usize },
+    #[error("network region {region} has {nodes} nodes, more than its /24 can address")]
+    RegionTooLarge { region: String, nodes: usize },
 }
 
 /// Top-level docker-compose descriptor built from a GeneratedTopology.
 #[derive(Clone, Debug, Serialize)]
 pub struct ComposeDescriptor {
-    prometheus: PrometheusTemplate,
-    grafana: GrafanaTemplate,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prometheus: Option<PrometheusTemplate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    grafana: Option<GrafanaTemplate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tempo: Option<TempoTemplate>,
+    network: NetworkTemplate,
     validators: Vec<NodeDescriptor>,
     executors: Vec<NodeDescriptor>,
+    /// Index of the next added validator and executor. Removed nodes keep
+    /// their index, so it is not derived from the node lists.
+    #[serde(skip)]
+    next_index: (usize, usize),
 }
 
 impl ComposeDescriptor {
     /// Start building a descriptor from a generated topology.
     #[must_use]
-    pub const fn builder(topology: &GeneratedTopology) -> ComposeDescriptorBuilder<'_> {
+    pub fn builder(topology: &GeneratedTopology) -> ComposeDescriptorBuilder<'_> {
         ComposeDescriptorBuilder::new(topology)
     }
 
-    #[cfg(test)]
+    /// Append a node of `role` modelled on the role's first node, returning
+    /// the new node, or `None` when the topology has no node of that role.
+    pub(crate) fn add_node(&mut self, role: NodeRole) -> Option<(NodeTarget, &NodeDescriptor)> {
+        let (kind, nodes, next_index) = self.role_nodes(role);
+        let index = *next_index;
+        let node = nodes.first()?.renamed(kind, index);
+        *next_index += 1;
+        nodes.push(node);
+        Some((
+            kind.target(index),
+            nodes.last().expect("node was just added"),
+        ))
+    }
+
+    /// Undo [`Self::add_node`] for `target`. Only the last node of its role
+    /// can be dropped, since later nodes are named after their index;
+    /// returns whether it was.
+    pub(crate) fn drop_added_node(&mut self, target: NodeTarget) -> bool {
+        let (_, nodes, next_index) = self.role_nodes(target.role());
+        let name = target.to_string();
+        if target.index() + 1 != *next_index || nodes.last().is_none_or(|node| node.name() != name)
+        {
+            return false;
+        }
+        nodes.pop();
+        *next_index -= 1;
+        true
+    }
+
+    /// Forget a removed node so a rendered compose file no longer declares
+    /// it. Its index is not handed out again; returns whether the node was
+    /// part of the descriptor.
+    pub(crate) fn remove_node(&mut self, target: NodeTarget) -> bool {
+        let (_, nodes, _) = self.role_nodes(target.role());
+        let name = target.to_string();
+        let count = nodes.len();
+        nodes.retain(|node| node.name() != name);
+        nodes.len() != count
+    }
+
+    /// Services whose traffic towards other network regions is shaped.
+    pub(crate) fn region_shaped_services(&self) -> Vec<String> {
+        self.validators
+            .iter()
+            .chain(&self.executors)
+            .filter(|node| node.has_egress_shaping())
+            .map(|node| node.name().to_owned())
+            .collect()
+    }
+
+    fn role_nodes(
+        &mut self,
+        role: NodeRole,
+    ) -> (ComposeNodeKind, &mut Vec<NodeDescriptor>, &mut usize) {
+        match role {
+            NodeRole::Validator => (
+                ComposeNodeKind::Validator,
+                &mut self.validators,
+                &mut self.next_index.0,
+            ),
+            NodeRole::Executor => (
+                ComposeNodeKind::Executor,
+                &mut self.executors,
+                &mut self.next_index.1,
+            ),
+        }
+    }
+
+    #[must_use]
     pub fn validators(&self) -> &[NodeDescriptor] {
         &self.validators
     }
 
-    #[cfg(test)]
+    #[must_use]
     pub fn executors(&self) -> &[NodeDescriptor] {
         &self.executors
     }
@@ -50,18 +156,32 @@ pub struct ComposeDescriptorBuilder<'a> {
     topology: &'a GeneratedTopology,
     use_kzg_mount: bool,
     cfgsync_port: Option<u16>,
+    cfgsync_url: Option<String>,
     prometheus_port: Option<u16>,
     grafana_port: Option<u16>,
+    observability: bool,
+    dashboards: bool,
+    tracing: bool,
+    images: ImageOverrides,
+    node_env: NodeEnv,
+    healthcheck: Option<Healthcheck>,
 }
 
 impl<'a> ComposeDescriptorBuilder<'a> {
-    const fn new(topology: &'a GeneratedTopology) -> Self {
+    fn new(topology: &'a GeneratedTopology) -> Self {
         Self {
             topology,
             use_kzg_mount: false,
             cfgsync_port: None,
+            cfgsync_url: None,
             prometheus_port: None,
             grafana_port: None,
+            observability: true,
+            dashboards: false,
+            tracing: false,
+            images: ImageOverrides::default(),
+            node_env: NodeEnv::default(),
+            healthcheck: Some(Healthcheck::default()),
         }
     }
 
@@ -79,6 +199,14 @@ impl<'a> ComposeDescriptorBuilder<'a> {
         self
     }
 
+    #[must_use]
+    /// Point nodes at the cfgsync server at `url` instead of the one on the
+    /// cfgsync port of the docker host.
+    pub fn with_cfgsync_url(mut self, url: impl Into<String>) -> Self {
+        self.cfgsync_url = Some(url.into());
+        self
+    }
+
     #[must_use]
     /// Set host port mapping for Prometheus.
     pub const fn with_prometheus_port(mut self, port: u16) -> Self {
@@ -93,14 +221,118 @@ impl<'a> ComposeDescriptorBuilder<'a> {
         self
     }
 
+    #[must_use]
+    /// Run the Prometheus and Grafana services (enabled by default). Without
+    /// them no Prometheus port is needed and dashboards are not mounted.
+    pub const fn with_observability(mut self, enabled: bool) -> Self {
+        self.observability = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Mount the scenario dashboard (see [`ScenarioDashboard`]) into Grafana.
+    /// The caller writes it to `stack/monitoring/grafana/scenario/`.
+    pub const fn with_dashboards(mut self, enabled: bool) -> Self {
+        self.dashboards = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Run a Tempo service collecting the nodes' OTLP traces.
+    pub const fn with_tracing(mut self, enabled: bool) -> Self {
+        self.tracing = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Run every validator from `image` instead of the default image.
+    pub fn with_validators_image(mut self, image: impl Into<String>) -> Self {
+        self.images = self.images.with_validators(image);
+        self
+    }
+
+    #[must_use]
+    /// Run every executor from `image` instead of the default image.
+    pub fn with_executors_image(mut self, image: impl Into<String>) -> Self {
+        self.images = self.images.with_executors(image);
+        self
+    }
+
+    #[must_use]
+    /// Run validator `index` from `image`, e.g. an older release when
+    /// testing upgrade compatibility.
+    pub fn with_validator_image(mut self, index: usize, image: impl Into<String>) -> Self {
+        self.images = self.images.with_node(NodeTarget::Validator(index), image);
+        self
+    }
+
+    #[must_use]
+    /// Run executor `index` from `image`.
+    pub fn with_executor_image(mut self, index: usize, image: impl Into<String>) -> Self {
+        self.images = self.images.with_node(NodeTarget::Executor(index), image);
+        self
+    }
+
+    #[must_use]
+    /// Replace all image overrides at once.
+    pub fn with_image_overrides(mut self, images: ImageOverrides) -> Self {
+        self.images = images;
+        self
+    }
+
+    #[must_use]
+    /// Set `key` to `value` in the environment of a single node, replacing
+    /// the value the template would set.
+    pub fn with_node_env(
+        mut self,
+        role: NodeRole,
+        index: usize,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        let target = match role {
+            NodeRole::Validator => NodeTarget::Validator(index),
+            NodeRole::Executor => NodeTarget::Executor(index),
+        };
+        self.node_env = self.node_env.with_var(target, key, value);
+        self
+    }
+
+    #[must_use]
+    /// Replace all per-node environment overrides at once, e.g. with
+    /// [`Scenario::node_env`](testing_framework_core::scenario::Scenario::node_env).
+    pub fn with_node_env_overrides(mut self, node_env: NodeEnv) -> Self {
+        self.node_env = node_env;
+        self
+    }
+
+    #[must_use]
+    /// Run `healthcheck` in every node container, or none with `None`.
+    /// Defaults to [`Healthcheck::default`].
+    pub const fn with_healthcheck(mut self, healthcheck: Option<Healthcheck>) -> Self {
+        self.healthcheck = healthcheck;
+        self
+    }
+
     /// Finish building the descriptor, erroring if required fields are missing.
     pub fn build(self) -> Result<ComposeDescriptor, DescriptorBuildError> {
         let cfgsync_port = self.cfgsync_port.unwrap_or(DEFAULT_CFGSYNC_PORT);
-        let prometheus_host_port = self
-            .prometheus_port
-            .ok_or(DescriptorBuildError::MissingPrometheusPort)?;
-        let grafana_host_port = self.grafana_port.unwrap_or(0);
+        let cfgsync_url = self
+            .cfgsync_url
+            .clone()
+            .unwrap_or_else(|| format!("http://host.docker.internal:{cfgsync_port}"));
+        let prometheus_host_port = if self.observability {
+            Some(
+                self.prometheus_port
+                    .ok_or(DescriptorBuildError::MissingPrometheusPort)?,
+            )
+        } else {
+            None
+        };
+        self.validate_targets()?;
 
+        let ip_stack = self.topology.config().network_params.ip_stack;
+        let regions = RegionPlan::new(&self.topology.config().regions, ip_stack, cfgsync_port)?;
         let (image, platform) = resolve_image();
         // Prometheus image is x86_64-only on some tags; set platform when on arm hosts.
         let prometheus_platform = match std::env::consts::ARCH {
@@ -111,28 +343,64 @@ impl<'a> ComposeDescriptorBuilder<'a> {
         let validators = build_nodes(
             self.topology.validators(),
             ComposeNodeKind::Validator,
+            self.topology.late_joins(),
             &image,
             platform.as_deref(),
+            &self.images,
+            &self.node_env,
             self.use_kzg_mount,
-            cfgsync_port,
+            &cfgsync_url,
+            ip_stack,
+            regions.as_ref(),
+            self.healthcheck.as_ref(),
         );
 
         let executors = build_nodes(
             self.topology.executors(),
             ComposeNodeKind::Executor,
+            self.topology.late_joins(),
             &image,
             platform.as_deref(),
+            &self.images,
+            &self.node_env,
             self.use_kzg_mount,
-            cfgsync_port,
+            &cfgsync_url,
+            ip_stack,
+            regions.as_ref(),
+            self.healthcheck.as_ref(),
         );
 
         Ok(ComposeDescriptor {
-            prometheus: PrometheusTemplate::new(prometheus_host_port, prometheus_platform),
-            grafana: GrafanaTemplate::new(grafana_host_port),
+            prometheus: prometheus_host_port
+                .map(|port| PrometheusTemplate::new(port, prometheus_platform)),
+            grafana: prometheus_host_port
+                .map(|_| GrafanaTemplate::new(self.grafana_port.unwrap_or(0), self.dashboards)),
+            tempo: self.tracing.then(TempoTemplate::new),
+            network: NetworkTemplate::new(ip_stack, cfgsync_port, regions.as_ref()),
+            next_index: (validators.len(), executors.len()),
             validators,
             executors,
         })
     }
+
+    fn validate_targets(&self) -> Result<(), DescriptorBuildError> {
+        if let Some(target) = self.unknown_target(self.images.node_targets()) {
+            return Err(DescriptorBuildError::UnknownImageTarget { target });
+        }
+        if let Some(target) = self.unknown_target(self.node_env.targets()) {
+            return Err(DescriptorBuildError::UnknownEnvTarget { target });
+        }
+        Ok(())
+    }
+
+    fn unknown_target(&self, mut targets: impl Iterator<Item = NodeTarget>) -> Option<NodeTarget> {
+        let validators = self.topology.validators().len();
+        let executors = self.topology.executors().len();
+        targets.find(|target| match *target {
+            NodeTarget::Validator(index) => index >= validators,
+            NodeTarget::Executor(index) => index >= executors,
+        })
+    }
 }
 
 /// Minimal Prometheus service mapping used in the compose template.
@@ -146,7 +414,11 @@ pub struct PrometheusTemplate {
 impl PrometheusTemplate {
     fn new(port: u16, platform: Option<String>) -> Self {
         Self {
-            host_port: format!("127.0.0.1:{port}:{}", DEFAULT_PROMETHEUS_HTTP_PORT),
+            host_port: format!(
+                "{}:{port}:{}",
+                published_bind_address(),
+                DEFAULT_PROMETHEUS_HTTP_PORT
+            ),
             platform,
         }
     }
@@ -156,16 +428,62 @@ impl PrometheusTemplate {
 #[derive(Clone, Debug, Serialize)]
 pub struct GrafanaTemplate {
     host_port: String,
+    scenario_dashboards: bool,
 }
 
 impl GrafanaTemplate {
-    fn new(port: u16) -> Self {
+    fn new(port: u16, scenario_dashboards: bool) -> Self {
+        let bind = published_bind_address();
         let host_port = match port {
-            0 => "127.0.0.1::3000".to_string(), // docker assigns host port
-            _ => format!("127.0.0.1:{port}:3000"),
+            0 => format!("{bind}::3000"), // docker assigns host port
+            _ => format!("{bind}:{port}:3000"),
         };
 
-        Self { host_port }
+        Self {
+            host_port,
+            scenario_dashboards,
+        }
+    }
+}
+
+/// Tempo service receiving node spans; docker assigns the query port.
+#[derive(Clone, Debug, Serialize)]
+pub struct TempoTemplate {
+    host_port: String,
+}
+
+impl TempoTemplate {
+    fn new() -> Self {
+        Self {
+            host_port: format!("{}::{DEFAULT_TEMPO_HTTP_PORT}", published_bind_address()),
+        }
+    }
+}
+
+/// Default compose network settings; IPv6 is enabled for non-IPv4 stacks,
+/// and an IPv4 subnet is pinned when nodes are placed in network regions.
+#[derive(Clone, Debug, Serialize)]
+pub struct NetworkTemplate {
+    ipv6: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipv6_subnet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipv4_subnet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipv4_ip_range: Option<String>,
+}
+
+impl NetworkTemplate {
+    fn new(ip_stack: IpStack, cfgsync_port: u16, regions: Option<&RegionPlan<'_>>) -> Self {
+        let ipv6 = ip_stack != IpStack::V4;
+        Self {
+            ipv6,
+            // Unique-local /64 keyed by the cfgsync port so parallel stacks
+            // do not claim overlapping pools.
+            ipv6_subnet: ipv6.then(|| format!("fd00:6e6f:{cfgsync_port:x}::/64")),
+            ipv4_subnet: regions.map(RegionPlan::subnet),
+            ipv4_ip_range: regions.map(RegionPlan::ip_range),
+        }
     }
 }
 
@@ -183,6 +501,13 @@ impl ComposeNodeKind {
         }
     }
 
+    const fn target(self, index: usize) -> NodeTarget {
+        match self {
+            Self::Validator => NodeTarget::Validator(index),
+            Self::Executor => NodeTarget::Executor(index),
+        }
+    }
+
     const fn entrypoint(self) -> &'static str {
         match self {
             Self::Validator => "/etc/nomos/scripts/run_nomos_node.sh",
@@ -194,24 +519,53 @@ impl ComposeNodeKind {
 fn build_nodes(
     nodes: &[GeneratedNodeConfig],
     kind: ComposeNodeKind,
-    image: &str,
-    platform: Option<&str>,
+    late_joins: &[LateJoin],
+    default_image: &str,
+    default_platform: Option<&str>,
+    overrides: &ImageOverrides,
+    node_env: &NodeEnv,
     use_kzg_mount: bool,
-    cfgsync_port: u16,
+    cfgsync_url: &str,
+    ip_stack: IpStack,
+    regions: Option<&RegionPlan<'_>>,
+    healthcheck: Option<&Healthcheck>,
 ) -> Vec<NodeDescriptor> {
     nodes
         .iter()
         .enumerate()
         .map(|(index, node)| {
-            NodeDescriptor::from_node(
+            let (image, platform) = match overrides.image_for(kind.target(index)) {
+                Some(image) => (image, image_platform(image)),
+                None => (default_image, default_platform.map(ToOwned::to_owned)),
+            };
+            let descriptor = NodeDescriptor::from_node(
                 kind,
                 index,
                 node,
                 image,
-                platform,
+                platform.as_deref(),
                 use_kzg_mount,
-                cfgsync_port,
-            )
+                cfgsync_url,
+                ip_stack,
+                healthcheck,
+            );
+            let descriptor = match regions.and_then(|plan| plan.node(kind.target(index))) {
+                Some(region) => descriptor.in_region(region),
+                None => descriptor,
+            };
+            let descriptor = if late_joins
+                .iter()
+                .any(|join| join.target == kind.target(index))
+            {
+                descriptor.deferred()
+            } else {
+                descriptor
+            };
+            node_env
+                .vars_for(kind.target(index))
+                .fold(descriptor, |descriptor, (key, value)| {
+                    descriptor.with_env(key, value)
+                })
         })
         .collect()
 }
@@ -228,23 +582,389 @@ fn default_extra_hosts() -> Vec<String> {
     host_gateway_entry().into_iter().collect()
 }
 
-fn base_environment(cfgsync_port: u16) -> Vec<EnvEntry> {
-    let pol_mode = std::env::var("POL_PROOF_DEV_MODE").unwrap_or_else(|_| "true".to_string());
+fn base_environment(cfgsync_url: &str, ip_stack: IpStack) -> Vec<EnvEntry> {
     let rust_log = std::env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
     let nomos_log_level = std::env::var("NOMOS_LOG_LEVEL").unwrap_or_else(|_| "info".to_string());
     let time_backend = std::env::var("NOMOS_TIME_BACKEND").unwrap_or_else(|_| "monotonic".into());
     let kzg_path =
         std::env::var("NOMOS_KZGRS_PARAMS_PATH").unwrap_or_else(|_| kzg_container_path());
     vec![
-        EnvEntry::new("POL_PROOF_DEV_MODE", pol_mode),
+        EnvEntry::new(POL_PROOF_DEV_MODE_ENV, pol_proof_dev_mode().to_string()),
         EnvEntry::new("RUST_LOG", rust_log),
         EnvEntry::new("NOMOS_LOG_LEVEL", nomos_log_level),
         EnvEntry::new("NOMOS_TIME_BACKEND", time_backend),
         EnvEntry::new("NOMOS_KZGRS_PARAMS_PATH", kzg_path),
-        EnvEntry::new(
-            "CFG_SERVER_ADDR",
-            format!("http://host.docker.internal:{cfgsync_port}"),
-        ),
+        EnvEntry::new("CFG_SERVER_ADDR", cfgsync_url),
         EnvEntry::new("OTEL_METRIC_EXPORT_INTERVAL", "5000"),
+        EnvEntry::new("CFG_IP_STACK", ip_stack.as_str()),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use testing_framework_core::{
+        scenario::{NODE_LABEL, ScenarioBuilder, TopologyConfigurator, node_selector},
+        topology::config::NetworkRegions,
+    };
+
+    use super::*;
+
+    fn descriptor(
+        validators: usize,
+        executors: usize,
+        f: impl FnOnce(ComposeDescriptorBuilder<'_>) -> ComposeDescriptorBuilder<'_>,
+    ) -> Result<ComposeDescriptor, DescriptorBuildError> {
+        descriptor_with(|t| t.validators(validators).executors(executors), f)
+    }
+
+    fn descriptor_with(
+        topology: impl FnOnce(TopologyConfigurator<()>) -> TopologyConfigurator<()>,
+        f: impl FnOnce(ComposeDescriptorBuilder<'_>) -> ComposeDescriptorBuilder<'_>,
+    ) -> Result<ComposeDescriptor, DescriptorBuildError> {
+        let scenario = ScenarioBuilder::topology_with(topology).build();
+        let builder = ComposeDescriptor::builder(scenario.topology())
+            .with_prometheus_port(9090)
+            .with_cfgsync_port(4400);
+        f(builder).build()
+    }
+
+    fn env(node: &NodeDescriptor, key: &str) -> Option<String> {
+        node.environment()
+            .iter()
+            .find(|entry| entry.key() == key)
+            .map(|entry| entry.value().to_owned())
+    }
+
+    #[test]
+    fn image_overrides_apply_per_role_and_node() {
+        let descriptor = descriptor(2, 1, |b| {
+            b.with_validators_image("nomos:old")
+                .with_validator_image(1, "nomos:rc")
+        })
+        .expect("descriptor builds");
+
+        let (default_image, _) = resolve_image();
+        let validator_images = descriptor
+            .validators()
+            .iter()
+            .map(NodeDescriptor::image)
+            .collect::<Vec<_>>();
+        assert_eq!(validator_images, ["nomos:old", "nomos:rc"]);
+        assert_eq!(descriptor.executors()[0].image(), default_image);
+    }
+
+    #[test]
+    fn node_metrics_resolve_through_host_identifier() {
+        let compose = descriptor(2, 1, |b| b).expect("descriptor builds");
+
+        let validators = compose
+            .validators()
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeTarget::Validator(index), node));
+        let executors = compose
+            .executors()
+            .iter()
+            .enumerate()
+            .map(|(index, node)| (NodeTarget::Executor(index), node));
+        for (target, node) in validators.chain(executors) {
+            let identifier = env(node, "CFG_HOST_IDENTIFIER").expect("node has an identifier");
+            assert_eq!(
+                node_selector(target),
+                format!("{NODE_LABEL}=\"{identifier}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn image_override_for_missing_node_is_rejected() {
+        let result = descriptor(1, 1, |b| b.with_executor_image(3, "nomos:old"));
+
+        assert!(matches!(
+            result,
+            Err(DescriptorBuildError::UnknownImageTarget {
+                target: NodeTarget::Executor(3)
+            })
+        ));
+    }
+
+    #[test]
+    fn ipv6_stack_enables_compose_ipv6_network() {
+        let descriptor = descriptor_with(
+            |t| t.validators(1).executors(1).ip_stack(IpStack::V6),
+            |b| b,
+        )
+        .expect("descriptor builds");
+
+        assert!(descriptor.network.ipv6);
+        assert_eq!(
+            descriptor.network.ipv6_subnet.as_deref(),
+            Some("fd00:6e6f:1130::/64")
+        );
+        assert_eq!(
+            env(&descriptor.validators()[0], "CFG_IP_STACK").as_deref(),
+            Some("v6")
+        );
+    }
+
+    #[test]
+    fn cfgsync_url_overrides_node_server_addr() {
+        let local = descriptor(1, 1, |b| b).expect("descriptor builds");
+        assert_eq!(
+            env(&local.executors()[0], "CFG_SERVER_ADDR").as_deref(),
+            Some("http://host.docker.internal:4400")
+        );
+
+        let external = descriptor(1, 1, |b| b.with_cfgsync_url("http://cfgsync.test:4401"))
+            .expect("descriptor builds");
+        assert_eq!(
+            env(&external.executors()[0], "CFG_SERVER_ADDR").as_deref(),
+            Some("http://cfgsync.test:4401")
+        );
+    }
+
+    #[test]
+    fn late_joining_node_waits_for_join_marker() {
+        let descriptor = descriptor_with(
+            |t| {
+                t.validators(2)
+                    .executors(1)
+                    .late_join_validator(1, Duration::from_secs(30))
+            },
+            |b| b,
+        )
+        .expect("descriptor builds");
+
+        let has_marker = |node: &NodeDescriptor| env(node, "NOMOS_JOIN_MARKER").is_some();
+        assert!(!has_marker(&descriptor.validators()[0]));
+        assert!(has_marker(&descriptor.validators()[1]));
+        assert!(!has_marker(&descriptor.executors()[0]));
+    }
+
+    #[test]
+    fn healthcheck_probes_api_port_and_passes_while_held_back() {
+        let compose = descriptor_with(
+            |t| {
+                t.validators(2)
+                    .executors(1)
+                    .late_join_validator(1, Duration::from_secs(30))
+            },
+            |b| b.with_healthcheck(Some(Healthcheck::new().with_retries(3))),
+        )
+        .expect("descriptor builds");
+
+        let validator = &compose.validators()[0];
+        let healthcheck = validator.healthcheck().expect("healthcheck rendered");
+        let (api_port, _) = validator.http_ports();
+        let test: Vec<String> =
+            serde_json::from_str(healthcheck.test()).expect("test is a JSON array");
+        assert_eq!(test[..3], ["CMD", "bash", "-c"]);
+        assert!(test[3].starts_with(&format!("exec 3<>/dev/tcp/127.0.0.1/{api_port} ")));
+        assert!(!test[3].contains('$'));
+
+        let late = compose.validators()[1]
+            .healthcheck()
+            .expect("healthcheck rendered");
+        assert!(late.test().contains("[ ! -e /tmp/nomos-join ] ||"));
+
+        let without = descriptor(2, 1, |b| b.with_healthcheck(None)).expect("descriptor builds");
+        assert!(without.executors()[0].healthcheck().is_none());
+    }
+
+    #[test]
+    fn node_env_overrides_apply_to_single_node() {
+        let descriptor = descriptor(2, 1, |b| {
+            b.with_node_env(NodeRole::Validator, 1, "NOMOS_EXPERIMENTAL_SYNC", "1")
+                .with_node_env(NodeRole::Validator, 1, "RUST_LOG", "debug")
+        })
+        .expect("descriptor builds");
+
+        let env_values = |node: &NodeDescriptor, key: &str| {
+            node.environment()
+                .iter()
+                .filter(|entry| entry.key() == key)
+                .map(|entry| entry.value().to_owned())
+                .collect::<Vec<_>>()
+        };
+        let validators = descriptor.validators();
+        assert_eq!(env_values(&validators[1], "NOMOS_EXPERIMENTAL_SYNC"), ["1"]);
+        assert_eq!(env_values(&validators[1], "RUST_LOG"), ["debug"]);
+        assert!(env_values(&validators[0], "NOMOS_EXPERIMENTAL_SYNC").is_empty());
+        assert!(env_values(&descriptor.executors()[0], "NOMOS_EXPERIMENTAL_SYNC").is_empty());
+    }
+
+    #[test]
+    fn added_node_takes_next_index_without_join_marker() {
+        let mut descriptor = descriptor_with(
+            |t| {
+                t.validators(2)
+                    .executors(1)
+                    .late_join_validator(0, Duration::from_secs(30))
+            },
+            |b| b,
+        )
+        .expect("descriptor builds");
+
+        let (target, node) = descriptor
+            .add_node(NodeRole::Validator)
+            .expect("validator template exists");
+        assert_eq!(target, NodeTarget::Validator(2));
+        assert_eq!(node.name(), "validator-2");
+        assert_eq!(
+            env(&node, "CFG_HOST_IDENTIFIER").as_deref(),
+            Some("validator-2")
+        );
+        assert_eq!(env(&node, "NOMOS_JOIN_MARKER"), None);
+        assert!(
+            node.volumes()
+                .iter()
+                .any(|volume| volume == "./storage/validator-2:/state")
+        );
+        assert!(
+            !node
+                .volumes()
+                .iter()
+                .any(|volume| volume.starts_with("./storage/validator-0"))
+        );
+        assert_eq!(descriptor.validators().len(), 3);
+    }
+
+    #[test]
+    fn only_the_last_added_node_can_be_dropped() {
+        let mut descriptor = descriptor(1, 1, |b| b).expect("descriptor builds");
+
+        let (first, _) = descriptor
+            .add_node(NodeRole::Validator)
+            .expect("validator template exists");
+        let (second, _) = descriptor
+            .add_node(NodeRole::Validator)
+            .expect("validator template exists");
+
+        assert!(!descriptor.drop_added_node(first));
+        assert!(descriptor.drop_added_node(second));
+        assert!(descriptor.drop_added_node(first));
+        assert_eq!(descriptor.validators().len(), 1);
+        assert_eq!(descriptor.executors().len(), 1);
+    }
+
+    #[test]
+    fn removed_node_index_is_not_reused() {
+        let mut descriptor = descriptor(3, 1, |b| b).expect("descriptor builds");
+
+        assert!(descriptor.remove_node(NodeTarget::Validator(2)));
+        assert!(!descriptor.remove_node(NodeTarget::Validator(2)));
+        assert!(!descriptor.drop_added_node(NodeTarget::Validator(1)));
+        assert_eq!(descriptor.validators().len(), 2);
+
+        let (target, node) = descriptor
+            .add_node(NodeRole::Validator)
+            .expect("validator template exists");
+        assert_eq!(target, NodeTarget::Validator(3));
+        assert_eq!(node.name(), "validator-3");
+
+        assert!(descriptor.remove_node(NodeTarget::Validator(0)));
+        let (target, _) = descriptor
+            .add_node(NodeRole::Validator)
+            .expect("remaining validators serve as template");
+        assert_eq!(target, NodeTarget::Validator(4));
+        let names = descriptor
+            .validators()
+            .iter()
+            .map(NodeDescriptor::name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["validator-1", "validator-3", "validator-4"]);
+    }
+
+    #[test]
+    fn each_node_mounts_its_own_storage() {
+        let descriptor = descriptor(2, 1, |b| b).expect("descriptor builds");
+
+        let nodes = descriptor.validators().iter().chain(descriptor.executors());
+        for node in nodes {
+            let expected = format!("./storage/{}:/state", node.name());
+            assert!(node.volumes().contains(&expected), "{}", node.name());
+        }
+    }
+
+    #[test]
+    fn disabled_observability_skips_monitoring_services() {
+        let scenario = ScenarioBuilder::topology_with(|t| t.validators(1).executors(1)).build();
+        let descriptor = ComposeDescriptor::builder(scenario.topology())
+            .with_observability(false)
+            .with_dashboards(true)
+            .build()
+            .expect("descriptor builds without a prometheus port");
+
+        assert!(descriptor.prometheus.is_none());
+        assert!(descriptor.grafana.is_none());
+        assert_eq!(descriptor.validators().len(), 1);
+    }
+
+    #[test]
+    fn regions_pin_addresses_and_shape_cross_region_traffic() {
+        let regions = NetworkRegions::new()
+            .region("eu", [NodeTarget::Validator(0), NodeTarget::Executor(0)])
+            .region("us", [NodeTarget::Validator(1)])
+            .latency("eu", "us", Duration::from_millis(80));
+        let descriptor = descriptor_with(|t| t.validators(3).executors(1).regions(regions), |b| b)
+            .expect("descriptor builds");
+
+        assert_eq!(
+            descriptor.network.ipv4_subnet.as_deref(),
+            Some("10.50.0.0/16")
+        );
+        let validators = descriptor.validators();
+        let executor = &descriptor.executors()[0];
+        assert_eq!(validators[0].ipv4_address(), Some("10.50.1.10"));
+        assert_eq!(executor.ipv4_address(), Some("10.50.1.11"));
+        assert_eq!(validators[1].ipv4_address(), Some("10.50.2.10"));
+        assert_eq!(validators[2].ipv4_address(), None);
+
+        let shaping = |node: &NodeDescriptor| env(node, "NOMOS_EGRESS_SHAPING");
+        let eu = shaping(&validators[0]).expect("eu node is shaped");
+        assert!(eu.contains("netem delay 80ms"), "{eu}");
+        assert!(eu.contains("match ip dst 10.50.2.0/24"), "{eu}");
+        let us = shaping(&validators[1]).expect("us node is shaped");
+        assert!(us.contains("match ip dst 10.50.1.0/24"), "{us}");
+        assert_eq!(shaping(&validators[2]), None);
+        assert_eq!(
+            descriptor.region_shaped_services(),
+            ["validator-0", "validator-1", "executor-0"]
+        );
+    }
+
+    #[test]
+    fn regions_reject_ipv6_stack() {
+        let regions = NetworkRegions::new().region("eu", [NodeTarget::Validator(0)]);
+        let result = descriptor_with(
+            |t| {
+                t.validators(1)
+                    .executors(1)
+                    .ip_stack(IpStack::V6)
+                    .regions(regions)
+            },
+            |b| b,
+        );
+
+        assert!(matches!(
+            result,
+            Err(DescriptorBuildError::RegionsRequireIpv4)
+        ));
+    }
+
+    #[test]
+    fn node_env_for_missing_node_is_rejected() {
+        let result = descriptor(1, 1, |b| {
+            b.with_node_env(NodeRole::Executor, 3, "NOMOS_EXPERIMENTAL_SYNC", "1")
+        });
+
+        assert!(matches!(
+            result,
+            Err(DescriptorBuildError::UnknownEnvTarget {
+                target: NodeTarget::Executor(3)
+            })
+        ));
+    }
+}
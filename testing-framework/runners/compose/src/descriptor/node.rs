@@ -1,7 +1,14 @@
 use serde::Serialize;
-use testing_framework_core::topology::generation::GeneratedNodeConfig;
+use testing_framework_core::{
+    constants::{JOIN_MARKER_ENV, JOIN_MARKER_PATH, NODE_STATE_CONTAINER_DIR},
+    topology::{configs::network::IpStack, generation::GeneratedNodeConfig},
+};
 
-use super::{ComposeNodeKind, base_environment, base_volumes, default_extra_hosts};
+use super::{
+    ComposeNodeKind, base_environment, base_volumes, default_extra_hosts,
+    healthcheck::{Healthcheck, HealthcheckTemplate},
+    regions::{EGRESS_SHAPING_ENV, RegionNode},
+};
 
 /// Describes a validator or executor container in the compose stack.
 #[derive(Clone, Debug, Serialize)]
@@ -9,12 +16,21 @@ pub struct NodeDescriptor {
     name: String,
     image: String,
     entrypoint: String,
+    working_dir: String,
     volumes: Vec<String>,
     extra_hosts: Vec<String>,
     ports: Vec<String>,
     environment: Vec<EnvEntry>,
     #[serde(skip_serializing_if = "Option::is_none")]
     platform: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ipv4_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    healthcheck: Option<HealthcheckTemplate>,
+    #[serde(skip)]
+    api_port: u16,
+    #[serde(skip)]
+    testing_http_port: u16,
 }
 
 /// Environment variable entry for docker-compose templating.
@@ -51,10 +67,13 @@ impl NodeDescriptor {
         image: &str,
         platform: Option<&str>,
         use_kzg_mount: bool,
-        cfgsync_port: u16,
+        cfgsync_url: &str,
+        ip_stack: IpStack,
+        healthcheck: Option<&Healthcheck>,
     ) -> Self {
-        let mut environment = base_environment(cfgsync_port);
+        let mut environment = base_environment(cfgsync_url, ip_stack);
         let identifier = kind.instance_name(index);
+        let volumes = node_volumes(&identifier, use_kzg_mount);
         environment.extend([
             EnvEntry::new(
                 "CFG_NETWORK_PORT",
@@ -77,25 +96,111 @@ impl NodeDescriptor {
             EnvEntry::new("CFG_HOST_IDENTIFIER", identifier),
         ]);
 
-        let ports = vec![
-            node.general.api_config.address.port().to_string(),
-            node.general
-                .api_config
-                .testing_http_address
-                .port()
-                .to_string(),
-        ];
+        let api_port = node.general.api_config.address.port();
+        let testing_http_port = node.general.api_config.testing_http_address.port();
+        let ports = vec![api_port.to_string(), testing_http_port.to_string()];
 
         Self {
             name: kind.instance_name(index),
             image: image.to_owned(),
             entrypoint: kind.entrypoint().to_owned(),
-            volumes: base_volumes(use_kzg_mount),
+            working_dir: NODE_STATE_CONTAINER_DIR.to_owned(),
+            volumes,
             extra_hosts: default_extra_hosts(),
             ports,
             environment,
             platform: platform.map(ToOwned::to_owned),
+            ipv4_address: None,
+            healthcheck: healthcheck
+                .map(|healthcheck| HealthcheckTemplate::new(healthcheck, api_port)),
+            api_port,
+            testing_http_port,
+        }
+    }
+
+    /// Copy of this node under the identity of `kind` node `index`, for nodes
+    /// added while the stack runs. Container ports are kept since every
+    /// container has its own address. Added nodes are outside every network
+    /// region.
+    pub(crate) fn renamed(&self, kind: ComposeNodeKind, index: usize) -> Self {
+        let name = kind.instance_name(index);
+        let mut node = self.clone().with_env("CFG_HOST_IDENTIFIER", &name);
+        node.environment
+            .retain(|entry| entry.key != JOIN_MARKER_ENV && entry.key != EGRESS_SHAPING_ENV);
+        node.ipv4_address = None;
+        node.healthcheck = node
+            .healthcheck
+            .map(|healthcheck| healthcheck.held_until(None));
+        let own_storage = storage_volume(&node.name);
+        let storage = storage_volume(&name);
+        for volume in &mut node.volumes {
+            if *volume == own_storage {
+                volume.clone_from(&storage);
+            }
         }
+        node.name = name;
+        node
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Whether the node shapes its traffic towards other network regions.
+    pub(crate) fn has_egress_shaping(&self) -> bool {
+        self.environment
+            .iter()
+            .any(|entry| entry.key == EGRESS_SHAPING_ENV)
+    }
+
+    /// Whether the container runs a healthcheck.
+    pub(crate) const fn has_healthcheck(&self) -> bool {
+        self.healthcheck.is_some()
+    }
+
+    /// Container ports of the API and testing HTTP servers.
+    pub(crate) const fn http_ports(&self) -> (u16, u16) {
+        (self.api_port, self.testing_http_port)
+    }
+
+    /// Hold the node process back until the join marker is created; used
+    /// for late-joining nodes.
+    pub(crate) fn deferred(mut self) -> Self {
+        self.environment
+            .push(EnvEntry::new(JOIN_MARKER_ENV, JOIN_MARKER_PATH));
+        self.healthcheck = self
+            .healthcheck
+            .map(|healthcheck| healthcheck.held_until(Some(JOIN_MARKER_PATH)));
+        self
+    }
+
+    /// Pin the node to its region address and shape its traffic towards
+    /// other regions on start.
+    pub(super) fn in_region(mut self, region: RegionNode) -> Self {
+        self.ipv4_address = Some(region.ipv4_address);
+        if let Some(commands) = region.egress_shaping {
+            self.environment
+                .push(EnvEntry::new(EGRESS_SHAPING_ENV, commands));
+        }
+        self
+    }
+
+    /// Set `key` to `value`, replacing an entry the template set.
+    pub(crate) fn with_env(mut self, key: &str, value: &str) -> Self {
+        match self.environment.iter_mut().find(|entry| entry.key == key) {
+            Some(entry) => entry.value = value.to_owned(),
+            None => self.environment.push(EnvEntry::new(key, value)),
+        }
+        self
+    }
+
+    #[cfg(test)]
+    pub fn volumes(&self) -> &[String] {
+        &self.volumes
+    }
+
+    pub(crate) fn image(&self) -> &str {
+        &self.image
     }
 
     #[cfg(test)]
@@ -103,8 +208,32 @@ impl NodeDescriptor {
         &self.ports
     }
 
+    #[cfg(test)]
+    pub fn ipv4_address(&self) -> Option<&str> {
+        self.ipv4_address.as_deref()
+    }
+
+    #[cfg(test)]
+    pub const fn healthcheck(&self) -> Option<&HealthcheckTemplate> {
+        self.healthcheck.as_ref()
+    }
+
     #[cfg(test)]
     pub fn environment(&self) -> &[EnvEntry] {
         &self.environment
     }
 }
+
+/// Workspace directory holding one data directory per node, bind-mounted
+/// as the container's working directory.
+pub(crate) const STORAGE_DIR: &str = "storage";
+
+fn storage_volume(name: &str) -> String {
+    format!("./{STORAGE_DIR}/{name}:{NODE_STATE_CONTAINER_DIR}")
+}
+
+fn node_volumes(name: &str, use_kzg_mount: bool) -> Vec<String> {
+    let mut volumes = base_volumes(use_kzg_mount);
+    volumes.push(storage_volume(name));
+    volumes
+}
@@ -0,0 +1,150 @@
+use testing_framework_core::{
+    scenario::NodeTarget,
+    topology::{config::NetworkRegions, configs::network::IpStack},
+};
+
+use super::DescriptorBuildError;
+use crate::docker::control::{SHAPED_INTERFACE, netem_args};
+
+/// Environment variable holding the `tc` commands the entrypoint runs to
+/// shape a node's traffic towards other regions.
+pub(crate) const EGRESS_SHAPING_ENV: &str = "NOMOS_EGRESS_SHAPING";
+
+/// `tc prio` supports at most 16 bands: one for unshaped traffic plus one
+/// per remote region.
+const MAX_REGIONS: usize = 16;
+/// Host part of the first node address in a region's /24.
+const FIRST_HOST: usize = 10;
+
+/// Static IPv4 addressing and egress shaping for the regions of a topology.
+///
+/// Region `k` owns `10.<prefix>.<k + 1>.0/24` so a node can match traffic to
+/// a remote region by destination subnet; containers outside every region
+/// get a dynamic address from `10.<prefix>.0.0/24`.
+pub(super) struct RegionPlan<'a> {
+    regions: &'a NetworkRegions,
+    prefix: u8,
+}
+
+/// Address and shaping commands of a node placed in a region.
+pub(super) struct RegionNode {
+    pub ipv4_address: String,
+    pub egress_shaping: Option<String>,
+}
+
+impl<'a> RegionPlan<'a> {
+    /// Plan addressing for `regions`, or `None` when no region is declared.
+    pub fn new(
+        regions: &'a NetworkRegions,
+        ip_stack: IpStack,
+        cfgsync_port: u16,
+    ) -> Result<Option<Self>, DescriptorBuildError> {
+        if regions.is_empty() {
+            return Ok(None);
+        }
+        if ip_stack != IpStack::V4 {
+            return Err(DescriptorBuildError::RegionsRequireIpv4);
+        }
+        if regions.regions.len() > MAX_REGIONS {
+            return Err(DescriptorBuildError::TooManyRegions {
+                count: regions.regions.len(),
+                max: MAX_REGIONS,
+            });
+        }
+        if let Some(region) = regions
+            .regions
+            .iter()
+            .find(|region| FIRST_HOST + region.nodes.len() > 255)
+        {
+            return Err(DescriptorBuildError::RegionTooLarge {
+                region: region.name.clone(),
+                nodes: region.nodes.len(),
+            });
+        }
+
+        // Keyed by the cfgsync port so parallel stacks do not claim
+        // overlapping pools.
+        let prefix = 50 + (cfgsync_port % 200) as u8;
+        Ok(Some(Self { regions, prefix }))
+    }
+
+    /// Subnet of the compose network.
+    pub fn subnet(&self) -> String {
+        format!("10.{}.0.0/16", self.prefix)
+    }
+
+    /// Pool docker assigns addresses from to containers outside any region.
+    pub fn ip_range(&self) -> String {
+        format!("10.{}.0.0/24", self.prefix)
+    }
+
+    /// Address and shaping of `target`, or `None` when it is in no region.
+    pub fn node(&self, target: NodeTarget) -> Option<RegionNode> {
+        let (region_index, region) = self
+            .regions
+            .regions
+            .iter()
+            .enumerate()
+            .find(|(_, region)| region.nodes.contains(&target))?;
+        let host = region
+            .nodes
+            .iter()
+            .position(|node| *node == target)
+            .expect("target is in region");
+
+        Some(RegionNode {
+            ipv4_address: format!(
+                "10.{}.{}.{}",
+                self.prefix,
+                region_index + 1,
+                FIRST_HOST + host
+            ),
+            egress_shaping: self.egress_shaping(region_index),
+        })
+    }
+
+    fn region_subnet(&self, region_index: usize) -> String {
+        format!("10.{}.{}.0/24", self.prefix, region_index + 1)
+    }
+
+    /// `tc` commands delaying traffic from region `from` to each linked
+    /// region: a prio qdisc sends everything to band 1 by default, and one
+    /// filter per linked region steers its subnet to a band with netem.
+    fn egress_shaping(&self, from: usize) -> Option<String> {
+        let name = &self.regions.regions[from].name;
+        let links = self
+            .regions
+            .regions
+            .iter()
+            .enumerate()
+            .filter_map(|(index, region)| {
+                self.regions
+                    .shaping_between(name, &region.name)
+                    .filter(|shaping| index != from && !shaping.is_noop())
+                    .map(|shaping| (index, shaping))
+            })
+            .collect::<Vec<_>>();
+        if links.is_empty() {
+            return None;
+        }
+
+        let mut commands = vec![format!(
+            "tc qdisc replace dev {SHAPED_INTERFACE} root handle 1: prio bands {} priomap{}",
+            links.len() + 1,
+            " 0".repeat(16)
+        )];
+        for (band, (region_index, shaping)) in links.iter().enumerate() {
+            let class = band + 2;
+            commands.push(format!(
+                "tc qdisc add dev {SHAPED_INTERFACE} parent 1:{class:x} handle {:x}: netem {}",
+                0x10 + band,
+                netem_args(shaping)
+            ));
+            commands.push(format!(
+                "tc filter add dev {SHAPED_INTERFACE} parent 1: protocol ip prio 1 u32 match ip dst {} flowid 1:{class:x}",
+                self.region_subnet(*region_index)
+            ));
+        }
+        Some(commands.join(" && "))
+    }
+}
@@ -1,10 +1,12 @@
-use std::{io, path::Path, process, time::Duration};
+use std::{fs, io, os::unix::fs::MetadataExt as _, path::Path, process, time::Duration};
 
 use testing_framework_core::adjust_timeout;
 use tokio::{process::Command, time::timeout};
 use tracing::{debug, info, warn};
 
-const COMPOSE_UP_TIMEOUT: Duration = Duration::from_secs(120);
+const COMPOSE_DOWN_TIMEOUT: Duration = Duration::from_secs(120);
+const COMPOSE_CONFIG_TIMEOUT: Duration = Duration::from_secs(30);
+const STORAGE_RECLAIM_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Errors running docker compose commands.
 #[derive(Debug, thiserror::Error)]
@@ -41,11 +43,13 @@ pub async fn run_docker_command(
     }
 }
 
-/// Runs `docker compose up -d` for the generated stack.
+/// Runs `docker compose up -d` for the generated stack, giving up after
+/// `timeout_duration` (scaled by `adjust_timeout`).
 pub async fn compose_up(
     compose_path: &Path,
     project_name: &str,
     root: &Path,
+    timeout_duration: Duration,
 ) -> Result<(), ComposeCommandError> {
     let mut cmd = Command::new("docker");
     cmd.arg("compose")
@@ -64,7 +68,127 @@ pub async fn compose_up(
         "running docker compose up"
     );
 
-    run_compose_command(cmd, adjust_timeout(COMPOSE_UP_TIMEOUT), "docker compose up").await
+    run_compose_command(cmd, adjust_timeout(timeout_duration), "docker compose up").await
+}
+
+/// Runs `docker compose up -d --no-deps` for `services` of the generated
+/// stack only, leaving the others as they are.
+pub async fn compose_up_services(
+    compose_path: &Path,
+    project_name: &str,
+    root: &Path,
+    services: &[String],
+    timeout_duration: Duration,
+) -> Result<(), ComposeCommandError> {
+    let mut cmd = Command::new("docker");
+    cmd.arg("compose")
+        .arg("-f")
+        .arg(compose_path)
+        .arg("-p")
+        .arg(project_name)
+        .arg("up")
+        .arg("-d")
+        .arg("--no-deps")
+        .args(services)
+        .current_dir(root);
+
+    info!(
+        project = project_name,
+        ?services,
+        "running docker compose up for services"
+    );
+
+    run_compose_command(cmd, adjust_timeout(timeout_duration), "docker compose up").await
+}
+
+/// Lists the services of the generated stack (`docker compose config
+/// --services`).
+pub async fn compose_services(
+    compose_path: &Path,
+    project_name: &str,
+    root: &Path,
+) -> Result<Vec<String>, ComposeCommandError> {
+    let output = compose_query(
+        compose_path,
+        project_name,
+        root,
+        &["config", "--services"],
+        "docker compose config",
+    )
+    .await?;
+    Ok(output
+        .lines()
+        .map(str::trim)
+        .filter(|service| !service.is_empty())
+        .map(str::to_owned)
+        .collect())
+}
+
+/// Health status (`starting`, `healthy` or `unhealthy`) of every running
+/// container of the stack that has a healthcheck, keyed by service.
+pub async fn compose_health(
+    compose_path: &Path,
+    project_name: &str,
+    root: &Path,
+) -> Result<Vec<(String, String)>, ComposeCommandError> {
+    let output = compose_query(
+        compose_path,
+        project_name,
+        root,
+        &["ps", "--format", "{{.Service}} {{.Health}}"],
+        "docker compose ps",
+    )
+    .await?;
+    Ok(parse_health(&output))
+}
+
+/// Parse `docker compose ps` lines of `<service> <health>`; containers
+/// without a healthcheck report an empty health and are skipped.
+fn parse_health(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().split_once(' '))
+        .map(|(service, health)| (service, health.trim()))
+        .filter(|(_, health)| !health.is_empty())
+        .map(|(service, health)| (service.to_owned(), health.to_owned()))
+        .collect()
+}
+
+async fn compose_query(
+    compose_path: &Path,
+    project_name: &str,
+    root: &Path,
+    args: &[&str],
+    description: &str,
+) -> Result<String, ComposeCommandError> {
+    let mut cmd = Command::new("docker");
+    cmd.arg("compose")
+        .arg("-f")
+        .arg(compose_path)
+        .arg("-p")
+        .arg(project_name)
+        .args(args)
+        .current_dir(root);
+
+    let timeout_duration = adjust_timeout(COMPOSE_CONFIG_TIMEOUT);
+    let output = timeout(timeout_duration, cmd.output())
+        .await
+        .map_err(|_| ComposeCommandError::Timeout {
+            command: description.to_owned(),
+            timeout: timeout_duration,
+        })?
+        .map_err(|source| ComposeCommandError::Spawn {
+            command: description.to_owned(),
+            source,
+        })?;
+    if !output.status.success() {
+        return Err(ComposeCommandError::Failed {
+            command: description.to_owned(),
+            status: output.status,
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 /// Runs `docker compose down --volumes` for the generated stack.
@@ -92,12 +216,42 @@ pub async fn compose_down(
 
     run_compose_command(
         cmd,
-        adjust_timeout(COMPOSE_UP_TIMEOUT),
+        adjust_timeout(COMPOSE_DOWN_TIMEOUT),
         "docker compose down",
     )
     .await
 }
 
+/// Hands the node data directories below `storage` back to the owner of
+/// `storage`. The nodes run as root, so without this the host user cannot
+/// delete what they wrote and the run artifacts directory is leaked.
+pub async fn reclaim_storage(image: &str, storage: &Path) -> Result<(), ComposeCommandError> {
+    let Ok(metadata) = fs::metadata(storage) else {
+        return Ok(());
+    };
+    let owner = format!("{}:{}", metadata.uid(), metadata.gid());
+
+    let mut cmd = Command::new("docker");
+    cmd.arg("run")
+        .arg("--rm")
+        .arg("--entrypoint")
+        .arg("chown")
+        .arg("-v")
+        .arg(format!("{}:/storage", storage.display()))
+        .arg(image)
+        .arg("-R")
+        .arg(&owner)
+        .arg("/storage");
+
+    debug!(storage = %storage.display(), owner, "reclaiming node storage");
+    run_docker_command(
+        cmd,
+        adjust_timeout(STORAGE_RECLAIM_TIMEOUT),
+        "docker run chown",
+    )
+    .await
+}
+
 /// Dump docker compose logs to stderr for debugging failures.
 pub async fn dump_compose_logs(compose_file: &Path, project: &str, root: &Path) {
     let mut cmd = Command::new("docker");
@@ -171,3 +325,24 @@ fn handle_compose_status(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_skips_containers_without_healthcheck() {
+        let output =
+            "validator-0 healthy\nexecutor-0 starting\ncfgsync \n\nvalidator-1 unhealthy\n";
+
+        assert_eq!(
+            parse_health(output),
+            vec![
+                ("validator-0".to_owned(), "healthy".to_owned()),
+                ("executor-0".to_owned(), "starting".to_owned()),
+                ("validator-1".to_owned(), "unhealthy".to_owned()),
+            ]
+        );
+        assert!(parse_health("").is_empty());
+    }
+}
@@ -1,15 +1,128 @@
-use std::path::{Path, PathBuf};
+use std::{
+    net::IpAddr,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 
-use testing_framework_core::scenario::{DynError, NodeControlHandle};
-use tokio::process::Command;
-use tracing::info;
+use reqwest::Url;
+use testing_framework_core::{
+    adjust_timeout,
+    constants::JOIN_MARKER_PATH,
+    nodes::ApiClient,
+    scenario::{
+        AddedNode, DynError, ExecOutput, NetworkShaping, NodeControlHandle, NodeExit, NodeTarget,
+    },
+    topology::generation::NodeRole,
+};
+use tokio::{process::Command, time::timeout};
+use tracing::{debug, info, warn};
 
-use crate::{docker::commands::run_docker_command, errors::ComposeRunnerError};
+use crate::{
+    descriptor::ComposeDescriptor,
+    docker::commands::{ComposeCommandError, run_docker_command},
+    errors::ComposeRunnerError,
+    infrastructure::{
+        environment::resolve_service_port,
+        template::{ComposeTemplate, write_compose_file_with},
+    },
+};
+
+const EXEC_TIMEOUT: Duration = Duration::from_secs(30);
+const SERVICE_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+pub(crate) const SHAPED_INTERFACE: &str = "eth0";
+/// How often a terminating service is checked for having exited.
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 pub async fn restart_compose_service(
     compose_file: &Path,
     project_name: &str,
     service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "restarting compose service");
+    run_service_command(compose_file, project_name, &["restart"], service).await
+}
+
+/// Stop a compose service container, keeping it (and its state) for a later
+/// [`start_compose_service`].
+pub async fn stop_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "stopping compose service");
+    run_service_command(compose_file, project_name, &["stop"], service).await
+}
+
+/// Send `SIGKILL` to a compose service container. The container stays down
+/// (the restart policy ignores explicit kills) until
+/// [`start_compose_service`].
+pub async fn kill_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "killing compose service");
+    run_service_command(
+        compose_file,
+        project_name,
+        &["kill", "--signal", "SIGKILL"],
+        service,
+    )
+    .await
+}
+
+/// Stop and remove a compose service container.
+pub async fn remove_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "removing compose service");
+    run_service_command(
+        compose_file,
+        project_name,
+        &["rm", "--stop", "--force"],
+        service,
+    )
+    .await
+}
+
+/// Freeze a compose service container with `docker compose pause`.
+pub async fn pause_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "pausing compose service");
+    run_service_command(compose_file, project_name, &["pause"], service).await
+}
+
+/// Resume a compose service container frozen by [`pause_compose_service`].
+pub async fn unpause_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "unpausing compose service");
+    run_service_command(compose_file, project_name, &["unpause"], service).await
+}
+
+/// Start a previously stopped compose service container.
+pub async fn start_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+) -> Result<(), ComposeRunnerError> {
+    info!(service, project = project_name, compose_file = %compose_file.display(), "starting compose service");
+    run_service_command(compose_file, project_name, &["start"], service).await
+}
+
+async fn run_service_command(
+    compose_file: &Path,
+    project_name: &str,
+    action: &[&str],
+    service: &str,
 ) -> Result<(), ComposeRunnerError> {
     let mut command = Command::new("docker");
     command
@@ -18,24 +131,430 @@ pub async fn restart_compose_service(
         .arg(compose_file)
         .arg("-p")
         .arg(project_name)
-        .arg("restart")
+        .args(action)
         .arg(service);
 
-    let description = "docker compose restart";
-    info!(service, project = project_name, compose_file = %compose_file.display(), "restarting compose service");
+    let description = format!("docker compose {}", action.join(" "));
     run_docker_command(
         command,
-        testing_framework_core::adjust_timeout(std::time::Duration::from_secs(120)),
-        description,
+        adjust_timeout(SERVICE_COMMAND_TIMEOUT),
+        &description,
     )
     .await
     .map_err(ComposeRunnerError::Compose)
 }
 
+/// Run a shell snippet inside a compose service container and return stdout.
+pub async fn exec_in_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    script: &str,
+) -> Result<String, ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("exec")
+        .arg("-T")
+        .arg(service)
+        .arg("sh")
+        .arg("-c")
+        .arg(script);
+
+    let description = format!("docker compose exec {service}");
+    debug!(service, script, "executing command in compose service");
+    command_stdout(command, description, adjust_timeout(EXEC_TIMEOUT)).await
+}
+
+/// Run `command` inside a compose service container without a shell and
+/// capture its output. Unlike [`exec_in_compose_service`], a non-zero exit is
+/// reported in the output instead of failing.
+pub async fn exec_command_in_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    command: &[&str],
+) -> Result<ExecOutput, ComposeRunnerError> {
+    let mut docker = Command::new("docker");
+    docker
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .arg("exec")
+        .arg("-T")
+        .arg(service)
+        .args(command);
+
+    let description = format!("docker compose exec {service}");
+    let timeout_duration = adjust_timeout(SERVICE_COMMAND_TIMEOUT);
+    debug!(service, ?command, "executing command in compose service");
+    let output = timeout(timeout_duration, docker.output())
+        .await
+        .map_err(|_| ComposeCommandError::Timeout {
+            command: description.clone(),
+            timeout: timeout_duration,
+        })?
+        .map_err(|source| ComposeCommandError::Spawn {
+            command: description,
+            source,
+        })?;
+
+    Ok(ExecOutput {
+        exit_code: output.status.code(),
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+    })
+}
+
+/// Stop a compose service with `SIGTERM`, killing it once `deadline` passes,
+/// and report how it exited.
+///
+/// The deadline is tracked here rather than through `docker compose stop`,
+/// whose timeout only takes whole seconds, and a kill is only reported as a
+/// timeout when this function sent it: exit code 137 alone may also be an
+/// out-of-memory kill.
+pub async fn terminate_compose_service(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    deadline: Duration,
+) -> Result<NodeExit, ComposeRunnerError> {
+    info!(
+        service,
+        project = project_name,
+        ?deadline,
+        "stopping compose service gracefully"
+    );
+    let started = Instant::now();
+    run_service_command(
+        compose_file,
+        project_name,
+        &["kill", "--signal", "SIGTERM"],
+        service,
+    )
+    .await?;
+
+    let mut timed_out = false;
+    while service_state(compose_file, project_name, service, "{{.State}}").await? == "running" {
+        if started.elapsed() >= deadline {
+            debug!(service, "service ignored SIGTERM; killing it");
+            kill_compose_service(compose_file, project_name, service).await?;
+            timed_out = true;
+            break;
+        }
+        tokio::time::sleep(EXIT_POLL_INTERVAL.min(deadline)).await;
+    }
+    let elapsed = started.elapsed();
+
+    let exit_code = service_state(compose_file, project_name, service, "{{.ExitCode}}")
+        .await?
+        .parse::<i32>()
+        .ok();
+
+    Ok(NodeExit {
+        elapsed,
+        exit_code,
+        timed_out,
+    })
+}
+
+/// Field of `docker compose ps` for `service`, rendered with `format`.
+async fn service_state(
+    compose_file: &Path,
+    project_name: &str,
+    service: &str,
+    format: &str,
+) -> Result<String, ComposeRunnerError> {
+    let mut command = Command::new("docker");
+    command
+        .arg("compose")
+        .arg("-f")
+        .arg(compose_file)
+        .arg("-p")
+        .arg(project_name)
+        .args(["ps", "--all", "--format", format])
+        .arg(service);
+    command_stdout(
+        command,
+        format!("docker compose ps {service}"),
+        adjust_timeout(EXEC_TIMEOUT),
+    )
+    .await
+}
+
+async fn command_stdout(
+    mut command: Command,
+    description: String,
+    timeout_duration: Duration,
+) -> Result<String, ComposeRunnerError> {
+    let output = timeout(timeout_duration, command.output())
+        .await
+        .map_err(|_| ComposeCommandError::Timeout {
+            command: description.clone(),
+            timeout: timeout_duration,
+        })?
+        .map_err(|source| ComposeCommandError::Spawn {
+            command: description.clone(),
+            source,
+        })?;
+
+    if !output.status.success() {
+        return Err(ComposeCommandError::Failed {
+            command: description,
+            status: output.status,
+        }
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
 /// Compose-specific node control handle for restarting nodes.
 pub struct ComposeNodeControl {
     pub(crate) compose_file: PathBuf,
     pub(crate) project_name: String,
+    /// Services isolated by [`NodeControlHandle::partition`] and the peer IPs
+    /// each one drops, so healing removes only those rules.
+    partitioned: Mutex<Vec<(String, Vec<IpAddr>)>>,
+    /// Services shaping their traffic towards other network regions; chaos
+    /// shaping would replace their root qdisc.
+    region_shaped: Vec<String>,
+    scaling: Option<Scaling>,
+}
+
+/// State needed to add services to the running stack.
+struct Scaling {
+    descriptor: Mutex<ComposeDescriptor>,
+    root: PathBuf,
+    host: String,
+    template: ComposeTemplate,
+}
+
+impl ComposeNodeControl {
+    #[must_use]
+    pub fn new(compose_file: PathBuf, project_name: String) -> Self {
+        Self {
+            compose_file,
+            project_name,
+            partitioned: Mutex::new(Vec::new()),
+            region_shaped: Vec::new(),
+            scaling: None,
+        }
+    }
+
+    /// Reject [`NodeControlHandle::shape_network`] on `services`, whose
+    /// inter-region shaping lives in the root qdisc netem would replace.
+    #[must_use]
+    pub fn with_region_shaping(mut self, services: Vec<String>) -> Self {
+        self.region_shaped = services;
+        self
+    }
+
+    #[must_use]
+    /// Allow [`NodeControlHandle::add_node`]: new services are appended to
+    /// `descriptor`, the compose file is rendered again from `template` and
+    /// the service is brought up from `root`. Published ports are reached
+    /// through `host`.
+    pub fn with_scaling(
+        mut self,
+        descriptor: ComposeDescriptor,
+        template: ComposeTemplate,
+        root: PathBuf,
+        host: String,
+    ) -> Self {
+        self.scaling = Some(Scaling {
+            descriptor: Mutex::new(descriptor),
+            root,
+            host,
+            template,
+        });
+        self
+    }
+
+    async fn add(&self, role: NodeRole) -> Result<AddedNode, DynError> {
+        let scaling = self
+            .scaling
+            .as_ref()
+            .ok_or("this compose node control cannot add nodes")?;
+
+        let (target, service, (api_port, testing_port)) = {
+            let mut descriptor = scaling
+                .descriptor
+                .lock()
+                .expect("compose descriptor lock poisoned");
+            let (target, node) = descriptor
+                .add_node(role)
+                .ok_or_else(|| format!("no {role:?} in the stack to model a new node on"))?;
+            let added = (target, node.name().to_owned(), node.http_ports());
+            write_compose_file_with(&scaling.template, &descriptor, &self.compose_file)?;
+            added
+        };
+
+        info!(service, "adding compose service");
+        if let Err(err) = run_service_command(
+            &self.compose_file,
+            &self.project_name,
+            &["up", "--detach", "--no-deps"],
+            &service,
+        )
+        .await
+        {
+            self.rollback_add(scaling, target, &service).await;
+            return Err(err.into());
+        }
+
+        match self
+            .added_client(scaling, &service, api_port, testing_port)
+            .await
+        {
+            Ok(client) => Ok(AddedNode { target, client }),
+            Err(err) => {
+                self.rollback_add(scaling, target, &service).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Client for an added service, reached through its published ports.
+    async fn added_client(
+        &self,
+        scaling: &Scaling,
+        service: &str,
+        api_port: u16,
+        testing_port: u16,
+    ) -> Result<ApiClient, DynError> {
+        let resolve = |container_port| {
+            resolve_service_port(
+                &self.compose_file,
+                &self.project_name,
+                &scaling.root,
+                service,
+                container_port,
+            )
+        };
+        let api = resolve(api_port).await?;
+        let testing = resolve(testing_port).await?;
+        let url = |port: u16| Url::parse(&format!("http://{}:{port}/", scaling.host));
+        Ok(ApiClient::from_urls(url(api)?, Some(url(testing)?)))
+    }
+
+    /// Remove the service of `target` and, when nodes can be added, drop it
+    /// from the compose file so later `up` calls do not bring it back.
+    async fn remove(&self, target: NodeTarget) -> Result<(), DynError> {
+        remove_compose_service(&self.compose_file, &self.project_name, &target.to_string()).await?;
+        let Some(scaling) = &self.scaling else {
+            return Ok(());
+        };
+        let mut descriptor = scaling
+            .descriptor
+            .lock()
+            .expect("compose descriptor lock poisoned");
+        if descriptor.remove_node(target) {
+            write_compose_file_with(&scaling.template, &descriptor, &self.compose_file)?;
+        }
+        Ok(())
+    }
+
+    /// Remove whatever `docker compose up` left of a failed addition, then
+    /// drop the node from the descriptor and the compose file so the next
+    /// addition reuses its index.
+    async fn rollback_add(&self, scaling: &Scaling, target: NodeTarget, service: &str) {
+        if let Err(err) =
+            remove_compose_service(&self.compose_file, &self.project_name, service).await
+        {
+            debug!(service, error = %err, "no container to remove after failed addition");
+        }
+
+        let mut descriptor = scaling
+            .descriptor
+            .lock()
+            .expect("compose descriptor lock poisoned");
+        if !descriptor.drop_added_node(target) {
+            warn!(%target, "another node was added meanwhile; keeping failed node in compose file");
+            return;
+        }
+        if let Err(err) =
+            write_compose_file_with(&scaling.template, &descriptor, &self.compose_file)
+        {
+            warn!(%target, error = %err, "failed to restore compose file after failed addition");
+        }
+    }
+
+    async fn exec_script(&self, service: &str, script: &str) -> Result<String, ComposeRunnerError> {
+        exec_in_compose_service(&self.compose_file, &self.project_name, service, script).await
+    }
+
+    /// Fail when `service` shapes traffic towards other network regions, so
+    /// chaos shaping does not wipe the inter-region latency.
+    fn ensure_not_region_shaped(&self, service: &str) -> Result<(), DynError> {
+        if self.region_shaped.iter().any(|shaped| shaped == service) {
+            return Err(format!(
+                "{service} shapes traffic towards other network regions; chaos network shaping is not supported on it"
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Addresses of `service`; dual-stack containers report one per family.
+    async fn service_ips(&self, service: &str) -> Result<Vec<IpAddr>, DynError> {
+        let output = self.exec_script(service, "hostname -i").await?;
+        let ips = parse_service_ips(&output);
+        if ips.is_empty() {
+            return Err(format!("no usable address in `hostname -i` output {output:?}").into());
+        }
+        Ok(ips)
+    }
+}
+
+/// Parse the space-separated addresses printed by `hostname -i`, skipping
+/// loopback and anything that is not a plain address (e.g. scoped
+/// link-local ones).
+fn parse_service_ips(output: &str) -> Vec<IpAddr> {
+    output
+        .split_whitespace()
+        .filter_map(|token| token.parse::<IpAddr>().ok())
+        .filter(|ip| !ip.is_loopback() && !ip.is_unspecified())
+        .collect()
+}
+
+/// Render the `iptables`/`ip6tables` invocations appending (`-A`) or
+/// deleting (`-D`) the rules that drop traffic to and from `ips`. Other rules
+/// in the container are left alone.
+fn drop_rules_command(action: &str, ips: &[IpAddr]) -> String {
+    ips.iter()
+        .map(|ip| {
+            let tables = if ip.is_ipv6() { "ip6tables" } else { "iptables" };
+            format!("{tables} {action} INPUT -s {ip} -j DROP && {tables} {action} OUTPUT -d {ip} -j DROP")
+        })
+        .collect::<Vec<_>>()
+        .join(" && ")
+}
+
+/// Render the `tc` invocation installing a netem qdisc for the given profile.
+fn netem_command(shaping: &NetworkShaping) -> String {
+    format!(
+        "tc qdisc replace dev {SHAPED_INTERFACE} root netem {}",
+        netem_args(shaping)
+    )
+}
+
+/// Render the netem options (`delay ...`, `loss ...`) for the given profile.
+pub(crate) fn netem_args(shaping: &NetworkShaping) -> String {
+    let mut args = format!("delay {}ms", shaping.latency.as_millis());
+    if !shaping.jitter.is_zero() {
+        args.push_str(&format!(" {}ms", shaping.jitter.as_millis()));
+    }
+    if shaping.loss_percent > 0.0 {
+        args.push_str(&format!(" loss {:.2}%", shaping.loss_percent.min(100.0)));
+    }
+    args
 }
 
 #[async_trait::async_trait]
@@ -59,4 +578,287 @@ impl NodeControlHandle for ComposeNodeControl {
         .await
         .map_err(|err| format!("executor restart failed: {err}").into())
     }
+
+    async fn stop_validator(&self, index: usize) -> Result<(), DynError> {
+        stop_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &NodeTarget::Validator(index).to_string(),
+        )
+        .await
+        .map_err(|err| format!("validator stop failed: {err}").into())
+    }
+
+    async fn start_validator(&self, index: usize) -> Result<(), DynError> {
+        start_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &NodeTarget::Validator(index).to_string(),
+        )
+        .await
+        .map_err(|err| format!("validator start failed: {err}").into())
+    }
+
+    async fn stop_executor(&self, index: usize) -> Result<(), DynError> {
+        stop_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &NodeTarget::Executor(index).to_string(),
+        )
+        .await
+        .map_err(|err| format!("executor stop failed: {err}").into())
+    }
+
+    async fn start_executor(&self, index: usize) -> Result<(), DynError> {
+        start_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &NodeTarget::Executor(index).to_string(),
+        )
+        .await
+        .map_err(|err| format!("executor start failed: {err}").into())
+    }
+
+    async fn kill_validator(&self, index: usize) -> Result<(), DynError> {
+        kill_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &NodeTarget::Validator(index).to_string(),
+        )
+        .await
+        .map_err(|err| format!("validator kill failed: {err}").into())
+    }
+
+    async fn kill_executor(&self, index: usize) -> Result<(), DynError> {
+        kill_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &NodeTarget::Executor(index).to_string(),
+        )
+        .await
+        .map_err(|err| format!("executor kill failed: {err}").into())
+    }
+
+    async fn terminate_node(
+        &self,
+        target: NodeTarget,
+        deadline: Duration,
+    ) -> Result<NodeExit, DynError> {
+        terminate_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &target.to_string(),
+            deadline,
+        )
+        .await
+        .map_err(|err| format!("{target} terminate failed: {err}").into())
+    }
+
+    async fn partition(&self, groups: &[Vec<NodeTarget>]) -> Result<(), DynError> {
+        let mut resolved = Vec::with_capacity(groups.len());
+        for group in groups {
+            let mut members = Vec::with_capacity(group.len());
+            for target in group {
+                let service = target.to_string();
+                let ips = self
+                    .service_ips(&service)
+                    .await
+                    .map_err(|err| format!("failed to resolve ip for {service}: {err}"))?;
+                members.push((service, ips));
+            }
+            resolved.push(members);
+        }
+
+        for (group_index, group) in resolved.iter().enumerate() {
+            let foreign_ips: Vec<IpAddr> = resolved
+                .iter()
+                .enumerate()
+                .filter(|(other_index, _)| *other_index != group_index)
+                .flat_map(|(_, other)| other.iter().flat_map(|(_, ips)| ips.iter().copied()))
+                .collect();
+            if foreign_ips.is_empty() {
+                continue;
+            }
+
+            let script = drop_rules_command("-A", &foreign_ips);
+            for (service, _) in group {
+                info!(service, group = group_index, "isolating compose service");
+                self.exec_script(service, &script)
+                    .await
+                    .map_err(|err| format!("failed to partition {service}: {err}"))?;
+                self.partitioned
+                    .lock()
+                    .expect("partition state lock poisoned")
+                    .push((service.clone(), foreign_ips.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn heal_partition(&self) -> Result<(), DynError> {
+        let services = std::mem::take(
+            &mut *self
+                .partitioned
+                .lock()
+                .expect("partition state lock poisoned"),
+        );
+        // Try every service and keep the rules that could not be removed, so
+        // a later heal can retry them.
+        let mut failures = Vec::new();
+        for (service, ips) in services {
+            info!(service, "healing compose service network");
+            if let Err(err) = self
+                .exec_script(&service, &drop_rules_command("-D", &ips))
+                .await
+            {
+                warn!(service, error = %err, "failed to heal partition");
+                failures.push(format!("{service}: {err}"));
+                self.partitioned
+                    .lock()
+                    .expect("partition state lock poisoned")
+                    .push((service, ips));
+            }
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("failed to heal partition on {}", failures.join("; ")).into())
+        }
+    }
+
+    async fn pause_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        pause_compose_service(&self.compose_file, &self.project_name, &target.to_string())
+            .await
+            .map_err(|err| format!("pausing {target} failed: {err}").into())
+    }
+
+    async fn unpause_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        unpause_compose_service(&self.compose_file, &self.project_name, &target.to_string())
+            .await
+            .map_err(|err| format!("unpausing {target} failed: {err}").into())
+    }
+
+    async fn shape_network(
+        &self,
+        target: NodeTarget,
+        shaping: NetworkShaping,
+    ) -> Result<(), DynError> {
+        let service = target.to_string();
+        self.ensure_not_region_shaped(&service)?;
+        if shaping.is_noop() {
+            return self.clear_network_shaping(target).await;
+        }
+
+        info!(service, ?shaping, "shaping compose service network");
+        self.exec_script(&service, &netem_command(&shaping))
+            .await
+            .map(|_| ())
+            .map_err(|err| format!("failed to shape network on {service}: {err}").into())
+    }
+
+    async fn clear_network_shaping(&self, target: NodeTarget) -> Result<(), DynError> {
+        let service = target.to_string();
+        self.ensure_not_region_shaped(&service)?;
+        info!(service, "clearing compose service network shaping");
+        // Deleting a missing qdisc fails; treat that as already cleared.
+        self.exec_script(
+            &service,
+            &format!("tc qdisc del dev {SHAPED_INTERFACE} root 2>/dev/null || true"),
+        )
+        .await
+        .map(|_| ())
+        .map_err(|err| format!("failed to clear network shaping on {service}: {err}").into())
+    }
+
+    /// Create the join marker, then restart the container so the node starts
+    /// with a fresh healthcheck start period instead of one that ran out
+    /// while it was held back.
+    async fn join_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        let service = target.to_string();
+        info!(service, "releasing late-joining compose service");
+        self.exec_script(&service, &format!("touch {JOIN_MARKER_PATH}"))
+            .await
+            .map_err(|err| format!("failed to release late-joining {service}: {err}"))?;
+        restart_compose_service(&self.compose_file, &self.project_name, &service)
+            .await
+            .map_err(|err| format!("failed to start late-joining {service}: {err}").into())
+    }
+
+    async fn add_node(&self, role: NodeRole) -> Result<AddedNode, DynError> {
+        self.add(role)
+            .await
+            .map_err(|err| format!("adding {role:?} failed: {err}").into())
+    }
+
+    async fn remove_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        self.remove(target)
+            .await
+            .map_err(|err| format!("removing {target} failed: {err}").into())
+    }
+
+    async fn exec(&self, target: NodeTarget, command: &[&str]) -> Result<ExecOutput, DynError> {
+        exec_command_in_compose_service(
+            &self.compose_file,
+            &self.project_name,
+            &target.to_string(),
+            command,
+        )
+        .await
+        .map_err(|err| format!("exec in {target} failed: {err}").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(raw: &str) -> IpAddr {
+        raw.parse().expect("valid address")
+    }
+
+    #[tokio::test]
+    async fn region_shaped_services_reject_chaos_shaping() {
+        let control = ComposeNodeControl::new(PathBuf::from("compose.yml"), "test".to_owned())
+            .with_region_shaping(vec!["validator-0".to_owned()]);
+        let shaping = NetworkShaping {
+            latency: Duration::from_millis(200),
+            jitter: Duration::ZERO,
+            loss_percent: 0.0,
+        };
+
+        let shaped = control
+            .shape_network(NodeTarget::Validator(0), shaping)
+            .await
+            .expect_err("shaping would replace the region qdisc");
+        assert!(shaped.to_string().contains("network regions"), "{shaped}");
+        let cleared = control
+            .clear_network_shaping(NodeTarget::Validator(0))
+            .await
+            .expect_err("clearing would delete the region qdisc");
+        assert!(cleared.to_string().contains("network regions"), "{cleared}");
+    }
+
+    #[test]
+    fn service_ips_parse_every_family() {
+        assert_eq!(
+            parse_service_ips("172.18.0.3 fd00:18::3 fe80::1%eth0 127.0.0.1\n"),
+            [ip("172.18.0.3"), ip("fd00:18::3")]
+        );
+        assert!(parse_service_ips("\n").is_empty());
+        assert!(parse_service_ips("hostname: Name or service not known").is_empty());
+    }
+
+    #[test]
+    fn drop_rules_pick_the_table_per_family() {
+        let ips = [ip("172.18.0.3"), ip("fd00:18::3")];
+
+        let rules = drop_rules_command("-A", &ips);
+
+        assert_eq!(
+            rules,
+            "iptables -A INPUT -s 172.18.0.3 -j DROP && iptables -A OUTPUT -d 172.18.0.3 -j DROP \
+             && ip6tables -A INPUT -s fd00:18::3 -j DROP && ip6tables -A OUTPUT -d fd00:18::3 -j DROP"
+        );
+    }
 }
@@ -0,0 +1,206 @@
+use std::{
+    fs::{self, File},
+    io::{self, Write as _},
+    path::{Path, PathBuf},
+    process::Stdio,
+    thread,
+    time::{Duration, Instant},
+};
+
+use flate2::{Compression, write::GzEncoder};
+use testing_framework_core::scenario::NodeLogs;
+use tokio::process::{Child, Command};
+use tracing::{debug, info, warn};
+
+/// Directory (relative to the compose workspace root) receiving node logs.
+pub const LOG_DIR_NAME: &str = "logs";
+/// When set, collected logs are copied into `<dir>/<project>` at cleanup.
+pub const LOG_ARCHIVE_ENV: &str = "COMPOSE_RUNNER_LOG_DIR";
+const FOLLOWER_EXIT_GRACE: Duration = Duration::from_secs(5);
+const FOLLOWER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Failures starting log collection.
+#[derive(Debug, thiserror::Error)]
+pub enum LogCollectorError {
+    #[error("failed to prepare log file {path}: {source}")]
+    File {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("failed to spawn log follower for {service}: {source}")]
+    Spawn {
+        service: String,
+        #[source]
+        source: io::Error,
+    },
+}
+
+struct LogFollower {
+    service: String,
+    path: PathBuf,
+    child: Child,
+}
+
+/// Streams each compose service's logs into `<root>/logs/<service>.log` for
+/// the lifetime of the run.
+pub struct LogCollector {
+    dir: PathBuf,
+    project_name: String,
+    compress: bool,
+    followers: Vec<LogFollower>,
+}
+
+impl LogCollector {
+    /// Start a `docker compose logs --follow` process per service.
+    pub fn start(
+        compose_file: &Path,
+        project_name: &str,
+        root: &Path,
+        services: &[String],
+        compress: bool,
+    ) -> Result<Self, LogCollectorError> {
+        let dir = root.join(LOG_DIR_NAME);
+        fs::create_dir_all(&dir).map_err(|source| LogCollectorError::File {
+            path: dir.clone(),
+            source,
+        })?;
+
+        let mut followers = Vec::with_capacity(services.len());
+        for service in services {
+            let path = dir.join(format!("{service}.log"));
+            let (stdout, stderr) = open_log_file(&path, service, project_name)?;
+
+            let mut command = Command::new("docker");
+            command
+                .arg("compose")
+                .arg("-f")
+                .arg(compose_file)
+                .arg("-p")
+                .arg(project_name)
+                .arg("logs")
+                .arg("--follow")
+                .arg("--no-color")
+                .arg("--no-log-prefix")
+                .arg("--timestamps")
+                .arg(service)
+                .current_dir(root)
+                .stdin(Stdio::null())
+                .stdout(stdout)
+                .stderr(stderr)
+                .kill_on_drop(true);
+
+            let child = command.spawn().map_err(|source| LogCollectorError::Spawn {
+                service: service.clone(),
+                source,
+            })?;
+            debug!(service, path = %path.display(), "following compose service logs");
+            followers.push(LogFollower {
+                service: service.clone(),
+                path,
+                child,
+            });
+        }
+
+        info!(dir = %dir.display(), services = services.len(), "collecting compose logs");
+        Ok(Self {
+            dir,
+            project_name: project_name.to_owned(),
+            compress,
+            followers,
+        })
+    }
+
+    /// Directory holding the collected log files.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Per-node log files, keyed by service name.
+    #[must_use]
+    pub fn node_logs(&self) -> NodeLogs {
+        self.followers
+            .iter()
+            .fold(NodeLogs::new(), |logs, follower| {
+                logs.with_file(follower.service.clone(), follower.path.clone())
+            })
+    }
+
+    /// Stop following, compress the files if requested and copy them to the
+    /// archive directory when [`LOG_ARCHIVE_ENV`] is set.
+    ///
+    /// Followers exit on their own once the containers stop, so call this
+    /// after `docker compose down` to keep shutdown output.
+    pub fn finish(mut self) {
+        let deadline = Instant::now() + FOLLOWER_EXIT_GRACE;
+        for follower in &mut self.followers {
+            while matches!(follower.child.try_wait(), Ok(None)) && Instant::now() < deadline {
+                thread::sleep(FOLLOWER_POLL_INTERVAL);
+            }
+            if let Err(err) = follower.child.start_kill() {
+                debug!(service = %follower.service, error = ?err, "log follower already exited");
+            }
+        }
+
+        if self.compress {
+            for follower in &mut self.followers {
+                match compress_file(&follower.path) {
+                    Ok(compressed) => follower.path = compressed,
+                    Err(err) => {
+                        warn!(path = %follower.path.display(), error = ?err, "failed to compress log file");
+                    }
+                }
+            }
+        }
+
+        if let Some(archive_root) = std::env::var_os(LOG_ARCHIVE_ENV) {
+            let archive = PathBuf::from(archive_root).join(&self.project_name);
+            match self.archive(&archive) {
+                Ok(()) => info!(path = %archive.display(), "archived compose logs"),
+                Err(err) => {
+                    warn!(path = %archive.display(), error = ?err, "failed to archive compose logs");
+                }
+            }
+        }
+    }
+
+    fn archive(&self, target: &Path) -> io::Result<()> {
+        fs::create_dir_all(target)?;
+        for follower in &self.followers {
+            if let Some(name) = follower.path.file_name() {
+                fs::copy(&follower.path, target.join(name))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Create the log file with a header naming the node, returning handles for
+/// the follower's stdout and stderr.
+fn open_log_file(
+    path: &Path,
+    service: &str,
+    project_name: &str,
+) -> Result<(File, File), LogCollectorError> {
+    let file_err = |source| LogCollectorError::File {
+        path: path.to_path_buf(),
+        source,
+    };
+    let mut file = File::create(path).map_err(file_err)?;
+    writeln!(file, "# node={service} project={project_name}").map_err(file_err)?;
+    let stderr = file.try_clone().map_err(file_err)?;
+    Ok((file, stderr))
+}
+
+fn compress_file(path: &Path) -> io::Result<PathBuf> {
+    let mut compressed_name = path.as_os_str().to_owned();
+    compressed_name.push(".gz");
+    let compressed = PathBuf::from(compressed_name);
+
+    let mut encoder = GzEncoder::new(File::create(&compressed)?, Compression::default());
+    io::copy(&mut File::open(path)?, &mut encoder)?;
+    encoder.finish()?;
+    fs::remove_file(path)?;
+    Ok(compressed)
+}
@@ -1,5 +1,6 @@
 pub mod commands;
 pub mod control;
+pub mod logs;
 pub mod platform;
 pub mod workspace;
 
@@ -9,11 +10,10 @@ use tokio::{process::Command, time::timeout};
 use tracing::{debug, info, warn};
 
 use crate::{
-    docker::commands::ComposeCommandError, errors::ComposeRunnerError,
+    descriptor::ImageOverrides, docker::commands::ComposeCommandError, errors::ComposeRunnerError,
     infrastructure::template::repository_root,
 };
 
-const IMAGE_BUILD_TIMEOUT: Duration = Duration::from_secs(600);
 const DOCKER_INFO_TIMEOUT: Duration = Duration::from_secs(15);
 const IMAGE_INSPECT_TIMEOUT: Duration = Duration::from_secs(60);
 
@@ -44,17 +44,29 @@ pub async fn ensure_docker_available() -> Result<(), ComposeRunnerError> {
     }
 }
 
-/// Ensure the configured compose image exists, building a local one if needed.
-pub async fn ensure_compose_image() -> Result<(), ComposeRunnerError> {
+/// Ensure the configured compose image and any per-node override images
+/// exist, building a local one (within `build_timeout`) if needed.
+pub async fn ensure_compose_image(
+    overrides: &ImageOverrides,
+    build_timeout: Duration,
+) -> Result<(), ComposeRunnerError> {
     let (image, platform) = crate::docker::platform::resolve_image();
     info!(image, platform = ?platform, "ensuring compose image is present");
-    ensure_image_present(&image, platform.as_deref()).await
+    ensure_image_present(&image, platform.as_deref(), build_timeout).await?;
+
+    for image in overrides.images() {
+        let platform = crate::docker::platform::image_platform(image);
+        info!(image, platform = ?platform, "ensuring override image is present");
+        ensure_image_present(image, platform.as_deref(), build_timeout).await?;
+    }
+    Ok(())
 }
 
 /// Verify an image exists locally, optionally building it for the default tag.
 pub async fn ensure_image_present(
     image: &str,
     platform: Option<&str>,
+    build_timeout: Duration,
 ) -> Result<(), ComposeRunnerError> {
     if docker_image_exists(image).await? {
         debug!(image, "docker image already present");
@@ -67,7 +79,7 @@ pub async fn ensure_image_present(
         });
     }
 
-    build_local_image(image, platform).await
+    build_local_image(image, platform, build_timeout).await
 }
 
 /// Returns true when `docker image inspect` succeeds for the image.
@@ -101,6 +113,7 @@ pub async fn docker_image_exists(image: &str) -> Result<bool, ComposeRunnerError
 pub async fn build_local_image(
     image: &str,
     platform: Option<&str>,
+    build_timeout: Duration,
 ) -> Result<(), ComposeRunnerError> {
     let repo_root =
         repository_root().map_err(|source| ComposeRunnerError::ImageBuild { source })?;
@@ -156,16 +169,12 @@ pub async fn build_local_image(
 
     cmd.current_dir(&repo_root);
 
-    let status = timeout(
-        testing_framework_core::adjust_timeout(IMAGE_BUILD_TIMEOUT),
-        cmd.status(),
-    )
-    .await
-    .map_err(|_| {
-        warn!(image, timeout = ?IMAGE_BUILD_TIMEOUT, "docker build timed out");
+    let build_timeout = testing_framework_core::adjust_timeout(build_timeout);
+    let status = timeout(build_timeout, cmd.status()).await.map_err(|_| {
+        warn!(image, timeout = ?build_timeout, "docker build timed out");
         ComposeRunnerError::Compose(ComposeCommandError::Timeout {
             command: String::from("docker build"),
-            timeout: testing_framework_core::adjust_timeout(IMAGE_BUILD_TIMEOUT),
+            timeout: build_timeout,
         })
     })?;
 
@@ -1,17 +1,27 @@
 use std::env;
 
 use tracing::debug;
+use url::Url;
+
+const LOOPBACK: &str = "127.0.0.1";
 
 /// Select the compose image and optional platform, honoring
 /// NOMOS_TESTNET_IMAGE.
 pub fn resolve_image() -> (String, Option<String>) {
     let image = env::var("NOMOS_TESTNET_IMAGE")
         .unwrap_or_else(|_| String::from("logos-blockchain-testing:local"));
-    let platform = (image == "ghcr.io/logos-co/nomos:testnet").then(|| "linux/amd64".to_owned());
+    let platform = image_platform(&image);
     debug!(image, platform = ?platform, "resolved compose image");
     (image, platform)
 }
 
+/// Platform to request for `image`; the published testnet image is
+/// amd64-only.
+#[must_use]
+pub fn image_platform(image: &str) -> Option<String> {
+    (image == "ghcr.io/logos-co/nomos:testnet").then(|| "linux/amd64".to_owned())
+}
+
 /// Optional extra hosts entry for host networking.
 pub fn host_gateway_entry() -> Option<String> {
     if let Ok(value) = env::var("COMPOSE_RUNNER_HOST_GATEWAY") {
@@ -29,3 +39,72 @@ pub fn host_gateway_entry() -> Option<String> {
 
     Some("host.docker.internal:host-gateway".into())
 }
+
+/// Address of the Docker daemon host when `DOCKER_HOST` points at a remote
+/// machine (`tcp://` or `ssh://`); `None` for local sockets and pipes.
+#[must_use]
+pub fn remote_docker_host() -> Option<String> {
+    env::var("DOCKER_HOST")
+        .ok()
+        .and_then(|value| docker_host_address(&value))
+}
+
+/// Host part of a `DOCKER_HOST` value, bracketed for IPv6 so it can be used
+/// in URLs directly.
+fn docker_host_address(docker_host: &str) -> Option<String> {
+    let url = Url::parse(docker_host.trim()).ok()?;
+    if !matches!(url.scheme(), "tcp" | "ssh" | "http" | "https") {
+        return None;
+    }
+    url.host_str()
+        .filter(|host| !host.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+/// Interface published monitoring ports bind to on the Docker host. Ports
+/// stay on loopback locally but must be reachable from the runner when the
+/// daemon is remote.
+#[must_use]
+pub fn published_bind_address() -> &'static str {
+    if remote_docker_host().is_some() {
+        "0.0.0.0"
+    } else {
+        LOOPBACK
+    }
+}
+
+/// Host the runner uses to reach published container ports:
+/// `COMPOSE_RUNNER_HOST` when set, else the remote `DOCKER_HOST` address,
+/// else loopback.
+#[must_use]
+pub fn published_ports_host() -> String {
+    if let Ok(host) = env::var("COMPOSE_RUNNER_HOST") {
+        if !host.is_empty() {
+            return host;
+        }
+    }
+    remote_docker_host().unwrap_or_else(|| LOOPBACK.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn docker_host_address_resolves_remote_daemons_only() {
+        assert_eq!(docker_host_address("unix:///var/run/docker.sock"), None);
+        assert_eq!(docker_host_address("npipe:////./pipe/docker_engine"), None);
+        assert_eq!(
+            docker_host_address("tcp://10.0.0.5:2376").as_deref(),
+            Some("10.0.0.5")
+        );
+        assert_eq!(
+            docker_host_address("ssh://ci@build-box:2222").as_deref(),
+            Some("build-box")
+        );
+        assert_eq!(
+            docker_host_address("tcp://[fd00::5]:2375").as_deref(),
+            Some("[fd00::5]")
+        );
+    }
+}
@@ -1,21 +1,25 @@
 use std::{
     env, fs,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use anyhow::{Context as _, Result};
-use tempfile::TempDir;
+use testing_framework_core::{artifacts::RunArtifacts, scenario::Profile};
 use tracing::{debug, info};
 
-/// Copy the repository stack assets into a scenario-specific temp dir.
+/// Copy the repository stack assets into the `compose` directory of a fresh
+/// run artifacts directory.
 #[derive(Debug)]
 pub struct ComposeWorkspace {
-    root: TempDir,
+    root: PathBuf,
+    artifacts: Arc<RunArtifacts>,
 }
 
 impl ComposeWorkspace {
-    /// Clone the stack assets into a temporary directory.
-    pub fn create() -> Result<Self> {
+    /// Clone the stack assets into a new run artifacts directory, kept and
+    /// pruned according to `profile`.
+    pub fn create(profile: Option<Profile>) -> Result<Self> {
         let repo_root = env::var("CARGO_WORKSPACE_DIR")
             .map(PathBuf::from)
             .or_else(|_| {
@@ -27,10 +31,11 @@ impl ComposeWorkspace {
                     .context("resolving workspace root from manifest dir")
             })
             .context("locating repository root")?;
-        let temp = tempfile::Builder::new()
-            .prefix("nomos-testnet-")
-            .tempdir()
-            .context("creating testnet temp dir")?;
+        let artifacts =
+            Arc::new(RunArtifacts::create_for(profile).context("creating run artifacts dir")?);
+        let root = artifacts
+            .dir("compose")
+            .context("creating compose workspace dir")?;
         let stack_source = stack_assets_root(&repo_root);
         if !stack_source.exists() {
             anyhow::bail!(
@@ -43,16 +48,16 @@ impl ComposeWorkspace {
             stack_source = %stack_source.display(),
             "copying stack assets into temporary workspace"
         );
-        copy_dir_recursive(&stack_source, &temp.path().join("stack"))?;
+        copy_dir_recursive(&stack_source, &root.join("stack"))?;
         let scripts_source = stack_scripts_root(&repo_root);
         if scripts_source.exists() {
-            copy_dir_recursive(&scripts_source, &temp.path().join("stack/scripts"))?;
+            copy_dir_recursive(&scripts_source, &root.join("stack/scripts"))?;
         }
 
         // Ensure Prometheus config is a file (Docker bind mount fails if a directory
         // exists).
         let prometheus_src = stack_source.join("monitoring/prometheus.yml");
-        let prometheus_dst = temp.path().join("stack/monitoring/prometheus.yml");
+        let prometheus_dst = root.join("stack/monitoring/prometheus.yml");
         if prometheus_dst.exists() && prometheus_dst.is_dir() {
             fs::remove_dir_all(&prometheus_dst)
                 .with_context(|| format!("removing bogus dir {}", prometheus_dst.display()))?;
@@ -68,7 +73,7 @@ impl ComposeWorkspace {
         }
 
         let kzg_source = repo_root.join("testing-framework/assets/stack/kzgrs_test_params");
-        let target = temp.path().join("kzgrs_test_params");
+        let target = root.join("kzgrs_test_params");
         if kzg_source.exists() {
             if kzg_source.is_dir() {
                 copy_dir_recursive(&kzg_source, &target)?;
@@ -92,25 +97,51 @@ impl ComposeWorkspace {
             );
         }
 
-        info!(root = %temp.path().display(), "compose workspace created");
-        Ok(Self { root: temp })
+        info!(
+            run_id = artifacts.run_id(),
+            root = %root.display(),
+            "compose workspace created"
+        );
+        Ok(Self { root, artifacts })
+    }
+
+    /// Re-attach to the workspace of an earlier run, e.g. to resume its
+    /// stack.
+    pub fn reopen(artifacts: Arc<RunArtifacts>) -> Result<Self> {
+        let root = artifacts.root().join("compose");
+        if !root.is_dir() {
+            anyhow::bail!("compose workspace not found at {}", root.display());
+        }
+        info!(
+            run_id = artifacts.run_id(),
+            root = %root.display(),
+            "compose workspace reopened"
+        );
+        Ok(Self { root, artifacts })
     }
 
     #[must_use]
-    /// Root of the temporary workspace on disk.
+    /// Root of the workspace on disk.
     pub fn root_path(&self) -> &Path {
-        self.root.path()
+        &self.root
     }
 
     #[must_use]
     /// Path to the copied assets directory.
     pub fn stack_dir(&self) -> PathBuf {
-        self.root.path().join("stack")
+        self.root.join("stack")
     }
 
     #[must_use]
-    /// Consume the workspace and return the underlying temp directory.
-    pub fn into_inner(self) -> TempDir {
+    /// Run artifacts directory the workspace lives in.
+    pub fn artifacts(&self) -> &Arc<RunArtifacts> {
+        &self.artifacts
+    }
+
+    /// Keep the run artifacts directory after the run and return the
+    /// workspace root.
+    pub fn keep(self) -> PathBuf {
+        self.artifacts.keep();
         self.root
     }
 }
@@ -1,16 +1,20 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
+use cfgsync::config::schema::SchemaError;
 use testing_framework_core::{
+    artifacts::ArtifactsError,
+    errors::{ClassifiedError, ErrorKind},
     scenario::{
-        MetricsError,
+        FaultProxyError, MetricsError,
         http_probe::{HttpReadinessError, NodeRole},
     },
     topology::readiness::ReadinessError,
 };
-use url::ParseError;
+use url::{ParseError, Url};
 
 use crate::{
-    descriptor::DescriptorBuildError, docker::commands::ComposeCommandError,
+    descriptor::DescriptorBuildError,
+    docker::{commands::ComposeCommandError, logs::LogCollectorError},
     infrastructure::template::TemplateError,
 };
 
@@ -38,10 +42,20 @@ pub enum ComposeRunnerError {
     Compose(#[from] ComposeCommandError),
     #[error(transparent)]
     Readiness(#[from] StackReadinessError),
+    #[error("stack did not become ready while cfgsync registration was incomplete ({cfgsync})")]
+    CfgsyncIncomplete {
+        cfgsync: String,
+        #[source]
+        source: Box<ComposeRunnerError>,
+    },
+    #[error(transparent)]
+    State(#[from] StateError),
     #[error(transparent)]
     NodeClients(#[from] NodeClientError),
     #[error(transparent)]
     Telemetry(#[from] MetricsError),
+    #[error(transparent)]
+    Logs(#[from] LogCollectorError),
     #[error("block feed requires at least one validator client")]
     BlockFeedMissing,
     #[error("failed to start block feed: {source}")]
@@ -58,6 +72,8 @@ pub enum ComposeRunnerError {
         #[source]
         source: anyhow::Error,
     },
+    #[error(transparent)]
+    ApiFaults(#[from] FaultProxyError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -83,6 +99,12 @@ pub enum ConfigError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("generated cfgsync configuration at {path} is invalid: {source}")]
+    InvalidCfgsync {
+        path: PathBuf,
+        #[source]
+        source: SchemaError,
+    },
     #[error("failed to allocate cfgsync port: {source}")]
     Port {
         #[source]
@@ -94,6 +116,20 @@ pub enum ConfigError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("external cfgsync server at {url} is unreachable: {source}")]
+    ExternalCfgsync {
+        url: Url,
+        #[source]
+        source: anyhow::Error,
+    },
+    #[error(
+        "external cfgsync server at {url} expects {expected} hosts but the topology has {nodes} nodes"
+    )]
+    ExternalCfgsyncTooSmall {
+        url: Url,
+        expected: usize,
+        nodes: usize,
+    },
     #[error("failed to build compose descriptor: {source}")]
     Descriptor {
         #[source]
@@ -104,6 +140,58 @@ pub enum ConfigError {
         #[source]
         source: TemplateError,
     },
+    #[error("failed to write scenario dashboard at {path}: {source}")]
+    Dashboard {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+/// Failures persisting a deployment's state or resuming it.
+pub enum StateError {
+    #[error("cannot resume run {run_id}: {source}")]
+    Artifacts {
+        run_id: String,
+        #[source]
+        source: ArtifactsError,
+    },
+    #[error("failed to read deployment state {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to write deployment state {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to serialize deployment state {path}: {source}")]
+    Serialize {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("invalid deployment state {path}: {source}")]
+    Parse {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error(
+        "run {run_id} deployed {validators} validators and {executors} executors, \
+         but the scenario has {expected_validators} and {expected_executors}"
+    )]
+    TopologyMismatch {
+        run_id: String,
+        validators: usize,
+        executors: usize,
+        expected_validators: usize,
+        expected_executors: usize,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -123,6 +211,20 @@ pub enum StackReadinessError {
         #[source]
         source: ReadinessError,
     },
+    #[error("transaction readiness probe failed: {source}")]
+    Transactions {
+        #[source]
+        source: ReadinessError,
+    },
+    #[error("node containers failed their healthcheck: {services}")]
+    Unhealthy { services: String },
+    #[error("node containers did not turn healthy within {timeout:?}: {services}")]
+    HealthTimeout { services: String, timeout: Duration },
+    #[error("failed to read container health: {source}")]
+    Health {
+        #[source]
+        source: ComposeCommandError,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -140,3 +242,76 @@ pub enum NodeClientError {
         source: ParseError,
     },
 }
+
+impl ClassifiedError for ComposeRunnerError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::MissingValidator { .. }
+            | Self::NodeClients(_)
+            | Self::BlockFeedMissing
+            | Self::MissingImage { .. } => ErrorKind::Configuration,
+            Self::DockerUnavailable
+            | Self::PortDiscovery { .. }
+            | Self::Workspace(_)
+            | Self::Logs(_)
+            | Self::ImageBuild { .. } => ErrorKind::Infrastructure,
+            Self::Config(source) => source.kind(),
+            Self::Compose(source) => source.kind(),
+            Self::Readiness(source) => source.kind(),
+            Self::CfgsyncIncomplete { source, .. } => source.kind(),
+            Self::State(source) => source.kind(),
+            Self::Telemetry(source) => source.kind(),
+            Self::BlockFeed { .. } => ErrorKind::NodeFailure,
+            Self::ApiFaults(source) => source.kind(),
+        }
+    }
+}
+
+impl ClassifiedError for ConfigError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Descriptor { .. }
+            | Self::Template { .. }
+            | Self::ExternalCfgsyncTooSmall { .. } => ErrorKind::Configuration,
+            Self::Cfgsync { .. }
+            | Self::Port { .. }
+            | Self::CfgsyncStart { .. }
+            | Self::ExternalCfgsync { .. }
+            | Self::Dashboard { .. } => ErrorKind::Infrastructure,
+        }
+    }
+}
+
+impl ClassifiedError for ComposeCommandError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Timeout { .. } => ErrorKind::Timeout,
+            Self::Failed { .. } | Self::Spawn { .. } => ErrorKind::Infrastructure,
+        }
+    }
+}
+
+impl ClassifiedError for StateError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Artifacts { source, .. } => source.kind(),
+            Self::Read { .. } | Self::Write { .. } | Self::Serialize { .. } => {
+                ErrorKind::Infrastructure
+            }
+            Self::Parse { .. } | Self::TopologyMismatch { .. } => ErrorKind::Configuration,
+        }
+    }
+}
+
+impl ClassifiedError for StackReadinessError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Http(source) => source.kind(),
+            Self::Endpoint { .. } => ErrorKind::Configuration,
+            Self::Remote { source } | Self::Transactions { source } => source.kind(),
+            Self::Unhealthy { .. } => ErrorKind::NodeFailure,
+            Self::HealthTimeout { .. } => ErrorKind::Timeout,
+            Self::Health { source } => source.kind(),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{
+        Deployer as _, NodeClients, NodeTarget, RequiresNodeControl, RunContext, RunHandle, Runner,
+        Scenario, ScenarioError,
+    },
+};
+
+use crate::{deployer::ComposeDeployer, errors::ComposeRunnerError};
+
+/// A compose stack scoped to a single test.
+///
+/// The stack is torn down when the harness is dropped, including when the
+/// test panics, so `#[tokio::test]` functions can deploy a small topology,
+/// talk to its nodes and return without explicit cleanup. Every stack gets
+/// its own compose project name, cfgsync port and IPv6 subnet, and node
+/// ports are published on ephemeral host ports, so tests using a harness
+/// can run concurrently (e.g. under `cargo nextest`).
+///
+/// Teardown runs on its own thread, so the harness can be dropped from any
+/// runtime flavor. Set `COMPOSE_RUNNER_PRESERVE` to keep the stack for
+/// debugging, as with [`ComposeDeployer`].
+pub struct ComposeTestHarness {
+    runner: Runner,
+}
+
+impl ComposeTestHarness {
+    /// Deployer used by [`Self::start`]: the default compose deployer without
+    /// Prometheus and Grafana, whose fixed default ports would collide
+    /// between concurrent tests and which few tests need.
+    #[must_use]
+    pub fn deployer() -> ComposeDeployer {
+        ComposeDeployer::new()
+            .with_observability(false)
+            .with_dashboards(false)
+    }
+
+    /// Deploy `scenario`'s topology with [`Self::deployer`] and wait for the
+    /// nodes to be ready.
+    pub async fn start<Caps>(scenario: &Scenario<Caps>) -> Result<Self, ComposeRunnerError>
+    where
+        Caps: RequiresNodeControl + Send + Sync,
+    {
+        Self::start_with(&Self::deployer(), scenario).await
+    }
+
+    /// Deploy `scenario`'s topology with a custom `deployer`, e.g. to enable
+    /// tracing or pin node images.
+    pub async fn start_with<Caps>(
+        deployer: &ComposeDeployer,
+        scenario: &Scenario<Caps>,
+    ) -> Result<Self, ComposeRunnerError>
+    where
+        Caps: RequiresNodeControl + Send + Sync,
+    {
+        let runner = deployer.deploy(scenario).await?;
+        Ok(Self { runner })
+    }
+
+    /// Context of the deployed stack: node clients, block feed, node control
+    /// and logs.
+    #[must_use]
+    pub fn context(&self) -> Arc<RunContext> {
+        self.runner.context()
+    }
+
+    #[must_use]
+    pub fn node_clients(&self) -> NodeClients {
+        self.context().node_clients().clone()
+    }
+
+    /// Client of validator `index`; panics if the topology has no such node.
+    #[must_use]
+    pub fn validator(&self, index: usize) -> ApiClient {
+        self.client(NodeTarget::Validator(index))
+    }
+
+    /// Client of executor `index`; panics if the topology has no such node.
+    #[must_use]
+    pub fn executor(&self, index: usize) -> ApiClient {
+        self.client(NodeTarget::Executor(index))
+    }
+
+    fn client(&self, target: NodeTarget) -> ApiClient {
+        self.context()
+            .node_clients()
+            .client(target)
+            .cloned()
+            .unwrap_or_else(|| panic!("compose test stack has no {target}"))
+    }
+
+    /// Run `scenario`'s workloads and expectations against the stack. The
+    /// returned handle keeps the stack alive and tears it down when dropped.
+    pub async fn run<Caps>(self, scenario: &mut Scenario<Caps>) -> Result<RunHandle, ScenarioError>
+    where
+        Caps: Send + Sync,
+    {
+        self.runner.run(scenario).await
+    }
+
+    /// Tear the stack down now rather than when the harness goes out of
+    /// scope.
+    pub fn shutdown(self) {
+        drop(self);
+    }
+}
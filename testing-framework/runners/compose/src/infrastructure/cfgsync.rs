@@ -1,22 +1,97 @@
 use std::{path::Path, process::Command as StdCommand};
 
+use cfgsync::{
+    client::get_status,
+    status::{SyncPhase, SyncStatus},
+};
 use testing_framework_core::{
-    scenario::cfgsync::{apply_topology_overrides, load_cfgsync_template, write_cfgsync_template},
+    scenario::cfgsync::{
+        apply_topology_overrides, disable_otlp_metrics, enable_otlp_tracing, load_cfgsync_template,
+        write_cfgsync_template,
+    },
     topology::generation::GeneratedTopology,
 };
 use tracing::{debug, info, warn};
+use url::{Host, Url};
+
+use crate::infrastructure::ports::compose_runner_host;
 
-/// Handle that tracks a cfgsync server started for compose runs.
+/// Handle that tracks the cfgsync server of a compose run.
 #[derive(Debug)]
 pub enum CfgsyncServerHandle {
-    Container { name: String, stopped: bool },
+    Container {
+        name: String,
+        port: u16,
+        stopped: bool,
+    },
+    /// A server the runner neither started nor stops.
+    External { url: Url },
 }
 
 impl CfgsyncServerHandle {
+    /// Host port of the cfgsync container, if the runner started one.
+    pub const fn port(&self) -> Option<u16> {
+        match self {
+            Self::Container { port, .. } => Some(*port),
+            Self::External { .. } => None,
+        }
+    }
+
+    /// Name of the docker container running the server, if the runner
+    /// started one.
+    pub fn container_name(&self) -> Option<&str> {
+        match self {
+            Self::Container { name, .. } => Some(name),
+            Self::External { .. } => None,
+        }
+    }
+
+    /// URL of an external server.
+    pub const fn external_url(&self) -> Option<&Url> {
+        match self {
+            Self::Container { .. } => None,
+            Self::External { url } => Some(url),
+        }
+    }
+
+    fn status_url(&self) -> String {
+        match self {
+            Self::Container { port, .. } => format!("http://{}:{port}", compose_runner_host()),
+            Self::External { url } => url.as_str().to_owned(),
+        }
+    }
+
+    /// Query cfgsync registration progress, logging it for failure
+    /// diagnostics. Returns `None` when the server cannot be reached.
+    pub async fn report_status(&self) -> Option<SyncStatus> {
+        let url = self.status_url();
+        match get_status(&url).await {
+            Ok(status) if status.phase == SyncPhase::Completed => {
+                info!("{}", status.summary());
+                Some(status)
+            }
+            Ok(status) => {
+                warn!(
+                    phase = ?status.phase,
+                    registered = status.registered,
+                    expected = status.expected,
+                    missing = ?status.missing,
+                    "{}",
+                    status.summary()
+                );
+                Some(status)
+            }
+            Err(err) => {
+                debug!(error = %err, "cfgsync status unavailable");
+                None
+            }
+        }
+    }
+
     /// Stop the backing container if still running.
     pub fn shutdown(&mut self) {
         match self {
-            Self::Container { name, stopped } if !*stopped => {
+            Self::Container { name, stopped, .. } if !*stopped => {
                 info!(container = name, "stopping cfgsync container");
                 remove_container(name);
                 *stopped = true;
@@ -54,24 +129,91 @@ impl Drop for CfgsyncServerHandle {
     }
 }
 
+/// `url` as reached from inside the stack's containers: loopback hosts are
+/// replaced by the docker host.
+pub fn container_cfgsync_url(url: &Url) -> String {
+    let loopback = match url.host() {
+        Some(Host::Domain(domain)) => domain == "localhost",
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    };
+    let mut url = url.clone();
+    if loopback && url.set_host(Some("host.docker.internal")).is_err() {
+        warn!(%url, "cannot rewrite loopback cfgsync url for containers");
+    }
+    url.as_str().trim_end_matches('/').to_owned()
+}
+
 /// Updates the cfgsync template on disk with topology-driven overrides.
+/// Without `metrics`, nodes stop pushing metrics to Prometheus. With
+/// `node_control`, hosts registering after the initial round are served.
 pub fn update_cfgsync_config(
     path: &Path,
     topology: &GeneratedTopology,
     use_kzg_mount: bool,
+    node_control: bool,
     port: u16,
+    otlp_endpoint: Option<&str>,
+    metrics: bool,
 ) -> anyhow::Result<()> {
     debug!(
         path = %path.display(),
         use_kzg_mount,
+        node_control,
         port,
+        otlp_endpoint,
+        metrics,
         validators = topology.validators().len(),
         executors = topology.executors().len(),
         "updating cfgsync template"
     );
     let mut cfg = load_cfgsync_template(path)?;
     cfg.port = port;
-    apply_topology_overrides(&mut cfg, topology, use_kzg_mount);
+    apply_topology_overrides(&mut cfg, topology, use_kzg_mount, node_control);
+    if let Some(endpoint) = otlp_endpoint {
+        enable_otlp_tracing(&mut cfg, endpoint)?;
+    }
+    if !metrics {
+        disable_otlp_metrics(&mut cfg);
+    }
     write_cfgsync_template(path, &cfg)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container_url(raw: &str) -> String {
+        container_cfgsync_url(&Url::parse(raw).expect("valid url"))
+    }
+
+    #[test]
+    fn loopback_hosts_point_at_the_docker_host() {
+        assert_eq!(
+            container_url("http://127.0.0.1:4400/"),
+            "http://host.docker.internal:4400"
+        );
+        assert_eq!(
+            container_url("http://localhost:4400"),
+            "http://host.docker.internal:4400"
+        );
+        assert_eq!(
+            container_url("http://[::1]:4400"),
+            "http://host.docker.internal:4400"
+        );
+    }
+
+    #[test]
+    fn remote_hosts_are_kept() {
+        assert_eq!(
+            container_url("http://cfgsync.internal:4400/"),
+            "http://cfgsync.internal:4400"
+        );
+        assert_eq!(
+            container_url("http://10.0.0.5:4400"),
+            "http://10.0.0.5:4400"
+        );
+    }
+}
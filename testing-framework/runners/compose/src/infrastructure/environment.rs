@@ -1,38 +1,75 @@
 use std::{
     env,
-    net::{Ipv4Addr, TcpListener as StdTcpListener},
     path::{Path, PathBuf},
-    time::Duration,
+    sync::Arc,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context as _, anyhow};
+use cfgsync::{client::get_status, config::schema::validate_file, status::SyncStatus};
 use testing_framework_core::{
-    adjust_timeout, scenario::CleanupGuard, topology::generation::GeneratedTopology,
+    adjust_timeout,
+    artifacts::RunArtifacts,
+    constants::{
+        DEFAULT_CFGSYNC_PORT, DEFAULT_TEMPO_HTTP_PORT, DEFAULT_TEMPO_OTLP_PORT,
+        DEFAULT_TEMPO_SERVICE_NAME,
+    },
+    ports::{PortAllocator, PortProtocol, PortReservation},
+    scenario::{
+        CleanupGuard, DeploymentTimeline, NodeEnv, NodeLogs, NodeStorage, Profile, ScenarioTimings,
+    },
+    topology::generation::GeneratedTopology,
 };
 use tokio::{process::Command, time::timeout};
 use tracing::{debug, info, warn};
+use url::Url;
 use uuid::Uuid;
 
 use crate::{
-    deployer::setup::DEFAULT_PROMETHEUS_PORT,
-    descriptor::ComposeDescriptor,
+    deployer::setup::{DEFAULT_PROMETHEUS_PORT, allocate_prometheus_port},
+    descriptor::{ComposeDescriptor, Healthcheck, ImageOverrides, STORAGE_DIR, ScenarioDashboard},
     docker::{
         commands::{compose_up, dump_compose_logs, run_docker_command},
         ensure_compose_image,
+        logs::LogCollector,
         platform::resolve_image,
         workspace::ComposeWorkspace,
     },
-    errors::{ComposeRunnerError, ConfigError, WorkspaceError},
+    errors::{ComposeRunnerError, ConfigError, StateError, WorkspaceError},
     infrastructure::{
-        cfgsync::{CfgsyncServerHandle, update_cfgsync_config},
-        template::write_compose_file,
+        cfgsync::{CfgsyncServerHandle, container_cfgsync_url, update_cfgsync_config},
+        stagger::{StaggeredPlan, StaggeredStart},
+        state::ComposeRunState,
+        template::{ComposeTemplate, write_compose_file_with},
     },
     lifecycle::cleanup::RunnerCleanup,
 };
 
-const CFGSYNC_START_TIMEOUT: Duration = Duration::from_secs(180);
 const STACK_BRINGUP_MAX_ATTEMPTS: usize = 3;
 
+/// Deployer-level knobs that shape the rendered stack.
+#[derive(Clone, Debug, Default)]
+pub struct StackOptions {
+    pub images: ImageOverrides,
+    pub observability: bool,
+    pub dashboards: bool,
+    pub tracing: bool,
+    /// The scenario may add or restart nodes while it runs.
+    pub node_control: bool,
+    pub timings: ScenarioTimings,
+    pub node_env: NodeEnv,
+    /// Environment profile of the scenario; its artifact settings apply to
+    /// the workspace.
+    pub profile: Option<Profile>,
+    /// Serve node configs from this cfgsync server instead of starting one.
+    pub external_cfgsync: Option<Url>,
+    pub template: ComposeTemplate,
+    /// Start node containers in batches rather than all at once.
+    pub staggered_start: Option<StaggeredStart>,
+    /// Healthcheck run in node containers; `None` renders none.
+    pub healthcheck: Option<Healthcheck>,
+}
+
 /// Paths and flags describing the prepared compose workspace.
 pub struct WorkspaceState {
     pub workspace: ComposeWorkspace,
@@ -44,12 +81,16 @@ pub struct WorkspaceState {
 /// Holds paths and handles for a running docker-compose stack.
 pub struct StackEnvironment {
     compose_path: PathBuf,
+    descriptor: ComposeDescriptor,
     project_name: String,
     root: PathBuf,
+    artifacts: Arc<RunArtifacts>,
     workspace: Option<ComposeWorkspace>,
     cfgsync_handle: Option<CfgsyncServerHandle>,
-    prometheus_port: u16,
-    grafana_port: u16,
+    prometheus_port: Option<u16>,
+    grafana_port: Option<u16>,
+    tempo_port: Option<u16>,
+    log_collector: Option<LogCollector>,
 }
 
 impl StackEnvironment {
@@ -57,10 +98,11 @@ impl StackEnvironment {
     pub fn from_workspace(
         state: WorkspaceState,
         compose_path: PathBuf,
+        descriptor: ComposeDescriptor,
         project_name: String,
         cfgsync_handle: Option<CfgsyncServerHandle>,
-        prometheus_port: u16,
-        grafana_port: u16,
+        prometheus_port: Option<u16>,
+        grafana_port: Option<u16>,
     ) -> Self {
         let WorkspaceState {
             workspace, root, ..
@@ -68,29 +110,87 @@ impl StackEnvironment {
 
         Self {
             compose_path,
+            descriptor,
             project_name,
             root,
+            artifacts: Arc::clone(workspace.artifacts()),
             workspace: Some(workspace),
             cfgsync_handle,
             prometheus_port,
             grafana_port,
+            tempo_port: None,
+            log_collector: None,
+        }
+    }
+
+    /// Re-attach to the stack recorded in `state`, whose workspace was
+    /// reopened from the run artifacts.
+    pub fn resume(
+        state: ComposeRunState,
+        workspace: ComposeWorkspace,
+        descriptor: ComposeDescriptor,
+    ) -> Self {
+        let external = state
+            .cfgsync_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok())
+            .map(|url| CfgsyncServerHandle::External { url });
+        let cfgsync_handle = external.or_else(|| {
+            state
+                .cfgsync_container
+                .map(|name| CfgsyncServerHandle::Container {
+                    name,
+                    port: state.cfgsync_port,
+                    stopped: false,
+                })
+        });
+
+        Self {
+            compose_path: state.compose_path,
+            descriptor,
+            project_name: state.project_name,
+            root: state.root,
+            artifacts: Arc::clone(workspace.artifacts()),
+            workspace: Some(workspace),
+            cfgsync_handle,
+            prometheus_port: state.prometheus_port,
+            grafana_port: state.grafana_port,
+            tempo_port: state.tempo_port,
+            log_collector: None,
         }
     }
 
+    /// Record the host port of the Tempo query API.
+    #[must_use]
+    pub const fn with_tempo_port(mut self, port: Option<u16>) -> Self {
+        self.tempo_port = port;
+        self
+    }
+
     pub fn compose_path(&self) -> &Path {
         &self.compose_path
     }
 
-    /// Host port exposed by Prometheus.
-    pub const fn prometheus_port(&self) -> u16 {
+    /// Descriptor the compose file was rendered from.
+    pub const fn descriptor(&self) -> &ComposeDescriptor {
+        &self.descriptor
+    }
+
+    /// Host port exposed by Prometheus, unless observability is disabled.
+    pub const fn prometheus_port(&self) -> Option<u16> {
         self.prometheus_port
     }
 
-    /// Host port exposed by Grafana.
-    pub const fn grafana_port(&self) -> u16 {
+    /// Host port exposed by Grafana, unless observability is disabled.
+    pub const fn grafana_port(&self) -> Option<u16> {
         self.grafana_port
     }
 
+    /// Host port of the Tempo query API, when tracing is enabled.
+    pub const fn tempo_port(&self) -> Option<u16> {
+        self.tempo_port
+    }
+
     /// Docker compose project name.
     pub fn project_name(&self) -> &str {
         &self.project_name
@@ -101,31 +201,118 @@ impl StackEnvironment {
         &self.root
     }
 
+    /// Run artifacts directory the workspace lives in.
+    pub const fn artifacts(&self) -> &Arc<RunArtifacts> {
+        &self.artifacts
+    }
+
+    /// Write what is needed to resume this stack into the run artifacts, so
+    /// a crashed harness can re-attach with `ComposeDeployer::resume`.
+    pub fn persist_state(&self, descriptors: &GeneratedTopology) -> Result<(), StateError> {
+        ComposeRunState {
+            run_id: self.artifacts.run_id().to_owned(),
+            project_name: self.project_name.clone(),
+            compose_path: self.compose_path.clone(),
+            root: self.root.clone(),
+            cfgsync_container: self
+                .cfgsync_handle
+                .as_ref()
+                .and_then(CfgsyncServerHandle::container_name)
+                .map(ToOwned::to_owned),
+            cfgsync_port: self
+                .cfgsync_handle
+                .as_ref()
+                .and_then(CfgsyncServerHandle::port)
+                .unwrap_or(DEFAULT_CFGSYNC_PORT),
+            cfgsync_url: self
+                .cfgsync_handle
+                .as_ref()
+                .and_then(CfgsyncServerHandle::external_url)
+                .map(ToString::to_string),
+            prometheus_port: self.prometheus_port,
+            grafana_port: self.grafana_port,
+            tempo_port: self.tempo_port,
+            validators: descriptors.validators().len(),
+            executors: descriptors.executors().len(),
+        }
+        .save(&self.artifacts)
+    }
+
+    /// Host data directories of the given services, bind-mounted into the
+    /// containers.
+    pub fn node_storage(&self, services: &[String]) -> NodeStorage {
+        let storage = self.root.join(STORAGE_DIR);
+        services.iter().fold(NodeStorage::new(), |nodes, service| {
+            nodes.with_node(service, storage.join(service))
+        })
+    }
+
+    /// Start streaming the given services' logs into the workspace, returning
+    /// the per-node log files.
+    pub fn start_log_collection(
+        &mut self,
+        services: &[String],
+        compress: bool,
+    ) -> Result<NodeLogs, ComposeRunnerError> {
+        let collector = LogCollector::start(
+            &self.compose_path,
+            &self.project_name,
+            &self.root,
+            services,
+            compress,
+        )?;
+        let node_logs = collector.node_logs();
+        self.log_collector = Some(collector);
+        Ok(node_logs)
+    }
+
+    fn node_image(&self) -> Option<String> {
+        self.descriptor
+            .validators()
+            .iter()
+            .chain(self.descriptor.executors())
+            .next()
+            .map(|node| node.image().to_owned())
+    }
+
     /// Convert into a cleanup guard while keeping the environment borrowed.
     pub fn take_cleanup(&mut self) -> RunnerCleanup {
         RunnerCleanup::new(
             self.compose_path.clone(),
             self.project_name.clone(),
             self.root.clone(),
+            self.node_image(),
             self.workspace
                 .take()
                 .expect("workspace must be available while cleaning up"),
             self.cfgsync_handle.take(),
+            self.log_collector.take(),
         )
     }
 
     /// Convert into a cleanup guard, consuming the environment.
     pub fn into_cleanup(self) -> RunnerCleanup {
+        let node_image = self.node_image();
         RunnerCleanup::new(
             self.compose_path,
             self.project_name,
             self.root,
+            node_image,
             self.workspace
                 .expect("workspace must be available while cleaning up"),
             self.cfgsync_handle,
+            self.log_collector,
         )
     }
 
+    /// Registration progress reported by the cfgsync server, if reachable.
+    pub async fn cfgsync_status(&self) -> Option<SyncStatus> {
+        match self.cfgsync_handle.as_ref() {
+            Some(cfgsync) => cfgsync.report_status().await,
+            None => None,
+        }
+    }
+
     /// Dump compose logs and trigger cleanup after a failure.
     pub async fn fail(&mut self, reason: &str) {
         use tracing::error;
@@ -139,27 +326,6 @@ impl StackEnvironment {
     }
 }
 
-/// Represents a claimed port, optionally guarded by an open socket.
-pub struct PortReservation {
-    port: u16,
-    _guard: Option<StdTcpListener>,
-}
-
-impl PortReservation {
-    /// Holds a port and an optional socket guard to keep it reserved.
-    pub const fn new(port: u16, guard: Option<StdTcpListener>) -> Self {
-        Self {
-            port,
-            _guard: guard,
-        }
-    }
-
-    /// The reserved port number.
-    pub const fn port(&self) -> u16 {
-        self.port
-    }
-}
-
 /// Verifies the topology has at least one validator so compose can start.
 pub fn ensure_supported_topology(
     descriptors: &GeneratedTopology,
@@ -175,8 +341,8 @@ pub fn ensure_supported_topology(
 }
 
 /// Create a temporary workspace with copied testnet assets and derived paths.
-pub fn prepare_workspace_state() -> Result<WorkspaceState, WorkspaceError> {
-    let workspace = ComposeWorkspace::create().map_err(WorkspaceError::new)?;
+pub fn prepare_workspace_state(profile: Option<Profile>) -> Result<WorkspaceState, WorkspaceError> {
+    let workspace = ComposeWorkspace::create(profile).map_err(WorkspaceError::new)?;
     let root = workspace.root_path().to_path_buf();
     let cfgsync_path = workspace.stack_dir().join("cfgsync.yaml");
     let use_kzg = workspace.root_path().join("kzgrs_test_params").exists();
@@ -199,9 +365,14 @@ pub fn prepare_workspace_state() -> Result<WorkspaceState, WorkspaceError> {
 }
 
 /// Log wrapper for `prepare_workspace_state`.
-pub fn prepare_workspace_logged() -> Result<WorkspaceState, ComposeRunnerError> {
-    info!("preparing compose workspace");
-    prepare_workspace_state().map_err(Into::into)
+pub fn prepare_workspace_logged(
+    profile: Option<Profile>,
+) -> Result<WorkspaceState, ComposeRunnerError> {
+    info!(
+        profile = profile.map(Profile::name),
+        "preparing compose workspace"
+    );
+    prepare_workspace_state(profile).map_err(Into::into)
 }
 
 /// Render cfgsync config based on the topology and chosen port, logging
@@ -210,61 +381,109 @@ pub fn update_cfgsync_logged(
     workspace: &WorkspaceState,
     descriptors: &GeneratedTopology,
     cfgsync_port: u16,
+    options: &StackOptions,
 ) -> Result<(), ComposeRunnerError> {
-    info!(cfgsync_port, "updating cfgsync configuration");
-    configure_cfgsync(workspace, descriptors, cfgsync_port).map_err(Into::into)
+    info!(
+        cfgsync_port,
+        tracing = options.tracing,
+        observability = options.observability,
+        "updating cfgsync configuration"
+    );
+    configure_cfgsync(workspace, descriptors, cfgsync_port, options).map_err(Into::into)
 }
 
 /// Start the cfgsync server container using the generated config.
 pub async fn start_cfgsync_stage(
     workspace: &WorkspaceState,
     cfgsync_port: u16,
+    start_timeout: Duration,
 ) -> Result<CfgsyncServerHandle, ComposeRunnerError> {
     info!(cfgsync_port = cfgsync_port, "launching cfgsync server");
-    let handle = launch_cfgsync(&workspace.cfgsync_path, cfgsync_port).await?;
+    let handle = launch_cfgsync(&workspace.cfgsync_path, cfgsync_port, start_timeout).await?;
     debug!(container = ?handle, "cfgsync server launched");
     Ok(handle)
 }
 
-/// Update cfgsync YAML on disk with topology-derived values.
+/// Update cfgsync YAML on disk with topology-derived values, pointing node
+/// tracing at the Tempo service when tracing is enabled and dropping the
+/// metrics export when the stack runs without Prometheus. The result is
+/// validated so schema errors surface before any container starts.
 pub fn configure_cfgsync(
     workspace: &WorkspaceState,
     descriptors: &GeneratedTopology,
     cfgsync_port: u16,
+    options: &StackOptions,
 ) -> Result<(), ConfigError> {
+    let otlp_endpoint = options
+        .tracing
+        .then(|| format!("http://{DEFAULT_TEMPO_SERVICE_NAME}:{DEFAULT_TEMPO_OTLP_PORT}"));
     update_cfgsync_config(
         &workspace.cfgsync_path,
         descriptors,
         workspace.use_kzg,
+        options.node_control,
         cfgsync_port,
+        otlp_endpoint.as_deref(),
+        options.observability,
     )
     .map_err(|source| ConfigError::Cfgsync {
         path: workspace.cfgsync_path.clone(),
         source,
-    })
+    })?;
+    validate_file(&workspace.cfgsync_path).map_err(|source| ConfigError::InvalidCfgsync {
+        path: workspace.cfgsync_path.clone(),
+        source,
+    })?;
+    Ok(())
 }
 
-/// Bind an ephemeral port for cfgsync, returning the chosen value.
-pub fn allocate_cfgsync_port() -> Result<u16, ConfigError> {
-    let listener =
-        StdTcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(|source| ConfigError::Port {
-            source: source.into(),
+/// Check that the external cfgsync server at `url` answers before nodes are
+/// pointed at it. Its config must already describe this topology: a server
+/// expecting fewer hosts than there are nodes is rejected.
+pub async fn check_external_cfgsync(
+    url: &Url,
+    descriptors: &GeneratedTopology,
+) -> Result<CfgsyncServerHandle, ConfigError> {
+    let status = get_status(url.as_str())
+        .await
+        .map_err(|err| ConfigError::ExternalCfgsync {
+            url: url.clone(),
+            source: anyhow!(err),
         })?;
+    let nodes = descriptors.validators().len() + descriptors.executors().len();
+    check_expected_hosts(url, status.expected, nodes)?;
+    info!(%url, phase = ?status.phase, "using external cfgsync server");
+    Ok(CfgsyncServerHandle::External { url: url.clone() })
+}
+
+fn check_expected_hosts(url: &Url, expected: usize, nodes: usize) -> Result<(), ConfigError> {
+    if expected < nodes {
+        return Err(ConfigError::ExternalCfgsyncTooSmall {
+            url: url.clone(),
+            expected,
+            nodes,
+        });
+    }
+    Ok(())
+}
 
-    let port = listener
-        .local_addr()
+/// Reserve a port for cfgsync; the reservation is released right before
+/// the cfgsync container starts.
+pub fn allocate_cfgsync_port() -> Result<PortReservation, ConfigError> {
+    let reservation = PortAllocator::global()
+        .reserve(PortProtocol::Tcp)
         .map_err(|source| ConfigError::Port {
             source: source.into(),
-        })?
-        .port();
-    debug!(port, "allocated cfgsync port");
-    Ok(port)
+        })?;
+    debug!(port = reservation.port(), "allocated cfgsync port");
+    Ok(reservation)
 }
 
 /// Launch cfgsync in a detached docker container on the provided port.
 pub async fn launch_cfgsync(
     cfgsync_path: &Path,
     port: u16,
+    start_timeout: Duration,
 ) -> Result<CfgsyncServerHandle, ConfigError> {
     let testnet_dir = cfgsync_path
         .parent()
@@ -305,7 +524,7 @@ pub async fn launch_cfgsync(
 
     run_docker_command(
         command,
-        adjust_timeout(CFGSYNC_START_TIMEOUT),
+        adjust_timeout(start_timeout),
         "docker run cfgsync server",
     )
     .await
@@ -318,6 +537,7 @@ pub async fn launch_cfgsync(
 
     Ok(CfgsyncServerHandle::Container {
         name: container_name,
+        port,
         stopped: false,
     })
 }
@@ -326,10 +546,11 @@ pub async fn launch_cfgsync(
 pub fn write_compose_artifacts(
     workspace: &WorkspaceState,
     descriptors: &GeneratedTopology,
+    options: &StackOptions,
     cfgsync_port: u16,
-    prometheus_port: u16,
+    prometheus_port: Option<u16>,
     grafana_port: u16,
-) -> Result<PathBuf, ConfigError> {
+) -> Result<(PathBuf, ComposeDescriptor), ConfigError> {
     debug!(
         cfgsync_port,
         prometheus_port,
@@ -337,29 +558,122 @@ pub fn write_compose_artifacts(
         workspace_root = %workspace.root.display(),
         "building compose descriptor"
     );
-    let descriptor = ComposeDescriptor::builder(descriptors)
-        .with_kzg_mount(workspace.use_kzg)
-        .with_cfgsync_port(cfgsync_port)
-        .with_prometheus_port(prometheus_port)
-        .with_grafana_port(grafana_port)
-        .build()
-        .map_err(|source| ConfigError::Descriptor { source })?;
+    let descriptor = build_descriptor(
+        descriptors,
+        options,
+        workspace.use_kzg,
+        cfgsync_port,
+        prometheus_port,
+        grafana_port,
+    )?;
+
+    if options.observability && options.dashboards {
+        write_scenario_dashboard(workspace, descriptors)?;
+    }
 
     let compose_path = workspace.root.join("compose.generated.yml");
-    write_compose_file(&descriptor, &compose_path)
+    write_compose_file_with(&options.template, &descriptor, &compose_path)
+        .and_then(|()| options.template.persist(&workspace.root))
         .map_err(|source| ConfigError::Template { source })?;
     debug!(compose_file = %compose_path.display(), "rendered compose file");
-    Ok(compose_path)
+    Ok((compose_path, descriptor))
+}
+
+fn build_descriptor(
+    descriptors: &GeneratedTopology,
+    options: &StackOptions,
+    use_kzg: bool,
+    cfgsync_port: u16,
+    prometheus_port: Option<u16>,
+    grafana_port: u16,
+) -> Result<ComposeDescriptor, ConfigError> {
+    let builder = ComposeDescriptor::builder(descriptors)
+        .with_kzg_mount(use_kzg)
+        .with_cfgsync_port(cfgsync_port)
+        .with_grafana_port(grafana_port)
+        .with_observability(options.observability);
+    let builder = match prometheus_port {
+        Some(port) => builder.with_prometheus_port(port),
+        None => builder,
+    };
+    let builder = match &options.external_cfgsync {
+        Some(url) => builder.with_cfgsync_url(container_cfgsync_url(url)),
+        None => builder,
+    };
+    builder
+        .with_dashboards(options.dashboards)
+        .with_tracing(options.tracing)
+        .with_image_overrides(options.images.clone())
+        .with_node_env_overrides(options.node_env.clone())
+        .with_healthcheck(options.healthcheck)
+        .build()
+        .map_err(|source| ConfigError::Descriptor { source })
+}
+
+/// Rebuild the environment of a stack deployed by an earlier run from its
+/// persisted state. The compose file on disk is reused as is; the descriptor
+/// is rebuilt from `descriptors` so node control can still scale the stack.
+pub fn resume_environment(
+    state: ComposeRunState,
+    workspace: ComposeWorkspace,
+    descriptors: &GeneratedTopology,
+    options: &StackOptions,
+) -> Result<StackEnvironment, ComposeRunnerError> {
+    let use_kzg = workspace.root_path().join("kzgrs_test_params").exists();
+    // The stack keeps the monitoring services and cfgsync server it was
+    // deployed with.
+    let options = StackOptions {
+        observability: state.prometheus_port.is_some(),
+        external_cfgsync: state
+            .cfgsync_url
+            .as_deref()
+            .and_then(|url| Url::parse(url).ok()),
+        ..options.clone()
+    };
+    let descriptor = build_descriptor(
+        descriptors,
+        &options,
+        use_kzg,
+        state.cfgsync_port,
+        state.prometheus_port,
+        state.grafana_port.unwrap_or(0),
+    )?;
+    info!(
+        run_id = %state.run_id,
+        project = %state.project_name,
+        compose_file = %state.compose_path.display(),
+        "resuming compose stack"
+    );
+    Ok(StackEnvironment::resume(state, workspace, descriptor))
+}
+
+/// Write the scenario Grafana dashboard where the compose template mounts it.
+fn write_scenario_dashboard(
+    workspace: &WorkspaceState,
+    descriptors: &GeneratedTopology,
+) -> Result<(), ConfigError> {
+    let dir = workspace.root.join("stack/monitoring/grafana/scenario");
+    let path = dir.join("scenario-dashboard.json");
+    let dashboard = ScenarioDashboard::from_topology(descriptors).render();
+    std::fs::create_dir_all(&dir)
+        .and_then(|()| std::fs::write(&path, dashboard.to_string()))
+        .map_err(|source| ConfigError::Dashboard {
+            path: path.clone(),
+            source,
+        })?;
+    debug!(dashboard = %path.display(), "wrote scenario grafana dashboard");
+    Ok(())
 }
 
 /// Log and wrap `write_compose_artifacts` errors for the runner.
 pub fn render_compose_logged(
     workspace: &WorkspaceState,
     descriptors: &GeneratedTopology,
+    options: &StackOptions,
     cfgsync_port: u16,
-    prometheus_port: u16,
+    prometheus_port: Option<u16>,
     grafana_port: u16,
-) -> Result<PathBuf, ComposeRunnerError> {
+) -> Result<(PathBuf, ComposeDescriptor), ComposeRunnerError> {
     info!(
         cfgsync_port,
         prometheus_port, grafana_port, "rendering compose file with ports"
@@ -367,6 +681,7 @@ pub fn render_compose_logged(
     write_compose_artifacts(
         workspace,
         descriptors,
+        options,
         cfgsync_port,
         prometheus_port,
         grafana_port,
@@ -374,14 +689,24 @@ pub fn render_compose_logged(
     .map_err(Into::into)
 }
 
-/// Bring up docker compose; shut down cfgsync if start-up fails.
+/// Bring up docker compose, in staggered batches when `staggered` is set;
+/// shut down cfgsync if start-up fails.
 pub async fn bring_up_stack(
     compose_path: &Path,
     project_name: &str,
     workspace_root: &Path,
     cfgsync_handle: &mut CfgsyncServerHandle,
+    up_timeout: Duration,
+    staggered: Option<&StaggeredPlan>,
 ) -> Result<(), ComposeRunnerError> {
-    if let Err(err) = compose_up(compose_path, project_name, workspace_root).await {
+    let result = match staggered {
+        Some(plan) => {
+            plan.bring_up(compose_path, project_name, workspace_root, up_timeout)
+                .await
+        }
+        None => compose_up(compose_path, project_name, workspace_root, up_timeout).await,
+    };
+    if let Err(err) = result {
         cfgsync_handle.shutdown();
         return Err(ComposeRunnerError::Compose(err));
     }
@@ -395,27 +720,59 @@ pub async fn bring_up_stack_logged(
     project_name: &str,
     workspace_root: &Path,
     cfgsync_handle: &mut CfgsyncServerHandle,
+    up_timeout: Duration,
+    staggered: Option<&StaggeredPlan>,
 ) -> Result<(), ComposeRunnerError> {
-    info!(project = %project_name, "bringing up docker compose stack");
-    bring_up_stack(compose_path, project_name, workspace_root, cfgsync_handle).await
+    info!(
+        project = %project_name,
+        staggered = staggered.is_some(),
+        "bringing up docker compose stack"
+    );
+    bring_up_stack(
+        compose_path,
+        project_name,
+        workspace_root,
+        cfgsync_handle,
+        up_timeout,
+        staggered,
+    )
+    .await
 }
 
 /// Prepare workspace, cfgsync, compose artifacts, and launch the stack.
+/// `prometheus_port` is `None` when observability is disabled. With an
+/// external cfgsync server no cfgsync container is started. Each step is
+/// recorded in `timeline`.
 pub async fn prepare_environment(
     descriptors: &GeneratedTopology,
-    mut prometheus_port: PortReservation,
+    options: &StackOptions,
+    mut prometheus_port: Option<PortReservation>,
     prometheus_port_locked: bool,
+    timeline: &mut DeploymentTimeline,
 ) -> Result<StackEnvironment, ComposeRunnerError> {
-    let workspace = prepare_workspace_logged()?;
-    let cfgsync_port = allocate_cfgsync_port()?;
+    let workspace_started = Instant::now();
+    let workspace = prepare_workspace_logged(options.profile)?;
+    // Also keys the stack's IPv6 subnet, so it is allocated with an external
+    // cfgsync server too.
+    let cfgsync_reservation = allocate_cfgsync_port()?;
+    let cfgsync_port = cfgsync_reservation.port();
+    let mut cfgsync_reservation = Some(cfgsync_reservation);
     let grafana_env = env::var("COMPOSE_GRAFANA_PORT")
         .ok()
         .and_then(|raw| raw.parse::<u16>().ok());
     if let Some(port) = grafana_env {
         info!(port, "using grafana port from env");
     }
-    update_cfgsync_logged(&workspace, descriptors, cfgsync_port)?;
-    ensure_compose_image().await?;
+    if options.external_cfgsync.is_none() {
+        update_cfgsync_logged(&workspace, descriptors, cfgsync_port, options)?;
+    }
+    timeline.record("workspace", workspace_started);
+    timeline
+        .measure(
+            "image_build",
+            ensure_compose_image(&options.images, options.timings.image_build()),
+        )
+        .await?;
 
     let attempts = if prometheus_port_locked {
         1
@@ -425,38 +782,78 @@ pub async fn prepare_environment(
     let mut last_err = None;
 
     for _ in 0..attempts {
-        let prometheus_port_value = prometheus_port.port();
+        let prometheus_port_value = prometheus_port.as_ref().map(PortReservation::port);
         let grafana_port_value = grafana_env.unwrap_or(0);
-        let compose_path = render_compose_logged(
+        let (compose_path, descriptor) = render_compose_logged(
             &workspace,
             descriptors,
+            options,
             cfgsync_port,
             prometheus_port_value,
             grafana_port_value,
         )?;
 
+        let staggered = options.staggered_start.map(|staggered| {
+            staggered.plan(&descriptor, &mut descriptors.rng("compose/staggered_start"))
+        });
+
         let project_name = format!("nomos-compose-{}", Uuid::new_v4());
-        let mut cfgsync_handle = start_cfgsync_stage(&workspace, cfgsync_port).await?;
-
-        drop(prometheus_port);
-        match bring_up_stack_logged(
-            &compose_path,
-            &project_name,
-            &workspace.root,
-            &mut cfgsync_handle,
-        )
-        .await
-        {
-            Ok(()) => {
-                let grafana_port_resolved = resolve_service_port(
+        let cfgsync_started = Instant::now();
+        let mut cfgsync_handle = match &options.external_cfgsync {
+            Some(url) => check_external_cfgsync(url, descriptors).await?,
+            None => {
+                drop(cfgsync_reservation.take());
+                start_cfgsync_stage(&workspace, cfgsync_port, options.timings.cfgsync_start())
+                    .await?
+            }
+        };
+        timeline.record("cfgsync", cfgsync_started);
+
+        drop(prometheus_port.take());
+        let bring_up = timeline
+            .measure(
+                "compose_up",
+                bring_up_stack_logged(
                     &compose_path,
                     &project_name,
                     &workspace.root,
-                    "grafana",
-                    3000,
-                )
-                .await
-                .unwrap_or(grafana_port_value);
+                    &mut cfgsync_handle,
+                    options.timings.bring_up(),
+                    staggered.as_ref(),
+                ),
+            )
+            .await;
+        match bring_up {
+            Ok(()) => {
+                let grafana_port_resolved = if options.observability {
+                    Some(
+                        resolve_service_port(
+                            &compose_path,
+                            &project_name,
+                            &workspace.root,
+                            "grafana",
+                            3000,
+                        )
+                        .await
+                        .unwrap_or(grafana_port_value),
+                    )
+                } else {
+                    None
+                };
+                let tempo_port = if options.tracing {
+                    resolve_service_port(
+                        &compose_path,
+                        &project_name,
+                        &workspace.root,
+                        DEFAULT_TEMPO_SERVICE_NAME,
+                        DEFAULT_TEMPO_HTTP_PORT,
+                    )
+                    .await
+                    .inspect_err(|err| warn!(error = %err, "failed to resolve tempo port"))
+                    .ok()
+                } else {
+                    None
+                };
 
                 info!(
                     project = %project_name,
@@ -464,16 +861,19 @@ pub async fn prepare_environment(
                     cfgsync_port,
                     prometheus_port = prometheus_port_value,
                     grafana_port = grafana_port_resolved,
+                    tempo_port,
                     "compose stack is up"
                 );
                 return Ok(StackEnvironment::from_workspace(
                     workspace,
                     compose_path,
+                    descriptor,
                     project_name,
                     Some(cfgsync_handle),
                     prometheus_port_value,
                     grafana_port_resolved,
-                ));
+                )
+                .with_tempo_port(tempo_port));
             }
             Err(err) => {
                 // Attempt to capture container logs even when bring-up fails early.
@@ -487,10 +887,13 @@ pub async fn prepare_environment(
                     error = %last_err.as_ref().unwrap(),
                     "compose bring-up failed; retrying with a new prometheus port"
                 );
-                prometheus_port = allocate_prometheus_port()
-                    .unwrap_or_else(|| PortReservation::new(DEFAULT_PROMETHEUS_PORT, None));
+                if prometheus_port_value.is_some() {
+                    prometheus_port = Some(allocate_prometheus_port().unwrap_or_else(|| {
+                        PortReservation::unguarded(PortProtocol::Tcp, DEFAULT_PROMETHEUS_PORT)
+                    }));
+                }
                 debug!(
-                    next_prometheus_port = prometheus_port.port(),
+                    next_prometheus_port = prometheus_port.as_ref().map(PortReservation::port),
                     "retrying compose bring-up"
                 );
             }
@@ -500,17 +903,7 @@ pub async fn prepare_environment(
     Err(last_err.expect("prepare_environment should return or fail with error"))
 }
 
-fn allocate_prometheus_port() -> Option<PortReservation> {
-    reserve_prometheus_port(DEFAULT_PROMETHEUS_PORT).or_else(|| reserve_prometheus_port(0))
-}
-
-fn reserve_prometheus_port(port: u16) -> Option<PortReservation> {
-    let listener = StdTcpListener::bind((Ipv4Addr::LOCALHOST, port)).ok()?;
-    let actual_port = listener.local_addr().ok()?.port();
-    Some(PortReservation::new(actual_port, Some(listener)))
-}
-
-async fn resolve_service_port(
+pub(crate) async fn resolve_service_port(
     compose_file: &Path,
     project_name: &str,
     root: &Path,
@@ -569,3 +962,24 @@ async fn resolve_service_port(
         source: anyhow!("unable to parse docker compose port output: {stdout}"),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn external_cfgsync_must_expect_every_node() {
+        let url = Url::parse("http://127.0.0.1:4400").expect("valid url");
+
+        assert!(check_expected_hosts(&url, 4, 4).is_ok());
+        assert!(check_expected_hosts(&url, 6, 4).is_ok());
+        assert!(matches!(
+            check_expected_hosts(&url, 3, 4),
+            Err(ConfigError::ExternalCfgsyncTooSmall {
+                expected: 3,
+                nodes: 4,
+                ..
+            })
+        ));
+    }
+}
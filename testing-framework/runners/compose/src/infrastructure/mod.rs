@@ -1,4 +1,6 @@
 pub mod cfgsync;
 pub mod environment;
 pub mod ports;
+pub mod stagger;
+pub mod state;
 pub mod template;
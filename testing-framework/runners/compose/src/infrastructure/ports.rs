@@ -4,14 +4,18 @@ use anyhow::{Context as _, anyhow};
 use reqwest::Url;
 use testing_framework_core::{
     adjust_timeout,
-    scenario::http_probe::NodeRole as HttpNodeRole,
-    topology::generation::{GeneratedTopology, NodeRole as TopologyNodeRole},
+    scenario::{NodeTarget, http_probe::NodeRole as HttpNodeRole},
+    topology::{
+        generation::{GeneratedTopology, NodeRole as TopologyNodeRole},
+        readiness::ReadinessConfig,
+    },
 };
 use tokio::{process::Command, time::timeout};
 use tracing::{debug, info};
 use url::ParseError;
 
 use crate::{
+    docker::platform::published_ports_host,
     errors::{ComposeRunnerError, StackReadinessError},
     infrastructure::environment::StackEnvironment,
 };
@@ -40,6 +44,29 @@ impl HostPortMapping {
     pub fn executor_api_ports(&self) -> Vec<u16> {
         self.executors.iter().map(|ports| ports.api).collect()
     }
+
+    /// Mapping without the nodes `descriptors` declares as late-joining,
+    /// matching [`GeneratedTopology::initial_topology`].
+    pub fn initial(&self, descriptors: &GeneratedTopology) -> Self {
+        let config = descriptors.config();
+        let keep = |target: NodeTarget| !config.joins_late(target);
+        Self {
+            validators: self
+                .validators
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| keep(NodeTarget::Validator(*index)))
+                .map(|(_, ports)| ports.clone())
+                .collect(),
+            executors: self
+                .executors
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| keep(NodeTarget::Executor(*index)))
+                .map(|(_, ports)| ports.clone())
+                .collect(),
+        }
+    }
 }
 
 /// Resolve host ports for all nodes from docker compose.
@@ -146,6 +173,7 @@ async fn resolve_service_port(
 pub async fn ensure_remote_readiness_with_ports(
     descriptors: &GeneratedTopology,
     mapping: &HostPortMapping,
+    readiness: ReadinessConfig,
 ) -> Result<(), StackReadinessError> {
     let validator_urls = mapping
         .validators
@@ -175,6 +203,7 @@ pub async fn ensure_remote_readiness_with_ports(
             &executor_urls,
             Some(&validator_membership_urls),
             Some(&executor_membership_urls),
+            readiness,
         )
         .await
         .map_err(|source| StackReadinessError::Remote { source })
@@ -196,7 +225,7 @@ fn node_identifier(role: TopologyNodeRole, index: usize) -> String {
 }
 
 pub(crate) fn compose_runner_host() -> String {
-    let host = std::env::var("COMPOSE_RUNNER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
+    let host = published_ports_host();
     debug!(host, "compose runner host resolved for readiness URLs");
     host
 }
@@ -0,0 +1,162 @@
+use std::{num::NonZeroUsize, path::Path, time::Duration};
+
+use rand::Rng;
+use tokio::time::sleep;
+use tracing::info;
+
+use crate::{
+    descriptor::ComposeDescriptor,
+    docker::commands::{ComposeCommandError, compose_services, compose_up_services},
+};
+
+/// Bring node containers up in batches instead of all at once, modelling a
+/// rolling start and sparing cfgsync a burst of simultaneous requests.
+///
+/// Every other service (cfgsync, Prometheus, ...) starts first. Batches of
+/// `batch_size` nodes, validators before executors, then follow each other
+/// `interval` plus a random share of `jitter` apart; the jitter is drawn from
+/// the scenario seed, so seeded runs start nodes at the same offsets.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StaggeredStart {
+    interval: Duration,
+    jitter: Duration,
+    batch_size: Option<NonZeroUsize>,
+}
+
+impl StaggeredStart {
+    #[must_use]
+    /// Start one node every `interval`, delayed by up to `jitter` more.
+    pub const fn new(interval: Duration, jitter: Duration) -> Self {
+        Self {
+            interval,
+            jitter,
+            batch_size: None,
+        }
+    }
+
+    #[must_use]
+    /// Start `batch_size` nodes together instead of one at a time.
+    pub const fn with_batch_size(mut self, batch_size: NonZeroUsize) -> Self {
+        self.batch_size = Some(batch_size);
+        self
+    }
+
+    #[must_use]
+    pub const fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    #[must_use]
+    pub const fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    #[must_use]
+    pub fn batch_size(&self) -> usize {
+        self.batch_size.map_or(1, NonZeroUsize::get)
+    }
+
+    /// Split the node services of `descriptor` into batches and draw the
+    /// delay before each batch but the first.
+    pub(crate) fn plan(&self, descriptor: &ComposeDescriptor, rng: &mut impl Rng) -> StaggeredPlan {
+        let nodes = descriptor
+            .validators()
+            .iter()
+            .chain(descriptor.executors())
+            .map(|node| node.name().to_owned())
+            .collect::<Vec<_>>();
+        let batches = nodes
+            .chunks(self.batch_size())
+            .map(<[String]>::to_vec)
+            .collect::<Vec<_>>();
+        let delays = (1..batches.len())
+            .map(|_| self.interval + self.jitter.mul_f64(rng.gen_range(0.0..=1.0)))
+            .collect();
+        StaggeredPlan { batches, delays }
+    }
+}
+
+/// Node batches to start in order, with the delay preceding each batch after
+/// the first.
+#[derive(Debug)]
+pub(crate) struct StaggeredPlan {
+    batches: Vec<Vec<String>>,
+    delays: Vec<Duration>,
+}
+
+impl StaggeredPlan {
+    /// Start every service other than the nodes, then the node batches.
+    pub(crate) async fn bring_up(
+        &self,
+        compose_path: &Path,
+        project_name: &str,
+        root: &Path,
+        timeout_duration: Duration,
+    ) -> Result<(), ComposeCommandError> {
+        let infrastructure = compose_services(compose_path, project_name, root)
+            .await?
+            .into_iter()
+            .filter(|service| !self.batches.iter().flatten().any(|node| node == service))
+            .collect::<Vec<_>>();
+        if !infrastructure.is_empty() {
+            compose_up_services(
+                compose_path,
+                project_name,
+                root,
+                &infrastructure,
+                timeout_duration,
+            )
+            .await?;
+        }
+
+        for (index, batch) in self.batches.iter().enumerate() {
+            if let Some(delay) = index.checked_sub(1).map(|prev| self.delays[prev]) {
+                sleep(delay).await;
+            }
+            info!(
+                batch = index,
+                batches = self.batches.len(),
+                nodes = ?batch,
+                "starting staggered node batch"
+            );
+            compose_up_services(compose_path, project_name, root, batch, timeout_duration).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+    use testing_framework_core::scenario::ScenarioBuilder;
+
+    use super::*;
+
+    #[test]
+    fn plan_batches_nodes_and_bounds_delays() {
+        let scenario = ScenarioBuilder::topology_with(|t| t.validators(3).executors(2)).build();
+        let descriptor = ComposeDescriptor::builder(scenario.topology())
+            .with_prometheus_port(9090)
+            .build()
+            .expect("descriptor builds");
+
+        let staggered = StaggeredStart::new(Duration::from_secs(2), Duration::from_secs(1))
+            .with_batch_size(NonZeroUsize::new(2).expect("non-zero"));
+        let plan = staggered.plan(&descriptor, &mut StdRng::seed_from_u64(7));
+
+        assert_eq!(
+            plan.batches,
+            vec![
+                vec!["validator-0".to_owned(), "validator-1".to_owned()],
+                vec!["validator-2".to_owned(), "executor-0".to_owned()],
+                vec!["executor-1".to_owned()],
+            ]
+        );
+        assert_eq!(plan.delays.len(), 2);
+        assert!(
+            plan.delays
+                .iter()
+                .all(|delay| (Duration::from_secs(2)..=Duration::from_secs(3)).contains(delay))
+        );
+    }
+}
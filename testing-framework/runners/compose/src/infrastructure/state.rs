@@ -0,0 +1,121 @@
+use std::{fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use testing_framework_core::artifacts::RunArtifacts;
+use tracing::{debug, warn};
+
+use crate::errors::StateError;
+
+/// File in the run artifacts directory holding the deployment state.
+pub const STATE_FILE: &str = "compose-state.json";
+
+/// What is needed to re-attach to a running stack after the harness process
+/// died: written once the stack is up, removed when it is torn down.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ComposeRunState {
+    pub run_id: String,
+    pub project_name: String,
+    pub compose_path: PathBuf,
+    pub root: PathBuf,
+    pub cfgsync_container: Option<String>,
+    pub cfgsync_port: u16,
+    /// External cfgsync server the nodes were pointed at.
+    #[serde(default)]
+    pub cfgsync_url: Option<String>,
+    #[serde(default)]
+    pub prometheus_port: Option<u16>,
+    #[serde(default)]
+    pub grafana_port: Option<u16>,
+    #[serde(default)]
+    pub tempo_port: Option<u16>,
+    pub validators: usize,
+    pub executors: usize,
+}
+
+impl ComposeRunState {
+    /// Path of the state file of the run owning `artifacts`.
+    pub fn path(artifacts: &RunArtifacts) -> PathBuf {
+        artifacts.file(STATE_FILE)
+    }
+
+    pub fn save(&self, artifacts: &RunArtifacts) -> Result<(), StateError> {
+        let path = Self::path(artifacts);
+        let contents =
+            serde_json::to_string_pretty(self).map_err(|source| StateError::Serialize {
+                path: path.clone(),
+                source,
+            })?;
+        fs::write(&path, contents).map_err(|source| StateError::Write {
+            path: path.clone(),
+            source,
+        })?;
+        debug!(path = %path.display(), "compose deployment state written");
+        Ok(())
+    }
+
+    pub fn load(artifacts: &RunArtifacts) -> Result<Self, StateError> {
+        let path = Self::path(artifacts);
+        let contents = fs::read_to_string(&path).map_err(|source| StateError::Read {
+            path: path.clone(),
+            source,
+        })?;
+        serde_json::from_str(&contents).map_err(|source| StateError::Parse { path, source })
+    }
+
+    /// Forget the state once the stack is gone, so the run cannot be resumed.
+    pub fn remove(artifacts: &RunArtifacts) {
+        let path = Self::path(artifacts);
+        match fs::remove_file(&path) {
+            Ok(()) => debug!(path = %path.display(), "compose deployment state removed"),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => {
+                warn!(path = %path.display(), error = %err, "failed to remove deployment state");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_core::artifacts::RetentionPolicy;
+
+    use super::*;
+
+    #[test]
+    fn saved_state_loads_back() {
+        let base = std::env::temp_dir().join(format!("compose-state-{}", std::process::id()));
+        let artifacts =
+            RunArtifacts::create_in(&base, RetentionPolicy::unlimited()).expect("run dir created");
+        let state = ComposeRunState {
+            run_id: artifacts.run_id().to_owned(),
+            project_name: "nomos-compose-test".to_owned(),
+            compose_path: artifacts.file("compose.generated.yml"),
+            root: artifacts.root().to_path_buf(),
+            cfgsync_container: Some("cfgsync-test".to_owned()),
+            cfgsync_port: 4400,
+            cfgsync_url: None,
+            prometheus_port: Some(9090),
+            grafana_port: None,
+            tempo_port: None,
+            validators: 2,
+            executors: 1,
+        };
+
+        state.save(&artifacts).expect("state saved");
+        let loaded = ComposeRunState::load(&artifacts).expect("state loads");
+        assert_eq!(loaded.run_id, state.run_id);
+        assert_eq!(loaded.project_name, state.project_name);
+        assert_eq!(loaded.compose_path, state.compose_path);
+        assert_eq!(loaded.cfgsync_container, state.cfgsync_container);
+        assert_eq!(loaded.prometheus_port, Some(9090));
+        assert_eq!((loaded.validators, loaded.executors), (2, 1));
+
+        ComposeRunState::remove(&artifacts);
+        assert!(matches!(
+            ComposeRunState::load(&artifacts),
+            Err(StateError::Read { .. })
+        ));
+        drop(artifacts);
+        let _ = fs::remove_dir_all(base);
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     env, fs,
     path::{Path, PathBuf},
 };
@@ -9,17 +10,16 @@ use tracing::{debug, info};
 
 use crate::descriptor::ComposeDescriptor;
 
-const TEMPLATE_RELATIVE_PATH: &str =
-    "testing-framework/runners/compose/assets/docker-compose.yml.tera";
+/// Compose template shipped with the crate.
+const EMBEDDED_TEMPLATE: &str = include_str!("../../assets/docker-compose.yml.tera");
+/// Copy of a custom template kept in the workspace root, so nodes added later
+/// render from the template the stack was deployed with, also after the run
+/// is resumed.
+pub const PERSISTED_TEMPLATE_FILE: &str = "compose.template.tera";
 
 /// Errors when templating docker-compose files.
 #[derive(Debug, thiserror::Error)]
 pub enum TemplateError {
-    #[error("failed to resolve repository root for compose template: {source}")]
-    RepositoryRoot {
-        #[source]
-        source: anyhow::Error,
-    },
     #[error("failed to read compose template at {path}: {source}")]
     Read {
         path: PathBuf,
@@ -31,9 +31,9 @@ pub enum TemplateError {
         #[source]
         source: tera::Error,
     },
-    #[error("failed to render compose template at {path}: {source}")]
+    #[error("failed to render compose template {template}: {source}")]
     Render {
-        path: PathBuf,
+        template: String,
         #[source]
         source: tera::Error,
     },
@@ -45,55 +45,112 @@ pub enum TemplateError {
     },
 }
 
-/// Render and write the compose file to disk.
-pub fn write_compose_file(
-    descriptor: &ComposeDescriptor,
-    compose_path: &Path,
-) -> Result<(), TemplateError> {
-    info!(file = %compose_path.display(), "writing compose file");
-    TemplateSource::load()?.write(descriptor, compose_path)
+/// Tera template the compose file is rendered from. Templates are rendered
+/// against the serialised [`ComposeDescriptor`], like the embedded default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ComposeTemplate {
+    /// Template shipped with the crate.
+    #[default]
+    Embedded,
+    /// Template read from a file when the compose file is rendered.
+    File(PathBuf),
+    /// Template source given inline.
+    Inline(String),
 }
 
-struct TemplateSource {
-    path: PathBuf,
-    contents: String,
-}
+impl ComposeTemplate {
+    #[must_use]
+    /// Template read from `path` when the compose file is rendered.
+    pub fn file(path: impl Into<PathBuf>) -> Self {
+        Self::File(path.into())
+    }
 
-impl TemplateSource {
-    fn load() -> Result<Self, TemplateError> {
-        let repo_root =
-            repository_root().map_err(|source| TemplateError::RepositoryRoot { source })?;
-        let path = repo_root.join(TEMPLATE_RELATIVE_PATH);
-        let contents = fs::read_to_string(&path).map_err(|source| TemplateError::Read {
-            path: path.clone(),
-            source,
-        })?;
+    #[must_use]
+    /// Template whose Tera source is `contents`.
+    pub fn source(contents: impl Into<String>) -> Self {
+        Self::Inline(contents.into())
+    }
 
-        Ok(Self { path, contents })
+    #[must_use]
+    /// Template the stack rooted at `root` was deployed with: the copy of a
+    /// custom template written there, otherwise the embedded one.
+    pub fn persisted(root: &Path) -> Self {
+        let path = root.join(PERSISTED_TEMPLATE_FILE);
+        if path.is_file() {
+            Self::File(path)
+        } else {
+            Self::Embedded
+        }
     }
 
-    fn render(&self, descriptor: &ComposeDescriptor) -> Result<String, TemplateError> {
-        debug!(template = %self.path.display(), "rendering compose template");
-        let context = TeraContext::from_serialize(descriptor)
-            .map_err(|source| TemplateError::Serialize { source })?;
+    /// Copy a custom template into `root` for [`Self::persisted`].
+    pub(crate) fn persist(&self, root: &Path) -> Result<(), TemplateError> {
+        if matches!(self, Self::Embedded) {
+            return Ok(());
+        }
+        let path = root.join(PERSISTED_TEMPLATE_FILE);
+        let contents = self.contents()?;
+        fs::write(&path, contents.as_ref()).map_err(|source| TemplateError::Write { path, source })
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Embedded => "embedded".to_owned(),
+            Self::File(path) => path.display().to_string(),
+            Self::Inline(_) => "inline".to_owned(),
+        }
+    }
 
-        tera::Tera::one_off(&self.contents, &context, false).map_err(|source| {
-            TemplateError::Render {
-                path: self.path.clone(),
-                source,
+    fn contents(&self) -> Result<Cow<'_, str>, TemplateError> {
+        match self {
+            Self::Embedded => Ok(Cow::Borrowed(EMBEDDED_TEMPLATE)),
+            Self::File(path) => {
+                fs::read_to_string(path)
+                    .map(Cow::Owned)
+                    .map_err(|source| TemplateError::Read {
+                        path: path.clone(),
+                        source,
+                    })
             }
-        })
+            Self::Inline(contents) => Ok(Cow::Borrowed(contents)),
+        }
     }
 
-    fn write(&self, descriptor: &ComposeDescriptor, output: &Path) -> Result<(), TemplateError> {
-        let rendered = self.render(descriptor)?;
-        fs::write(output, rendered).map_err(|source| TemplateError::Write {
-            path: output.to_path_buf(),
+    fn render(&self, descriptor: &ComposeDescriptor) -> Result<String, TemplateError> {
+        debug!(template = %self.describe(), "rendering compose template");
+        let contents = self.contents()?;
+        let context = TeraContext::from_serialize(descriptor)
+            .map_err(|source| TemplateError::Serialize { source })?;
+
+        tera::Tera::one_off(&contents, &context, false).map_err(|source| TemplateError::Render {
+            template: self.describe(),
             source,
         })
     }
 }
 
+/// Render the embedded template and write the compose file to disk.
+pub fn write_compose_file(
+    descriptor: &ComposeDescriptor,
+    compose_path: &Path,
+) -> Result<(), TemplateError> {
+    write_compose_file_with(&ComposeTemplate::Embedded, descriptor, compose_path)
+}
+
+/// Render `template` and write the compose file to disk.
+pub fn write_compose_file_with(
+    template: &ComposeTemplate,
+    descriptor: &ComposeDescriptor,
+    compose_path: &Path,
+) -> Result<(), TemplateError> {
+    info!(file = %compose_path.display(), template = %template.describe(), "writing compose file");
+    let rendered = template.render(descriptor)?;
+    fs::write(compose_path, rendered).map_err(|source| TemplateError::Write {
+        path: compose_path.to_path_buf(),
+        source,
+    })
+}
+
 /// Resolve the repository root, respecting `CARGO_WORKSPACE_DIR` override.
 pub fn repository_root() -> anyhow::Result<PathBuf> {
     env::var("CARGO_WORKSPACE_DIR")
@@ -107,3 +164,101 @@ pub fn repository_root() -> anyhow::Result<PathBuf> {
                 .context("resolving repository root from manifest dir")
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_core::scenario::ScenarioBuilder;
+
+    use super::*;
+
+    fn descriptor() -> ComposeDescriptor {
+        let scenario = ScenarioBuilder::topology_with(|t| t.validators(2).executors(1)).build();
+        ComposeDescriptor::builder(scenario.topology())
+            .with_prometheus_port(9090)
+            .with_cfgsync_port(4400)
+            .build()
+            .expect("descriptor builds")
+    }
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("compose-template-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("scratch dir");
+        dir
+    }
+
+    #[test]
+    fn embedded_template_renders_every_node() {
+        let descriptor = descriptor();
+        let rendered = ComposeTemplate::Embedded
+            .render(&descriptor)
+            .expect("embedded template renders");
+
+        for node in descriptor.validators().iter().chain(descriptor.executors()) {
+            assert!(rendered.contains(node.name()), "{} missing", node.name());
+        }
+    }
+
+    #[test]
+    fn custom_sources_render_against_the_descriptor() {
+        let template = "{% for node in validators %}{{ node.name }}\n{% endfor %}";
+        let names = descriptor()
+            .validators()
+            .iter()
+            .map(|node| format!("{}\n", node.name()))
+            .collect::<String>();
+        assert_eq!(
+            ComposeTemplate::source(template)
+                .render(&descriptor())
+                .expect("inline template renders"),
+            names
+        );
+
+        let dir = scratch_dir("file");
+        let path = dir.join("custom.tera");
+        fs::write(&path, template).expect("template written");
+        assert_eq!(
+            ComposeTemplate::file(&path)
+                .render(&descriptor())
+                .expect("file template renders"),
+            names
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn template_failures_are_reported() {
+        let missing = ComposeTemplate::file("/nonexistent/compose.tera").render(&descriptor());
+        assert!(
+            matches!(missing, Err(TemplateError::Read { .. })),
+            "{missing:?}"
+        );
+
+        let broken = ComposeTemplate::source("{{ no_such_field }}").render(&descriptor());
+        assert!(
+            matches!(&broken, Err(TemplateError::Render { template, .. }) if template == "inline"),
+            "{broken:?}"
+        );
+    }
+
+    #[test]
+    fn custom_templates_persist_for_resumed_runs() {
+        let dir = scratch_dir("persist");
+        fs::remove_file(dir.join(PERSISTED_TEMPLATE_FILE)).ok();
+
+        ComposeTemplate::Embedded
+            .persist(&dir)
+            .expect("nothing to persist");
+        assert_eq!(ComposeTemplate::persisted(&dir), ComposeTemplate::Embedded);
+
+        ComposeTemplate::source("services: {}")
+            .persist(&dir)
+            .expect("template persisted");
+        let persisted = ComposeTemplate::persisted(&dir);
+        assert_eq!(
+            persisted,
+            ComposeTemplate::file(dir.join(PERSISTED_TEMPLATE_FILE))
+        );
+        assert_eq!(persisted.contents().expect("copy readable"), "services: {}");
+        fs::remove_dir_all(dir).ok();
+    }
+}
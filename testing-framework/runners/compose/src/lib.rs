@@ -2,17 +2,30 @@ pub mod deployer;
 pub mod descriptor;
 pub mod docker;
 pub mod errors;
+pub mod harness;
 pub mod infrastructure;
 pub mod lifecycle;
 
 pub use deployer::ComposeDeployer;
-pub use descriptor::{ComposeDescriptor, ComposeDescriptorBuilder, EnvEntry, NodeDescriptor};
+pub use descriptor::{
+    ComposeDescriptor, ComposeDescriptorBuilder, EnvEntry, Healthcheck, ImageOverrides,
+    NodeDescriptor, ScenarioDashboard,
+};
 pub use docker::{
     commands::{ComposeCommandError, compose_down, compose_up, dump_compose_logs},
-    platform::{host_gateway_entry, resolve_image},
+    logs::{LOG_ARCHIVE_ENV, LogCollector, LogCollectorError},
+    platform::{
+        host_gateway_entry, image_platform, published_bind_address, published_ports_host,
+        remote_docker_host, resolve_image,
+    },
 };
 pub use errors::ComposeRunnerError;
+pub use harness::ComposeTestHarness;
 pub use infrastructure::{
     ports::{HostPortMapping, NodeHostPorts},
-    template::{TemplateError, repository_root, write_compose_file},
+    stagger::StaggeredStart,
+    template::{
+        ComposeTemplate, TemplateError, repository_root, write_compose_file,
+        write_compose_file_with,
+    },
 };
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use testing_framework_core::scenario::{BlockFeed, BlockFeedTask, NodeClients, spawn_block_feed};
+use testing_framework_core::{
+    scenario::{BlockFeed, BlockFeedTask, NodeClients, spawn_failover_block_feed},
+    topology::generation::GeneratedTopology,
+};
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
 
@@ -11,6 +14,7 @@ const BLOCK_FEED_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 async fn spawn_block_feed_with(
     node_clients: &NodeClients,
+    descriptors: &GeneratedTopology,
 ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
     debug!(
         validators = node_clients.validator_clients().len(),
@@ -19,22 +23,26 @@ async fn spawn_block_feed_with(
     );
 
     let block_source_client = node_clients
-        .random_validator()
+        .random_initial_validator(descriptors)
         .cloned()
         .ok_or(ComposeRunnerError::BlockFeedMissing)?;
 
-    spawn_block_feed(block_source_client)
-        .await
-        .map_err(|source| ComposeRunnerError::BlockFeed { source })
+    spawn_failover_block_feed(
+        block_source_client,
+        node_clients.validator_clients().to_vec(),
+    )
+    .await
+    .map_err(|source| ComposeRunnerError::BlockFeed { source })
 }
 
 pub async fn spawn_block_feed_with_retry(
     node_clients: &NodeClients,
+    descriptors: &GeneratedTopology,
 ) -> Result<(BlockFeed, BlockFeedTask), ComposeRunnerError> {
     let mut last_err = None;
     for attempt in 1..=BLOCK_FEED_MAX_ATTEMPTS {
         info!(attempt, "starting block feed");
-        match spawn_block_feed_with(node_clients).await {
+        match spawn_block_feed_with(node_clients, descriptors).await {
             Ok(result) => {
                 info!(attempt, "block feed established");
                 return Ok(result);
@@ -1,14 +1,16 @@
-use std::{env, path::PathBuf, thread};
+use std::{env, future::Future, path::PathBuf, thread};
 
 use testing_framework_core::scenario::CleanupGuard;
 use tracing::{debug, info, warn};
 
 use crate::{
+    descriptor::STORAGE_DIR,
     docker::{
-        commands::{ComposeCommandError, compose_down},
+        commands::{ComposeCommandError, compose_down, reclaim_storage},
+        logs::LogCollector,
         workspace::ComposeWorkspace,
     },
-    infrastructure::cfgsync::CfgsyncServerHandle,
+    infrastructure::{cfgsync::CfgsyncServerHandle, state::ComposeRunState},
 };
 
 /// Cleans up a compose deployment and associated cfgsync container.
@@ -16,8 +18,11 @@ pub struct RunnerCleanup {
     pub compose_file: PathBuf,
     pub project_name: String,
     pub root: PathBuf,
+    /// Node image used to hand the node storage back to the host user.
+    node_image: Option<String>,
     workspace: Option<ComposeWorkspace>,
     cfgsync: Option<CfgsyncServerHandle>,
+    log_collector: Option<LogCollector>,
 }
 
 impl RunnerCleanup {
@@ -26,8 +31,10 @@ impl RunnerCleanup {
         compose_file: PathBuf,
         project_name: String,
         root: PathBuf,
+        node_image: Option<String>,
         workspace: ComposeWorkspace,
         cfgsync: Option<CfgsyncServerHandle>,
+        log_collector: Option<LogCollector>,
     ) -> Self {
         debug_assert!(
             !compose_file.as_os_str().is_empty() && !project_name.is_empty(),
@@ -37,47 +44,63 @@ impl RunnerCleanup {
             compose_file,
             project_name,
             root,
+            node_image,
             workspace: Some(workspace),
             cfgsync,
+            log_collector,
         }
     }
 
     fn teardown_compose(&self) {
-        if let Err(err) =
-            run_compose_down_blocking(&self.compose_file, &self.project_name, &self.root)
-        {
+        let (compose_file, project_name, root) = (
+            self.compose_file.clone(),
+            self.project_name.clone(),
+            self.root.clone(),
+        );
+        if let Err(err) = run_blocking("docker compose down", async move {
+            compose_down(&compose_file, &project_name, &root).await
+        }) {
             warn!(error = ?err, "docker compose down failed");
         }
     }
+
+    fn reclaim_storage(&self) {
+        let Some(image) = self.node_image.clone() else {
+            return;
+        };
+        let storage = self.root.join(STORAGE_DIR);
+        if let Err(err) = run_blocking("docker run chown", async move {
+            reclaim_storage(&image, &storage).await
+        }) {
+            warn!(error = ?err, "failed to hand node storage back to the host user");
+        }
+    }
 }
 
-fn run_compose_down_blocking(
-    compose_file: &PathBuf,
-    project_name: &str,
-    root: &PathBuf,
+fn run_blocking(
+    command: &str,
+    future: impl Future<Output = Result<(), ComposeCommandError>> + Send + 'static,
 ) -> Result<(), ComposeCommandError> {
-    let compose_file = compose_file.clone();
-    let project_name = project_name.to_owned();
-    let root = root.clone();
+    let spawn_error = |source| ComposeCommandError::Spawn {
+        command: command.to_owned(),
+        source,
+    };
 
     let handle = thread::spawn(move || {
         tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()
-            .map_err(|err| ComposeCommandError::Spawn {
-                command: "docker compose down".into(),
-                source: std::io::Error::new(std::io::ErrorKind::Other, err),
-            })?
-            .block_on(compose_down(&compose_file, &project_name, &root))
+            .map(|runtime| runtime.block_on(future))
     });
 
-    handle.join().map_err(|_| ComposeCommandError::Spawn {
-        command: "docker compose down".into(),
-        source: std::io::Error::new(
+    match handle.join() {
+        Ok(Ok(result)) => result,
+        Ok(Err(err)) => Err(spawn_error(err)),
+        Err(_) => Err(spawn_error(std::io::Error::new(
             std::io::ErrorKind::Other,
-            "join failure running compose down",
-        ),
-    })?
+            format!("join failure running {command}"),
+        ))),
+    }
 }
 impl CleanupGuard for RunnerCleanup {
     fn cleanup(mut self: Box<Self>) {
@@ -89,11 +112,17 @@ impl CleanupGuard for RunnerCleanup {
             "compose cleanup started"
         );
         if self.should_preserve() {
+            self.finish_log_collection();
             self.persist_workspace();
             return;
         }
 
         self.teardown_compose();
+        self.reclaim_storage();
+        self.finish_log_collection();
+        if let Some(workspace) = self.workspace.as_ref() {
+            ComposeRunState::remove(workspace.artifacts());
+        }
 
         if let Some(mut handle) = self.cfgsync.take() {
             handle.shutdown();
@@ -106,9 +135,15 @@ impl RunnerCleanup {
         env::var("COMPOSE_RUNNER_PRESERVE").is_ok() || env::var("TESTNET_RUNNER_PRESERVE").is_ok()
     }
 
+    fn finish_log_collection(&mut self) {
+        if let Some(collector) = self.log_collector.take() {
+            collector.finish();
+        }
+    }
+
     fn persist_workspace(&mut self) {
         if let Some(workspace) = self.workspace.take() {
-            let keep = workspace.into_inner().keep();
+            let keep = workspace.keep();
             info!(path = %keep.display(), "preserving docker state");
         }
 
@@ -2,3 +2,4 @@ pub mod block_feed;
 pub mod cleanup;
 pub mod readiness;
 pub mod wait;
+pub mod watchdog;
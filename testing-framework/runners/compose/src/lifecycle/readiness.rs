@@ -1,14 +1,20 @@
-use std::time::Duration;
+use std::{
+    path::Path,
+    time::{Duration, Instant},
+};
 
 use reqwest::Url;
 use testing_framework_core::{
     nodes::ApiClient,
-    scenario::{Metrics, MetricsError, NodeClients, http_probe::NodeRole as HttpNodeRole},
+    scenario::{
+        Metrics, MetricsError, NodeClients, ScenarioTimings, http_probe::NodeRole as HttpNodeRole,
+    },
     topology::generation::{GeneratedTopology, NodeRole as TopologyNodeRole},
 };
 use tokio::time::sleep;
 
 use crate::{
+    docker::commands::compose_health,
     errors::{NodeClientError, StackReadinessError},
     infrastructure::ports::{HostPortMapping, NodeHostPorts},
     lifecycle::wait::{wait_for_executors, wait_for_validators},
@@ -24,21 +30,76 @@ pub fn metrics_handle_from_port(port: u16, host: &str) -> Result<Metrics, Metric
 }
 
 /// Wait until all validators respond on their API ports.
-pub async fn ensure_validators_ready_with_ports(ports: &[u16]) -> Result<(), StackReadinessError> {
+pub async fn ensure_validators_ready_with_ports(
+    ports: &[u16],
+    timings: &ScenarioTimings,
+) -> Result<(), StackReadinessError> {
     if ports.is_empty() {
         return Ok(());
     }
 
-    wait_for_validators(ports).await.map_err(Into::into)
+    wait_for_validators(ports, timings)
+        .await
+        .map_err(Into::into)
 }
 
 /// Wait until all executors respond on their API ports.
-pub async fn ensure_executors_ready_with_ports(ports: &[u16]) -> Result<(), StackReadinessError> {
+pub async fn ensure_executors_ready_with_ports(
+    ports: &[u16],
+    timings: &ScenarioTimings,
+) -> Result<(), StackReadinessError> {
     if ports.is_empty() {
         return Ok(());
     }
 
-    wait_for_executors(ports).await.map_err(Into::into)
+    wait_for_executors(ports, timings).await.map_err(Into::into)
+}
+
+/// Wait until the healthchecks of `services` pass, failing as soon as one of
+/// them is unhealthy. Services without a running container are ignored.
+pub async fn ensure_services_healthy(
+    compose_path: &Path,
+    project_name: &str,
+    root: &Path,
+    services: &[String],
+    timings: &ScenarioTimings,
+) -> Result<(), StackReadinessError> {
+    if services.is_empty() {
+        return Ok(());
+    }
+
+    let timeout = timings.node_http();
+    let started = Instant::now();
+    loop {
+        let health = compose_health(compose_path, project_name, root)
+            .await
+            .map_err(|source| StackReadinessError::Health { source })?;
+        let with_status = |status: &str| {
+            health
+                .iter()
+                .filter(|(service, health)| health == status && services.contains(service))
+                .map(|(service, _)| service.as_str())
+                .collect::<Vec<_>>()
+        };
+
+        let unhealthy = with_status("unhealthy");
+        if !unhealthy.is_empty() {
+            return Err(StackReadinessError::Unhealthy {
+                services: unhealthy.join(", "),
+            });
+        }
+        let starting = with_status("starting");
+        if starting.is_empty() {
+            return Ok(());
+        }
+        if started.elapsed() >= timeout {
+            return Err(StackReadinessError::HealthTimeout {
+                services: starting.join(", "),
+                timeout,
+            });
+        }
+        sleep(timings.http_poll_interval()).await;
+    }
 }
 
 /// Allow a brief pause when readiness probes are disabled.
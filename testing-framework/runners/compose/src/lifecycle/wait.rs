@@ -1,37 +1,41 @@
-use std::{env, time::Duration};
-
 use testing_framework_core::{
     adjust_timeout,
-    scenario::http_probe::{self, HttpReadinessError, NodeRole},
+    scenario::{
+        ScenarioTimings,
+        http_probe::{self, HttpReadinessError, NodeRole},
+    },
 };
-use tracing::{debug, info};
+use tracing::info;
 
-const DEFAULT_WAIT: Duration = Duration::from_secs(180);
-const POLL_INTERVAL: Duration = Duration::from_millis(250);
+use crate::infrastructure::ports::compose_runner_host;
 
-pub async fn wait_for_validators(ports: &[u16]) -> Result<(), HttpReadinessError> {
-    wait_for_ports(ports, NodeRole::Validator).await
+pub async fn wait_for_validators(
+    ports: &[u16],
+    timings: &ScenarioTimings,
+) -> Result<(), HttpReadinessError> {
+    wait_for_ports(ports, NodeRole::Validator, timings).await
 }
 
-pub async fn wait_for_executors(ports: &[u16]) -> Result<(), HttpReadinessError> {
-    wait_for_ports(ports, NodeRole::Executor).await
+pub async fn wait_for_executors(
+    ports: &[u16],
+    timings: &ScenarioTimings,
+) -> Result<(), HttpReadinessError> {
+    wait_for_ports(ports, NodeRole::Executor, timings).await
 }
 
-async fn wait_for_ports(ports: &[u16], role: NodeRole) -> Result<(), HttpReadinessError> {
+async fn wait_for_ports(
+    ports: &[u16],
+    role: NodeRole,
+    timings: &ScenarioTimings,
+) -> Result<(), HttpReadinessError> {
     let host = compose_runner_host();
     info!(role = ?role, ports = ?ports, host, "waiting for compose HTTP readiness");
     http_probe::wait_for_http_ports_with_host(
         ports,
         role,
         &host,
-        adjust_timeout(DEFAULT_WAIT),
-        POLL_INTERVAL,
+        adjust_timeout(timings.node_http()),
+        timings.http_poll_interval(),
     )
     .await
 }
-
-fn compose_runner_host() -> String {
-    let host = env::var("COMPOSE_RUNNER_HOST").unwrap_or_else(|_| "127.0.0.1".to_string());
-    debug!(host, "compose runner host resolved");
-    host
-}
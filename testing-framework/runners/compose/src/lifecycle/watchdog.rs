@@ -0,0 +1,385 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::Stdio,
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+use testing_framework_core::scenario::{NodeEventKind, NodeTarget, RunAbort, RunReporter};
+use tokio::{
+    io::{AsyncBufReadExt as _, BufReader},
+    process::{ChildStdout, Command},
+    task::JoinHandle,
+};
+use tracing::{debug, info, warn};
+
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+/// A container dying this soon after a `kill` event (sent by `docker stop`,
+/// `restart` and `kill`) was stopped on purpose, e.g. by a chaos workload.
+const INTENTIONAL_STOP_WINDOW: Duration = Duration::from_secs(30);
+/// Log lines of the dying container included in warnings and abort reasons.
+const EXIT_LOG_TAIL: usize = 50;
+
+#[derive(Deserialize)]
+struct DockerEvent {
+    #[serde(rename = "Action")]
+    action: String,
+    #[serde(rename = "Actor")]
+    actor: EventActor,
+}
+
+#[derive(Deserialize)]
+struct EventActor {
+    #[serde(rename = "Attributes", default)]
+    attributes: HashMap<String, String>,
+}
+
+/// Streams `docker events` for the compose project and reports node
+/// containers that exit or fail their healthcheck while the scenario runs.
+///
+/// Every unexpected exit is logged together with the container's last log
+/// lines and recorded as a node event in the run report. With `fail_fast`
+/// the run is aborted on the first one instead of waiting for expectations
+/// to fail at the end. Containers turning unhealthy are logged and recorded
+/// the same way but never abort the run. Containers stopped or paused
+/// through node control are ignored.
+pub struct ContainerWatchdog {
+    task: JoinHandle<()>,
+}
+
+impl ContainerWatchdog {
+    /// Start watching `services`. Returns `None` (after logging why) when
+    /// `docker events` cannot be started; the run continues unwatched.
+    pub fn start(
+        compose_file: &Path,
+        project_name: &str,
+        root: &Path,
+        services: HashMap<String, NodeTarget>,
+        report: RunReporter,
+        abort: Option<RunAbort>,
+    ) -> Option<Self> {
+        let mut command = Command::new("docker");
+        command
+            .arg("events")
+            .arg("--filter")
+            .arg("type=container")
+            .arg("--filter")
+            .arg(format!("label={PROJECT_LABEL}={project_name}"))
+            .arg("--filter")
+            .arg("event=kill")
+            .arg("--filter")
+            .arg("event=die")
+            .arg("--filter")
+            .arg("event=health_status")
+            .arg("--filter")
+            .arg("event=pause")
+            .arg("--filter")
+            .arg("event=unpause")
+            .arg("--format")
+            .arg("{{json .}}")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(err) => {
+                warn!(error = ?err, "failed to start container watchdog; node exits will go unnoticed until expectations run");
+                return None;
+            }
+        };
+        let stdout = child.stdout.take()?;
+
+        let watcher = ExitWatcher {
+            compose_file: compose_file.to_path_buf(),
+            project_name: project_name.to_owned(),
+            root: root.to_path_buf(),
+            services,
+            report,
+            abort,
+            last_kill: HashMap::new(),
+            paused: HashSet::new(),
+        };
+        info!(
+            project = project_name,
+            fail_fast = watcher.abort.is_some(),
+            "container watchdog started"
+        );
+        let task = tokio::spawn(async move {
+            watcher.run(stdout).await;
+            // Keep the events process alive for as long as the task reads it.
+            drop(child);
+        });
+        Some(Self { task })
+    }
+}
+
+impl Drop for ContainerWatchdog {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// What an observed event calls for.
+#[derive(Debug, PartialEq, Eq)]
+enum Reaction {
+    Exit { exit_code: String },
+    Unhealthy,
+}
+
+struct ExitWatcher {
+    compose_file: PathBuf,
+    project_name: String,
+    root: PathBuf,
+    services: HashMap<String, NodeTarget>,
+    report: RunReporter,
+    abort: Option<RunAbort>,
+    last_kill: HashMap<String, Instant>,
+    paused: HashSet<String>,
+}
+
+impl ExitWatcher {
+    async fn run(mut self, stdout: ChildStdout) {
+        let mut lines = BufReader::new(stdout).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => self.handle_line(&line).await,
+                Ok(None) => {
+                    debug!("docker events stream closed; container watchdog exiting");
+                    break;
+                }
+                Err(err) => {
+                    warn!(error = ?err, "failed to read docker events; container watchdog exiting");
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn handle_line(&mut self, line: &str) {
+        let event = match serde_json::from_str::<DockerEvent>(line) {
+            Ok(event) => event,
+            Err(err) => {
+                debug!(error = %err, line, "ignoring unparsable docker event");
+                return;
+            }
+        };
+        match self.observe(&event) {
+            Some((service, target, Reaction::Exit { exit_code })) => {
+                self.on_unexpected_exit(service, target, &exit_code).await;
+            }
+            Some((service, target, Reaction::Unhealthy)) => {
+                self.on_unhealthy(service, target).await;
+            }
+            None => {}
+        }
+    }
+
+    /// Track `event` and tell whether it needs reporting.
+    fn observe(&mut self, event: &DockerEvent) -> Option<(String, NodeTarget, Reaction)> {
+        let service = event.actor.attributes.get(SERVICE_LABEL)?;
+        let target = self.services.get(service).copied()?;
+
+        let reaction = match event.action.as_str() {
+            "kill" => {
+                self.last_kill.insert(service.clone(), Instant::now());
+                None
+            }
+            "die" => {
+                if self.recently_killed(service) {
+                    debug!(service, "node container stopped on request");
+                    return None;
+                }
+                let exit_code = event
+                    .actor
+                    .attributes
+                    .get("exitCode")
+                    .map_or("unknown", String::as_str)
+                    .to_owned();
+                Some(Reaction::Exit { exit_code })
+            }
+            "pause" => {
+                self.paused.insert(service.clone());
+                None
+            }
+            "unpause" => {
+                self.paused.remove(service);
+                None
+            }
+            // Docker reports the new status in the action itself.
+            "health_status: unhealthy" => {
+                if self.paused.contains(service) || self.recently_killed(service) {
+                    debug!(service, "stopped or paused node container turned unhealthy");
+                    return None;
+                }
+                Some(Reaction::Unhealthy)
+            }
+            "health_status: healthy" => {
+                debug!(service, "node container is healthy");
+                None
+            }
+            _ => None,
+        };
+        reaction.map(|reaction| (service.clone(), target, reaction))
+    }
+
+    fn recently_killed(&self, service: &str) -> bool {
+        self.last_kill
+            .get(service)
+            .is_some_and(|at| at.elapsed() <= INTENTIONAL_STOP_WINDOW)
+    }
+
+    async fn on_unexpected_exit(&self, service: String, target: NodeTarget, exit_code: &str) {
+        self.report.record_node_event(target, NodeEventKind::Exit);
+        self.report.increment("compose_watchdog", "node_exits", 1);
+
+        let logs = self.tail_logs(&service).await;
+        warn!(
+            service,
+            exit_code,
+            logs = %logs,
+            "node container exited unexpectedly"
+        );
+
+        if let Some(abort) = &self.abort {
+            abort.abort(format!(
+                "{service} exited unexpectedly with code {exit_code}; last {EXIT_LOG_TAIL} log lines:\n{logs}"
+            ));
+        }
+    }
+
+    async fn on_unhealthy(&self, service: String, target: NodeTarget) {
+        self.report
+            .record_node_event(target, NodeEventKind::Unhealthy);
+        self.report
+            .increment("compose_watchdog", "unhealthy_nodes", 1);
+
+        let logs = self.tail_logs(&service).await;
+        warn!(
+            service,
+            logs = %logs,
+            "node container failed its healthcheck"
+        );
+    }
+
+    async fn tail_logs(&self, service: &str) -> String {
+        let output = Command::new("docker")
+            .arg("compose")
+            .arg("-f")
+            .arg(&self.compose_file)
+            .arg("-p")
+            .arg(&self.project_name)
+            .arg("logs")
+            .arg("--no-color")
+            .arg("--no-log-prefix")
+            .arg("--tail")
+            .arg(EXIT_LOG_TAIL.to_string())
+            .arg(service)
+            .current_dir(&self.root)
+            .output()
+            .await;
+        match output {
+            Ok(output) => {
+                let mut logs = String::from_utf8_lossy(&output.stdout).into_owned();
+                logs.push_str(&String::from_utf8_lossy(&output.stderr));
+                logs
+            }
+            Err(err) => format!("<failed to read logs: {err}>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watcher() -> ExitWatcher {
+        ExitWatcher {
+            compose_file: PathBuf::from("compose.generated.yml"),
+            project_name: "nomos-test".to_owned(),
+            root: PathBuf::from("."),
+            services: HashMap::from([
+                ("validator-0".to_owned(), NodeTarget::Validator(0)),
+                ("executor-0".to_owned(), NodeTarget::Executor(0)),
+            ]),
+            report: RunReporter::new(),
+            abort: None,
+            last_kill: HashMap::new(),
+            paused: HashSet::new(),
+        }
+    }
+
+    fn event(service: &str, action: &str) -> DockerEvent {
+        let line = serde_json::json!({
+            "Action": action,
+            "Actor": { "Attributes": { "com.docker.compose.service": service, "exitCode": "1" } },
+        });
+        serde_json::from_value(line).expect("docker event parses")
+    }
+
+    #[test]
+    fn unhealthy_nodes_are_reported() {
+        let mut watcher = watcher();
+
+        assert_eq!(
+            watcher.observe(&event("validator-0", "health_status: unhealthy")),
+            Some((
+                "validator-0".to_owned(),
+                NodeTarget::Validator(0),
+                Reaction::Unhealthy
+            ))
+        );
+        assert_eq!(
+            watcher.observe(&event("validator-0", "health_status: healthy")),
+            None
+        );
+        assert_eq!(
+            watcher.observe(&event("cfgsync", "health_status: unhealthy")),
+            None,
+            "only node services are watched"
+        );
+    }
+
+    #[test]
+    fn paused_or_stopped_nodes_are_not_unhealthy() {
+        let mut watcher = watcher();
+
+        assert_eq!(watcher.observe(&event("validator-0", "pause")), None);
+        assert_eq!(
+            watcher.observe(&event("validator-0", "health_status: unhealthy")),
+            None
+        );
+        assert_eq!(watcher.observe(&event("validator-0", "unpause")), None);
+        assert!(
+            watcher
+                .observe(&event("validator-0", "health_status: unhealthy"))
+                .is_some()
+        );
+
+        assert_eq!(watcher.observe(&event("executor-0", "kill")), None);
+        assert_eq!(
+            watcher.observe(&event("executor-0", "health_status: unhealthy")),
+            None
+        );
+        assert_eq!(watcher.observe(&event("executor-0", "die")), None);
+    }
+
+    #[test]
+    fn unexpected_exits_carry_the_exit_code() {
+        let mut watcher = watcher();
+
+        assert_eq!(
+            watcher.observe(&event("executor-0", "die")),
+            Some((
+                "executor-0".to_owned(),
+                NodeTarget::Executor(0),
+                Reaction::Exit {
+                    exit_code: "1".to_owned()
+                }
+            ))
+        );
+    }
+}
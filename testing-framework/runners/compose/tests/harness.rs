@@ -0,0 +1,32 @@
+use testing_framework_core::scenario::ScenarioBuilder;
+use testing_framework_runner_compose::ComposeTestHarness;
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "needs Docker and the testnet image from build_test_image.sh"]
+async fn harness_serves_nodes_until_dropped() {
+    let scenario = ScenarioBuilder::topology_with(|t| t.network_star().validators(1).executors(1))
+        .enable_node_control()
+        .build();
+
+    let harness = ComposeTestHarness::start(&scenario)
+        .await
+        .expect("compose stack should start");
+    assert_eq!(harness.node_clients().validator_clients().len(), 1);
+    harness
+        .validator(0)
+        .consensus_info()
+        .await
+        .expect("validator should serve consensus info");
+    harness
+        .executor(0)
+        .consensus_info()
+        .await
+        .expect("executor should serve consensus info");
+
+    let validator = harness.validator(0);
+    harness.shutdown();
+    assert!(
+        validator.consensus_info().await.is_err(),
+        "stack should be torn down once the harness is dropped"
+    );
+}
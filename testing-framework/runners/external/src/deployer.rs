@@ -0,0 +1,261 @@
+use std::env;
+
+use async_trait::async_trait;
+use testing_framework_core::{
+    errors::{ClassifiedError, ErrorKind},
+    nodes::HttpOptions,
+    scenario::{
+        AttachDeployer, AttachDeployerError, AttachedNode, Deployer, DryRunReport, Runner, Scenario,
+    },
+};
+use thiserror::Error;
+use url::Url;
+
+/// Comma separated validator API URLs read by [`ExternalDeployer::from_env`].
+pub const VALIDATORS_ENV: &str = "NOMOS_EXTERNAL_VALIDATORS";
+/// Comma separated executor API URLs.
+pub const EXECUTORS_ENV: &str = "NOMOS_EXTERNAL_EXECUTORS";
+/// Comma separated validator testing API URLs, in the order of
+/// [`VALIDATORS_ENV`].
+pub const VALIDATOR_TESTING_ENV: &str = "NOMOS_EXTERNAL_VALIDATOR_TESTING";
+/// Comma separated executor testing API URLs, in the order of
+/// [`EXECUTORS_ENV`].
+pub const EXECUTOR_TESTING_ENV: &str = "NOMOS_EXTERNAL_EXECUTOR_TESTING";
+/// Prometheus scraping the cluster.
+pub const PROMETHEUS_ENV: &str = "NOMOS_EXTERNAL_PROMETHEUS";
+/// Bearer token sent with every node API request.
+pub const BEARER_TOKEN_ENV: &str = "NOMOS_EXTERNAL_BEARER_TOKEN";
+
+/// Points scenarios at an already running cluster, such as a long-lived
+/// testnet, whose endpoints are read from the environment.
+///
+/// This only parses the environment into an [`AttachDeployer`] with API
+/// readiness enabled; build one directly to configure endpoints in code.
+#[derive(Clone, Debug)]
+pub struct ExternalDeployer {
+    attach: AttachDeployer,
+}
+
+/// Errors surfaced by the external deployer.
+#[derive(Debug, Error)]
+pub enum ExternalDeployerError {
+    #[error("no validator endpoint configured; set {VALIDATORS_ENV}")]
+    NoValidators,
+    #[error("{var} lists {testing} testing URLs for {nodes} nodes")]
+    TestingUrlCount {
+        var: &'static str,
+        testing: usize,
+        nodes: usize,
+    },
+    #[error("invalid URL {value:?} in {var}: {source}")]
+    InvalidUrl {
+        var: &'static str,
+        value: String,
+        #[source]
+        source: url::ParseError,
+    },
+    #[error(transparent)]
+    Attach(#[from] AttachDeployerError),
+}
+
+impl ClassifiedError for ExternalDeployerError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::NoValidators | Self::TestingUrlCount { .. } | Self::InvalidUrl { .. } => {
+                ErrorKind::Configuration
+            }
+            Self::Attach(source) => source.kind(),
+        }
+    }
+}
+
+impl ExternalDeployer {
+    /// Read endpoints from [`VALIDATORS_ENV`], [`EXECUTORS_ENV`], their
+    /// testing counterparts, [`PROMETHEUS_ENV`] and [`BEARER_TOKEN_ENV`].
+    pub fn from_env() -> Result<Self, ExternalDeployerError> {
+        Self::from_lookup(|var| env::var(var).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Result<Self, ExternalDeployerError> {
+        let validators = nodes_from_env(&lookup, VALIDATORS_ENV, VALIDATOR_TESTING_ENV)?;
+        if validators.is_empty() {
+            return Err(ExternalDeployerError::NoValidators);
+        }
+        let executors = nodes_from_env(&lookup, EXECUTORS_ENV, EXECUTOR_TESTING_ENV)?;
+
+        let mut attach = AttachDeployer::new().with_readiness(true);
+        for node in validators {
+            attach = attach.with_validator(node.base_url, node.testing_url);
+        }
+        for node in executors {
+            attach = attach.with_executor(node.base_url, node.testing_url);
+        }
+        if let Some(raw) = lookup(PROMETHEUS_ENV).filter(|raw| !raw.is_empty()) {
+            attach = attach.with_prometheus(parse_url(PROMETHEUS_ENV, &raw)?);
+        }
+        if let Some(token) = lookup(BEARER_TOKEN_ENV).filter(|raw| !raw.is_empty()) {
+            attach = attach.with_http_options(HttpOptions::default().with_bearer_token(token));
+        }
+        Ok(Self { attach })
+    }
+
+    #[must_use]
+    /// The attach deployer built from the environment, for further tuning.
+    pub fn into_attach(self) -> AttachDeployer {
+        self.attach
+    }
+}
+
+#[async_trait]
+impl Deployer<()> for ExternalDeployer {
+    type Error = ExternalDeployerError;
+
+    async fn deploy(&self, scenario: &Scenario<()>) -> Result<Runner, Self::Error> {
+        Ok(self.attach.deploy(scenario).await?)
+    }
+
+    fn dry_run(&self, scenario: &Scenario<()>) -> Result<DryRunReport, Self::Error> {
+        Ok(self.attach.dry_run(scenario)?)
+    }
+}
+
+fn nodes_from_env(
+    lookup: &impl Fn(&str) -> Option<String>,
+    var: &'static str,
+    testing_var: &'static str,
+) -> Result<Vec<AttachedNode>, ExternalDeployerError> {
+    let mut nodes = url_list(lookup, var)?
+        .into_iter()
+        .map(|url| AttachedNode::new(url, None))
+        .collect::<Vec<_>>();
+    let testing = url_list(lookup, testing_var)?;
+    if testing.is_empty() {
+        return Ok(nodes);
+    }
+    if testing.len() != nodes.len() {
+        return Err(ExternalDeployerError::TestingUrlCount {
+            var: testing_var,
+            testing: testing.len(),
+            nodes: nodes.len(),
+        });
+    }
+    for (node, testing) in nodes.iter_mut().zip(testing) {
+        node.testing_url = Some(testing);
+    }
+    Ok(nodes)
+}
+
+fn url_list(
+    lookup: &impl Fn(&str) -> Option<String>,
+    var: &'static str,
+) -> Result<Vec<Url>, ExternalDeployerError> {
+    lookup(var)
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .map(|raw| parse_url(var, raw))
+        .collect()
+}
+
+fn parse_url(var: &'static str, raw: &str) -> Result<Url, ExternalDeployerError> {
+    Url::parse(raw).map_err(|source| ExternalDeployerError::InvalidUrl {
+        var,
+        value: raw.to_owned(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn lookup(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars = vars
+            .iter()
+            .map(|(key, value)| ((*key).to_owned(), (*value).to_owned()))
+            .collect::<HashMap<_, _>>();
+        move |var| vars.get(var).cloned()
+    }
+
+    #[test]
+    fn url_lists_are_trimmed_and_skip_empty_entries() {
+        let vars = lookup(&[(VALIDATORS_ENV, " http://a:8080/ ,, http://b:8080/,")]);
+
+        let urls = url_list(&vars, VALIDATORS_ENV).expect("valid urls");
+        assert_eq!(
+            urls.iter().map(Url::as_str).collect::<Vec<_>>(),
+            ["http://a:8080/", "http://b:8080/"]
+        );
+        assert!(url_list(&vars, EXECUTORS_ENV).expect("unset").is_empty());
+
+        let invalid = lookup(&[(VALIDATORS_ENV, "http://a:8080/,not a url")]);
+        assert!(matches!(
+            url_list(&invalid, VALIDATORS_ENV),
+            Err(ExternalDeployerError::InvalidUrl {
+                var: VALIDATORS_ENV,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn testing_urls_pair_with_nodes_in_order() {
+        let vars = lookup(&[
+            (VALIDATORS_ENV, "http://a:8080/,http://b:8080/"),
+            (VALIDATOR_TESTING_ENV, "http://a:8081/,http://b:8081/"),
+        ]);
+
+        let nodes =
+            nodes_from_env(&vars, VALIDATORS_ENV, VALIDATOR_TESTING_ENV).expect("paired nodes");
+        assert_eq!(
+            nodes[1].testing_url.as_ref().map(Url::as_str),
+            Some("http://b:8081/")
+        );
+
+        let vars = lookup(&[
+            (VALIDATORS_ENV, "http://a:8080/,http://b:8080/"),
+            (VALIDATOR_TESTING_ENV, "http://a:8081/"),
+        ]);
+        assert!(matches!(
+            nodes_from_env(&vars, VALIDATORS_ENV, VALIDATOR_TESTING_ENV),
+            Err(ExternalDeployerError::TestingUrlCount {
+                testing: 1,
+                nodes: 2,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn from_env_requires_a_validator() {
+        assert!(matches!(
+            ExternalDeployer::from_lookup(lookup(&[(EXECUTORS_ENV, "http://e:8080/")])),
+            Err(ExternalDeployerError::NoValidators)
+        ));
+
+        let deployer = ExternalDeployer::from_lookup(lookup(&[
+            (VALIDATORS_ENV, "http://a:8080/"),
+            (EXECUTORS_ENV, "http://executor-host:8080/"),
+            (PROMETHEUS_ENV, "http://prometheus-host:9090/"),
+            (BEARER_TOKEN_ENV, "secret"),
+        ]))
+        .expect("valid environment");
+        let debug = format!("{:?}", deployer.into_attach());
+        assert!(debug.contains("executor-host"), "{debug}");
+        assert!(debug.contains("prometheus-host"), "{debug}");
+
+        assert!(matches!(
+            ExternalDeployer::from_lookup(lookup(&[
+                (VALIDATORS_ENV, "http://a:8080/"),
+                (PROMETHEUS_ENV, "::"),
+            ])),
+            Err(ExternalDeployerError::InvalidUrl {
+                var: PROMETHEUS_ENV,
+                ..
+            })
+        ));
+    }
+}
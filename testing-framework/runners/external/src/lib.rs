@@ -0,0 +1,6 @@
+mod deployer;
+
+pub use deployer::{
+    BEARER_TOKEN_ENV, EXECUTOR_TESTING_ENV, EXECUTORS_ENV, ExternalDeployer, ExternalDeployerError,
+    PROMETHEUS_ENV, VALIDATOR_TESTING_ENV, VALIDATORS_ENV,
+};
@@ -1,30 +1,54 @@
+use std::{
+    env,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use anyhow::Error;
 use async_trait::async_trait;
 use kube::Client;
+use serde_yaml::Value;
 use testing_framework_core::{
-    scenario::{BlockFeedTask, CleanupGuard, Deployer, MetricsError, RunContext, Runner, Scenario},
-    topology::generation::GeneratedTopology,
+    artifacts::{ArtifactsError, RunArtifacts},
+    errors::{ClassifiedError, ErrorKind},
+    scenario::{
+        ApiFaultProxies, BlockFeedTask, CleanupGuard, Deployer, DeploymentTimeline, DryRunReport,
+        FaultProxyError, MetricsError, NodeControlHandle, NodeEnv, RequiresNodeControl, RunContext,
+        Runner, Scenario, ScenarioTimings,
+    },
+    topology::{
+        generation::GeneratedTopology,
+        readiness::{ReadinessConfig, ReadinessError, wait_transaction_readiness},
+    },
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 use crate::{
     infrastructure::{
         assets::{AssetsError, prepare_assets},
         cluster::{
-            ClusterEnvironment, NodeClientError, PortSpecs, RemoteReadinessError,
-            build_node_clients, cluster_identifiers, collect_port_specs, ensure_cluster_readiness,
-            install_stack, kill_port_forwards, metrics_handle_from_port, wait_for_ports_or_cleanup,
+            ClusterEnvironment, ClusterIdentifiers, NodeClientError, PortSpecs,
+            RemoteReadinessError, build_node_clients, cluster_identifiers, collect_port_specs,
+            ensure_cluster_readiness, install_stack, kill_port_forwards, metrics_handle_from_port,
+            wait_for_ports_or_cleanup,
         },
         helm::HelmError,
     },
-    lifecycle::{block_feed::spawn_block_feed_with, cleanup::RunnerCleanup},
-    wait::ClusterWaitError,
+    lifecycle::{
+        block_feed::spawn_block_feed_with,
+        cleanup::RunnerCleanup,
+        node_control::K8sNodeControl,
+        wait::{GRAFANA_HTTP_PORT, find_node_port, grafana_service_name},
+    },
+    wait::{ClusterWaitError, PortForward},
 };
 
 /// Deploys a scenario into Kubernetes using Helm charts and port-forwards.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct K8sDeployer {
     readiness_checks: bool,
+    readiness: ReadinessConfig,
+    values_override: Option<Value>,
 }
 
 impl Default for K8sDeployer {
@@ -39,6 +63,8 @@ impl K8sDeployer {
     pub const fn new() -> Self {
         Self {
             readiness_checks: true,
+            readiness: ReadinessConfig::new(),
+            values_override: None,
         }
     }
 
@@ -48,6 +74,30 @@ impl K8sDeployer {
         self.readiness_checks = enabled;
         self
     }
+
+    #[must_use]
+    /// Time allowed for each network/membership readiness check. Defaults to
+    /// the scenario's readiness timing; raise it for large topologies.
+    pub const fn with_readiness_timeout(mut self, timeout: Duration) -> Self {
+        self.readiness = self.readiness.with_timeout(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Delay between readiness polls.
+    pub const fn with_readiness_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.readiness = self.readiness.with_poll_interval(poll_interval);
+        self
+    }
+
+    #[must_use]
+    /// Deep-merge `values` into the generated Helm values, e.g. to set a
+    /// storage class or node selectors the chart exposes. Mappings merge key
+    /// by key; other values replace the generated ones.
+    pub fn with_values_override(mut self, values: Value) -> Self {
+        self.values_override = Some(values);
+        self
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -57,6 +107,10 @@ pub enum K8sRunnerError {
         "kubernetes runner requires at least one validator and one executor (validators={validators}, executors={executors})"
     )]
     UnsupportedTopology { validators: usize, executors: usize },
+    #[error(
+        "kubernetes runner cannot terminate nodes, so the graceful shutdown check is unsupported; use the compose or process runner"
+    )]
+    UnsupportedGracefulShutdown,
     #[error("failed to initialise kubernetes client: {source}")]
     ClientInit {
         #[source]
@@ -64,12 +118,22 @@ pub enum K8sRunnerError {
     },
     #[error(transparent)]
     Assets(#[from] AssetsError),
+    #[error("failed to prepare run artifacts: {source}")]
+    Artifacts {
+        #[from]
+        source: ArtifactsError,
+    },
     #[error(transparent)]
     Helm(#[from] HelmError),
     #[error(transparent)]
     Cluster(#[from] Box<ClusterWaitError>),
     #[error(transparent)]
     Readiness(#[from] RemoteReadinessError),
+    #[error("transaction readiness probe failed: {source}")]
+    TransactionReadiness {
+        #[source]
+        source: ReadinessError,
+    },
     #[error(transparent)]
     NodeClients(#[from] NodeClientError),
     #[error(transparent)]
@@ -81,17 +145,83 @@ pub enum K8sRunnerError {
         #[source]
         source: Error,
     },
+    #[error(transparent)]
+    ApiFaults(#[from] FaultProxyError),
+}
+
+impl ClassifiedError for K8sRunnerError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::UnsupportedTopology { .. }
+            | Self::UnsupportedGracefulShutdown
+            | Self::NodeClients(_)
+            | Self::BlockFeedMissing => ErrorKind::Configuration,
+            Self::ClientInit { .. } | Self::Helm(_) => ErrorKind::Infrastructure,
+            Self::Assets(source) => source.kind(),
+            Self::Artifacts { source } => source.kind(),
+            Self::Cluster(source) => source.kind(),
+            Self::Readiness(source) => source.kind(),
+            Self::TransactionReadiness { source } => source.kind(),
+            Self::Telemetry(source) => source.kind(),
+            Self::BlockFeed { .. } => ErrorKind::NodeFailure,
+            Self::ApiFaults(source) => source.kind(),
+        }
+    }
+}
+
+impl ClassifiedError for AssetsError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Artifacts { source } => source.kind(),
+            Self::Io { .. } => ErrorKind::Infrastructure,
+            Self::WorkspaceRoot { .. }
+            | Self::Cfgsync { .. }
+            | Self::MissingScript { .. }
+            | Self::MissingKzg { .. }
+            | Self::MissingChart { .. }
+            | Self::UnknownEnvTarget { .. }
+            | Self::Values { .. } => ErrorKind::Configuration,
+        }
+    }
+}
+
+impl ClassifiedError for ClusterWaitError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::DeploymentTimeout { .. }
+            | Self::NodeHttpTimeout { .. }
+            | Self::PrometheusTimeout { .. } => ErrorKind::Timeout,
+            Self::DeploymentFetch { .. }
+            | Self::ServiceFetch { .. }
+            | Self::NodePortUnavailable { .. }
+            | Self::PortForward { .. } => ErrorKind::Infrastructure,
+            Self::MissingValidator => ErrorKind::Configuration,
+        }
+    }
+}
+
+impl ClassifiedError for RemoteReadinessError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Endpoint { .. } => ErrorKind::Configuration,
+            Self::Remote { source } => source.kind(),
+        }
+    }
 }
 
 #[async_trait]
-impl Deployer for K8sDeployer {
+impl<Caps> Deployer<Caps> for K8sDeployer
+where
+    Caps: RequiresNodeControl + Send + Sync,
+{
     type Error = K8sRunnerError;
 
-    async fn deploy(&self, scenario: &Scenario) -> Result<Runner, Self::Error> {
+    async fn deploy(&self, scenario: &Scenario<Caps>) -> Result<Runner, Self::Error> {
         let descriptors = scenario.topology().clone();
         let validator_count = descriptors.validators().len();
         let executor_count = descriptors.executors().len();
         ensure_supported_topology(&descriptors)?;
+        ensure_supported_shutdown(scenario.graceful_shutdown())?;
 
         let client = Client::try_default()
             .await
@@ -105,8 +235,33 @@ impl Deployer for K8sDeployer {
         );
 
         let port_specs = collect_port_specs(&descriptors);
-        let mut cluster =
-            Some(setup_cluster(&client, &port_specs, &descriptors, self.readiness_checks).await?);
+        let artifacts = Arc::new(RunArtifacts::create_for(scenario.profile())?);
+        if env::var("K8S_RUNNER_PRESERVE").is_ok() {
+            artifacts.keep();
+        }
+        let startup = Instant::now();
+        let timings = *scenario.timings();
+        let readiness = self
+            .readiness_checks
+            .then(|| self.readiness.or_timings(&timings));
+        let mut timeline = DeploymentTimeline::new();
+        let mut cluster = Some(
+            setup_cluster(
+                &client,
+                &port_specs,
+                &descriptors,
+                Caps::REQUIRED,
+                scenario.node_env(),
+                self.values_override.as_ref(),
+                &artifacts,
+                readiness,
+                &timings,
+                &mut timeline,
+            )
+            .await?,
+        );
+
+        let startup_elapsed = startup.elapsed();
 
         info!("building node clients");
         let node_clients = match build_node_clients(
@@ -124,6 +279,23 @@ impl Deployer for K8sDeployer {
             }
         };
 
+        if scenario.transaction_readiness() {
+            let readiness = self.readiness.or_timings(&timings);
+            if let Err(source) = timeline
+                .measure(
+                    "transaction_readiness",
+                    wait_transaction_readiness(&node_clients, &descriptors, readiness),
+                )
+                .await
+            {
+                if let Some(env) = cluster.as_mut() {
+                    env.fail("transaction readiness probe failed").await;
+                }
+                error!(error = ?source, "transaction readiness probe failed");
+                return Err(K8sRunnerError::TransactionReadiness { source });
+            }
+        }
+
         let telemetry = match metrics_handle_from_port(
             cluster
                 .as_ref()
@@ -140,7 +312,24 @@ impl Deployer for K8sDeployer {
                 return Err(err.into());
             }
         };
-        let (block_feed, block_feed_guard) = match spawn_block_feed_with(&node_clients).await {
+        let (node_clients, api_faults) =
+            match ApiFaultProxies::wrap(node_clients, scenario.api_faults()).await {
+                Ok(wrapped) => wrapped,
+                Err(err) => {
+                    if let Some(env) = cluster.as_mut() {
+                        env.fail("failed to start node api fault proxies").await;
+                    }
+                    error!(error = ?err, "failed to start node api fault proxies");
+                    return Err(err.into());
+                }
+            };
+        let (block_feed, block_feed_guard) = match timeline
+            .measure(
+                "block_feed",
+                spawn_block_feed_with(&node_clients, &descriptors),
+            )
+            .await
+        {
             Ok(pair) => pair,
             Err(err) => {
                 if let Some(env) = cluster.as_mut() {
@@ -151,10 +340,20 @@ impl Deployer for K8sDeployer {
             }
         };
 
-        tracing::info!(
-            grafana_url = %format!("http://{}:{}/", crate::host::node_host(), 30030),
-            "grafana dashboard available via NodePort"
+        let node_control = maybe_node_control::<Caps>(
+            cluster
+                .as_ref()
+                .expect("cluster must be available for node control"),
+            &descriptors,
+            timings,
         );
+
+        log_grafana_url(
+            cluster
+                .as_ref()
+                .expect("cluster must be available for grafana lookup"),
+        )
+        .await;
         let (cleanup, port_forwards) = cluster
             .take()
             .expect("cluster should still be available")
@@ -171,8 +370,14 @@ impl Deployer for K8sDeployer {
             scenario.duration(),
             telemetry,
             block_feed,
-            None,
-        );
+            node_control,
+        )
+        .with_deployment_timeline(timeline)
+        .with_api_faults(api_faults)
+        .with_artifacts(artifacts);
+        context
+            .report()
+            .record_readiness("k8s_cluster", startup_elapsed);
         info!(
             validators = validator_count,
             executors = executor_count,
@@ -181,6 +386,31 @@ impl Deployer for K8sDeployer {
         );
         Ok(Runner::new(context, Some(cleanup_guard)))
     }
+
+    fn dry_run(&self, scenario: &Scenario<Caps>) -> Result<DryRunReport, Self::Error> {
+        let descriptors = scenario.topology();
+        let mut report = scenario.dry_run().with_deployer("k8s");
+        report.record_check(
+            "k8s_topology",
+            ensure_supported_topology(descriptors).map_err(|err| err.to_string()),
+        );
+        report.record_check(
+            "k8s_graceful_shutdown",
+            ensure_supported_shutdown(scenario.graceful_shutdown()).map_err(|err| err.to_string()),
+        );
+        let artifacts = Arc::new(RunArtifacts::create_for(scenario.profile())?);
+        dry_run_assets(
+            descriptors,
+            Caps::REQUIRED,
+            scenario.node_env(),
+            self.values_override.as_ref(),
+            &artifacts,
+            &mut report,
+        )?;
+        artifacts.keep();
+        info!(dir = %artifacts.root().display(), "k8s dry run rendered");
+        Ok(report)
+    }
 }
 
 impl From<ClusterWaitError> for K8sRunnerError {
@@ -189,6 +419,81 @@ impl From<ClusterWaitError> for K8sRunnerError {
     }
 }
 
+/// Node control is also needed, regardless of `Caps`, to start late-joining
+/// nodes.
+fn maybe_node_control<Caps>(
+    cluster: &ClusterEnvironment,
+    descriptors: &GeneratedTopology,
+    timings: ScenarioTimings,
+) -> Option<Arc<dyn NodeControlHandle>>
+where
+    Caps: RequiresNodeControl,
+{
+    let needed = Caps::REQUIRED || !descriptors.late_joins().is_empty();
+    needed.then(|| {
+        Arc::new(K8sNodeControl::new(
+            cluster.client().clone(),
+            cluster.namespace().to_owned(),
+            cluster.release().to_owned(),
+            timings,
+        )) as Arc<dyn NodeControlHandle>
+    })
+}
+
+/// Grafana gets a cluster-assigned `NodePort` so parallel runs do not clash.
+async fn log_grafana_url(cluster: &ClusterEnvironment) {
+    let service = grafana_service_name(cluster.release());
+    match find_node_port(
+        cluster.client(),
+        cluster.namespace(),
+        &service,
+        GRAFANA_HTTP_PORT,
+    )
+    .await
+    {
+        Ok(port) => info!(
+            grafana_url = %format!("http://{}:{port}/", crate::host::node_host()),
+            "grafana dashboard available via NodePort"
+        ),
+        Err(err) => warn!(%service, error = %err, "grafana NodePort not found"),
+    }
+}
+
+/// Render the cfgsync config and Helm values into the run artifacts without
+/// talking to the cluster, recording them and whether they rendered in
+/// `report`. Failures outside the scenario's configuration are returned.
+fn dry_run_assets(
+    descriptors: &GeneratedTopology,
+    node_control: bool,
+    node_env: &NodeEnv,
+    values_override: Option<&Value>,
+    artifacts: &Arc<RunArtifacts>,
+    report: &mut DryRunReport,
+) -> Result<(), K8sRunnerError> {
+    let release = cluster_identifiers().release;
+    match prepare_assets(
+        descriptors,
+        node_control,
+        &release,
+        node_env,
+        values_override,
+        artifacts,
+    ) {
+        Ok(assets) => {
+            report.record_file("cfgsync.yaml", &assets.cfgsync_file);
+            report.record_file("values.yaml", &assets.values_file);
+            report.record_image(assets.image);
+            report.record_check("k8s_assets", Ok(()));
+            Ok(())
+        }
+        Err(err) if err.kind() == ErrorKind::Configuration => {
+            report.record_check("k8s_assets", Err(err.to_string()));
+            Ok(())
+        }
+        Err(err) => Err(err.into()),
+    }
+}
+
 fn ensure_supported_topology(descriptors: &GeneratedTopology) -> Result<(), K8sRunnerError> {
     let validators = descriptors.validators().len();
     let executors = descriptors.executors().len();
@@ -201,25 +506,78 @@ fn ensure_supported_topology(descriptors: &GeneratedTopology) -> Result<(), K8sR
     Ok(())
 }
 
+/// Nodes cannot be terminated on k8s, so refuse the graceful shutdown check
+/// before deploying rather than failing once the run is over.
+const fn ensure_supported_shutdown(
+    graceful_shutdown: Option<Duration>,
+) -> Result<(), K8sRunnerError> {
+    if graceful_shutdown.is_some() {
+        return Err(K8sRunnerError::UnsupportedGracefulShutdown);
+    }
+    Ok(())
+}
+
 async fn setup_cluster(
     client: &Client,
     specs: &PortSpecs,
     descriptors: &GeneratedTopology,
-    readiness_checks: bool,
+    node_control: bool,
+    node_env: &NodeEnv,
+    values_override: Option<&Value>,
+    artifacts: &Arc<RunArtifacts>,
+    readiness: Option<ReadinessConfig>,
+    timings: &ScenarioTimings,
+    timeline: &mut DeploymentTimeline,
 ) -> Result<ClusterEnvironment, K8sRunnerError> {
-    let assets = prepare_assets(descriptors)?;
+    let identifiers = cluster_identifiers();
+    let ClusterIdentifiers {
+        namespace, release, ..
+    } = identifiers.clone();
+    let assets_started = Instant::now();
+    let assets = prepare_assets(
+        descriptors,
+        node_control,
+        &release,
+        node_env,
+        values_override,
+        artifacts,
+    )?;
+    timeline.record("assets", assets_started);
     let validators = descriptors.validators().len();
     let executors = descriptors.executors().len();
 
-    let (namespace, release) = cluster_identifiers();
-    info!(%namespace, %release, validators, executors, "preparing k8s assets and namespace");
+    info!(
+        %namespace,
+        %release,
+        owns_namespace = identifiers.owns_namespace,
+        validators,
+        executors,
+        "preparing k8s assets and namespace"
+    );
 
-    let mut cleanup_guard =
-        Some(install_stack(client, &assets, &namespace, &release, validators, executors).await?);
+    let mut cleanup_guard = Some(
+        timeline
+            .measure(
+                "helm_install",
+                install_stack(client, &assets, &identifiers, validators, executors),
+            )
+            .await?,
+    );
 
     info!("waiting for helm-managed services to become ready");
-    let cluster_ready =
-        wait_for_ports_or_cleanup(client, &namespace, &release, specs, &mut cleanup_guard).await?;
+    let cluster_ready = timeline
+        .measure(
+            "port_discovery",
+            wait_for_ports_or_cleanup(
+                client,
+                &namespace,
+                &release,
+                specs,
+                timings,
+                &mut cleanup_guard,
+            ),
+        )
+        .await?;
 
     info!(
         prometheus_port = cluster_ready.ports.prometheus,
@@ -237,9 +595,14 @@ async fn setup_cluster(
         cluster_ready.port_forwards,
     );
 
-    if readiness_checks {
+    if let Some(readiness) = readiness {
         info!("probing cluster readiness");
-        ensure_cluster_readiness(descriptors, &environment).await?;
+        timeline
+            .measure(
+                "readiness",
+                ensure_cluster_readiness(descriptors, &environment, readiness),
+            )
+            .await?;
         info!("cluster readiness probes passed");
     }
 
@@ -249,14 +612,14 @@ async fn setup_cluster(
 struct K8sCleanupGuard {
     cleanup: RunnerCleanup,
     block_feed: Option<BlockFeedTask>,
-    port_forwards: Vec<std::process::Child>,
+    port_forwards: Vec<PortForward>,
 }
 
 impl K8sCleanupGuard {
     const fn new(
         cleanup: RunnerCleanup,
         block_feed: BlockFeedTask,
-        port_forwards: Vec<std::process::Child>,
+        port_forwards: Vec<PortForward>,
     ) -> Self {
         Self {
             cleanup,
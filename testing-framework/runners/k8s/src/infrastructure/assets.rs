@@ -2,19 +2,34 @@ use std::{
     collections::BTreeMap,
     env, fs, io,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use anyhow::{Context as _, Result as AnyResult};
+use cfgsync::config::schema::{SchemaError, validate_str};
 use serde::Serialize;
-use tempfile::TempDir;
+use serde_yaml::Value;
 use testing_framework_core::{
-    constants::cfgsync_port,
-    scenario::cfgsync::{apply_topology_overrides, load_cfgsync_template, render_cfgsync_yaml},
-    topology::generation::GeneratedTopology,
+    artifacts::{ArtifactsError, RunArtifacts},
+    constants::{JOIN_MARKER_ENV, JOIN_MARKER_PATH, cfgsync_port},
+    scenario::{
+        NodeEnv, NodeTarget, POL_PROOF_DEV_MODE_ENV,
+        cfgsync::{
+            apply_topology_overrides, load_cfgsync_template, render_cfgsync_yaml,
+            set_otlp_metrics_host,
+        },
+        pol_proof_dev_mode,
+    },
+    topology::{
+        config::{NodeResources, ResourceQuantities},
+        generation::GeneratedTopology,
+    },
 };
 use thiserror::Error;
 use tracing::{debug, info};
 
+use crate::lifecycle::wait::prometheus_service_name;
+
 /// Paths and image metadata required to deploy the Helm chart.
 pub struct RunnerAssets {
     pub image: String,
@@ -26,7 +41,8 @@ pub struct RunnerAssets {
     pub run_nomos_node_script: PathBuf,
     pub run_nomos_executor_script: PathBuf,
     pub values_file: PathBuf,
-    _tempdir: TempDir,
+    /// Run directory the rendered files are written to.
+    pub artifacts: Arc<RunArtifacts>,
 }
 
 pub fn cfgsync_port_value() -> u16 {
@@ -46,16 +62,23 @@ pub enum AssetsError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("rendered cfgsync configuration is invalid: {source}")]
+    InvalidCfgsync {
+        #[source]
+        source: SchemaError,
+    },
     #[error("missing required script at {path}")]
     MissingScript { path: PathBuf },
     #[error("missing KZG parameters at {path}; build them with `make kzgrs_test_params`")]
     MissingKzg { path: PathBuf },
     #[error("missing Helm chart at {path}; ensure the repository is up-to-date")]
     MissingChart { path: PathBuf },
-    #[error("failed to create temporary directory for rendered assets: {source}")]
-    TempDir {
+    #[error("node env override targets {target}, which is not part of the topology")]
+    UnknownEnvTarget { target: NodeTarget },
+    #[error("failed to create directory for rendered assets: {source}")]
+    Artifacts {
         #[source]
-        source: io::Error,
+        source: ArtifactsError,
     },
     #[error("failed to write asset at {path}: {source}")]
     Io {
@@ -71,28 +94,37 @@ pub enum AssetsError {
 }
 
 /// Render cfgsync config, Helm values, and locate scripts/KZG assets for a
-/// topology.
-pub fn prepare_assets(topology: &GeneratedTopology) -> Result<RunnerAssets, AssetsError> {
+/// topology. `values_override` is deep-merged into the generated Helm values.
+/// Rendered files go to the `helm` directory of `artifacts`. With
+/// `node_control`, cfgsync serves hosts registering after the initial round.
+pub fn prepare_assets(
+    topology: &GeneratedTopology,
+    node_control: bool,
+    release: &str,
+    node_env: &NodeEnv,
+    values_override: Option<&Value>,
+    artifacts: &Arc<RunArtifacts>,
+) -> Result<RunnerAssets, AssetsError> {
     info!(
         validators = topology.validators().len(),
         executors = topology.executors().len(),
         "preparing k8s runner assets"
     );
+    validate_env_targets(topology, node_env)?;
 
     let root = workspace_root().map_err(|source| AssetsError::WorkspaceRoot { source })?;
-    let cfgsync_yaml = render_cfgsync_config(&root, topology)?;
+    let cfgsync_yaml = render_cfgsync_config(&root, topology, node_control, release)?;
 
-    let tempdir = tempfile::Builder::new()
-        .prefix("nomos-helm-")
-        .tempdir()
-        .map_err(|source| AssetsError::TempDir { source })?;
+    let rendered_dir = artifacts
+        .dir("helm")
+        .map_err(|source| AssetsError::Artifacts { source })?;
 
-    let cfgsync_file = write_temp_file(tempdir.path(), "cfgsync.yaml", cfgsync_yaml)?;
+    let cfgsync_file = write_temp_file(&rendered_dir, "cfgsync.yaml", cfgsync_yaml)?;
     let scripts = validate_scripts(&root)?;
     let kzg_path = validate_kzg_params(&root)?;
     let chart_path = helm_chart_path()?;
-    let values_yaml = render_values_yaml(topology)?;
-    let values_file = write_temp_file(tempdir.path(), "values.yaml", values_yaml)?;
+    let values_yaml = render_values_yaml(topology, node_env, values_override)?;
+    let values_file = write_temp_file(&rendered_dir, "values.yaml", values_yaml)?;
     let image = env::var("NOMOS_TESTNET_IMAGE")
         .unwrap_or_else(|_| String::from("logos-blockchain-testing:local"));
 
@@ -115,21 +147,29 @@ pub fn prepare_assets(topology: &GeneratedTopology) -> Result<RunnerAssets, Asse
         run_nomos_node_script: scripts.run_node,
         run_nomos_executor_script: scripts.run_executor,
         values_file,
-        _tempdir: tempdir,
+        artifacts: Arc::clone(artifacts),
     })
 }
 
 const CFGSYNC_K8S_TIMEOUT_SECS: u64 = 300;
-const DEFAULT_GRAFANA_NODE_PORT: u16 = 30030;
 
-fn render_cfgsync_config(root: &Path, topology: &GeneratedTopology) -> Result<String, AssetsError> {
+fn render_cfgsync_config(
+    root: &Path,
+    topology: &GeneratedTopology,
+    node_control: bool,
+    release: &str,
+) -> Result<String, AssetsError> {
     let cfgsync_template_path = stack_assets_root(root).join("cfgsync.yaml");
     debug!(path = %cfgsync_template_path.display(), "loading cfgsync template");
     let mut cfg = load_cfgsync_template(&cfgsync_template_path)
         .map_err(|source| AssetsError::Cfgsync { source })?;
-    apply_topology_overrides(&mut cfg, topology, true);
+    apply_topology_overrides(&mut cfg, topology, true, node_control);
     cfg.timeout = cfg.timeout.max(CFGSYNC_K8S_TIMEOUT_SECS);
-    render_cfgsync_yaml(&cfg).map_err(|source| AssetsError::Cfgsync { source })
+    set_otlp_metrics_host(&mut cfg, &prometheus_service_name(release))
+        .map_err(|source| AssetsError::Cfgsync { source })?;
+    let yaml = render_cfgsync_yaml(&cfg).map_err(|source| AssetsError::Cfgsync { source })?;
+    validate_str(&yaml).map_err(|source| AssetsError::InvalidCfgsync { source })?;
+    Ok(yaml)
 }
 
 struct ScriptPaths {
@@ -189,11 +229,54 @@ fn helm_chart_path() -> Result<PathBuf, AssetsError> {
     }
 }
 
-fn render_values_yaml(topology: &GeneratedTopology) -> Result<String, AssetsError> {
-    let values = build_values(topology);
+fn validate_env_targets(
+    topology: &GeneratedTopology,
+    node_env: &NodeEnv,
+) -> Result<(), AssetsError> {
+    for target in node_env.targets() {
+        let count = match target {
+            NodeTarget::Validator(_) => topology.validators().len(),
+            NodeTarget::Executor(_) => topology.executors().len(),
+        };
+        if target.index() >= count {
+            return Err(AssetsError::UnknownEnvTarget { target });
+        }
+    }
+    Ok(())
+}
+
+fn render_values_yaml(
+    topology: &GeneratedTopology,
+    node_env: &NodeEnv,
+    values_override: Option<&Value>,
+) -> Result<String, AssetsError> {
+    let mut values = serde_yaml::to_value(build_values(topology, node_env))
+        .map_err(|source| AssetsError::Values { source })?;
+    if let Some(overlay) = values_override {
+        debug!("merging Helm values override");
+        merge_values(&mut values, overlay);
+    }
     serde_yaml::to_string(&values).map_err(|source| AssetsError::Values { source })
 }
 
+/// Deep-merge `overlay` into `base`: mappings are merged key by key, any
+/// other value (including sequences) replaces the base value.
+fn merge_values(base: &mut Value, overlay: &Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay.clone(),
+    }
+}
+
 fn write_temp_file(
     dir: &Path,
     name: &str,
@@ -265,6 +348,35 @@ struct NodeValues {
     #[serde(rename = "testingHttpPort")]
     testing_http_port: u16,
     env: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resources: Option<ResourceValues>,
+}
+
+#[derive(Serialize)]
+struct ResourceValues {
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    requests: BTreeMap<&'static str, String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    limits: BTreeMap<&'static str, String>,
+}
+
+impl ResourceValues {
+    fn from_resources(resources: &NodeResources) -> Option<Self> {
+        if resources.is_empty() {
+            return None;
+        }
+        Some(Self {
+            requests: quantities(&resources.requests),
+            limits: quantities(&resources.limits),
+        })
+    }
+}
+
+fn quantities(quantities: &ResourceQuantities) -> BTreeMap<&'static str, String> {
+    [("cpu", &quantities.cpu), ("memory", &quantities.memory)]
+        .into_iter()
+        .filter_map(|(name, value)| value.clone().map(|value| (name, value)))
+        .collect()
 }
 
 #[derive(Serialize)]
@@ -288,11 +400,11 @@ struct GrafanaServiceValues {
     node_port: Option<u16>,
 }
 
-fn build_values(topology: &GeneratedTopology) -> HelmValues {
+fn build_values(topology: &GeneratedTopology, node_env: &NodeEnv) -> HelmValues {
     let cfgsync = CfgsyncValues {
         port: cfgsync_port(),
     };
-    let pol_mode = pol_proof_mode();
+    let pol_mode = pol_proof_dev_mode().to_string();
     let grafana = GrafanaValues {
         enabled: true,
         image: "grafana/grafana:10.4.1".into(),
@@ -301,17 +413,19 @@ fn build_values(topology: &GeneratedTopology) -> HelmValues {
         admin_password: "admin".into(),
         service: GrafanaServiceValues {
             type_field: "NodePort".into(),
-            node_port: Some(DEFAULT_GRAFANA_NODE_PORT),
+            node_port: None,
         },
     };
-    debug!(pol_mode, "rendering Helm values for k8s stack");
+    let ip_stack = topology.config().network_params.ip_stack.as_str();
+    debug!(pol_mode, ip_stack, "rendering Helm values for k8s stack");
     let validators = topology
         .validators()
         .iter()
         .enumerate()
         .map(|(index, validator)| {
             let mut env = BTreeMap::new();
-            env.insert("POL_PROOF_DEV_MODE".into(), pol_mode.clone());
+            env.insert(POL_PROOF_DEV_MODE_ENV.into(), pol_mode.clone());
+            env.insert("CFG_IP_STACK".into(), ip_stack.into());
             env.insert(
                 "CFG_NETWORK_PORT".into(),
                 validator.network_port().to_string(),
@@ -333,11 +447,23 @@ fn build_values(topology: &GeneratedTopology) -> HelmValues {
             );
             env.insert("CFG_HOST_KIND".into(), "validator".into());
             env.insert("CFG_HOST_IDENTIFIER".into(), format!("validator-{index}"));
+            if topology.config().joins_late(NodeTarget::Validator(index)) {
+                env.insert(JOIN_MARKER_ENV.into(), JOIN_MARKER_PATH.into());
+            }
+            for (key, value) in node_env.vars_for(NodeTarget::Validator(index)) {
+                env.insert(key.into(), value.into());
+            }
 
             NodeValues {
                 api_port: validator.general.api_config.address.port(),
                 testing_http_port: validator.general.api_config.testing_http_address.port(),
                 env,
+                resources: ResourceValues::from_resources(
+                    topology
+                        .config()
+                        .resources
+                        .for_node(NodeTarget::Validator(index)),
+                ),
             }
         })
         .collect();
@@ -348,7 +474,8 @@ fn build_values(topology: &GeneratedTopology) -> HelmValues {
         .enumerate()
         .map(|(index, executor)| {
             let mut env = BTreeMap::new();
-            env.insert("POL_PROOF_DEV_MODE".into(), pol_mode.clone());
+            env.insert(POL_PROOF_DEV_MODE_ENV.into(), pol_mode.clone());
+            env.insert("CFG_IP_STACK".into(), ip_stack.into());
             env.insert(
                 "CFG_NETWORK_PORT".into(),
                 executor.network_port().to_string(),
@@ -370,11 +497,23 @@ fn build_values(topology: &GeneratedTopology) -> HelmValues {
             );
             env.insert("CFG_HOST_KIND".into(), "executor".into());
             env.insert("CFG_HOST_IDENTIFIER".into(), format!("executor-{index}"));
+            if topology.config().joins_late(NodeTarget::Executor(index)) {
+                env.insert(JOIN_MARKER_ENV.into(), JOIN_MARKER_PATH.into());
+            }
+            for (key, value) in node_env.vars_for(NodeTarget::Executor(index)) {
+                env.insert(key.into(), value.into());
+            }
 
             NodeValues {
                 api_port: executor.general.api_config.address.port(),
                 testing_http_port: executor.general.api_config.testing_http_address.port(),
                 env,
+                resources: ResourceValues::from_resources(
+                    topology
+                        .config()
+                        .resources
+                        .for_node(NodeTarget::Executor(index)),
+                ),
             }
         })
         .collect();
@@ -393,6 +532,49 @@ fn build_values(topology: &GeneratedTopology) -> HelmValues {
     }
 }
 
-fn pol_proof_mode() -> String {
-    env::var("POL_PROOF_DEV_MODE").unwrap_or_else(|_| "true".to_string())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn yaml(raw: &str) -> Value {
+        serde_yaml::from_str(raw).expect("valid yaml")
+    }
+
+    #[test]
+    fn overrides_merge_mappings_and_replace_everything_else() {
+        let mut values = yaml(
+            "
+validators:
+  count: 2
+  resources: { cpu: 500m, memory: 1Gi }
+  env: [A, B]
+image: nomos:latest
+",
+        );
+        let overlay = yaml(
+            "
+validators:
+  resources: { memory: 2Gi }
+  env: [C]
+  nodeSelector: { pool: nomos }
+image: null
+",
+        );
+
+        merge_values(&mut values, &overlay);
+
+        assert_eq!(
+            values,
+            yaml(
+                "
+validators:
+  count: 2
+  resources: { cpu: 500m, memory: 2Gi }
+  env: [C]
+  nodeSelector: { pool: nomos }
+image: null
+",
+            )
+        );
+    }
 }
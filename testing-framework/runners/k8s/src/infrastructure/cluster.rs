@@ -4,8 +4,14 @@ use kube::Client;
 use reqwest::Url;
 use testing_framework_core::{
     nodes::ApiClient,
-    scenario::{CleanupGuard, Metrics, MetricsError, NodeClients, http_probe::NodeRole},
-    topology::{generation::GeneratedTopology, readiness::ReadinessError},
+    scenario::{
+        CleanupGuard, Metrics, MetricsError, NodeClients, NodeTarget, ScenarioTimings,
+        http_probe::NodeRole,
+    },
+    topology::{
+        generation::GeneratedTopology,
+        readiness::{ReadinessConfig, ReadinessError},
+    },
 };
 use tracing::{debug, info};
 use url::ParseError;
@@ -14,14 +20,16 @@ use uuid::Uuid;
 use crate::{
     host::node_host,
     infrastructure::assets::RunnerAssets,
-    lifecycle::{cleanup::RunnerCleanup, logs::dump_namespace_logs},
-    wait::{ClusterPorts, ClusterReady, NodeConfigPorts, wait_for_cluster_ready},
+    lifecycle::{cleanup::RunnerCleanup, logs::dump_release_logs},
+    wait::{ClusterPorts, ClusterReady, NodeConfigPorts, PortForward, wait_for_cluster_ready},
 };
 
 #[derive(Default)]
 pub struct PortSpecs {
     pub validators: Vec<NodeConfigPorts>,
     pub executors: Vec<NodeConfigPorts>,
+    /// Nodes whose process is held back, so their HTTP ports stay closed.
+    pub late_joins: Vec<NodeTarget>,
 }
 
 /// Holds k8s namespace, Helm release, port forwards, and cleanup guard.
@@ -35,7 +43,7 @@ pub struct ClusterEnvironment {
     executor_api_ports: Vec<u16>,
     executor_testing_ports: Vec<u16>,
     prometheus_port: u16,
-    port_forwards: Vec<std::process::Child>,
+    port_forwards: Vec<PortForward>,
 }
 
 impl ClusterEnvironment {
@@ -45,7 +53,7 @@ impl ClusterEnvironment {
         release: String,
         cleanup: RunnerCleanup,
         ports: &ClusterPorts,
-        port_forwards: Vec<std::process::Child>,
+        port_forwards: Vec<PortForward>,
     ) -> Self {
         Self {
             client,
@@ -68,20 +76,32 @@ impl ClusterEnvironment {
             release = %self.release,
             "k8s stack failure; collecting diagnostics"
         );
-        dump_namespace_logs(&self.client, &self.namespace).await;
+        dump_release_logs(&self.client, &self.namespace, &self.release).await;
         kill_port_forwards(&mut self.port_forwards);
         if let Some(guard) = self.cleanup.take() {
             CleanupGuard::cleanup(Box::new(guard));
         }
     }
 
-    pub fn into_cleanup(self) -> (RunnerCleanup, Vec<std::process::Child>) {
+    pub fn into_cleanup(self) -> (RunnerCleanup, Vec<PortForward>) {
         (
             self.cleanup.expect("cleanup guard should be available"),
             self.port_forwards,
         )
     }
 
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn release(&self) -> &str {
+        &self.release
+    }
+
     pub fn prometheus_port(&self) -> u16 {
         self.prometheus_port
     }
@@ -149,9 +169,16 @@ pub fn collect_port_specs(descriptors: &GeneratedTopology) -> PortSpecs {
         })
         .collect();
 
+    let late_joins = descriptors
+        .late_joins()
+        .iter()
+        .map(|join| join.target)
+        .collect();
+
     let specs = PortSpecs {
         validators,
         executors,
+        late_joins,
     };
 
     debug!(
@@ -201,15 +228,31 @@ pub fn metrics_handle_from_port(port: u16) -> Result<Metrics, MetricsError> {
 pub async fn ensure_cluster_readiness(
     descriptors: &GeneratedTopology,
     cluster: &ClusterEnvironment,
+    readiness: ReadinessConfig,
 ) -> Result<(), RemoteReadinessError> {
     info!("waiting for remote readiness (API + membership)");
+    // Late-joining nodes are held back until the run starts.
+    let config = descriptors.config();
+    let initial = |ports: &[u16], target: fn(usize) -> NodeTarget| {
+        ports
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !config.joins_late(target(*index)))
+            .map(|(_, port)| *port)
+            .collect::<Vec<_>>()
+    };
     let (validator_api, validator_testing) = cluster.validator_ports();
     let (executor_api, executor_testing) = cluster.executor_ports();
+    let validator_api = initial(validator_api, NodeTarget::Validator);
+    let validator_testing = initial(validator_testing, NodeTarget::Validator);
+    let executor_api = initial(executor_api, NodeTarget::Executor);
+    let executor_testing = initial(executor_testing, NodeTarget::Executor);
+    let descriptors = descriptors.initial_topology();
 
-    let validator_urls = readiness_urls(validator_api, NodeRole::Validator)?;
-    let executor_urls = readiness_urls(executor_api, NodeRole::Executor)?;
-    let validator_membership_urls = readiness_urls(validator_testing, NodeRole::Validator)?;
-    let executor_membership_urls = readiness_urls(executor_testing, NodeRole::Executor)?;
+    let validator_urls = readiness_urls(&validator_api, NodeRole::Validator)?;
+    let executor_urls = readiness_urls(&executor_api, NodeRole::Executor)?;
+    let validator_membership_urls = readiness_urls(&validator_testing, NodeRole::Validator)?;
+    let executor_membership_urls = readiness_urls(&executor_testing, NodeRole::Executor)?;
 
     descriptors
         .wait_remote_readiness(
@@ -217,6 +260,7 @@ pub async fn ensure_cluster_readiness(
             &executor_urls,
             Some(&validator_membership_urls),
             Some(&executor_membership_urls),
+            readiness,
         )
         .await
         .map_err(|source| RemoteReadinessError::Remote { source })?;
@@ -230,20 +274,54 @@ pub async fn ensure_cluster_readiness(
     Ok(())
 }
 
-pub fn cluster_identifiers() -> (String, String) {
-    let run_id = Uuid::new_v4().simple().to_string();
-    let namespace = format!("nomos-k8s-{run_id}");
-    (namespace.clone(), namespace)
+/// Pins the namespace instead of generating one per run, for clusters where
+/// the runner may only deploy into pre-provisioned namespaces. A pinned
+/// namespace is left in place on cleanup.
+pub const K8S_NAMESPACE_ENV: &str = "K8S_RUNNER_NAMESPACE";
+/// Pins the Helm release name instead of generating one per run.
+pub const K8S_RELEASE_ENV: &str = "K8S_RUNNER_RELEASE";
+
+/// Namespace and Helm release a run deploys into.
+#[derive(Clone, Debug)]
+pub struct ClusterIdentifiers {
+    pub namespace: String,
+    pub release: String,
+    /// Whether the namespace was generated for this run and should be
+    /// deleted with it.
+    pub owns_namespace: bool,
+}
+
+/// Unique `nomos-k8s-<uuid>` names so parallel runs can share a cluster,
+/// unless pinned by [`K8S_NAMESPACE_ENV`] / [`K8S_RELEASE_ENV`]. The release
+/// stays unique under a pinned namespace so runs can share that too.
+pub fn cluster_identifiers() -> ClusterIdentifiers {
+    let generated = format!("nomos-k8s-{}", Uuid::new_v4().simple());
+    let pinned_namespace = non_empty_env(K8S_NAMESPACE_ENV);
+    let owns_namespace = pinned_namespace.is_none();
+    ClusterIdentifiers {
+        namespace: pinned_namespace.unwrap_or_else(|| generated.clone()),
+        release: non_empty_env(K8S_RELEASE_ENV).unwrap_or(generated),
+        owns_namespace,
+    }
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    env::var(key)
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
 }
 
 pub async fn install_stack(
     client: &Client,
     assets: &RunnerAssets,
-    namespace: &str,
-    release: &str,
+    identifiers: &ClusterIdentifiers,
     validators: usize,
     executors: usize,
 ) -> Result<RunnerCleanup, crate::deployer::K8sRunnerError> {
+    let ClusterIdentifiers {
+        namespace, release, ..
+    } = identifiers;
     tracing::info!(
         release = %release,
         namespace = %namespace,
@@ -256,8 +334,9 @@ pub async fn install_stack(
     let preserve = env::var("K8S_RUNNER_PRESERVE").is_ok();
     Ok(RunnerCleanup::new(
         client.clone(),
-        namespace.to_owned(),
-        release.to_owned(),
+        namespace.clone(),
+        release.clone(),
+        identifiers.owns_namespace,
         preserve,
     ))
 }
@@ -267,6 +346,7 @@ pub async fn wait_for_ports_or_cleanup(
     namespace: &str,
     release: &str,
     specs: &PortSpecs,
+    timings: &ScenarioTimings,
     cleanup_guard: &mut Option<RunnerCleanup>,
 ) -> Result<ClusterReady, crate::deployer::K8sRunnerError> {
     info!(
@@ -282,6 +362,8 @@ pub async fn wait_for_ports_or_cleanup(
         release,
         &specs.validators,
         &specs.executors,
+        &specs.late_joins,
+        timings,
     )
     .await
     {
@@ -295,22 +377,30 @@ pub async fn wait_for_ports_or_cleanup(
             Ok(ports)
         }
         Err(err) => {
-            cleanup_pending(client, namespace, cleanup_guard).await;
+            cleanup_pending(client, namespace, release, cleanup_guard).await;
             Err(err.into())
         }
     }
 }
 
-pub fn kill_port_forwards(handles: &mut Vec<std::process::Child>) {
-    for handle in handles.iter_mut() {
-        let _ = handle.kill();
-        let _ = handle.wait();
+pub fn kill_port_forwards(handles: &mut Vec<PortForward>) {
+    for handle in handles.iter().filter(|handle| handle.reconnects() > 0) {
+        debug!(
+            service = handle.service(),
+            reconnects = handle.reconnects(),
+            "port-forward followed pod replacements"
+        );
     }
     handles.clear();
 }
 
-async fn cleanup_pending(client: &Client, namespace: &str, guard: &mut Option<RunnerCleanup>) {
-    crate::lifecycle::logs::dump_namespace_logs(client, namespace).await;
+async fn cleanup_pending(
+    client: &Client,
+    namespace: &str,
+    release: &str,
+    guard: &mut Option<RunnerCleanup>,
+) {
+    dump_release_logs(client, namespace, release).await;
     if let Some(guard) = guard.take() {
         CleanupGuard::cleanup(Box::new(guard));
     }
@@ -7,3 +7,5 @@ pub mod wait {
 }
 
 pub use deployer::{K8sDeployer, K8sRunnerError};
+pub use infrastructure::cluster::{K8S_NAMESPACE_ENV, K8S_RELEASE_ENV};
+pub use lifecycle::node_control::{K8sNodeControl, K8sNodeControlError};
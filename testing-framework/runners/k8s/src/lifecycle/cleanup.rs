@@ -17,12 +17,20 @@ pub struct RunnerCleanup {
     client: Client,
     namespace: String,
     release: String,
+    owns_namespace: bool,
     preserve: bool,
 }
 
 impl RunnerCleanup {
-    /// Build a cleanup guard; `preserve` skips deletion when true.
-    pub fn new(client: Client, namespace: String, release: String, preserve: bool) -> Self {
+    /// Build a cleanup guard; `preserve` skips deletion when true and the
+    /// namespace is only deleted when `owns_namespace` is set.
+    pub fn new(
+        client: Client,
+        namespace: String,
+        release: String,
+        owns_namespace: bool,
+        preserve: bool,
+    ) -> Self {
         debug_assert!(
             !namespace.is_empty() && !release.is_empty(),
             "k8s cleanup requires namespace and release"
@@ -31,6 +39,7 @@ impl RunnerCleanup {
             client,
             namespace,
             release,
+            owns_namespace,
             preserve,
         }
     }
@@ -45,7 +54,16 @@ impl RunnerCleanup {
             return;
         }
 
-        uninstall_release_and_namespace(&self.client, &self.release, &self.namespace).await;
+        if self.owns_namespace {
+            uninstall_release_and_namespace(&self.client, &self.release, &self.namespace).await;
+        } else if let Err(err) = uninstall_release(&self.release, &self.namespace).await {
+            warn!(
+                release = %self.release,
+                namespace = %self.namespace,
+                error = ?err,
+                "helm uninstall failed during cleanup"
+            );
+        }
     }
 
     fn blocking_cleanup_success(&self) -> bool {
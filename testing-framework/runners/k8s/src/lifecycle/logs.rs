@@ -5,8 +5,10 @@ use kube::{
 };
 use tracing::{info, warn};
 
-pub async fn dump_namespace_logs(client: &Client, namespace: &str) {
-    let pod_names = match list_pod_names(client, namespace).await {
+/// Log the tail of every pod belonging to `release`; other runs may share the
+/// namespace.
+pub async fn dump_release_logs(client: &Client, namespace: &str, release: &str) {
+    let pod_names = match list_pod_names(client, namespace, release).await {
         Ok(names) => names,
         Err(err) => {
             warn!(%namespace, error = ?err, "failed to list pods for log dump");
@@ -19,9 +21,14 @@ pub async fn dump_namespace_logs(client: &Client, namespace: &str) {
     }
 }
 
-async fn list_pod_names(client: &Client, namespace: &str) -> Result<Vec<String>, kube::Error> {
+async fn list_pod_names(
+    client: &Client,
+    namespace: &str,
+    release: &str,
+) -> Result<Vec<String>, kube::Error> {
+    let selector = format!("app.kubernetes.io/instance={release}");
     let list = Api::<Pod>::namespaced(client.clone(), namespace)
-        .list(&ListParams::default())
+        .list(&ListParams::default().labels(&selector))
         .await?;
     Ok(list
         .into_iter()
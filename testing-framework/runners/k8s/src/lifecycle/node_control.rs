@@ -0,0 +1,824 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    time::Duration,
+};
+
+use k8s_openapi::{
+    api::{
+        apps::v1::Deployment,
+        autoscaling::v1::{Scale, ScaleSpec},
+        core::v1::{EnvVar, Pod, Service},
+    },
+    apimachinery::pkg::apis::meta::v1::{ObjectMeta, Status},
+};
+use kube::{
+    Api, Client,
+    api::{AttachParams, DeleteParams, ListParams, Patch, PatchParams, PostParams},
+};
+use reqwest::Url;
+use testing_framework_core::{
+    adjust_timeout,
+    constants::{JOIN_MARKER_ENV, JOIN_MARKER_PATH},
+    nodes::ApiClient,
+    scenario::{AddedNode, DynError, ExecOutput, NodeControlHandle, NodeTarget, ScenarioTimings},
+    topology::generation::NodeRole,
+};
+use thiserror::Error;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt as _},
+    time::{Instant, sleep, timeout},
+};
+use tracing::{debug, info, warn};
+
+use crate::{
+    host::node_host,
+    lifecycle::wait::{ClusterWaitError, find_node_port, wait_for_deployment_ready},
+};
+
+const HOST_IDENTIFIER_ENV: &str = "CFG_HOST_IDENTIFIER";
+/// Upper bound for a command run through [`NodeControlHandle::exec`] and for
+/// releasing a late-joining pod, matching the compose runner.
+const EXEC_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Failures while restarting or releasing a node pod.
+#[derive(Debug, Error)]
+pub enum K8sNodeControlError {
+    #[error("failed to list pods for {target}: {source}")]
+    List {
+        target: NodeTarget,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("no pods found for {target} (selector {selector})")]
+    NoPods {
+        target: NodeTarget,
+        selector: String,
+    },
+    #[error("failed to delete pod {pod}: {source}")]
+    Delete {
+        pod: String,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("replacement pod for {target} did not become ready within {timeout:?}")]
+    Timeout {
+        target: NodeTarget,
+        timeout: Duration,
+    },
+    #[error("failed to exec in pod {pod}: {source}")]
+    Exec {
+        pod: String,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("command in pod {pod} did not succeed: {status}")]
+    ExecStatus { pod: String, status: String },
+    #[error("command in pod {pod} did not finish within {timeout:?}")]
+    ExecTimeout { pod: String, timeout: Duration },
+    #[error("failed to read output of command in pod {pod}: {source}")]
+    ExecOutput {
+        pod: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("failed to list {role} deployments: {source}")]
+    ListDeployments {
+        role: &'static str,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("failed to fetch {name}: {source}")]
+    Fetch {
+        name: String,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("failed to create {name}: {source}")]
+    Create {
+        name: String,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("failed to scale {name}: {source}")]
+    Scale {
+        name: String,
+        #[source]
+        source: kube::Error,
+    },
+    #[error("service {service} exposes no {port_name} port")]
+    MissingPort {
+        service: String,
+        port_name: &'static str,
+    },
+    #[error("added node {target} did not come up: {source}")]
+    Wait {
+        target: NodeTarget,
+        #[source]
+        source: Box<ClusterWaitError>,
+    },
+    #[error("invalid endpoint for {target}: {source}")]
+    Endpoint {
+        target: NodeTarget,
+        #[source]
+        source: url::ParseError,
+    },
+}
+
+/// Kubernetes node control handle restarting nodes by deleting their pods and
+/// waiting for the owning deployment to bring up a ready replacement.
+pub struct K8sNodeControl {
+    client: Client,
+    namespace: String,
+    release: String,
+    timings: ScenarioTimings,
+}
+
+impl K8sNodeControl {
+    #[must_use]
+    /// Replacement pods get [`ScenarioTimings::bring_up`] to become ready.
+    pub const fn new(
+        client: Client,
+        namespace: String,
+        release: String,
+        timings: ScenarioTimings,
+    ) -> Self {
+        Self {
+            client,
+            namespace,
+            release,
+            timings,
+        }
+    }
+
+    fn selector(&self, target: NodeTarget) -> String {
+        let (role, index) = match target {
+            NodeTarget::Validator(index) => ("validator", index),
+            NodeTarget::Executor(index) => ("executor", index),
+        };
+        format!(
+            "app.kubernetes.io/instance={},nomos/logical-role={role},nomos/{role}-index={index}",
+            self.release
+        )
+    }
+
+    /// Name of the deployment and service the chart renders for `target`.
+    fn object_name(&self, target: NodeTarget) -> String {
+        format!("{}-{target}", self.release)
+    }
+
+    /// Create a deployment and `NodePort` service for a new node, copied from
+    /// the role's first node, and wait for it to become ready.
+    async fn add(&self, role: NodeRole) -> Result<AddedNode, K8sNodeControlError> {
+        let role_label = match role {
+            NodeRole::Validator => "validator",
+            NodeRole::Executor => "executor",
+        };
+        let deployments: Api<Deployment> = Api::namespaced(self.client.clone(), &self.namespace);
+        let services: Api<Service> = Api::namespaced(self.client.clone(), &self.namespace);
+
+        // Removed nodes keep their (scaled down) deployment, so indices are
+        // never reused.
+        let role_selector = format!(
+            "app.kubernetes.io/instance={},nomos/logical-role={role_label}",
+            self.release
+        );
+        let index = deployments
+            .list(&ListParams::default().labels(&role_selector))
+            .await
+            .map_err(|source| K8sNodeControlError::ListDeployments {
+                role: role_label,
+                source,
+            })?
+            .items
+            .len();
+        let (template_target, target) = match role {
+            NodeRole::Validator => (NodeTarget::Validator(0), NodeTarget::Validator(index)),
+            NodeRole::Executor => (NodeTarget::Executor(0), NodeTarget::Executor(index)),
+        };
+        let template_name = self.object_name(template_target);
+        let name = self.object_name(target);
+
+        let template =
+            deployments
+                .get(&template_name)
+                .await
+                .map_err(|source| K8sNodeControlError::Fetch {
+                    name: template_name.clone(),
+                    source,
+                })?;
+        // Check the service template before creating anything, so a bad
+        // template leaves no deployment behind.
+        let template_service =
+            services
+                .get(&template_name)
+                .await
+                .map_err(|source| K8sNodeControlError::Fetch {
+                    name: template_name.clone(),
+                    source,
+                })?;
+        let api_port = service_port(&template_service, "http")?;
+        let testing_port = service_port(&template_service, "testing-http")?;
+
+        let mut labels = template.metadata.labels.clone().unwrap_or_default();
+        labels.insert(format!("nomos/{role_label}-index"), index.to_string());
+
+        info!(%target, deployment = name, namespace = %self.namespace, "adding node deployment");
+        deployments
+            .create(
+                &PostParams::default(),
+                &added_deployment(template, &name, &labels, &target.to_string()),
+            )
+            .await
+            .map_err(|source| K8sNodeControlError::Create {
+                name: name.clone(),
+                source,
+            })?;
+
+        let added = self
+            .expose_added(
+                &services,
+                added_service(template_service, &name, &labels),
+                &name,
+                target,
+                api_port,
+                testing_port,
+            )
+            .await;
+        if added.is_err() {
+            Self::rollback_add(&deployments, &services, &name).await;
+        }
+        added
+    }
+
+    /// Create the service of an added node, wait for its deployment to
+    /// become ready and build a client reaching it through its node ports.
+    async fn expose_added(
+        &self,
+        services: &Api<Service>,
+        service: Service,
+        name: &str,
+        target: NodeTarget,
+        api_port: u16,
+        testing_port: u16,
+    ) -> Result<AddedNode, K8sNodeControlError> {
+        services
+            .create(&PostParams::default(), &service)
+            .await
+            .map_err(|source| K8sNodeControlError::Create {
+                name: name.to_owned(),
+                source,
+            })?;
+
+        let wait_error = |source| K8sNodeControlError::Wait {
+            target,
+            source: Box::new(source),
+        };
+        wait_for_deployment_ready(
+            &self.client,
+            &self.namespace,
+            name,
+            adjust_timeout(self.timings.bring_up()),
+        )
+        .await
+        .map_err(wait_error)?;
+        let api = find_node_port(&self.client, &self.namespace, name, api_port)
+            .await
+            .map_err(wait_error)?;
+        let testing = find_node_port(&self.client, &self.namespace, name, testing_port)
+            .await
+            .map_err(wait_error)?;
+
+        let host = node_host();
+        let url = |port: u16| {
+            Url::parse(&format!("http://{host}:{port}/"))
+                .map_err(|source| K8sNodeControlError::Endpoint { target, source })
+        };
+        let client = ApiClient::from_urls(url(api)?, Some(url(testing)?));
+        Ok(AddedNode { target, client })
+    }
+
+    /// Delete the service and deployment of a failed addition so they stop
+    /// using cluster resources. Without the deployment, the index is free
+    /// for the next addition.
+    async fn rollback_add(deployments: &Api<Deployment>, services: &Api<Service>, name: &str) {
+        if let Err(err) = services.delete(name, &DeleteParams::default()).await {
+            debug!(service = name, error = %err, "no service to delete after failed addition");
+        }
+        if let Err(err) = deployments.delete(name, &DeleteParams::default()).await {
+            warn!(deployment = name, error = %err, "failed to delete deployment after failed addition");
+        }
+    }
+
+    /// Scale the node's deployment to zero replicas.
+    async fn remove(&self, target: NodeTarget) -> Result<(), K8sNodeControlError> {
+        let deployments: Api<Deployment> = Api::namespaced(self.client.clone(), &self.namespace);
+        let name = self.object_name(target);
+        info!(%target, deployment = name, namespace = %self.namespace, "scaling node deployment to zero");
+        let scale = Scale {
+            spec: Some(ScaleSpec { replicas: Some(0) }),
+            ..Scale::default()
+        };
+        deployments
+            .patch_scale(&name, &PatchParams::default(), &Patch::Merge(&scale))
+            .await
+            .map(|_| ())
+            .map_err(|source| K8sNodeControlError::Scale { name, source })
+    }
+
+    async fn restart(&self, target: NodeTarget) -> Result<(), K8sNodeControlError> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let selector = self.selector(target);
+        let params = ListParams::default().labels(&selector);
+
+        let existing: HashSet<String> = pods
+            .list(&params)
+            .await
+            .map_err(|source| K8sNodeControlError::List { target, source })?
+            .into_iter()
+            .filter_map(|pod| pod.metadata.name)
+            .collect();
+        if existing.is_empty() {
+            return Err(K8sNodeControlError::NoPods { target, selector });
+        }
+
+        for pod in &existing {
+            info!(%target, pod, namespace = %self.namespace, "deleting node pod");
+            pods.delete(pod, &DeleteParams::default())
+                .await
+                .map_err(|source| K8sNodeControlError::Delete {
+                    pod: pod.clone(),
+                    source,
+                })?;
+        }
+
+        self.wait_for_replacement(&pods, &params, &existing, target)
+            .await
+    }
+
+    /// Create the join marker in the node's pods so a late-joining node's
+    /// entrypoint starts the node process.
+    async fn join(&self, target: NodeTarget) -> Result<(), K8sNodeControlError> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let selector = self.selector(target);
+        let names: Vec<String> = pods
+            .list(&ListParams::default().labels(&selector))
+            .await
+            .map_err(|source| K8sNodeControlError::List { target, source })?
+            .into_iter()
+            .filter_map(|pod| pod.metadata.name)
+            .collect();
+        if names.is_empty() {
+            return Err(K8sNodeControlError::NoPods { target, selector });
+        }
+
+        let timeout_duration = adjust_timeout(EXEC_TIMEOUT);
+        for pod in names {
+            info!(%target, pod, namespace = %self.namespace, "releasing late-joining node pod");
+            timeout(timeout_duration, Self::touch_join_marker(&pods, &pod))
+                .await
+                .map_err(|_| K8sNodeControlError::ExecTimeout {
+                    pod: pod.clone(),
+                    timeout: timeout_duration,
+                })??;
+        }
+        Ok(())
+    }
+
+    async fn touch_join_marker(pods: &Api<Pod>, pod: &str) -> Result<(), K8sNodeControlError> {
+        let mut process = pods
+            .exec(
+                pod,
+                ["touch", JOIN_MARKER_PATH],
+                &AttachParams::default().stdout(false).stderr(false),
+            )
+            .await
+            .map_err(|source| K8sNodeControlError::Exec {
+                pod: pod.to_owned(),
+                source,
+            })?;
+        let status = match process.take_status() {
+            Some(status) => status.await,
+            None => None,
+        };
+        if let Some(status) = status.filter(|status| status.status.as_deref() != Some("Success")) {
+            return Err(K8sNodeControlError::ExecStatus {
+                pod: pod.to_owned(),
+                status: status
+                    .message
+                    .unwrap_or_else(|| format!("{:?}", status.status)),
+            });
+        }
+        Ok(())
+    }
+
+    /// Run `command` in the first pod of `target` and capture its output.
+    async fn exec_in(
+        &self,
+        target: NodeTarget,
+        command: &[&str],
+    ) -> Result<ExecOutput, K8sNodeControlError> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), &self.namespace);
+        let selector = self.selector(target);
+        let pod = pods
+            .list(&ListParams::default().labels(&selector))
+            .await
+            .map_err(|source| K8sNodeControlError::List { target, source })?
+            .into_iter()
+            .filter(|pod| pod.metadata.deletion_timestamp.is_none())
+            .find_map(|pod| pod.metadata.name)
+            .ok_or(K8sNodeControlError::NoPods { target, selector })?;
+
+        debug!(%target, pod, ?command, "executing command in node pod");
+        let timeout_duration = adjust_timeout(EXEC_TIMEOUT);
+        timeout(timeout_duration, Self::run_exec(&pods, &pod, command))
+            .await
+            .map_err(|_| K8sNodeControlError::ExecTimeout {
+                pod: pod.clone(),
+                timeout: timeout_duration,
+            })?
+    }
+
+    async fn run_exec(
+        pods: &Api<Pod>,
+        pod: &str,
+        command: &[&str],
+    ) -> Result<ExecOutput, K8sNodeControlError> {
+        let mut process = pods
+            .exec(
+                pod,
+                command.iter().copied(),
+                &AttachParams::default().stdout(true).stderr(true),
+            )
+            .await
+            .map_err(|source| K8sNodeControlError::Exec {
+                pod: pod.to_owned(),
+                source,
+            })?;
+        let (stdout, stderr) = (process.stdout(), process.stderr());
+        let (stdout, stderr) =
+            tokio::try_join!(read_stream(stdout), read_stream(stderr)).map_err(|source| {
+                K8sNodeControlError::ExecOutput {
+                    pod: pod.to_owned(),
+                    source,
+                }
+            })?;
+        let status = match process.take_status() {
+            Some(status) => status.await,
+            None => None,
+        };
+
+        Ok(ExecOutput {
+            exit_code: status.as_ref().and_then(exit_code),
+            stdout,
+            stderr,
+        })
+    }
+
+    async fn wait_for_replacement(
+        &self,
+        pods: &Api<Pod>,
+        params: &ListParams,
+        previous: &HashSet<String>,
+        target: NodeTarget,
+    ) -> Result<(), K8sNodeControlError> {
+        let timeout = adjust_timeout(self.timings.bring_up());
+        let deadline = Instant::now() + timeout;
+
+        while Instant::now() < deadline {
+            let list = pods
+                .list(params)
+                .await
+                .map_err(|source| K8sNodeControlError::List { target, source })?;
+            let replacement_ready = list.into_iter().any(|pod| {
+                let fresh = pod
+                    .metadata
+                    .name
+                    .as_ref()
+                    .is_some_and(|name| !previous.contains(name));
+                fresh && pod.metadata.deletion_timestamp.is_none() && pod_ready(&pod)
+            });
+            if replacement_ready {
+                info!(%target, "replacement pod ready");
+                return Ok(());
+            }
+
+            debug!(%target, "waiting for replacement pod");
+            sleep(self.timings.http_poll_interval()).await;
+        }
+
+        Err(K8sNodeControlError::Timeout { target, timeout })
+    }
+}
+
+/// Copy of `template` running as a new node: fresh metadata, one replica,
+/// the given labels and a new cfgsync host identifier. The copy never waits
+/// for a join marker.
+fn added_deployment(
+    template: Deployment,
+    name: &str,
+    labels: &BTreeMap<String, String>,
+    identifier: &str,
+) -> Deployment {
+    let mut spec = template.spec.unwrap_or_default();
+    spec.replicas = Some(1);
+    spec.selector.match_labels = Some(labels.clone());
+    spec.template
+        .metadata
+        .get_or_insert_with(ObjectMeta::default)
+        .labels = Some(labels.clone());
+    if let Some(pod_spec) = spec.template.spec.as_mut() {
+        for container in &mut pod_spec.containers {
+            let env = container.env.get_or_insert_with(Vec::new);
+            env.retain(|var| var.name != JOIN_MARKER_ENV && var.name != HOST_IDENTIFIER_ENV);
+            env.push(EnvVar {
+                name: HOST_IDENTIFIER_ENV.to_owned(),
+                value: Some(identifier.to_owned()),
+                ..EnvVar::default()
+            });
+        }
+    }
+
+    Deployment {
+        metadata: ObjectMeta {
+            name: Some(name.to_owned()),
+            labels: Some(labels.clone()),
+            ..ObjectMeta::default()
+        },
+        spec: Some(spec),
+        status: None,
+    }
+}
+
+/// Copy of `template` selecting the new node's pods; cluster IP and node
+/// ports are left for the API server to allocate.
+fn added_service(template: Service, name: &str, labels: &BTreeMap<String, String>) -> Service {
+    let mut spec = template.spec.unwrap_or_default();
+    spec.selector = Some(labels.clone());
+    spec.cluster_ip = None;
+    spec.cluster_ips = None;
+    for port in spec.ports.iter_mut().flatten() {
+        port.node_port = None;
+    }
+
+    Service {
+        metadata: ObjectMeta {
+            name: Some(name.to_owned()),
+            labels: Some(labels.clone()),
+            ..ObjectMeta::default()
+        },
+        spec: Some(spec),
+        status: None,
+    }
+}
+
+fn service_port(service: &Service, port_name: &'static str) -> Result<u16, K8sNodeControlError> {
+    service
+        .spec
+        .as_ref()
+        .and_then(|spec| spec.ports.as_ref())
+        .and_then(|ports| {
+            ports
+                .iter()
+                .find(|port| port.name.as_deref() == Some(port_name))
+        })
+        .and_then(|port| u16::try_from(port.port).ok())
+        .ok_or_else(|| K8sNodeControlError::MissingPort {
+            service: service.metadata.name.clone().unwrap_or_default(),
+            port_name,
+        })
+}
+
+async fn read_stream(stream: Option<impl AsyncRead + Unpin>) -> Result<String, std::io::Error> {
+    let mut buffer = Vec::new();
+    if let Some(mut stream) = stream {
+        stream.read_to_end(&mut buffer).await?;
+    }
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+/// Exit code reported by the exec status channel: `Success`, or a
+/// `NonZeroExitCode` failure carrying the code as an `ExitCode` cause.
+fn exit_code(status: &Status) -> Option<i32> {
+    if status.status.as_deref() == Some("Success") {
+        return Some(0);
+    }
+    status
+        .details
+        .as_ref()?
+        .causes
+        .as_ref()?
+        .iter()
+        .find(|cause| cause.reason.as_deref() == Some("ExitCode"))
+        .and_then(|cause| cause.message.as_deref()?.parse().ok())
+}
+
+fn pod_ready(pod: &Pod) -> bool {
+    pod.status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .is_some_and(|conditions| {
+            conditions
+                .iter()
+                .any(|condition| condition.type_ == "Ready" && condition.status == "True")
+        })
+}
+
+#[async_trait::async_trait]
+impl NodeControlHandle for K8sNodeControl {
+    async fn restart_validator(&self, index: usize) -> Result<(), DynError> {
+        self.restart(NodeTarget::Validator(index))
+            .await
+            .map_err(|err| format!("validator restart failed: {err}").into())
+    }
+
+    async fn restart_executor(&self, index: usize) -> Result<(), DynError> {
+        self.restart(NodeTarget::Executor(index))
+            .await
+            .map_err(|err| format!("executor restart failed: {err}").into())
+    }
+
+    async fn join_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        self.join(target)
+            .await
+            .map_err(|err| format!("starting late-joining {target} failed: {err}").into())
+    }
+
+    async fn add_node(&self, role: NodeRole) -> Result<AddedNode, DynError> {
+        self.add(role)
+            .await
+            .map_err(|err| format!("adding {role:?} failed: {err}").into())
+    }
+
+    async fn remove_node(&self, target: NodeTarget) -> Result<(), DynError> {
+        self.remove(target)
+            .await
+            .map_err(|err| format!("removing {target} failed: {err}").into())
+    }
+
+    async fn exec(&self, target: NodeTarget, command: &[&str]) -> Result<ExecOutput, DynError> {
+        self.exec_in(target, command)
+            .await
+            .map_err(|err| format!("exec in {target} failed: {err}").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use k8s_openapi::api::{
+        apps::v1::DeploymentSpec,
+        core::v1::{Container, PodSpec, PodTemplateSpec, ServicePort, ServiceSpec},
+    };
+
+    use super::*;
+
+    fn labels(index: usize) -> BTreeMap<String, String> {
+        BTreeMap::from([(String::from("nomos/validator-index"), index.to_string())])
+    }
+
+    fn template_service() -> Service {
+        let port = |name: &str, port, node_port| ServicePort {
+            name: Some(name.to_owned()),
+            port,
+            node_port: Some(node_port),
+            ..ServicePort::default()
+        };
+        Service {
+            metadata: ObjectMeta {
+                name: Some("release-validator-0".to_owned()),
+                ..ObjectMeta::default()
+            },
+            spec: Some(ServiceSpec {
+                cluster_ip: Some("10.0.0.1".to_owned()),
+                selector: Some(labels(0)),
+                ports: Some(vec![
+                    port("http", 18080, 30001),
+                    port("testing-http", 18081, 30002),
+                ]),
+                ..ServiceSpec::default()
+            }),
+            status: None,
+        }
+    }
+
+    #[test]
+    fn service_port_requires_named_port() {
+        let service = template_service();
+
+        assert_eq!(service_port(&service, "http").expect("http port"), 18080);
+        assert_eq!(
+            service_port(&service, "testing-http").expect("testing port"),
+            18081
+        );
+        assert!(matches!(
+            service_port(&service, "metrics"),
+            Err(K8sNodeControlError::MissingPort {
+                port_name: "metrics",
+                ..
+            })
+        ));
+        assert!(service_port(&Service::default(), "http").is_err());
+    }
+
+    #[test]
+    fn added_service_selects_new_node_and_drops_allocations() {
+        let service = added_service(template_service(), "release-validator-3", &labels(3));
+
+        assert_eq!(
+            service.metadata.name.as_deref(),
+            Some("release-validator-3")
+        );
+        let spec = service.spec.expect("spec is kept");
+        assert_eq!(spec.selector, Some(labels(3)));
+        assert_eq!(spec.cluster_ip, None);
+        let ports = spec.ports.expect("ports are kept");
+        assert_eq!(ports.len(), 2);
+        assert!(ports.iter().all(|port| port.node_port.is_none()));
+    }
+
+    #[test]
+    fn added_deployment_runs_without_join_marker() {
+        let env = |name: &str, value: &str| EnvVar {
+            name: name.to_owned(),
+            value: Some(value.to_owned()),
+            ..EnvVar::default()
+        };
+        let template = Deployment {
+            spec: Some(DeploymentSpec {
+                replicas: Some(0),
+                template: PodTemplateSpec {
+                    spec: Some(PodSpec {
+                        containers: vec![Container {
+                            name: "node".to_owned(),
+                            env: Some(vec![
+                                env(JOIN_MARKER_ENV, JOIN_MARKER_PATH),
+                                env(HOST_IDENTIFIER_ENV, "validator-0"),
+                                env("RUST_LOG", "info"),
+                            ]),
+                            ..Container::default()
+                        }],
+                        ..PodSpec::default()
+                    }),
+                    ..PodTemplateSpec::default()
+                },
+                ..DeploymentSpec::default()
+            }),
+            ..Deployment::default()
+        };
+
+        let deployment =
+            added_deployment(template, "release-validator-3", &labels(3), "validator-3");
+
+        assert_eq!(
+            deployment.metadata.name.as_deref(),
+            Some("release-validator-3")
+        );
+        let spec = deployment.spec.expect("spec is kept");
+        assert_eq!(spec.replicas, Some(1));
+        assert_eq!(spec.selector.match_labels, Some(labels(3)));
+        let pod_labels = spec.template.metadata.and_then(|metadata| metadata.labels);
+        assert_eq!(pod_labels, Some(labels(3)));
+        let env = spec.template.spec.expect("pod spec is kept").containers[0]
+            .env
+            .clone()
+            .expect("env is kept");
+        let names = env.iter().map(|var| var.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["RUST_LOG", HOST_IDENTIFIER_ENV]);
+        assert_eq!(env[1].value.as_deref(), Some("validator-3"));
+    }
+
+    #[test]
+    fn exit_code_reads_success_and_exit_code_cause() {
+        use k8s_openapi::apimachinery::pkg::apis::meta::v1::{StatusCause, StatusDetails};
+
+        let failure = |causes: Vec<StatusCause>| Status {
+            status: Some("Failure".to_owned()),
+            reason: Some("NonZeroExitCode".to_owned()),
+            details: Some(StatusDetails {
+                causes: Some(causes),
+                ..StatusDetails::default()
+            }),
+            ..Status::default()
+        };
+        let cause = |reason: &str, message: &str| StatusCause {
+            reason: Some(reason.to_owned()),
+            message: Some(message.to_owned()),
+            field: None,
+        };
+
+        let success = Status {
+            status: Some("Success".to_owned()),
+            ..Status::default()
+        };
+        assert_eq!(exit_code(&success), Some(0));
+        assert_eq!(
+            exit_code(&failure(vec![
+                cause("Other", "ignored"),
+                cause("ExitCode", "42")
+            ])),
+            Some(42)
+        );
+        assert_eq!(exit_code(&failure(vec![cause("ExitCode", "oops")])), None);
+        assert_eq!(exit_code(&failure(Vec::new())), None);
+        assert_eq!(exit_code(&Status::default()), None);
+    }
+}
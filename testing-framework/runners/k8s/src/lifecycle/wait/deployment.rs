@@ -2,17 +2,18 @@ use k8s_openapi::api::apps::v1::Deployment;
 use kube::{Api, Client};
 use tokio::time::sleep;
 
-use super::{ClusterWaitError, DEPLOYMENT_TIMEOUT};
+use super::ClusterWaitError;
 
 pub async fn wait_for_deployment_ready(
     client: &Client,
     namespace: &str,
     name: &str,
+    timeout: std::time::Duration,
 ) -> Result<(), ClusterWaitError> {
     let mut elapsed = std::time::Duration::ZERO;
     let interval = std::time::Duration::from_secs(2);
 
-    while elapsed <= DEPLOYMENT_TIMEOUT {
+    while elapsed <= timeout {
         match Api::<Deployment>::namespaced(client.clone(), namespace)
             .get(name)
             .await
@@ -47,6 +48,6 @@ pub async fn wait_for_deployment_ready(
     Err(ClusterWaitError::DeploymentTimeout {
         name: name.to_owned(),
         namespace: namespace.to_owned(),
-        timeout: DEPLOYMENT_TIMEOUT,
+        timeout,
     })
 }
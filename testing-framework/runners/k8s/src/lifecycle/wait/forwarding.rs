@@ -1,40 +1,77 @@
 use std::{
-    net::{Ipv4Addr, TcpListener, TcpStream},
-    process::{Child, Command as StdCommand, Stdio},
-    thread,
-    time::Duration,
+    net::Ipv4Addr,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
+use k8s_openapi::api::core::v1::{Pod, Service};
+use kube::{
+    Api, Client,
+    api::{ListParams, Portforwarder},
+};
+use tokio::{io::copy_bidirectional, net::TcpListener, task::JoinHandle};
+use tracing::{debug, info, warn};
+
 use super::{ClusterWaitError, NodeConfigPorts, NodePortAllocation};
 
-pub fn port_forward_group(
+/// In-process port forward from a local port to a service's pod.
+///
+/// Every accepted local connection opens its own forwarding stream through
+/// the Kubernetes API. When the backing pod goes away (e.g. it was deleted by
+/// a chaos restart) the next connection resolves the service's current pod,
+/// so forwards survive pod replacement during long runs. The accept loop is
+/// stopped when the handle is dropped.
+#[derive(Debug)]
+pub struct PortForward {
+    service: String,
+    local_port: u16,
+    reconnects: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+}
+
+impl PortForward {
+    #[must_use]
+    pub const fn local_port(&self) -> u16 {
+        self.local_port
+    }
+
+    #[must_use]
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    #[must_use]
+    /// Times the forward switched to a different backing pod.
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for PortForward {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+pub async fn port_forward_group(
+    client: &Client,
     namespace: &str,
     release: &str,
     kind: &str,
     ports: &[NodeConfigPorts],
     allocations: &mut Vec<NodePortAllocation>,
-) -> Result<Vec<Child>, ClusterWaitError> {
+) -> Result<Vec<PortForward>, ClusterWaitError> {
     let mut forwards = Vec::new();
     for (index, ports) in ports.iter().enumerate() {
         let service = format!("{release}-{kind}-{index}");
-        let (api_port, api_forward) = match port_forward_service(namespace, &service, ports.api) {
-            Ok(forward) => forward,
-            Err(err) => {
-                kill_port_forwards(&mut forwards);
-                return Err(err);
-            }
-        };
-        let (testing_port, testing_forward) =
-            match port_forward_service(namespace, &service, ports.testing) {
-                Ok(forward) => forward,
-                Err(err) => {
-                    kill_port_forwards(&mut forwards);
-                    return Err(err);
-                }
-            };
+        let api_forward = port_forward_service(client, namespace, &service, ports.api).await?;
+        let testing_forward =
+            port_forward_service(client, namespace, &service, ports.testing).await?;
         allocations.push(NodePortAllocation {
-            api: api_port,
-            testing: testing_port,
+            api: api_forward.local_port(),
+            testing: testing_forward.local_port(),
         });
         forwards.push(api_forward);
         forwards.push(testing_forward);
@@ -42,65 +79,176 @@ pub fn port_forward_group(
     Ok(forwards)
 }
 
-pub fn port_forward_service(
+pub async fn port_forward_service(
+    client: &Client,
     namespace: &str,
     service: &str,
     remote_port: u16,
-) -> Result<(u16, Child), ClusterWaitError> {
-    let local_port = allocate_local_port().map_err(|source| ClusterWaitError::PortForward {
+) -> Result<PortForward, ClusterWaitError> {
+    let error = |source: anyhow::Error| ClusterWaitError::PortForward {
         service: service.to_owned(),
         port: remote_port,
         source,
-    })?;
-
-    let mut child = StdCommand::new("kubectl")
-        .arg("port-forward")
-        .arg("-n")
-        .arg(namespace)
-        .arg(format!("svc/{service}"))
-        .arg(format!("{local_port}:{remote_port}"))
-        .stdout(Stdio::null())
-        .stderr(Stdio::null())
-        .spawn()
-        .map_err(|source| ClusterWaitError::PortForward {
-            service: service.to_owned(),
-            port: remote_port,
-            source: source.into(),
-        })?;
-
-    for _ in 0..20 {
-        if let Ok(Some(status)) = child.try_wait() {
-            return Err(ClusterWaitError::PortForward {
-                service: service.to_owned(),
-                port: remote_port,
-                source: anyhow::anyhow!("kubectl exited with {status}"),
-            });
-        }
-        if TcpStream::connect((Ipv4Addr::LOCALHOST, local_port)).is_ok() {
-            return Ok((local_port, child));
-        }
-        thread::sleep(Duration::from_millis(250));
-    }
+    };
 
-    let _ = child.kill();
-    Err(ClusterWaitError::PortForward {
+    let target = ForwardTarget {
+        pods: Api::namespaced(client.clone(), namespace),
+        services: Api::namespaced(client.clone(), namespace),
         service: service.to_owned(),
-        port: remote_port,
-        source: anyhow::anyhow!("port-forward did not become ready"),
+        remote_port,
+        current_pod: Mutex::new(None),
+    };
+    // Fail fast when the service has no pod to forward to; end-to-end
+    // reachability is checked by the HTTP probes that follow.
+    target.pod().await.map_err(error)?;
+
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .await
+        .map_err(|err| error(err.into()))?;
+    let local_port = listener
+        .local_addr()
+        .map_err(|err| error(err.into()))?
+        .port();
+
+    let reconnects = Arc::new(AtomicU64::new(0));
+    let task = tokio::spawn(accept_loop(
+        listener,
+        Arc::new(target),
+        Arc::clone(&reconnects),
+    ));
+    debug!(service, remote_port, local_port, "port-forward listening");
+    Ok(PortForward {
+        service: service.to_owned(),
+        local_port,
+        reconnects,
+        task,
     })
 }
 
-pub fn kill_port_forwards(handles: &mut Vec<Child>) {
-    for handle in handles.iter_mut() {
-        let _ = handle.kill();
-        let _ = handle.wait();
-    }
+pub fn kill_port_forwards(handles: &mut Vec<PortForward>) {
     handles.clear();
 }
 
-fn allocate_local_port() -> anyhow::Result<u16> {
-    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
-    let port = listener.local_addr()?.port();
-    drop(listener);
-    Ok(port)
+/// Service whose current pod receives forwarded connections.
+struct ForwardTarget {
+    pods: Api<Pod>,
+    services: Api<Service>,
+    service: String,
+    remote_port: u16,
+    current_pod: Mutex<Option<String>>,
+}
+
+impl ForwardTarget {
+    /// Cached pod name, resolving it from the service selector when unset.
+    async fn pod(&self) -> anyhow::Result<String> {
+        if let Some(pod) = self
+            .current_pod
+            .lock()
+            .expect("port-forward pod lock poisoned")
+            .clone()
+        {
+            return Ok(pod);
+        }
+
+        let service = self.services.get(&self.service).await?;
+        let selector = service
+            .spec
+            .and_then(|spec| spec.selector)
+            .filter(|selector| !selector.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("service {} has no pod selector", self.service))?
+            .into_iter()
+            .map(|(key, value)| format!("{key}={value}"))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let pod = self
+            .pods
+            .list(&ListParams::default().labels(&selector))
+            .await?
+            .items
+            .into_iter()
+            .filter(|pod| pod.metadata.deletion_timestamp.is_none())
+            .find(|pod| {
+                pod.status
+                    .as_ref()
+                    .and_then(|status| status.phase.as_deref())
+                    == Some("Running")
+            })
+            .and_then(|pod| pod.metadata.name)
+            .ok_or_else(|| anyhow::anyhow!("no running pod backs service {}", self.service))?;
+
+        *self
+            .current_pod
+            .lock()
+            .expect("port-forward pod lock poisoned") = Some(pod.clone());
+        Ok(pod)
+    }
+
+    fn forget_pod(&self) {
+        self.current_pod
+            .lock()
+            .expect("port-forward pod lock poisoned")
+            .take();
+    }
+
+    async fn open(&self, pod: &str) -> anyhow::Result<Portforwarder> {
+        Ok(self.pods.portforward(pod, &[self.remote_port]).await?)
+    }
+
+    /// Open a stream to the current pod, re-resolving the pod once if the
+    /// cached one is gone.
+    async fn connect(&self, reconnects: &AtomicU64) -> anyhow::Result<Portforwarder> {
+        let pod = self.pod().await?;
+        match self.open(&pod).await {
+            Ok(forwarder) => Ok(forwarder),
+            Err(err) => {
+                debug!(service = %self.service, pod, error = %err, "re-resolving port-forward pod");
+                self.forget_pod();
+                let replacement = self.pod().await?;
+                if replacement != pod {
+                    reconnects.fetch_add(1, Ordering::Relaxed);
+                    info!(service = %self.service, from = pod, to = replacement, "port-forward switched pod");
+                }
+                self.open(&replacement).await
+            }
+        }
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    target: Arc<ForwardTarget>,
+    reconnects: Arc<AtomicU64>,
+) {
+    loop {
+        let mut connection = match listener.accept().await {
+            Ok((connection, _)) => connection,
+            Err(err) => {
+                warn!(service = %target.service, error = %err, "port-forward accept failed");
+                continue;
+            }
+        };
+
+        let target = Arc::clone(&target);
+        let reconnects = Arc::clone(&reconnects);
+        tokio::spawn(async move {
+            let mut forwarder = match target.connect(&reconnects).await {
+                Ok(forwarder) => forwarder,
+                Err(err) => {
+                    warn!(service = %target.service, error = %err, "port-forward connect failed");
+                    target.forget_pod();
+                    return;
+                }
+            };
+            let Some(mut upstream) = forwarder.take_stream(target.remote_port) else {
+                warn!(service = %target.service, "port-forward stream unavailable");
+                return;
+            };
+            if let Err(err) = copy_bidirectional(&mut connection, &mut upstream).await {
+                debug!(service = %target.service, error = %err, "port-forward connection closed");
+            }
+            drop(upstream);
+            let _ = forwarder.join().await;
+        });
+    }
 }
@@ -2,12 +2,7 @@ use std::time::Duration;
 
 use kube::Error as KubeError;
 use testing_framework_core::{
-    constants::{
-        DEFAULT_HTTP_POLL_INTERVAL, DEFAULT_K8S_DEPLOYMENT_TIMEOUT,
-        DEFAULT_NODE_HTTP_PROBE_TIMEOUT, DEFAULT_NODE_HTTP_TIMEOUT, DEFAULT_PROMETHEUS_HTTP_PORT,
-        DEFAULT_PROMETHEUS_HTTP_PROBE_TIMEOUT, DEFAULT_PROMETHEUS_HTTP_TIMEOUT,
-        DEFAULT_PROMETHEUS_SERVICE_NAME,
-    },
+    constants::{DEFAULT_PROMETHEUS_HTTP_PORT, DEFAULT_PROMETHEUS_SERVICE_NAME},
     scenario::http_probe::NodeRole,
 };
 use thiserror::Error;
@@ -19,7 +14,10 @@ mod orchestrator;
 mod ports;
 mod prometheus;
 
+pub(crate) use deployment::wait_for_deployment_ready;
+pub use forwarding::PortForward;
 pub use orchestrator::wait_for_cluster_ready;
+pub(crate) use ports::find_node_port;
 
 /// Container and host-side HTTP ports for a node in the Helm chart values.
 #[derive(Clone, Copy, Debug)]
@@ -47,7 +45,7 @@ pub struct ClusterPorts {
 #[derive(Debug)]
 pub struct ClusterReady {
     pub ports: ClusterPorts,
-    pub port_forwards: Vec<std::process::Child>,
+    pub port_forwards: Vec<PortForward>,
 }
 
 #[derive(Debug, Error)]
@@ -95,11 +93,15 @@ pub enum ClusterWaitError {
     },
 }
 
-pub(crate) const DEPLOYMENT_TIMEOUT: Duration = DEFAULT_K8S_DEPLOYMENT_TIMEOUT;
-pub(crate) const NODE_HTTP_TIMEOUT: Duration = DEFAULT_NODE_HTTP_TIMEOUT;
-pub(crate) const NODE_HTTP_PROBE_TIMEOUT: Duration = DEFAULT_NODE_HTTP_PROBE_TIMEOUT;
-pub(crate) const HTTP_POLL_INTERVAL: Duration = DEFAULT_HTTP_POLL_INTERVAL;
 pub(crate) const PROMETHEUS_HTTP_PORT: u16 = DEFAULT_PROMETHEUS_HTTP_PORT;
-pub(crate) const PROMETHEUS_HTTP_TIMEOUT: Duration = DEFAULT_PROMETHEUS_HTTP_TIMEOUT;
-pub(crate) const PROMETHEUS_HTTP_PROBE_TIMEOUT: Duration = DEFAULT_PROMETHEUS_HTTP_PROBE_TIMEOUT;
-pub(crate) const PROMETHEUS_SERVICE_NAME: &str = DEFAULT_PROMETHEUS_SERVICE_NAME;
+pub(crate) const GRAFANA_HTTP_PORT: u16 = 3000;
+
+/// Chart services are prefixed with the release so several runs can share a
+/// namespace.
+pub(crate) fn prometheus_service_name(release: &str) -> String {
+    format!("{release}-{DEFAULT_PROMETHEUS_SERVICE_NAME}")
+}
+
+pub(crate) fn grafana_service_name(release: &str) -> String {
+    format!("{release}-grafana")
+}
@@ -1,9 +1,9 @@
 use kube::Client;
-use testing_framework_core::scenario::http_probe::NodeRole;
+use testing_framework_core::scenario::{NodeTarget, ScenarioTimings, http_probe::NodeRole};
 
 use super::{
-    ClusterPorts, ClusterReady, ClusterWaitError, NodeConfigPorts, PROMETHEUS_HTTP_PORT,
-    PROMETHEUS_HTTP_PROBE_TIMEOUT, PROMETHEUS_SERVICE_NAME,
+    ClusterPorts, ClusterReady, ClusterWaitError, NodeConfigPorts, NodePortAllocation,
+    PROMETHEUS_HTTP_PORT, prometheus_service_name,
 };
 use crate::lifecycle::wait::{
     deployment::wait_for_deployment_ready,
@@ -19,6 +19,8 @@ pub async fn wait_for_cluster_ready(
     release: &str,
     validator_ports: &[NodeConfigPorts],
     executor_ports: &[NodeConfigPorts],
+    late_joins: &[NodeTarget],
+    timings: &ScenarioTimings,
 ) -> Result<ClusterReady, ClusterWaitError> {
     if validator_ports.is_empty() {
         return Err(ClusterWaitError::MissingValidator);
@@ -28,35 +30,34 @@ pub async fn wait_for_cluster_ready(
 
     for (index, ports) in validator_ports.iter().enumerate() {
         let name = format!("{release}-validator-{index}");
-        wait_for_deployment_ready(client, namespace, &name).await?;
+        wait_for_deployment_ready(client, namespace, &name, timings.bring_up()).await?;
         let allocation = discover_node_ports(client, namespace, &name, *ports).await?;
         validator_allocations.push(allocation);
     }
 
     let mut port_forwards = Vec::new();
 
-    let validator_api_ports: Vec<u16> = validator_allocations
-        .iter()
-        .map(|ports| ports.api)
-        .collect();
-    if wait_for_node_http_nodeport(&validator_api_ports, NodeRole::Validator)
+    let validator_api_ports =
+        initial_api_ports(&validator_allocations, NodeTarget::Validator, late_joins);
+    if wait_for_node_http_nodeport(&validator_api_ports, NodeRole::Validator, timings)
         .await
         .is_err()
     {
         validator_allocations.clear();
         port_forwards = port_forward_group(
+            client,
             namespace,
             release,
             "validator",
             validator_ports,
             &mut validator_allocations,
-        )?;
-        let validator_api_ports: Vec<u16> = validator_allocations
-            .iter()
-            .map(|ports| ports.api)
-            .collect();
+        )
+        .await?;
+        let validator_api_ports =
+            initial_api_ports(&validator_allocations, NodeTarget::Validator, late_joins);
         if let Err(err) =
-            wait_for_node_http_port_forward(&validator_api_ports, NodeRole::Validator).await
+            wait_for_node_http_port_forward(&validator_api_ports, NodeRole::Validator, timings)
+                .await
         {
             kill_port_forwards(&mut port_forwards);
             return Err(err);
@@ -66,61 +67,64 @@ pub async fn wait_for_cluster_ready(
     let mut executor_allocations = Vec::with_capacity(executor_ports.len());
     for (index, ports) in executor_ports.iter().enumerate() {
         let name = format!("{release}-executor-{index}");
-        wait_for_deployment_ready(client, namespace, &name).await?;
+        wait_for_deployment_ready(client, namespace, &name, timings.bring_up()).await?;
         let allocation = discover_node_ports(client, namespace, &name, *ports).await?;
         executor_allocations.push(allocation);
     }
 
-    let executor_api_ports: Vec<u16> = executor_allocations.iter().map(|ports| ports.api).collect();
-    if !executor_allocations.is_empty()
-        && wait_for_node_http_nodeport(&executor_api_ports, NodeRole::Executor)
+    let executor_api_ports =
+        initial_api_ports(&executor_allocations, NodeTarget::Executor, late_joins);
+    if !executor_api_ports.is_empty()
+        && wait_for_node_http_nodeport(&executor_api_ports, NodeRole::Executor, timings)
             .await
             .is_err()
     {
         executor_allocations.clear();
         match port_forward_group(
+            client,
             namespace,
             release,
             "executor",
             executor_ports,
             &mut executor_allocations,
-        ) {
+        )
+        .await
+        {
             Ok(forwards) => port_forwards.extend(forwards),
             Err(err) => {
                 kill_port_forwards(&mut port_forwards);
                 return Err(err);
             }
         }
-        let executor_api_ports: Vec<u16> =
-            executor_allocations.iter().map(|ports| ports.api).collect();
+        let executor_api_ports =
+            initial_api_ports(&executor_allocations, NodeTarget::Executor, late_joins);
         if let Err(err) =
-            wait_for_node_http_port_forward(&executor_api_ports, NodeRole::Executor).await
+            wait_for_node_http_port_forward(&executor_api_ports, NodeRole::Executor, timings).await
         {
             kill_port_forwards(&mut port_forwards);
             return Err(err);
         }
     }
 
-    let mut prometheus_port = find_node_port(
-        client,
-        namespace,
-        PROMETHEUS_SERVICE_NAME,
-        PROMETHEUS_HTTP_PORT,
-    )
-    .await?;
-    if wait_for_prometheus_http_nodeport(prometheus_port, PROMETHEUS_HTTP_PROBE_TIMEOUT)
+    let prometheus_service = prometheus_service_name(release);
+    let mut prometheus_port =
+        find_node_port(client, namespace, &prometheus_service, PROMETHEUS_HTTP_PORT).await?;
+    if wait_for_prometheus_http_nodeport(prometheus_port, timings.prometheus_http_probe())
         .await
         .is_err()
     {
-        let (local_port, forward) =
-            port_forward_service(namespace, PROMETHEUS_SERVICE_NAME, PROMETHEUS_HTTP_PORT)
+        let forward =
+            port_forward_service(client, namespace, &prometheus_service, PROMETHEUS_HTTP_PORT)
+                .await
                 .map_err(|err| {
                     kill_port_forwards(&mut port_forwards);
                     err
                 })?;
-        prometheus_port = local_port;
+        prometheus_port = forward.local_port();
         port_forwards.push(forward);
-        if let Err(err) = wait_for_prometheus_http_port_forward(prometheus_port).await {
+        if let Err(err) =
+            wait_for_prometheus_http_port_forward(prometheus_port, timings.prometheus_http()).await
+        {
             kill_port_forwards(&mut port_forwards);
             return Err(err);
         }
@@ -135,3 +139,18 @@ pub async fn wait_for_cluster_ready(
         port_forwards,
     })
 }
+
+/// API ports of the nodes started with the cluster; late-joining nodes only
+/// answer once the run releases them.
+fn initial_api_ports(
+    allocations: &[NodePortAllocation],
+    target: fn(usize) -> NodeTarget,
+    late_joins: &[NodeTarget],
+) -> Vec<u16> {
+    allocations
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !late_joins.contains(&target(*index)))
+        .map(|(_, ports)| ports.api)
+        .collect()
+}
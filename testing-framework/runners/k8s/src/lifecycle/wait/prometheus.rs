@@ -1,6 +1,6 @@
 use tokio::time::sleep;
 
-use super::{ClusterWaitError, PROMETHEUS_HTTP_TIMEOUT};
+use super::ClusterWaitError;
 use crate::host::node_host;
 
 pub async fn wait_for_prometheus_http_nodeport(
@@ -11,8 +11,11 @@ pub async fn wait_for_prometheus_http_nodeport(
     wait_for_prometheus_http(&host, port, timeout).await
 }
 
-pub async fn wait_for_prometheus_http_port_forward(port: u16) -> Result<(), ClusterWaitError> {
-    wait_for_prometheus_http("127.0.0.1", port, PROMETHEUS_HTTP_TIMEOUT).await
+pub async fn wait_for_prometheus_http_port_forward(
+    port: u16,
+    timeout: std::time::Duration,
+) -> Result<(), ClusterWaitError> {
+    wait_for_prometheus_http("127.0.0.1", port, timeout).await
 }
 
 async fn wait_for_prometheus_http(
@@ -1,10 +1,21 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
 use async_trait::async_trait;
 use testing_framework_core::{
+    artifacts::{ArtifactsError, RunArtifacts},
+    errors::{ClassifiedError, ErrorKind, classify},
     scenario::{
-        BlockFeed, BlockFeedTask, Deployer, DynError, Metrics, NodeClients, RunContext, Runner,
-        Scenario, ScenarioError, spawn_block_feed,
+        ApiFaultProxies, BlockFeed, BlockFeedTask, Deployer, DeploymentTimeline, DynError,
+        FaultProxyError, Metrics, NodeClients, RunContext, Runner, Scenario, ScenarioError,
+        spawn_failover_block_feed,
+    },
+    topology::{
+        deployment::Topology,
+        readiness::{ReadinessConfig, ReadinessError, wait_transaction_readiness},
     },
-    topology::{deployment::Topology, readiness::ReadinessError},
 };
 use thiserror::Error;
 use tracing::{debug, info};
@@ -14,6 +25,7 @@ use tracing::{debug, info};
 #[derive(Clone)]
 pub struct LocalDeployer {
     membership_check: bool,
+    readiness: ReadinessConfig,
 }
 
 /// Errors surfaced by the local deployer while driving a scenario.
@@ -24,6 +36,11 @@ pub enum LocalDeployerError {
         #[source]
         source: ReadinessError,
     },
+    #[error("required node features unavailable: {source}")]
+    MissingFeatures {
+        #[source]
+        source: DynError,
+    },
     #[error("workload failed: {source}")]
     WorkloadFailed {
         #[source]
@@ -34,15 +51,51 @@ pub enum LocalDeployerError {
         #[source]
         source: DynError,
     },
+    #[error("late-joining nodes are not supported by the local deployer ({count} declared)")]
+    LateJoinUnsupported { count: usize },
+    #[error("failed to prepare run artifacts: {source}")]
+    Artifacts {
+        #[from]
+        source: ArtifactsError,
+    },
+    #[error(transparent)]
+    ApiFaults(#[from] FaultProxyError),
+}
+
+impl ClassifiedError for LocalDeployerError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::ReadinessFailed { source } => source.kind(),
+            Self::WorkloadFailed { source } | Self::ExpectationsFailed { source } => {
+                classify(source.as_ref()).unwrap_or(ErrorKind::NodeFailure)
+            }
+            Self::LateJoinUnsupported { .. } | Self::MissingFeatures { .. } => {
+                ErrorKind::Configuration
+            }
+            Self::Artifacts { source } => source.kind(),
+            Self::ApiFaults(source) => source.kind(),
+        }
+    }
 }
 
 impl From<ScenarioError> for LocalDeployerError {
     fn from(value: ScenarioError) -> Self {
         match value {
+            ScenarioError::MissingFeatures(source) => Self::MissingFeatures { source },
             ScenarioError::Workload(source) => Self::WorkloadFailed { source },
             ScenarioError::ExpectationCapture(source) | ScenarioError::Expectations(source) => {
                 Self::ExpectationsFailed { source }
             }
+            error @ (ScenarioError::Checkpoint { .. } | ScenarioError::Shutdown(_)) => {
+                Self::ExpectationsFailed {
+                    source: error.into(),
+                }
+            }
+            error @ (ScenarioError::BlockTarget { .. }
+            | ScenarioError::Aborted(_)
+            | ScenarioError::Interrupted(_)) => Self::WorkloadFailed {
+                source: error.into(),
+            },
         }
     }
 }
@@ -52,16 +105,52 @@ impl Deployer<()> for LocalDeployer {
     type Error = LocalDeployerError;
 
     async fn deploy(&self, scenario: &Scenario<()>) -> Result<Runner, Self::Error> {
+        let late_joins = scenario.topology().late_joins().len();
+        if late_joins > 0 {
+            return Err(LocalDeployerError::LateJoinUnsupported { count: late_joins });
+        }
         info!(
             validators = scenario.topology().validators().len(),
             executors = scenario.topology().executors().len(),
             membership_checks = self.membership_check,
             "starting local deployment"
         );
-        let topology = Self::prepare_topology(scenario, self.membership_check).await?;
+        let artifacts = Arc::new(RunArtifacts::create_for(scenario.profile())?);
+        let startup = Instant::now();
+        let mut timeline = DeploymentTimeline::new();
+        let topology = timeline
+            .measure(
+                "node_startup",
+                artifacts.scope_nodes(Self::prepare_topology(
+                    scenario,
+                    self.membership_check,
+                    self.readiness.or_timings(scenario.timings()),
+                )),
+            )
+            .await??;
         let node_clients = NodeClients::from_topology(scenario.topology(), &topology);
+        if scenario.transaction_readiness() {
+            timeline
+                .measure(
+                    "transaction_readiness",
+                    wait_transaction_readiness(
+                        &node_clients,
+                        scenario.topology(),
+                        self.readiness.or_timings(scenario.timings()),
+                    ),
+                )
+                .await
+                .map_err(|source| LocalDeployerError::ReadinessFailed { source })?;
+        }
+        let startup_elapsed = startup.elapsed();
+        let (node_clients, api_faults) =
+            ApiFaultProxies::wrap(node_clients, scenario.api_faults()).await?;
+        let node_logs = topology.node_logs();
+        let node_storage = topology.node_storage();
 
-        let (block_feed, block_feed_guard) = spawn_block_feed_with(&node_clients).await?;
+        let (block_feed, block_feed_guard) = timeline
+            .measure("block_feed", spawn_block_feed_with(&node_clients))
+            .await?;
 
         let context = RunContext::new(
             scenario.topology().clone(),
@@ -71,7 +160,15 @@ impl Deployer<()> for LocalDeployer {
             Metrics::empty(),
             block_feed,
             None,
-        );
+        )
+        .with_deployment_timeline(timeline)
+        .with_node_logs(node_logs)
+        .with_node_storage(node_storage)
+        .with_api_faults(api_faults)
+        .with_artifacts(artifacts);
+        context
+            .report()
+            .record_readiness("local_cluster", startup_elapsed);
 
         Ok(Runner::new(context, Some(Box::new(block_feed_guard))))
     }
@@ -91,9 +188,25 @@ impl LocalDeployer {
         self
     }
 
+    #[must_use]
+    /// Time allowed for each readiness check. Defaults to the scenario's
+    /// readiness timing.
+    pub const fn with_readiness_timeout(mut self, timeout: Duration) -> Self {
+        self.readiness = self.readiness.with_timeout(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Delay between readiness polls.
+    pub const fn with_readiness_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.readiness = self.readiness.with_poll_interval(poll_interval);
+        self
+    }
+
     async fn prepare_topology(
         scenario: &Scenario<()>,
         membership_check: bool,
+        readiness: ReadinessConfig,
     ) -> Result<Topology, LocalDeployerError> {
         let descriptors = scenario.topology();
         info!(
@@ -104,7 +217,7 @@ impl LocalDeployer {
         let topology = descriptors.clone().spawn_local().await;
 
         let skip_membership = !membership_check;
-        if let Err(source) = wait_for_readiness(&topology, skip_membership).await {
+        if let Err(source) = wait_for_readiness(&topology, skip_membership, readiness).await {
             debug!(error = ?source, "local readiness failed");
             return Err(LocalDeployerError::ReadinessFailed { source });
         }
@@ -118,6 +231,7 @@ impl Default for LocalDeployer {
     fn default() -> Self {
         Self {
             membership_check: true,
+            readiness: ReadinessConfig::new(),
         }
     }
 }
@@ -125,17 +239,18 @@ impl Default for LocalDeployer {
 async fn wait_for_readiness(
     topology: &Topology,
     skip_membership: bool,
+    readiness: ReadinessConfig,
 ) -> Result<(), ReadinessError> {
     info!("waiting for local network readiness");
-    topology.wait_network_ready().await?;
+    topology.wait_network_ready_with(readiness).await?;
     if skip_membership {
         // Allow callers to bypass deeper readiness for lightweight demos.
         return Ok(());
     }
     info!("waiting for membership readiness");
-    topology.wait_membership_ready().await?;
+    topology.wait_membership_ready_with(readiness).await?;
     info!("waiting for DA balancer readiness");
-    topology.wait_da_balancer_ready().await
+    topology.wait_da_balancer_ready_with(readiness).await
 }
 
 async fn spawn_block_feed_with(
@@ -154,9 +269,12 @@ async fn spawn_block_feed_with(
     })?;
 
     info!("starting block feed");
-    spawn_block_feed(block_source_client)
-        .await
-        .map_err(|source| LocalDeployerError::WorkloadFailed {
-            source: source.into(),
-        })
+    spawn_failover_block_feed(
+        block_source_client,
+        node_clients.validator_clients().to_vec(),
+    )
+    .await
+    .map_err(|source| LocalDeployerError::WorkloadFailed {
+        source: source.into(),
+    })
 }
@@ -0,0 +1,252 @@
+use std::{sync::Mutex, time::Duration};
+
+use reqwest::Url;
+use testing_framework_core::{
+    nodes::{
+        ApiClient,
+        common::node::SpawnNodeError,
+        executor::{Executor, create_executor_config},
+        validator::{Validator, create_validator_config},
+    },
+    scenario::{NodeClients, NodeExit, NodeTarget},
+    topology::generation::GeneratedTopology,
+};
+use tokio::{
+    net::TcpStream,
+    time::{Instant, sleep},
+};
+use tracing::{debug, info};
+
+use crate::{control::ProcessControlError, deployer::ProcessDeployerError};
+
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+const PORT_RELEASE_POLL: Duration = Duration::from_millis(100);
+
+/// Node processes owned by a process deployment.
+///
+/// Slots are `None` while a node is being restarted or after shutdown.
+pub(crate) struct ProcessCluster {
+    descriptors: GeneratedTopology,
+    validators: Mutex<Vec<Option<Validator>>>,
+    executors: Mutex<Vec<Option<Executor>>>,
+    wipe_on_restart: bool,
+}
+
+/// Endpoints of the running nodes, used for readiness probes.
+pub(crate) struct ProcessEndpoints {
+    pub validators: Vec<Url>,
+    pub executors: Vec<Url>,
+    pub validator_testing: Option<Vec<Url>>,
+    pub executor_testing: Option<Vec<Url>>,
+}
+
+impl ProcessCluster {
+    /// Spawns every validator and executor from the generated configs. If a
+    /// node does not become ready, the processes spawned so far are killed
+    /// when their handles drop and the error is returned.
+    pub(crate) async fn spawn(
+        descriptors: GeneratedTopology,
+        wipe_on_restart: bool,
+    ) -> Result<Self, ProcessDeployerError> {
+        descriptors.release_ports();
+        let mut validators = Vec::with_capacity(descriptors.validators().len());
+        for node in descriptors.validators() {
+            info!(index = node.index(), "spawning validator process");
+            validators.push(Some(
+                spawn_validator(&descriptors, node.index())
+                    .await
+                    .map_err(|source| ProcessDeployerError::Spawn {
+                        target: NodeTarget::Validator(node.index()),
+                        source,
+                    })?,
+            ));
+        }
+
+        let mut executors = Vec::with_capacity(descriptors.executors().len());
+        for node in descriptors.executors() {
+            info!(index = node.index(), "spawning executor process");
+            executors.push(Some(
+                spawn_executor(&descriptors, node.index())
+                    .await
+                    .map_err(|source| ProcessDeployerError::Spawn {
+                        target: NodeTarget::Executor(node.index()),
+                        source,
+                    })?,
+            ));
+        }
+
+        Ok(Self {
+            descriptors,
+            validators: Mutex::new(validators),
+            executors: Mutex::new(executors),
+            wipe_on_restart,
+        })
+    }
+
+    pub(crate) fn node_clients(&self) -> NodeClients {
+        let validators = self
+            .validators
+            .lock()
+            .expect("validator lock poisoned")
+            .iter()
+            .flatten()
+            .map(|node| ApiClient::from_urls(node.url(), node.testing_url()))
+            .collect();
+        let executors = self
+            .executors
+            .lock()
+            .expect("executor lock poisoned")
+            .iter()
+            .flatten()
+            .map(|node| ApiClient::from_urls(node.url(), node.testing_url()))
+            .collect();
+        NodeClients::new(validators, executors)
+    }
+
+    pub(crate) fn endpoints(&self) -> ProcessEndpoints {
+        let validators = self.validators.lock().expect("validator lock poisoned");
+        let executors = self.executors.lock().expect("executor lock poisoned");
+        ProcessEndpoints {
+            validators: validators.iter().flatten().map(|node| node.url()).collect(),
+            executors: executors.iter().flatten().map(|node| node.url()).collect(),
+            validator_testing: validators
+                .iter()
+                .flatten()
+                .map(|node| node.testing_url())
+                .collect(),
+            executor_testing: executors
+                .iter()
+                .flatten()
+                .map(|node| node.testing_url())
+                .collect(),
+        }
+    }
+
+    /// Kills the node process and starts it again from its directory and
+    /// config, so it comes back with its database and blobs. With
+    /// `wipe_on_restart`, a fresh process with an empty data directory is
+    /// spawned from the generated config instead.
+    pub(crate) async fn restart(&self, target: NodeTarget) -> Result<(), ProcessControlError> {
+        let spawn_error = |source| ProcessControlError::Spawn { target, source };
+        match target {
+            NodeTarget::Validator(index) => {
+                let mut node = take_slot(&self.validators, target, index)?;
+                if self.wipe_on_restart {
+                    let url = node.url();
+                    drop(node);
+                    wait_for_port_release(&url).await;
+                    node = spawn_validator(&self.descriptors, index)
+                        .await
+                        .map_err(spawn_error)?;
+                } else {
+                    node.respawn().await.map_err(spawn_error)?;
+                }
+                self.validators.lock().expect("validator lock poisoned")[index] = Some(node);
+            }
+            NodeTarget::Executor(index) => {
+                let mut node = take_slot(&self.executors, target, index)?;
+                if self.wipe_on_restart {
+                    let url = node.url();
+                    drop(node);
+                    wait_for_port_release(&url).await;
+                    node = spawn_executor(&self.descriptors, index)
+                        .await
+                        .map_err(spawn_error)?;
+                } else {
+                    node.respawn().await.map_err(spawn_error)?;
+                }
+                self.executors.lock().expect("executor lock poisoned")[index] = Some(node);
+            }
+        }
+        info!(%target, "node process restarted");
+        Ok(())
+    }
+
+    /// Sends `SIGTERM` to the node process and waits up to `deadline` for it
+    /// to exit. The node is not respawned; its exited handle goes back into
+    /// the slot so its directory (and logs) live until [`Self::shutdown`].
+    pub(crate) async fn terminate(
+        &self,
+        target: NodeTarget,
+        deadline: Duration,
+    ) -> Result<NodeExit, ProcessControlError> {
+        let exit = match target {
+            NodeTarget::Validator(index) => {
+                let mut node = take_slot(&self.validators, target, index)?;
+                let exit = node.terminate(deadline).await;
+                self.validators.lock().expect("validator lock poisoned")[index] = Some(node);
+                exit
+            }
+            NodeTarget::Executor(index) => {
+                let mut node = take_slot(&self.executors, target, index)?;
+                let exit = node.terminate(deadline).await;
+                self.executors.lock().expect("executor lock poisoned")[index] = Some(node);
+                exit
+            }
+        }
+        .map_err(|source| ProcessControlError::Terminate { target, source })?;
+        info!(%target, ?exit, "node process terminated");
+        Ok(exit)
+    }
+
+    /// Kills every node process still owned by the cluster.
+    pub(crate) fn shutdown(&self) {
+        let validators =
+            std::mem::take(&mut *self.validators.lock().expect("validator lock poisoned"));
+        let executors =
+            std::mem::take(&mut *self.executors.lock().expect("executor lock poisoned"));
+        debug!(
+            validators = validators.len(),
+            executors = executors.len(),
+            "stopping node processes"
+        );
+        drop(validators);
+        drop(executors);
+    }
+}
+
+fn take_slot<T>(
+    slots: &Mutex<Vec<Option<T>>>,
+    target: NodeTarget,
+    index: usize,
+) -> Result<T, ProcessControlError> {
+    slots
+        .lock()
+        .expect("node slot lock poisoned")
+        .get_mut(index)
+        .and_then(Option::take)
+        .ok_or(ProcessControlError::Unavailable { target })
+}
+
+async fn spawn_validator(
+    descriptors: &GeneratedTopology,
+    index: usize,
+) -> Result<Validator, SpawnNodeError> {
+    let general = descriptors.validators()[index].general.clone();
+    Validator::spawn(create_validator_config(general)).await
+}
+
+async fn spawn_executor(
+    descriptors: &GeneratedTopology,
+    index: usize,
+) -> Result<Executor, SpawnNodeError> {
+    let general = descriptors.executors()[index].general.clone();
+    Executor::try_spawn(create_executor_config(general)).await
+}
+
+/// Waits until nothing accepts connections on the node's API port so the
+/// replacement process can bind it again.
+async fn wait_for_port_release(url: &Url) {
+    let Some(port) = url.port_or_known_default() else {
+        return;
+    };
+    let host = url.host_str().unwrap_or("127.0.0.1").to_owned();
+    let deadline = Instant::now() + PORT_RELEASE_TIMEOUT;
+    while Instant::now() < deadline {
+        if TcpStream::connect((host.as_str(), port)).await.is_err() {
+            return;
+        }
+        sleep(PORT_RELEASE_POLL).await;
+    }
+    debug!(%url, "node port still accepting connections after shutdown");
+}
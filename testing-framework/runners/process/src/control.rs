@@ -0,0 +1,67 @@
+use std::{io, sync::Arc, time::Duration};
+
+use testing_framework_core::{
+    nodes::common::node::SpawnNodeError,
+    scenario::{DynError, NodeControlHandle, NodeExit, NodeTarget},
+};
+use thiserror::Error;
+
+use crate::cluster::ProcessCluster;
+
+/// Failures while restarting a node process.
+#[derive(Debug, Error)]
+pub enum ProcessControlError {
+    #[error("{target} is not running (already restarting or shut down)")]
+    Unavailable { target: NodeTarget },
+    #[error("{target} failed to come back after restart: {source}")]
+    Spawn {
+        target: NodeTarget,
+        #[source]
+        source: SpawnNodeError,
+    },
+    #[error("failed to signal {target}: {source}")]
+    Terminate {
+        target: NodeTarget,
+        #[source]
+        source: io::Error,
+    },
+}
+
+/// Node control handle restarting local node processes.
+pub struct ProcessNodeControl {
+    cluster: Arc<ProcessCluster>,
+}
+
+impl ProcessNodeControl {
+    pub(crate) const fn new(cluster: Arc<ProcessCluster>) -> Self {
+        Self { cluster }
+    }
+}
+
+#[async_trait::async_trait]
+impl NodeControlHandle for ProcessNodeControl {
+    async fn restart_validator(&self, index: usize) -> Result<(), DynError> {
+        self.cluster
+            .restart(NodeTarget::Validator(index))
+            .await
+            .map_err(|err| format!("validator restart failed: {err}").into())
+    }
+
+    async fn restart_executor(&self, index: usize) -> Result<(), DynError> {
+        self.cluster
+            .restart(NodeTarget::Executor(index))
+            .await
+            .map_err(|err| format!("executor restart failed: {err}").into())
+    }
+
+    async fn terminate_node(
+        &self,
+        target: NodeTarget,
+        deadline: Duration,
+    ) -> Result<NodeExit, DynError> {
+        self.cluster
+            .terminate(target, deadline)
+            .await
+            .map_err(|err| format!("{target} terminate failed: {err}").into())
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use reqwest::Url;
+use testing_framework_core::{
+    errors::{ClassifiedError, ErrorKind},
+    nodes::common::node::SpawnNodeError,
+    scenario::{
+        ApiFaultProxies, BlockFeed, BlockFeedTask, CleanupGuard, Deployer, DeploymentTimeline,
+        DynError, FaultProxyError, Metrics, MetricsError, NodeClients, NodeControlHandle,
+        NodeTarget, RequiresNodeControl, RunContext, Runner, Scenario, spawn_failover_block_feed,
+    },
+    topology::{
+        generation::GeneratedTopology,
+        readiness::{ReadinessConfig, ReadinessError, wait_transaction_readiness},
+    },
+};
+use thiserror::Error;
+use tracing::{debug, info};
+
+use crate::{cluster::ProcessCluster, control::ProcessNodeControl};
+
+/// Runs validators and executors as native child processes of the test
+/// binary, without Docker or Kubernetes.
+///
+/// Nodes are spawned from the scenario's generated configs using the
+/// `nomos-node`/`nomos-executor` binaries resolved the same way as the local
+/// harness (`NOMOS_NODE_BIN`, `NOMOS_EXECUTOR_BIN`, or the shared assets
+/// directory).
+#[derive(Clone)]
+pub struct ProcessDeployer {
+    readiness_checks: bool,
+    readiness: ReadinessConfig,
+    prometheus_url: Option<Url>,
+    wipe_on_restart: bool,
+}
+
+/// Errors surfaced by the process deployer.
+#[derive(Debug, Error)]
+pub enum ProcessDeployerError {
+    #[error("{target} process failed to start: {source}")]
+    Spawn {
+        target: NodeTarget,
+        #[source]
+        source: SpawnNodeError,
+    },
+    #[error("readiness probe failed: {source}")]
+    Readiness {
+        #[source]
+        source: ReadinessError,
+    },
+    #[error(transparent)]
+    Telemetry(#[from] MetricsError),
+    #[error("failed to initialize block feed: {source}")]
+    BlockFeed {
+        #[source]
+        source: DynError,
+    },
+    #[error("late-joining nodes are not supported by the process deployer ({count} declared)")]
+    LateJoinUnsupported { count: usize },
+    #[error(transparent)]
+    ApiFaults(#[from] FaultProxyError),
+}
+
+impl ClassifiedError for ProcessDeployerError {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            Self::Spawn { .. } | Self::BlockFeed { .. } => ErrorKind::NodeFailure,
+            Self::Readiness { source } => source.kind(),
+            Self::Telemetry(source) => source.kind(),
+            Self::LateJoinUnsupported { .. } => ErrorKind::Configuration,
+            Self::ApiFaults(source) => source.kind(),
+        }
+    }
+}
+
+impl Default for ProcessDeployer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProcessDeployer {
+    #[must_use]
+    /// Construct with network and membership readiness checks enabled.
+    pub const fn new() -> Self {
+        Self {
+            readiness_checks: true,
+            readiness: ReadinessConfig::new(),
+            prometheus_url: None,
+            wipe_on_restart: false,
+        }
+    }
+
+    #[must_use]
+    /// Enable or disable readiness probes before handing control to
+    /// workloads.
+    pub const fn with_readiness(mut self, enabled: bool) -> Self {
+        self.readiness_checks = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Time allowed for each network/membership readiness check. Defaults to
+    /// the scenario's readiness timing.
+    pub const fn with_readiness_timeout(mut self, timeout: Duration) -> Self {
+        self.readiness = self.readiness.with_timeout(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Delay between readiness polls.
+    pub const fn with_readiness_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.readiness = self.readiness.with_poll_interval(poll_interval);
+        self
+    }
+
+    #[must_use]
+    /// Query an externally managed Prometheus (scraping the node processes)
+    /// for scenario telemetry.
+    pub fn with_prometheus(mut self, url: Url) -> Self {
+        self.prometheus_url = Some(url);
+        self
+    }
+
+    #[must_use]
+    /// Restart nodes with an empty data directory instead of the state they
+    /// had when stopped (off by default).
+    pub const fn with_wipe_on_restart(mut self, enabled: bool) -> Self {
+        self.wipe_on_restart = enabled;
+        self
+    }
+
+    fn telemetry(&self) -> Result<Metrics, MetricsError> {
+        self.prometheus_url
+            .clone()
+            .map_or_else(|| Ok(Metrics::empty()), Metrics::from_prometheus)
+    }
+}
+
+#[async_trait]
+impl<Caps> Deployer<Caps> for ProcessDeployer
+where
+    Caps: RequiresNodeControl + Send + Sync,
+{
+    type Error = ProcessDeployerError;
+
+    async fn deploy(&self, scenario: &Scenario<Caps>) -> Result<Runner, Self::Error> {
+        let descriptors = scenario.topology().clone();
+        let late_joins = descriptors.late_joins().len();
+        if late_joins > 0 {
+            return Err(ProcessDeployerError::LateJoinUnsupported { count: late_joins });
+        }
+        info!(
+            validators = descriptors.validators().len(),
+            executors = descriptors.executors().len(),
+            readiness_checks = self.readiness_checks,
+            "starting process deployment"
+        );
+
+        let telemetry = self.telemetry()?;
+
+        let startup = Instant::now();
+        let mut timeline = DeploymentTimeline::new();
+        let cluster = Arc::new(
+            timeline
+                .measure(
+                    "node_startup",
+                    ProcessCluster::spawn(descriptors.clone(), self.wipe_on_restart),
+                )
+                .await?,
+        );
+        if self.readiness_checks {
+            let readiness = self.readiness.or_timings(scenario.timings());
+            if let Err(source) = timeline
+                .measure(
+                    "readiness",
+                    wait_for_readiness(&descriptors, &cluster, readiness),
+                )
+                .await
+            {
+                cluster.shutdown();
+                return Err(ProcessDeployerError::Readiness { source });
+            }
+        }
+        let node_clients = cluster.node_clients();
+        if scenario.transaction_readiness() {
+            let readiness = self.readiness.or_timings(scenario.timings());
+            if let Err(source) = timeline
+                .measure(
+                    "transaction_readiness",
+                    wait_transaction_readiness(&node_clients, &descriptors, readiness),
+                )
+                .await
+            {
+                cluster.shutdown();
+                return Err(ProcessDeployerError::Readiness { source });
+            }
+        }
+        let startup_elapsed = startup.elapsed();
+
+        let (node_clients, api_faults) =
+            match ApiFaultProxies::wrap(node_clients, scenario.api_faults()).await {
+                Ok(wrapped) => wrapped,
+                Err(source) => {
+                    cluster.shutdown();
+                    return Err(source.into());
+                }
+            };
+        let (block_feed, block_feed_guard) = match timeline
+            .measure("block_feed", spawn_block_feed_with(&node_clients))
+            .await
+        {
+            Ok(pair) => pair,
+            Err(err) => {
+                cluster.shutdown();
+                return Err(err);
+            }
+        };
+
+        let node_control = Caps::REQUIRED.then(|| {
+            Arc::new(ProcessNodeControl::new(Arc::clone(&cluster))) as Arc<dyn NodeControlHandle>
+        });
+
+        let context = RunContext::new(
+            descriptors,
+            None,
+            node_clients,
+            scenario.duration(),
+            telemetry,
+            block_feed,
+            node_control,
+        )
+        .with_deployment_timeline(timeline)
+        .with_api_faults(api_faults);
+        context
+            .report()
+            .record_readiness("process_cluster", startup_elapsed);
+
+        info!("process deployment ready; handing control to scenario runner");
+        let cleanup: Box<dyn CleanupGuard> =
+            Box::new(ProcessCleanupGuard::new(cluster, block_feed_guard));
+        Ok(Runner::new(context, Some(cleanup)))
+    }
+}
+
+async fn wait_for_readiness(
+    descriptors: &GeneratedTopology,
+    cluster: &ProcessCluster,
+    readiness: ReadinessConfig,
+) -> Result<(), ReadinessError> {
+    let endpoints = cluster.endpoints();
+    info!("waiting for process cluster readiness");
+    descriptors
+        .wait_remote_readiness(
+            &endpoints.validators,
+            &endpoints.executors,
+            endpoints.validator_testing.as_deref(),
+            endpoints.executor_testing.as_deref(),
+            readiness,
+        )
+        .await
+}
+
+async fn spawn_block_feed_with(
+    node_clients: &NodeClients,
+) -> Result<(BlockFeed, BlockFeedTask), ProcessDeployerError> {
+    debug!(
+        validators = node_clients.validator_clients().len(),
+        "selecting validator client for process block feed"
+    );
+
+    let block_source_client = node_clients.random_validator().cloned().ok_or_else(|| {
+        ProcessDeployerError::BlockFeed {
+            source: "block feed requires at least one validator".into(),
+        }
+    })?;
+
+    spawn_failover_block_feed(
+        block_source_client,
+        node_clients.validator_clients().to_vec(),
+    )
+    .await
+    .map_err(|source| ProcessDeployerError::BlockFeed {
+        source: source.into(),
+    })
+}
+
+/// Stops the block feed and kills every node process when the run ends.
+struct ProcessCleanupGuard {
+    cluster: Arc<ProcessCluster>,
+    block_feed: Option<BlockFeedTask>,
+}
+
+impl ProcessCleanupGuard {
+    const fn new(cluster: Arc<ProcessCluster>, block_feed: BlockFeedTask) -> Self {
+        Self {
+            cluster,
+            block_feed: Some(block_feed),
+        }
+    }
+}
+
+impl CleanupGuard for ProcessCleanupGuard {
+    fn cleanup(mut self: Box<Self>) {
+        if let Some(block_feed) = self.block_feed.take() {
+            CleanupGuard::cleanup(Box::new(block_feed));
+        }
+        self.cluster.shutdown();
+    }
+}
@@ -0,0 +1,6 @@
+mod cluster;
+mod control;
+mod deployer;
+
+pub use control::{ProcessControlError, ProcessNodeControl};
+pub use deployer::{ProcessDeployer, ProcessDeployerError};
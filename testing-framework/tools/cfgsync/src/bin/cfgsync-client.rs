@@ -1,13 +1,13 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env, fs,
-    net::Ipv4Addr,
+    net::{IpAddr, Ipv4Addr},
     process,
     str::FromStr,
 };
 
 use cfgsync::{
-    client::{FetchedConfig, get_config},
+    client::{FetchedConfig, RetryPolicy, get_config_with_retry},
     server::ClientIp,
 };
 use nomos_executor::config::Config as ExecutorConfig;
@@ -17,11 +17,52 @@ use serde::{Serialize, de::DeserializeOwned};
 use subnetworks_assignations::{MembershipCreator, MembershipHandler, SubnetworkId};
 use testing_framework_core::constants::cfgsync_port as default_cfgsync_port;
 
-fn parse_ip(ip_str: &str) -> Ipv4Addr {
-    ip_str.parse().unwrap_or_else(|_| {
-        eprintln!("Invalid IP format, defaulting to 127.0.0.1");
-        Ipv4Addr::LOCALHOST
-    })
+/// Pick the host IP from `CFG_HOST_IP`, which may list several addresses
+/// (e.g. `hostname -i` on a dual-stack network). IPv6 is preferred when
+/// `CFG_IP_STACK=v6`.
+fn parse_ip(raw: &str, prefer_v6: bool) -> IpAddr {
+    let candidates: Vec<IpAddr> = raw
+        .split_whitespace()
+        .filter_map(|candidate| candidate.parse().ok())
+        .collect();
+    candidates
+        .iter()
+        .find(|ip| ip.is_ipv6() == prefer_v6)
+        .or_else(|| candidates.first())
+        .copied()
+        .unwrap_or_else(|| {
+            eprintln!("Invalid IP format, defaulting to 127.0.0.1");
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        })
+}
+
+/// IPv4 address listed in `CFG_HOST_IP` when `ip` is IPv6. The libp2p swarm
+/// only listens on IPv4, so peers dial it there.
+fn parse_swarm_ip(raw: &str, ip: IpAddr) -> Option<IpAddr> {
+    if ip.is_ipv4() {
+        return None;
+    }
+    raw.split_whitespace()
+        .filter_map(|candidate| candidate.parse::<IpAddr>().ok())
+        .find(IpAddr::is_ipv4)
+}
+
+/// Parse `CFG_LISTENER_PORTS`, a comma-separated list of
+/// `<json pointer>=<port>` entries.
+fn parse_listener_ports(raw: &str) -> BTreeMap<String, u16> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .filter_map(|entry| {
+            let parsed = entry.split_once('=').and_then(|(pointer, port)| {
+                Some((pointer.trim().to_owned(), port.trim().parse().ok()?))
+            });
+            if parsed.is_none() {
+                eprintln!("Ignoring invalid listener port entry {entry:?}");
+            }
+            parsed
+        })
+        .collect()
 }
 
 fn parse_assignations(raw: &serde_json::Value) -> Option<HashMap<SubnetworkId, HashSet<PeerId>>> {
@@ -66,7 +107,10 @@ where
     Config: Serialize + DeserializeOwned,
     F: FnOnce(&mut Config, HashMap<SubnetworkId, HashSet<PeerId>>),
 {
-    let FetchedConfig { mut config, raw } = get_config::<Config>(payload, url).await?;
+    let FetchedConfig { mut config, raw } =
+        get_config_with_retry::<Config>(&payload, url, &RetryPolicy::from_env())
+            .await
+            .map_err(|err| err.to_string())?;
 
     if let Some(assignations) = parse_assignations(&raw) {
         apply_membership(&mut config, assignations);
@@ -86,7 +130,10 @@ async fn main() {
     let config_file_path = env::var("CFG_FILE_PATH").unwrap_or_else(|_| "config.yaml".to_owned());
     let server_addr = env::var("CFG_SERVER_ADDR")
         .unwrap_or_else(|_| format!("http://127.0.0.1:{}", default_cfgsync_port()));
-    let ip = parse_ip(&env::var("CFG_HOST_IP").unwrap_or_else(|_| "127.0.0.1".to_owned()));
+    let prefer_v6 = env::var("CFG_IP_STACK").is_ok_and(|stack| stack == "v6");
+    let host_ip = env::var("CFG_HOST_IP").unwrap_or_else(|_| "127.0.0.1".to_owned());
+    let ip = parse_ip(&host_ip, prefer_v6);
+    let swarm_ip = parse_swarm_ip(&host_ip, ip);
     let identifier =
         env::var("CFG_HOST_IDENTIFIER").unwrap_or_else(|_| "unidentified-node".to_owned());
 
@@ -101,15 +148,24 @@ async fn main() {
     let testing_http_port = env::var("CFG_TESTING_HTTP_PORT")
         .ok()
         .and_then(|v| v.parse().ok());
+    let metrics_port = env::var("CFG_METRICS_PORT")
+        .ok()
+        .and_then(|v| v.parse().ok());
+    let listener_ports = env::var("CFG_LISTENER_PORTS")
+        .map(|raw| parse_listener_ports(&raw))
+        .unwrap_or_default();
 
     let payload = ClientIp {
         ip,
+        swarm_ip,
         identifier,
         network_port,
         da_port,
         blend_port,
         api_port,
         testing_http_port,
+        metrics_port,
+        listener_ports,
     };
 
     let node_config_endpoint = match host_kind.as_str() {
@@ -150,3 +206,24 @@ async fn main() {
         process::exit(1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listener_ports_skip_invalid_entries() {
+        let ports = parse_listener_ports(
+            " /http/backend_settings/address = 8080,,/tracing/metrics/address=9100,no-port,/x=70000",
+        );
+
+        assert_eq!(
+            ports,
+            BTreeMap::from([
+                ("/http/backend_settings/address".to_owned(), 8080),
+                ("/tracing/metrics/address".to_owned(), 9100),
+            ])
+        );
+        assert!(parse_listener_ports("").is_empty());
+    }
+}
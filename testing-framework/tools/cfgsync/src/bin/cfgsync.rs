@@ -0,0 +1,38 @@
+use std::{path::PathBuf, process};
+
+use cfgsync::config::schema::validate_file;
+use clap::{Parser, Subcommand};
+
+#[derive(Parser, Debug)]
+#[command(about = "CfgSync config tooling")]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Check a cfgsync server config for unknown fields, missing parameters
+    /// and inconsistent values.
+    Validate { config: PathBuf },
+}
+
+fn main() {
+    let Args { command } = Args::parse();
+
+    match command {
+        Command::Validate { config } => match validate_file(&config) {
+            Ok(parsed) => {
+                println!(
+                    "{} is a valid cfgsync config for {} hosts",
+                    config.display(),
+                    parsed.n_hosts
+                );
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+        },
+    }
+}
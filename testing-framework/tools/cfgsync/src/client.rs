@@ -1,7 +1,97 @@
-use reqwest::{Client, Response};
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use reqwest::{Client, Response, StatusCode};
 use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::{server::ClientIp, status::SyncStatus};
+
+/// Overrides the retry deadline (in seconds) used by `cfgsync-client`.
+pub const RETRY_DEADLINE_ENV: &str = "CFG_RETRY_DEADLINE_SECS";
 
-use crate::server::ClientIp;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(90);
+const MAX_BODY_CHARS: usize = 512;
+
+/// Exponential backoff applied while the cfgsync server is unreachable or
+/// failing.
+#[derive(Clone, Copy, Debug)]
+pub struct RetryPolicy {
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Total time budget across all attempts.
+    pub deadline: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            deadline: DEFAULT_DEADLINE,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Default policy with the deadline taken from `CFG_RETRY_DEADLINE_SECS`
+    /// when set.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let deadline = env::var(RETRY_DEADLINE_ENV)
+            .ok()
+            .and_then(|raw| raw.parse::<u64>().ok())
+            .map_or(DEFAULT_DEADLINE, Duration::from_secs);
+        Self {
+            deadline,
+            ..Self::default()
+        }
+    }
+
+    fn next_backoff(&self, current: Duration) -> Duration {
+        current.saturating_mul(2).min(self.max_backoff)
+    }
+}
+
+/// Failure to obtain a node config, with enough context to tell a slow
+/// server apart from a registration round that will never complete.
+#[derive(Debug, Error)]
+pub enum ConfigFetchError {
+    #[error(
+        "cfgsync at {url} rejected the request after {attempts} attempt(s): HTTP {status}: {body}{}",
+        describe_server_status(.server_status.as_ref())
+    )]
+    Rejected {
+        url: String,
+        attempts: u32,
+        status: StatusCode,
+        body: String,
+        server_status: Option<SyncStatus>,
+    },
+    #[error(
+        "cfgsync at {url} did not answer within {elapsed:?} ({attempts} attempt(s)); last error: {last_error}{}",
+        describe_server_status(.server_status.as_ref())
+    )]
+    DeadlineExceeded {
+        url: String,
+        attempts: u32,
+        elapsed: Duration,
+        last_error: String,
+        last_status: Option<StatusCode>,
+        last_body: Option<String>,
+        server_status: Option<SyncStatus>,
+    },
+    #[error("invalid config from cfgsync at {url}: {message}")]
+    InvalidConfig { url: String, message: String },
+}
+
+fn describe_server_status(status: Option<&SyncStatus>) -> String {
+    status.map_or_else(String::new, |status| format!(" ({})", status.summary()))
+}
 
 #[derive(Debug)]
 pub struct FetchedConfig<Config> {
@@ -44,3 +134,103 @@ pub async fn get_config<Config: DeserializeOwned>(
 
     deserialize_response(response).await
 }
+
+/// Fetch the registration progress from a cfgsync server's `GET /status`.
+pub async fn get_status(base_url: &str) -> Result<SyncStatus, String> {
+    let url = format!("{}/status", base_url.trim_end_matches('/'));
+    let response = Client::new()
+        .get(&url)
+        .send()
+        .await
+        .map_err(|err| format!("Failed to query cfgsync status: {err}"))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Server error: {:?}", response.status()));
+    }
+
+    response
+        .json()
+        .await
+        .map_err(|err| format!("Failed to parse cfgsync status: {err}"))
+}
+
+/// Like [`get_config`], but retries connection failures and server errors
+/// with exponential backoff until `policy.deadline` elapses.
+///
+/// Request timeouts (HTTP 408) and client errors are not retried: they mean
+/// the registration round failed and another attempt cannot succeed.
+pub async fn get_config_with_retry<Config: DeserializeOwned>(
+    payload: &ClientIp,
+    url: &str,
+    policy: &RetryPolicy,
+) -> Result<FetchedConfig<Config>, ConfigFetchError> {
+    let client = Client::new();
+    let started = Instant::now();
+    let mut backoff = policy.initial_backoff;
+    let mut attempts = 0;
+    let mut last_status = None;
+    let mut last_body = None;
+
+    loop {
+        attempts += 1;
+        let last_error = match client.post(url).json(payload).send().await {
+            Ok(response) if response.status().is_success() => {
+                return deserialize_response(response).await.map_err(|message| {
+                    ConfigFetchError::InvalidConfig {
+                        url: url.to_owned(),
+                        message,
+                    }
+                });
+            }
+            Ok(response) => {
+                let status = response.status();
+                let body = truncated_body(response).await;
+                if !is_retryable(status) {
+                    return Err(ConfigFetchError::Rejected {
+                        url: url.to_owned(),
+                        attempts,
+                        status,
+                        body,
+                        server_status: server_status(url).await,
+                    });
+                }
+                last_status = Some(status);
+                last_body = Some(body);
+                format!("HTTP {status}")
+            }
+            Err(err) => err.to_string(),
+        };
+
+        let elapsed = started.elapsed();
+        if elapsed + backoff > policy.deadline {
+            return Err(ConfigFetchError::DeadlineExceeded {
+                url: url.to_owned(),
+                attempts,
+                elapsed,
+                last_error,
+                last_status,
+                last_body,
+                server_status: server_status(url).await,
+            });
+        }
+
+        eprintln!("cfgsync not ready (attempt {attempts}): {last_error}; retrying in {backoff:?}");
+        tokio::time::sleep(backoff).await;
+        backoff = policy.next_backoff(backoff);
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS
+}
+
+async fn truncated_body(response: Response) -> String {
+    let body = response.text().await.unwrap_or_default();
+    body.chars().take(MAX_BODY_CHARS).collect()
+}
+
+/// Best-effort registration snapshot from the server that served `url`.
+async fn server_status(url: &str) -> Option<SyncStatus> {
+    let base_url = url.rsplit_once('/').map_or(url, |(base, _)| base);
+    get_status(base_url).await.ok()
+}
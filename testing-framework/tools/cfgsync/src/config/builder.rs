@@ -1,26 +1,33 @@
-use std::{collections::HashMap, net::Ipv4Addr, str::FromStr as _};
+use std::{
+    collections::HashMap,
+    net::{Ipv4Addr, SocketAddr},
+};
 
 use nomos_core::mantle::GenesisTx as _;
 use nomos_libp2p::{Multiaddr, PeerId, ed25519};
 use nomos_tracing_service::TracingSettings;
-use nomos_utils::net::get_available_udp_port;
 use rand::{Rng as _, thread_rng};
-use testing_framework_config::topology::configs::{
-    GeneralConfig,
-    api::GeneralApiConfig,
-    blend,
-    blend::create_blend_configs,
-    bootstrap,
-    bootstrap::{SHORT_PROLONGED_BOOTSTRAP_PERIOD, create_bootstrap_configs},
-    consensus,
-    consensus::{ConsensusParams, create_consensus_configs, create_genesis_tx_with_declarations},
-    da,
-    da::{DaParams, create_da_configs},
-    network,
-    network::{NetworkParams, create_network_configs},
-    time::default_time_config,
-    wallet::WalletConfig,
+use testing_framework_config::{
+    quic_multiaddr,
+    topology::configs::{
+        GeneralConfig,
+        api::GeneralApiConfig,
+        blend,
+        blend::create_blend_configs,
+        bootstrap,
+        bootstrap::{SHORT_PROLONGED_BOOTSTRAP_PERIOD, create_bootstrap_configs},
+        consensus,
+        consensus::{ConsensusParams, create_consensus_configs, create_custom_genesis_tx},
+        da,
+        da::{DaParams, create_da_configs},
+        genesis::GenesisCustomization,
+        network,
+        network::{IpStack, NetworkParams, create_network_configs},
+        time::default_time_config,
+        wallet::WalletConfig,
+    },
 };
+use testing_framework_core::ports::allocate_udp_port;
 
 use crate::{
     config::{
@@ -40,6 +47,7 @@ pub fn create_node_configs(
     ids: Option<Vec<[u8; 32]>>,
     da_ports: Option<Vec<u16>>,
     blend_ports: Option<Vec<u16>>,
+    ip_stack: IpStack,
     hosts: Vec<Host>,
 ) -> HashMap<Host, GeneralConfig> {
     let hosts = sort_hosts(hosts);
@@ -70,8 +78,9 @@ pub fn create_node_configs(
         &ids,
         &ports,
         &blend_ports,
+        ip_stack,
     );
-    let api_configs = build_api_configs(&hosts);
+    let api_configs = build_api_configs(&hosts, ip_stack);
     let mut configured_hosts = HashMap::new();
 
     let initial_peer_templates: Vec<Vec<Multiaddr>> = network_configs
@@ -91,7 +100,8 @@ pub fn create_node_configs(
         &peer_ids,
     );
 
-    let providers = create_providers(&hosts, &consensus_configs, &blend_configs, &da_configs);
+    let mut providers = create_providers(&hosts, &consensus_configs, &blend_configs, &da_configs);
+    providers.extend(consensus_configs[0].extra_providers.iter().cloned());
 
     // Update genesis TX to contain Blend and DA providers.
     let ledger_tx = consensus_configs[0]
@@ -99,7 +109,8 @@ pub fn create_node_configs(
         .mantle_tx()
         .ledger_tx
         .clone();
-    let genesis_tx = create_genesis_tx_with_declarations(ledger_tx, providers);
+    let genesis_tx =
+        create_custom_genesis_tx(ledger_tx, providers, &consensus_params.genesis.inscriptions);
     for c in &mut consensus_configs {
         c.genesis_tx = genesis_tx.clone();
     }
@@ -111,34 +122,17 @@ pub fn create_node_configs(
         let consensus_config = consensus_configs[i].clone();
         let api_config = api_configs[i].clone();
 
-        // DA Libp2p network config.
         let mut da_config = da_configs[i].clone();
-        da_config.listening_address = Multiaddr::from_str(&format!(
-            "/ip4/0.0.0.0/udp/{}/quic-v1",
-            host.da_network_port,
-        ))
-        .unwrap();
-        if matches!(host.kind, HostKind::Validator) {
-            da_config.policy_settings.min_dispersal_peers = 0;
-        }
-
-        // Libp2p network config.
         let mut network_config = network_configs[i].clone();
-        network_config.backend.swarm.host = Ipv4Addr::from_str("0.0.0.0").unwrap();
-        network_config.backend.swarm.port = host.network_port;
-        network_config.backend.initial_peers = host_network_init_peers[i].clone();
-        network_config.backend.swarm.nat_config = nomos_libp2p::NatSettings::Static {
-            external_address: Multiaddr::from_str(&format!(
-                "/ip4/{}/udp/{}/quic-v1",
-                host.ip, host.network_port
-            ))
-            .unwrap(),
-        };
-
-        // Blend network config.
         let mut blend_config = blend_configs[i].clone();
-        blend_config.backend_core.listening_address =
-            Multiaddr::from_str(&format!("/ip4/0.0.0.0/udp/{}/quic-v1", host.blend_port)).unwrap();
+        bind_to_host(
+            &host,
+            ip_stack,
+            &mut da_config,
+            &mut network_config,
+            &mut blend_config,
+        );
+        network_config.backend.initial_peers = host_network_init_peers[i].clone();
 
         let tracing_config =
             update_tracing_identifier(tracing_settings.clone(), host.identifier.clone());
@@ -163,6 +157,109 @@ pub fn create_node_configs(
     configured_hosts
 }
 
+/// Builds the config of a host that registers after the initial round.
+///
+/// The host gets fresh keys but shares the genesis, ledger and DA membership
+/// of `template`, a config handed out in the initial round. It is not
+/// declared in genesis, so it follows the chain without leading blocks or
+/// serving DA subnetworks.
+#[must_use]
+pub fn create_late_node_config(
+    template: &GeneralConfig,
+    consensus_params: &ConsensusParams,
+    da_params: &DaParams,
+    tracing_settings: &TracingSettings,
+    wallet_config: &WalletConfig,
+    ip_stack: IpStack,
+    host: &Host,
+    initial_peers: Vec<Multiaddr>,
+) -> GeneralConfig {
+    let ids = generate_ids(1, None);
+    // The genesis comes from `template`; only the keys are generated here.
+    let single_node = ConsensusParams {
+        n_participants: 1,
+        genesis: GenesisCustomization::new(),
+        ..consensus_params.clone()
+    };
+
+    let BaseConfigs {
+        consensus_configs,
+        bootstrap_configs,
+        da_configs,
+        network_configs,
+        blend_configs,
+    } = build_base_configs(
+        &single_node,
+        da_params,
+        wallet_config,
+        &ids,
+        &[host.da_network_port],
+        &[host.blend_port],
+        ip_stack,
+    );
+    let kms_config = create_kms_configs(&blend_configs, &da_configs).remove(0);
+
+    // Only the leader keys are the joiner's own; everything derived from
+    // genesis has to match the running cluster.
+    let mut consensus_config = template.consensus_config.clone();
+    consensus_config.leader_config = consensus_configs[0].leader_config.clone();
+
+    let mut da_config = da_configs[0].clone();
+    da_config.membership = template.da_config.membership.clone();
+    let mut network_config = network_configs[0].clone();
+    let mut blend_config = blend_configs[0].clone();
+    bind_to_host(
+        host,
+        ip_stack,
+        &mut da_config,
+        &mut network_config,
+        &mut blend_config,
+    );
+    network_config.backend.initial_peers = initial_peers;
+
+    GeneralConfig {
+        consensus_config,
+        bootstrapping_config: bootstrap_configs[0].clone(),
+        da_config,
+        network_config,
+        blend_config,
+        api_config: build_api_configs(std::slice::from_ref(host), ip_stack).remove(0),
+        tracing_config: update_tracing_identifier(
+            tracing_settings.clone(),
+            host.identifier.clone(),
+        ),
+        time_config: default_time_config(),
+        kms_config,
+    }
+}
+
+fn bind_to_host(
+    host: &Host,
+    ip_stack: IpStack,
+    da_config: &mut da::GeneralDaConfig,
+    network_config: &mut network::GeneralNetworkConfig,
+    blend_config: &mut blend::GeneralBlendConfig,
+) {
+    // DA Libp2p network config.
+    let listen_ip = ip_stack.unspecified_for(host.ip);
+    da_config.listening_address = quic_multiaddr(listen_ip, host.da_network_port);
+    if matches!(host.kind, HostKind::Validator) {
+        da_config.policy_settings.min_dispersal_peers = 0;
+    }
+
+    // Libp2p network config.
+    // The swarm host is IPv4-typed upstream; it always binds the IPv4
+    // wildcard and is advertised at the host's IPv4 address.
+    network_config.backend.swarm.host = Ipv4Addr::UNSPECIFIED;
+    network_config.backend.swarm.port = host.network_port;
+    network_config.backend.swarm.nat_config = nomos_libp2p::NatSettings::Static {
+        external_address: quic_multiaddr(host.swarm_ip, host.network_port),
+    };
+
+    // Blend network config.
+    blend_config.backend_core.listening_address = quic_multiaddr(listen_ip, host.blend_port);
+}
+
 fn generate_ids(count: usize, ids: Option<Vec<[u8; 32]>>) -> Vec<[u8; 32]> {
     ids.unwrap_or_else(|| {
         let mut generated = vec![[0; 32]; count];
@@ -174,11 +271,7 @@ fn generate_ids(count: usize, ids: Option<Vec<[u8; 32]>>) -> Vec<[u8; 32]> {
 }
 
 fn resolve_da_ports(count: usize, da_ports: Option<Vec<u16>>) -> Vec<u16> {
-    da_ports.unwrap_or_else(|| {
-        (0..count)
-            .map(|_| get_available_udp_port().unwrap())
-            .collect()
-    })
+    da_ports.unwrap_or_else(|| (0..count).map(|_| allocate_udp_port()).collect())
 }
 
 fn resolve_blend_ports(hosts: &[Host], blend_ports: Option<Vec<u16>>) -> Vec<u16> {
@@ -192,24 +285,32 @@ fn build_base_configs(
     ids: &[[u8; 32]],
     da_ports: &[u16],
     blend_ports: &[u16],
+    ip_stack: IpStack,
 ) -> BaseConfigs {
     BaseConfigs {
         consensus_configs: create_consensus_configs(ids, consensus_params, wallet_config),
         bootstrap_configs: create_bootstrap_configs(ids, SHORT_PROLONGED_BOOTSTRAP_PERIOD),
-        da_configs: create_da_configs(ids, da_params, da_ports),
-        network_configs: create_network_configs(ids, &NetworkParams::default()),
-        blend_configs: create_blend_configs(ids, blend_ports),
+        da_configs: create_da_configs(ids, da_params, da_ports, ip_stack),
+        network_configs: create_network_configs(
+            ids,
+            &NetworkParams {
+                ip_stack,
+                ..NetworkParams::default()
+            },
+        ),
+        blend_configs: create_blend_configs(ids, blend_ports, ip_stack),
     }
 }
 
-fn build_api_configs(hosts: &[Host]) -> Vec<GeneralApiConfig> {
+fn build_api_configs(hosts: &[Host], ip_stack: IpStack) -> Vec<GeneralApiConfig> {
     hosts
         .iter()
-        .map(|host| GeneralApiConfig {
-            address: format!("0.0.0.0:{}", host.api_port).parse().unwrap(),
-            testing_http_address: format!("0.0.0.0:{}", host.testing_http_port)
-                .parse()
-                .unwrap(),
+        .map(|host| {
+            let listen_ip = ip_stack.unspecified_for(host.ip);
+            GeneralApiConfig {
+                address: SocketAddr::new(listen_ip, host.api_port),
+                testing_http_address: SocketAddr::new(listen_ip, host.testing_http_port),
+            }
         })
         .collect::<Vec<_>>()
 }
@@ -1,11 +1,11 @@
-use std::str::FromStr;
-
 use nomos_core::sdp::{Locator, ServiceType};
-use nomos_libp2p::Multiaddr;
-use testing_framework_config::topology::configs::{
-    blend::GeneralBlendConfig,
-    consensus::{GeneralConsensusConfig, ProviderInfo},
-    da::GeneralDaConfig,
+use testing_framework_config::{
+    quic_multiaddr,
+    topology::configs::{
+        blend::GeneralBlendConfig,
+        consensus::{GeneralConsensusConfig, ProviderInfo},
+        da::GeneralDaConfig,
+    },
 };
 
 use crate::host::Host;
@@ -23,31 +23,22 @@ pub fn create_providers(
             service_type: ServiceType::DataAvailability,
             provider_sk: da_conf.signer.clone(),
             zk_sk: da_conf.secret_zk_key.clone(),
-            locator: Locator(
-                Multiaddr::from_str(&format!(
-                    "/ip4/{}/udp/{}/quic-v1",
-                    hosts[i].ip, hosts[i].da_network_port
-                ))
-                .unwrap(),
-            ),
+            locator: Locator(quic_multiaddr(hosts[i].ip, hosts[i].da_network_port)),
             note: consensus_configs[0].da_notes[i].clone(),
         })
         .collect();
-    providers.extend(blend_configs.iter().enumerate().map(|(i, blend_conf)| {
-        ProviderInfo {
-            service_type: ServiceType::BlendNetwork,
-            provider_sk: blend_conf.signer.clone(),
-            zk_sk: blend_conf.secret_zk_key.clone(),
-            locator: Locator(
-                Multiaddr::from_str(&format!(
-                    "/ip4/{}/udp/{}/quic-v1",
-                    hosts[i].ip, hosts[i].blend_port
-                ))
-                .unwrap(),
-            ),
-            note: consensus_configs[0].blend_notes[i].clone(),
-        }
-    }));
+    providers.extend(
+        blend_configs
+            .iter()
+            .enumerate()
+            .map(|(i, blend_conf)| ProviderInfo {
+                service_type: ServiceType::BlendNetwork,
+                provider_sk: blend_conf.signer.clone(),
+                zk_sk: blend_conf.secret_zk_key.clone(),
+                locator: Locator(quic_multiaddr(hosts[i].ip, hosts[i].blend_port)),
+                note: consensus_configs[0].blend_notes[i].clone(),
+            }),
+    );
 
     providers
 }
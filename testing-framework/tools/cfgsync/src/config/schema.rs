@@ -0,0 +1,361 @@
+use std::{
+    collections::HashSet,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use thiserror::Error;
+
+use crate::server::CfgSyncConfig;
+
+/// What is wrong with a cfgsync config entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A key the config schema does not know.
+    UnknownField,
+    /// A required key is absent.
+    MissingField,
+    /// A value has the wrong type or is out of range on its own.
+    InvalidValue,
+    /// A value contradicts other values of the config.
+    Inconsistent,
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::UnknownField => "unknown field",
+            Self::MissingField => "missing field",
+            Self::InvalidValue => "invalid value",
+            Self::Inconsistent => "inconsistent value",
+        })
+    }
+}
+
+/// A single problem found in a cfgsync config, located by the dotted path of
+/// the offending key (`.` for the document root).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub path: String,
+    pub kind: DiagnosticKind,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(path: impl Into<String>, kind: DiagnosticKind, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.path, self.kind, self.message)
+    }
+}
+
+/// Failures loading or validating a cfgsync config.
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("failed to read cfgsync config {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+    #[error("invalid cfgsync config:\n{}", format_diagnostics(.0))]
+    Invalid(Vec<Diagnostic>),
+}
+
+impl SchemaError {
+    /// Problems found in the config; empty when it could not be read.
+    #[must_use]
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            Self::Read { .. } => &[],
+            Self::Invalid(diagnostics) => diagnostics,
+        }
+    }
+}
+
+fn format_diagnostics(diagnostics: &[Diagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|diagnostic| format!("- {diagnostic}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Read and validate the cfgsync config at `path`.
+pub fn validate_file(path: &Path) -> Result<CfgSyncConfig, SchemaError> {
+    let yaml = fs::read_to_string(path).map_err(|source| SchemaError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    validate_str(&yaml)
+}
+
+/// Parse `yaml` as a cfgsync config and check it for consistency.
+///
+/// Parsing stops at the first unknown, missing or mistyped field; a config
+/// that parses is then checked as a whole and every inconsistency is
+/// reported. Unknown keys are rejected at the top level and in `wallet` and
+/// `genesis`; `replication_settings` and `tracing_settings` are node types
+/// that ignore keys they do not know.
+pub fn validate_str(yaml: &str) -> Result<CfgSyncConfig, SchemaError> {
+    let config = parse(yaml).map_err(|diagnostic| SchemaError::Invalid(vec![diagnostic]))?;
+    let diagnostics = check_consistency(&config);
+    if diagnostics.is_empty() {
+        Ok(config)
+    } else {
+        Err(SchemaError::Invalid(diagnostics))
+    }
+}
+
+fn parse(yaml: &str) -> Result<CfgSyncConfig, Diagnostic> {
+    let deserializer = serde_yaml::Deserializer::from_str(yaml);
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let path = err.path().to_string();
+        let message = err.inner().to_string();
+        // serde only exposes the error kind through its message.
+        if let Some(field) = quoted_field(&message, "missing field `") {
+            let path = if path == "." {
+                field.to_owned()
+            } else {
+                format!("{path}.{field}")
+            };
+            Diagnostic::new(path, DiagnosticKind::MissingField, message)
+        } else if message.starts_with("unknown field `") {
+            Diagnostic::new(path, DiagnosticKind::UnknownField, message)
+        } else {
+            Diagnostic::new(path, DiagnosticKind::InvalidValue, message)
+        }
+    })
+}
+
+fn quoted_field<'a>(message: &'a str, prefix: &str) -> Option<&'a str> {
+    let rest = message.split_once(prefix)?.1;
+    rest.split_once('`').map(|(field, _)| field)
+}
+
+/// Every inconsistency between the values of a parsed config.
+#[must_use]
+pub fn check_consistency(config: &CfgSyncConfig) -> Vec<Diagnostic> {
+    use DiagnosticKind::{Inconsistent, InvalidValue};
+
+    let mut diagnostics = Vec::new();
+    let hosts = config.n_hosts;
+
+    if hosts == 0 {
+        diagnostics.push(Diagnostic::new(
+            "n_hosts",
+            InvalidValue,
+            "must be at least 1",
+        ));
+    }
+    if config.timeout == 0 {
+        diagnostics.push(Diagnostic::new(
+            "timeout",
+            InvalidValue,
+            "must be at least 1 second",
+        ));
+    }
+    if !(config.active_slot_coeff > 0.0 && config.active_slot_coeff <= 1.0) {
+        diagnostics.push(Diagnostic::new(
+            "active_slot_coeff",
+            InvalidValue,
+            format!("{} is not within (0, 1]", config.active_slot_coeff),
+        ));
+    }
+    if config.session_duration == 0 {
+        diagnostics.push(Diagnostic::new(
+            "session_duration",
+            InvalidValue,
+            "must be at least 1 block",
+        ));
+    }
+
+    if let Some(expected) = &config.expected_hosts {
+        if expected.len() != hosts {
+            diagnostics.push(Diagnostic::new(
+                "expected_hosts",
+                Inconsistent,
+                format!("lists {} hosts but n_hosts is {hosts}", expected.len()),
+            ));
+        }
+        let mut seen = HashSet::new();
+        for host in expected.iter().filter(|host| !seen.insert(*host)) {
+            diagnostics.push(Diagnostic::new(
+                "expected_hosts",
+                Inconsistent,
+                format!("`{host}` is listed more than once"),
+            ));
+        }
+    }
+    for (path, len) in [
+        ("ids", config.ids.as_ref().map(Vec::len)),
+        ("da_ports", config.da_ports.as_ref().map(Vec::len)),
+        ("blend_ports", config.blend_ports.as_ref().map(Vec::len)),
+    ] {
+        if let Some(len) = len.filter(|len| *len != hosts) {
+            diagnostics.push(Diagnostic::new(
+                path,
+                Inconsistent,
+                format!("has {len} entries but n_hosts is {hosts}"),
+            ));
+        }
+    }
+    if let Some(pointer) = &config.metrics_address {
+        if !pointer.starts_with('/') {
+            diagnostics.push(Diagnostic::new(
+                "metrics_address",
+                InvalidValue,
+                format!("`{pointer}` is not a JSON pointer"),
+            ));
+        }
+    }
+
+    if config.dispersal_factor == 0 {
+        diagnostics.push(Diagnostic::new(
+            "dispersal_factor",
+            InvalidValue,
+            "must be at least 1",
+        ));
+    } else if hosts > 0 && config.dispersal_factor > hosts {
+        diagnostics.push(Diagnostic::new(
+            "dispersal_factor",
+            Inconsistent,
+            format!(
+                "{} exceeds n_hosts {hosts}; subnetworks cannot have more members than there \
+                 are nodes",
+                config.dispersal_factor
+            ),
+        ));
+    }
+    if config.subnetwork_size == 0 {
+        diagnostics.push(Diagnostic::new(
+            "subnetwork_size",
+            InvalidValue,
+            "must be at least 1",
+        ));
+    }
+    if config.num_subnets == 0 {
+        diagnostics.push(Diagnostic::new(
+            "num_subnets",
+            InvalidValue,
+            "must be at least 1",
+        ));
+    }
+    if config.num_samples == 0 {
+        diagnostics.push(Diagnostic::new(
+            "num_samples",
+            InvalidValue,
+            "must be at least 1",
+        ));
+    } else if config.num_samples > config.num_subnets {
+        diagnostics.push(Diagnostic::new(
+            "num_samples",
+            Inconsistent,
+            format!(
+                "{} exceeds num_subnets {}; nodes cannot sample more subnets than exist",
+                config.num_samples, config.num_subnets
+            ),
+        ));
+    }
+    if config.global_params_path.trim().is_empty() {
+        diagnostics.push(Diagnostic::new(
+            "global_params_path",
+            InvalidValue,
+            "must not be empty",
+        ));
+    }
+
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = include_str!("../../../../assets/stack/cfgsync.yaml");
+
+    /// The stack template with the wallet the runners render into it.
+    fn valid_yaml() -> String {
+        format!("{TEMPLATE}\nwallet:\n  accounts: []\n")
+    }
+
+    fn single(yaml: &str) -> Diagnostic {
+        let err = validate_str(yaml).expect_err("config is invalid");
+        let [diagnostic] = err.diagnostics() else {
+            panic!("expected one diagnostic, got {err}");
+        };
+        diagnostic.clone()
+    }
+
+    #[test]
+    fn stack_template_is_valid() {
+        let config = validate_str(&valid_yaml()).expect("template validates");
+        assert_eq!(config.n_hosts, 4);
+    }
+
+    #[test]
+    fn parse_errors_are_classified() {
+        let missing = single(&valid_yaml().replace("timeout: 10\n", ""));
+        assert_eq!(missing.kind, DiagnosticKind::MissingField);
+        assert_eq!(missing.path, "timeout");
+
+        let unknown = single(&format!("{}n_nodes: 4\n", valid_yaml()));
+        assert_eq!(unknown.kind, DiagnosticKind::UnknownField);
+        assert_eq!(unknown.path, ".");
+        assert!(unknown.message.contains("n_nodes"), "{unknown}");
+
+        let nested = single(&format!("{}  funded: true\n", valid_yaml()));
+        assert_eq!(nested.kind, DiagnosticKind::UnknownField);
+        assert_eq!(nested.path, "wallet");
+
+        let mistyped = single(&valid_yaml().replace("n_hosts: 4", "n_hosts: four"));
+        assert_eq!(mistyped.kind, DiagnosticKind::InvalidValue);
+        assert_eq!(mistyped.path, "n_hosts");
+    }
+
+    #[test]
+    fn every_inconsistency_is_reported() {
+        let yaml = valid_yaml()
+            .replace("dispersal_factor: 2", "dispersal_factor: 5")
+            .replace("num_samples: 1", "num_samples: 3")
+            .replace("timeout: 10", "timeout: 0");
+        let yaml = format!("{yaml}expected_hosts: [validator-0, validator-0]\n");
+
+        let err = validate_str(&yaml).expect_err("config is inconsistent");
+        let paths = err
+            .diagnostics()
+            .iter()
+            .map(|diagnostic| (diagnostic.path.as_str(), diagnostic.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            paths,
+            vec![
+                ("timeout", DiagnosticKind::InvalidValue),
+                ("expected_hosts", DiagnosticKind::Inconsistent),
+                ("expected_hosts", DiagnosticKind::Inconsistent),
+                ("dispersal_factor", DiagnosticKind::Inconsistent),
+                ("num_samples", DiagnosticKind::Inconsistent),
+            ]
+        );
+        assert!(
+            err.to_string()
+                .contains("- dispersal_factor: inconsistent value: 5 exceeds n_hosts 4")
+        );
+    }
+
+    #[test]
+    fn unreadable_file_has_no_diagnostics() {
+        let err =
+            validate_file(Path::new("/nonexistent/cfgsync.yaml")).expect_err("file does not exist");
+        assert!(matches!(err, SchemaError::Read { .. }));
+        assert!(err.diagnostics().is_empty());
+    }
+}
@@ -1,4 +1,4 @@
-use std::net::Ipv4Addr;
+use std::{collections::BTreeMap, net::IpAddr};
 
 pub const DEFAULT_LIBP2P_NETWORK_PORT: u16 = 3000;
 pub const DEFAULT_DA_NETWORK_PORT: u16 = 3300;
@@ -14,47 +14,68 @@ pub enum HostKind {
 #[derive(Eq, PartialEq, Hash, Clone)]
 pub struct Host {
     pub kind: HostKind,
-    pub ip: Ipv4Addr,
+    pub ip: IpAddr,
+    /// Address the libp2p swarm is dialed at. The swarm only listens on
+    /// IPv4, so IPv6 hosts announce their IPv4 address here.
+    pub swarm_ip: IpAddr,
     pub identifier: String,
     pub network_port: u16,
     pub da_network_port: u16,
     pub blend_port: u16,
     pub api_port: u16,
     pub testing_http_port: u16,
+    pub metrics_port: Option<u16>,
+    pub listener_ports: BTreeMap<String, u16>,
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Default)]
 pub struct PortOverrides {
     pub network_port: Option<u16>,
     pub da_network_port: Option<u16>,
     pub blend_port: Option<u16>,
     pub api_port: Option<u16>,
     pub testing_http_port: Option<u16>,
+    /// Port of the node's metrics listener, written to the config address
+    /// named by the server's `metrics_address` pointer.
+    pub metrics_port: Option<u16>,
+    /// Auxiliary listener ports keyed by the JSON pointer of their socket
+    /// address in the node config, e.g. `/http/backend_settings/address`.
+    pub listener_ports: BTreeMap<String, u16>,
 }
 
 impl Host {
-    fn from_parts(kind: HostKind, ip: Ipv4Addr, identifier: String, ports: PortOverrides) -> Self {
+    fn from_parts(kind: HostKind, ip: IpAddr, identifier: String, ports: PortOverrides) -> Self {
         Self {
             kind,
             ip,
+            swarm_ip: ip,
             identifier,
             network_port: ports.network_port.unwrap_or(DEFAULT_LIBP2P_NETWORK_PORT),
             da_network_port: ports.da_network_port.unwrap_or(DEFAULT_DA_NETWORK_PORT),
             blend_port: ports.blend_port.unwrap_or(DEFAULT_BLEND_PORT),
             api_port: ports.api_port.unwrap_or(DEFAULT_API_PORT),
             testing_http_port: ports.testing_http_port.unwrap_or(DEFAULT_API_PORT + 1),
+            metrics_port: ports.metrics_port,
+            listener_ports: ports.listener_ports,
         }
     }
 
     #[must_use]
-    pub fn validator_from_ip(ip: Ipv4Addr, identifier: String, ports: PortOverrides) -> Self {
+    pub fn validator_from_ip(ip: IpAddr, identifier: String, ports: PortOverrides) -> Self {
         Self::from_parts(HostKind::Validator, ip, identifier, ports)
     }
 
     #[must_use]
-    pub fn executor_from_ip(ip: Ipv4Addr, identifier: String, ports: PortOverrides) -> Self {
+    pub fn executor_from_ip(ip: IpAddr, identifier: String, ports: PortOverrides) -> Self {
         Self::from_parts(HostKind::Executor, ip, identifier, ports)
     }
+
+    #[must_use]
+    /// Dial the swarm at `ip` instead of the host IP.
+    pub const fn with_swarm_ip(mut self, ip: IpAddr) -> Self {
+        self.swarm_ip = ip;
+        self
+    }
 }
 
 #[must_use]
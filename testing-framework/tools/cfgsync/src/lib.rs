@@ -4,9 +4,11 @@ pub mod config {
     pub mod builder;
     pub mod kms;
     pub mod providers;
+    pub mod schema;
     pub mod tracing;
     pub mod validation;
 }
 pub mod network;
 pub mod repo;
 pub mod server;
+pub mod status;
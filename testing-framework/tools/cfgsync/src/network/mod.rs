@@ -1,4 +1,4 @@
 pub mod address;
 pub mod peers;
 
-pub use peers::rewrite_initial_peers;
+pub use peers::{peer_address, rewrite_initial_peers};
@@ -1,6 +1,5 @@
-use std::str::FromStr;
-
-use nomos_libp2p::{Multiaddr, PeerId};
+use nomos_libp2p::{Multiaddr, PeerId, Protocol, ed25519};
+use testing_framework_config::{quic_multiaddr, topology::configs::GeneralConfig};
 
 use super::address::find_matching_host;
 use crate::host::Host;
@@ -15,18 +14,26 @@ pub fn rewrite_initial_peers(
         .iter()
         .enumerate()
         .map(|(node_idx, peers)| {
-            peers
+            let peer_indices: Vec<usize> = peers
                 .iter()
                 .filter_map(|addr| find_matching_host(addr, original_ports))
                 .filter(|&peer_idx| peer_idx != node_idx)
+                .collect();
+            peer_indices
+                .into_iter()
                 .map(|peer_idx| {
-                    Multiaddr::from_str(&format!(
-                        "/ip4/{}/udp/{}/quic-v1/p2p/{}",
-                        hosts[peer_idx].ip, hosts[peer_idx].network_port, peer_ids[peer_idx]
-                    ))
-                    .expect("valid peer multiaddr")
+                    quic_multiaddr(hosts[peer_idx].swarm_ip, hosts[peer_idx].network_port)
+                        .with(Protocol::P2p(peer_ids[peer_idx]))
                 })
                 .collect()
         })
         .collect()
 }
+
+/// Dialable address of a host that already received `config`.
+#[must_use]
+pub fn peer_address(host: &Host, config: &GeneralConfig) -> Multiaddr {
+    let secret = config.network_config.backend.swarm.node_key.clone();
+    let peer_id = PeerId::from_public_key(&ed25519::Keypair::from(secret).public().into());
+    quic_multiaddr(host.swarm_ip, host.network_port).with(Protocol::P2p(peer_id))
+}
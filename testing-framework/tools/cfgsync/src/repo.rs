@@ -1,16 +1,23 @@
 use std::{
     collections::HashMap,
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
+use nomos_libp2p::Multiaddr;
 use nomos_tracing_service::TracingSettings;
 use testing_framework_config::topology::configs::{
-    GeneralConfig, consensus::ConsensusParams, da::DaParams, wallet::WalletConfig,
+    GeneralConfig, consensus::ConsensusParams, da::DaParams, network::IpStack, wallet::WalletConfig,
 };
 use tokio::{sync::oneshot::Sender, time::timeout};
 
-use crate::{config::builder::create_node_configs, host::Host, server::CfgSyncConfig};
+use crate::{
+    config::builder::{create_late_node_config, create_node_configs},
+    host::Host,
+    network::peer_address,
+    server::CfgSyncConfig,
+    status::{RegisteredHost, SyncPhase, SyncStatus},
+};
 
 pub enum RepoResponse {
     Config(Box<GeneralConfig>),
@@ -19,7 +26,16 @@ pub enum RepoResponse {
 
 pub struct ConfigRepo {
     waiting_hosts: Mutex<HashMap<Host, Sender<RepoResponse>>>,
+    late_hosts: Mutex<Option<LateHosts>>,
+    /// Keep serving configs once the initial round completes: hosts that
+    /// register later get one minted by [`create_late_node_config`], hosts
+    /// that register again get the config they were given before.
+    accept_late_hosts: bool,
+    registered_hosts: Mutex<Vec<RegisteredHost>>,
+    phase: Mutex<SyncPhase>,
+    started: Instant,
     n_hosts: usize,
+    expected_hosts: Option<Vec<String>>,
     consensus_params: ConsensusParams,
     da_params: DaParams,
     tracing_settings: TracingSettings,
@@ -28,6 +44,8 @@ pub struct ConfigRepo {
     ids: Option<Vec<[u8; 32]>>,
     da_ports: Option<Vec<u16>>,
     blend_ports: Option<Vec<u16>>,
+    ip_stack: IpStack,
+    metrics_address: Option<String>,
 }
 
 impl From<CfgSyncConfig> for Arc<ConfigRepo> {
@@ -39,9 +57,13 @@ impl From<CfgSyncConfig> for Arc<ConfigRepo> {
         let ids = config.ids;
         let da_ports = config.da_ports;
         let blend_ports = config.blend_ports;
+        let ip_stack = config.ip_stack;
+        let accept_late_hosts = config.accept_late_hosts;
+        let metrics_address = config.metrics_address;
 
         ConfigRepo::new(
             config.n_hosts,
+            config.expected_hosts,
             consensus_params,
             da_params,
             tracing_settings,
@@ -49,15 +71,27 @@ impl From<CfgSyncConfig> for Arc<ConfigRepo> {
             ids,
             da_ports,
             blend_ports,
+            ip_stack,
+            metrics_address,
+            accept_late_hosts,
             Duration::from_secs(config.timeout),
         )
     }
 }
 
+/// Configs handed out so far, kept once the initial round completes so that
+/// hosts registering later can be served.
+struct LateHosts {
+    template: GeneralConfig,
+    configs: HashMap<String, GeneralConfig>,
+    peers: Vec<Multiaddr>,
+}
+
 impl ConfigRepo {
     #[must_use]
     pub fn new(
         n_hosts: usize,
+        expected_hosts: Option<Vec<String>>,
         consensus_params: ConsensusParams,
         da_params: DaParams,
         tracing_settings: TracingSettings,
@@ -65,11 +99,20 @@ impl ConfigRepo {
         ids: Option<Vec<[u8; 32]>>,
         da_ports: Option<Vec<u16>>,
         blend_ports: Option<Vec<u16>>,
+        ip_stack: IpStack,
+        metrics_address: Option<String>,
+        accept_late_hosts: bool,
         timeout_duration: Duration,
     ) -> Arc<Self> {
         let repo = Arc::new(Self {
             waiting_hosts: Mutex::new(HashMap::new()),
+            late_hosts: Mutex::new(None),
+            accept_late_hosts,
+            registered_hosts: Mutex::new(Vec::new()),
+            phase: Mutex::new(SyncPhase::Waiting),
+            started: Instant::now(),
             n_hosts,
+            expected_hosts,
             consensus_params,
             da_params,
             tracing_settings,
@@ -77,6 +120,8 @@ impl ConfigRepo {
             ids,
             da_ports,
             blend_ports,
+            ip_stack,
+            metrics_address,
             timeout_duration,
         });
 
@@ -88,17 +133,100 @@ impl ConfigRepo {
         repo
     }
 
+    /// IP families the generated configs listen on.
+    #[must_use]
+    pub const fn ip_stack(&self) -> IpStack {
+        self.ip_stack
+    }
+
+    /// JSON pointer of the metrics listener address in node configs.
+    #[must_use]
+    pub fn metrics_address(&self) -> Option<&str> {
+        self.metrics_address.as_deref()
+    }
+
     pub fn register(&self, host: Host, reply_tx: Sender<RepoResponse>) {
+        let mut registered_hosts = self.registered_hosts.lock().unwrap();
+        registered_hosts.retain(|registered| registered.identifier != host.identifier);
+        registered_hosts.push(RegisteredHost::from(&host));
+        drop(registered_hosts);
+
+        // Held until the host is queued so the initial round cannot complete
+        // in between and leave it waiting forever.
+        let mut late_hosts = self.late_hosts.lock().unwrap();
+        if let Some(late_hosts) = late_hosts.as_mut() {
+            let config = self.late_host_config(late_hosts, &host);
+            let _ = reply_tx.send(RepoResponse::Config(Box::new(config)));
+            return;
+        }
+
         let mut waiting_hosts = self.waiting_hosts.lock().unwrap();
         waiting_hosts.insert(host, reply_tx);
     }
 
+    fn late_host_config(&self, late_hosts: &mut LateHosts, host: &Host) -> GeneralConfig {
+        if let Some(config) = late_hosts.configs.get(&host.identifier) {
+            println!("Serving previous config to {}", host.identifier);
+            return config.clone();
+        }
+
+        println!("Minting config for late host {}", host.identifier);
+        let config = create_late_node_config(
+            &late_hosts.template,
+            &self.consensus_params,
+            &self.da_params,
+            &self.tracing_settings,
+            &self.wallet_config,
+            self.ip_stack,
+            host,
+            late_hosts.peers.clone(),
+        );
+        late_hosts.peers.push(peer_address(host, &config));
+        late_hosts
+            .configs
+            .insert(host.identifier.clone(), config.clone());
+        config
+    }
+
+    /// Snapshot of the registration round, served on `GET /status`.
+    #[must_use]
+    pub fn status(&self) -> SyncStatus {
+        let registered_hosts = self.registered_hosts.lock().unwrap();
+        let missing = self.expected_hosts.as_ref().map(|expected| {
+            expected
+                .iter()
+                .filter(|identifier| {
+                    !registered_hosts
+                        .iter()
+                        .any(|host| &host.identifier == *identifier)
+                })
+                .cloned()
+                .collect()
+        });
+
+        SyncStatus {
+            phase: *self.phase.lock().unwrap(),
+            expected: self.n_hosts,
+            registered: registered_hosts.len(),
+            missing,
+            timeout_secs: self.timeout_duration.as_secs(),
+            elapsed_secs: self.started.elapsed().as_secs(),
+        }
+    }
+
+    /// Hosts that have announced themselves so far, served on `GET /hosts`.
+    #[must_use]
+    pub fn registered_hosts(&self) -> Vec<RegisteredHost> {
+        self.registered_hosts.lock().unwrap().clone()
+    }
+
     async fn run(&self) {
         let timeout_duration = self.timeout_duration;
 
         if timeout(timeout_duration, self.wait_for_hosts()).await == Ok(()) {
             println!("All hosts have announced their IPs");
 
+            let mut late_hosts = self.late_hosts.lock().unwrap();
             let mut waiting_hosts = self.waiting_hosts.lock().unwrap();
             let hosts = waiting_hosts.keys().cloned().collect();
 
@@ -110,6 +238,7 @@ impl ConfigRepo {
                 self.ids.clone(),
                 self.da_ports.clone(),
                 self.blend_ports.clone(),
+                self.ip_stack,
                 hosts,
             );
 
@@ -117,8 +246,24 @@ impl ConfigRepo {
                 let config = configs.get(&host).expect("host should have a config");
                 let _ = sender.send(RepoResponse::Config(Box::new(config.to_owned())));
             }
+            if self.accept_late_hosts {
+                *late_hosts = Some(LateHosts {
+                    template: configs.values().next().expect("at least one host").clone(),
+                    peers: configs
+                        .iter()
+                        .map(|(host, config)| peer_address(host, config))
+                        .collect(),
+                    configs: configs
+                        .into_iter()
+                        .map(|(host, config)| (host.identifier, config))
+                        .collect(),
+                });
+            }
+            *self.phase.lock().unwrap() = SyncPhase::Completed;
         } else {
             println!("Timeout: Not all hosts announced within the time limit");
+            println!("{}", self.status().summary());
+            *self.phase.lock().unwrap() = SyncPhase::TimedOut;
 
             let mut waiting_hosts = self.waiting_hosts.lock().unwrap();
             for (_, sender) in waiting_hosts.drain() {
@@ -136,3 +281,75 @@ impl ConfigRepo {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use tokio::sync::oneshot::{Receiver, channel};
+
+    use super::*;
+    use crate::{config::schema::validate_str, host::PortOverrides};
+
+    const TEMPLATE: &str = include_str!("../../../assets/stack/cfgsync.yaml");
+
+    fn late_joining_repo() -> Arc<ConfigRepo> {
+        let mut config = validate_str(&format!("{TEMPLATE}\nwallet:\n  accounts: []\n"))
+            .expect("template validates");
+        config.accept_late_hosts = true;
+        config.into()
+    }
+
+    fn host(index: u8) -> Host {
+        Host::validator_from_ip(
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, index)),
+            format!("validator-{index}"),
+            PortOverrides::default(),
+        )
+    }
+
+    fn register(repo: &ConfigRepo, host: Host) -> Receiver<RepoResponse> {
+        let (reply_tx, reply_rx) = channel();
+        repo.register(host, reply_tx);
+        reply_rx
+    }
+
+    async fn served(reply: Receiver<RepoResponse>) -> GeneralConfig {
+        match reply.await.expect("repo replies") {
+            RepoResponse::Config(config) => *config,
+            RepoResponse::Timeout => panic!("registration round timed out"),
+        }
+    }
+
+    #[tokio::test]
+    async fn late_hosts_are_served_after_the_initial_round() {
+        let repo = late_joining_repo();
+        let initial = (1..=4)
+            .map(|index| register(&repo, host(index)))
+            .collect::<Vec<_>>();
+        for reply in initial {
+            served(reply).await;
+        }
+
+        let late = served(register(&repo, host(5))).await;
+        assert_eq!(late.network_config.backend.initial_peers.len(), 4);
+
+        let again = served(register(&repo, host(5))).await;
+        assert_eq!(
+            again.network_config.backend.initial_peers,
+            late.network_config.backend.initial_peers
+        );
+        assert_eq!(again.api_config.address, late.api_config.address);
+
+        let next = served(register(&repo, host(6))).await;
+        assert_eq!(
+            next.network_config.backend.initial_peers.len(),
+            5,
+            "later joiners also peer with earlier ones"
+        );
+
+        let status = repo.status();
+        assert_eq!(status.phase, SyncPhase::Completed);
+        assert_eq!(status.registered, 6);
+    }
+}
@@ -1,6 +1,19 @@
-use std::{fs, net::Ipv4Addr, num::NonZero, path::PathBuf, sync::Arc, time::Duration};
+use std::{
+    collections::BTreeMap,
+    net::{IpAddr, SocketAddr},
+    num::NonZero,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
 
-use axum::{Json, Router, extract::State, http::StatusCode, response::IntoResponse, routing::post};
+use axum::{
+    Json, Router,
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+};
 use nomos_da_network_core::swarm::{
     DAConnectionMonitorSettings, DAConnectionPolicySettings, ReplicationConfig,
 };
@@ -12,32 +25,64 @@ use serde_with::serde_as;
 use subnetworks_assignations::MembershipHandler;
 use testing_framework_config::{
     nodes::{executor::create_executor_config, validator::create_validator_config},
-    topology::configs::{consensus::ConsensusParams, da::DaParams, wallet::WalletConfig},
+    topology::configs::{
+        consensus::{self, ConsensusParams},
+        da::DaParams,
+        genesis::GenesisCustomization,
+        network::IpStack,
+        wallet::WalletConfig,
+    },
 };
 use tokio::sync::oneshot::channel;
+use tracing::warn;
 
 use crate::{
+    config::schema::{self, SchemaError},
     host::{Host, PortOverrides},
     repo::{ConfigRepo, RepoResponse},
+    status::{RegisteredHost, SyncStatus},
 };
 
 #[serde_as]
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CfgSyncConfig {
     pub port: u16,
     pub n_hosts: usize,
+    /// Identifiers the registering hosts are expected to use (e.g.
+    /// `validator-0`), reported as missing on `GET /status` until they
+    /// register.
+    #[serde(default)]
+    pub expected_hosts: Option<Vec<String>>,
     pub timeout: u64,
+    /// Serve configs to hosts registering after the initial `n_hosts` round,
+    /// e.g. nodes added to a running cluster.
+    #[serde(default)]
+    pub accept_late_hosts: bool,
 
     // ConsensusConfig related parameters
     pub security_param: NonZero<u32>,
     pub active_slot_coeff: f64,
+    /// Blocks per SDP session of the DA and blend services.
+    #[serde(default = "default_session_duration")]
+    pub session_duration: u64,
     pub wallet: WalletConfig,
+    /// Extra genesis notes, declarations and inscriptions.
+    #[serde(default)]
+    pub genesis: GenesisCustomization,
     #[serde(default)]
     pub ids: Option<Vec<[u8; 32]>>,
     #[serde(default)]
     pub da_ports: Option<Vec<u16>>,
     #[serde(default)]
     pub blend_ports: Option<Vec<u16>>,
+    #[serde(default)]
+    pub ip_stack: IpStack,
+    /// JSON pointer of the metrics listener's socket address in node configs
+    /// (e.g. `/tracing/metrics/address`); hosts registering a metrics port
+    /// get it written there.
+    #[serde(default)]
+    pub metrics_address: Option<String>,
 
     // DaConfig related parameters
     pub subnetwork_size: usize,
@@ -58,25 +103,34 @@ pub struct CfgSyncConfig {
     pub replication_settings: ReplicationConfig,
     pub retry_shares_limit: usize,
     pub retry_commitments_limit: usize,
+    /// Dispersal mempool publish strategy of older configs. Nodes no longer
+    /// read it; it is accepted so those configs keep loading.
+    #[serde(default, rename = "mempool_publish_strategy")]
+    _mempool_publish_strategy: Option<serde::de::IgnoredAny>,
 
     // Tracing params
     pub tracing_settings: TracingSettings,
 }
 
+const fn default_session_duration() -> u64 {
+    consensus::DEFAULT_SESSION_DURATION
+}
+
 impl CfgSyncConfig {
-    pub fn load_from_file(file_path: &PathBuf) -> Result<Self, String> {
-        let config_content = fs::read_to_string(file_path)
-            .map_err(|err| format!("Failed to read config file: {err}"))?;
-        serde_yaml::from_str(&config_content)
-            .map_err(|err| format!("Failed to parse config file: {err}"))
+    /// Load and validate the config at `file_path`; see
+    /// [`schema::validate_file`].
+    pub fn load_from_file(file_path: &Path) -> Result<Self, SchemaError> {
+        schema::validate_file(file_path)
     }
 
     #[must_use]
-    pub const fn to_consensus_params(&self) -> ConsensusParams {
+    pub fn to_consensus_params(&self) -> ConsensusParams {
         ConsensusParams {
             n_participants: self.n_hosts,
             security_param: self.security_param,
             active_slot_coeff: self.active_slot_coeff,
+            session_duration: self.session_duration,
+            genesis: self.genesis.clone(),
         }
     }
 
@@ -124,7 +178,11 @@ impl CfgSyncConfig {
 
 #[derive(Serialize, Deserialize)]
 pub struct ClientIp {
-    pub ip: Ipv4Addr,
+    pub ip: IpAddr,
+    /// IPv4 address of the host when `ip` is IPv6; the libp2p swarm is
+    /// dialed there.
+    #[serde(default)]
+    pub swarm_ip: Option<IpAddr>,
     pub identifier: String,
     #[serde(default)]
     pub network_port: Option<u16>,
@@ -136,6 +194,12 @@ pub struct ClientIp {
     pub api_port: Option<u16>,
     #[serde(default)]
     pub testing_http_port: Option<u16>,
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+    /// Auxiliary listener ports keyed by the JSON pointer of their address in
+    /// the node config.
+    #[serde(default)]
+    pub listener_ports: BTreeMap<String, u16>,
 }
 
 async fn validator_config(
@@ -144,12 +208,15 @@ async fn validator_config(
 ) -> impl IntoResponse {
     let ClientIp {
         ip,
+        swarm_ip,
         identifier,
         network_port,
         da_port,
         blend_port,
         api_port,
         testing_http_port,
+        metrics_port,
+        listener_ports,
     } = payload;
     let ports = PortOverrides {
         network_port,
@@ -157,10 +224,17 @@ async fn validator_config(
         blend_port,
         api_port,
         testing_http_port,
+        metrics_port,
+        listener_ports,
     };
 
+    let listen_ip = config_repo.ip_stack().unspecified_for(ip);
     let (reply_tx, reply_rx) = channel();
-    config_repo.register(Host::validator_from_ip(ip, identifier, ports), reply_tx);
+    let mut host = Host::validator_from_ip(ip, identifier, ports.clone());
+    if let Some(swarm_ip) = swarm_ip {
+        host = host.with_swarm_ip(swarm_ip);
+    }
+    config_repo.register(host, reply_tx);
 
     (reply_rx.await).map_or_else(
         |_| (StatusCode::INTERNAL_SERVER_ERROR, "Error receiving config").into_response(),
@@ -170,7 +244,12 @@ async fn validator_config(
                 let mut value =
                     serde_json::to_value(&config).expect("validator config should serialize");
                 inject_defaults(&mut value);
-                override_api_ports(&mut value, &ports);
+                override_listener_ports(
+                    &mut value,
+                    &ports,
+                    listen_ip,
+                    config_repo.metrics_address(),
+                );
                 inject_da_assignations(&mut value, &config.da_network.membership);
                 override_min_session_members(&mut value);
                 (StatusCode::OK, Json(value)).into_response()
@@ -186,12 +265,15 @@ async fn executor_config(
 ) -> impl IntoResponse {
     let ClientIp {
         ip,
+        swarm_ip,
         identifier,
         network_port,
         da_port,
         blend_port,
         api_port,
         testing_http_port,
+        metrics_port,
+        listener_ports,
     } = payload;
     let ports = PortOverrides {
         network_port,
@@ -199,10 +281,17 @@ async fn executor_config(
         blend_port,
         api_port,
         testing_http_port,
+        metrics_port,
+        listener_ports,
     };
 
+    let listen_ip = config_repo.ip_stack().unspecified_for(ip);
     let (reply_tx, reply_rx) = channel();
-    config_repo.register(Host::executor_from_ip(ip, identifier, ports), reply_tx);
+    let mut host = Host::executor_from_ip(ip, identifier, ports.clone());
+    if let Some(swarm_ip) = swarm_ip {
+        host = host.with_swarm_ip(swarm_ip);
+    }
+    config_repo.register(host, reply_tx);
 
     (reply_rx.await).map_or_else(
         |_| (StatusCode::INTERNAL_SERVER_ERROR, "Error receiving config").into_response(),
@@ -212,7 +301,12 @@ async fn executor_config(
                 let mut value =
                     serde_json::to_value(&config).expect("executor config should serialize");
                 inject_defaults(&mut value);
-                override_api_ports(&mut value, &ports);
+                override_listener_ports(
+                    &mut value,
+                    &ports,
+                    listen_ip,
+                    config_repo.metrics_address(),
+                );
                 inject_da_assignations(&mut value, &config.da_network.membership);
                 override_min_session_members(&mut value);
                 (StatusCode::OK, Json(value)).into_response()
@@ -222,23 +316,60 @@ async fn executor_config(
     )
 }
 
+async fn status(State(config_repo): State<Arc<ConfigRepo>>) -> Json<SyncStatus> {
+    Json(config_repo.status())
+}
+
+async fn hosts(State(config_repo): State<Arc<ConfigRepo>>) -> Json<Vec<RegisteredHost>> {
+    Json(config_repo.registered_hosts())
+}
+
 pub fn cfgsync_app(config_repo: Arc<ConfigRepo>) -> Router {
     Router::new()
         .route("/validator", post(validator_config))
         .route("/executor", post(executor_config))
+        .route("/status", get(status))
+        .route("/hosts", get(hosts))
         .with_state(config_repo)
 }
 
-fn override_api_ports(config: &mut serde_json::Value, ports: &PortOverrides) {
-    if let Some(api_port) = ports.api_port {
-        if let Some(address) = config.pointer_mut("/http/backend_settings/address") {
-            *address = json!(format!("0.0.0.0:{api_port}"));
-        }
+fn override_listener_ports(
+    config: &mut serde_json::Value,
+    ports: &PortOverrides,
+    listen_ip: IpAddr,
+    metrics_address: Option<&str>,
+) {
+    if let (None, Some(port)) = (metrics_address, ports.metrics_port) {
+        warn!(
+            port,
+            "host registered a metrics port but cfgsync has no metrics_address pointer; port not applied"
+        );
     }
 
-    if let Some(testing_port) = ports.testing_http_port {
-        if let Some(address) = config.pointer_mut("/testing_http/backend_settings/address") {
-            *address = json!(format!("0.0.0.0:{testing_port}"));
+    let listeners = [
+        ("/http/backend_settings/address", ports.api_port),
+        (
+            "/testing_http/backend_settings/address",
+            ports.testing_http_port,
+        ),
+    ]
+    .into_iter()
+    .chain(metrics_address.map(|pointer| (pointer, ports.metrics_port)))
+    .filter_map(|(pointer, port)| Some((pointer, port?)))
+    .chain(
+        ports
+            .listener_ports
+            .iter()
+            .map(|(pointer, port)| (pointer.as_str(), *port)),
+    );
+
+    for (pointer, port) in listeners {
+        match config.pointer_mut(pointer) {
+            Some(address) => *address = json!(SocketAddr::new(listen_ip, port).to_string()),
+            None => warn!(
+                pointer,
+                port, "node config has no listener address at pointer"
+            ),
         }
     }
 }
@@ -295,3 +426,62 @@ fn inject_defaults(config: &mut serde_json::Value) {
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    fn node_config() -> serde_json::Value {
+        json!({
+            "http": { "backend_settings": { "address": "0.0.0.0:18080" } },
+            "testing_http": { "backend_settings": { "address": "0.0.0.0:18081" } },
+            "tracing": { "metrics": { "address": "0.0.0.0:9000" } },
+        })
+    }
+
+    #[test]
+    fn listener_ports_are_written_to_their_pointers() {
+        let mut config = node_config();
+        let ports = PortOverrides {
+            api_port: Some(8080),
+            metrics_port: Some(9100),
+            listener_ports: BTreeMap::from([(
+                "/testing_http/backend_settings/address".to_owned(),
+                8181,
+            )]),
+            ..PortOverrides::default()
+        };
+
+        override_listener_ports(
+            &mut config,
+            &ports,
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            Some("/tracing/metrics/address"),
+        );
+
+        assert_eq!(
+            config["http"]["backend_settings"]["address"],
+            "0.0.0.0:8080"
+        );
+        assert_eq!(
+            config["testing_http"]["backend_settings"]["address"],
+            "0.0.0.0:8181"
+        );
+        assert_eq!(config["tracing"]["metrics"]["address"], "0.0.0.0:9100");
+    }
+
+    #[test]
+    fn metrics_port_needs_a_metrics_address_pointer() {
+        let mut config = node_config();
+        let ports = PortOverrides {
+            metrics_port: Some(9100),
+            ..PortOverrides::default()
+        };
+
+        override_listener_ports(&mut config, &ports, IpAddr::V4(Ipv4Addr::UNSPECIFIED), None);
+
+        assert_eq!(config, node_config());
+    }
+}
@@ -0,0 +1,73 @@
+use std::{collections::BTreeMap, net::IpAddr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::host::{Host, HostKind};
+
+/// Progress of the registration round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    /// Still waiting for hosts to announce themselves.
+    Waiting,
+    /// Every host registered and received its config.
+    Completed,
+    /// The timeout expired before every host registered.
+    TimedOut,
+}
+
+/// Response of `GET /status`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncStatus {
+    pub phase: SyncPhase,
+    pub expected: usize,
+    pub registered: usize,
+    /// Identifiers that have not registered yet. `None` when the server was
+    /// not told which identifiers to expect.
+    pub missing: Option<Vec<String>>,
+    pub timeout_secs: u64,
+    pub elapsed_secs: u64,
+}
+
+impl SyncStatus {
+    /// One-line summary suitable for error messages.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "cfgsync {:?}: {}/{} hosts registered after {}s (timeout {}s)",
+            self.phase, self.registered, self.expected, self.elapsed_secs, self.timeout_secs
+        );
+        if let Some(missing) = self.missing.as_ref().filter(|missing| !missing.is_empty()) {
+            summary.push_str(&format!("; missing: {}", missing.join(", ")));
+        }
+        summary
+    }
+}
+
+/// Entry of `GET /hosts`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RegisteredHost {
+    pub identifier: String,
+    pub kind: String,
+    pub ip: IpAddr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics_port: Option<u16>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub listener_ports: BTreeMap<String, u16>,
+}
+
+impl From<&Host> for RegisteredHost {
+    fn from(host: &Host) -> Self {
+        let kind = match host.kind {
+            HostKind::Validator => "validator",
+            HostKind::Executor => "executor",
+        };
+        Self {
+            identifier: host.identifier.clone(),
+            kind: kind.to_owned(),
+            ip: host.ip,
+            metrics_port: host.metrics_port,
+            listener_ports: host.listener_ports.clone(),
+        }
+    }
+}
@@ -0,0 +1,555 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{
+        ComponentInfo, ComponentRegistry, DynError, NodeEventKind, NodeTarget, RunContext,
+        RunReport, Runner,
+    },
+    topology::generation::GeneratedTopology,
+};
+use testing_framework_workflows::builtin_registry;
+use thiserror::Error;
+use tokio::{sync::Notify, task::AbortHandle};
+use tracing::{info, warn};
+
+/// Failures returned to API callers.
+#[derive(Debug, Error)]
+pub enum ApiError {
+    #[error("invalid workload request: {0}")]
+    InvalidWorkload(String),
+    #[error("workload {0} not found")]
+    UnknownWorkload(u64),
+    #[error("the deployed runner does not support node control")]
+    NodeControlUnavailable,
+    #[error("{target} is not part of the topology")]
+    UnknownNode { target: NodeTarget },
+    #[error("chaos action on {target} failed: {source}")]
+    Chaos {
+        target: NodeTarget,
+        #[source]
+        source: DynError,
+    },
+    #[error("cluster is shutting down")]
+    ShuttingDown,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            Self::InvalidWorkload(_) => StatusCode::BAD_REQUEST,
+            Self::UnknownWorkload(_) | Self::UnknownNode { .. } => StatusCode::NOT_FOUND,
+            Self::NodeControlUnavailable => StatusCode::NOT_IMPLEMENTED,
+            Self::Chaos { .. } => StatusCode::BAD_GATEWAY,
+            Self::ShuttingDown => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        (status, Json(json!({ "error": self.to_string() }))).into_response()
+    }
+}
+
+/// Workload to start on the running cluster: a workload registered under
+/// `kind` in the daemon's [`ComponentRegistry`], configured by the remaining
+/// fields, e.g. `{"kind": "transactions", "rate": 5}`. Omitted fields take the
+/// defaults listed by `GET /registry`, so `{"kind": "transactions"}` submits
+/// one transaction per block.
+#[derive(Debug, Deserialize)]
+pub struct WorkloadRequest {
+    pub kind: String,
+    #[serde(flatten)]
+    pub config: Value,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum WorkloadStatus {
+    Running,
+    Finished,
+    Failed { error: String },
+    Stopped,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct WorkloadInfo {
+    pub id: u64,
+    pub name: String,
+    pub status: WorkloadStatus,
+}
+
+struct WorkloadEntry {
+    name: String,
+    status: Arc<Mutex<WorkloadStatus>>,
+    abort: AbortHandle,
+}
+
+impl WorkloadEntry {
+    fn info(&self, id: u64) -> WorkloadInfo {
+        WorkloadInfo {
+            id,
+            name: self.name.clone(),
+            status: self
+                .status
+                .lock()
+                .expect("workload status poisoned")
+                .clone(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeRole {
+    Validator,
+    Executor,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChaosAction {
+    Restart,
+    Stop,
+    Kill,
+    Start,
+    Pause,
+    Unpause,
+}
+
+/// Node control action on a single node.
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ChaosRequest {
+    pub action: ChaosAction,
+    pub role: NodeRole,
+    pub index: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NodeStatus {
+    pub node: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ClusterStatus {
+    pub nodes: Vec<NodeStatus>,
+    pub workloads: Vec<WorkloadInfo>,
+    pub report: RunReport,
+}
+
+/// Shared state behind the daemon API: the deployed cluster plus the
+/// workloads started through it.
+pub struct DaemonState {
+    context: Arc<RunContext>,
+    registry: ComponentRegistry,
+    runner: Mutex<Option<Runner>>,
+    workloads: Mutex<BTreeMap<u64, WorkloadEntry>>,
+    next_workload_id: AtomicU64,
+    shutdown: Notify,
+}
+
+impl DaemonState {
+    /// State offering the built-in workloads.
+    #[must_use]
+    pub fn new(runner: Runner) -> Arc<Self> {
+        Self::with_registry(runner, builtin_registry())
+    }
+
+    /// State offering the workloads registered in `registry`.
+    #[must_use]
+    pub fn with_registry(runner: Runner, registry: ComponentRegistry) -> Arc<Self> {
+        Arc::new(Self {
+            context: runner.context(),
+            registry,
+            runner: Mutex::new(Some(runner)),
+            workloads: Mutex::new(BTreeMap::new()),
+            next_workload_id: AtomicU64::new(1),
+            shutdown: Notify::new(),
+        })
+    }
+
+    /// Resolves once a shutdown was requested through the API.
+    pub async fn shutdown_requested(&self) {
+        self.shutdown.notified().await;
+    }
+
+    /// Abort running workloads and tear the cluster down.
+    pub async fn teardown(&self) {
+        for entry in self.workloads.lock().expect("workloads poisoned").values() {
+            entry.abort.abort();
+        }
+
+        let runner = self.runner.lock().expect("runner poisoned").take();
+        if let Some(runner) = runner {
+            info!("tearing down daemon cluster");
+            // Runner cleanup shells out synchronously (docker, helm).
+            if let Err(err) = tokio::task::spawn_blocking(move || drop(runner)).await {
+                warn!(error = %err, "cluster cleanup task failed");
+            }
+        }
+    }
+
+    fn is_shutting_down(&self) -> bool {
+        self.runner.lock().expect("runner poisoned").is_none()
+    }
+
+    fn start_workload(&self, request: WorkloadRequest) -> Result<WorkloadInfo, ApiError> {
+        if self.is_shutting_down() {
+            return Err(ApiError::ShuttingDown);
+        }
+
+        let requires_node_control = self
+            .registry
+            .workload_info(&request.kind)
+            .is_some_and(|info| info.requires_node_control);
+        if requires_node_control && self.context.node_control().is_none() {
+            return Err(ApiError::NodeControlUnavailable);
+        }
+        let mut workload = self
+            .registry
+            .workload(&request.kind, request.config)
+            .map_err(|err| ApiError::InvalidWorkload(err.to_string()))?;
+        workload
+            .init(self.context.descriptors(), &self.context.run_metrics())
+            .map_err(|err| ApiError::InvalidWorkload(err.to_string()))?;
+
+        let id = self.next_workload_id.fetch_add(1, Ordering::Relaxed);
+        let name = workload.name().to_owned();
+        let status = Arc::new(Mutex::new(WorkloadStatus::Running));
+
+        let context = Arc::clone(&self.context);
+        let task_status = Arc::clone(&status);
+        let task_name = name.clone();
+        let handle = tokio::spawn(async move {
+            let outcome = workload.start(context.as_ref()).await;
+            let next = match outcome {
+                Ok(()) => WorkloadStatus::Finished,
+                Err(err) => {
+                    warn!(id, workload = %task_name, error = %err, "daemon workload failed");
+                    WorkloadStatus::Failed {
+                        error: err.to_string(),
+                    }
+                }
+            };
+            *task_status.lock().expect("workload status poisoned") = next;
+        });
+
+        info!(id, workload = %name, "daemon workload started");
+        let entry = WorkloadEntry {
+            name,
+            status,
+            abort: handle.abort_handle(),
+        };
+        let info = entry.info(id);
+        self.workloads
+            .lock()
+            .expect("workloads poisoned")
+            .insert(id, entry);
+        Ok(info)
+    }
+
+    fn stop_workload(&self, id: u64) -> Result<WorkloadInfo, ApiError> {
+        let workloads = self.workloads.lock().expect("workloads poisoned");
+        let entry = workloads.get(&id).ok_or(ApiError::UnknownWorkload(id))?;
+        if !entry.abort.is_finished() {
+            entry.abort.abort();
+            *entry.status.lock().expect("workload status poisoned") = WorkloadStatus::Stopped;
+            info!(id, workload = %entry.name, "daemon workload stopped");
+        }
+        Ok(entry.info(id))
+    }
+
+    fn workload_infos(&self) -> Vec<WorkloadInfo> {
+        self.workloads
+            .lock()
+            .expect("workloads poisoned")
+            .iter()
+            .map(|(id, entry)| entry.info(*id))
+            .collect()
+    }
+
+    async fn chaos(&self, request: ChaosRequest) -> Result<(), ApiError> {
+        let handle = self
+            .context
+            .node_control()
+            .ok_or(ApiError::NodeControlUnavailable)?;
+        let target = chaos_target(request, self.context.descriptors())?;
+
+        let (result, event) = match (request.action, target) {
+            (ChaosAction::Restart, NodeTarget::Validator(index)) => (
+                handle.restart_validator(index).await,
+                NodeEventKind::Restart,
+            ),
+            (ChaosAction::Restart, NodeTarget::Executor(index)) => {
+                (handle.restart_executor(index).await, NodeEventKind::Restart)
+            }
+            (ChaosAction::Stop, NodeTarget::Validator(index)) => {
+                (handle.stop_validator(index).await, NodeEventKind::Stop)
+            }
+            (ChaosAction::Stop, NodeTarget::Executor(index)) => {
+                (handle.stop_executor(index).await, NodeEventKind::Stop)
+            }
+            (ChaosAction::Kill, NodeTarget::Validator(index)) => {
+                (handle.kill_validator(index).await, NodeEventKind::Kill)
+            }
+            (ChaosAction::Kill, NodeTarget::Executor(index)) => {
+                (handle.kill_executor(index).await, NodeEventKind::Kill)
+            }
+            (ChaosAction::Start, NodeTarget::Validator(index)) => {
+                (handle.start_validator(index).await, NodeEventKind::Start)
+            }
+            (ChaosAction::Start, NodeTarget::Executor(index)) => {
+                (handle.start_executor(index).await, NodeEventKind::Start)
+            }
+            (ChaosAction::Pause, target) => (handle.pause_node(target).await, NodeEventKind::Pause),
+            (ChaosAction::Unpause, target) => {
+                (handle.unpause_node(target).await, NodeEventKind::Unpause)
+            }
+        };
+        result.map_err(|source| ApiError::Chaos { target, source })?;
+
+        info!(%target, action = ?request.action, "daemon chaos action applied");
+        self.context.report().record_node_event(target, event);
+        Ok(())
+    }
+
+    async fn status(&self) -> ClusterStatus {
+        let clients = self.context.node_clients();
+        let validators = clients
+            .validator_clients()
+            .iter()
+            .enumerate()
+            .map(|(index, client)| (NodeTarget::Validator(index), client));
+        let executors = clients
+            .executor_clients()
+            .iter()
+            .enumerate()
+            .map(|(index, client)| (NodeTarget::Executor(index), client));
+        let nodes = join_all(validators.chain(executors).map(node_status)).await;
+
+        ClusterStatus {
+            nodes,
+            workloads: self.workload_infos(),
+            report: self.context.report().snapshot(),
+        }
+    }
+}
+
+/// Node addressed by a chaos request, if it is part of the topology.
+fn chaos_target(
+    request: ChaosRequest,
+    topology: &GeneratedTopology,
+) -> Result<NodeTarget, ApiError> {
+    let (target, count) = match request.role {
+        NodeRole::Validator => (
+            NodeTarget::Validator(request.index),
+            topology.validators().len(),
+        ),
+        NodeRole::Executor => (
+            NodeTarget::Executor(request.index),
+            topology.executors().len(),
+        ),
+    };
+    if request.index >= count {
+        return Err(ApiError::UnknownNode { target });
+    }
+    Ok(target)
+}
+
+async fn node_status((target, client): (NodeTarget, &ApiClient)) -> NodeStatus {
+    match client.consensus_info().await {
+        Ok(info) => NodeStatus {
+            node: target.to_string(),
+            height: Some(info.height),
+            error: None,
+        },
+        Err(err) => NodeStatus {
+            node: target.to_string(),
+            height: None,
+            error: Some(err.to_string()),
+        },
+    }
+}
+
+async fn get_status(State(state): State<Arc<DaemonState>>) -> Json<ClusterStatus> {
+    Json(state.status().await)
+}
+
+async fn list_components(State(state): State<Arc<DaemonState>>) -> Json<Vec<ComponentInfo>> {
+    Json(component_list(&state.registry))
+}
+
+fn component_list(registry: &ComponentRegistry) -> Vec<ComponentInfo> {
+    registry.components().cloned().collect()
+}
+
+async fn list_workloads(State(state): State<Arc<DaemonState>>) -> Json<Vec<WorkloadInfo>> {
+    Json(state.workload_infos())
+}
+
+async fn start_workload(
+    State(state): State<Arc<DaemonState>>,
+    Json(request): Json<WorkloadRequest>,
+) -> Result<(StatusCode, Json<WorkloadInfo>), ApiError> {
+    state
+        .start_workload(request)
+        .map(|info| (StatusCode::CREATED, Json(info)))
+}
+
+async fn stop_workload(
+    State(state): State<Arc<DaemonState>>,
+    Path(id): Path<u64>,
+) -> Result<Json<WorkloadInfo>, ApiError> {
+    state.stop_workload(id).map(Json)
+}
+
+async fn chaos(
+    State(state): State<Arc<DaemonState>>,
+    Json(request): Json<ChaosRequest>,
+) -> Result<StatusCode, ApiError> {
+    state.chaos(request).await.map(|()| StatusCode::NO_CONTENT)
+}
+
+async fn shutdown(State(state): State<Arc<DaemonState>>) -> StatusCode {
+    info!("shutdown requested through daemon API");
+    state.shutdown.notify_one();
+    StatusCode::ACCEPTED
+}
+
+/// HTTP API for driving a deployed cluster.
+///
+/// - `GET /status`: node heights, workloads and the run report so far
+/// - `GET /registry`: workloads and expectations known to the daemon, with
+///   their default configs
+/// - `GET /workloads`, `POST /workloads`, `DELETE /workloads/{id}`
+/// - `POST /chaos`: restart/stop/start a node
+/// - `POST /shutdown`: stop workloads and tear the cluster down
+pub fn daemon_app(state: Arc<DaemonState>) -> Router {
+    Router::new()
+        .route("/status", get(get_status))
+        .route("/registry", get(list_components))
+        .route("/workloads", get(list_workloads).post(start_workload))
+        .route("/workloads/:id", delete(stop_workload))
+        .route("/chaos", post(chaos))
+        .route("/shutdown", post(shutdown))
+        .with_state(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_core::scenario::ScenarioBuilder;
+
+    use super::*;
+
+    fn status(error: ApiError) -> StatusCode {
+        error.into_response().status()
+    }
+
+    #[test]
+    fn workload_request_keeps_remaining_fields_as_config() {
+        let request: WorkloadRequest =
+            serde_json::from_value(json!({ "kind": "transactions", "rate": 5 }))
+                .expect("request parses");
+
+        assert_eq!(request.kind, "transactions");
+        assert_eq!(request.config, json!({ "rate": 5 }));
+    }
+
+    #[test]
+    fn chaos_request_resolves_nodes_in_the_topology() {
+        let scenario = ScenarioBuilder::topology_with(|t| t.validators(2).executors(1)).build();
+        let request =
+            |value: Value| serde_json::from_value::<ChaosRequest>(value).expect("request parses");
+
+        let target = chaos_target(
+            request(json!({ "action": "restart", "role": "validator", "index": 1 })),
+            scenario.topology(),
+        );
+        assert!(matches!(target, Ok(NodeTarget::Validator(1))));
+
+        let missing = chaos_target(
+            request(json!({ "action": "kill", "role": "executor", "index": 1 })),
+            scenario.topology(),
+        )
+        .expect_err("executor-1 is not deployed");
+        assert!(matches!(
+            missing,
+            ApiError::UnknownNode {
+                target: NodeTarget::Executor(1)
+            }
+        ));
+        assert_eq!(status(missing), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn registry_lists_components_with_default_configs() {
+        let listed = serde_json::to_value(component_list(&builtin_registry()))
+            .expect("components serialize");
+        let entry = |name: &str| {
+            listed
+                .as_array()
+                .expect("a list")
+                .iter()
+                .find(|entry| entry["name"] == name)
+                .unwrap_or_else(|| panic!("{name} is listed"))
+                .clone()
+        };
+
+        let transactions = entry("transactions");
+        assert_eq!(transactions["kind"], "workload");
+        assert_eq!(transactions["requires_node_control"], false);
+        assert_eq!(transactions["default_config"]["rate"], 1);
+        assert_eq!(entry("chaos_crash")["requires_node_control"], true);
+        assert_eq!(entry("chain_consistency")["kind"], "expectation");
+    }
+
+    #[test]
+    fn chaos_request_rejects_unknown_actions() {
+        let parsed = serde_json::from_value::<ChaosRequest>(
+            json!({ "action": "explode", "role": "validator", "index": 0 }),
+        );
+        assert!(parsed.is_err());
+    }
+
+    #[test]
+    fn errors_map_to_http_statuses() {
+        assert_eq!(
+            status(ApiError::InvalidWorkload(
+                "rate must be positive".to_owned()
+            )),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(status(ApiError::UnknownWorkload(7)), StatusCode::NOT_FOUND);
+        assert_eq!(
+            status(ApiError::NodeControlUnavailable),
+            StatusCode::NOT_IMPLEMENTED
+        );
+        assert_eq!(
+            status(ApiError::Chaos {
+                target: NodeTarget::Validator(0),
+                source: "container not found".into(),
+            }),
+            StatusCode::BAD_GATEWAY
+        );
+        assert_eq!(
+            status(ApiError::ShuttingDown),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{net::SocketAddr, process, time::Duration};
+
+use clap::Parser;
+use testing_framework_daemon::{
+    api::{DaemonState, daemon_app},
+    cluster::{ClusterSpec, RunnerKind, deploy},
+};
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+#[derive(Parser, Debug)]
+#[command(about = "Deploy a Nomos test cluster and drive it over HTTP")]
+struct Args {
+    /// Backend used to deploy the cluster.
+    #[arg(long, value_enum, default_value = "compose")]
+    runner: RunnerKind,
+    #[arg(long, default_value_t = 1)]
+    validators: usize,
+    #[arg(long, default_value_t = 1)]
+    executors: usize,
+    /// Funded wallets seeded into genesis for transaction workloads.
+    #[arg(long, default_value_t = 100)]
+    wallets: usize,
+    /// Nominal run length (seconds) used to size workload plans.
+    #[arg(long, default_value_t = 3600)]
+    horizon_secs: u64,
+    /// Address the control API listens on.
+    #[arg(long, env = "NOMOS_DAEMON_LISTEN", default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt::init();
+    let args = Args::parse();
+
+    let spec = ClusterSpec {
+        runner: args.runner,
+        validators: args.validators,
+        executors: args.executors,
+        wallets: args.wallets,
+        horizon: Duration::from_secs(args.horizon_secs),
+    };
+    let listener = TcpListener::bind(args.listen).await.unwrap_or_else(|err| {
+        eprintln!("failed to bind {}: {err}", args.listen);
+        process::exit(1);
+    });
+    let runner = deploy(spec).await.unwrap_or_else(|err| {
+        eprintln!("failed to deploy cluster: {err}");
+        process::exit(1);
+    });
+
+    let state = DaemonState::new(runner);
+    let app = daemon_app(state.clone());
+    info!(address = %args.listen, "daemon API listening");
+
+    let shutdown_state = state.clone();
+    let served = axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            tokio::select! {
+                () = shutdown_state.shutdown_requested() => {}
+                result = tokio::signal::ctrl_c() => {
+                    if let Err(err) = result {
+                        warn!(error = %err, "failed to listen for ctrl-c");
+                    }
+                }
+            }
+        })
+        .await;
+    if let Err(err) = served {
+        warn!(error = %err, "daemon API server failed");
+    }
+
+    state.teardown().await;
+}
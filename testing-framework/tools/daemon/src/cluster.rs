@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use testing_framework_core::scenario::{Deployer as _, DynError, Runner, ScenarioBuilder};
+use testing_framework_runner_compose::ComposeDeployer;
+use testing_framework_runner_k8s::K8sDeployer;
+use testing_framework_runner_local::LocalDeployer;
+use testing_framework_runner_process::ProcessDeployer;
+use tracing::info;
+
+/// Backend used to provision the long-running cluster.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum RunnerKind {
+    /// In-process nodes; no node control, so chaos actions are rejected.
+    Local,
+    /// Native child processes.
+    Process,
+    /// Docker Compose stack.
+    Compose,
+    /// Kubernetes via Helm.
+    K8s,
+}
+
+/// Topology the daemon deploys on startup.
+#[derive(Clone, Copy, Debug)]
+pub struct ClusterSpec {
+    pub runner: RunnerKind,
+    pub validators: usize,
+    pub executors: usize,
+    pub wallets: usize,
+    /// Nominal run length handed to workloads so they can size their plans;
+    /// the cluster itself stays up until shutdown.
+    pub horizon: Duration,
+}
+
+/// Deploy the topology and return the runner holding the cluster. Dropping
+/// the runner tears the cluster down.
+pub async fn deploy(spec: ClusterSpec) -> Result<Runner, DynError> {
+    info!(
+        runner = ?spec.runner,
+        validators = spec.validators,
+        executors = spec.executors,
+        wallets = spec.wallets,
+        "deploying daemon cluster"
+    );
+
+    let builder = ScenarioBuilder::topology_with(|t| {
+        t.network_star()
+            .validators(spec.validators)
+            .executors(spec.executors)
+    })
+    .with_env_profile()
+    .wallets(spec.wallets)
+    .with_run_duration(spec.horizon);
+
+    let runner = match spec.runner {
+        RunnerKind::Local => {
+            let scenario = builder.build();
+            LocalDeployer::new().deploy(&scenario).await?
+        }
+        RunnerKind::Process => {
+            let scenario = builder.enable_node_control().build();
+            ProcessDeployer::new().deploy(&scenario).await?
+        }
+        RunnerKind::Compose => {
+            let scenario = builder.enable_node_control().build();
+            ComposeDeployer::new().deploy(&scenario).await?
+        }
+        RunnerKind::K8s => {
+            let scenario = builder.enable_node_control().build();
+            K8sDeployer::new().deploy(&scenario).await?
+        }
+    };
+
+    info!("daemon cluster ready");
+    Ok(runner)
+}
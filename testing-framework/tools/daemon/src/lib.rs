@@ -0,0 +1,6 @@
+//! Long-running cluster driven over HTTP: deploy a topology once, then start
+//! and stop workloads, trigger node control actions and tear it down from
+//! external tools instead of in-process scenarios.
+
+pub mod api;
+pub mod cluster;
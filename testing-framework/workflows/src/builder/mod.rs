@@ -4,13 +4,36 @@ use std::{
 };
 
 use testing_framework_core::{
-    scenario::{Builder as CoreScenarioBuilder, NodeControlCapability},
-    topology::configs::wallet::WalletConfig,
+    scenario::{
+        ApiFaults, Builder as CoreScenarioBuilder, NetworkShaping, NodeControlCapability,
+        NodeTarget,
+    },
+    topology::{
+        configs::wallet::{TokenDistribution, WalletConfig},
+        generation::NodeRole,
+    },
 };
 
 use crate::{
-    expectations::ConsensusLiveness,
-    workloads::{chaos::RandomRestartWorkload, da, transaction},
+    expectations::{
+        ChainConsistencyExpectation, ConsensusLiveness, ConsensusStallExpectation,
+        DaConnectivityExpectation, DaReplicationExpectation, LateJoinSyncExpectation,
+        LogExpectation, MetricsExpectation, ProposerFairnessExpectation, RecoveryExpectation,
+        TraceExpectation,
+    },
+    registry::{
+        CrashConfig, DaPeerBlockingConfig, DowntimeConfig, LatencyConfig, PartitionConfig,
+        RestartConfig, duration,
+    },
+    workloads::{
+        chaos::{
+            ChaosTimeline, ChaosTimelineWorkload, ClusterResizeWorkload, DaPeerBlockingWorkload,
+            DiskPressureWorkload, ForkInjectionWorkload, ForkIsolation, NetworkLatencyWorkload,
+            NetworkPartitionWorkload, NodeCrashWorkload, NodeDowntimeWorkload,
+            RandomRestartWorkload, ResizeAction, ResizeStep,
+        },
+        da, transaction,
+    },
 };
 
 macro_rules! non_zero_rate_fn {
@@ -28,6 +51,11 @@ non_zero_rate_fn!(
     transaction_rate_checked,
     "transaction rate must be non-zero"
 );
+non_zero_rate_fn!(ramp_step_checked, "transaction ramp step must be non-zero");
+non_zero_rate_fn!(
+    max_pending_checked,
+    "mempool backpressure threshold must be non-zero"
+);
 non_zero_rate_fn!(channel_rate_checked, "channel rate must be non-zero");
 non_zero_rate_fn!(blob_rate_checked, "blob rate must be non-zero");
 
@@ -55,8 +83,81 @@ pub trait ScenarioBuilderExt<Caps>: Sized {
     fn expect_consensus_liveness(self) -> Self;
 
     #[must_use]
-    /// Seed deterministic wallets with total funds split across `users`.
-    fn initialize_wallet(self, total_funds: u64, users: usize) -> Self;
+    /// Attach an expectation failing the run when a node's height does not
+    /// advance for longer than `stall_blocks` expected block intervals.
+    fn expect_no_consensus_stalls(self, stall_blocks: u64) -> Self;
+
+    #[must_use]
+    /// Attach an expectation checking that all nodes agree on their chains
+    /// except for at most the last `security_param` blocks.
+    fn expect_chain_consistency(self) -> Self;
+
+    #[must_use]
+    /// Attach an expectation checking that block proposals are distributed
+    /// across nodes in proportion to their leader stake.
+    fn expect_proposer_fairness(self) -> Self;
+
+    #[must_use]
+    /// Attach an expectation checking that late-joining nodes end the run
+    /// within `security_param` blocks of the tip.
+    fn expect_late_join_sync(self) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when a node restarted by a chaos
+    /// workload takes longer than `max` to serve its API again and catch up
+    /// with the cluster's tip.
+    fn expect_recovery_within(self, max: Duration) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when a node stays below the DA
+    /// subnet threshold for longer than `max_outage`.
+    fn expect_da_connectivity(self, max_outage: Duration) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when a share of a recently
+    /// landed blob is stored by fewer than `min_replicas` members of its
+    /// subnet.
+    fn expect_da_replication(self, min_replicas: usize) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when the p95 latency between a
+    /// transaction being accepted and it landing in a block exceeds `max`.
+    fn expect_tx_inclusion_p95(self, max: Duration) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when node logs contain panics or
+    /// `ERROR` lines.
+    fn expect_clean_logs(self) -> Self;
+
+    #[must_use]
+    /// Attach an expectation that runs a PromQL `query` once the run finishes
+    /// and fails the scenario unless `predicate` accepts its (summed) value.
+    fn expect_metric(
+        self,
+        query: impl Into<String>,
+        predicate: impl Fn(f64) -> bool + Send + Sync + 'static,
+    ) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when any exported span named
+    /// `name` took longer than `max`. Requires a deployer collecting traces.
+    fn expect_span_duration_below(self, name: &str, max: Duration) -> Self;
+
+    #[must_use]
+    /// Attach an expectation failing the run when any exported span ended
+    /// with an error status. Requires a deployer collecting traces.
+    fn expect_no_error_spans(self) -> Self;
+
+    #[must_use]
+    /// Seed deterministic wallets with `total_funds` split according to
+    /// `distribution` (panics if the split is invalid).
+    fn initialize_wallet(self, total_funds: u64, distribution: TokenDistribution) -> Self;
+
+    #[must_use]
+    /// Send the run's node API requests, block feed included, through
+    /// proxies injecting the latency, errors and resets configured on
+    /// [`ApiFaults`].
+    fn api_faults_with(self, f: impl FnOnce(ApiFaults) -> ApiFaults) -> Self;
 }
 
 impl<Caps> ScenarioBuilderExt<Caps> for CoreScenarioBuilder<Caps> {
@@ -86,11 +187,67 @@ impl<Caps> ScenarioBuilderExt<Caps> for CoreScenarioBuilder<Caps> {
         self.with_expectation(ConsensusLiveness::default())
     }
 
-    fn initialize_wallet(self, total_funds: u64, users: usize) -> Self {
-        let user_count = NonZeroUsize::new(users).expect("wallet user count must be non-zero");
-        let wallet = WalletConfig::uniform(total_funds, user_count);
+    fn expect_no_consensus_stalls(self, stall_blocks: u64) -> Self {
+        self.with_expectation(ConsensusStallExpectation::new(stall_blocks))
+    }
+
+    fn expect_chain_consistency(self) -> Self {
+        self.with_expectation(ChainConsistencyExpectation::new())
+    }
+
+    fn expect_proposer_fairness(self) -> Self {
+        self.with_expectation(ProposerFairnessExpectation::new())
+    }
+
+    fn expect_late_join_sync(self) -> Self {
+        self.with_expectation(LateJoinSyncExpectation::new())
+    }
+
+    fn expect_recovery_within(self, max: Duration) -> Self {
+        self.with_expectation(RecoveryExpectation::new(max))
+    }
+
+    fn expect_da_connectivity(self, max_outage: Duration) -> Self {
+        self.with_expectation(DaConnectivityExpectation::new(max_outage))
+    }
+
+    fn expect_da_replication(self, min_replicas: usize) -> Self {
+        self.with_expectation(DaReplicationExpectation::new(min_replicas))
+    }
+
+    fn expect_tx_inclusion_p95(self, max: Duration) -> Self {
+        self.with_expectation(transaction::TxInclusionLatencyExpectation::p95(max))
+    }
+
+    fn expect_clean_logs(self) -> Self {
+        self.with_expectation(LogExpectation::default())
+    }
+
+    fn expect_metric(
+        self,
+        query: impl Into<String>,
+        predicate: impl Fn(f64) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.with_expectation(MetricsExpectation::new().check(query, predicate))
+    }
+
+    fn expect_span_duration_below(self, name: &str, max: Duration) -> Self {
+        self.with_expectation(TraceExpectation::new().max_span_duration(name, max))
+    }
+
+    fn expect_no_error_spans(self) -> Self {
+        self.with_expectation(TraceExpectation::new().no_error_spans())
+    }
+
+    fn initialize_wallet(self, total_funds: u64, distribution: TokenDistribution) -> Self {
+        let wallet = WalletConfig::distributed(total_funds, &distribution)
+            .unwrap_or_else(|err| panic!("invalid wallet distribution: {err}"));
         self.with_wallet_config(wallet)
     }
+
+    fn api_faults_with(self, f: impl FnOnce(ApiFaults) -> ApiFaults) -> Self {
+        self.with_api_faults(f(ApiFaults::new()))
+    }
 }
 
 /// Builder for transaction workloads.
@@ -98,6 +255,10 @@ pub struct TransactionFlowBuilder<Caps> {
     builder: CoreScenarioBuilder<Caps>,
     rate: NonZeroU64,
     users: Option<NonZeroUsize>,
+    ramp: Option<(NonZeroU64, NonZeroU64, NonZeroU64)>,
+    submit_via: Vec<NodeTarget>,
+    backpressure: Option<transaction::MempoolBackpressure>,
+    path: transaction::SubmissionPath,
 }
 
 impl<Caps> TransactionFlowBuilder<Caps> {
@@ -110,6 +271,10 @@ impl<Caps> TransactionFlowBuilder<Caps> {
             builder,
             rate: Self::default_rate(),
             users: None,
+            ramp: None,
+            submit_via: Vec::new(),
+            backpressure: None,
+            path: transaction::SubmissionPath::Mempool,
         }
     }
 
@@ -137,15 +302,98 @@ impl<Caps> TransactionFlowBuilder<Caps> {
         self
     }
 
+    #[must_use]
+    /// Ramp the submission rate from `start_rate` to `end_rate` transactions
+    /// per block in `step` increments, recording where the mempool saturates
+    /// instead of holding a fixed rate (panics on zero values or when
+    /// `end_rate < start_rate`).
+    pub const fn ramp(mut self, start_rate: u64, end_rate: u64, step: u64) -> Self {
+        assert!(
+            end_rate >= start_rate,
+            "ramp end rate must be at least the start rate"
+        );
+        self.ramp = Some((
+            transaction_rate_checked(start_rate),
+            transaction_rate_checked(end_rate),
+            ramp_step_checked(step),
+        ));
+        self
+    }
+
+    #[must_use]
+    /// Submit transactions only through the `role` nodes at `indices`
+    /// instead of random nodes, e.g. to watch mempool gossip spread from a
+    /// single entry point. Repeated calls add more nodes.
+    pub fn submit_via(mut self, role: NodeRole, indices: impl IntoIterator<Item = usize>) -> Self {
+        self.submit_via
+            .extend(indices.into_iter().map(|index| match role {
+                NodeRole::Validator => NodeTarget::Validator(index),
+                NodeRole::Executor => NodeTarget::Executor(index),
+            }));
+        self
+    }
+
+    #[must_use]
+    /// Pause submission while any submission node's mempool holds more than
+    /// `max_pending` transactions, resuming once it drains (panics on zero).
+    /// Ignored by the ramp, which looks for the saturation point instead.
+    pub const fn backpressure(mut self, max_pending: u64) -> Self {
+        self.backpressure = Some(transaction::MempoolBackpressure::new(max_pending_checked(
+            max_pending,
+        )));
+        self
+    }
+
+    #[must_use]
+    /// Pause submission while the mempool is saturated, with full control
+    /// over the threshold and poll interval.
+    pub const fn backpressure_with(
+        mut self,
+        backpressure: transaction::MempoolBackpressure,
+    ) -> Self {
+        self.backpressure = Some(backpressure);
+        self
+    }
+
+    #[must_use]
+    /// Exercise the privacy path: rotate submissions over the core blend
+    /// nodes (or the `submit_via` nodes) and require every transaction to be
+    /// included, not just a share of them. Ignored by the ramp.
+    pub const fn via_blend(mut self) -> Self {
+        self.path = transaction::SubmissionPath::Blend;
+        self
+    }
+
     #[must_use]
     /// Attach the transaction workload to the scenario.
     pub fn apply(mut self) -> CoreScenarioBuilder<Caps> {
+        if let Some((start_rate, end_rate, step)) = self.ramp {
+            let workload = transaction::RampWorkload::new(start_rate, end_rate, step)
+                .with_user_limit(self.users)
+                .with_submission_targets(self.submit_via);
+            tracing::info!(
+                start_rate = start_rate.get(),
+                end_rate = end_rate.get(),
+                step = step.get(),
+                users = self.users.map(|u| u.get()),
+                "attaching transaction ramp workload"
+            );
+            self.builder = self.builder.with_workload(workload);
+            return self.builder;
+        }
+
         let workload = transaction::Workload::with_rate(self.rate.get())
             .expect("transaction rate must be non-zero")
-            .with_user_limit(self.users);
+            .with_user_limit(self.users)
+            .with_submission_targets(self.submit_via.clone())
+            .with_backpressure(self.backpressure)
+            .with_submission_path(self.path);
         tracing::info!(
             rate = self.rate.get(),
             users = self.users.map(|u| u.get()),
+            submit_via = ?self.submit_via,
+            max_pending = self.backpressure.map(|b| b.max_pending().get()),
+            path = ?self.path,
             "attaching transaction workload"
         );
         self.builder = self.builder.with_workload(workload);
@@ -159,6 +407,14 @@ pub struct DataAvailabilityFlowBuilder<Caps> {
     channel_rate: NonZeroU64,
     blob_rate: NonZeroU64,
     headroom_percent: u64,
+    sampling: Option<NonZeroUsize>,
+    sampling_sweep: Option<NonZeroUsize>,
+    pruning: bool,
+    blob_sizes: da::BlobSizeDistribution,
+    executor_affinity: da::ExecutorAffinity,
+    commitments: Option<(NonZeroUsize, NonZeroUsize)>,
+    stress: Option<NonZeroUsize>,
+    stress_rate: Option<NonZeroU64>,
 }
 
 impl<Caps> DataAvailabilityFlowBuilder<Caps> {
@@ -176,6 +432,14 @@ impl<Caps> DataAvailabilityFlowBuilder<Caps> {
             channel_rate: Self::default_channel_rate(),
             blob_rate: Self::default_blob_rate(),
             headroom_percent: da::Workload::default_headroom_percent(),
+            sampling: None,
+            sampling_sweep: None,
+            pruning: false,
+            blob_sizes: da::BlobSizeDistribution::PowerOfTwoChunks,
+            executor_affinity: da::ExecutorAffinity::Random,
+            commitments: None,
+            stress: None,
+            stress_rate: None,
         }
     }
 
@@ -214,14 +478,157 @@ impl<Caps> DataAvailabilityFlowBuilder<Caps> {
         self
     }
 
+    #[must_use]
+    /// Choose blob payload sizes, e.g. [`da::BlobSizeDistribution::boundaries`]
+    /// to exercise encoding at its limits (panics on empty sizes or an
+    /// inverted range).
+    pub fn blob_size_distribution(mut self, blob_sizes: da::BlobSizeDistribution) -> Self {
+        if let Err(err) = blob_sizes.validate() {
+            panic!("invalid DA blob size distribution: {err}");
+        }
+        self.blob_sizes = blob_sizes;
+        self
+    }
+
+    #[must_use]
+    /// Choose which executor each blob is published through, e.g.
+    /// [`da::ExecutorAffinity::PerChannel`] to attribute dispersal failures
+    /// to a single executor. Does not apply to [`Self::dispersal_stress`],
+    /// which already loads every executor separately.
+    pub const fn executor_affinity(mut self, affinity: da::ExecutorAffinity) -> Self {
+        self.executor_affinity = affinity;
+        self
+    }
+
+    #[must_use]
+    /// Also sample up to `blobs_per_block` landed blobs on every validator via
+    /// historic sampling and fail the run if any node cannot (panics on
+    /// zero).
+    pub const fn verify_sampling(mut self, blobs_per_block: usize) -> Self {
+        match NonZeroUsize::new(blobs_per_block) {
+            Some(value) => self.sampling = Some(value),
+            None => panic!("DA sampling size must be non-zero"),
+        }
+        self
+    }
+
+    #[must_use]
+    /// With [`Self::verify_sampling`], also sample up to `blobs` random blobs
+    /// out of everything published during the run on every node before the
+    /// sampling expectation is evaluated (panics on zero).
+    pub const fn sampling_sweep(mut self, blobs: usize) -> Self {
+        match NonZeroUsize::new(blobs) {
+            Some(value) => self.sampling_sweep = Some(value),
+            None => panic!("DA sampling sweep size must be non-zero"),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Also recompute the commitments of up to `blobs` published blobs from
+    /// their payloads and check them against `nodes_per_blob` random nodes
+    /// each; see [`da::CommitmentsExpectation`]. Needs the KZG parameters on
+    /// the host running the scenario (panics on zero).
+    pub const fn verify_commitments(mut self, blobs: usize, nodes_per_blob: usize) -> Self {
+        match (NonZeroUsize::new(blobs), NonZeroUsize::new(nodes_per_blob)) {
+            (Some(blobs), Some(nodes)) => self.commitments = Some((blobs, nodes)),
+            _ => panic!("DA commitment verification blobs and nodes must be non-zero"),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Also check that landed blobs stop being sampleable once the DA
+    /// validity window has passed. Evaluation waits for the oldest blob to
+    /// expire, so the run should outlast `blobs_validity_duration`.
+    pub const fn verify_pruning(mut self) -> Self {
+        self.pruning = true;
+        self
+    }
+
+    #[must_use]
+    /// Replace the channel workload with a dispersal stress run keeping
+    /// `concurrency` publishes in flight per executor, without waiting for
+    /// blobs to land (panics on zero). Only the first blob of each stress
+    /// channel lands, so [`Self::apply`] panics if sampling, pruning or
+    /// commitment checks were also requested.
+    pub const fn dispersal_stress(mut self, concurrency: usize) -> Self {
+        match NonZeroUsize::new(concurrency) {
+            Some(value) => self.stress = Some(value),
+            None => panic!("DA dispersal stress concurrency must be non-zero"),
+        }
+        self
+    }
+
+    #[must_use]
+    /// Cap dispersal stress at `rate` publishes per second per executor
+    /// (panics on zero).
+    pub const fn stress_rate_per_executor(mut self, rate: u64) -> Self {
+        match NonZeroU64::new(rate) {
+            Some(value) => self.stress_rate = Some(value),
+            None => panic!("DA dispersal stress rate must be non-zero"),
+        }
+        self
+    }
+
     #[must_use]
     pub fn apply(mut self) -> CoreScenarioBuilder<Caps> {
-        let workload =
-            da::Workload::with_rate(self.blob_rate, self.channel_rate, self.headroom_percent);
+        assert!(
+            self.stress.is_none()
+                || (self.sampling.is_none() && !self.pruning && self.commitments.is_none()),
+            "DA dispersal stress cannot be combined with sampling, pruning or commitment checks"
+        );
+        if self.pruning {
+            tracing::info!("attaching DA pruning expectation");
+            self.builder = self
+                .builder
+                .with_expectation(da::PruningExpectation::default());
+        }
+
+        if let Some(sample_size) = self.sampling {
+            let mut workload = da::SamplingWorkload::new(sample_size);
+            if let Some(blobs) = self.sampling_sweep {
+                workload = workload.with_final_sweep(blobs);
+            }
+            tracing::info!(
+                sample_size = sample_size.get(),
+                final_sweep = self.sampling_sweep.map(NonZeroUsize::get),
+                "attaching DA sampling workload"
+            );
+            self.builder = self.builder.with_workload(workload);
+        }
+
+        if let Some(concurrency) = self.stress {
+            let mut workload = da::DispersalStressWorkload::new()
+                .with_concurrency(concurrency)
+                .with_blob_sizes(self.blob_sizes);
+            if let Some(rate) = self.stress_rate {
+                workload = workload.with_rate_per_executor(rate);
+            }
+            tracing::info!(
+                concurrency = concurrency.get(),
+                rate_per_executor = self.stress_rate.map(NonZeroU64::get),
+                blob_sizes = ?self.blob_sizes,
+                "attaching DA dispersal stress workload"
+            );
+            self.builder = self.builder.with_workload(workload);
+            return self.builder;
+        }
+
+        let mut workload =
+            da::Workload::with_rate(self.blob_rate, self.channel_rate, self.headroom_percent)
+                .with_blob_sizes(self.blob_sizes)
+                .with_executor_affinity(self.executor_affinity);
+        if let Some((blobs, nodes)) = self.commitments {
+            workload = workload.with_commitment_verification(blobs, nodes);
+        }
         tracing::info!(
             channel_rate = self.channel_rate.get(),
             blob_rate = self.blob_rate.get(),
             headroom_percent = self.headroom_percent,
+            blob_sizes = ?self.blob_sizes,
+            executor_affinity = ?self.executor_affinity,
+            commitment_checks = ?self.commitments.map(|(blobs, _)| blobs.get()),
             "attaching data-availability workload"
         );
         self.builder = self.builder.with_workload(workload);
@@ -257,7 +664,10 @@ impl ChaosBuilderExt for CoreScenarioBuilder<NodeControlCapability> {
 /// Chaos workload builder root.
 ///
 /// Start with `chaos()` on a scenario builder, then select a workload variant
-/// such as `restart()`.
+/// such as `restart()`, `crash()`, `downtime()`, `partition()`, `latency()`,
+/// `da_peer_blocking()`, `disk_pressure()`, `resize()` or `fork()`, or
+/// declare faults on an
+/// explicit [`ChaosTimeline`] with `timeline()`.
 pub struct ChaosBuilder {
     builder: CoreScenarioBuilder<NodeControlCapability>,
 }
@@ -272,15 +682,149 @@ impl ChaosBuilder {
     /// Configure a random restarts chaos workload.
     #[must_use]
     pub fn restart(self) -> ChaosRestartBuilder {
+        let defaults = RestartConfig::default();
         ChaosRestartBuilder {
             builder: self.builder,
-            min_delay: Duration::from_secs(10),
-            max_delay: Duration::from_secs(30),
-            target_cooldown: Duration::from_secs(60),
+            min_delay: duration(defaults.min_delay_secs),
+            max_delay: duration(defaults.max_delay_secs),
+            target_cooldown: duration(defaults.target_cooldown_secs),
+            include_validators: defaults.include_validators,
+            include_executors: defaults.include_executors,
+        }
+    }
+
+    /// Configure a workload that kills nodes with `SIGKILL` and checks they
+    /// recover once started again.
+    #[must_use]
+    pub fn crash(self) -> ChaosCrashBuilder {
+        let defaults = CrashConfig::default();
+        ChaosCrashBuilder {
+            builder: self.builder,
+            restart_delay: duration(defaults.restart_delay_secs),
+            interval: duration(defaults.interval_secs),
+            recovery_timeout: duration(defaults.recovery_timeout_secs),
+            include_validators: defaults.include_validators,
+            include_executors: defaults.include_executors,
+        }
+    }
+
+    /// Configure a workload that stops nodes for extended periods and checks
+    /// they catch up once started again.
+    #[must_use]
+    pub fn downtime(self) -> ChaosDowntimeBuilder {
+        let defaults = DowntimeConfig::default();
+        ChaosDowntimeBuilder {
+            builder: self.builder,
+            downtime: duration(defaults.downtime_secs),
+            interval: duration(defaults.interval_secs),
+            catch_up_timeout: duration(defaults.catch_up_timeout_secs),
+            include_validators: defaults.include_validators,
+            include_executors: defaults.include_executors,
+        }
+    }
+
+    /// Configure a workload that fills a node's blob storage filesystem for a
+    /// while and checks the node stays up and recovers once space is freed.
+    #[must_use]
+    pub fn disk_pressure(self) -> ChaosDiskPressureBuilder {
+        ChaosDiskPressureBuilder {
+            builder: self.builder,
+            fill_percent: 95,
+            duration: Duration::from_secs(30),
+            interval: Duration::from_secs(30),
+            recovery_timeout: Duration::from_secs(60),
+            path: None,
             include_validators: true,
             include_executors: true,
         }
     }
+
+    /// Configure a workload that blocks DA peers on executors for a window and
+    /// checks dispersal still succeeds through the remaining peers.
+    #[must_use]
+    pub fn da_peer_blocking(self) -> ChaosDaPeerBlockingBuilder {
+        let defaults = DaPeerBlockingConfig::default();
+        ChaosDaPeerBlockingBuilder {
+            builder: self.builder,
+            peers: defaults.peers.get(),
+            block_duration: duration(defaults.block_duration_secs),
+            interval: duration(defaults.interval_secs),
+            include_validators: defaults.include_validators,
+            include_executors: defaults.include_executors,
+        }
+    }
+
+    /// Configure a network partition chaos workload.
+    #[must_use]
+    pub fn partition(self) -> ChaosPartitionBuilder {
+        let defaults = PartitionConfig::default();
+        ChaosPartitionBuilder {
+            builder: self.builder,
+            groups: defaults.groups,
+            partition_duration: duration(defaults.partition_secs),
+            heal_duration: duration(defaults.heal_secs),
+            include_validators: defaults.include_validators,
+            include_executors: defaults.include_executors,
+        }
+    }
+
+    /// Configure a network latency/loss shaping chaos workload.
+    #[must_use]
+    pub fn latency(self) -> ChaosLatencyBuilder {
+        let defaults = LatencyConfig::default();
+        ChaosLatencyBuilder {
+            builder: self.builder,
+            target: NetworkShaping {
+                latency: Duration::from_millis(defaults.latency_ms),
+                jitter: Duration::from_millis(defaults.jitter_ms),
+                loss_percent: defaults.packet_loss_percent,
+            },
+            ramp_steps: defaults.ramp_steps.get(),
+            step_duration: duration(defaults.step_secs),
+            schedule: None,
+            nodes: defaults.nodes.map(NonZeroUsize::get),
+            include_validators: defaults.include_validators,
+            include_executors: defaults.include_executors,
+        }
+    }
+
+    /// Configure a workload that isolates a validator so it builds a
+    /// divergent chain, reconnects it and checks fork choice brings it back.
+    #[must_use]
+    pub fn fork(self) -> ChaosForkBuilder {
+        ChaosForkBuilder {
+            builder: self.builder,
+            isolation: ForkIsolation::Partition,
+            isolation_duration: Duration::from_secs(60),
+            interval: Duration::from_secs(30),
+            convergence_blocks: None,
+            validator: None,
+        }
+    }
+
+    /// Configure a workload that adds and removes nodes while the scenario
+    /// runs and checks the cluster keeps up with the new membership.
+    #[must_use]
+    pub fn resize(self) -> ChaosResizeBuilder {
+        ChaosResizeBuilder {
+            builder: self.builder,
+            steps: Vec::new(),
+            sync_timeout: Duration::from_secs(120),
+            max_lag: None,
+        }
+    }
+
+    /// Run the faults declared on `timeline` with a single scheduler and
+    /// attach it to the scenario.
+    #[must_use]
+    pub fn timeline(self, timeline: ChaosTimeline) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            !timeline.is_empty(),
+            "chaos timeline requires at least one fault"
+        );
+        self.builder
+            .with_workload(ChaosTimelineWorkload::new(timeline))
+    }
 }
 
 pub struct ChaosRestartBuilder {
@@ -361,3 +905,656 @@ impl ChaosRestartBuilder {
         self.builder
     }
 }
+
+pub struct ChaosCrashBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    restart_delay: Duration,
+    interval: Duration,
+    recovery_timeout: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl ChaosCrashBuilder {
+    #[must_use]
+    /// How long a killed node stays down before it is started again.
+    pub fn restart_delay(mut self, delay: Duration) -> Self {
+        assert!(
+            !delay.is_zero(),
+            "chaos crash restart delay must be non-zero"
+        );
+        self.restart_delay = delay;
+        self
+    }
+
+    #[must_use]
+    /// How long to wait between crashes.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        assert!(!interval.is_zero(), "chaos crash interval must be non-zero");
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// How long a killed node may take to reach its peers' height after it is
+    /// started again.
+    pub fn recovery_timeout(mut self, timeout: Duration) -> Self {
+        assert!(
+            !timeout.is_zero(),
+            "chaos crash recovery timeout must be non-zero"
+        );
+        self.recovery_timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Include validators in the crash target set.
+    pub const fn include_validators(mut self, enabled: bool) -> Self {
+        self.include_validators = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Include executors in the crash target set.
+    pub const fn include_executors(mut self, enabled: bool) -> Self {
+        self.include_executors = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos crash workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            self.include_validators || self.include_executors,
+            "chaos crash requires at least one node group"
+        );
+
+        let workload = NodeCrashWorkload::new(
+            self.restart_delay,
+            self.interval,
+            self.recovery_timeout,
+            self.include_validators,
+            self.include_executors,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosDaPeerBlockingBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    peers: usize,
+    block_duration: Duration,
+    interval: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl ChaosDaPeerBlockingBuilder {
+    #[must_use]
+    /// How many validators' DA peers to block per window. Must stay below the
+    /// validator count so dispersal has somewhere to go.
+    pub fn peers(mut self, peers: usize) -> Self {
+        assert!(peers > 0, "chaos DA peer blocking needs at least one peer");
+        self.peers = peers;
+        self
+    }
+
+    #[must_use]
+    /// How long peers stay blocked.
+    pub fn block_duration(mut self, duration: Duration) -> Self {
+        assert!(
+            !duration.is_zero(),
+            "chaos DA peer block duration must be non-zero"
+        );
+        self.block_duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// How long to wait between blocking windows.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "chaos DA peer blocking interval must be non-zero"
+        );
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Block the peers on validators too (off by default).
+    pub const fn include_validators(mut self, enabled: bool) -> Self {
+        self.include_validators = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Block the peers on executors, which drive dispersal.
+    pub const fn include_executors(mut self, enabled: bool) -> Self {
+        self.include_executors = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Finalize the DA peer blocking workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            self.include_validators || self.include_executors,
+            "chaos DA peer blocking requires at least one node group"
+        );
+
+        let workload = DaPeerBlockingWorkload::new(
+            self.peers,
+            self.block_duration,
+            self.interval,
+            self.include_validators,
+            self.include_executors,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosDowntimeBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    downtime: Duration,
+    interval: Duration,
+    catch_up_timeout: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl ChaosDowntimeBuilder {
+    #[must_use]
+    /// How long a stopped node stays offline.
+    pub fn downtime(mut self, downtime: Duration) -> Self {
+        assert!(!downtime.is_zero(), "chaos downtime must be non-zero");
+        self.downtime = downtime;
+        self
+    }
+
+    #[must_use]
+    /// How long to wait between outages.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "chaos downtime interval must be non-zero"
+        );
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// How long a restarted node may take to reach its peers' height.
+    pub fn catch_up_timeout(mut self, timeout: Duration) -> Self {
+        assert!(
+            !timeout.is_zero(),
+            "chaos downtime catch-up timeout must be non-zero"
+        );
+        self.catch_up_timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Include validators in the downtime target set.
+    pub const fn include_validators(mut self, enabled: bool) -> Self {
+        self.include_validators = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Include executors in the downtime target set.
+    pub const fn include_executors(mut self, enabled: bool) -> Self {
+        self.include_executors = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos downtime workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            self.include_validators || self.include_executors,
+            "chaos downtime requires at least one node group"
+        );
+
+        let workload = NodeDowntimeWorkload::new(
+            self.downtime,
+            self.interval,
+            self.catch_up_timeout,
+            self.include_validators,
+            self.include_executors,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosDiskPressureBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    fill_percent: u8,
+    duration: Duration,
+    interval: Duration,
+    recovery_timeout: Duration,
+    path: Option<String>,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl ChaosDiskPressureBuilder {
+    #[must_use]
+    /// How full the filesystem is made, in percent (1-100).
+    pub fn fill_percent(mut self, percent: u8) -> Self {
+        assert!(
+            (1..=100).contains(&percent),
+            "chaos disk pressure fill percent must be within 1..=100"
+        );
+        self.fill_percent = percent;
+        self
+    }
+
+    #[must_use]
+    /// How long the disk stays full.
+    pub fn duration(mut self, duration: Duration) -> Self {
+        assert!(
+            !duration.is_zero(),
+            "chaos disk pressure duration must be non-zero"
+        );
+        self.duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// How long to wait between pressure windows.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "chaos disk pressure interval must be non-zero"
+        );
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// How long a node may take to reach its peers' height once space is
+    /// released.
+    pub fn recovery_timeout(mut self, timeout: Duration) -> Self {
+        assert!(
+            !timeout.is_zero(),
+            "chaos disk pressure recovery timeout must be non-zero"
+        );
+        self.recovery_timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    /// Directory inside the node container to fill instead of the blob
+    /// storage directory; host bind mounts are refused.
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    #[must_use]
+    /// Include validators in the disk pressure target set.
+    pub const fn include_validators(mut self, enabled: bool) -> Self {
+        self.include_validators = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Include executors in the disk pressure target set.
+    pub const fn include_executors(mut self, enabled: bool) -> Self {
+        self.include_executors = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos disk pressure workload and attach it to the
+    /// scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            self.include_validators || self.include_executors,
+            "chaos disk pressure requires at least one node group"
+        );
+
+        let mut workload = DiskPressureWorkload::new(
+            self.fill_percent,
+            self.duration,
+            self.interval,
+            self.recovery_timeout,
+            self.include_validators,
+            self.include_executors,
+        );
+        if let Some(path) = self.path {
+            workload = workload.with_path(path);
+        }
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosPartitionBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    groups: usize,
+    partition_duration: Duration,
+    heal_duration: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl ChaosPartitionBuilder {
+    #[must_use]
+    /// Number of isolated groups to split the nodes into.
+    pub fn groups(mut self, groups: usize) -> Self {
+        assert!(groups >= 2, "chaos partition requires at least two groups");
+        self.groups = groups;
+        self
+    }
+
+    #[must_use]
+    /// How long each partition is held before healing.
+    pub fn partition_duration(mut self, duration: Duration) -> Self {
+        assert!(
+            !duration.is_zero(),
+            "chaos partition duration must be non-zero"
+        );
+        self.partition_duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// How long the network stays healed between partitions.
+    pub fn heal_duration(mut self, duration: Duration) -> Self {
+        assert!(
+            !duration.is_zero(),
+            "chaos partition heal duration must be non-zero"
+        );
+        self.heal_duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// Include validators in the partitioned node set.
+    pub const fn include_validators(mut self, enabled: bool) -> Self {
+        self.include_validators = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Include executors in the partitioned node set.
+    pub const fn include_executors(mut self, enabled: bool) -> Self {
+        self.include_executors = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos partition workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            self.include_validators || self.include_executors,
+            "chaos partition requires at least one node group"
+        );
+
+        let workload = NetworkPartitionWorkload::new(
+            self.groups,
+            self.partition_duration,
+            self.heal_duration,
+            self.include_validators,
+            self.include_executors,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosLatencyBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    target: NetworkShaping,
+    ramp_steps: usize,
+    step_duration: Duration,
+    schedule: Option<Vec<NetworkShaping>>,
+    nodes: Option<usize>,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl ChaosLatencyBuilder {
+    #[must_use]
+    /// Target one-way latency added to outgoing packets.
+    pub const fn latency(mut self, latency: Duration) -> Self {
+        self.target.latency = latency;
+        self
+    }
+
+    #[must_use]
+    /// Random variation applied on top of the latency.
+    pub const fn jitter(mut self, jitter: Duration) -> Self {
+        self.target.jitter = jitter;
+        self
+    }
+
+    #[must_use]
+    /// Target percentage of outgoing packets to drop.
+    pub fn packet_loss(mut self, percent: f64) -> Self {
+        assert!(
+            (0.0..=100.0).contains(&percent),
+            "chaos latency packet loss must be between 0 and 100 percent"
+        );
+        self.target.loss_percent = percent;
+        self
+    }
+
+    #[must_use]
+    /// Reach the target shaping in `steps` equal increments.
+    pub fn ramp_steps(mut self, steps: usize) -> Self {
+        assert!(steps > 0, "chaos latency ramp steps must be non-zero");
+        self.ramp_steps = steps;
+        self
+    }
+
+    #[must_use]
+    /// How long each ramp step is held before moving to the next one.
+    pub fn step_duration(mut self, duration: Duration) -> Self {
+        assert!(
+            !duration.is_zero(),
+            "chaos latency step duration must be non-zero"
+        );
+        self.step_duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// Use an explicit shaping schedule instead of a linear ramp.
+    pub fn schedule(mut self, schedule: Vec<NetworkShaping>) -> Self {
+        assert!(
+            !schedule.is_empty(),
+            "chaos latency schedule must not be empty"
+        );
+        self.schedule = Some(schedule);
+        self
+    }
+
+    #[must_use]
+    /// Shape only a random subset of `count` eligible nodes.
+    pub fn nodes(mut self, count: usize) -> Self {
+        assert!(count > 0, "chaos latency node count must be non-zero");
+        self.nodes = Some(count);
+        self
+    }
+
+    #[must_use]
+    /// Include validators in the shaped node set.
+    pub const fn include_validators(mut self, enabled: bool) -> Self {
+        self.include_validators = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Include executors in the shaped node set.
+    pub const fn include_executors(mut self, enabled: bool) -> Self {
+        self.include_executors = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos latency workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            self.include_validators || self.include_executors,
+            "chaos latency requires at least one node group"
+        );
+
+        let schedule = self
+            .schedule
+            .take()
+            .unwrap_or_else(|| NetworkLatencyWorkload::linear_ramp(self.target, self.ramp_steps));
+        tracing::info!(
+            steps = schedule.len(),
+            step_duration_secs = self.step_duration.as_secs(),
+            nodes = self.nodes,
+            "attaching chaos latency workload"
+        );
+        let workload = NetworkLatencyWorkload::new(
+            schedule,
+            self.step_duration,
+            self.nodes,
+            self.include_validators,
+            self.include_executors,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosResizeBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    steps: Vec<ResizeStep>,
+    sync_timeout: Duration,
+    max_lag: Option<u64>,
+}
+
+impl ChaosResizeBuilder {
+    #[must_use]
+    /// Add a validator `at` the given offset into the run.
+    pub fn add_validator(self, at: Duration) -> Self {
+        self.step(at, ResizeAction::Add(NodeRole::Validator))
+    }
+
+    #[must_use]
+    /// Add an executor `at` the given offset into the run.
+    pub fn add_executor(self, at: Duration) -> Self {
+        self.step(at, ResizeAction::Add(NodeRole::Executor))
+    }
+
+    #[must_use]
+    /// Remove `target` `at` the given offset into the run. Added nodes take
+    /// the next index of their role, so they can be removed again too.
+    pub fn remove(self, target: NodeTarget, at: Duration) -> Self {
+        self.step(at, ResizeAction::Remove(target))
+    }
+
+    #[must_use]
+    /// How long an added node may take to reach the cluster's height.
+    pub fn sync_timeout(mut self, timeout: Duration) -> Self {
+        assert!(
+            !timeout.is_zero(),
+            "chaos resize sync timeout must be non-zero"
+        );
+        self.sync_timeout = timeout;
+        self
+    }
+
+    #[must_use]
+    /// How many blocks added nodes may trail the tip at the end of the run.
+    /// Defaults to the consensus security parameter.
+    pub const fn max_lag(mut self, blocks: u64) -> Self {
+        self.max_lag = Some(blocks);
+        self
+    }
+
+    fn step(mut self, at: Duration, action: ResizeAction) -> Self {
+        self.steps.push(ResizeStep { at, action });
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos resize workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        assert!(
+            !self.steps.is_empty(),
+            "chaos resize requires at least one add or remove step"
+        );
+
+        let workload = ClusterResizeWorkload::new(self.steps, self.sync_timeout, self.max_lag);
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
+
+pub struct ChaosForkBuilder {
+    builder: CoreScenarioBuilder<NodeControlCapability>,
+    isolation: ForkIsolation,
+    isolation_duration: Duration,
+    interval: Duration,
+    convergence_blocks: Option<u64>,
+    validator: Option<usize>,
+}
+
+impl ChaosForkBuilder {
+    #[must_use]
+    /// Isolate by partition (default) or by pausing the validator.
+    pub const fn isolation(mut self, isolation: ForkIsolation) -> Self {
+        self.isolation = isolation;
+        self
+    }
+
+    #[must_use]
+    /// How long the validator stays isolated; a few slots at least, so it
+    /// has a chance to build its own blocks.
+    pub fn isolation_duration(mut self, duration: Duration) -> Self {
+        assert!(
+            !duration.is_zero(),
+            "chaos fork isolation duration must be non-zero"
+        );
+        self.isolation_duration = duration;
+        self
+    }
+
+    #[must_use]
+    /// Time with full connectivity before each isolation.
+    pub const fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    #[must_use]
+    /// Blocks the cluster may grow past the reconnection tip before the
+    /// validator must have converged. Defaults to the consensus security
+    /// parameter.
+    pub const fn convergence_blocks(mut self, blocks: u64) -> Self {
+        self.convergence_blocks = Some(blocks);
+        self
+    }
+
+    #[must_use]
+    /// Always isolate validator `index` instead of a random one.
+    pub const fn validator(mut self, index: usize) -> Self {
+        self.validator = Some(index);
+        self
+    }
+
+    #[must_use]
+    /// Finalize the chaos fork workload and attach it to the scenario.
+    pub fn apply(mut self) -> CoreScenarioBuilder<NodeControlCapability> {
+        let workload = ForkInjectionWorkload::new(
+            self.isolation,
+            self.isolation_duration,
+            self.interval,
+            self.convergence_blocks,
+            self.validator,
+        );
+        self.builder = self.builder.with_workload(workload);
+        self.builder
+    }
+}
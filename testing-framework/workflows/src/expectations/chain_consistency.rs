@@ -0,0 +1,279 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use nomos_core::header::HeaderId;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, PeriodicExpectation, RunContext, RunMetrics},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+
+use super::violations;
+
+/// Forked headers listed per node in failure reports.
+const MAX_REPORTED_HEADERS: usize = 5;
+
+/// Checks the common prefix property across every node's chain at the end of
+/// the run.
+///
+/// Each node's headers between its tip and LIB are compared with the chain of
+/// the highest node. A node may only disagree on its last
+/// `security_param` blocks; a deeper fork fails the run and reports the last
+/// common header and the headers each side has beyond it. This catches
+/// consensus splits where every node still makes progress, which
+/// [`super::ConsensusLiveness`] cannot see.
+///
+/// The check also runs at every checkpoint of soak scenarios, so a fork is
+/// reported when it happens rather than hours later.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChainConsistencyExpectation {
+    max_fork_depth: Option<u64>,
+}
+
+#[derive(Debug, Error)]
+enum ChainConsistencyIssue {
+    #[error("{node} headers request failed: {source}")]
+    RequestFailed {
+        node: String,
+        #[source]
+        source: DynError,
+    },
+    #[error(
+        "{node} forked {depth} blocks deep (allowed {allowed}) from {reference} after {fork_point}; {node} has {forked:?}, {reference} has {reference_forked:?}"
+    )]
+    DeepFork {
+        node: String,
+        reference: String,
+        fork_point: String,
+        depth: usize,
+        allowed: u64,
+        forked: Vec<String>,
+        reference_forked: Vec<String>,
+    },
+    #[error("{node} shares no header with {reference} down to its LIB {lib}")]
+    NoCommonPrefix {
+        node: String,
+        reference: String,
+        lib: String,
+    },
+}
+
+#[derive(Debug, Error)]
+enum ChainConsistencyError {
+    #[error("chain consistency requires at least one validator or executor")]
+    MissingParticipants,
+    #[error("chain consistency violated:\n{0}")]
+    Violations(String),
+}
+
+impl ChainConsistencyExpectation {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            max_fork_depth: None,
+        }
+    }
+
+    #[must_use]
+    /// Override how many trailing blocks nodes may disagree on. Defaults to
+    /// the topology's consensus security parameter.
+    pub const fn with_max_fork_depth(mut self, depth: u64) -> Self {
+        self.max_fork_depth = Some(depth);
+        self
+    }
+
+    async fn sample(
+        label: String,
+        client: &ApiClient,
+    ) -> Result<ChainSample, ChainConsistencyIssue> {
+        let failed = |source: DynError| ChainConsistencyIssue::RequestFailed {
+            node: label.clone(),
+            source,
+        };
+        let info = client
+            .consensus_info()
+            .await
+            .map_err(|err| failed(err.into()))?;
+        let mut headers = client
+            .consensus_headers(Some(info.tip), None)
+            .await
+            .map_err(|err| failed(err.into()))?;
+        // Store headers oldest first.
+        if headers.first() == Some(&info.tip) {
+            headers.reverse();
+        }
+        Ok(ChainSample {
+            label,
+            height: info.height,
+            tip: info.tip,
+            lib: info.lib,
+            headers,
+        })
+    }
+
+    async fn compare(
+        &self,
+        reference: &ChainSample,
+        reference_client: &ApiClient,
+        node: &ChainSample,
+    ) -> Result<(), ChainConsistencyIssue> {
+        let allowed = self.max_fork_depth.unwrap_or(0);
+        let mut reference_headers = reference.headers.clone();
+        if !reference_headers.contains(&node.lib) {
+            // The node lags behind the reference's LIB: extend the reference
+            // window down to the node's LIB so the two overlap.
+            if let Ok(mut extended) = reference_client
+                .consensus_headers(Some(reference.tip), Some(node.lib))
+                .await
+            {
+                if extended.first() == Some(&reference.tip) {
+                    extended.reverse();
+                }
+                reference_headers = extended;
+            }
+        }
+
+        let reference_set: HashSet<HeaderId> = reference_headers.iter().copied().collect();
+        let Some(common) = node
+            .headers
+            .iter()
+            .rposition(|header| reference_set.contains(header))
+        else {
+            return Err(ChainConsistencyIssue::NoCommonPrefix {
+                node: node.label.clone(),
+                reference: reference.label.clone(),
+                lib: format!("{:?}", node.lib),
+            });
+        };
+
+        let fork_point = node.headers[common];
+        let forked = &node.headers[common + 1..];
+        let reference_forked = reference_headers
+            .iter()
+            .position(|header| *header == fork_point)
+            .map_or(&[][..], |index| &reference_headers[index + 1..]);
+
+        if forked.is_empty() {
+            return Ok(());
+        }
+        if forked.len() as u64 <= allowed {
+            tracing::debug!(
+                node = %node.label,
+                depth = forked.len(),
+                "tolerating shallow fork within security parameter"
+            );
+            return Ok(());
+        }
+
+        Err(ChainConsistencyIssue::DeepFork {
+            node: node.label.clone(),
+            reference: reference.label.clone(),
+            fork_point: format!("{fork_point:?}"),
+            depth: forked.len(),
+            allowed,
+            forked: summarize(forked),
+            reference_forked: summarize(reference_forked),
+        })
+    }
+}
+
+#[async_trait]
+impl Expectation for ChainConsistencyExpectation {
+    fn name(&self) -> &'static str {
+        "chain_consistency"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["consensus"]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.max_fork_depth.is_none() {
+            self.max_fork_depth = Some(u64::from(
+                descriptors.config().consensus_params.security_param.get(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let clients: Vec<_> = ctx.node_clients().labeled_clients().collect();
+        if clients.is_empty() {
+            return Err(ChainConsistencyError::MissingParticipants.into());
+        }
+
+        let mut samples = Vec::with_capacity(clients.len());
+        let mut issues = Vec::new();
+        for (label, _, client) in &clients {
+            match Self::sample(label.clone(), client).await {
+                Ok(sample) => samples.push((sample, *client)),
+                Err(issue) => issues.push(issue),
+            }
+        }
+
+        if let Some(reference_index) = samples
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, (sample, _))| sample.height)
+            .map(|(index, _)| index)
+        {
+            let (reference, reference_client) = &samples[reference_index];
+            for (index, (node, _)) in samples.iter().enumerate() {
+                if index == reference_index {
+                    continue;
+                }
+                if let Err(issue) = self.compare(reference, reference_client, node).await {
+                    issues.push(issue);
+                }
+            }
+            tracing::debug!(
+                reference = %reference.label,
+                height = reference.height,
+                nodes = samples.len(),
+                "chain consistency compared against highest node"
+            );
+        }
+
+        let Some(details) = violations("chain consistency", &issues) else {
+            tracing::info!(
+                nodes = samples.len(),
+                "chain consistency expectation satisfied"
+            );
+            return Ok(());
+        };
+        Err(ChainConsistencyError::Violations(details).into())
+    }
+
+    fn as_periodic(&mut self) -> Option<&mut dyn PeriodicExpectation> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl PeriodicExpectation for ChainConsistencyExpectation {
+    async fn checkpoint(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.evaluate(ctx).await
+    }
+}
+
+struct ChainSample {
+    label: String,
+    height: u64,
+    tip: HeaderId,
+    lib: HeaderId,
+    /// Headers from LIB to tip, oldest first.
+    headers: Vec<HeaderId>,
+}
+
+fn summarize(headers: &[HeaderId]) -> Vec<String> {
+    headers
+        .iter()
+        .take(MAX_REPORTED_HEADERS)
+        .map(|header| format!("{header:?}"))
+        .collect()
+}
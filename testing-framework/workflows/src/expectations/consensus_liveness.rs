@@ -36,6 +36,10 @@ impl Expectation for ConsensusLiveness {
         "consensus_liveness"
     }
 
+    fn tags(&self) -> &[&'static str] {
+        &["consensus"]
+    }
+
     async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
         Self::ensure_participants(ctx)?;
         let target_hint = Self::target_blocks(ctx);
@@ -98,23 +102,26 @@ impl ConsensusLiveness {
     }
 
     async fn collect_results(ctx: &RunContext) -> LivenessCheck {
-        let clients: Vec<_> = ctx.node_clients().all_clients().collect();
+        let clients: Vec<_> = ctx.node_clients().labeled_clients().collect();
         let mut samples = Vec::with_capacity(clients.len());
         let mut issues = Vec::new();
 
-        for (idx, client) in clients.iter().enumerate() {
+        for (label, _, client) in clients {
             for attempt in 0..REQUEST_RETRIES {
                 match Self::fetch_cluster_info(client).await {
                     Ok((height, tip)) => {
-                        let label = format!("node-{idx}");
                         tracing::debug!(node = %label, height, tip = ?tip, attempt, "consensus_info collected");
-                        samples.push(NodeSample { label, height, tip });
+                        samples.push(NodeSample {
+                            label: label.clone(),
+                            height,
+                            tip,
+                        });
                         break;
                     }
                     Err(err) if attempt + 1 == REQUEST_RETRIES => {
-                        tracing::warn!(node = %format!("node-{idx}"), %err, "consensus_info failed after retries");
+                        tracing::warn!(node = %label, %err, "consensus_info failed after retries");
                         issues.push(ConsensusLivenessIssue::RequestFailed {
-                            node: format!("node-{idx}"),
+                            node: label.clone(),
                             source: err,
                         });
                     }
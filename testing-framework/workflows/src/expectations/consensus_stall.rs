@@ -0,0 +1,112 @@
+use async_trait::async_trait;
+use testing_framework_core::scenario::{
+    DynError, Expectation, RunContext, StallDetector, StallEvent,
+};
+use thiserror::Error;
+
+const EXPECTATION_NAME: &str = "consensus_stall";
+
+/// Fails the run when a node's height stops advancing for longer than a number
+/// of expected block intervals (the slot duration divided by the active slot
+/// coefficient).
+///
+/// A [`StallDetector`] polls every node once per slot during the run. Stalls
+/// that resolve before evaluation still count; nodes taken down on purpose
+/// (chaos workloads) stall too, so allow for them with
+/// [`Self::with_max_stalls`].
+#[derive(Clone)]
+pub struct ConsensusStallExpectation {
+    stall_blocks: u64,
+    max_stalls: usize,
+    detector: Option<StallDetector>,
+}
+
+#[derive(Debug, Error)]
+enum ConsensusStallError {
+    #[error("consensus progress was not captured")]
+    NotCaptured,
+    #[error(
+        "{count} consensus stall(s) longer than {blocks} block intervals (allowed {allowed}):\n{details}"
+    )]
+    Stalls {
+        count: usize,
+        blocks: u64,
+        allowed: usize,
+        details: String,
+    },
+}
+
+impl ConsensusStallExpectation {
+    #[must_use]
+    /// Fail when a node makes no progress for longer than `stall_blocks`
+    /// expected block intervals.
+    pub const fn new(stall_blocks: u64) -> Self {
+        Self {
+            stall_blocks,
+            max_stalls: 0,
+            detector: None,
+        }
+    }
+
+    #[must_use]
+    /// Number of stalls tolerated before the expectation fails.
+    pub const fn with_max_stalls(mut self, max_stalls: usize) -> Self {
+        self.max_stalls = max_stalls;
+        self
+    }
+}
+
+#[async_trait]
+impl Expectation for ConsensusStallExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["consensus"]
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.detector = Some(StallDetector::for_run(ctx, self.stall_blocks));
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let detector = self
+            .detector
+            .take()
+            .ok_or(ConsensusStallError::NotCaptured)?;
+        detector.stop();
+        detector.report_to(ctx.report(), EXPECTATION_NAME);
+
+        let stalls: Vec<String> = detector
+            .events()
+            .into_iter()
+            .filter_map(|event| match event {
+                StallEvent::Stalled {
+                    node,
+                    height,
+                    since,
+                    ..
+                } => Some(format!("- {node} stuck at height {height} from {since:?}")),
+                StallEvent::Resumed { .. } => None,
+            })
+            .collect();
+        if stalls.len() <= self.max_stalls {
+            tracing::info!(
+                stalls = stalls.len(),
+                longest = ?detector.longest_stall(),
+                "consensus stall expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        Err(ConsensusStallError::Stalls {
+            count: stalls.len(),
+            blocks: self.stall_blocks,
+            allowed: self.max_stalls,
+            details: stalls.join("\n"),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::{
+    scenario::{DynError, Expectation, RunContext, RunMetrics, StatsSampler},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+
+const EXPECTATION_NAME: &str = "da_connectivity";
+const DEFAULT_SAMPLE_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Checks that DA subnet connectivity never dropped below a threshold for
+/// longer than an allowed outage.
+///
+/// A [`StatsSampler`] polls every node's balancer stats during the run. A node
+/// fails when it spends more than `max_outage` in a row with fewer connected
+/// subnetworks than the threshold, which defaults to the subnet threshold the
+/// nodes use for their own readiness (`num_samples`).
+#[derive(Clone)]
+pub struct DaConnectivityExpectation {
+    max_outage: Duration,
+    threshold: Option<usize>,
+    sample_interval: Duration,
+    sampler: Option<StatsSampler>,
+}
+
+#[derive(Debug, Error)]
+enum DaConnectivityError {
+    #[error("DA stats were not captured")]
+    NotCaptured,
+    #[error("DA connectivity dropped for too long:\n{0}")]
+    Violations(String),
+}
+
+impl DaConnectivityExpectation {
+    #[must_use]
+    pub const fn new(max_outage: Duration) -> Self {
+        Self {
+            max_outage,
+            threshold: None,
+            sample_interval: DEFAULT_SAMPLE_INTERVAL,
+            sampler: None,
+        }
+    }
+
+    #[must_use]
+    /// Minimum number of connected subnetworks each node must keep.
+    pub const fn with_threshold(mut self, subnetworks: usize) -> Self {
+        self.threshold = Some(subnetworks);
+        self
+    }
+
+    #[must_use]
+    /// How often DA stats are polled; outages shorter than this may go
+    /// unnoticed (panics on zero).
+    pub const fn with_sample_interval(mut self, interval: Duration) -> Self {
+        assert!(
+            !interval.is_zero(),
+            "DA connectivity sample interval must be non-zero"
+        );
+        self.sample_interval = interval;
+        self
+    }
+}
+
+#[async_trait]
+impl Expectation for DaConnectivityExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.threshold.is_none() {
+            self.threshold = Some(usize::from(descriptors.config().da_params.num_samples));
+        }
+        Ok(())
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        self.sampler = Some(StatsSampler::spawn(
+            ctx.node_clients(),
+            self.sample_interval,
+        ));
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let sampler = self
+            .sampler
+            .take()
+            .ok_or(DaConnectivityError::NotCaptured)?;
+        sampler.stop();
+        let threshold = self.threshold.unwrap_or(0);
+
+        let mut issues = Vec::new();
+        for (label, _, _) in ctx.node_clients().labeled_clients() {
+            let outage = sampler.longest_below(&label, threshold);
+            tracing::debug!(node = %label, ?outage, threshold, "DA connectivity sampled");
+            if outage > self.max_outage {
+                issues.push(format!(
+                    "- {label} had fewer than {threshold} connected subnetworks for {outage:?} (allowed {:?})",
+                    self.max_outage
+                ));
+            }
+        }
+
+        if issues.is_empty() {
+            tracing::info!(
+                samples = sampler.samples().len(),
+                threshold,
+                "DA connectivity expectation satisfied"
+            );
+            Ok(())
+        } else {
+            Err(DaConnectivityError::Violations(issues.join("\n")).into())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "sample interval must be non-zero")]
+    fn zero_sample_interval_is_rejected() {
+        let _ = DaConnectivityExpectation::new(Duration::from_secs(30))
+            .with_sample_interval(Duration::ZERO);
+    }
+}
@@ -0,0 +1,465 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use kzgrs_backend::common::ShareIndex;
+use nomos_core::da::BlobId;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, NodeTarget, RunContext, RunMetrics},
+    topology::generation::{GeneratedTopology, NodeRole},
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    task::JoinHandle,
+    time::{Instant, sleep},
+};
+
+use crate::workloads::da::{LandedBlob, landed_blobs};
+
+const EXPECTATION_NAME: &str = "da_replication";
+const DEFAULT_SAMPLE_SIZE: usize = 4;
+/// Time a blob gets after landing for its shares to reach the subnet peers
+/// before it is checked.
+const REPLICATION_GRACE: Duration = Duration::from_secs(5);
+const CHECK_ATTEMPTS: usize = 3;
+const CHECK_RETRY_DELAY: Duration = Duration::from_secs(2);
+const MAX_REPORTED_FAILURES: usize = 5;
+
+#[derive(Debug, Error)]
+enum DaReplicationError {
+    #[error("DA replication expectation was not initialised with the topology")]
+    NotInitialised,
+    #[error(
+        "minimum replica count must be between 1 and the {members} member(s) of DA subnet {subnet}"
+    )]
+    InvalidMinReplicas { subnet: usize, members: usize },
+    #[error("DA replication sample size must be at least one blob")]
+    EmptySample,
+    #[error("no DA blob landed early enough to check its replication")]
+    NoBlobs,
+    #[error("{count} share(s) held by fewer than {min_replicas} node(s):\n{details}")]
+    UnderReplicated {
+        count: usize,
+        min_replicas: usize,
+        details: String,
+    },
+}
+
+/// Checks that the shares of blobs that landed during the run are stored by
+/// at least `min_replicas` members of their subnetwork each.
+///
+/// Blobs are collected from the block feed. At evaluation time the most
+/// recent blobs that landed at least `REPLICATION_GRACE` ago are checked:
+/// every reachable node is asked which shares of the blob it stores, and
+/// each share index (one per subnetwork) must be held by `min_replicas`
+/// members of that subnetwork. Members stopped by chaos workloads or not
+/// joined yet are left out, lowering the requirement to the members still
+/// reachable. Checks are retried a few times to give replication time to
+/// catch up. Older blobs are skipped since they may already be pruned.
+#[derive(Debug)]
+pub struct DaReplicationExpectation {
+    min_replicas: usize,
+    sample_size: usize,
+    /// Members of each subnetwork, indexed by share index.
+    subnets: Option<Vec<Vec<NodeTarget>>>,
+    observed: Arc<Mutex<Vec<LandedBlob<Instant>>>>,
+    capture: Option<JoinHandle<()>>,
+}
+
+impl DaReplicationExpectation {
+    #[must_use]
+    pub fn new(min_replicas: usize) -> Self {
+        Self {
+            min_replicas,
+            sample_size: DEFAULT_SAMPLE_SIZE,
+            subnets: None,
+            observed: Arc::new(Mutex::new(Vec::new())),
+            capture: None,
+        }
+    }
+
+    #[must_use]
+    /// Number of blobs checked at evaluation time; must be at least one.
+    pub const fn with_sample_size(mut self, blobs: usize) -> Self {
+        self.sample_size = blobs;
+        self
+    }
+
+    fn settled_sample(&self) -> Vec<BlobId> {
+        let now = Instant::now();
+        let observed = self
+            .observed
+            .lock()
+            .expect("replication state lock poisoned");
+        observed
+            .iter()
+            .rev()
+            .filter(|blob| blob.landed_at + REPLICATION_GRACE <= now)
+            .take(self.sample_size)
+            .map(|blob| blob.blob_id)
+            .collect()
+    }
+
+    /// Describe each share of `blob_id` held by fewer than the required
+    /// number of reachable subnet members.
+    async fn check_blob(
+        &self,
+        nodes: &[(NodeTarget, &ApiClient)],
+        subnets: &[Vec<NodeTarget>],
+        blob_id: BlobId,
+    ) -> Vec<String> {
+        let holdings = join_all(
+            nodes
+                .iter()
+                .map(|(_, client)| client.da_share_indices(blob_id)),
+        )
+        .await;
+        let holdings = nodes
+            .iter()
+            .zip(holdings)
+            .map(|((target, _), held)| (*target, held.map_err(|err| err.to_string())))
+            .collect::<HashMap<_, _>>();
+        replication_failures(blob_id, subnets, &holdings, self.min_replicas)
+    }
+}
+
+impl Drop for DaReplicationExpectation {
+    fn drop(&mut self) {
+        if let Some(capture) = self.capture.take() {
+            capture.abort();
+        }
+    }
+}
+
+/// Members of each subnetwork, indexed by share index, as assigned in the
+/// generated DA configs.
+fn subnet_members(descriptors: &GeneratedTopology) -> Vec<Vec<NodeTarget>> {
+    let num_subnets = descriptors.config().da_params.num_subnets;
+    (0..num_subnets)
+        .map(|subnet| {
+            descriptors
+                .nodes()
+                .filter(|node| node.general.da_config.verifier_index.contains(&subnet))
+                .map(|node| match node.role() {
+                    NodeRole::Validator => NodeTarget::Validator(node.index()),
+                    NodeRole::Executor => NodeTarget::Executor(node.index()),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Nodes that answer a `consensus_info` request; nodes stopped by chaos
+/// workloads or not joined yet are skipped.
+async fn reachable_nodes(ctx: &RunContext) -> Vec<(NodeTarget, &ApiClient)> {
+    let nodes = ctx.node_clients().targeted_clients().collect::<Vec<_>>();
+    let answers = join_all(nodes.iter().map(|(_, client)| client.consensus_info())).await;
+    nodes
+        .into_iter()
+        .zip(answers)
+        .filter_map(|((target, client), answer)| match answer {
+            Ok(_) => Some((target, client)),
+            Err(err) => {
+                tracing::debug!(%target, %err, "skipping unreachable node for DA replication");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Describe each share of `blob_id` held by fewer than
+/// `min(min_replicas, reachable members)` members of its subnetwork, given
+/// the shares every reachable node reported. Holders outside the subnetwork
+/// do not count.
+fn replication_failures(
+    blob_id: BlobId,
+    subnets: &[Vec<NodeTarget>],
+    holdings: &HashMap<NodeTarget, Result<HashSet<ShareIndex>, String>>,
+    min_replicas: usize,
+) -> Vec<String> {
+    let blob = hex::encode(blob_id);
+    let mut failures = holdings
+        .iter()
+        .filter_map(|(target, held)| {
+            held.as_ref()
+                .err()
+                .map(|err| format!("- {target}: could not query shares of blob {blob}: {err}"))
+        })
+        .collect::<Vec<_>>();
+    failures.sort();
+
+    for (index, members) in subnets.iter().enumerate() {
+        let reachable = members
+            .iter()
+            .filter(|member| holdings.contains_key(*member))
+            .collect::<Vec<_>>();
+        if reachable.is_empty() {
+            failures.push(format!(
+                "- blob {blob} share {index}: no member of its subnet is reachable"
+            ));
+            continue;
+        }
+        let holders = reachable
+            .iter()
+            .filter(|member| {
+                holdings[**member].as_ref().is_ok_and(|held| {
+                    ShareIndex::try_from(index).is_ok_and(|index| held.contains(&index))
+                })
+            })
+            .count();
+        let required = min_replicas.min(reachable.len());
+        if holders < required {
+            failures.push(format!(
+                "- blob {blob} share {index}: held by {holders} of {} reachable subnet member(s), expected {required}",
+                reachable.len()
+            ));
+        }
+    }
+    failures
+}
+
+#[async_trait]
+impl Expectation for DaReplicationExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.sample_size == 0 {
+            return Err(DaReplicationError::EmptySample.into());
+        }
+        let subnets = subnet_members(descriptors);
+        if let Some((subnet, members)) = subnets
+            .iter()
+            .enumerate()
+            .find(|(_, members)| self.min_replicas == 0 || self.min_replicas > members.len())
+        {
+            return Err(DaReplicationError::InvalidMinReplicas {
+                subnet,
+                members: members.len(),
+            }
+            .into());
+        }
+        self.subnets = Some(subnets);
+        Ok(())
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.capture.is_some() {
+            return Ok(());
+        }
+
+        let mut receiver = ctx.block_feed().subscribe();
+        let observed = Arc::clone(&self.observed);
+        self.capture = Some(tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => {
+                        let landed = landed_blobs(&record, Instant::now());
+                        if !landed.is_empty() {
+                            observed
+                                .lock()
+                                .expect("replication state lock poisoned")
+                                .extend(landed);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "DA replication capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if let Some(capture) = self.capture.take() {
+            capture.abort();
+        }
+        let subnets = self
+            .subnets
+            .as_deref()
+            .ok_or(DaReplicationError::NotInitialised)?;
+        let sample = self.settled_sample();
+        if sample.is_empty() {
+            return Err(DaReplicationError::NoBlobs.into());
+        }
+
+        let mut failures = Vec::new();
+        for attempt in 1..=CHECK_ATTEMPTS {
+            let nodes = reachable_nodes(ctx).await;
+            failures = join_all(
+                sample
+                    .iter()
+                    .map(|blob_id| self.check_blob(&nodes, subnets, *blob_id)),
+            )
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+            if failures.is_empty() {
+                break;
+            }
+            tracing::debug!(
+                attempt,
+                failures = failures.len(),
+                "DA shares not fully replicated yet"
+            );
+            if attempt < CHECK_ATTEMPTS {
+                sleep(CHECK_RETRY_DELAY).await;
+            }
+        }
+
+        ctx.report()
+            .increment(self.name(), "blobs_checked", sample.len() as u64);
+        if failures.is_empty() {
+            tracing::info!(
+                blobs = sample.len(),
+                min_replicas = self.min_replicas,
+                "DA replication expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        let mut details = failures
+            .iter()
+            .take(MAX_REPORTED_FAILURES)
+            .cloned()
+            .collect::<Vec<_>>();
+        if failures.len() > MAX_REPORTED_FAILURES {
+            details.push(format!(
+                "- ... and {} more",
+                failures.len() - MAX_REPORTED_FAILURES
+            ));
+        }
+        Err(DaReplicationError::UnderReplicated {
+            count: failures.len(),
+            min_replicas: self.min_replicas,
+            details: details.join("\n"),
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_core::scenario::{Scenario, ScenarioBuilder};
+
+    use super::*;
+
+    const BLOB: BlobId = [7; 32];
+
+    fn held(indices: &[ShareIndex]) -> Result<HashSet<ShareIndex>, String> {
+        Ok(indices.iter().copied().collect())
+    }
+
+    fn subnets() -> Vec<Vec<NodeTarget>> {
+        vec![
+            vec![NodeTarget::Validator(0), NodeTarget::Validator(1)],
+            vec![NodeTarget::Validator(1), NodeTarget::Executor(0)],
+        ]
+    }
+
+    #[test]
+    fn shares_must_be_held_by_subnet_members() {
+        let holdings = HashMap::from([
+            (NodeTarget::Validator(0), held(&[0, 1])),
+            (NodeTarget::Validator(1), held(&[0])),
+            (NodeTarget::Executor(0), held(&[])),
+        ]);
+
+        let failures = replication_failures(BLOB, &subnets(), &holdings, 2);
+        // Validator 0 holds share 1 but is not a member of subnet 1.
+        assert_eq!(failures.len(), 1, "{failures:?}");
+        assert!(
+            failures[0].contains("share 1: held by 0 of 2"),
+            "{failures:?}"
+        );
+    }
+
+    #[test]
+    fn unreachable_members_lower_the_requirement() {
+        let holdings = HashMap::from([
+            (NodeTarget::Validator(0), held(&[0])),
+            (NodeTarget::Validator(1), held(&[0, 1])),
+        ]);
+
+        assert!(replication_failures(BLOB, &subnets(), &holdings, 2).is_empty());
+
+        let holdings = HashMap::from([(NodeTarget::Validator(0), held(&[0]))]);
+        let failures = replication_failures(BLOB, &subnets(), &holdings, 2);
+        assert_eq!(failures.len(), 1, "{failures:?}");
+        assert!(
+            failures[0].contains("share 1: no member of its subnet is reachable"),
+            "{failures:?}"
+        );
+    }
+
+    #[test]
+    fn query_errors_of_reachable_nodes_are_reported() {
+        let holdings = HashMap::from([
+            (NodeTarget::Validator(0), held(&[0])),
+            (NodeTarget::Validator(1), Err("connection reset".to_owned())),
+            (NodeTarget::Executor(0), held(&[1])),
+        ]);
+
+        let failures = replication_failures(BLOB, &subnets(), &holdings, 1);
+        assert_eq!(failures.len(), 1, "{failures:?}");
+        assert!(
+            failures[0].starts_with("- validator-1: could not query shares"),
+            "{failures:?}"
+        );
+    }
+
+    #[test]
+    fn init_validates_against_subnet_membership() {
+        let scenario: Scenario<()> =
+            ScenarioBuilder::topology_with(|t| t.validators(2).executors(1)).build();
+        let descriptors = scenario.topology();
+        let metrics = RunMetrics::new(descriptors, Duration::from_secs(60));
+
+        let subnets = subnet_members(descriptors);
+        assert_eq!(
+            subnets.len(),
+            usize::from(descriptors.config().da_params.num_subnets)
+        );
+        let smallest = subnets.iter().map(Vec::len).min().expect("subnets");
+
+        assert!(
+            DaReplicationExpectation::new(smallest)
+                .init(descriptors, &metrics)
+                .is_ok()
+        );
+        assert!(
+            DaReplicationExpectation::new(smallest + 1)
+                .init(descriptors, &metrics)
+                .is_err()
+        );
+        assert!(
+            DaReplicationExpectation::new(0)
+                .init(descriptors, &metrics)
+                .is_err()
+        );
+        assert!(
+            DaReplicationExpectation::new(1)
+                .with_sample_size(0)
+                .init(descriptors, &metrics)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use async_trait::async_trait;
+use testing_framework_core::{
+    scenario::{DynError, Expectation, NodeTarget, RunContext, RunMetrics},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+
+use super::violations;
+
+const EXPECTATION_NAME: &str = "late_join_sync";
+
+/// Checks that nodes declared as late-joining caught up with the cluster.
+///
+/// At the end of the run each late joiner's height is compared with the
+/// highest node that ran from the start. A joiner may trail that tip by at
+/// most `security_param` blocks (or the configured lag); anything more means
+/// it failed to sync the chain it missed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LateJoinSyncExpectation {
+    max_lag: Option<u64>,
+}
+
+#[derive(Debug, Error)]
+enum LateJoinSyncIssue {
+    #[error("{node} consensus info request failed: {source}")]
+    RequestFailed {
+        node: NodeTarget,
+        #[source]
+        source: DynError,
+    },
+    #[error("{node} has no API client")]
+    MissingClient { node: NodeTarget },
+    #[error(
+        "{node} is at height {height}, {lag} blocks behind the tip at {tip} (allowed {allowed})"
+    )]
+    Behind {
+        node: NodeTarget,
+        height: u64,
+        tip: u64,
+        lag: u64,
+        allowed: u64,
+    },
+}
+
+#[derive(Debug, Error)]
+enum LateJoinSyncError {
+    #[error("topology declares no late-joining nodes")]
+    NoLateJoins,
+    #[error("no node running from the start reported its height")]
+    MissingReference,
+    #[error("late-joining nodes did not sync:\n{0}")]
+    Violations(String),
+}
+
+impl LateJoinSyncExpectation {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { max_lag: None }
+    }
+
+    #[must_use]
+    /// Override how many blocks a late joiner may trail the tip by. Defaults
+    /// to the topology's consensus security parameter.
+    pub const fn with_max_lag(mut self, blocks: u64) -> Self {
+        self.max_lag = Some(blocks);
+        self
+    }
+}
+
+#[async_trait]
+impl Expectation for LateJoinSyncExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["consensus", "sync"]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if descriptors.late_joins().is_empty() {
+            return Err(LateJoinSyncError::NoLateJoins.into());
+        }
+        if self.max_lag.is_none() {
+            self.max_lag = Some(u64::from(
+                descriptors.config().consensus_params.security_param.get(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let config = ctx.descriptors().config();
+        let allowed = self.max_lag.unwrap_or(0);
+
+        let mut tip = None;
+        for (target, client) in ctx.node_clients().targeted_clients() {
+            if config.joins_late(target) {
+                continue;
+            }
+            match client.consensus_info().await {
+                Ok(info) => tip = tip.max(Some(info.height)),
+                Err(err) => {
+                    tracing::debug!(node = %target, error = %err, "skipping unreachable reference node")
+                }
+            }
+        }
+        let tip = tip.ok_or(LateJoinSyncError::MissingReference)?;
+
+        let mut issues = Vec::new();
+        for join in ctx.descriptors().late_joins() {
+            let node = join.target;
+            let Some(client) = ctx.node_clients().client(node) else {
+                issues.push(LateJoinSyncIssue::MissingClient { node });
+                continue;
+            };
+            let height = match client.consensus_info().await {
+                Ok(info) => info.height,
+                Err(err) => {
+                    issues.push(LateJoinSyncIssue::RequestFailed {
+                        node,
+                        source: err.into(),
+                    });
+                    continue;
+                }
+            };
+            let lag = tip.saturating_sub(height);
+            ctx.report()
+                .set_counter(EXPECTATION_NAME, &format!("{node}_lag"), lag);
+            if lag > allowed {
+                issues.push(LateJoinSyncIssue::Behind {
+                    node,
+                    height,
+                    tip,
+                    lag,
+                    allowed,
+                });
+            } else {
+                tracing::debug!(%node, height, tip, lag, "late-joining node synced");
+            }
+        }
+
+        let Some(details) = violations("late join sync", &issues) else {
+            tracing::info!(
+                nodes = ctx.descriptors().late_joins().len(),
+                tip,
+                "late join sync expectation satisfied"
+            );
+            return Ok(());
+        };
+        Err(LateJoinSyncError::Violations(details).into())
+    }
+}
@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use regex::Regex;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+
+use super::truncated_violations;
+
+/// Patterns checked by [`LogExpectation::default`].
+pub const DEFAULT_LOG_PATTERNS: &[&str] = &[r"panicked at", r"\bERROR\b"];
+const MAX_REPORTED_MATCHES: usize = 5;
+
+#[derive(Debug, Error)]
+enum LogIssue {
+    #[error("{node}: failed to read logs: {source}")]
+    Read {
+        node: String,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("{node}: `{pattern}` matched {count} line(s), e.g. {sample}")]
+    Matched {
+        node: String,
+        pattern: String,
+        count: usize,
+        sample: String,
+    },
+}
+
+#[derive(Debug, Error)]
+enum LogExpectationError {
+    #[error("log expectation requires a deployer that exposes node logs")]
+    LogsUnavailable,
+    #[error("node logs matched forbidden patterns:\n{details}")]
+    Violations { details: String },
+}
+
+#[derive(Clone, Debug)]
+/// Fails the run when any node's logs contain a line matching one of the
+/// configured patterns (panics and `ERROR` lines by default).
+pub struct LogExpectation {
+    patterns: Vec<Regex>,
+}
+
+impl Default for LogExpectation {
+    fn default() -> Self {
+        Self::new(DEFAULT_LOG_PATTERNS.iter().copied())
+    }
+}
+
+impl LogExpectation {
+    /// Builds an expectation from regex patterns (panics on invalid regex).
+    #[must_use]
+    pub fn new<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            patterns: patterns
+                .into_iter()
+                .map(|pattern| {
+                    Regex::new(pattern)
+                        .unwrap_or_else(|err| panic!("invalid log pattern `{pattern}`: {err}"))
+                })
+                .collect(),
+        }
+    }
+
+    #[must_use]
+    /// Adds another forbidden pattern (panics on invalid regex).
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.patterns.push(
+            Regex::new(pattern)
+                .unwrap_or_else(|err| panic!("invalid log pattern `{pattern}`: {err}")),
+        );
+        self
+    }
+
+    fn scan(&self, node: &str, contents: &str) -> Vec<LogIssue> {
+        self.patterns
+            .iter()
+            .filter_map(|pattern| {
+                let mut matches = contents.lines().filter(|line| pattern.is_match(line));
+                let sample = matches.next()?;
+                Some(LogIssue::Matched {
+                    node: node.to_owned(),
+                    pattern: pattern.as_str().to_owned(),
+                    count: 1 + matches.count(),
+                    sample: sample.trim().to_owned(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Expectation for LogExpectation {
+    fn name(&self) -> &'static str {
+        "log_expectation"
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let node_logs = ctx.node_logs();
+        if node_logs.is_empty() {
+            return Err(LogExpectationError::LogsUnavailable.into());
+        }
+
+        let mut issues = Vec::new();
+        for source in node_logs.sources() {
+            match source.read() {
+                Ok(contents) => issues.extend(self.scan(&source.node, &contents)),
+                Err(source_err) => issues.push(LogIssue::Read {
+                    node: source.node.clone(),
+                    source: source_err,
+                }),
+            }
+        }
+
+        let Some(details) = truncated_violations("log expectation", &issues, MAX_REPORTED_MATCHES)
+        else {
+            tracing::info!(
+                nodes = node_logs.sources().len(),
+                patterns = self.patterns.len(),
+                "log expectation satisfied"
+            );
+            return Ok(());
+        };
+        Err(LogExpectationError::Violations { details }.into())
+    }
+}
@@ -0,0 +1,151 @@
+use std::{fmt, sync::Arc};
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{
+    DynError, Expectation, MetricsError, NodeTarget, RunContext, node_selector,
+};
+use thiserror::Error;
+
+use super::violations;
+
+/// Predicate applied to the value returned by a metric query.
+pub type MetricPredicate = Arc<dyn Fn(f64) -> bool + Send + Sync>;
+
+#[derive(Clone)]
+struct MetricCheck {
+    query: String,
+    predicate: MetricPredicate,
+}
+
+impl fmt::Debug for MetricCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricCheck")
+            .field("query", &self.query)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Error)]
+enum MetricIssue {
+    #[error("`{query}` failed: {source}")]
+    Query {
+        query: String,
+        #[source]
+        source: MetricsError,
+    },
+    #[error("`{query}` returned no samples")]
+    NoSamples { query: String },
+    #[error("`{query}` = {value} violates threshold")]
+    Violated { query: String, value: f64 },
+}
+
+#[derive(Debug, Error)]
+enum MetricsExpectationError {
+    #[error("metrics expectation requires a prometheus endpoint")]
+    PrometheusUnavailable,
+    #[error("metrics expectation violated:\n{details}")]
+    Violations { details: String },
+}
+
+#[derive(Clone, Debug, Default)]
+/// Runs PromQL queries once the run finishes and checks each result against a
+/// threshold predicate.
+///
+/// A query's samples are summed into a single value, so queries returning
+/// several series should aggregate explicitly (e.g. `histogram_quantile(...)`
+/// over a `sum by (le)`) when a sum is not meaningful.
+///
+/// Node series carry the node identifier in
+/// [`NODE_LABEL`](testing_framework_core::scenario::NODE_LABEL); build
+/// selectors with [`node_selector`] and
+/// [`role_selector`](testing_framework_core::scenario::role_selector), or use
+/// [`MetricsExpectation::check_node`].
+pub struct MetricsExpectation {
+    checks: Vec<MetricCheck>,
+}
+
+impl MetricsExpectation {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { checks: Vec::new() }
+    }
+
+    #[must_use]
+    /// Adds a query whose value must satisfy `predicate`.
+    pub fn check(
+        mut self,
+        query: impl Into<String>,
+        predicate: impl Fn(f64) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.checks.push(MetricCheck {
+            query: query.into(),
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    #[must_use]
+    /// Adds a check on `metric` as reported by `target` alone.
+    pub fn check_node(
+        self,
+        metric: &str,
+        target: NodeTarget,
+        predicate: impl Fn(f64) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        let query = format!("{metric}{{{}}}", node_selector(target));
+        self.check(query, predicate)
+    }
+
+    fn run_check(ctx: &RunContext, check: &MetricCheck) -> Result<f64, MetricIssue> {
+        let values = ctx
+            .telemetry()
+            .instant_values(&check.query)
+            .map_err(|source| MetricIssue::Query {
+                query: check.query.clone(),
+                source,
+            })?;
+        if values.is_empty() {
+            return Err(MetricIssue::NoSamples {
+                query: check.query.clone(),
+            });
+        }
+
+        let value = values.into_iter().sum();
+        if (check.predicate)(value) {
+            Ok(value)
+        } else {
+            Err(MetricIssue::Violated {
+                query: check.query.clone(),
+                value,
+            })
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for MetricsExpectation {
+    fn name(&self) -> &'static str {
+        "metrics_expectation"
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if !ctx.telemetry().is_configured() {
+            return Err(MetricsExpectationError::PrometheusUnavailable.into());
+        }
+
+        let mut issues = Vec::new();
+        for check in &self.checks {
+            match Self::run_check(ctx, check) {
+                Ok(value) => {
+                    tracing::info!(query = %check.query, value, "metric threshold satisfied");
+                }
+                Err(issue) => issues.push(issue),
+            }
+        }
+
+        let Some(details) = violations("metric threshold", &issues) else {
+            return Ok(());
+        };
+        Err(MetricsExpectationError::Violations { details }.into())
+    }
+}
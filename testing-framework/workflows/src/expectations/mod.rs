@@ -1,3 +1,56 @@
+use std::fmt::Display;
+
+mod chain_consistency;
 mod consensus_liveness;
+mod consensus_stall;
+mod da_connectivity;
+mod da_replication;
+mod late_join;
+mod logs;
+mod metrics;
+mod proposer_fairness;
+mod recovery;
+mod traces;
 
+pub use chain_consistency::ChainConsistencyExpectation;
 pub use consensus_liveness::ConsensusLiveness;
+pub use consensus_stall::ConsensusStallExpectation;
+pub use da_connectivity::DaConnectivityExpectation;
+pub use da_replication::DaReplicationExpectation;
+pub use late_join::LateJoinSyncExpectation;
+pub use logs::{DEFAULT_LOG_PATTERNS, LogExpectation};
+pub use metrics::{MetricPredicate, MetricsExpectation};
+pub use proposer_fairness::ProposerFairnessExpectation;
+pub use recovery::RecoveryExpectation;
+pub use traces::TraceExpectation;
+
+/// Logs each issue and renders them as a bulleted list for a `Violations`
+/// error, or returns `None` when there is nothing to report.
+pub(crate) fn violations(kind: &str, issues: &[impl Display]) -> Option<String> {
+    truncated_violations(kind, issues, usize::MAX)
+}
+
+/// Like [`violations`], but lists at most `limit` issues and summarises the
+/// rest.
+pub(crate) fn truncated_violations(
+    kind: &str,
+    issues: &[impl Display],
+    limit: usize,
+) -> Option<String> {
+    if issues.is_empty() {
+        return None;
+    }
+
+    for issue in issues {
+        tracing::warn!(%issue, "{kind} issue");
+    }
+    let mut details = issues
+        .iter()
+        .take(limit)
+        .map(|issue| format!("- {issue}"))
+        .collect::<Vec<_>>();
+    if issues.len() > limit {
+        details.push(format!("- ... and {} more", issues.len() - limit));
+    }
+    Some(details.join("\n"))
+}
@@ -0,0 +1,389 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use nomos_core::{header::HeaderId, proofs::leader_proof::LeaderProof as _};
+use rand::{
+    Rng,
+    distributions::{Distribution as _, WeightedIndex},
+};
+use testing_framework_core::{
+    scenario::{DynError, Expectation, RunContext, RunMetrics},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+/// Fewest blocks the distribution is judged on by default.
+const DEFAULT_MIN_BLOCKS: u64 = 20;
+const DEFAULT_SIGNIFICANCE: f64 = 0.001;
+/// Runs simulated under fair election to calibrate the statistic.
+const NULL_SAMPLES: usize = 2000;
+
+type ProposerCounts = Arc<Mutex<HashMap<[u8; 32], u64>>>;
+
+/// Checks that block proposals are spread across nodes roughly in proportion
+/// to their leader stake.
+///
+/// Every block seen on the block feed during the run is attributed to the
+/// leader key of its proof of leadership. Leadership proofs do not reveal
+/// which node's note won, so no proposer can be tied to a stake; the
+/// statistic is therefore invariant to who is who: the chi-square distance
+/// between the proposer counts and the stakes, both sorted. Sorting makes
+/// any run look closer to the stakes than it is, so the statistic is not
+/// compared with the chi-square distribution but with the same statistic over
+/// runs of the same length simulated under fair election. The run fails when
+/// more distinct proposers than stake holders show up, or when fewer than the
+/// configured share of simulated runs are as skewed as the observed one,
+/// which catches leader election favouring or starving nodes.
+#[derive(Clone, Debug)]
+pub struct ProposerFairnessExpectation {
+    min_blocks: u64,
+    significance: f64,
+    stakes: Vec<u64>,
+    counts: Option<ProposerCounts>,
+}
+
+#[derive(Debug, Error)]
+enum ProposerFairnessError {
+    #[error("proposer fairness requires nodes holding leader stake")]
+    MissingStake,
+    #[error("proposer fairness expectation not captured")]
+    NotCaptured,
+    #[error("only {observed} blocks observed; proposer fairness needs at least {required}")]
+    NotEnoughBlocks { observed: u64, required: u64 },
+    #[error("{proposers} distinct block proposers observed but only {holders} nodes hold stake")]
+    TooManyProposers { proposers: usize, holders: usize },
+    #[error(
+        "block proposals deviate from stake (chi-square {statistic:.1} over {blocks} blocks, p = {p_value:.4} < {significance}): observed {observed:?}, expected {expected:?}"
+    )]
+    Skewed {
+        statistic: f64,
+        p_value: f64,
+        significance: f64,
+        blocks: u64,
+        observed: Vec<u64>,
+        expected: Vec<String>,
+    },
+}
+
+impl Default for ProposerFairnessExpectation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProposerFairnessExpectation {
+    pub const NAME: &'static str = "proposer_fairness";
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            min_blocks: DEFAULT_MIN_BLOCKS,
+            significance: DEFAULT_SIGNIFICANCE,
+            stakes: Vec::new(),
+            counts: None,
+        }
+    }
+
+    #[must_use]
+    /// Fewest blocks the run must produce for the distribution to be judged;
+    /// shorter runs fail as inconclusive. Defaults to 20.
+    pub const fn with_min_blocks(mut self, blocks: u64) -> Self {
+        self.min_blocks = blocks;
+        self
+    }
+
+    #[must_use]
+    /// Significance level of the test: the run fails when a fair election
+    /// would produce proposals at least as skewed with lower probability.
+    /// Defaults to 0.001; lower values tolerate more skew (panics outside
+    /// `(0, 1)`).
+    pub fn with_significance(mut self, significance: f64) -> Self {
+        assert!(
+            significance > 0.0 && significance < 1.0,
+            "proposer fairness significance must be within (0, 1)"
+        );
+        self.significance = significance;
+        self
+    }
+
+    fn check(
+        &self,
+        counts: &HashMap<[u8; 32], u64>,
+        rng: &mut impl Rng,
+    ) -> Result<(), ProposerFairnessError> {
+        let blocks: u64 = counts.values().sum();
+        if blocks < self.min_blocks {
+            return Err(ProposerFairnessError::NotEnoughBlocks {
+                observed: blocks,
+                required: self.min_blocks,
+            });
+        }
+        if counts.len() > self.stakes.len() {
+            return Err(ProposerFairnessError::TooManyProposers {
+                proposers: counts.len(),
+                holders: self.stakes.len(),
+            });
+        }
+
+        let mut observed: Vec<u64> = counts.values().copied().collect();
+        observed.resize(self.stakes.len(), 0);
+        let total_stake: u64 = self.stakes.iter().sum();
+        let expected: Vec<f64> = self
+            .stakes
+            .iter()
+            .map(|stake| blocks as f64 * *stake as f64 / total_stake as f64)
+            .collect();
+        let statistic = sorted_chi_square(&mut observed, &expected);
+        let p_value = null_p_value(statistic, &self.stakes, &expected, blocks, rng);
+
+        tracing::debug!(
+            blocks,
+            statistic,
+            p_value,
+            ?observed,
+            "proposer fairness evaluated"
+        );
+        if p_value < self.significance {
+            return Err(ProposerFairnessError::Skewed {
+                statistic,
+                p_value,
+                significance: self.significance,
+                blocks,
+                observed,
+                expected: expected.iter().map(|count| format!("{count:.1}")).collect(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Expectation for ProposerFairnessExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["consensus"]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        self.stakes = leader_stakes(descriptors);
+        if self.stakes.is_empty() {
+            return Err(ProposerFairnessError::MissingStake.into());
+        }
+        // Sorted counts are compared with stakes largest first.
+        self.stakes.sort_unstable_by(|a, b| b.cmp(a));
+        Ok(())
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.counts.is_some() {
+            return Ok(());
+        }
+
+        let counts = ProposerCounts::default();
+        let spawn_counts = Arc::clone(&counts);
+        let mut receiver = ctx.block_feed().subscribe();
+        tokio::spawn(async move {
+            let genesis_parent = HeaderId::from([0; 32]);
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => {
+                        let header = record.block.header();
+                        if header.parent_block() == genesis_parent {
+                            continue;
+                        }
+                        let key = header.leader_proof().leader_key().to_bytes();
+                        *spawn_counts
+                            .lock()
+                            .expect("proposer counts lock poisoned")
+                            .entry(key)
+                            .or_default() += 1;
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "proposer fairness capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        tracing::debug!("proposer fairness capture feed closed");
+                        break;
+                    }
+                }
+            }
+        });
+
+        self.counts = Some(counts);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let counts = self
+            .counts
+            .as_ref()
+            .ok_or(ProposerFairnessError::NotCaptured)?
+            .lock()
+            .expect("proposer counts lock poisoned")
+            .clone();
+
+        ctx.report()
+            .set_counter(Self::NAME, "blocks", counts.values().sum());
+        ctx.report()
+            .set_counter(Self::NAME, "proposers", counts.len() as u64);
+
+        self.check(&counts, &mut ctx.rng(Self::NAME))?;
+        tracing::info!(
+            blocks = counts.values().sum::<u64>(),
+            proposers = counts.len(),
+            holders = self.stakes.len(),
+            "proposer fairness expectation satisfied"
+        );
+        Ok(())
+    }
+}
+
+/// Leader note value of every node with a non-zero stake.
+fn leader_stakes(descriptors: &GeneratedTopology) -> Vec<u64> {
+    descriptors
+        .nodes()
+        .map(|node| {
+            let consensus = &node.general.consensus_config;
+            consensus
+                .utxos
+                .iter()
+                .filter(|utxo| utxo.note.pk == consensus.leader_config.pk)
+                .map(|utxo| utxo.note.value)
+                .sum::<u64>()
+        })
+        .filter(|stake| *stake > 0)
+        .collect()
+}
+
+/// Chi-square distance between `counts` and `expected` (largest first) once
+/// `counts` is sorted largest first, so the result does not depend on which
+/// proposer is which.
+fn sorted_chi_square(counts: &mut [u64], expected: &[f64]) -> f64 {
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts
+        .iter()
+        .zip(expected)
+        .map(|(observed, expected)| (*observed as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+/// Share of [`NULL_SAMPLES`] runs of `blocks` blocks, with leaders drawn in
+/// proportion to `stakes`, whose [`sorted_chi_square`] is at least
+/// `statistic`.
+fn null_p_value(
+    statistic: f64,
+    stakes: &[u64],
+    expected: &[f64],
+    blocks: u64,
+    rng: &mut impl Rng,
+) -> f64 {
+    let Ok(leaders) = WeightedIndex::new(stakes) else {
+        return 1.0;
+    };
+    let mut counts = vec![0; stakes.len()];
+    let as_skewed = (0..NULL_SAMPLES)
+        .filter(|_| {
+            counts.fill(0);
+            for _ in 0..blocks {
+                counts[leaders.sample(rng)] += 1;
+            }
+            sorted_chi_square(&mut counts, expected) >= statistic
+        })
+        .count();
+    (as_skewed + 1) as f64 / (NULL_SAMPLES + 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+
+    fn expectation(stakes: Vec<u64>) -> ProposerFairnessExpectation {
+        ProposerFairnessExpectation {
+            stakes,
+            ..ProposerFairnessExpectation::new()
+        }
+    }
+
+    fn counts(values: &[u64]) -> HashMap<[u8; 32], u64> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(index, count)| ([index as u8; 32], *count))
+            .collect()
+    }
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(11)
+    }
+
+    #[test]
+    fn fair_proposals_pass() {
+        let fairness = expectation(vec![1000, 1000, 1000, 1000]);
+
+        assert!(
+            fairness
+                .check(&counts(&[26, 24, 27, 23]), &mut rng())
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn proposals_ignoring_stake_fail() {
+        let fairness = expectation(vec![1000, 1000, 1000, 1000]);
+
+        let result = fairness.check(&counts(&[97, 1, 1, 1]), &mut rng());
+
+        assert!(matches!(result, Err(ProposerFairnessError::Skewed { .. })));
+    }
+
+    #[test]
+    fn proposals_follow_unequal_stakes() {
+        let fairness = expectation(vec![1000, 7000, 1000, 1000]);
+
+        assert!(
+            fairness
+                .check(&counts(&[9, 12, 68, 11]), &mut rng())
+                .is_ok()
+        );
+        assert!(matches!(
+            fairness.check(&counts(&[25, 25, 25, 25]), &mut rng()),
+            Err(ProposerFairnessError::Skewed { .. })
+        ));
+    }
+
+    #[test]
+    fn too_few_blocks_or_too_many_proposers_fail() {
+        let fairness = expectation(vec![1000, 1000]);
+
+        assert!(matches!(
+            fairness.check(&counts(&[5, 5]), &mut rng()),
+            Err(ProposerFairnessError::NotEnoughBlocks { observed: 10, .. })
+        ));
+        assert!(matches!(
+            fairness.check(&counts(&[10, 10, 10]), &mut rng()),
+            Err(ProposerFairnessError::TooManyProposers {
+                proposers: 3,
+                holders: 2
+            })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "must be within (0, 1)")]
+    fn zero_significance_panics() {
+        let _ = ProposerFairnessExpectation::new().with_significance(0.0);
+    }
+}
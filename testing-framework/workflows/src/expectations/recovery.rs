@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{
+    DynError, Expectation, NodeTarget, RecoverySample, RunContext,
+};
+use thiserror::Error;
+use tokio::time::{Instant, sleep};
+
+use super::violations;
+
+const EXPECTATION_NAME: &str = "node_recovery";
+/// Time between two checks for restarts whose recovery is still tracked.
+const SETTLE_POLL: Duration = Duration::from_secs(1);
+
+/// Checks that every node restarted by a chaos workload recovered in time.
+///
+/// A restart counts as recovered once the node answers its API again and
+/// reaches the height the cluster's tip had when it came back (see
+/// [`NodeRecovery`](testing_framework_core::scenario::NodeRecovery)). Restarts
+/// still tracked when the run ends get up to `max` to finish; past that they
+/// cannot have recovered within `max` of being issued.
+#[derive(Clone, Copy, Debug)]
+pub struct RecoveryExpectation {
+    max: Duration,
+}
+
+#[derive(Debug, Error)]
+enum RecoveryIssue {
+    #[error("{node} recovered after {took:?} (allowed {max:?})")]
+    Slow {
+        node: NodeTarget,
+        took: Duration,
+        max: Duration,
+    },
+    #[error("{node} never served its API after restarting")]
+    NotReady { node: NodeTarget },
+    #[error("{node} served its API after {ready:?} but never caught up to the tip")]
+    NotCaughtUp { node: NodeTarget, ready: Duration },
+}
+
+#[derive(Debug, Error)]
+enum RecoveryError {
+    #[error("no node was restarted during the run")]
+    NoRestarts,
+    #[error("restarted nodes did not recover within {max:?}:\n{details}")]
+    Violations { max: Duration, details: String },
+}
+
+impl RecoveryExpectation {
+    #[must_use]
+    pub const fn new(max: Duration) -> Self {
+        Self { max }
+    }
+
+    async fn settled_samples(&self, ctx: &RunContext) -> Vec<RecoverySample> {
+        let deadline = Instant::now() + self.max;
+        loop {
+            let samples = ctx.recovery().samples();
+            if samples.iter().all(|sample| sample.settled) || Instant::now() >= deadline {
+                return samples;
+            }
+            sleep(SETTLE_POLL).await;
+        }
+    }
+}
+
+/// Why `sample` does not count as recovered within `max`, if it does not.
+fn classify(sample: &RecoverySample, max: Duration) -> Option<RecoveryIssue> {
+    let node = sample.node;
+    match (sample.ready_after, sample.caught_up_after) {
+        (_, Some(took)) if took > max => Some(RecoveryIssue::Slow { node, took, max }),
+        (_, Some(took)) => {
+            tracing::debug!(%node, ?took, "restarted node recovered");
+            None
+        }
+        (Some(ready), None) => Some(RecoveryIssue::NotCaughtUp { node, ready }),
+        (None, None) => Some(RecoveryIssue::NotReady { node }),
+    }
+}
+
+#[async_trait]
+impl Expectation for RecoveryExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos", "sync"]
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let samples = self.settled_samples(ctx).await;
+        if samples.is_empty() {
+            return Err(RecoveryError::NoRestarts.into());
+        }
+
+        let issues = samples
+            .iter()
+            .filter_map(|sample| classify(sample, self.max))
+            .collect::<Vec<_>>();
+
+        let recovered = samples
+            .iter()
+            .filter(|sample| sample.caught_up_after.is_some())
+            .count();
+        let report = ctx.report();
+        report.set_counter(EXPECTATION_NAME, "restarts", samples.len() as u64);
+        report.set_counter(EXPECTATION_NAME, "recovered", recovered as u64);
+        if let Some(slowest) = ctx.recovery().slowest() {
+            report.set_counter(
+                EXPECTATION_NAME,
+                "max_recovery_ms",
+                u64::try_from(slowest.as_millis()).unwrap_or(u64::MAX),
+            );
+        }
+
+        let Some(details) = violations("node recovery", &issues) else {
+            tracing::info!(
+                restarts = samples.len(),
+                max = ?self.max,
+                "node recovery expectation satisfied"
+            );
+            return Ok(());
+        };
+        Err(RecoveryError::Violations {
+            max: self.max,
+            details,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX: Duration = Duration::from_secs(30);
+
+    fn sample(ready: Option<u64>, caught_up: Option<u64>) -> RecoverySample {
+        RecoverySample {
+            node: NodeTarget::Executor(0),
+            ready_after: ready.map(Duration::from_secs),
+            caught_up_after: caught_up.map(Duration::from_secs),
+            settled: true,
+        }
+    }
+
+    #[test]
+    fn recovered_in_time_is_no_issue() {
+        assert!(classify(&sample(Some(5), Some(20)), MAX).is_none());
+        assert!(classify(&sample(Some(5), Some(30)), MAX).is_none());
+    }
+
+    #[test]
+    fn slow_recovery_reports_its_duration() {
+        let issue = classify(&sample(Some(5), Some(45)), MAX).expect("too slow");
+        assert!(matches!(
+            issue,
+            RecoveryIssue::Slow { took, max, .. }
+                if took == Duration::from_secs(45) && max == MAX
+        ));
+        assert_eq!(
+            issue.to_string(),
+            "executor-0 recovered after 45s (allowed 30s)"
+        );
+    }
+
+    #[test]
+    fn unrecovered_nodes_are_told_apart() {
+        assert!(matches!(
+            classify(&sample(Some(8), None), MAX),
+            Some(RecoveryIssue::NotCaughtUp { ready, .. }) if ready == Duration::from_secs(8)
+        ));
+        assert!(matches!(
+            classify(&sample(None, None), MAX),
+            Some(RecoveryIssue::NotReady {
+                node: NodeTarget::Executor(0)
+            })
+        ));
+    }
+}
@@ -0,0 +1,276 @@
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{
+    DynError, Expectation, RunContext, TempoEndpoint, TracesError,
+};
+use thiserror::Error;
+use tokio::time::sleep;
+
+use super::violations;
+
+/// Traces fetched per query; enough to report offenders without paging.
+const SEARCH_LIMIT: usize = 20;
+/// Nodes export spans in batches every few seconds; wait for the last batch
+/// before searching.
+const DEFAULT_EXPORT_GRACE: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TraceCheckKind {
+    /// The query must match no trace.
+    Forbid,
+    /// The query must match at least one trace.
+    Require,
+}
+
+#[derive(Clone, Debug)]
+struct TraceCheck {
+    query: String,
+    kind: TraceCheckKind,
+}
+
+#[derive(Debug, Error)]
+enum TraceIssue {
+    #[error("{0}")]
+    Search(#[from] TracesError),
+    #[error("`{query}` matched {count} trace(s), e.g. {examples}")]
+    Forbidden {
+        query: String,
+        count: usize,
+        examples: String,
+    },
+    #[error("`{query}` matched no trace")]
+    Missing { query: String },
+}
+
+#[derive(Debug, Error)]
+enum TraceExpectationError {
+    #[error("trace expectation requires a trace backend (compose with tracing enabled)")]
+    TracesUnavailable,
+    #[error("trace expectation violated:\n{details}")]
+    Violations { details: String },
+}
+
+#[derive(Clone, Debug)]
+/// Runs TraceQL searches over the spans exported during the run and checks
+/// whether each matches any trace.
+///
+/// Needs a deployer exporting node traces, e.g.
+/// `ComposeDeployer::with_tracing(true)`.
+pub struct TraceExpectation {
+    checks: Vec<TraceCheck>,
+    export_grace: Duration,
+    started_at: Option<SystemTime>,
+}
+
+impl Default for TraceExpectation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TraceExpectation {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            checks: Vec::new(),
+            export_grace: DEFAULT_EXPORT_GRACE,
+            started_at: None,
+        }
+    }
+
+    #[must_use]
+    /// Fails if the TraceQL `query` matches any trace.
+    pub fn forbid(mut self, query: impl Into<String>) -> Self {
+        self.checks.push(TraceCheck {
+            query: query.into(),
+            kind: TraceCheckKind::Forbid,
+        });
+        self
+    }
+
+    #[must_use]
+    /// Fails unless the TraceQL `query` matches at least one trace.
+    pub fn require(mut self, query: impl Into<String>) -> Self {
+        self.checks.push(TraceCheck {
+            query: query.into(),
+            kind: TraceCheckKind::Require,
+        });
+        self
+    }
+
+    #[must_use]
+    /// Fails if any span named `name` took longer than `max`.
+    pub fn max_span_duration(self, name: &str, max: Duration) -> Self {
+        let query = format!(
+            "{{ name = \"{}\" && duration > {} }}",
+            escape(name),
+            traceql_duration(max)
+        );
+        self.forbid(query)
+    }
+
+    #[must_use]
+    /// Fails unless at least one span named `name` was recorded.
+    pub fn require_span(self, name: &str) -> Self {
+        self.require(format!("{{ name = \"{}\" }}", escape(name)))
+    }
+
+    #[must_use]
+    /// Fails if any span ended with an error status.
+    pub fn no_error_spans(self) -> Self {
+        self.forbid("{ status = error }")
+    }
+
+    #[must_use]
+    /// Time to wait after the run before searching, so the last span batches
+    /// are exported (10s by default).
+    pub const fn with_export_grace(mut self, grace: Duration) -> Self {
+        self.export_grace = grace;
+        self
+    }
+
+    async fn run_check(
+        tempo: &TempoEndpoint,
+        check: &TraceCheck,
+        since: SystemTime,
+    ) -> Result<usize, TraceIssue> {
+        let traces = tempo.search(&check.query, since, SEARCH_LIMIT).await?;
+
+        match check.kind {
+            TraceCheckKind::Forbid if !traces.is_empty() => {
+                let examples = traces
+                    .iter()
+                    .take(3)
+                    .map(|trace| {
+                        let slowest = trace.spans.iter().map(|span| span.duration).max();
+                        format!(
+                            "{} ({} {:?})",
+                            trace.trace_id,
+                            trace.root_name,
+                            slowest.unwrap_or(trace.duration)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Err(TraceIssue::Forbidden {
+                    query: check.query.clone(),
+                    count: traces.len(),
+                    examples,
+                })
+            }
+            TraceCheckKind::Require if traces.is_empty() => Err(TraceIssue::Missing {
+                query: check.query.clone(),
+            }),
+            TraceCheckKind::Forbid | TraceCheckKind::Require => Ok(traces.len()),
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// `duration` as a TraceQL literal in the coarsest unit that keeps it exact.
+fn traceql_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    if nanos % 1_000_000 == 0 {
+        format!("{}ms", nanos / 1_000_000)
+    } else if nanos % 1_000 == 0 {
+        format!("{}us", nanos / 1_000)
+    } else {
+        format!("{nanos}ns")
+    }
+}
+
+#[async_trait]
+impl Expectation for TraceExpectation {
+    fn name(&self) -> &'static str {
+        "trace_expectation"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if ctx.traces().is_none() {
+            return Err(TraceExpectationError::TracesUnavailable.into());
+        }
+        self.started_at = Some(SystemTime::now());
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let Some(tempo) = ctx.traces() else {
+            return Err(TraceExpectationError::TracesUnavailable.into());
+        };
+        let since = self.started_at.unwrap_or_else(SystemTime::now);
+        sleep(self.export_grace).await;
+
+        let mut issues = Vec::new();
+        for check in &self.checks {
+            match Self::run_check(tempo, check, since).await {
+                Ok(matches) => {
+                    tracing::info!(query = %check.query, matches, "trace check satisfied");
+                }
+                Err(issue) => issues.push(issue),
+            }
+        }
+
+        let Some(details) = violations("trace check", &issues) else {
+            return Ok(());
+        };
+        Err(TraceExpectationError::Violations { details }.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queries(expectation: &TraceExpectation) -> Vec<(&str, TraceCheckKind)> {
+        expectation
+            .checks
+            .iter()
+            .map(|check| (check.query.as_str(), check.kind))
+            .collect()
+    }
+
+    #[test]
+    fn span_duration_limits_keep_sub_millisecond_precision() {
+        let expectation = TraceExpectation::new()
+            .max_span_duration("dispersal", Duration::from_secs(2))
+            .max_span_duration("sampling", Duration::from_micros(1500))
+            .max_span_duration("verify", Duration::from_nanos(800));
+
+        assert_eq!(
+            queries(&expectation),
+            vec![
+                (
+                    r#"{ name = "dispersal" && duration > 2000ms }"#,
+                    TraceCheckKind::Forbid
+                ),
+                (
+                    r#"{ name = "sampling" && duration > 1500us }"#,
+                    TraceCheckKind::Forbid
+                ),
+                (
+                    r#"{ name = "verify" && duration > 800ns }"#,
+                    TraceCheckKind::Forbid
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn span_names_are_escaped() {
+        let expectation = TraceExpectation::new()
+            .require_span(r#"say "hi" \ bye"#)
+            .no_error_spans();
+
+        assert_eq!(
+            queries(&expectation),
+            vec![
+                (r#"{ name = "say \"hi\" \\ bye" }"#, TraceCheckKind::Require),
+                ("{ status = error }", TraceCheckKind::Forbid),
+            ]
+        );
+    }
+}
@@ -1,8 +1,17 @@
 pub mod builder;
 pub mod expectations;
+pub mod registry;
+pub mod scenario_file;
 pub mod util;
 pub mod workloads;
 
 pub use builder::{ChaosBuilderExt, ScenarioBuilderExt};
-pub use expectations::ConsensusLiveness;
-pub use workloads::transaction::TxInclusionExpectation;
+pub use expectations::{
+    ChainConsistencyExpectation, ConsensusLiveness, DaConnectivityExpectation,
+    LateJoinSyncExpectation, LogExpectation, MetricsExpectation, ProposerFairnessExpectation,
+    TraceExpectation,
+};
+pub use registry::builtin_registry;
+pub use workloads::transaction::{
+    MempoolBackpressure, TxInclusionExpectation, TxInclusionLatencyExpectation,
+};
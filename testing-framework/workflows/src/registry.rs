@@ -0,0 +1,656 @@
+//! Built-in registrations for [`ComponentRegistry`], used by the scenario
+//! file loader and the daemon API to build workloads and expectations by
+//! name.
+//!
+//! Each config is a flat object whose fields all have defaults, which the DSL
+//! chaos builders start from too; durations are given in whole seconds (or
+//! milliseconds for latency shaping).
+
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use testing_framework_core::scenario::{ComponentRegistry, DynError, NetworkShaping};
+
+use crate::{
+    expectations::{ChainConsistencyExpectation, ConsensusLiveness},
+    workloads::{
+        chaos::{
+            DaPeerBlockingWorkload, NetworkLatencyWorkload, NetworkPartitionWorkload,
+            NodeCrashWorkload, NodeDowntimeWorkload, RandomRestartWorkload,
+        },
+        da, transaction,
+    },
+};
+
+/// Name of the registered [`RandomRestartWorkload`], whose restarts are
+/// tracked for recovery.
+pub(crate) const RESTART_WORKLOAD: &str = "chaos_restart";
+
+// Names the scenario file's `workloads` and `chaos` sections are built under.
+pub(crate) const TRANSACTIONS_WORKLOAD: &str = "transactions";
+pub(crate) const DA_WORKLOAD: &str = "da";
+pub(crate) const CRASH_WORKLOAD: &str = "chaos_crash";
+pub(crate) const DOWNTIME_WORKLOAD: &str = "chaos_downtime";
+pub(crate) const PARTITION_WORKLOAD: &str = "chaos_partition";
+pub(crate) const LATENCY_WORKLOAD: &str = "chaos_latency";
+pub(crate) const DA_PEER_BLOCKING_WORKLOAD: &str = "chaos_da_peer_blocking";
+
+/// A registry holding the framework's transaction, DA and chaos workloads
+/// and its standalone expectations. Register custom components on top of it
+/// to make them available to scenario files and the daemon.
+#[must_use]
+pub fn builtin_registry() -> ComponentRegistry {
+    let mut registry = ComponentRegistry::new();
+    registry
+        .register_workload(
+            TRANSACTIONS_WORKLOAD,
+            "wallet transactions at `rate` per block",
+            |config: TransactionsConfig| {
+                let path = if config.via_blend {
+                    transaction::SubmissionPath::Blend
+                } else {
+                    transaction::SubmissionPath::Mempool
+                };
+                Ok(transaction::Workload::new(config.rate)
+                    .with_user_limit(config.users)
+                    .with_backpressure(
+                        config
+                            .max_pending
+                            .map(transaction::MempoolBackpressure::new),
+                    )
+                    .with_submission_path(path))
+            },
+        )
+        .register_workload(
+            "transactions_ramp",
+            "wallet transactions ramped from `start_rate` to `end_rate` per block",
+            TransactionRampConfig::build,
+        )
+        .register_workload(
+            DA_WORKLOAD,
+            "DA channel inscriptions and blob publishing",
+            |config: DaConfig| {
+                Ok(da::Workload::with_rate(
+                    config.blob_rate,
+                    config.channel_rate,
+                    config.headroom_percent,
+                ))
+            },
+        )
+        .register_workload(
+            "da_sampling",
+            "historic sampling of landed blobs on every node",
+            |config: DaSamplingConfig| Ok(da::SamplingWorkload::new(config.sample_size)),
+        )
+        .register_node_control_workload(
+            RESTART_WORKLOAD,
+            "restarts random nodes at random intervals",
+            RestartConfig::build,
+        )
+        .register_node_control_workload(
+            CRASH_WORKLOAD,
+            "kills nodes with SIGKILL and checks they recover",
+            CrashConfig::build,
+        )
+        .register_node_control_workload(
+            DOWNTIME_WORKLOAD,
+            "stops nodes for a while and checks they catch up",
+            DowntimeConfig::build,
+        )
+        .register_node_control_workload(
+            PARTITION_WORKLOAD,
+            "splits nodes into isolated groups, then heals the network",
+            PartitionConfig::build,
+        )
+        .register_node_control_workload(
+            LATENCY_WORKLOAD,
+            "ramps up latency, jitter and packet loss on node traffic",
+            LatencyConfig::build,
+        )
+        .register_node_control_workload(
+            DA_PEER_BLOCKING_WORKLOAD,
+            "blocks DA peers and checks dispersal still succeeds",
+            DaPeerBlockingConfig::build,
+        )
+        .register_expectation(
+            "consensus_liveness",
+            "every node stays within `lag_allowance` blocks of the tip",
+            |config: ConsensusLivenessConfig| {
+                Ok(ConsensusLiveness::default().with_lag_allowance(config.lag_allowance))
+            },
+        )
+        .register_expectation(
+            "da_pruning",
+            "landed blobs stop being sampleable after the validity window",
+            |config: DaPruningConfig| {
+                Ok(config
+                    .sample_size
+                    .map_or_else(da::PruningExpectation::default, da::PruningExpectation::new))
+            },
+        )
+        .register_expectation(
+            "chain_consistency",
+            "nodes agree on the chain below `max_fork_depth`",
+            |config: ChainConsistencyConfig| {
+                let expectation = ChainConsistencyExpectation::new();
+                Ok(match config.max_fork_depth {
+                    Some(depth) => expectation.with_max_fork_depth(depth),
+                    None => expectation,
+                })
+            },
+        );
+    registry
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionsConfig {
+    /// Transactions per block.
+    pub rate: NonZeroU64,
+    /// Only use this many of the seeded wallets.
+    pub users: Option<NonZeroUsize>,
+    /// Pause submission while a node's mempool holds more than this many
+    /// pending transactions.
+    pub max_pending: Option<NonZeroU64>,
+    /// Take the blend submission path, see
+    /// [`transaction::SubmissionPath::Blend`].
+    pub via_blend: bool,
+}
+
+impl Default for TransactionsConfig {
+    fn default() -> Self {
+        Self {
+            rate: NonZeroU64::MIN,
+            users: None,
+            max_pending: None,
+            via_blend: false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TransactionRampConfig {
+    /// Transactions per block the ramp starts and ends at.
+    pub start_rate: NonZeroU64,
+    pub end_rate: NonZeroU64,
+    /// Rate increase per block.
+    pub step: NonZeroU64,
+    pub users: Option<NonZeroUsize>,
+}
+
+impl Default for TransactionRampConfig {
+    fn default() -> Self {
+        Self {
+            start_rate: NonZeroU64::MIN,
+            end_rate: NonZeroU64::new(10).expect("non-zero"),
+            step: NonZeroU64::MIN,
+            users: None,
+        }
+    }
+}
+
+impl TransactionRampConfig {
+    fn build(self) -> Result<transaction::RampWorkload, DynError> {
+        if self.end_rate < self.start_rate {
+            return Err("end_rate must be at least start_rate".into());
+        }
+        Ok(
+            transaction::RampWorkload::new(self.start_rate, self.end_rate, self.step)
+                .with_user_limit(self.users),
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaConfig {
+    pub channel_rate: NonZeroU64,
+    pub blob_rate: NonZeroU64,
+    pub headroom_percent: u64,
+}
+
+impl Default for DaConfig {
+    fn default() -> Self {
+        Self {
+            channel_rate: NonZeroU64::MIN,
+            blob_rate: NonZeroU64::MIN,
+            headroom_percent: da::Workload::default_headroom_percent(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaSamplingConfig {
+    /// Blobs sampled per block.
+    pub sample_size: NonZeroUsize,
+}
+
+impl Default for DaSamplingConfig {
+    fn default() -> Self {
+        Self {
+            sample_size: NonZeroUsize::new(4).expect("non-zero"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RestartConfig {
+    pub min_delay_secs: NonZeroU64,
+    pub max_delay_secs: NonZeroU64,
+    pub target_cooldown_secs: NonZeroU64,
+    pub include_validators: bool,
+    pub include_executors: bool,
+}
+
+impl Default for RestartConfig {
+    fn default() -> Self {
+        Self {
+            min_delay_secs: secs(10),
+            max_delay_secs: secs(30),
+            target_cooldown_secs: secs(60),
+            include_validators: true,
+            include_executors: true,
+        }
+    }
+}
+
+impl RestartConfig {
+    fn build(self) -> Result<RandomRestartWorkload, DynError> {
+        node_groups(
+            "chaos_restart",
+            self.include_validators,
+            self.include_executors,
+        )?;
+        if self.min_delay_secs > self.max_delay_secs {
+            return Err("min_delay_secs must not exceed max_delay_secs".into());
+        }
+        if self.target_cooldown_secs < self.min_delay_secs {
+            return Err("target_cooldown_secs must be >= min_delay_secs".into());
+        }
+        Ok(RandomRestartWorkload::new(
+            duration(self.min_delay_secs),
+            duration(self.max_delay_secs),
+            duration(self.target_cooldown_secs),
+            self.include_validators,
+            self.include_executors,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrashConfig {
+    pub restart_delay_secs: NonZeroU64,
+    pub interval_secs: NonZeroU64,
+    pub recovery_timeout_secs: NonZeroU64,
+    pub include_validators: bool,
+    pub include_executors: bool,
+}
+
+impl Default for CrashConfig {
+    fn default() -> Self {
+        Self {
+            restart_delay_secs: secs(10),
+            interval_secs: secs(30),
+            recovery_timeout_secs: secs(60),
+            include_validators: true,
+            include_executors: true,
+        }
+    }
+}
+
+impl CrashConfig {
+    fn build(self) -> Result<NodeCrashWorkload, DynError> {
+        node_groups(
+            "chaos_crash",
+            self.include_validators,
+            self.include_executors,
+        )?;
+        Ok(NodeCrashWorkload::new(
+            duration(self.restart_delay_secs),
+            duration(self.interval_secs),
+            duration(self.recovery_timeout_secs),
+            self.include_validators,
+            self.include_executors,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DowntimeConfig {
+    pub downtime_secs: NonZeroU64,
+    pub interval_secs: NonZeroU64,
+    pub catch_up_timeout_secs: NonZeroU64,
+    pub include_validators: bool,
+    pub include_executors: bool,
+}
+
+impl Default for DowntimeConfig {
+    fn default() -> Self {
+        Self {
+            downtime_secs: secs(30),
+            interval_secs: secs(30),
+            catch_up_timeout_secs: secs(60),
+            include_validators: true,
+            include_executors: true,
+        }
+    }
+}
+
+impl DowntimeConfig {
+    fn build(self) -> Result<NodeDowntimeWorkload, DynError> {
+        node_groups(
+            "chaos_downtime",
+            self.include_validators,
+            self.include_executors,
+        )?;
+        Ok(NodeDowntimeWorkload::new(
+            duration(self.downtime_secs),
+            duration(self.interval_secs),
+            duration(self.catch_up_timeout_secs),
+            self.include_validators,
+            self.include_executors,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PartitionConfig {
+    pub groups: usize,
+    pub partition_secs: NonZeroU64,
+    pub heal_secs: NonZeroU64,
+    pub include_validators: bool,
+    pub include_executors: bool,
+}
+
+impl Default for PartitionConfig {
+    fn default() -> Self {
+        Self {
+            groups: 2,
+            partition_secs: secs(30),
+            heal_secs: secs(30),
+            include_validators: true,
+            include_executors: true,
+        }
+    }
+}
+
+impl PartitionConfig {
+    fn build(self) -> Result<NetworkPartitionWorkload, DynError> {
+        node_groups(
+            "chaos_partition",
+            self.include_validators,
+            self.include_executors,
+        )?;
+        if self.groups < 2 {
+            return Err("groups must be at least 2".into());
+        }
+        Ok(NetworkPartitionWorkload::new(
+            self.groups,
+            duration(self.partition_secs),
+            duration(self.heal_secs),
+            self.include_validators,
+            self.include_executors,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LatencyConfig {
+    pub latency_ms: u64,
+    pub jitter_ms: u64,
+    pub packet_loss_percent: f64,
+    /// Reach the target shaping in this many equal steps.
+    pub ramp_steps: NonZeroUsize,
+    pub step_secs: NonZeroU64,
+    /// Shape only this many random nodes.
+    pub nodes: Option<NonZeroUsize>,
+    pub include_validators: bool,
+    pub include_executors: bool,
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            latency_ms: 200,
+            jitter_ms: 0,
+            packet_loss_percent: 0.0,
+            ramp_steps: NonZeroUsize::MIN,
+            step_secs: secs(30),
+            nodes: None,
+            include_validators: true,
+            include_executors: true,
+        }
+    }
+}
+
+impl LatencyConfig {
+    fn build(self) -> Result<NetworkLatencyWorkload, DynError> {
+        node_groups(
+            "chaos_latency",
+            self.include_validators,
+            self.include_executors,
+        )?;
+        if !(0.0..=100.0).contains(&self.packet_loss_percent) {
+            return Err("packet_loss_percent must be between 0 and 100".into());
+        }
+        let target = NetworkShaping {
+            latency: Duration::from_millis(self.latency_ms),
+            jitter: Duration::from_millis(self.jitter_ms),
+            loss_percent: self.packet_loss_percent,
+        };
+        Ok(NetworkLatencyWorkload::new(
+            NetworkLatencyWorkload::linear_ramp(target, self.ramp_steps.get()),
+            duration(self.step_secs),
+            self.nodes.map(NonZeroUsize::get),
+            self.include_validators,
+            self.include_executors,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaPeerBlockingConfig {
+    /// Peers blocked per window.
+    pub peers: NonZeroUsize,
+    pub block_duration_secs: NonZeroU64,
+    pub interval_secs: NonZeroU64,
+    /// Only executors block peers unless enabled.
+    pub include_validators: bool,
+    pub include_executors: bool,
+}
+
+impl Default for DaPeerBlockingConfig {
+    fn default() -> Self {
+        Self {
+            peers: NonZeroUsize::MIN,
+            block_duration_secs: secs(30),
+            interval_secs: secs(30),
+            include_validators: false,
+            include_executors: true,
+        }
+    }
+}
+
+impl DaPeerBlockingConfig {
+    fn build(self) -> Result<DaPeerBlockingWorkload, DynError> {
+        node_groups(
+            "chaos_da_peer_blocking",
+            self.include_validators,
+            self.include_executors,
+        )?;
+        Ok(DaPeerBlockingWorkload::new(
+            self.peers.get(),
+            duration(self.block_duration_secs),
+            duration(self.interval_secs),
+            self.include_validators,
+            self.include_executors,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusLivenessConfig {
+    /// Blocks a node may trail the highest observed height.
+    pub lag_allowance: u64,
+}
+
+impl Default for ConsensusLivenessConfig {
+    fn default() -> Self {
+        Self { lag_allowance: 2 }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DaPruningConfig {
+    /// Expired blobs checked per node; the expectation's default when unset.
+    pub sample_size: Option<NonZeroUsize>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChainConsistencyConfig {
+    /// Deepest fork tolerated between nodes; the expectation's default when
+    /// unset.
+    pub max_fork_depth: Option<u64>,
+}
+
+fn node_groups(name: &str, validators: bool, executors: bool) -> Result<(), DynError> {
+    if validators || executors {
+        Ok(())
+    } else {
+        Err(format!("{name} requires at least one node group").into())
+    }
+}
+
+const fn secs(secs: u64) -> NonZeroU64 {
+    match NonZeroU64::new(secs) {
+        Some(secs) => secs,
+        None => panic!("default durations must be non-zero"),
+    }
+}
+
+pub(crate) const fn duration(secs: NonZeroU64) -> Duration {
+    Duration::from_secs(secs.get())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{Value, json};
+    use testing_framework_core::scenario::{ComponentKind, RegistryError};
+
+    use super::*;
+
+    #[test]
+    fn builtin_components_build_from_defaults() {
+        let registry = builtin_registry();
+
+        for info in registry.components() {
+            let built = match info.kind {
+                ComponentKind::Workload => registry.workload(info.name, Value::Null).map(|_| ()),
+                ComponentKind::Expectation => {
+                    registry.expectation(info.name, Value::Null).map(|_| ())
+                }
+            };
+            built.unwrap_or_else(|err| panic!("{}: {err}", info.name));
+            assert!(info.default_config.is_object(), "{}", info.name);
+        }
+        assert_eq!(
+            registry
+                .workload_info("transactions")
+                .expect("registered")
+                .default_config,
+            json!({ "rate": 1, "users": null, "max_pending": null, "via_blend": false })
+        );
+    }
+
+    #[test]
+    fn chaos_workloads_require_node_control() {
+        let registry = builtin_registry();
+        let requires = |name: &str| {
+            registry
+                .workload_info(name)
+                .unwrap_or_else(|| panic!("{name} is registered"))
+                .requires_node_control
+        };
+
+        assert!(requires("chaos_restart"));
+        assert!(requires("chaos_crash"));
+        assert!(requires("chaos_da_peer_blocking"));
+        assert!(!requires("transactions"));
+        assert!(!requires("da_sampling"));
+        assert!(
+            registry
+                .components()
+                .filter(|info| info.kind == ComponentKind::Expectation)
+                .all(|info| !info.requires_node_control)
+        );
+    }
+
+    #[test]
+    fn unknown_names_and_invalid_configs_are_rejected() {
+        let registry = builtin_registry();
+
+        assert!(matches!(
+            registry.workload("chaos_meteor", Value::Null),
+            Err(RegistryError::Unknown {
+                kind: ComponentKind::Workload,
+                ..
+            })
+        ));
+        // Workload names are not expectation names.
+        assert!(matches!(
+            registry.expectation("transactions", Value::Null),
+            Err(RegistryError::Unknown {
+                kind: ComponentKind::Expectation,
+                ..
+            })
+        ));
+        assert!(matches!(
+            registry.workload("transactions", json!({ "rate": 0 })),
+            Err(RegistryError::Config { .. })
+        ));
+        assert!(matches!(
+            registry.workload(
+                "transactions_ramp",
+                json!({ "start_rate": 10, "end_rate": 5 })
+            ),
+            Err(RegistryError::Build { .. })
+        ));
+        assert!(matches!(
+            registry.workload("chaos_partition", json!({ "groups": 1 })),
+            Err(RegistryError::Build { .. })
+        ));
+        assert!(matches!(
+            registry.workload(
+                "chaos_crash",
+                json!({ "include_validators": false, "include_executors": false })
+            ),
+            Err(RegistryError::Build { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "workload `da` registered twice")]
+    fn duplicate_registration_panics() {
+        builtin_registry().register_workload("da", "another DA workload", |config: DaConfig| {
+            Ok(da::Workload::with_rate(
+                config.blob_rate,
+                config.channel_rate,
+                config.headroom_percent,
+            ))
+        });
+    }
+}
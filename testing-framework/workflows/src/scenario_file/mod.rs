@@ -0,0 +1,132 @@
+//! Declarative scenarios loaded from YAML or TOML files.
+//!
+//! ```yaml
+//! topology: { validators: 3, executors: 1 }
+//! wallets: { users: 20 }
+//! duration_secs: 120
+//! workloads:
+//!   transactions: { rate: 5 }
+//!   da: { channel_rate: 1, blob_rate: 1 }
+//! chaos:
+//!   restart: { min_delay_secs: 20, max_delay_secs: 40, target_cooldown_secs: 60 }
+//! expectations: { consensus_liveness: true }
+//! ```
+//!
+//! Workloads and chaos are built through a
+//! [`ComponentRegistry`](testing_framework_core::scenario::ComponentRegistry)
+//! (see [`crate::registry`]): `transactions` and `da` take the configs of the
+//! registered workloads of the same name, and each chaos entry the config of
+//! its `chaos_*` workload. Other components are listed by name under
+//! `registered`:
+//!
+//! ```yaml
+//! workloads:
+//!   registered:
+//!     - { name: da_sampling, config: { sample_size: 2 } }
+//!     - { name: chaos_crash, config: { interval_secs: 45 } }
+//! expectations:
+//!   registered:
+//!     - { name: chain_consistency }
+//! ```
+
+mod spec;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+pub use spec::{
+    ChaosSpec, ComponentSpec, DistributionSpec, ExpectationsSpec, LateJoinSpec, ScenarioSpec,
+    TopologySpec, WalletSpec, WorkloadsSpec,
+};
+use testing_framework_core::scenario::{NodeControlCapability, RegistryError, Scenario};
+use thiserror::Error;
+
+/// Errors loading a scenario file.
+#[derive(Debug, Error)]
+pub enum ScenarioFileError {
+    #[error("failed to read scenario file {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("unsupported scenario file {path}; expected a .yaml, .yml or .toml extension")]
+    UnknownFormat { path: PathBuf },
+    #[error("invalid YAML scenario: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("invalid TOML scenario: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("invalid scenario: {0}")]
+    Invalid(String),
+    #[error("invalid scenario component: {0}")]
+    Component(#[from] RegistryError),
+}
+
+/// A scenario built from a file. Chaos settings (or `node_control: true`)
+/// require a deployer with node control.
+pub enum LoadedScenario {
+    Plain(Scenario<()>),
+    NodeControl(Scenario<NodeControlCapability>),
+}
+
+impl LoadedScenario {
+    #[must_use]
+    pub const fn requires_node_control(&self) -> bool {
+        matches!(self, Self::NodeControl(_))
+    }
+}
+
+/// Load and build a scenario from a YAML (`.yaml`/`.yml`) or TOML (`.toml`)
+/// file.
+pub fn from_file(path: impl AsRef<Path>) -> Result<LoadedScenario, ScenarioFileError> {
+    build(load_spec(path)?)
+}
+
+/// Parse a scenario file without building it, e.g. to inspect or adjust the
+/// description first. Call [`ScenarioSpec::validate`] before building.
+pub fn load_spec(path: impl AsRef<Path>) -> Result<ScenarioSpec, ScenarioFileError> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path).map_err(|source| ScenarioFileError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("yaml" | "yml") => Ok(serde_yaml::from_str(&contents)?),
+        Some("toml") => Ok(toml::from_str(&contents)?),
+        _ => Err(ScenarioFileError::UnknownFormat {
+            path: path.to_path_buf(),
+        }),
+    }
+}
+
+/// Returns `true` for paths with a scenario file extension.
+#[must_use]
+pub fn is_scenario_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yaml" | "yml" | "toml")
+    )
+}
+
+/// Build a scenario from a YAML description.
+pub fn from_yaml_str(contents: &str) -> Result<LoadedScenario, ScenarioFileError> {
+    build(serde_yaml::from_str(contents)?)
+}
+
+/// Build a scenario from a TOML description.
+pub fn from_toml_str(contents: &str) -> Result<LoadedScenario, ScenarioFileError> {
+    build(toml::from_str(contents)?)
+}
+
+fn build(spec: ScenarioSpec) -> Result<LoadedScenario, ScenarioFileError> {
+    spec.validate()?;
+    tracing::info!(
+        validators = spec.topology.validators,
+        executors = spec.topology.executors,
+        "building scenario from file"
+    );
+    spec.build()
+}
@@ -0,0 +1,707 @@
+use std::{
+    collections::HashSet,
+    num::{NonZeroU64, NonZeroUsize},
+    path::PathBuf,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use testing_framework_core::{
+    scenario::{
+        Builder as CoreScenarioBuilder, ComponentRegistry, Expectation, NodeFeature, NodeTarget,
+        ScenarioBuilder, ScenarioTimings, TagFilter, Workload,
+    },
+    topology::configs::{network::IpStack, wallet::TokenDistribution},
+};
+
+use super::{LoadedScenario, ScenarioFileError};
+use crate::{
+    builder::ScenarioBuilderExt as _,
+    registry::{
+        CRASH_WORKLOAD, CrashConfig, DA_PEER_BLOCKING_WORKLOAD, DA_WORKLOAD, DOWNTIME_WORKLOAD,
+        DaConfig, DaPeerBlockingConfig, DowntimeConfig, LATENCY_WORKLOAD, LatencyConfig,
+        PARTITION_WORKLOAD, PartitionConfig, RESTART_WORKLOAD, RestartConfig,
+        TRANSACTIONS_WORKLOAD, TransactionsConfig, builtin_registry,
+    },
+};
+
+/// Declarative scenario description, as read from a YAML or TOML file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioSpec {
+    pub topology: TopologySpec,
+    #[serde(default)]
+    pub wallets: Option<WalletSpec>,
+    /// Run length in seconds; mutually exclusive with `run_blocks`.
+    #[serde(default)]
+    pub duration_secs: Option<NonZeroU64>,
+    /// Run until this many blocks were observed.
+    #[serde(default)]
+    pub run_blocks: Option<NonZeroU64>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Multiplier applied to deployer timeouts; overrides
+    /// `NOMOS_TIMEOUT_SCALE`.
+    #[serde(default)]
+    pub timeout_scale: Option<f64>,
+    /// Check periodic expectations every this many seconds during the run.
+    #[serde(default)]
+    pub checkpoint_interval_secs: Option<NonZeroU64>,
+    /// Fail the run at the first failing checkpoint.
+    #[serde(default)]
+    pub abort_on_checkpoint_failure: bool,
+    /// Tag filter expression such as `da,!slow`; overrides
+    /// `NOMOS_SCENARIO_TAGS`.
+    #[serde(default)]
+    pub tags: Option<String>,
+    /// Request node control even without chaos workloads.
+    #[serde(default)]
+    pub node_control: bool,
+    /// Write the run report here; `.html` renders a page, anything else JSON.
+    #[serde(default)]
+    pub report: Option<PathBuf>,
+    /// Node features to probe before the workloads start, on top of the ones
+    /// the configured workloads and expectations declare.
+    #[serde(default)]
+    pub requires: Vec<NodeFeature>,
+    #[serde(default)]
+    pub workloads: WorkloadsSpec,
+    #[serde(default)]
+    pub chaos: ChaosSpec,
+    #[serde(default)]
+    pub expectations: ExpectationsSpec,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TopologySpec {
+    #[serde(default)]
+    pub validators: usize,
+    #[serde(default)]
+    pub executors: usize,
+    /// Use the star libp2p layout.
+    #[serde(default)]
+    pub network_star: bool,
+    /// `v4` (default), `v6` or `dual_stack`.
+    #[serde(default)]
+    pub ip_stack: IpStack,
+    /// Nodes started partway through the run instead of with the cluster.
+    #[serde(default)]
+    pub late_joins: Vec<LateJoinSpec>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LateJoinSpec {
+    /// Node label, e.g. `validator-2`.
+    pub node: String,
+    /// Seconds after the run starts at which the node is brought up.
+    pub delay_secs: u64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WalletSpec {
+    pub users: NonZeroUsize,
+    /// Total funds split across `users`; defaults to the framework's
+    /// per-wallet amount, or the sum of explicit amounts.
+    #[serde(default)]
+    pub total_funds: Option<u64>,
+    /// How funds are split; uniform when omitted.
+    #[serde(default)]
+    pub distribution: Option<DistributionSpec>,
+}
+
+/// Token distribution across wallets, e.g. `{ kind: pareto, alpha: 1.2 }`.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields, tag = "kind", rename_all = "snake_case")]
+pub enum DistributionSpec {
+    Uniform,
+    Pareto { alpha: f64 },
+    Zipf { exponent: f64 },
+    Explicit { amounts: Vec<u64> },
+}
+
+impl WalletSpec {
+    fn total_funds(&self) -> Option<u64> {
+        match (&self.distribution, self.total_funds) {
+            (Some(DistributionSpec::Explicit { amounts }), None) => amounts
+                .iter()
+                .try_fold(0u64, |sum, amount| sum.checked_add(*amount)),
+            (_, total) => total,
+        }
+    }
+
+    fn token_distribution(&self) -> TokenDistribution {
+        match &self.distribution {
+            None | Some(DistributionSpec::Uniform) => TokenDistribution::uniform(self.users),
+            Some(DistributionSpec::Pareto { alpha }) => {
+                TokenDistribution::pareto(self.users, *alpha)
+            }
+            Some(DistributionSpec::Zipf { exponent }) => {
+                TokenDistribution::zipf(self.users, *exponent)
+            }
+            Some(DistributionSpec::Explicit { amounts }) => {
+                TokenDistribution::explicit(amounts.clone())
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), ScenarioFileError> {
+        let distribution = self.token_distribution();
+        if distribution.users() != self.users.get() {
+            return Err(ScenarioFileError::Invalid(format!(
+                "wallet distribution funds {} accounts but users is {}",
+                distribution.users(),
+                self.users
+            )));
+        }
+        match self.total_funds() {
+            Some(total) => distribution
+                .amounts(total)
+                .map(drop)
+                .map_err(|err| ScenarioFileError::Invalid(format!("wallets: {err}"))),
+            None if matches!(self.distribution, None | Some(DistributionSpec::Uniform)) => Ok(()),
+            None => Err(ScenarioFileError::Invalid(
+                "skewed wallet distributions require a total_funds that fits in u64".to_owned(),
+            )),
+        }
+    }
+}
+
+/// Workloads of the scenario. The `transactions` and `da` sections take the
+/// configs of the registered `transactions` and `da` workloads.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WorkloadsSpec {
+    #[serde(default)]
+    pub transactions: Option<TransactionsConfig>,
+    #[serde(default)]
+    pub da: Option<DaConfig>,
+    /// Workloads built by name through the component registry.
+    #[serde(default)]
+    pub registered: Vec<ComponentSpec>,
+}
+
+/// A workload or expectation built through a [`ComponentRegistry`], e.g.
+/// `{ name: chaos_crash, config: { interval_secs: 45 } }`. Fields missing from
+/// `config` keep the component's defaults.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ComponentSpec {
+    pub name: String,
+    #[serde(default)]
+    pub config: serde_json::Value,
+}
+
+impl ComponentSpec {
+    fn from_config(name: &str, config: &impl Serialize) -> Self {
+        Self {
+            name: name.to_owned(),
+            config: serde_json::to_value(config).expect("registry configs serialize to JSON"),
+        }
+    }
+}
+
+/// Chaos workloads, each configured like the registered `chaos_*` workload
+/// of the same name.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChaosSpec {
+    #[serde(default)]
+    pub restart: Option<RestartConfig>,
+    #[serde(default)]
+    pub crash: Option<CrashConfig>,
+    #[serde(default)]
+    pub da_peer_blocking: Option<DaPeerBlockingConfig>,
+    #[serde(default)]
+    pub downtime: Option<DowntimeConfig>,
+    #[serde(default)]
+    pub partition: Option<PartitionConfig>,
+    #[serde(default)]
+    pub latency: Option<LatencyConfig>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExpectationsSpec {
+    #[serde(default)]
+    pub consensus_liveness: bool,
+    #[serde(default)]
+    pub chain_consistency: bool,
+    #[serde(default)]
+    pub late_join_sync: bool,
+    #[serde(default)]
+    pub clean_logs: bool,
+    /// Fail when p95 transaction inclusion latency exceeds this many
+    /// milliseconds.
+    #[serde(default)]
+    pub tx_inclusion_p95_ms: Option<NonZeroU64>,
+    /// Fail when a node restarted by chaos takes longer than this many
+    /// seconds to serve its API and catch up with the tip.
+    #[serde(default)]
+    pub recovery_within_secs: Option<NonZeroU64>,
+    /// Fail when a node stays below the DA subnet threshold for longer than
+    /// this many seconds.
+    #[serde(default)]
+    pub da_connectivity_max_outage_secs: Option<u64>,
+    /// Fail when a share of a recently landed blob is stored by fewer than
+    /// this many nodes.
+    #[serde(default)]
+    pub da_min_replicas: Option<usize>,
+    /// Expectations built by name through the component registry.
+    #[serde(default)]
+    pub registered: Vec<ComponentSpec>,
+}
+
+impl ScenarioSpec {
+    /// Check constraints the builders would otherwise enforce by panicking.
+    pub fn validate(&self) -> Result<(), ScenarioFileError> {
+        self.validate_with(&builtin_registry())
+    }
+
+    /// [`Self::validate`], resolving registered components in `registry`.
+    pub fn validate_with(&self, registry: &ComponentRegistry) -> Result<(), ScenarioFileError> {
+        let invalid = |reason: &str| Err(ScenarioFileError::Invalid(reason.to_owned()));
+
+        if self.topology.validators + self.topology.executors == 0 {
+            return invalid("topology must include at least one node");
+        }
+        if self.duration_secs.is_some() && self.run_blocks.is_some() {
+            return invalid("set either duration_secs or run_blocks, not both");
+        }
+        if self
+            .timeout_scale
+            .is_some_and(|scale| !scale.is_finite() || scale <= 0.0)
+        {
+            return invalid("timeout_scale must be a positive number");
+        }
+        let mut late_nodes = HashSet::new();
+        for join in &self.topology.late_joins {
+            let target = NodeTarget::from_label(&join.node);
+            let in_topology = match target {
+                Some(NodeTarget::Validator(index)) => index < self.topology.validators,
+                Some(NodeTarget::Executor(index)) => index < self.topology.executors,
+                None => false,
+            };
+            if !in_topology {
+                return Err(ScenarioFileError::Invalid(format!(
+                    "late join node {} is not part of the topology",
+                    join.node
+                )));
+            }
+            if !late_nodes.insert(target) {
+                return Err(ScenarioFileError::Invalid(format!(
+                    "late join node {} is listed more than once",
+                    join.node
+                )));
+            }
+        }
+        let late_validators = late_nodes
+            .iter()
+            .filter(|target| matches!(target, Some(NodeTarget::Validator(_))))
+            .count();
+        if late_validators > 0 && late_validators >= self.topology.validators {
+            return invalid("at least one validator must run from the start");
+        }
+        if let Some(wallets) = &self.wallets {
+            wallets.validate()?;
+        }
+        if self.abort_on_checkpoint_failure && self.checkpoint_interval_secs.is_none() {
+            return invalid("abort_on_checkpoint_failure requires checkpoint_interval_secs");
+        }
+        if self.expectations.late_join_sync && self.topology.late_joins.is_empty() {
+            return invalid("late_join_sync requires topology late_joins");
+        }
+        if self.expectations.recovery_within_secs.is_some() && !self.restarts_nodes(registry) {
+            return invalid("recovery_within_secs requires chaos restart");
+        }
+        if self.expectations.da_min_replicas.is_some_and(|replicas| {
+            replicas == 0 || replicas > self.topology.validators + self.topology.executors
+        }) {
+            return invalid("da_min_replicas must be between 1 and the number of nodes");
+        }
+        // Building is cheap and the only way to check a config against the
+        // component's own constraints.
+        self.build_workloads(registry)?;
+        self.build_expectations(registry)?;
+
+        Ok(())
+    }
+
+    /// Whether the scenario needs a deployer with node control: requested
+    /// explicitly, or implied by chaos or a registered node control workload.
+    #[must_use]
+    pub fn requires_node_control(&self) -> bool {
+        self.requires_node_control_with(&builtin_registry())
+    }
+
+    /// Whether nodes are restarted through [`NodeRecovery`], by the chaos
+    /// restart settings or a registered `chaos_restart` workload.
+    ///
+    /// [`NodeRecovery`]: testing_framework_core::scenario::NodeRecovery
+    fn restarts_nodes(&self, registry: &ComponentRegistry) -> bool {
+        self.chaos.restart.is_some()
+            || self.workloads.registered.iter().any(|component| {
+                component.name == RESTART_WORKLOAD
+                    && registry.workload_info(&component.name).is_some()
+            })
+    }
+
+    /// [`Self::requires_node_control`], resolving registered components in
+    /// `registry`.
+    #[must_use]
+    pub fn requires_node_control_with(&self, registry: &ComponentRegistry) -> bool {
+        self.node_control
+            || self.chaos.is_enabled()
+            || self.workloads.registered.iter().any(|component| {
+                registry
+                    .workload_info(&component.name)
+                    .is_some_and(|info| info.requires_node_control)
+            })
+    }
+
+    /// Build the described scenario. Call [`Self::validate`] first; fails
+    /// when a workload or expectation cannot be built from its config.
+    pub fn build(&self) -> Result<LoadedScenario, ScenarioFileError> {
+        self.build_with(&builtin_registry())
+    }
+
+    /// [`Self::build`], resolving components in `registry`. Call
+    /// [`Self::validate_with`] with the same registry first.
+    pub fn build_with(
+        &self,
+        registry: &ComponentRegistry,
+    ) -> Result<LoadedScenario, ScenarioFileError> {
+        let workloads = self.build_workloads(registry)?;
+        let expectations = self.build_expectations(registry)?;
+        let topology = &self.topology;
+        let builder = ScenarioBuilder::topology_with(|t| {
+            let mut t = t
+                .validators(topology.validators)
+                .executors(topology.executors)
+                .ip_stack(topology.ip_stack);
+            for join in &topology.late_joins {
+                if let Some(target) = NodeTarget::from_label(&join.node) {
+                    t = t.late_join(target, Duration::from_secs(join.delay_secs));
+                }
+            }
+            if topology.network_star {
+                t.network_star()
+            } else {
+                t
+            }
+        })
+        .with_env_profile();
+        let builder = self.apply_common(builder, workloads, expectations);
+
+        Ok(if self.requires_node_control_with(registry) {
+            LoadedScenario::NodeControl(builder.enable_node_control().build())
+        } else {
+            LoadedScenario::Plain(builder.build())
+        })
+    }
+
+    /// Every workload of the scenario as a registry component: the
+    /// `transactions` and `da` sections, the chaos settings, then the
+    /// registered workloads.
+    fn workload_components(&self) -> impl Iterator<Item = ComponentSpec> {
+        [
+            self.workloads
+                .transactions
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(TRANSACTIONS_WORKLOAD, config)),
+            self.workloads
+                .da
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(DA_WORKLOAD, config)),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(self.chaos.components())
+        .chain(self.workloads.registered.iter().cloned())
+    }
+
+    fn build_workloads(
+        &self,
+        registry: &ComponentRegistry,
+    ) -> Result<Vec<Box<dyn Workload>>, ScenarioFileError> {
+        Ok(self
+            .workload_components()
+            .map(|component| registry.workload(&component.name, component.config))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn build_expectations(
+        &self,
+        registry: &ComponentRegistry,
+    ) -> Result<Vec<Box<dyn Expectation>>, ScenarioFileError> {
+        Ok(self
+            .expectations
+            .registered
+            .iter()
+            .map(|component| registry.expectation(&component.name, component.config.clone()))
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn apply_common<Caps>(
+        &self,
+        mut builder: CoreScenarioBuilder<Caps>,
+        workloads: Vec<Box<dyn Workload>>,
+        expectations: Vec<Box<dyn Expectation>>,
+    ) -> CoreScenarioBuilder<Caps> {
+        if let Some(wallets) = &self.wallets {
+            builder = match wallets.total_funds() {
+                Some(total) => builder.initialize_wallet(total, wallets.token_distribution()),
+                None => builder.wallets(wallets.users.get()),
+            };
+        }
+        if let Some(secs) = self.duration_secs {
+            builder = builder.with_run_duration(Duration::from_secs(secs.get()));
+        }
+        if let Some(blocks) = self.run_blocks {
+            builder = builder.run_for_blocks(blocks.get());
+        }
+        if let Some(seed) = self.seed {
+            builder = builder.with_seed(seed);
+        }
+        if let Some(scale) = self.timeout_scale {
+            builder = builder.with_timings(ScenarioTimings::default().with_scale(scale));
+        }
+        if let Some(tags) = &self.tags {
+            builder = builder.with_tag_filter(TagFilter::parse(tags));
+        }
+        if let Some(path) = &self.report {
+            builder = builder.with_report_path(path.clone());
+        }
+        if !self.requires.is_empty() {
+            builder = builder.require_features(self.requires.iter().copied());
+        }
+        if let Some(secs) = self.checkpoint_interval_secs {
+            builder = builder.with_checkpoint_interval(Duration::from_secs(secs.get()));
+            if self.abort_on_checkpoint_failure {
+                builder = builder.abort_on_checkpoint_failure();
+            }
+        }
+
+        if self.expectations.consensus_liveness {
+            builder = builder.expect_consensus_liveness();
+        }
+        if self.expectations.chain_consistency {
+            builder = builder.expect_chain_consistency();
+        }
+        if self.expectations.late_join_sync {
+            builder = builder.expect_late_join_sync();
+        }
+        if self.expectations.clean_logs {
+            builder = builder.expect_clean_logs();
+        }
+        if let Some(ms) = self.expectations.tx_inclusion_p95_ms {
+            builder = builder.expect_tx_inclusion_p95(Duration::from_millis(ms.get()));
+        }
+        if let Some(secs) = self.expectations.recovery_within_secs {
+            builder = builder.expect_recovery_within(Duration::from_secs(secs.get()));
+        }
+        if let Some(secs) = self.expectations.da_connectivity_max_outage_secs {
+            builder = builder.expect_da_connectivity(Duration::from_secs(secs));
+        }
+        if let Some(min_replicas) = self.expectations.da_min_replicas {
+            builder = builder.expect_da_replication(min_replicas);
+        }
+
+        for workload in workloads {
+            builder = builder.with_boxed_workload(workload);
+        }
+        for expectation in expectations {
+            builder = builder.with_boxed_expectation(expectation);
+        }
+        builder
+    }
+}
+
+impl ChaosSpec {
+    /// Returns `true` when any chaos workload is configured, which requires
+    /// node control.
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.restart.is_some()
+            || self.crash.is_some()
+            || self.da_peer_blocking.is_some()
+            || self.downtime.is_some()
+            || self.partition.is_some()
+            || self.latency.is_some()
+    }
+
+    fn components(&self) -> impl Iterator<Item = ComponentSpec> {
+        [
+            self.restart
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(RESTART_WORKLOAD, config)),
+            self.crash
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(CRASH_WORKLOAD, config)),
+            self.da_peer_blocking
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(DA_PEER_BLOCKING_WORKLOAD, config)),
+            self.downtime
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(DOWNTIME_WORKLOAD, config)),
+            self.partition
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(PARTITION_WORKLOAD, config)),
+            self.latency
+                .as_ref()
+                .map(|config| ComponentSpec::from_config(LATENCY_WORKLOAD, config)),
+        ]
+        .into_iter()
+        .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use testing_framework_core::scenario::RegistryError;
+
+    use super::*;
+    use crate::scenario_file::{is_scenario_file, load_spec};
+
+    fn yaml(contents: &str) -> ScenarioSpec {
+        serde_yaml::from_str(contents).expect("scenario parses")
+    }
+
+    fn invalid_reason(spec: &ScenarioSpec) -> String {
+        match spec.validate() {
+            Err(ScenarioFileError::Invalid(reason)) => reason,
+            other => panic!("expected an invalid scenario, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn example_scenarios_parse_and_validate() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../examples/scenarios");
+        let mut examples = fs::read_dir(&dir)
+            .expect("examples directory exists")
+            .map(|entry| entry.expect("directory entry").path())
+            .filter(|path| is_scenario_file(path))
+            .collect::<Vec<_>>();
+        examples.sort();
+        assert!(!examples.is_empty());
+
+        for path in &examples {
+            let spec = load_spec(path).unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+            spec.validate()
+                .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+        }
+
+        let restart = load_spec(dir.join("restart-chaos.toml")).expect("example parses");
+        assert!(restart.requires_node_control());
+        let smoke = load_spec(dir.join("smoke.yaml")).expect("example parses");
+        assert!(!smoke.requires_node_control());
+        assert_eq!(smoke.topology.validators, 2);
+        let registered = load_spec(dir.join("registered-chaos.yaml")).expect("example parses");
+        assert!(registered.requires_node_control());
+        assert_eq!(registered.workloads.registered.len(), 2);
+        assert_eq!(registered.expectations.registered.len(), 1);
+    }
+
+    #[test]
+    fn unknown_fields_are_rejected() {
+        let err =
+            serde_yaml::from_str::<ScenarioSpec>("topology: { validators: 1, validator: 2 }\n")
+                .expect_err("misspelled topology field");
+        assert!(
+            err.to_string().contains("unknown field `validator`"),
+            "{err}"
+        );
+
+        let err = toml::from_str::<ScenarioSpec>(
+            "[topology]\nvalidators = 1\n\n[chaos.restart]\nmin_delay = 5\n",
+        )
+        .expect_err("misspelled chaos field");
+        assert!(
+            err.to_string().contains("unknown field `min_delay`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn recovery_needs_restarts_from_chaos_or_the_registry() {
+        let spec =
+            yaml("topology: { validators: 2 }\nexpectations: { recovery_within_secs: 60 }\n");
+        assert_eq!(
+            invalid_reason(&spec),
+            "recovery_within_secs requires chaos restart"
+        );
+
+        let spec = yaml(
+            "topology: { validators: 2 }\nworkloads:\n  registered: [{ name: chaos_restart }]\nexpectations: { recovery_within_secs: 60 }\n",
+        );
+        spec.validate().expect("registered restarts are tracked");
+        assert!(
+            !spec.restarts_nodes(&ComponentRegistry::new()),
+            "unknown to an empty registry"
+        );
+    }
+
+    #[test]
+    fn invalid_values_are_reported() {
+        let spec = yaml("topology: { validators: 0, executors: 0 }\n");
+        assert_eq!(
+            invalid_reason(&spec),
+            "topology must include at least one node"
+        );
+
+        let spec = yaml("topology: { validators: 1 }\nduration_secs: 60\nrun_blocks: 10\n");
+        assert_eq!(
+            invalid_reason(&spec),
+            "set either duration_secs or run_blocks, not both"
+        );
+
+        let spec = yaml(
+            "topology:\n  validators: 2\n  late_joins: [{ node: validator-5, delay_secs: 30 }]\n",
+        );
+        assert_eq!(
+            invalid_reason(&spec),
+            "late join node validator-5 is not part of the topology"
+        );
+
+        let spec = yaml(
+            "topology:\n  validators: 3\n  late_joins:\n    - { node: validator-1, delay_secs: 30 }\n    - { node: validator-1, delay_secs: 60 }\n",
+        );
+        assert_eq!(
+            invalid_reason(&spec),
+            "late join node validator-1 is listed more than once"
+        );
+    }
+
+    #[test]
+    fn component_configs_are_checked_by_the_registry() {
+        let spec = yaml(
+            "topology: { validators: 2 }\nchaos:\n  restart: { min_delay_secs: 40, max_delay_secs: 20 }\n",
+        );
+        let err = spec.validate().expect_err("restart delays are inverted");
+        assert!(
+            matches!(
+                err,
+                ScenarioFileError::Component(RegistryError::Build { .. })
+            ),
+            "{err}"
+        );
+        assert!(
+            err.to_string()
+                .contains("min_delay_secs must not exceed max_delay_secs"),
+            "{err}"
+        );
+
+        // Building without validating reports the error instead of panicking.
+        let spec = yaml(
+            "topology: { validators: 2 }\nworkloads:\n  registered: [{ name: chaos_partition, config: { groups: 1 } }]\n",
+        );
+        assert!(matches!(
+            spec.build(),
+            Err(ScenarioFileError::Component(RegistryError::Build { .. }))
+        ));
+    }
+}
@@ -1,8 +1,11 @@
 use key_management_system_service::keys::{Ed25519Key, ZkKey};
-use nomos_core::mantle::{
-    MantleTx, Op, OpProof, SignedMantleTx, Transaction as _,
-    ledger::Tx as LedgerTx,
-    ops::channel::{ChannelId, MsgId, inscribe::InscriptionOp},
+use nomos_core::{
+    mantle::{
+        MantleTx, Op, OpProof, SignedMantleTx, Transaction as _,
+        ledger::Tx as LedgerTx,
+        ops::channel::{ChannelId, MsgId, inscribe::InscriptionOp},
+    },
+    sdp::{DeclarationId, DeclarationMessage, WithdrawMessage},
 };
 
 /// Builds a signed inscription transaction with deterministic payload for
@@ -38,3 +41,70 @@ pub fn create_inscription_transaction_with_id(id: ChannelId) -> SignedMantleTx {
     )
     .expect("valid transaction")
 }
+
+/// Builds a transaction withdrawing SDP declaration `declaration_id`, signed
+/// with the declaration's zk key. `nonce` must exceed the last nonce used for
+/// the declaration.
+#[must_use]
+pub fn create_sdp_withdraw_transaction(
+    declaration_id: DeclarationId,
+    nonce: u64,
+    zk_key: &ZkKey,
+) -> SignedMantleTx {
+    let mantle_tx = MantleTx {
+        ops: vec![Op::SDPWithdraw(WithdrawMessage {
+            declaration_id,
+            nonce,
+        })],
+        ledger_tx: LedgerTx::new(vec![], vec![]),
+        storage_gas_price: 0,
+        execution_gas_price: 0,
+    };
+
+    let tx_hash = mantle_tx.hash();
+    let zk_sig = ZkKey::multi_sign(std::slice::from_ref(zk_key), tx_hash.as_ref())
+        .expect("zk signature generation");
+    tracing::debug!(?declaration_id, nonce, tx_hash = ?tx_hash, "building SDP withdraw transaction");
+
+    SignedMantleTx::new(
+        mantle_tx,
+        vec![OpProof::ZkSig(zk_sig)],
+        ZkKey::multi_sign(&[ZkKey::zero()], tx_hash.as_ref()).expect("zk signature generation"),
+    )
+    .expect("valid transaction")
+}
+
+/// Builds a transaction submitting `declaration`, signed like the genesis
+/// declarations: the locked note's key and the declared zk key jointly, plus
+/// the provider key.
+#[must_use]
+pub fn create_sdp_declare_transaction(
+    declaration: DeclarationMessage,
+    note_key: &ZkKey,
+    zk_key: &ZkKey,
+    provider_key: &Ed25519Key,
+) -> SignedMantleTx {
+    let provider_id = declaration.provider_id;
+    let mantle_tx = MantleTx {
+        ops: vec![Op::SDPDeclare(declaration)],
+        ledger_tx: LedgerTx::new(vec![], vec![]),
+        storage_gas_price: 0,
+        execution_gas_price: 0,
+    };
+
+    let tx_hash = mantle_tx.hash();
+    let zk_sig = ZkKey::multi_sign(&[note_key.clone(), zk_key.clone()], tx_hash.as_ref())
+        .expect("zk signature generation");
+    let ed25519_sig = provider_key.sign_payload(tx_hash.as_signing_bytes().as_ref());
+    tracing::debug!(?provider_id, tx_hash = ?tx_hash, "building SDP declare transaction");
+
+    SignedMantleTx::new(
+        mantle_tx,
+        vec![OpProof::ZkAndEd25519Sigs {
+            zk_sig,
+            ed25519_sig,
+        }],
+        ZkKey::multi_sign(&[ZkKey::zero()], tx_hash.as_ref()).expect("zk signature generation"),
+    )
+    .expect("valid transaction")
+}
@@ -0,0 +1,4 @@
+mod traffic;
+
+pub use traffic::BlendTrafficWorkload;
+pub(crate) use traffic::core_blend_nodes;
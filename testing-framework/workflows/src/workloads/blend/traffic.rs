@@ -0,0 +1,543 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    num::NonZeroU64,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nomos_core::{
+    header::HeaderId,
+    mantle::{
+        AuthenticatedMantleTx as _,
+        ops::{Op, channel::ChannelId},
+    },
+};
+use testing_framework_core::{
+    scenario::{DynError, Expectation, NodeTarget, RunContext, RunMetrics, Workload},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::{sync::broadcast, task::JoinHandle, time::sleep};
+use tracing::{debug, info, warn};
+
+use crate::{util::tx, workloads::util::submit_transaction_via_nodes};
+
+const WORKLOAD_NAME: &str = "blend_traffic";
+const EXPECTATION_NAME: &str = "blend_participation";
+/// Placeholder replaced by the node label (`validator-0`, ...) in activity
+/// metric queries.
+const NODE_PLACEHOLDER: &str = "{node}";
+/// Share of submitted messages that must be included; the last ones may still
+/// be in flight when the run ends.
+const MIN_INCLUSION_RATIO: f64 = 0.5;
+/// Message interval used when the topology gives no block interval hint.
+const FALLBACK_INTERVAL: Duration = Duration::from_secs(1);
+/// Most recent blocks carrying inscriptions kept by the capture.
+const MAX_CAPTURED_BLOCKS: usize = 1024;
+/// Blocks carrying blend messages looked up in every core node's storage.
+const MAX_CHECKED_CARRIERS: usize = 16;
+
+/// Messages the workload submitted, shared with its expectation.
+type SharedMessages = Arc<Mutex<Vec<BlendMessage>>>;
+
+#[derive(Clone, Copy, Debug)]
+struct BlendMessage {
+    entry: NodeTarget,
+    channel: ChannelId,
+}
+
+/// Sends message traffic through the Blend network and checks that every
+/// core blend node takes part in routing it.
+///
+/// Blend carries block proposals: leaders hand them to the core blend nodes,
+/// which mix them with their cover traffic before the block is broadcast.
+/// Nodes expose no endpoint to submit a Blend message directly, so the
+/// workload keeps proposals loaded instead: it submits small channel
+/// inscriptions to the mempool of each core blend node in turn, at
+/// `messages_per_block`, and the blocks including them cross the Blend
+/// network.
+///
+/// The paired expectation requires at least half of the inscriptions, and
+/// some from every entry node, to be included; every core blend node's chain
+/// to have advanced during the run and to hold the blocks that carried them;
+/// and, when [`Self::with_activity_metric`] is set, that metric to be
+/// non-zero for every core blend node.
+pub struct BlendTrafficWorkload {
+    messages_per_block: NonZeroU64,
+    activity_metric: Option<String>,
+    core_nodes: Vec<NodeTarget>,
+    messages: SharedMessages,
+}
+
+impl Default for BlendTrafficWorkload {
+    fn default() -> Self {
+        Self::new(NonZeroU64::MIN)
+    }
+}
+
+impl BlendTrafficWorkload {
+    #[must_use]
+    pub fn new(messages_per_block: NonZeroU64) -> Self {
+        Self {
+            messages_per_block,
+            activity_metric: None,
+            core_nodes: Vec::new(),
+            messages: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    #[must_use]
+    /// Also require the PromQL `query` to be positive for every core blend
+    /// node; `{node}` in the query is replaced by the node label, e.g.
+    /// `sum(blend_messages_processed_total{node="{node}"})`.
+    pub fn with_activity_metric(mut self, query: impl Into<String>) -> Self {
+        self.activity_metric = Some(query.into());
+        self
+    }
+
+    fn interval(&self, ctx: &RunContext) -> Duration {
+        ctx.run_metrics()
+            .block_interval_hint()
+            .map_or(FALLBACK_INTERVAL, |block| {
+                block / u32::try_from(self.messages_per_block.get()).unwrap_or(u32::MAX)
+            })
+    }
+}
+
+#[async_trait]
+impl Workload for BlendTrafficWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["blend"]
+    }
+
+    fn observes_cancellation(&self) -> bool {
+        true
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(BlendParticipationExpectation {
+            messages: Arc::clone(&self.messages),
+            activity_metric: self.activity_metric.clone(),
+            core_nodes: Vec::new(),
+            start_heights: HashMap::new(),
+            captured: Arc::default(),
+            capture: None,
+        })]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        self.core_nodes = core_blend_nodes(descriptors);
+        if self.core_nodes.is_empty() {
+            return Err(BlendTrafficError::NoCoreNodes.into());
+        }
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let interval = self.interval(ctx);
+        info!(
+            messages_per_block = self.messages_per_block.get(),
+            core_nodes = self.core_nodes.len(),
+            interval_ms = interval.as_millis(),
+            "starting blend traffic workload"
+        );
+
+        for (sequence, entry) in (0u64..).zip(self.core_nodes.iter().copied().cycle()) {
+            let channel = blend_channel_id(sequence);
+            let inscription = Arc::new(tx::create_inscription_transaction_with_id(channel));
+            match submit_transaction_via_nodes(ctx, inscription, &[entry]).await {
+                Ok(()) => {
+                    self.messages
+                        .lock()
+                        .expect("blend messages lock poisoned")
+                        .push(BlendMessage { entry, channel });
+                    ctx.report().increment(WORKLOAD_NAME, "submitted", 1);
+                    debug!(%entry, ?channel, "blend message submitted");
+                }
+                Err(err) => {
+                    ctx.report().increment(WORKLOAD_NAME, "rejected", 1);
+                    warn!(%entry, error = %err, "blend message rejected");
+                }
+            }
+            if ctx
+                .cancellation()
+                .until_cancelled(sleep(interval))
+                .await
+                .is_none()
+            {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Every node is declared a blend provider in genesis; nodes joining late are
+/// left out because they may not have routed anything yet.
+pub(crate) fn core_blend_nodes(descriptors: &GeneratedTopology) -> Vec<NodeTarget> {
+    let validators = (0..descriptors.validators().len()).map(NodeTarget::Validator);
+    let executors = (0..descriptors.executors().len()).map(NodeTarget::Executor);
+    validators
+        .chain(executors)
+        .filter(|target| !descriptors.config().joins_late(*target))
+        .collect()
+}
+
+fn blend_channel_id(sequence: u64) -> ChannelId {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(b"chn_blnd");
+    bytes[24..].copy_from_slice(&sequence.to_be_bytes());
+    ChannelId::from(bytes)
+}
+
+#[derive(Debug, Error)]
+enum BlendTrafficError {
+    #[error("blend traffic needs at least one core blend node")]
+    NoCoreNodes,
+}
+
+/// Inscriptions seen on the block feed.
+#[derive(Default)]
+struct Captured {
+    included: HashSet<ChannelId>,
+    /// Most recent blocks carrying inscriptions, with their channels.
+    blocks: VecDeque<(HeaderId, Vec<ChannelId>)>,
+}
+
+impl Captured {
+    fn observe(&mut self, block: HeaderId, channels: Vec<ChannelId>) {
+        if channels.is_empty() {
+            return;
+        }
+        self.included.extend(channels.iter().copied());
+        self.blocks.push_back((block, channels));
+        if self.blocks.len() > MAX_CAPTURED_BLOCKS {
+            self.blocks.pop_front();
+        }
+    }
+
+    /// The most recent blocks, up to `limit`, that included at least one of
+    /// `messages`.
+    fn carriers(&self, messages: &[BlendMessage], limit: usize) -> Vec<HeaderId> {
+        let submitted = messages
+            .iter()
+            .map(|message| message.channel)
+            .collect::<HashSet<_>>();
+        self.blocks
+            .iter()
+            .rev()
+            .filter(|(_, channels)| channels.iter().any(|channel| submitted.contains(channel)))
+            .map(|(block, _)| *block)
+            .take(limit)
+            .collect()
+    }
+}
+
+/// Checks that traffic from [`BlendTrafficWorkload`] made it through the
+/// Blend network and that every core blend node participated.
+struct BlendParticipationExpectation {
+    messages: SharedMessages,
+    activity_metric: Option<String>,
+    core_nodes: Vec<NodeTarget>,
+    start_heights: HashMap<NodeTarget, u64>,
+    captured: Arc<Mutex<Captured>>,
+    capture: Option<JoinHandle<()>>,
+}
+
+impl Drop for BlendParticipationExpectation {
+    fn drop(&mut self) {
+        if let Some(capture) = self.capture.take() {
+            capture.abort();
+        }
+    }
+}
+
+/// Messages included, and one violation per shortfall: fewer than
+/// [`MIN_INCLUSION_RATIO`] of them included, or an entry node none of whose
+/// messages were.
+fn inclusion_violations(
+    messages: &[BlendMessage],
+    included: &HashSet<ChannelId>,
+) -> (usize, Vec<String>) {
+    let mut included_by_entry = HashMap::<NodeTarget, usize>::new();
+    for message in messages {
+        let count = included_by_entry.entry(message.entry).or_default();
+        if included.contains(&message.channel) {
+            *count += 1;
+        }
+    }
+
+    let mut violations = Vec::new();
+    let included_total = included_by_entry.values().sum::<usize>();
+    let required = (messages.len() as f64 * MIN_INCLUSION_RATIO).ceil() as usize;
+    if included_total < required {
+        violations.push(format!(
+            "only {included_total} of {} messages included (required {required})",
+            messages.len()
+        ));
+    }
+    let mut silent = included_by_entry
+        .into_iter()
+        .filter(|(_, count)| *count == 0)
+        .map(|(entry, _)| entry)
+        .collect::<Vec<_>>();
+    silent.sort_by_key(ToString::to_string);
+    violations.extend(
+        silent
+            .into_iter()
+            .map(|entry| format!("{entry}: none of the messages it relayed were included")),
+    );
+    (included_total, violations)
+}
+
+#[derive(Debug, Error)]
+enum BlendParticipationError {
+    #[error("blend traffic workload submitted no messages")]
+    NoMessages,
+    #[error("blend activity metric requires a prometheus endpoint")]
+    PrometheusUnavailable,
+    #[error("blend participation incomplete:\n{0}")]
+    Violations(String),
+}
+
+#[async_trait]
+impl Expectation for BlendParticipationExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["blend"]
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.activity_metric.is_some() && ctx.telemetry().prometheus().is_none() {
+            return Err(BlendParticipationError::PrometheusUnavailable.into());
+        }
+
+        self.core_nodes = core_blend_nodes(ctx.descriptors());
+        for target in &self.core_nodes {
+            let client = ctx
+                .node_clients()
+                .client(*target)
+                .ok_or_else(|| format!("no api client for core blend node {target}"))?;
+            let height = client
+                .consensus_info()
+                .await
+                .map_err(|err| format!("consensus info of {target} failed: {err}"))?
+                .height;
+            self.start_heights.insert(*target, height);
+        }
+
+        if let Some(previous) = self.capture.take() {
+            previous.abort();
+        }
+        let mut receiver = ctx.block_feed().subscribe();
+        let captured = Arc::clone(&self.captured);
+        self.capture = Some(tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => {
+                        let channels = record
+                            .block
+                            .transactions()
+                            .flat_map(|tx| tx.mantle_tx().ops.iter())
+                            .filter_map(|op| match op {
+                                Op::ChannelInscribe(inscription) => Some(inscription.channel_id),
+                                _ => None,
+                            })
+                            .collect();
+                        captured
+                            .lock()
+                            .expect("blend capture lock poisoned")
+                            .observe(record.header, channels);
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        debug!(skipped, "blend participation capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }));
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let messages = self
+            .messages
+            .lock()
+            .expect("blend messages lock poisoned")
+            .clone();
+        if messages.is_empty() {
+            return Err(BlendParticipationError::NoMessages.into());
+        }
+
+        if let Some(capture) = self.capture.take() {
+            capture.abort();
+        }
+        let (included_total, mut violations, carriers) = {
+            let captured = self.captured.lock().expect("blend capture lock poisoned");
+            let (included_total, violations) = inclusion_violations(&messages, &captured.included);
+            let carriers = captured.carriers(&messages, MAX_CHECKED_CARRIERS);
+            (included_total, violations, carriers)
+        };
+        ctx.report()
+            .set_counter(WORKLOAD_NAME, "included", included_total as u64);
+
+        for target in &self.core_nodes {
+            let Some(client) = ctx.node_clients().client(*target) else {
+                violations.push(format!("{target}: no api client"));
+                continue;
+            };
+            let start = self.start_heights.get(target).copied().unwrap_or_default();
+            match client.consensus_info().await {
+                Ok(info) if info.height > start => {}
+                Ok(info) => violations.push(format!(
+                    "{target}: chain stuck at height {} (started at {start})",
+                    info.height
+                )),
+                Err(err) => violations.push(format!("{target}: consensus info failed: {err}")),
+            }
+
+            // The carrying blocks were proposed through Blend; a node holding
+            // none of them was cut off from it.
+            if !carriers.is_empty() {
+                let mut held = 0;
+                for block in &carriers {
+                    match client.storage_block(block).await {
+                        Ok(Some(_)) => held += 1,
+                        Ok(None) => {}
+                        Err(err) => {
+                            violations.push(format!("{target}: block lookup failed: {err}"));
+                            break;
+                        }
+                    }
+                }
+                if held == 0 {
+                    violations.push(format!(
+                        "{target}: holds none of the {} latest blocks carrying blend messages",
+                        carriers.len()
+                    ));
+                }
+            }
+
+            if let Some(template) = &self.activity_metric {
+                let query = template.replace(NODE_PLACEHOLDER, &target.to_string());
+                match ctx.telemetry().counter_value(&query) {
+                    Ok(value) if value > 0.0 => {}
+                    Ok(value) => violations.push(format!("{target}: `{query}` = {value}")),
+                    Err(err) => violations.push(format!("{target}: `{query}` failed: {err}")),
+                }
+            }
+        }
+
+        if violations.is_empty() {
+            info!(
+                messages = messages.len(),
+                core_nodes = self.core_nodes.len(),
+                "every core blend node participated"
+            );
+            return Ok(());
+        }
+        Err(BlendParticipationError::Violations(violations.join("\n")).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use testing_framework_core::scenario::{Scenario, ScenarioBuilder};
+
+    use super::*;
+
+    fn message(entry: NodeTarget, sequence: u64) -> BlendMessage {
+        BlendMessage {
+            entry,
+            channel: blend_channel_id(sequence),
+        }
+    }
+
+    #[test]
+    fn channel_ids_are_distinct_per_sequence() {
+        let ids = (0..64).map(blend_channel_id).collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 64);
+        assert_eq!(blend_channel_id(3), blend_channel_id(3));
+    }
+
+    #[test]
+    fn late_joiners_are_not_core_blend_nodes() {
+        let scenario: Scenario<()> = ScenarioBuilder::topology_with(|t| {
+            t.validators(3)
+                .executors(1)
+                .late_join_validator(1, Duration::from_secs(30))
+        })
+        .with_run_duration(Duration::from_secs(120))
+        .build();
+
+        assert_eq!(
+            core_blend_nodes(scenario.topology()),
+            vec![
+                NodeTarget::Validator(0),
+                NodeTarget::Validator(2),
+                NodeTarget::Executor(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn inclusion_requires_a_share_and_every_entry() {
+        let (a, b) = (NodeTarget::Validator(0), NodeTarget::Validator(1));
+        let messages = (0..4)
+            .map(|sequence| message(if sequence % 2 == 0 { a } else { b }, sequence))
+            .collect::<Vec<_>>();
+
+        let all = messages.iter().map(|message| message.channel).collect();
+        assert_eq!(inclusion_violations(&messages, &all), (4, Vec::new()));
+
+        let half = [0, 1].map(blend_channel_id).into_iter().collect();
+        assert_eq!(inclusion_violations(&messages, &half), (2, Vec::new()));
+
+        let only_a = [0, 2].map(blend_channel_id).into_iter().collect();
+        let (included, violations) = inclusion_violations(&messages, &only_a);
+        assert_eq!(included, 2);
+        assert_eq!(
+            violations,
+            vec!["validator-1: none of the messages it relayed were included".to_owned()]
+        );
+
+        let (included, violations) = inclusion_violations(&messages, &HashSet::new());
+        assert_eq!(included, 0);
+        assert_eq!(violations.len(), 3, "{violations:?}");
+        assert!(violations[0].contains("only 0 of 4"), "{violations:?}");
+    }
+
+    #[test]
+    fn carriers_are_the_latest_blocks_with_submitted_messages() {
+        let entry = NodeTarget::Validator(0);
+        let messages = vec![message(entry, 0), message(entry, 1)];
+        let foreign = ChannelId::from([9; 32]);
+        let mut captured = Captured::default();
+        captured.observe(HeaderId::from([1; 32]), vec![blend_channel_id(0)]);
+        captured.observe(HeaderId::from([2; 32]), vec![foreign]);
+        captured.observe(HeaderId::from([3; 32]), Vec::new());
+        captured.observe(HeaderId::from([4; 32]), vec![foreign, blend_channel_id(1)]);
+
+        assert_eq!(captured.blocks.len(), 3);
+        assert_eq!(
+            captured.carriers(&messages, 16),
+            vec![HeaderId::from([4; 32]), HeaderId::from([1; 32])]
+        );
+        assert_eq!(
+            captured.carriers(&messages, 1),
+            vec![HeaderId::from([4; 32])]
+        );
+    }
+}
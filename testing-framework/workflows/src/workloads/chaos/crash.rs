@@ -0,0 +1,121 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::seq::SliceRandom as _;
+use testing_framework_core::scenario::{
+    DynError, NodeControlHandle, NodeEventKind, NodeTarget, RunContext, Workload,
+};
+use tokio::time::sleep;
+use tracing::info;
+
+use super::downtime::{DowntimeGuard, wait_for_catch_up};
+
+/// Kills one node at a time with `SIGKILL`, starts it again after a delay and
+/// verifies it recovers.
+///
+/// Unlike [`super::RandomRestartWorkload`], the node gets no graceful
+/// shutdown, so this exercises recovery from an unclean stop (partially
+/// written storage, WAL replay). Each cycle waits `interval`, kills a random
+/// eligible node, waits `restart_delay`, starts it and fails the workload
+/// unless the node reaches its peers' height within `recovery_timeout`.
+#[derive(Debug)]
+pub struct NodeCrashWorkload {
+    restart_delay: Duration,
+    interval: Duration,
+    recovery_timeout: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl NodeCrashWorkload {
+    /// Creates a crash workload killing a node every `interval` and starting
+    /// it again after `restart_delay`.
+    #[must_use]
+    pub const fn new(
+        restart_delay: Duration,
+        interval: Duration,
+        recovery_timeout: Duration,
+        include_validators: bool,
+        include_executors: bool,
+    ) -> Self {
+        Self {
+            restart_delay,
+            interval,
+            recovery_timeout,
+            include_validators,
+            include_executors,
+        }
+    }
+
+    fn targets(&self, ctx: &RunContext) -> Vec<NodeTarget> {
+        let mut targets = Vec::new();
+        let validator_count = ctx.descriptors().validators().len();
+        if self.include_validators {
+            if validator_count > 1 {
+                targets.extend((0..validator_count).map(NodeTarget::Validator));
+            } else if validator_count == 1 {
+                info!("chaos crash skipping validators: only one validator configured");
+            }
+        }
+        if self.include_executors {
+            targets.extend((0..ctx.descriptors().executors().len()).map(NodeTarget::Executor));
+        }
+        targets
+    }
+}
+
+#[async_trait]
+impl Workload for NodeCrashWorkload {
+    fn name(&self) -> &'static str {
+        "chaos_crash"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos crash workload requires node control".to_owned())?;
+
+        let targets = self.targets(ctx);
+        if targets.is_empty() {
+            return Err("chaos crash workload has no eligible targets".into());
+        }
+
+        info!(
+            config = ?self,
+            target_count = targets.len(),
+            "starting chaos crash workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        loop {
+            sleep(self.interval).await;
+
+            let target = *targets.choose(&mut rng).expect("targets checked non-empty");
+            info!(%target, restart_delay = ?self.restart_delay, "chaos killing node");
+            let guard = DowntimeGuard::new(Arc::clone(&handle), target);
+            kill_node(handle.as_ref(), target)
+                .await
+                .map_err(|err| format!("killing {target} failed: {err}"))?;
+            ctx.report().record_node_event(target, NodeEventKind::Kill);
+
+            sleep(self.restart_delay).await;
+
+            info!(%target, "chaos starting killed node");
+            guard.start().await?;
+            ctx.report().record_node_event(target, NodeEventKind::Start);
+
+            wait_for_catch_up(ctx, target, self.recovery_timeout).await?;
+        }
+    }
+}
+
+async fn kill_node(handle: &dyn NodeControlHandle, target: NodeTarget) -> Result<(), DynError> {
+    match target {
+        NodeTarget::Validator(index) => handle.kill_validator(index).await,
+        NodeTarget::Executor(index) => handle.kill_executor(index).await,
+    }
+}
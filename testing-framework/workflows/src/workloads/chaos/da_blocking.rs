@@ -0,0 +1,422 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use nomos_core::mantle::{AuthenticatedMantleTx as _, ops::Op};
+use rand::seq::SliceRandom as _;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, NodeFeature, NodeTarget, RunContext, Workload},
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    time::{Instant, sleep},
+};
+use tracing::{info, warn};
+
+const WORKLOAD_NAME: &str = "chaos_da_peer_blocking";
+
+/// When peers were blocked; `end` is unset while the window is open.
+#[derive(Clone, Copy, Debug)]
+struct BlockedWindow {
+    start: Instant,
+    end: Option<Instant>,
+}
+
+type BlockedWindows = Arc<Mutex<Vec<BlockedWindow>>>;
+
+/// Blocks DA peers on selected nodes for a window, then unblocks them.
+///
+/// Each cycle waits `interval`, picks `peers` random validators and makes
+/// every selected node block their DA peer ids through the DA testing API for
+/// `block_duration`. Blocked peers are never the whole validator set, so
+/// dispersal can still reach the remaining ones. The paired expectation
+/// fails the run unless DA blobs kept landing while peers were blocked.
+#[derive(Debug)]
+pub struct DaPeerBlockingWorkload {
+    peers: usize,
+    block_duration: Duration,
+    interval: Duration,
+    include_validators: bool,
+    include_executors: bool,
+    windows: BlockedWindows,
+}
+
+impl DaPeerBlockingWorkload {
+    /// Creates a workload blocking `peers` validators' DA peer ids on the
+    /// selected node groups.
+    #[must_use]
+    pub fn new(
+        peers: usize,
+        block_duration: Duration,
+        interval: Duration,
+        include_validators: bool,
+        include_executors: bool,
+    ) -> Self {
+        Self {
+            peers,
+            block_duration,
+            interval,
+            include_validators,
+            include_executors,
+            windows: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn blocking_nodes(&self, ctx: &RunContext) -> Vec<NodeTarget> {
+        let mut targets = Vec::new();
+        if self.include_validators {
+            targets.extend((0..ctx.descriptors().validators().len()).map(NodeTarget::Validator));
+        }
+        if self.include_executors {
+            targets.extend((0..ctx.descriptors().executors().len()).map(NodeTarget::Executor));
+        }
+        targets
+    }
+}
+
+#[async_trait]
+impl Workload for DaPeerBlockingWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos", "da"]
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        &[NodeFeature::TestingHttp]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(DaDispersalUnderBlockingExpectation::new(
+            Arc::clone(&self.windows),
+        ))]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let validators = ctx.descriptors().validators();
+        if self.peers == 0 || self.peers >= validators.len() {
+            return Err(format!(
+                "DA peer blocking needs 1..{} blocked peers to leave dispersal a route, got {}",
+                validators.len(),
+                self.peers
+            )
+            .into());
+        }
+
+        let nodes = self
+            .blocking_nodes(ctx)
+            .into_iter()
+            .filter_map(|target| {
+                let client = ctx.node_clients().client(target)?;
+                Some((target, client.clone()))
+            })
+            .collect::<Vec<_>>();
+        if nodes.is_empty() {
+            return Err("DA peer blocking workload has no nodes to block peers on".into());
+        }
+
+        info!(
+            peers = self.peers,
+            nodes = nodes.len(),
+            block_secs = self.block_duration.as_secs(),
+            interval_secs = self.interval.as_secs(),
+            "starting DA peer blocking workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        loop {
+            sleep(self.interval).await;
+
+            let mut candidates = (0..validators.len()).collect::<Vec<_>>();
+            candidates.shuffle(&mut rng);
+            let blocked = candidates
+                .into_iter()
+                .take(self.peers)
+                .map(|index| {
+                    (
+                        NodeTarget::Validator(index),
+                        validators[index].general.da_config.peer_id.to_string(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            // A node blocking itself is meaningless; skip those pairs.
+            let pairs = nodes
+                .iter()
+                .flat_map(|(node, client)| {
+                    blocked
+                        .iter()
+                        .filter(move |(peer, _)| peer != node)
+                        .map(move |(_, peer_id)| (client.clone(), peer_id.clone()))
+                })
+                .collect::<Vec<_>>();
+
+            info!(
+                peers = ?blocked.iter().map(|(target, _)| target.to_string()).collect::<Vec<_>>(),
+                "blocking DA peers"
+            );
+            let guard = UnblockGuard::new(pairs, Arc::clone(&self.windows));
+            guard.block().await?;
+            ctx.report()
+                .increment(self.name(), "blocked_peers", blocked.len() as u64);
+
+            sleep(self.block_duration).await;
+
+            info!("unblocking DA peers");
+            guard.unblock().await?;
+        }
+    }
+}
+
+/// Tracks one blocking window. Unblocks peers when the workload is aborted
+/// mid-window so expectations do not evaluate against a degraded DA network.
+struct UnblockGuard {
+    pairs: Option<Vec<(ApiClient, String)>>,
+    windows: BlockedWindows,
+}
+
+impl UnblockGuard {
+    const fn new(pairs: Vec<(ApiClient, String)>, windows: BlockedWindows) -> Self {
+        Self {
+            pairs: Some(pairs),
+            windows,
+        }
+    }
+
+    fn close_window(&self) {
+        if let Some(window) = self
+            .windows
+            .lock()
+            .expect("blocked windows lock poisoned")
+            .last_mut()
+        {
+            window.end.get_or_insert_with(Instant::now);
+        }
+    }
+
+    async fn block(&self) -> Result<(), DynError> {
+        self.windows
+            .lock()
+            .expect("blocked windows lock poisoned")
+            .push(BlockedWindow {
+                start: Instant::now(),
+                end: None,
+            });
+        let pairs = self.pairs.as_deref().unwrap_or_default();
+        let results = join_all(
+            pairs
+                .iter()
+                .map(|(client, peer_id)| client.block_peer(peer_id)),
+        )
+        .await;
+        for ((client, peer_id), result) in pairs.iter().zip(results) {
+            result.map_err(|err| {
+                format!(
+                    "blocking DA peer {peer_id} on {} failed: {err}",
+                    client.base_url()
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Unblock every pair, even after some fail, and report all failures.
+    async fn unblock(mut self) -> Result<(), DynError> {
+        self.close_window();
+        let pairs = self.pairs.take().unwrap_or_default();
+        let results = join_all(
+            pairs
+                .iter()
+                .map(|(client, peer_id)| client.unblock_peer(peer_id)),
+        )
+        .await;
+        let failures = pairs
+            .iter()
+            .zip(results)
+            .filter_map(|((client, peer_id), result)| {
+                result.err().map(|err| {
+                    format!(
+                        "unblocking DA peer {peer_id} on {} failed: {err}",
+                        client.base_url()
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+        if failures.is_empty() {
+            return Ok(());
+        }
+        Err(failures.join("; ").into())
+    }
+}
+
+impl Drop for UnblockGuard {
+    fn drop(&mut self) {
+        if let Some(pairs) = self.pairs.take() {
+            self.close_window();
+            tokio::spawn(async move {
+                for (client, peer_id) in pairs {
+                    if let Err(err) = client.unblock_peer(&peer_id).await {
+                        warn!(peer_id, error = %err, "failed to unblock DA peer after abort");
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum DispersalUnderBlockingError {
+    #[error("no DA peers were blocked during the run; extend the run or shorten the interval")]
+    NoBlockedWindow,
+    #[error(
+        "no DA blob landed while peers were blocked ({windows} window(s), {total} blob(s) overall); dispersal did not route around blocked peers"
+    )]
+    NoDispersal { windows: usize, total: u64 },
+}
+
+/// Checks that DA blobs keep landing while [`DaPeerBlockingWorkload`] has
+/// peers blocked. Requires a DA workload dispersing blobs during the run.
+struct DaDispersalUnderBlockingExpectation {
+    windows: BlockedWindows,
+    landings: Arc<Mutex<Vec<Instant>>>,
+    capturing: bool,
+}
+
+impl DaDispersalUnderBlockingExpectation {
+    fn new(windows: BlockedWindows) -> Self {
+        Self {
+            windows,
+            landings: Arc::new(Mutex::new(Vec::new())),
+            capturing: false,
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for DaDispersalUnderBlockingExpectation {
+    fn name(&self) -> &'static str {
+        "da_dispersal_under_peer_blocking"
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.capturing {
+            return Ok(());
+        }
+        self.capturing = true;
+
+        let mut receiver = ctx.block_feed().subscribe();
+        let landings = Arc::clone(&self.landings);
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => {
+                        let blobs = record
+                            .block
+                            .transactions()
+                            .flat_map(|tx| tx.mantle_tx().ops.iter())
+                            .filter(|op| matches!(op, Op::ChannelBlob(_)))
+                            .count();
+                        if blobs > 0 {
+                            let now = Instant::now();
+                            landings
+                                .lock()
+                                .expect("blob landings lock poisoned")
+                                .extend(std::iter::repeat_n(now, blobs));
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "DA peer blocking capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let windows = self
+            .windows
+            .lock()
+            .expect("blocked windows lock poisoned")
+            .clone();
+        if windows.is_empty() {
+            return Err(DispersalUnderBlockingError::NoBlockedWindow.into());
+        }
+
+        let landings = self
+            .landings
+            .lock()
+            .expect("blob landings lock poisoned")
+            .clone();
+        let now = Instant::now();
+        let during = landings
+            .iter()
+            .filter(|landed| {
+                windows
+                    .iter()
+                    .any(|window| window.start <= **landed && **landed <= window.end.unwrap_or(now))
+            })
+            .count() as u64;
+
+        ctx.report()
+            .set_counter(WORKLOAD_NAME, "blobs_while_blocked", during);
+        if during == 0 {
+            return Err(DispersalUnderBlockingError::NoDispersal {
+                windows: windows.len(),
+                total: landings.len() as u64,
+            }
+            .into());
+        }
+
+        info!(
+            windows = windows.len(),
+            blobs = during,
+            "DA dispersal continued while peers were blocked"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn unblock_attempts_every_peer() {
+        // Nothing listens on port 1, so every unblock fails.
+        let client = ApiClient::new(SocketAddr::from(([127, 0, 0, 1], 1)), None);
+        let windows = BlockedWindows::default();
+        windows
+            .lock()
+            .expect("blocked windows lock poisoned")
+            .push(BlockedWindow {
+                start: Instant::now(),
+                end: None,
+            });
+        let guard = UnblockGuard::new(
+            vec![
+                (client.clone(), "peer-a".to_owned()),
+                (client, "peer-b".to_owned()),
+            ],
+            Arc::clone(&windows),
+        );
+
+        let err = guard.unblock().await.expect_err("unreachable nodes");
+
+        let message = err.to_string();
+        assert!(message.contains("peer-a"), "{message}");
+        assert!(message.contains("peer-b"), "{message}");
+        let windows = windows.lock().expect("blocked windows lock poisoned");
+        assert!(windows[0].end.is_some());
+    }
+}
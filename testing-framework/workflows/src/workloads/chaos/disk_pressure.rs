@@ -0,0 +1,477 @@
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use rand::seq::SliceRandom as _;
+use testing_framework_core::{
+    constants::{NODE_BLOBS_DIR, NODE_STATE_CONTAINER_DIR},
+    scenario::{
+        DynError, Expectation, NodeControlHandle, NodeEventKind, NodeTarget, RunContext,
+        RunMetrics, Workload,
+    },
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+use super::downtime::wait_for_catch_up;
+
+const WORKLOAD_NAME: &str = "chaos_disk_pressure";
+/// File allocated next to the blobs to take up space.
+const FILL_FILE: &str = ".chaos-disk-pressure";
+const LIVENESS_ATTEMPTS: usize = 3;
+const LIVENESS_RETRY_DELAY: Duration = Duration::from_secs(2);
+const MAX_REPORTED_FAILURES: usize = 5;
+
+#[derive(Debug, Default)]
+struct PressureState {
+    cycles: AtomicU64,
+    failures: Mutex<Vec<String>>,
+}
+
+impl PressureState {
+    fn fail(&self, failure: String) {
+        warn!(failure, "disk pressure check failed");
+        self.failures
+            .lock()
+            .expect("disk pressure failures lock poisoned")
+            .push(failure);
+    }
+}
+
+/// Fills the filesystem holding a node's blob storage, holds the pressure and
+/// releases it again.
+///
+/// Each cycle waits `interval`, picks a random eligible node and allocates a
+/// file (`fallocate`) in its blob directory until the filesystem is
+/// `fill_percent` full, keeps it for `duration`, then deletes it. The paired
+/// expectation fails the run unless every node still answered its API at the
+/// end of the pressure window and reached its peers' height within
+/// `recovery_timeout` once space was released.
+///
+/// Directories bind-mounted from the host are refused, since filling them
+/// fills the host disk. Compose nodes keep their state in such a mount, so
+/// point [`Self::with_path`] at a size-capped tmpfs or volume there.
+#[derive(Debug)]
+pub struct DiskPressureWorkload {
+    fill_percent: u8,
+    duration: Duration,
+    interval: Duration,
+    recovery_timeout: Duration,
+    path: String,
+    include_validators: bool,
+    include_executors: bool,
+    state: Arc<PressureState>,
+}
+
+impl DiskPressureWorkload {
+    /// Creates a workload filling a node's blob filesystem to `fill_percent`
+    /// for `duration` every `interval`.
+    #[must_use]
+    pub fn new(
+        fill_percent: u8,
+        duration: Duration,
+        interval: Duration,
+        recovery_timeout: Duration,
+        include_validators: bool,
+        include_executors: bool,
+    ) -> Self {
+        Self {
+            fill_percent,
+            duration,
+            interval,
+            recovery_timeout,
+            path: format!("{NODE_STATE_CONTAINER_DIR}/{NODE_BLOBS_DIR}"),
+            include_validators,
+            include_executors,
+            state: Arc::new(PressureState::default()),
+        }
+    }
+
+    #[must_use]
+    /// Directory inside the node container to fill, defaulting to the blob
+    /// storage directory. It must not be bind-mounted from the host.
+    pub fn with_path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    fn validate(&self) -> Result<(), DynError> {
+        if !(1..=100).contains(&self.fill_percent) {
+            return Err(format!(
+                "chaos disk pressure fill percent must be within 1..=100, got {}",
+                self.fill_percent
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn targets(&self, ctx: &RunContext) -> Vec<NodeTarget> {
+        let mut targets = Vec::new();
+        if self.include_validators {
+            targets.extend((0..ctx.descriptors().validators().len()).map(NodeTarget::Validator));
+        }
+        if self.include_executors {
+            targets.extend((0..ctx.descriptors().executors().len()).map(NodeTarget::Executor));
+        }
+        targets
+    }
+
+    /// Checks the node still serves its API while under pressure.
+    async fn check_liveness(&self, ctx: &RunContext, target: NodeTarget) {
+        let Some(client) = ctx.node_clients().client(target) else {
+            self.state.fail(format!("no api client for {target}"));
+            return;
+        };
+        let mut last_error = String::new();
+        for attempt in 1..=LIVENESS_ATTEMPTS {
+            match client.consensus_info().await {
+                Ok(_) => return,
+                Err(err) => last_error = err.to_string(),
+            }
+            if attempt < LIVENESS_ATTEMPTS {
+                sleep(LIVENESS_RETRY_DELAY).await;
+            }
+        }
+        self.state.fail(format!(
+            "{target} stopped answering under disk pressure: {last_error}"
+        ));
+    }
+}
+
+#[async_trait]
+impl Workload for DiskPressureWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(DiskPressureRecoveryExpectation {
+            state: Arc::clone(&self.state),
+        })]
+    }
+
+    fn init(
+        &mut self,
+        _descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        self.validate()
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos disk pressure workload requires node control".to_owned())?;
+
+        let targets = self.targets(ctx);
+        if targets.is_empty() {
+            return Err("chaos disk pressure workload has no eligible targets".into());
+        }
+
+        info!(
+            config = ?self,
+            target_count = targets.len(),
+            "starting chaos disk pressure workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        loop {
+            sleep(self.interval).await;
+
+            let target = *targets.choose(&mut rng).expect("targets checked non-empty");
+            let guard = PressureGuard::new(Arc::clone(&handle), target, self.path.clone());
+            let filled_kib = guard.fill(self.fill_percent).await?;
+            ctx.report()
+                .record_node_event(target, NodeEventKind::DiskFill);
+            ctx.report()
+                .increment(self.name(), "bytes_filled", filled_kib * 1024);
+            info!(
+                %target,
+                filled_kib,
+                fill_percent = self.fill_percent,
+                duration = ?self.duration,
+                "chaos filled node disk"
+            );
+
+            sleep(self.duration).await;
+            self.check_liveness(ctx, target).await;
+
+            guard.release().await?;
+            ctx.report()
+                .record_node_event(target, NodeEventKind::DiskRelease);
+            info!(%target, "chaos released node disk");
+
+            if let Err(err) = wait_for_catch_up(ctx, target, self.recovery_timeout).await {
+                self.state.fail(format!(
+                    "{target} did not recover from disk pressure: {err}"
+                ));
+            }
+            self.state.cycles.fetch_add(1, Ordering::Relaxed);
+            ctx.report().increment(self.name(), "cycles", 1);
+        }
+    }
+}
+
+/// Deletes the fill file if the workload stops before releasing it.
+struct PressureGuard {
+    handle: Arc<dyn NodeControlHandle>,
+    target: NodeTarget,
+    path: String,
+    released: bool,
+}
+
+impl PressureGuard {
+    const fn new(handle: Arc<dyn NodeControlHandle>, target: NodeTarget, path: String) -> Self {
+        Self {
+            handle,
+            target,
+            path,
+            released: false,
+        }
+    }
+
+    /// Allocate space until the filesystem is `percent` full, returning the
+    /// KiB allocated (zero when it already was).
+    async fn fill(&self, percent: u8) -> Result<u64, DynError> {
+        self.ensure_not_host_mount().await?;
+        let script = fill_script(percent);
+        let output = self
+            .handle
+            .exec(self.target, &["sh", "-c", &script, "sh", &self.path])
+            .await?;
+        if !output.success() {
+            return Err(format!(
+                "filling disk on {} failed ({:?}): {}",
+                self.target,
+                output.exit_code,
+                output.stderr.trim()
+            )
+            .into());
+        }
+        let filled =
+            output.stdout.trim().parse::<i64>().map_err(|err| {
+                format!("unexpected disk fill output from {}: {err}", self.target)
+            })?;
+        Ok(filled.max(0).unsigned_abs())
+    }
+
+    /// Refuse to fill a directory bind-mounted from the host.
+    async fn ensure_not_host_mount(&self) -> Result<(), DynError> {
+        let output = self
+            .handle
+            .exec(self.target, &["sh", "-c", MOUNT_SCRIPT, "sh", &self.path])
+            .await?;
+        if !output.success() {
+            return Err(format!(
+                "inspecting the mount of {} on {} failed ({:?}): {}",
+                self.path,
+                self.target,
+                output.exit_code,
+                output.stderr.trim()
+            )
+            .into());
+        }
+        let (mount_point, mountinfo) = output.stdout.split_once('\n').unwrap_or_default();
+        match mount_root(mountinfo, mount_point.trim()) {
+            Some("/") => Ok(()),
+            Some(root) => Err(format!(
+                "{} on {} is bind-mounted from {root} on the host; filling it would fill the \
+                 host disk, use a size-capped tmpfs or volume instead",
+                self.path, self.target
+            )
+            .into()),
+            None => Err(format!(
+                "no mount found for {} ({}) on {}",
+                self.path, mount_point, self.target
+            )
+            .into()),
+        }
+    }
+
+    async fn release(mut self) -> Result<(), DynError> {
+        self.released = true;
+        release(self.handle.as_ref(), self.target, &self.path).await
+    }
+}
+
+impl Drop for PressureGuard {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        let handle = Arc::clone(&self.handle);
+        let target = self.target;
+        let path = std::mem::take(&mut self.path);
+        tokio::spawn(async move {
+            if let Err(err) = release(handle.as_ref(), target, &path).await {
+                warn!(%target, error = %err, "failed to release disk pressure after abort");
+            }
+        });
+    }
+}
+
+async fn release(
+    handle: &dyn NodeControlHandle,
+    target: NodeTarget,
+    path: &str,
+) -> Result<(), DynError> {
+    let file = format!("{path}/{FILL_FILE}");
+    let output = handle.exec(target, &["rm", "-f", &file]).await?;
+    if output.success() {
+        return Ok(());
+    }
+    Err(format!(
+        "releasing disk on {target} failed ({:?}): {}",
+        output.exit_code,
+        output.stderr.trim()
+    )
+    .into())
+}
+
+/// Prints the mount point of the directory passed as `$1`, then the mount
+/// table.
+const MOUNT_SCRIPT: &str = "mkdir -p \"$1\" && df -P \"$1\" | tail -n 1 | awk '{print $NF}' && \
+                            cat /proc/self/mountinfo";
+
+/// Root within its source filesystem of the mount at `mount_point`, `/`
+/// unless it is a bind mount of a subdirectory.
+fn mount_root<'a>(mountinfo: &'a str, mount_point: &str) -> Option<&'a str> {
+    mountinfo
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace().skip(3);
+            let root = fields.next()?;
+            (fields.next()? == mount_point).then_some(root)
+        })
+        .last()
+}
+
+/// Shell snippet allocating the space missing for the filesystem of the
+/// directory passed as `$1` to be `percent` full and printing the KiB it
+/// allocated (negative when the filesystem was already fuller).
+fn fill_script(percent: u8) -> String {
+    format!(
+        "dir=\"$1\" && mkdir -p \"$dir\" && set -- $(df -Pk \"$dir\" | tail -n 1) && \
+         fill=$(( $2 * {percent} / 100 - $3 )) && \
+         if [ \"$fill\" -gt 0 ]; then fallocate -l \"${{fill}}K\" \"$dir/{FILL_FILE}\"; fi && \
+         echo \"$fill\""
+    )
+}
+
+#[derive(Debug, Error)]
+enum DiskPressureError {
+    #[error("no disk pressure cycle completed; extend the run or shorten the interval")]
+    NoCycles,
+    #[error("nodes did not cope with disk pressure {count} time(s):\n{details}")]
+    Failures { count: usize, details: String },
+}
+
+/// Fails the run when a node stopped answering under
+/// [`DiskPressureWorkload`] or did not recover once space was released.
+#[derive(Debug)]
+struct DiskPressureRecoveryExpectation {
+    state: Arc<PressureState>,
+}
+
+#[async_trait]
+impl Expectation for DiskPressureRecoveryExpectation {
+    fn name(&self) -> &'static str {
+        "chaos_disk_pressure_recovery"
+    }
+
+    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+        let failures = self
+            .state
+            .failures
+            .lock()
+            .expect("disk pressure failures lock poisoned")
+            .clone();
+        if !failures.is_empty() {
+            let mut details = failures
+                .iter()
+                .take(MAX_REPORTED_FAILURES)
+                .map(|failure| format!("- {failure}"))
+                .collect::<Vec<_>>();
+            if failures.len() > MAX_REPORTED_FAILURES {
+                details.push(format!(
+                    "- ... and {} more",
+                    failures.len() - MAX_REPORTED_FAILURES
+                ));
+            }
+            return Err(DiskPressureError::Failures {
+                count: failures.len(),
+                details: details.join("\n"),
+            }
+            .into());
+        }
+
+        let cycles = self.state.cycles.load(Ordering::Relaxed);
+        if cycles == 0 {
+            return Err(DiskPressureError::NoCycles.into());
+        }
+        info!(cycles, "nodes recovered from every disk pressure cycle");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTINFO: &str = "\
+22 1 0:21 / / rw,relatime - overlay overlay rw
+23 22 0:22 / /proc rw - proc proc rw
+24 22 8:1 /home/ci/run/storage/validator-0 /state rw,relatime - ext4 /dev/sda1 rw
+25 24 0:30 / /state/blobs rw - tmpfs tmpfs rw,size=524288k";
+
+    #[test]
+    fn bind_mounts_have_a_subdirectory_root() {
+        assert_eq!(
+            mount_root(MOUNTINFO, "/state"),
+            Some("/home/ci/run/storage/validator-0")
+        );
+        assert_eq!(mount_root(MOUNTINFO, "/state/blobs"), Some("/"));
+        assert_eq!(mount_root(MOUNTINFO, "/"), Some("/"));
+        assert_eq!(mount_root(MOUNTINFO, "/missing"), None);
+    }
+
+    #[test]
+    fn fill_percent_is_checked_before_deployment() {
+        let workload = |fill_percent| {
+            DiskPressureWorkload::new(
+                fill_percent,
+                Duration::from_secs(10),
+                Duration::from_secs(30),
+                Duration::from_secs(60),
+                true,
+                false,
+            )
+        };
+
+        assert!(workload(0).validate().is_err());
+        assert!(workload(101).validate().is_err());
+        assert!(workload(1).validate().is_ok());
+        assert!(workload(100).validate().is_ok());
+    }
+
+    #[test]
+    fn fill_script_takes_the_path_as_argument() {
+        let script = fill_script(90);
+
+        assert!(script.starts_with("dir=\"$1\""), "{script}");
+        assert!(script.contains("* 90 / 100"), "{script}");
+        assert!(script.contains(FILL_FILE), "{script}");
+    }
+}
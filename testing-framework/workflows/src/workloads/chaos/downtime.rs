@@ -0,0 +1,202 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::seq::SliceRandom as _;
+use testing_framework_core::{
+    nodes::{ApiClient, wait_for_height},
+    scenario::{DynError, NodeControlHandle, NodeEventKind, NodeTarget, RunContext, Workload},
+};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// Takes one node at a time offline for an extended period, then starts it
+/// again and verifies it catches up with the rest of the network.
+///
+/// Each cycle waits `interval`, stops a random eligible node for `downtime`,
+/// starts it and fails the workload unless the node reaches the height the
+/// remaining nodes report within `catch_up_timeout`.
+#[derive(Debug)]
+pub struct NodeDowntimeWorkload {
+    downtime: Duration,
+    interval: Duration,
+    catch_up_timeout: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl NodeDowntimeWorkload {
+    /// Creates a downtime workload stopping nodes for `downtime` every
+    /// `interval`.
+    #[must_use]
+    pub const fn new(
+        downtime: Duration,
+        interval: Duration,
+        catch_up_timeout: Duration,
+        include_validators: bool,
+        include_executors: bool,
+    ) -> Self {
+        Self {
+            downtime,
+            interval,
+            catch_up_timeout,
+            include_validators,
+            include_executors,
+        }
+    }
+
+    fn targets(&self, ctx: &RunContext) -> Vec<NodeTarget> {
+        let mut targets = Vec::new();
+        let validator_count = ctx.descriptors().validators().len();
+        if self.include_validators {
+            if validator_count > 1 {
+                targets.extend((0..validator_count).map(NodeTarget::Validator));
+            } else if validator_count == 1 {
+                info!("chaos downtime skipping validators: only one validator configured");
+            }
+        }
+        if self.include_executors {
+            targets.extend((0..ctx.descriptors().executors().len()).map(NodeTarget::Executor));
+        }
+        targets
+    }
+}
+
+/// Waits until `target` reaches the height its peers report, failing after
+/// `timeout`.
+pub(super) async fn wait_for_catch_up(
+    ctx: &RunContext,
+    target: NodeTarget,
+    timeout: Duration,
+) -> Result<(), DynError> {
+    let clients = ctx.node_clients();
+    let client = clients
+        .client(target)
+        .ok_or_else(|| format!("no api client for {target}"))?;
+    let peers = clients
+        .targeted_clients()
+        .filter(|(candidate, _)| *candidate != target)
+        .map(|(_, client)| client);
+
+    let reference = max_height(peers).await;
+    let info = wait_for_height(client, reference, timeout)
+        .await
+        .map_err(|err| format!("{target} did not catch up: {err}"))?;
+    info!(%target, height = info.height, reference, "node caught up");
+    Ok(())
+}
+
+/// Highest height reported by any reachable client.
+pub(super) async fn max_height<'a>(clients: impl Iterator<Item = &'a ApiClient>) -> u64 {
+    let mut height = 0;
+    for client in clients {
+        if let Ok(info) = client.consensus_info().await {
+            height = height.max(info.height);
+        }
+    }
+    height
+}
+
+#[async_trait]
+impl Workload for NodeDowntimeWorkload {
+    fn name(&self) -> &'static str {
+        "chaos_downtime"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos downtime workload requires node control".to_owned())?;
+
+        let targets = self.targets(ctx);
+        if targets.is_empty() {
+            return Err("chaos downtime workload has no eligible targets".into());
+        }
+
+        info!(
+            config = ?self,
+            target_count = targets.len(),
+            "starting chaos downtime workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        loop {
+            sleep(self.interval).await;
+
+            let target = *targets.choose(&mut rng).expect("targets checked non-empty");
+            info!(%target, downtime = ?self.downtime, "chaos stopping node");
+            let guard = DowntimeGuard::new(Arc::clone(&handle), target);
+            stop_node(handle.as_ref(), target)
+                .await
+                .map_err(|err| format!("stopping {target} failed: {err}"))?;
+            ctx.report().record_node_event(target, NodeEventKind::Stop);
+
+            sleep(self.downtime).await;
+
+            info!(%target, "chaos starting node");
+            guard.start().await?;
+            ctx.report().record_node_event(target, NodeEventKind::Start);
+
+            wait_for_catch_up(ctx, target, self.catch_up_timeout).await?;
+        }
+    }
+}
+
+pub(super) async fn stop_node(
+    handle: &dyn NodeControlHandle,
+    target: NodeTarget,
+) -> Result<(), DynError> {
+    match target {
+        NodeTarget::Validator(index) => handle.stop_validator(index).await,
+        NodeTarget::Executor(index) => handle.stop_executor(index).await,
+    }
+}
+
+pub(super) async fn start_node(
+    handle: &dyn NodeControlHandle,
+    target: NodeTarget,
+) -> Result<(), DynError> {
+    match target {
+        NodeTarget::Validator(index) => handle.start_validator(index).await,
+        NodeTarget::Executor(index) => handle.start_executor(index).await,
+    }
+}
+
+/// Starts a stopped node when the workload is aborted during its downtime so
+/// expectations see the full cluster.
+pub(super) struct DowntimeGuard {
+    handle: Option<Arc<dyn NodeControlHandle>>,
+    target: NodeTarget,
+}
+
+impl DowntimeGuard {
+    pub(super) fn new(handle: Arc<dyn NodeControlHandle>, target: NodeTarget) -> Self {
+        Self {
+            handle: Some(handle),
+            target,
+        }
+    }
+
+    pub(super) async fn start(mut self) -> Result<(), DynError> {
+        let handle = self.handle.take().expect("downtime guard holds a handle");
+        start_node(handle.as_ref(), self.target)
+            .await
+            .map_err(|err| format!("starting {} failed: {err}", self.target).into())
+    }
+}
+
+impl Drop for DowntimeGuard {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let target = self.target;
+            tokio::spawn(async move {
+                if let Err(err) = start_node(handle.as_ref(), target).await {
+                    warn!(%target, error = %err, "failed to start node after abort");
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,524 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use nomos_core::header::HeaderId;
+use rand::seq::SliceRandom as _;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{
+        DynError, Expectation, NodeControlHandle, NodeEventKind, NodeTarget, RunContext,
+        RunMetrics, Workload,
+    },
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::time::{Instant, sleep};
+use tracing::{debug, info, warn};
+
+use crate::expectations::violations;
+
+const WORKLOAD_NAME: &str = "chaos_fork";
+const EXPECTATION_NAME: &str = "fork_convergence";
+const CONVERGENCE_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Convergence wait when the topology gives no block interval.
+const FALLBACK_CONVERGENCE_TIMEOUT: Duration = Duration::from_secs(300);
+/// Expected block intervals per window block granted before giving up on a
+/// validator whose reference stopped growing.
+const CONVERGENCE_TIMEOUT_SLACK: u32 = 4;
+
+/// How [`ForkInjectionWorkload`] cuts a validator off from the cluster.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ForkIsolation {
+    /// Partition the validator away. It keeps running and builds its own
+    /// chain whenever it wins a slot.
+    #[default]
+    Partition,
+    /// Freeze the validator (`docker pause`). It builds nothing while
+    /// paused, but wakes up with a stale tip and stale slot view.
+    Pause,
+}
+
+/// One isolation of the workload, shared with its expectation.
+#[derive(Clone, Debug)]
+struct ForkRecord {
+    target: NodeTarget,
+    reference: NodeTarget,
+    /// The reference node's tip and height when the validator rejoined.
+    heal_tip: HeaderId,
+    heal_height: u64,
+    /// Whether the validator's tip was off the reference chain at that point.
+    diverged: bool,
+    /// Reference height at which the validator adopted `heal_tip`.
+    converged_at: Option<u64>,
+}
+
+type SharedForkRecords = Arc<Mutex<Vec<ForkRecord>>>;
+
+/// Isolates a validator long enough to build a divergent chain, reconnects
+/// it and checks that fork choice brings it back onto the cluster's chain.
+///
+/// Each cycle waits `interval`, isolates one validator for
+/// `isolation_duration`, then reconnects it. The validator has converged once
+/// its chain contains the tip the rest of the cluster had at reconnection.
+/// The paired expectation fails the run unless that happened before the
+/// cluster grew `convergence_blocks` past that tip.
+#[derive(Debug)]
+pub struct ForkInjectionWorkload {
+    isolation: ForkIsolation,
+    isolation_duration: Duration,
+    interval: Duration,
+    convergence_blocks: Option<u64>,
+    validator: Option<usize>,
+    records: SharedForkRecords,
+}
+
+impl ForkInjectionWorkload {
+    /// Creates a fork injection workload. `validator` pins the isolated
+    /// validator; `None` picks a random one every cycle.
+    #[must_use]
+    pub fn new(
+        isolation: ForkIsolation,
+        isolation_duration: Duration,
+        interval: Duration,
+        convergence_blocks: Option<u64>,
+        validator: Option<usize>,
+    ) -> Self {
+        Self {
+            isolation,
+            isolation_duration,
+            interval,
+            convergence_blocks,
+            validator,
+            records: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    async fn isolate(
+        &self,
+        handle: &dyn NodeControlHandle,
+        target: NodeTarget,
+        others: &[NodeTarget],
+    ) -> Result<NodeEventKind, DynError> {
+        match self.isolation {
+            ForkIsolation::Partition => {
+                handle
+                    .partition(&[vec![target], others.to_vec()])
+                    .await
+                    .map_err(|err| format!("isolating {target} failed: {err}"))?;
+                Ok(NodeEventKind::Partition)
+            }
+            ForkIsolation::Pause => {
+                handle
+                    .pause_node(target)
+                    .await
+                    .map_err(|err| format!("pausing {target} failed: {err}"))?;
+                Ok(NodeEventKind::Pause)
+            }
+        }
+    }
+
+    /// Polls until `target` adopts the reference chain, the reference grew
+    /// past the convergence window or `timeout` elapsed.
+    async fn wait_for_convergence(
+        &self,
+        client: &ApiClient,
+        reference: &ApiClient,
+        record: &ForkRecord,
+        timeout: Duration,
+    ) -> Option<u64> {
+        let window = self.convergence_blocks.unwrap_or(0);
+        let deadline = Instant::now() + timeout;
+        loop {
+            let height = match reference.consensus_info().await {
+                Ok(info) => info.height,
+                Err(err) => {
+                    debug!(error = %err, "fork reference not answering");
+                    record.heal_height
+                }
+            };
+            if chain_contains(client, record.heal_tip, record.heal_height).await {
+                return Some(height);
+            }
+            if height > record.heal_height + window || Instant::now() >= deadline {
+                return None;
+            }
+            sleep(CONVERGENCE_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// How long to wait for a validator to converge: [`CONVERGENCE_TIMEOUT_SLACK`]
+/// block intervals per window block, at least one block's worth.
+fn convergence_timeout(window: u64, block_interval: Option<Duration>) -> Duration {
+    block_interval.map_or(FALLBACK_CONVERGENCE_TIMEOUT, |interval| {
+        let blocks = u32::try_from(window.max(1)).unwrap_or(u32::MAX);
+        interval
+            .saturating_mul(blocks)
+            .saturating_mul(CONVERGENCE_TIMEOUT_SLACK)
+    })
+}
+
+/// Whether `header`, at `height`, is on the chain `client` currently
+/// follows. Headers older than the node's LIB are looked up down to `header`.
+async fn chain_contains(client: &ApiClient, header: HeaderId, height: u64) -> bool {
+    let Ok(info) = client.consensus_info().await else {
+        return false;
+    };
+    if info.tip == header {
+        return true;
+    }
+    let Ok(headers) = client.consensus_headers(Some(info.tip), None).await else {
+        return false;
+    };
+    if let Some(found) = header_in_window(&headers, info.height, header, height) {
+        return found;
+    }
+    client
+        .consensus_headers(Some(info.tip), Some(header))
+        .await
+        .is_ok_and(|headers| headers.contains(&header))
+}
+
+/// Whether `header`, at `height`, is among `headers`, the chain from a tip at
+/// `tip_height` down to LIB. `None` when `height` lies below that window.
+fn header_in_window(
+    headers: &[HeaderId],
+    tip_height: u64,
+    header: HeaderId,
+    height: u64,
+) -> Option<bool> {
+    if headers.contains(&header) {
+        return Some(true);
+    }
+    let lib_height = tip_height.saturating_sub(headers.len().saturating_sub(1) as u64);
+    (height >= lib_height).then_some(false)
+}
+
+#[async_trait]
+impl Workload for ForkInjectionWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos", "consensus"]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(ForkConvergenceExpectation {
+            records: Arc::clone(&self.records),
+            convergence_blocks: self.convergence_blocks,
+        })]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        let validators = descriptors.validators().len();
+        if validators < 2 {
+            return Err("fork injection needs at least two validators".into());
+        }
+        if let Some(index) = self.validator.filter(|index| *index >= validators) {
+            return Err(format!(
+                "fork injection targets validator-{index}, topology has {validators}"
+            )
+            .into());
+        }
+        if self.convergence_blocks.is_none() {
+            self.convergence_blocks = Some(u64::from(
+                descriptors.config().consensus_params.security_param.get(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "fork injection workload requires node control".to_owned())?;
+        let validators = (0..ctx.descriptors().validators().len())
+            .map(NodeTarget::Validator)
+            .collect::<Vec<_>>();
+
+        let timeout = convergence_timeout(
+            self.convergence_blocks.unwrap_or(0),
+            ctx.run_metrics().block_interval_hint(),
+        );
+        info!(config = ?self, ?timeout, "starting fork injection workload");
+
+        let mut rng = ctx.rng(self.name());
+        loop {
+            sleep(self.interval).await;
+
+            let target = self.validator.map_or_else(
+                || *validators.choose(&mut rng).expect("validators checked"),
+                NodeTarget::Validator,
+            );
+            let others = ctx
+                .node_clients()
+                .targeted_clients()
+                .map(|(node, _)| node)
+                .filter(|node| *node != target)
+                .collect::<Vec<_>>();
+            let reference = *validators
+                .iter()
+                .find(|node| **node != target)
+                .expect("validators checked");
+            let client = ctx
+                .node_clients()
+                .client(target)
+                .ok_or_else(|| format!("no api client for {target}"))?
+                .clone();
+            let reference_client = ctx
+                .node_clients()
+                .client(reference)
+                .ok_or_else(|| format!("no api client for {reference}"))?
+                .clone();
+
+            info!(%target, isolation = ?self.isolation, "isolating validator to force a fork");
+            let guard = IsolationGuard::new(Arc::clone(&handle), self.isolation, target);
+            let event = self.isolate(handle.as_ref(), target, &others).await?;
+            ctx.report().record_node_event(target, event);
+
+            sleep(self.isolation_duration).await;
+
+            let event = guard.reconnect().await?;
+            ctx.report().record_node_event(target, event);
+
+            let heal = reference_client
+                .consensus_info()
+                .await
+                .map_err(|err| format!("{reference} consensus info request failed: {err}"))?;
+            let diverged = match client.consensus_info().await {
+                Ok(info) => !chain_contains(&reference_client, info.tip, info.height).await,
+                Err(_) => false,
+            };
+            info!(%target, %reference, height = heal.height, diverged, "validator reconnected");
+
+            let mut record = ForkRecord {
+                target,
+                reference,
+                heal_tip: heal.tip,
+                heal_height: heal.height,
+                diverged,
+                converged_at: None,
+            };
+            let index = {
+                let mut records = self.records.lock().expect("fork records lock poisoned");
+                records.push(record.clone());
+                records.len() - 1
+            };
+
+            record.converged_at = self
+                .wait_for_convergence(&client, &reference_client, &record, timeout)
+                .await;
+            match record.converged_at {
+                Some(height) => info!(%target, height, "validator converged after fork"),
+                None => warn!(%target, "validator did not converge within the window"),
+            }
+            self.records.lock().expect("fork records lock poisoned")[index] = record;
+        }
+    }
+}
+
+/// Reconnects an isolated validator when the workload is aborted mid-cycle.
+struct IsolationGuard {
+    handle: Option<Arc<dyn NodeControlHandle>>,
+    isolation: ForkIsolation,
+    target: NodeTarget,
+}
+
+impl IsolationGuard {
+    fn new(
+        handle: Arc<dyn NodeControlHandle>,
+        isolation: ForkIsolation,
+        target: NodeTarget,
+    ) -> Self {
+        Self {
+            handle: Some(handle),
+            isolation,
+            target,
+        }
+    }
+
+    async fn reconnect(mut self) -> Result<NodeEventKind, DynError> {
+        let handle = self.handle.take().expect("isolation guard holds a handle");
+        reconnect(handle.as_ref(), self.isolation, self.target)
+            .await
+            .map_err(|err| format!("reconnecting {} failed: {err}", self.target).into())
+    }
+}
+
+impl Drop for IsolationGuard {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            let (isolation, target) = (self.isolation, self.target);
+            tokio::spawn(async move {
+                if let Err(err) = reconnect(handle.as_ref(), isolation, target).await {
+                    warn!(%target, error = %err, "failed to reconnect validator after abort");
+                }
+            });
+        }
+    }
+}
+
+async fn reconnect(
+    handle: &dyn NodeControlHandle,
+    isolation: ForkIsolation,
+    target: NodeTarget,
+) -> Result<NodeEventKind, DynError> {
+    match isolation {
+        ForkIsolation::Partition => handle
+            .heal_partition()
+            .await
+            .map(|()| NodeEventKind::HealPartition),
+        ForkIsolation::Pause => handle
+            .unpause_node(target)
+            .await
+            .map(|()| NodeEventKind::Unpause),
+    }
+}
+
+/// Checks that every validator isolated by [`ForkInjectionWorkload`] came
+/// back onto the cluster's chain within the convergence window.
+struct ForkConvergenceExpectation {
+    records: SharedForkRecords,
+    convergence_blocks: Option<u64>,
+}
+
+#[derive(Debug, Error)]
+enum ForkConvergenceError {
+    #[error("no fork was injected before the end of the run")]
+    NoForks,
+    #[error("isolated validators did not converge:\n{0}")]
+    Violations(String),
+}
+
+#[async_trait]
+impl Expectation for ForkConvergenceExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.convergence_blocks.is_none() {
+            self.convergence_blocks = Some(u64::from(
+                descriptors.config().consensus_params.security_param.get(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let records = self
+            .records
+            .lock()
+            .expect("fork records lock poisoned")
+            .clone();
+        if records.is_empty() {
+            return Err(ForkConvergenceError::NoForks.into());
+        }
+
+        let window = self.convergence_blocks.unwrap_or(0);
+        let mut issues = Vec::new();
+        for record in &records {
+            let target = record.target;
+            let converged_at = match record.converged_at {
+                Some(height) => Some(height),
+                // The run ended while the workload was still waiting; check
+                // where the validator stands now.
+                None => match (
+                    ctx.node_clients().client(target),
+                    ctx.node_clients().client(record.reference),
+                ) {
+                    (Some(client), Some(reference))
+                        if chain_contains(client, record.heal_tip, record.heal_height).await =>
+                    {
+                        reference
+                            .consensus_info()
+                            .await
+                            .ok()
+                            .map(|info| info.height)
+                    }
+                    _ => None,
+                },
+            };
+
+            let blocks = converged_at.map(|height| height.saturating_sub(record.heal_height));
+            if record.diverged {
+                ctx.report().increment(EXPECTATION_NAME, "diverged", 1);
+            }
+            match blocks {
+                Some(blocks) if blocks <= window => {
+                    ctx.report().set_counter(
+                        EXPECTATION_NAME,
+                        &format!("{target}_convergence_blocks"),
+                        blocks,
+                    );
+                }
+                Some(blocks) => issues.push(format!(
+                    "{target} converged {blocks} blocks after rejoining at height {} (allowed {window})",
+                    record.heal_height
+                )),
+                None => issues.push(format!(
+                    "{target} never adopted {}'s chain after rejoining at height {} (diverged: {})",
+                    record.reference, record.heal_height, record.diverged
+                )),
+            }
+        }
+
+        let Some(details) = violations("fork convergence", &issues) else {
+            info!(
+                forks = records.len(),
+                diverged = records.iter().filter(|record| record.diverged).count(),
+                "fork convergence expectation satisfied"
+            );
+            return Ok(());
+        };
+        Err(ForkConvergenceError::Violations(details).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(n: u8) -> HeaderId {
+        HeaderId::from([n; 32])
+    }
+
+    #[test]
+    fn headers_below_lib_need_a_deeper_lookup() {
+        // Tip at height 12, LIB at height 10.
+        let headers = [header(12), header(11), header(10)];
+
+        assert_eq!(header_in_window(&headers, 12, header(11), 11), Some(true));
+        assert_eq!(header_in_window(&headers, 12, header(99), 11), Some(false));
+        assert_eq!(header_in_window(&headers, 12, header(99), 10), Some(false));
+        assert_eq!(header_in_window(&headers, 12, header(9), 9), None);
+    }
+
+    #[test]
+    fn convergence_timeout_scales_with_window() {
+        let block = Duration::from_secs(2);
+
+        assert_eq!(
+            convergence_timeout(10, Some(block)),
+            Duration::from_secs(2 * 10 * u64::from(CONVERGENCE_TIMEOUT_SLACK))
+        );
+        assert_eq!(
+            convergence_timeout(0, Some(block)),
+            block * CONVERGENCE_TIMEOUT_SLACK
+        );
+        assert_eq!(convergence_timeout(10, None), FALLBACK_CONVERGENCE_TIMEOUT);
+    }
+}
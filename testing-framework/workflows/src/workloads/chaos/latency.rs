@@ -0,0 +1,206 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::seq::SliceRandom as _;
+use testing_framework_core::scenario::{
+    DynError, NetworkShaping, NodeControlHandle, NodeEventKind, NodeTarget, RunContext,
+    RunReporter, Workload,
+};
+use tokio::time::sleep;
+use tracing::{debug, info, warn};
+
+/// Degrades node networking with latency, jitter and packet loss.
+///
+/// The workload walks through `schedule`, applying each step to the selected
+/// nodes and holding it for `step_duration`. The last step stays in place
+/// until the run is cancelled, at which point shaping is removed again.
+#[derive(Debug)]
+pub struct NetworkLatencyWorkload {
+    schedule: Vec<NetworkShaping>,
+    step_duration: Duration,
+    node_limit: Option<usize>,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl NetworkLatencyWorkload {
+    /// Creates a latency workload from an explicit shaping schedule.
+    ///
+    /// `node_limit` restricts shaping to a random subset of the eligible
+    /// nodes; `None` shapes every eligible node.
+    #[must_use]
+    pub const fn new(
+        schedule: Vec<NetworkShaping>,
+        step_duration: Duration,
+        node_limit: Option<usize>,
+        include_validators: bool,
+        include_executors: bool,
+    ) -> Self {
+        Self {
+            schedule,
+            step_duration,
+            node_limit,
+            include_validators,
+            include_executors,
+        }
+    }
+
+    /// Builds a linear ramp from no shaping up to `target` in `steps` steps.
+    #[must_use]
+    pub fn linear_ramp(target: NetworkShaping, steps: usize) -> Vec<NetworkShaping> {
+        let steps = steps.max(1);
+        (1..=steps)
+            .map(|step| {
+                let fraction = step as f64 / steps as f64;
+                NetworkShaping {
+                    latency: target.latency.mul_f64(fraction),
+                    jitter: target.jitter.mul_f64(fraction),
+                    loss_percent: target.loss_percent * fraction,
+                }
+            })
+            .collect()
+    }
+
+    fn targets(&self, ctx: &RunContext) -> Vec<NodeTarget> {
+        let mut targets = Vec::new();
+        if self.include_validators {
+            targets.extend((0..ctx.descriptors().validators().len()).map(NodeTarget::Validator));
+        }
+        if self.include_executors {
+            targets.extend((0..ctx.descriptors().executors().len()).map(NodeTarget::Executor));
+        }
+        if let Some(limit) = self.node_limit {
+            targets.shuffle(&mut ctx.rng("chaos_latency"));
+            targets.truncate(limit);
+        }
+        targets
+    }
+
+    /// Walks the schedule, then holds the last step until the run is
+    /// cancelled.
+    async fn apply_schedule(
+        &self,
+        ctx: &RunContext,
+        handle: &dyn NodeControlHandle,
+        targets: &[NodeTarget],
+    ) -> Result<(), DynError> {
+        let cancellation = ctx.cancellation();
+        for (step, shaping) in self.schedule.iter().enumerate() {
+            debug!(step, ?shaping, "chaos applying network shaping step");
+            for target in targets {
+                handle
+                    .shape_network(*target, *shaping)
+                    .await
+                    .map_err(|err| format!("network shaping failed: {err}"))?;
+                ctx.report()
+                    .record_node_event(*target, NodeEventKind::NetworkShaping);
+            }
+            if cancellation
+                .until_cancelled(sleep(self.step_duration))
+                .await
+                .is_none()
+            {
+                return Ok(());
+            }
+        }
+
+        cancellation.cancelled().await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Workload for NetworkLatencyWorkload {
+    fn name(&self) -> &'static str {
+        "chaos_latency"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
+    fn observes_cancellation(&self) -> bool {
+        true
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos latency workload requires node control".to_owned())?;
+
+        if self.schedule.is_empty() {
+            return Err("chaos latency workload has an empty schedule".into());
+        }
+
+        let targets = self.targets(ctx);
+        if targets.is_empty() {
+            return Err("chaos latency workload has no eligible targets".into());
+        }
+
+        info!(
+            config = ?self,
+            ?targets,
+            "starting chaos latency workload"
+        );
+
+        let guard = ShapingGuard::new(Arc::clone(&handle), targets.clone(), ctx.report().clone());
+        let outcome = self.apply_schedule(ctx, handle.as_ref(), &targets).await;
+        info!("chaos clearing network shaping");
+        guard.clear().await;
+        outcome
+    }
+}
+
+/// Clears shaping on all targets when the workload is aborted before it
+/// could do so itself.
+struct ShapingGuard {
+    handle: Arc<dyn NodeControlHandle>,
+    targets: Vec<NodeTarget>,
+    report: RunReporter,
+}
+
+impl ShapingGuard {
+    const fn new(
+        handle: Arc<dyn NodeControlHandle>,
+        targets: Vec<NodeTarget>,
+        report: RunReporter,
+    ) -> Self {
+        Self {
+            handle,
+            targets,
+            report,
+        }
+    }
+
+    async fn clear(mut self) {
+        let targets = std::mem::take(&mut self.targets);
+        clear_shaping(self.handle.as_ref(), targets, &self.report).await;
+    }
+}
+
+impl Drop for ShapingGuard {
+    fn drop(&mut self) {
+        if self.targets.is_empty() {
+            return;
+        }
+        let handle = Arc::clone(&self.handle);
+        let targets = std::mem::take(&mut self.targets);
+        let report = self.report.clone();
+        tokio::spawn(async move {
+            clear_shaping(handle.as_ref(), targets, &report).await;
+        });
+    }
+}
+
+async fn clear_shaping(
+    handle: &dyn NodeControlHandle,
+    targets: Vec<NodeTarget>,
+    report: &RunReporter,
+) {
+    for target in targets {
+        match handle.clear_network_shaping(target).await {
+            Ok(()) => report.record_node_event(target, NodeEventKind::ClearNetworkShaping),
+            Err(err) => warn!(%target, error = %err, "failed to clear network shaping"),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+mod crash;
+mod da_blocking;
+mod disk_pressure;
+mod downtime;
+mod fork;
+mod latency;
+mod partition;
+mod resize;
+mod restart;
+mod timeline;
+
+pub use crash::NodeCrashWorkload;
+pub use da_blocking::DaPeerBlockingWorkload;
+pub use disk_pressure::DiskPressureWorkload;
+pub use downtime::NodeDowntimeWorkload;
+pub use fork::{ForkInjectionWorkload, ForkIsolation};
+pub use latency::NetworkLatencyWorkload;
+pub use partition::NetworkPartitionWorkload;
+pub use resize::{ClusterResizeWorkload, ResizeAction, ResizeStep};
+pub use restart::RandomRestartWorkload;
+pub use timeline::{ChaosTimeline, ChaosTimelineWorkload, FaultTarget};
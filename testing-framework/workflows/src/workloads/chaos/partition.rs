@@ -0,0 +1,175 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::{Rng, seq::SliceRandom as _};
+use testing_framework_core::scenario::{
+    DynError, NodeControlHandle, NodeEventKind, NodeTarget, RunContext, Workload,
+};
+use tokio::time::sleep;
+use tracing::{info, warn};
+
+/// Periodically splits the network into isolated groups and heals it again.
+///
+/// Each cycle waits `heal_duration` with full connectivity, shuffles the
+/// eligible nodes into `group_count` groups, keeps them isolated for
+/// `partition_duration`, then restores connectivity.
+#[derive(Debug)]
+pub struct NetworkPartitionWorkload {
+    group_count: usize,
+    partition_duration: Duration,
+    heal_duration: Duration,
+    include_validators: bool,
+    include_executors: bool,
+}
+
+impl NetworkPartitionWorkload {
+    /// Creates a partition workload splitting nodes into `group_count` groups.
+    #[must_use]
+    pub const fn new(
+        group_count: usize,
+        partition_duration: Duration,
+        heal_duration: Duration,
+        include_validators: bool,
+        include_executors: bool,
+    ) -> Self {
+        Self {
+            group_count,
+            partition_duration,
+            heal_duration,
+            include_validators,
+            include_executors,
+        }
+    }
+
+    fn targets(&self, ctx: &RunContext) -> Vec<NodeTarget> {
+        let mut targets = Vec::new();
+        if self.include_validators {
+            targets.extend((0..ctx.descriptors().validators().len()).map(NodeTarget::Validator));
+        }
+        if self.include_executors {
+            targets.extend((0..ctx.descriptors().executors().len()).map(NodeTarget::Executor));
+        }
+        targets
+    }
+
+    fn random_groups(&self, targets: &[NodeTarget], rng: &mut impl Rng) -> Vec<Vec<NodeTarget>> {
+        let mut shuffled = targets.to_vec();
+        shuffled.shuffle(rng);
+
+        let mut groups = vec![Vec::new(); self.group_count];
+        for (position, target) in shuffled.into_iter().enumerate() {
+            groups[position % self.group_count].push(target);
+        }
+        groups
+    }
+}
+
+#[async_trait]
+impl Workload for NetworkPartitionWorkload {
+    fn name(&self) -> &'static str {
+        "chaos_partition"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
+    fn observes_cancellation(&self) -> bool {
+        true
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos partition workload requires node control".to_owned())?;
+
+        let targets = self.targets(ctx);
+        if targets.len() < self.group_count {
+            return Err(format!(
+                "chaos partition needs at least {} nodes, found {}",
+                self.group_count,
+                targets.len()
+            )
+            .into());
+        }
+
+        info!(
+            config = ?self,
+            target_count = targets.len(),
+            "starting chaos partition workload"
+        );
+
+        let mut rng = ctx.rng(self.name());
+        let cancellation = ctx.cancellation();
+        loop {
+            if cancellation
+                .until_cancelled(sleep(self.heal_duration))
+                .await
+                .is_none()
+            {
+                return Ok(());
+            }
+
+            let groups = self.random_groups(&targets, &mut rng);
+            info!(?groups, "chaos partitioning network");
+            let guard = PartitionGuard::new(Arc::clone(&handle));
+            handle
+                .partition(&groups)
+                .await
+                .map_err(|err| format!("network partition failed: {err}"))?;
+            for target in groups.iter().flatten() {
+                ctx.report()
+                    .record_node_event(*target, NodeEventKind::Partition);
+            }
+
+            let cancelled = cancellation
+                .until_cancelled(sleep(self.partition_duration))
+                .await
+                .is_none();
+
+            info!("chaos healing network partition");
+            guard.heal().await?;
+            for target in &targets {
+                ctx.report()
+                    .record_node_event(*target, NodeEventKind::HealPartition);
+            }
+            if cancelled {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Heals an active partition when the workload is aborted mid-cycle so the
+/// cluster is not left split while expectations evaluate.
+struct PartitionGuard {
+    handle: Option<Arc<dyn NodeControlHandle>>,
+}
+
+impl PartitionGuard {
+    fn new(handle: Arc<dyn NodeControlHandle>) -> Self {
+        Self {
+            handle: Some(handle),
+        }
+    }
+
+    async fn heal(mut self) -> Result<(), DynError> {
+        let handle = self.handle.take().expect("partition guard holds a handle");
+        handle
+            .heal_partition()
+            .await
+            .map_err(|err| format!("network heal failed: {err}").into())
+    }
+}
+
+impl Drop for PartitionGuard {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            tokio::spawn(async move {
+                if let Err(err) = handle.heal_partition().await {
+                    warn!(error = %err, "failed to heal network partition after abort");
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::{
+    future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use testing_framework_core::{
+    nodes::{ApiClient, wait_for_height},
+    scenario::{
+        AddedNode, DynError, Expectation, NodeEventKind, NodeTarget, RunContext, RunMetrics,
+        Workload,
+    },
+    topology::generation::{GeneratedTopology, NodeRole},
+};
+use thiserror::Error;
+use tokio::time::{Instant, sleep};
+use tracing::info;
+
+use super::downtime::max_height;
+use crate::expectations::violations;
+
+const WORKLOAD_NAME: &str = "chaos_resize";
+const EXPECTATION_NAME: &str = "cluster_resize";
+
+/// A membership change applied by [`ClusterResizeWorkload`].
+#[derive(Clone, Copy, Debug)]
+pub enum ResizeAction {
+    /// Start a new node of the given role.
+    Add(NodeRole),
+    /// Remove a deployed node, or one added by an earlier step.
+    Remove(NodeTarget),
+}
+
+/// A [`ResizeAction`] scheduled `at` an offset from the workload start.
+#[derive(Clone, Copy, Debug)]
+pub struct ResizeStep {
+    pub at: Duration,
+    pub action: ResizeAction,
+}
+
+/// What the workload changed, shared with its expectation.
+#[derive(Default)]
+struct ResizeState {
+    added: Vec<AddedNode>,
+    removed: Vec<NodeTarget>,
+    /// Highest height the remaining nodes reported after the last step.
+    settled_height: u64,
+}
+
+type SharedResizeState = Arc<Mutex<ResizeState>>;
+
+/// Grows and shrinks the cluster while the scenario runs.
+///
+/// Steps run in order of their offset. An added node must reach the height
+/// the rest of the cluster reports within `sync_timeout`, otherwise the
+/// workload fails. The paired expectation checks at the end of the run that
+/// added nodes still follow the tip and that the chain kept advancing after
+/// the last membership change.
+pub struct ClusterResizeWorkload {
+    steps: Vec<ResizeStep>,
+    sync_timeout: Duration,
+    max_lag: Option<u64>,
+    state: SharedResizeState,
+}
+
+impl ClusterResizeWorkload {
+    /// Creates a resize workload running `steps`.
+    #[must_use]
+    pub fn new(mut steps: Vec<ResizeStep>, sync_timeout: Duration, max_lag: Option<u64>) -> Self {
+        steps.sort_by_key(|step| step.at);
+        Self {
+            steps,
+            sync_timeout,
+            max_lag,
+            state: Arc::new(Mutex::new(ResizeState::default())),
+        }
+    }
+
+    fn validate(&self, descriptors: &GeneratedTopology) -> Result<(), DynError> {
+        if self.steps.is_empty() {
+            return Err("chaos resize workload has no steps".into());
+        }
+
+        let mut validators = descriptors.validators().len();
+        let mut executors = descriptors.executors().len();
+        let mut removed = Vec::new();
+        for step in &self.steps {
+            match step.action {
+                ResizeAction::Add(NodeRole::Validator) => validators += 1,
+                ResizeAction::Add(NodeRole::Executor) => executors += 1,
+                ResizeAction::Remove(target) => {
+                    let known = match target {
+                        NodeTarget::Validator(index) => index < validators,
+                        NodeTarget::Executor(index) => index < executors,
+                    };
+                    if !known || removed.contains(&target) {
+                        return Err(format!(
+                            "chaos resize removes {target} at {:?}, which is not running then",
+                            step.at
+                        )
+                        .into());
+                    }
+                    removed.push(target);
+                }
+            }
+        }
+        if validators
+            <= removed
+                .iter()
+                .filter(|target| target.role() == NodeRole::Validator)
+                .count()
+        {
+            return Err("chaos resize would remove every validator".into());
+        }
+        Ok(())
+    }
+
+    /// Clients of every node still part of the cluster.
+    fn remaining_clients(&self, ctx: &RunContext) -> Vec<(NodeTarget, ApiClient)> {
+        let state = self.state.lock().expect("resize state lock poisoned");
+        ctx.node_clients()
+            .targeted_clients()
+            .map(|(target, client)| (target, client.clone()))
+            .chain(
+                state
+                    .added
+                    .iter()
+                    .map(|node| (node.target, node.client.clone())),
+            )
+            .filter(|(target, _)| !state.removed.contains(target))
+            .collect()
+    }
+
+    async fn add(&self, ctx: &RunContext, role: NodeRole) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos resize workload requires node control".to_owned())?;
+        let node = handle
+            .add_node(role)
+            .await
+            .map_err(|err| format!("adding {role:?} node failed: {err}"))?;
+        ctx.report()
+            .record_node_event(node.target, NodeEventKind::Add);
+        info!(target = %node.target, "chaos resize added node");
+
+        let reference =
+            max_height(self.remaining_clients(ctx).iter().map(|(_, client)| client)).await;
+        self.state
+            .lock()
+            .expect("resize state lock poisoned")
+            .added
+            .push(node.clone());
+        wait_for_sync(&node, reference, self.sync_timeout).await
+    }
+
+    async fn remove(&self, ctx: &RunContext, target: NodeTarget) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos resize workload requires node control".to_owned())?;
+        handle
+            .remove_node(target)
+            .await
+            .map_err(|err| format!("removing {target} failed: {err}"))?;
+        ctx.report()
+            .record_node_event(target, NodeEventKind::Remove);
+        info!(%target, "chaos resize removed node");
+
+        self.state
+            .lock()
+            .expect("resize state lock poisoned")
+            .removed
+            .push(target);
+        Ok(())
+    }
+}
+
+/// Waits until an added node reaches `reference`, failing after `timeout`.
+async fn wait_for_sync(
+    node: &AddedNode,
+    reference: u64,
+    timeout: Duration,
+) -> Result<(), DynError> {
+    let target = node.target;
+    let info = wait_for_height(&node.client, reference, timeout)
+        .await
+        .map_err(|err| format!("added {target} did not sync: {err}"))?;
+    info!(%target, height = info.height, reference, "added node synced");
+    Ok(())
+}
+
+#[async_trait]
+impl Workload for ClusterResizeWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos", "resize"]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(ClusterResizeExpectation {
+            state: Arc::clone(&self.state),
+            max_lag: self.max_lag,
+        })]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        self.validate(descriptors)
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        if ctx.node_control().is_none() {
+            return Err("chaos resize workload requires node control".into());
+        }
+        info!(
+            steps = ?self.steps,
+            sync_timeout = ?self.sync_timeout,
+            "starting chaos resize workload"
+        );
+
+        let started = Instant::now();
+        for step in &self.steps {
+            sleep(step.at.saturating_sub(started.elapsed())).await;
+            match step.action {
+                ResizeAction::Add(role) => self.add(ctx, role).await?,
+                ResizeAction::Remove(target) => self.remove(ctx, target).await?,
+            }
+
+            let height =
+                max_height(self.remaining_clients(ctx).iter().map(|(_, client)| client)).await;
+            self.state
+                .lock()
+                .expect("resize state lock poisoned")
+                .settled_height = height;
+        }
+
+        // Keep running so a finished resize schedule does not end the run
+        // before expectations had time to observe the new membership.
+        future::pending::<()>().await;
+        Ok(())
+    }
+}
+
+/// Checks that the cluster kept working after it was resized.
+struct ClusterResizeExpectation {
+    state: SharedResizeState,
+    max_lag: Option<u64>,
+}
+
+#[derive(Debug, Error)]
+enum ClusterResizeError {
+    #[error("no resize step ran before the end of the run")]
+    NoSteps,
+    #[error("no remaining node reported its height")]
+    MissingReference,
+    #[error("chain stalled at height {tip} after the last resize step (settled at {settled})")]
+    Stalled { tip: u64, settled: u64 },
+    #[error("added nodes fell behind:\n{0}")]
+    Violations(String),
+}
+
+#[async_trait]
+impl Expectation for ClusterResizeExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.max_lag.is_none() {
+            self.max_lag = Some(u64::from(
+                descriptors.config().consensus_params.security_param.get(),
+            ));
+        }
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let (added, removed, settled) = {
+            let state = self.state.lock().expect("resize state lock poisoned");
+            (
+                state.added.clone(),
+                state.removed.clone(),
+                state.settled_height,
+            )
+        };
+        if added.is_empty() && removed.is_empty() {
+            return Err(ClusterResizeError::NoSteps.into());
+        }
+
+        let mut tip = None;
+        for (target, client) in ctx.node_clients().targeted_clients() {
+            if removed.contains(&target) {
+                continue;
+            }
+            if let Ok(info) = client.consensus_info().await {
+                tip = tip.max(Some(info.height));
+            }
+        }
+        let tip = tip.ok_or(ClusterResizeError::MissingReference)?;
+        if tip <= settled {
+            return Err(ClusterResizeError::Stalled { tip, settled }.into());
+        }
+
+        let allowed = self.max_lag.unwrap_or(0);
+        let mut issues = Vec::new();
+        for node in added.iter().filter(|node| !removed.contains(&node.target)) {
+            let target = node.target;
+            match node.client.consensus_info().await {
+                Ok(info) => {
+                    let lag = tip.saturating_sub(info.height);
+                    ctx.report()
+                        .set_counter(EXPECTATION_NAME, &format!("{target}_lag"), lag);
+                    if lag > allowed {
+                        issues.push(format!(
+                            "{target} is at height {}, {lag} blocks behind the tip at {tip} (allowed {allowed})",
+                            info.height
+                        ));
+                    }
+                }
+                Err(err) => issues.push(format!("{target} consensus info request failed: {err}")),
+            }
+        }
+
+        let Some(details) = violations("cluster resize", &issues) else {
+            info!(
+                added = added.len(),
+                removed = removed.len(),
+                tip,
+                "cluster resize expectation satisfied"
+            );
+            return Ok(());
+        };
+        Err(ClusterResizeError::Violations(details).into())
+    }
+}
@@ -1,8 +1,8 @@
 use std::{collections::HashMap, time::Duration};
 
 use async_trait::async_trait;
-use rand::{Rng as _, seq::SliceRandom as _, thread_rng};
-use testing_framework_core::scenario::{DynError, RunContext, Workload};
+use rand::{Rng, seq::SliceRandom as _};
+use testing_framework_core::scenario::{DynError, NodeEventKind, NodeTarget, RunContext, Workload};
 use tokio::time::{Instant, sleep};
 use tracing::info;
 
@@ -59,7 +59,7 @@ impl RandomRestartWorkload {
         targets
     }
 
-    fn random_delay(&self) -> Duration {
+    fn random_delay(&self, rng: &mut impl Rng) -> Duration {
         if self.max_delay <= self.min_delay {
             return self.min_delay;
         }
@@ -68,7 +68,7 @@ impl RandomRestartWorkload {
             .checked_sub(self.min_delay)
             .unwrap_or_else(|| Duration::from_millis(1))
             .as_secs_f64();
-        let offset = thread_rng().gen_range(0.0..=spread);
+        let offset = rng.gen_range(0.0..=spread);
         let delay = self
             .min_delay
             .checked_add(Duration::from_secs_f64(offset))
@@ -91,6 +91,7 @@ impl RandomRestartWorkload {
         &self,
         targets: &[Target],
         cooldowns: &HashMap<Target, Instant>,
+        rng: &mut (impl Rng + Send),
     ) -> Target {
         loop {
             let now = Instant::now();
@@ -117,13 +118,13 @@ impl RandomRestartWorkload {
                 .filter(|target| cooldowns.get(target).is_none_or(|ready| *ready <= now))
                 .collect();
 
-            if let Some(choice) = available.choose(&mut thread_rng()).copied() {
+            if let Some(choice) = available.choose(rng).copied() {
                 tracing::debug!(?choice, "chaos restart picked target");
                 return choice;
             }
 
             return targets
-                .choose(&mut thread_rng())
+                .choose(rng)
                 .copied()
                 .expect("chaos restart workload has targets");
         }
@@ -136,6 +137,10 @@ impl Workload for RandomRestartWorkload {
         "chaos_restart"
     }
 
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
     async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
         let handle = ctx
             .node_control()
@@ -155,27 +160,24 @@ impl Workload for RandomRestartWorkload {
         );
 
         let mut cooldowns = self.initialize_cooldowns(&targets);
+        let mut rng = ctx.rng(self.name());
 
         loop {
-            sleep(self.random_delay()).await;
-            let target = self.pick_target(&targets, &cooldowns).await;
-
-            match target {
-                Target::Validator(index) => {
-                    tracing::info!(index, "chaos restarting validator");
-                    handle
-                        .restart_validator(index)
-                        .await
-                        .map_err(|err| format!("validator restart failed: {err}"))?
-                }
-                Target::Executor(index) => {
-                    tracing::info!(index, "chaos restarting executor");
-                    handle
-                        .restart_executor(index)
-                        .await
-                        .map_err(|err| format!("executor restart failed: {err}"))?
-                }
-            }
+            sleep(self.random_delay(&mut rng)).await;
+            let target = self.pick_target(&targets, &cooldowns, &mut rng).await;
+
+            let node = NodeTarget::from(target);
+            tracing::info!(%node, "chaos restarting node");
+            ctx.recovery()
+                .restart(
+                    handle.as_ref(),
+                    node,
+                    ctx.node_clients(),
+                    ctx.run_duration(),
+                )
+                .await
+                .map_err(|err| format!("{node} restart failed: {err}"))?;
+            ctx.report().record_node_event(node, NodeEventKind::Restart);
 
             cooldowns.insert(target, Instant::now() + self.target_cooldown);
         }
@@ -187,3 +189,12 @@ enum Target {
     Validator(usize),
     Executor(usize),
 }
+
+impl From<Target> for NodeTarget {
+    fn from(target: Target) -> Self {
+        match target {
+            Target::Validator(index) => Self::Validator(index),
+            Target::Executor(index) => Self::Executor(index),
+        }
+    }
+}
@@ -0,0 +1,932 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rand::{rngs::StdRng, seq::SliceRandom as _};
+use testing_framework_core::{
+    scenario::{
+        DynError, NetworkShaping, NodeControlHandle, NodeEventKind, NodeTarget, RunContext,
+        RunMetrics, RunReporter, Workload,
+    },
+    topology::{generation::GeneratedTopology, utils::seeded_rng},
+};
+use tokio::time::{Instant, sleep};
+use tracing::{info, warn};
+
+use super::downtime::{start_node, stop_node};
+
+const WORKLOAD_NAME: &str = "chaos_timeline";
+
+/// Node a timeline fault applies to. The `Any*` variants are resolved from
+/// the timeline seed before the run starts, skipping nodes that are stopped
+/// at that point of the timeline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FaultTarget {
+    Node(NodeTarget),
+    AnyValidator,
+    AnyExecutor,
+    AnyNode,
+}
+
+impl From<NodeTarget> for FaultTarget {
+    fn from(target: NodeTarget) -> Self {
+        Self::Node(target)
+    }
+}
+
+/// How a timeline partition splits the network.
+#[derive(Clone, Debug)]
+enum PartitionLayout {
+    /// Shuffle every node into this many groups.
+    Random(usize),
+    Groups(Vec<Vec<NodeTarget>>),
+}
+
+#[derive(Clone, Debug)]
+enum Fault {
+    Restart(FaultTarget),
+    Stop {
+        target: FaultTarget,
+        duration: Duration,
+    },
+    Partition {
+        layout: PartitionLayout,
+        duration: Duration,
+    },
+    Latency {
+        targets: Vec<FaultTarget>,
+        shaping: NetworkShaping,
+        duration: Option<Duration>,
+    },
+}
+
+impl Fault {
+    const fn duration(&self) -> Option<Duration> {
+        match self {
+            Self::Restart(_) => None,
+            Self::Stop { duration, .. } | Self::Partition { duration, .. } => Some(*duration),
+            Self::Latency { duration, .. } => *duration,
+        }
+    }
+}
+
+/// A fault declared `at` an offset from the start of the timeline.
+#[derive(Clone, Debug)]
+struct TimelineFault {
+    at: Duration,
+    fault: Fault,
+}
+
+impl TimelineFault {
+    fn end(&self) -> Option<Duration> {
+        self.fault.duration().map(|duration| self.at + duration)
+    }
+}
+
+/// Faults declared on an explicit timeline, e.g. restart validator 2 at
+/// +30s, partition the network at +60s for 45s and add latency from +120s.
+///
+/// Offsets are measured from the start of the workload and must fall within
+/// the run duration. Random choices
+/// (`Any*` targets, random partition groups) are drawn from the scenario
+/// seed, or from [`ChaosTimeline::seed`] when set, so a timeline replays the
+/// same faults on the same nodes. Each applied fault is exported in the
+/// `chaos_timeline` section of the run report.
+#[derive(Clone, Debug, Default)]
+pub struct ChaosTimeline {
+    faults: Vec<TimelineFault>,
+    seed: Option<u64>,
+}
+
+impl ChaosTimeline {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            faults: Vec::new(),
+            seed: None,
+        }
+    }
+
+    #[must_use]
+    /// Draw random choices from `seed` instead of the scenario seed.
+    pub const fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    #[must_use]
+    /// Restart `target` at `at`.
+    pub fn restart(self, at: Duration, target: impl Into<FaultTarget>) -> Self {
+        self.fault(at, Fault::Restart(target.into()))
+    }
+
+    #[must_use]
+    /// Stop `target` at `at` and start it again `duration` later.
+    pub fn stop(self, at: Duration, duration: Duration, target: impl Into<FaultTarget>) -> Self {
+        self.fault(
+            at,
+            Fault::Stop {
+                target: target.into(),
+                duration,
+            },
+        )
+    }
+
+    #[must_use]
+    /// Split every node into `groups` random groups at `at` and heal the
+    /// network `duration` later.
+    pub fn partition(self, at: Duration, duration: Duration, groups: usize) -> Self {
+        self.fault(
+            at,
+            Fault::Partition {
+                layout: PartitionLayout::Random(groups),
+                duration,
+            },
+        )
+    }
+
+    #[must_use]
+    /// Split the network into explicit `groups` at `at` and heal it
+    /// `duration` later. Nodes not listed keep their connectivity.
+    pub fn partition_groups(
+        self,
+        at: Duration,
+        duration: Duration,
+        groups: Vec<Vec<NodeTarget>>,
+    ) -> Self {
+        self.fault(
+            at,
+            Fault::Partition {
+                layout: PartitionLayout::Groups(groups),
+                duration,
+            },
+        )
+    }
+
+    #[must_use]
+    /// Shape every node's traffic from `at` until the end of the run.
+    pub fn latency(self, at: Duration, shaping: NetworkShaping) -> Self {
+        self.fault(
+            at,
+            Fault::Latency {
+                targets: Vec::new(),
+                shaping,
+                duration: None,
+            },
+        )
+    }
+
+    #[must_use]
+    /// Shape the traffic of `targets` from `at` for `duration`.
+    pub fn latency_window(
+        self,
+        at: Duration,
+        duration: Duration,
+        targets: impl IntoIterator<Item = FaultTarget>,
+        shaping: NetworkShaping,
+    ) -> Self {
+        self.fault(
+            at,
+            Fault::Latency {
+                targets: targets.into_iter().collect(),
+                shaping,
+                duration: Some(duration),
+            },
+        )
+    }
+
+    fn fault(mut self, at: Duration, fault: Fault) -> Self {
+        self.faults.push(TimelineFault { at, fault });
+        self
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.faults.is_empty()
+    }
+}
+
+/// Node control call made by the scheduler.
+#[derive(Clone, Debug)]
+enum Action {
+    Restart(NodeTarget),
+    Stop(NodeTarget),
+    Start(NodeTarget),
+    Partition(Vec<Vec<NodeTarget>>),
+    Heal,
+    Shape(Vec<NodeTarget>, NetworkShaping),
+    ClearShaping(Vec<NodeTarget>),
+}
+
+/// An action resolved from a [`TimelineFault`]; `fault` is set for the
+/// action that applies a fault and `None` for the one that lifts it.
+#[derive(Clone, Debug)]
+struct Step {
+    at: Duration,
+    action: Action,
+    fault: Option<(&'static str, Duration, Option<Duration>)>,
+}
+
+/// Executes a [`ChaosTimeline`] with a single scheduler.
+///
+/// The timeline is validated and resolved into concrete node control calls
+/// when the scenario is built. Faults still in place when the run ends are
+/// lifted before expectations evaluate: stopped nodes are started,
+/// partitions healed and shaping cleared.
+pub struct ChaosTimelineWorkload {
+    timeline: ChaosTimeline,
+    steps: Vec<Step>,
+}
+
+impl ChaosTimelineWorkload {
+    #[must_use]
+    pub const fn new(timeline: ChaosTimeline) -> Self {
+        Self {
+            timeline,
+            steps: Vec::new(),
+        }
+    }
+
+    fn plan(
+        &self,
+        mut planner: Planner<'_>,
+        run_duration: Duration,
+    ) -> Result<Vec<Step>, DynError> {
+        let mut faults = self.timeline.faults.clone();
+        if faults.is_empty() {
+            return Err("chaos timeline has no faults".into());
+        }
+        faults.sort_by_key(|fault| fault.at);
+        if let Some(late) = faults.iter().find(|fault| fault.at >= run_duration) {
+            return Err(format!(
+                "chaos timeline fault at {:?} starts after the run ends ({run_duration:?})",
+                late.at
+            )
+            .into());
+        }
+        check_overlaps(&faults)?;
+
+        let mut steps = Vec::new();
+        for fault in &faults {
+            let at = fault.at;
+            let end = fault.end();
+            planner.release_until(at);
+            let record = |name| Some((name, at, fault.fault.duration()));
+            match &fault.fault {
+                Fault::Restart(target) => {
+                    let target = planner.resolve(*target, at)?;
+                    steps.push(Step {
+                        at,
+                        action: Action::Restart(target),
+                        fault: record("restart"),
+                    });
+                }
+                Fault::Stop { target, .. } => {
+                    let target = planner.resolve(*target, at)?;
+                    let end = end.expect("stop faults have a duration");
+                    planner.stopped.push((target, end));
+                    steps.push(Step {
+                        at,
+                        action: Action::Stop(target),
+                        fault: record("stop"),
+                    });
+                    steps.push(Step {
+                        at: end,
+                        action: Action::Start(target),
+                        fault: None,
+                    });
+                }
+                Fault::Partition { layout, .. } => {
+                    let groups = planner.groups(layout, at)?;
+                    steps.push(Step {
+                        at,
+                        action: Action::Partition(groups),
+                        fault: record("partition"),
+                    });
+                    steps.push(Step {
+                        at: end.expect("partition faults have a duration"),
+                        action: Action::Heal,
+                        fault: None,
+                    });
+                }
+                Fault::Latency {
+                    targets, shaping, ..
+                } => {
+                    let targets = if targets.is_empty() {
+                        planner.all_nodes()
+                    } else {
+                        targets
+                            .iter()
+                            .map(|target| planner.resolve(*target, at))
+                            .collect::<Result<Vec<_>, _>>()?
+                    };
+                    steps.push(Step {
+                        at,
+                        action: Action::Shape(targets.clone(), *shaping),
+                        fault: record("latency"),
+                    });
+                    if let Some(end) = end {
+                        steps.push(Step {
+                            at: end,
+                            action: Action::ClearShaping(targets),
+                            fault: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        // Lift faults before applying the ones scheduled at the same offset.
+        steps.sort_by_key(|step| (step.at, step.fault.is_some()));
+        Ok(steps)
+    }
+}
+
+/// Rejects partitions or latency windows that overlap in time: a node
+/// holds one partition and one shaping profile at a time.
+fn check_overlaps(faults: &[TimelineFault]) -> Result<(), DynError> {
+    check_overlap(faults, "partitions", |fault| {
+        matches!(fault, Fault::Partition { .. })
+    })?;
+    check_overlap(faults, "latency faults", |fault| {
+        matches!(fault, Fault::Latency { .. })
+    })
+}
+
+fn check_overlap(
+    faults: &[TimelineFault],
+    kind: &str,
+    selected: impl Fn(&Fault) -> bool,
+) -> Result<(), DynError> {
+    // `Some(None)` marks a fault held until the end of the run.
+    let mut active_until: Option<Option<Duration>> = None;
+    for fault in faults.iter().filter(|fault| selected(&fault.fault)) {
+        if active_until.is_some_and(|until| until.is_none_or(|until| until > fault.at)) {
+            return Err(format!(
+                "chaos timeline {kind} overlap at {:?}; only one can be active at a time",
+                fault.at
+            )
+            .into());
+        }
+        active_until = Some(fault.end());
+    }
+    Ok(())
+}
+
+struct Planner<'a> {
+    validators: usize,
+    executors: usize,
+    /// Nodes stopped by the timeline and the offset they start again.
+    stopped: Vec<(NodeTarget, Duration)>,
+    rng: &'a mut StdRng,
+}
+
+impl<'a> Planner<'a> {
+    fn new(validators: usize, executors: usize, rng: &'a mut StdRng) -> Self {
+        Self {
+            validators,
+            executors,
+            stopped: Vec::new(),
+            rng,
+        }
+    }
+
+    fn release_until(&mut self, at: Duration) {
+        self.stopped.retain(|(_, until)| *until > at);
+    }
+
+    fn is_stopped(&self, target: NodeTarget) -> bool {
+        self.stopped.iter().any(|(stopped, _)| *stopped == target)
+    }
+
+    fn all_nodes(&self) -> Vec<NodeTarget> {
+        (0..self.validators)
+            .map(NodeTarget::Validator)
+            .chain((0..self.executors).map(NodeTarget::Executor))
+            .collect()
+    }
+
+    fn exists(&self, target: NodeTarget) -> bool {
+        match target {
+            NodeTarget::Validator(index) => index < self.validators,
+            NodeTarget::Executor(index) => index < self.executors,
+        }
+    }
+
+    fn resolve(&mut self, target: FaultTarget, at: Duration) -> Result<NodeTarget, DynError> {
+        let candidates = match target {
+            FaultTarget::Node(node) => {
+                if !self.exists(node) {
+                    return Err(format!("chaos timeline targets unknown node {node}").into());
+                }
+                if self.is_stopped(node) {
+                    return Err(format!(
+                        "chaos timeline targets {node} at {at:?} while it is stopped"
+                    )
+                    .into());
+                }
+                return Ok(node);
+            }
+            FaultTarget::AnyValidator => (0..self.validators).map(NodeTarget::Validator).collect(),
+            FaultTarget::AnyExecutor => (0..self.executors).map(NodeTarget::Executor).collect(),
+            FaultTarget::AnyNode => self.all_nodes(),
+        };
+        let candidates = candidates
+            .into_iter()
+            .filter(|node: &NodeTarget| !self.is_stopped(*node))
+            .collect::<Vec<_>>();
+        candidates
+            .choose(self.rng)
+            .copied()
+            .ok_or_else(|| format!("chaos timeline has no running {target:?} at {at:?}").into())
+    }
+
+    fn groups(
+        &mut self,
+        layout: &PartitionLayout,
+        at: Duration,
+    ) -> Result<Vec<Vec<NodeTarget>>, DynError> {
+        match layout {
+            PartitionLayout::Random(count) => {
+                let mut nodes = self.all_nodes();
+                if *count < 2 || nodes.len() < *count {
+                    return Err(format!(
+                        "chaos timeline partition at {at:?} needs at least two groups and one node per group, got {count} groups for {} nodes",
+                        nodes.len()
+                    )
+                    .into());
+                }
+                nodes.shuffle(self.rng);
+                let mut groups = vec![Vec::new(); *count];
+                for (position, node) in nodes.into_iter().enumerate() {
+                    groups[position % count].push(node);
+                }
+                Ok(groups)
+            }
+            PartitionLayout::Groups(groups) => {
+                if groups.len() < 2 {
+                    return Err(format!(
+                        "chaos timeline partition at {at:?} needs at least two groups"
+                    )
+                    .into());
+                }
+                if let Some(node) = groups.iter().flatten().find(|node| !self.exists(**node)) {
+                    return Err(format!("chaos timeline partitions unknown node {node}").into());
+                }
+                Ok(groups.clone())
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Workload for ChaosTimelineWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["chaos"]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        let mut rng = match self.timeline.seed {
+            Some(seed) => seeded_rng(Some(seed), WORKLOAD_NAME),
+            None => descriptors.rng(WORKLOAD_NAME),
+        };
+        let planner = Planner::new(
+            descriptors.validators().len(),
+            descriptors.executors().len(),
+            &mut rng,
+        );
+        self.steps = self.plan(planner, run_metrics.run_duration())?;
+        Ok(())
+    }
+
+    fn observes_cancellation(&self) -> bool {
+        true
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let handle = ctx
+            .node_control()
+            .ok_or_else(|| "chaos timeline workload requires node control".to_owned())?;
+
+        info!(
+            faults = self.timeline.faults.len(),
+            steps = self.steps.len(),
+            seed = ?self.timeline.seed.or(ctx.descriptors().seed()),
+            "starting chaos timeline workload"
+        );
+
+        let mut active = ActiveFaults::new(Arc::clone(&handle), ctx.report().clone());
+        let outcome = self.run_steps(&mut active, ctx).await;
+        active.lift().await;
+        outcome
+    }
+}
+
+impl ChaosTimelineWorkload {
+    /// Apply the planned steps on schedule, then keep faults declared until
+    /// the end of the run in place until the run is cancelled.
+    async fn run_steps(&self, active: &mut ActiveFaults, ctx: &RunContext) -> Result<(), DynError> {
+        let cancellation = ctx.cancellation();
+        let started = Instant::now();
+        for step in &self.steps {
+            if cancellation
+                .until_cancelled(sleep(step.at.saturating_sub(started.elapsed())))
+                .await
+                .is_none()
+            {
+                return Ok(());
+            }
+            info!(at = ?step.at, action = ?step.action, "chaos timeline step");
+            active.apply(&step.action).await?;
+            if let Some((name, scheduled, duration)) = step.fault {
+                ctx.report()
+                    .record_chaos_fault(name, &step.action.nodes(), scheduled, duration);
+            }
+            ctx.report().increment(WORKLOAD_NAME, "steps", 1);
+        }
+        cancellation.cancelled().await;
+        Ok(())
+    }
+}
+
+impl Action {
+    fn nodes(&self) -> Vec<NodeTarget> {
+        match self {
+            Self::Restart(target) | Self::Stop(target) | Self::Start(target) => vec![*target],
+            Self::Partition(groups) => groups.iter().flatten().copied().collect(),
+            Self::Heal => Vec::new(),
+            Self::Shape(targets, _) | Self::ClearShaping(targets) => targets.clone(),
+        }
+    }
+}
+
+/// Faults the scheduler applied and has not lifted yet. The workload lifts
+/// them before it returns; dropping the guard with faults still in place
+/// only happens when the runner aborts the workload, and lifts them in the
+/// background as a last resort.
+struct ActiveFaults {
+    handle: Arc<dyn NodeControlHandle>,
+    report: RunReporter,
+    stopped: Vec<NodeTarget>,
+    partitioned: Vec<NodeTarget>,
+    shaped: Vec<NodeTarget>,
+}
+
+impl ActiveFaults {
+    const fn new(handle: Arc<dyn NodeControlHandle>, report: RunReporter) -> Self {
+        Self {
+            handle,
+            report,
+            stopped: Vec::new(),
+            partitioned: Vec::new(),
+            shaped: Vec::new(),
+        }
+    }
+
+    async fn apply(&mut self, action: &Action) -> Result<(), DynError> {
+        let handle = self.handle.as_ref();
+        match action {
+            Action::Restart(target) => {
+                match target {
+                    NodeTarget::Validator(index) => handle.restart_validator(*index).await,
+                    NodeTarget::Executor(index) => handle.restart_executor(*index).await,
+                }
+                .map_err(|err| format!("restarting {target} failed: {err}"))?;
+                self.report
+                    .record_node_event(*target, NodeEventKind::Restart);
+            }
+            Action::Stop(target) => {
+                stop_node(handle, *target)
+                    .await
+                    .map_err(|err| format!("stopping {target} failed: {err}"))?;
+                self.stopped.push(*target);
+                self.report.record_node_event(*target, NodeEventKind::Stop);
+            }
+            Action::Start(target) => {
+                start_node(handle, *target)
+                    .await
+                    .map_err(|err| format!("starting {target} failed: {err}"))?;
+                self.stopped.retain(|stopped| stopped != target);
+                self.report.record_node_event(*target, NodeEventKind::Start);
+            }
+            Action::Partition(groups) => {
+                handle
+                    .partition(groups)
+                    .await
+                    .map_err(|err| format!("network partition failed: {err}"))?;
+                self.partitioned = action.nodes();
+                for target in &self.partitioned {
+                    self.report
+                        .record_node_event(*target, NodeEventKind::Partition);
+                }
+            }
+            Action::Heal => {
+                handle
+                    .heal_partition()
+                    .await
+                    .map_err(|err| format!("network heal failed: {err}"))?;
+                for target in std::mem::take(&mut self.partitioned) {
+                    self.report
+                        .record_node_event(target, NodeEventKind::HealPartition);
+                }
+            }
+            Action::Shape(targets, shaping) => {
+                for target in targets {
+                    handle
+                        .shape_network(*target, *shaping)
+                        .await
+                        .map_err(|err| format!("network shaping failed: {err}"))?;
+                    self.shaped.push(*target);
+                    self.report
+                        .record_node_event(*target, NodeEventKind::NetworkShaping);
+                }
+            }
+            Action::ClearShaping(targets) => {
+                for target in targets {
+                    handle
+                        .clear_network_shaping(*target)
+                        .await
+                        .map_err(|err| format!("clearing network shaping failed: {err}"))?;
+                    self.shaped.retain(|shaped| shaped != target);
+                    self.report
+                        .record_node_event(*target, NodeEventKind::ClearNetworkShaping);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ActiveFaults {
+    const fn is_empty(&self) -> bool {
+        self.stopped.is_empty() && self.partitioned.is_empty() && self.shaped.is_empty()
+    }
+
+    /// Start stopped nodes, heal the partition and clear shaping, logging
+    /// what fails so every fault gets its chance to be lifted.
+    async fn lift(&mut self) {
+        let handle = self.handle.as_ref();
+        for target in std::mem::take(&mut self.stopped) {
+            match start_node(handle, target).await {
+                Ok(()) => self.report.record_node_event(target, NodeEventKind::Start),
+                Err(err) => {
+                    warn!(%target, error = %err, "failed to start node after chaos timeline")
+                }
+            }
+        }
+        let partitioned = std::mem::take(&mut self.partitioned);
+        if !partitioned.is_empty() {
+            match handle.heal_partition().await {
+                Ok(()) => {
+                    for target in partitioned {
+                        self.report
+                            .record_node_event(target, NodeEventKind::HealPartition);
+                    }
+                }
+                Err(err) => {
+                    warn!(error = %err, "failed to heal network partition after chaos timeline")
+                }
+            }
+        }
+        for target in std::mem::take(&mut self.shaped) {
+            match handle.clear_network_shaping(target).await {
+                Ok(()) => self
+                    .report
+                    .record_node_event(target, NodeEventKind::ClearNetworkShaping),
+                Err(err) => warn!(%target, error = %err, "failed to clear network shaping"),
+            }
+        }
+    }
+}
+
+impl Drop for ActiveFaults {
+    fn drop(&mut self) {
+        if self.is_empty() {
+            return;
+        }
+        let mut faults = Self {
+            handle: Arc::clone(&self.handle),
+            report: self.report.clone(),
+            stopped: std::mem::take(&mut self.stopped),
+            partitioned: std::mem::take(&mut self.partitioned),
+            shaped: std::mem::take(&mut self.shaped),
+        };
+        warn!("chaos timeline aborted with faults in place; lifting them in the background");
+        tokio::spawn(async move { faults.lift().await });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUN: Duration = Duration::from_secs(300);
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    fn plan(timeline: ChaosTimeline) -> Result<Vec<Step>, DynError> {
+        let mut rng = seeded_rng(Some(7), WORKLOAD_NAME);
+        ChaosTimelineWorkload::new(timeline).plan(Planner::new(3, 1, &mut rng), RUN)
+    }
+
+    fn timeline_fault(at: u64, fault: Fault) -> TimelineFault {
+        TimelineFault {
+            at: secs(at),
+            fault,
+        }
+    }
+
+    fn partition(duration: u64) -> Fault {
+        Fault::Partition {
+            layout: PartitionLayout::Random(2),
+            duration: secs(duration),
+        }
+    }
+
+    #[test]
+    fn plan_lifts_faults_before_applying_the_next_ones() {
+        let steps = plan(
+            ChaosTimeline::new()
+                .restart(secs(30), NodeTarget::Validator(0))
+                .stop(secs(10), secs(20), NodeTarget::Validator(0)),
+        )
+        .expect("valid timeline");
+
+        let actions = steps
+            .iter()
+            .map(|step| (step.at, step.action.clone(), step.fault.is_some()))
+            .collect::<Vec<_>>();
+        assert!(matches!(
+            actions.as_slice(),
+            [
+                (at, Action::Stop(NodeTarget::Validator(0)), true),
+                (end, Action::Start(NodeTarget::Validator(0)), false),
+                (restart, Action::Restart(NodeTarget::Validator(0)), true),
+            ] if *at == secs(10) && *end == secs(30) && *restart == secs(30)
+        ));
+        assert_eq!(steps[0].fault, Some(("stop", secs(10), Some(secs(20)))));
+    }
+
+    #[test]
+    fn plan_rejects_invalid_timelines() {
+        assert!(plan(ChaosTimeline::new()).is_err());
+        assert!(plan(ChaosTimeline::new().restart(RUN, NodeTarget::Validator(0))).is_err());
+        assert!(plan(ChaosTimeline::new().restart(secs(5), NodeTarget::Validator(3))).is_err());
+        assert!(
+            plan(
+                ChaosTimeline::new()
+                    .stop(secs(10), secs(20), NodeTarget::Validator(1))
+                    .restart(secs(20), NodeTarget::Validator(1)),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn plan_is_reproducible_for_a_seed() {
+        let timeline = ChaosTimeline::new()
+            .restart(secs(10), FaultTarget::AnyNode)
+            .partition(secs(20), secs(10), 2);
+        let describe = |steps: Vec<Step>| {
+            steps
+                .iter()
+                .map(|step| format!("{:?}", step.action))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            describe(plan(timeline.clone()).expect("valid timeline")),
+            describe(plan(timeline).expect("valid timeline"))
+        );
+    }
+
+    #[test]
+    fn overlapping_windows_are_rejected() {
+        let sequential = [
+            timeline_fault(10, partition(20)),
+            timeline_fault(30, partition(5)),
+        ];
+        assert!(check_overlaps(&sequential).is_ok());
+
+        let overlapping = [
+            timeline_fault(10, partition(20)),
+            timeline_fault(29, partition(5)),
+        ];
+        assert!(check_overlaps(&overlapping).is_err());
+
+        let shaping = NetworkShaping::default();
+        let until_end = Fault::Latency {
+            targets: Vec::new(),
+            shaping,
+            duration: None,
+        };
+        let window = Fault::Latency {
+            targets: Vec::new(),
+            shaping,
+            duration: Some(secs(5)),
+        };
+        assert!(
+            check_overlaps(&[timeline_fault(10, until_end), timeline_fault(200, window)]).is_err()
+        );
+
+        // A partition and a latency fault may overlap.
+        let mixed = [
+            timeline_fault(10, partition(20)),
+            timeline_fault(
+                15,
+                Fault::Latency {
+                    targets: Vec::new(),
+                    shaping,
+                    duration: Some(secs(5)),
+                },
+            ),
+        ];
+        assert!(check_overlaps(&mixed).is_ok());
+    }
+
+    #[test]
+    fn resolve_skips_stopped_nodes() {
+        let mut rng = seeded_rng(Some(1), WORKLOAD_NAME);
+        let mut planner = Planner::new(2, 0, &mut rng);
+        planner.stopped.push((NodeTarget::Validator(0), secs(60)));
+
+        for _ in 0..8 {
+            assert_eq!(
+                planner
+                    .resolve(FaultTarget::AnyValidator, secs(30))
+                    .expect("a running validator"),
+                NodeTarget::Validator(1)
+            );
+        }
+        assert!(planner.resolve(FaultTarget::AnyExecutor, secs(30)).is_err());
+        assert!(
+            planner
+                .resolve(NodeTarget::Validator(0).into(), secs(30))
+                .is_err()
+        );
+
+        planner.release_until(secs(60));
+        assert_eq!(
+            planner
+                .resolve(NodeTarget::Validator(0).into(), secs(60))
+                .expect("started again"),
+            NodeTarget::Validator(0)
+        );
+    }
+
+    #[test]
+    fn groups_cover_every_node() {
+        let mut rng = seeded_rng(Some(3), WORKLOAD_NAME);
+        let mut planner = Planner::new(3, 1, &mut rng);
+
+        let groups = planner
+            .groups(&PartitionLayout::Random(2), secs(0))
+            .expect("two groups");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.iter().map(Vec::len).collect::<Vec<_>>(), vec![2, 2]);
+        let mut nodes = groups.into_iter().flatten().collect::<Vec<_>>();
+        nodes.sort_by_key(ToString::to_string);
+        let mut all = planner.all_nodes();
+        all.sort_by_key(ToString::to_string);
+        assert_eq!(nodes, all);
+
+        assert!(
+            planner
+                .groups(&PartitionLayout::Random(1), secs(0))
+                .is_err()
+        );
+        assert!(
+            planner
+                .groups(&PartitionLayout::Random(5), secs(0))
+                .is_err()
+        );
+        assert!(
+            planner
+                .groups(
+                    &PartitionLayout::Groups(vec![vec![NodeTarget::Validator(0)]]),
+                    secs(0)
+                )
+                .is_err()
+        );
+        assert!(
+            planner
+                .groups(
+                    &PartitionLayout::Groups(vec![
+                        vec![NodeTarget::Validator(0)],
+                        vec![NodeTarget::Executor(1)],
+                    ]),
+                    secs(0)
+                )
+                .is_err()
+        );
+    }
+}
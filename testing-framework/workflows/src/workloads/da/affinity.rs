@@ -0,0 +1,100 @@
+use rand::{Rng, seq::SliceRandom as _};
+
+/// How the DA workload picks the executor each blob is published through.
+///
+/// With any mode other than [`Self::Random`] a publish only ever goes to the
+/// executor the mode selects, and retries stay on it, so a failing publish
+/// is attributed to exactly one executor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutorAffinity {
+    /// Try every executor in a random order on each publish attempt.
+    #[default]
+    Random,
+    /// Pin channel `i` to executor `i % executors` for the whole run.
+    PerChannel,
+    /// Send the `k`-th blob of channel `i` to executor
+    /// `(i + k) % executors`, spreading each channel over every executor in
+    /// a fixed order.
+    RoundRobin,
+    /// Publish every blob through executor `index`.
+    Executor(usize),
+}
+
+impl ExecutorAffinity {
+    /// Check the mode against the topology's executor count.
+    pub fn validate(self, executors: usize) -> Result<(), String> {
+        if executors == 0 {
+            return Err("da workload requires at least one executor".to_owned());
+        }
+        match self {
+            Self::Executor(index) if index >= executors => Err(format!(
+                "da executor affinity pins executor {index}, but the topology has {executors}"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Executor indices to try, in order, for the `blob`-th publish of the
+    /// `channel`-th channel.
+    pub(super) fn candidates(
+        self,
+        channel: usize,
+        blob: u64,
+        executors: usize,
+        rng: &mut impl Rng,
+    ) -> Vec<usize> {
+        match self {
+            Self::Random => {
+                let mut order: Vec<usize> = (0..executors).collect();
+                order.shuffle(rng);
+                order
+            }
+            Self::PerChannel => vec![channel % executors],
+            Self::RoundRobin => {
+                let offset = (blob % executors as u64) as usize;
+                vec![(channel + offset) % executors]
+            }
+            Self::Executor(index) => vec![index],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+
+    fn candidates(affinity: ExecutorAffinity, channel: usize, blob: u64) -> Vec<usize> {
+        affinity.candidates(channel, blob, 3, &mut StdRng::seed_from_u64(7))
+    }
+
+    #[test]
+    fn random_tries_every_executor_once() {
+        let mut order = candidates(ExecutorAffinity::Random, 0, 0);
+        order.sort_unstable();
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn pinned_modes_pick_a_single_executor() {
+        assert_eq!(candidates(ExecutorAffinity::PerChannel, 4, 0), vec![1]);
+        assert_eq!(candidates(ExecutorAffinity::PerChannel, 4, 9), vec![1]);
+        assert_eq!(candidates(ExecutorAffinity::Executor(2), 0, 5), vec![2]);
+
+        let round_robin = (0..4)
+            .map(|blob| candidates(ExecutorAffinity::RoundRobin, 1, blob)[0])
+            .collect::<Vec<_>>();
+        assert_eq!(round_robin, vec![1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn validate_checks_the_executor_count() {
+        assert!(ExecutorAffinity::Random.validate(0).is_err());
+        assert!(ExecutorAffinity::Executor(2).validate(3).is_ok());
+        let err = ExecutorAffinity::Executor(3)
+            .validate(3)
+            .expect_err("executor 3 is out of range");
+        assert!(err.contains("pins executor 3"), "{err}");
+    }
+}
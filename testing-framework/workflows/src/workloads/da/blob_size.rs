@@ -0,0 +1,174 @@
+use rand::{Rng, seq::SliceRandom as _};
+
+/// Payload bytes encoded into one DA chunk.
+pub const BLOB_CHUNK_BYTES: usize = 31;
+/// Largest chunk count published by the default distribution and probed by
+/// [`BlobSizeDistribution::boundaries`].
+pub const DEFAULT_MAX_BLOB_CHUNKS: usize = 8;
+
+// KZGRS encoder expects the polynomial degree to be a power of two, which
+// effectively constrains the blob chunk count.
+const POWER_OF_TWO_CHUNKS: &[usize] = &[1, 2, 4, 8];
+
+/// Sizes of the blob payloads published by the DA workload.
+///
+/// Sizes that are not a power-of-two number of whole chunks rely on the
+/// executor padding the payload before encoding.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlobSizeDistribution {
+    /// 1, 2, 4 or 8 whole chunks, picked at random.
+    #[default]
+    PowerOfTwoChunks,
+    /// Every blob has exactly this many bytes.
+    Fixed(usize),
+    /// Random size between `min` and `max` bytes, inclusive.
+    Uniform { min: usize, max: usize },
+    /// Cycle through sizes at the encoder's edges: a single byte, one byte
+    /// short of and past a chunk, and one byte short of and exactly
+    /// `max_chunks` chunks. Sizes above `max_chunks` chunks are skipped.
+    Boundaries { max_chunks: usize },
+}
+
+impl BlobSizeDistribution {
+    #[must_use]
+    pub const fn fixed(bytes: usize) -> Self {
+        Self::Fixed(bytes)
+    }
+
+    #[must_use]
+    pub const fn uniform(min: usize, max: usize) -> Self {
+        Self::Uniform { min, max }
+    }
+
+    #[must_use]
+    /// Boundary cases up to [`DEFAULT_MAX_BLOB_CHUNKS`] chunks.
+    pub const fn boundaries() -> Self {
+        Self::boundaries_up_to(DEFAULT_MAX_BLOB_CHUNKS)
+    }
+
+    #[must_use]
+    pub const fn boundaries_up_to(max_chunks: usize) -> Self {
+        Self::Boundaries { max_chunks }
+    }
+
+    /// Rejects empty payloads and inverted ranges.
+    pub const fn validate(&self) -> Result<(), &'static str> {
+        match *self {
+            Self::PowerOfTwoChunks => Ok(()),
+            Self::Fixed(0) => Err("DA blob size must be non-zero"),
+            Self::Uniform { min: 0, .. } => Err("DA blob size range must start above zero"),
+            Self::Uniform { min, max } if min > max => {
+                Err("DA blob size range minimum exceeds its maximum")
+            }
+            Self::Boundaries { max_chunks: 0 } => Err("DA boundary chunk count must be non-zero"),
+            Self::Fixed(_) | Self::Uniform { .. } | Self::Boundaries { .. } => Ok(()),
+        }
+    }
+
+    /// Size in bytes of the `index`-th blob of a channel.
+    pub fn sample(&self, index: u64, rng: &mut impl Rng) -> usize {
+        match *self {
+            Self::PowerOfTwoChunks => {
+                let chunks = *POWER_OF_TWO_CHUNKS
+                    .choose(rng)
+                    .expect("non-empty chunk options");
+                chunks * BLOB_CHUNK_BYTES
+            }
+            Self::Fixed(bytes) => bytes,
+            Self::Uniform { min, max } => rng.gen_range(min..=max),
+            Self::Boundaries { max_chunks } => {
+                let cases = boundary_sizes(max_chunks);
+                cases[(index % cases.len() as u64) as usize]
+            }
+        }
+    }
+
+    /// Random payload for the `index`-th blob of a channel.
+    pub fn payload(&self, index: u64, rng: &mut impl Rng) -> Vec<u8> {
+        let mut data = vec![0u8; self.sample(index, rng)];
+        rng.fill_bytes(&mut data);
+        data
+    }
+}
+
+fn boundary_sizes(max_chunks: usize) -> Vec<usize> {
+    let max_bytes = max_chunks.saturating_mul(BLOB_CHUNK_BYTES);
+    let mut sizes = [
+        1,
+        BLOB_CHUNK_BYTES - 1,
+        BLOB_CHUNK_BYTES + 1,
+        max_bytes - 1,
+        max_bytes,
+    ]
+    .into_iter()
+    .filter(|size| *size <= max_bytes)
+    .collect::<Vec<_>>();
+    sizes.dedup();
+    sizes
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{SeedableRng as _, rngs::StdRng};
+
+    use super::*;
+
+    fn cycle(distribution: BlobSizeDistribution, count: u64) -> Vec<usize> {
+        let mut rng = StdRng::seed_from_u64(3);
+        (0..count)
+            .map(|index| distribution.sample(index, &mut rng))
+            .collect()
+    }
+
+    #[test]
+    fn boundaries_cycle_through_the_chunk_edges() {
+        assert_eq!(
+            cycle(BlobSizeDistribution::boundaries(), 6),
+            vec![1, 30, 32, 247, 248, 1]
+        );
+    }
+
+    #[test]
+    fn boundaries_stay_within_max_chunks() {
+        assert_eq!(
+            cycle(BlobSizeDistribution::boundaries_up_to(1), 4),
+            vec![1, 30, 31, 1]
+        );
+        assert_eq!(
+            cycle(BlobSizeDistribution::boundaries_up_to(2), 5),
+            vec![1, 30, 32, 61, 62]
+        );
+    }
+
+    #[test]
+    fn random_sizes_stay_in_range() {
+        let power_of_two = cycle(BlobSizeDistribution::PowerOfTwoChunks, 50);
+        assert!(
+            power_of_two.iter().all(|size| POWER_OF_TWO_CHUNKS
+                .contains(&(size / BLOB_CHUNK_BYTES))
+                && size % BLOB_CHUNK_BYTES == 0),
+            "{power_of_two:?}"
+        );
+
+        let uniform = cycle(BlobSizeDistribution::uniform(10, 20), 50);
+        assert!(
+            uniform.iter().all(|size| (10..=20).contains(size)),
+            "{uniform:?}"
+        );
+        assert_eq!(cycle(BlobSizeDistribution::fixed(7), 2), vec![7, 7]);
+    }
+
+    #[test]
+    fn empty_or_inverted_sizes_are_invalid() {
+        assert!(BlobSizeDistribution::fixed(0).validate().is_err());
+        assert!(BlobSizeDistribution::uniform(0, 10).validate().is_err());
+        assert!(BlobSizeDistribution::uniform(10, 5).validate().is_err());
+        assert!(
+            BlobSizeDistribution::boundaries_up_to(0)
+                .validate()
+                .is_err()
+        );
+        assert!(BlobSizeDistribution::uniform(5, 5).validate().is_ok());
+        assert!(BlobSizeDistribution::default().validate().is_ok());
+    }
+}
@@ -0,0 +1,407 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use kzgrs_backend::{
+    common::{build_blob_id, share::DaSharesCommitments},
+    encoder::{DaEncoder, DaEncoderParams},
+    global::global_parameters_from_file,
+};
+use nomos_core::da::{BlobId, DaEncoder as _};
+use rand::{Rng, seq::SliceRandom as _};
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, Expectation, RunContext},
+    topology::generation::NodeRole,
+};
+use thiserror::Error;
+
+const MAX_REPORTED_FAILURES: usize = 5;
+
+/// A blob the DA workload published, with the payload it was built from.
+#[derive(Clone, Debug)]
+struct BlobFixture {
+    blob_id: BlobId,
+    payload: Arc<[u8]>,
+}
+
+/// Payloads recorded by the DA workload for [`CommitmentsExpectation`].
+///
+/// Only a uniform random sample of `capacity` published blobs is kept
+/// (reservoir sampling), so memory does not grow with the run length. Clones
+/// share the same set.
+#[derive(Clone, Debug)]
+pub(super) struct BlobFixtures {
+    inner: Arc<Mutex<Reservoir>>,
+}
+
+#[derive(Debug)]
+struct Reservoir {
+    capacity: usize,
+    /// Blobs recorded so far, kept or not.
+    seen: u64,
+    fixtures: Vec<BlobFixture>,
+}
+
+impl BlobFixtures {
+    pub(super) fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(Reservoir {
+                capacity: capacity.get(),
+                seen: 0,
+                fixtures: Vec::with_capacity(capacity.get()),
+            })),
+        }
+    }
+
+    /// Offer a published blob to the sample; it replaces a kept one with
+    /// probability `capacity / seen` once the sample is full.
+    pub(super) fn record(&self, blob_id: BlobId, payload: Vec<u8>, rng: &mut impl Rng) {
+        let mut reservoir = self.inner.lock().expect("blob fixtures lock poisoned");
+        reservoir.seen += 1;
+        let slot = if reservoir.fixtures.len() < reservoir.capacity {
+            reservoir.fixtures.len()
+        } else {
+            match usize::try_from(rng.gen_range(0..reservoir.seen)) {
+                Ok(slot) if slot < reservoir.capacity => slot,
+                _ => return,
+            }
+        };
+        let fixture = BlobFixture {
+            blob_id,
+            payload: payload.into(),
+        };
+        if slot == reservoir.fixtures.len() {
+            reservoir.fixtures.push(fixture);
+        } else {
+            reservoir.fixtures[slot] = fixture;
+        }
+    }
+
+    fn snapshot(&self) -> Vec<BlobFixture> {
+        self.inner
+            .lock()
+            .expect("blob fixtures lock poisoned")
+            .fixtures
+            .clone()
+    }
+}
+
+#[derive(Debug, Error)]
+enum CommitmentsError {
+    #[error("no DA blob payloads were recorded; did the workload publish any blob?")]
+    NoFixtures,
+    #[error("no validator running from the start answered; cannot check DA commitments")]
+    NoReachableValidators,
+    #[error("failed to load KZG global parameters from {path}: {message}")]
+    GlobalParameters { path: String, message: String },
+    #[error("{count} of {blobs} DA blob(s) failed commitment verification:\n{details}")]
+    Failures {
+        count: usize,
+        blobs: usize,
+        details: String,
+    },
+}
+
+/// Recomputes the commitments of blobs published by the DA workload from
+/// their payloads and checks them against what nodes serve.
+///
+/// For a random sample of up to `blobs` blobs, the payload is encoded with the
+/// topology's column count and KZG parameters. The blob id derived from the
+/// recomputed row commitments must match the id the executor returned on
+/// publish, and every queried node that stores commitments for the blob must
+/// serve exactly the recomputed ones. Up to `nodes` random validators are
+/// queried per blob and at least one of them must hold the commitments; only
+/// validators running from the start that still answer are queried, so nodes
+/// stopped by chaos workloads or joining late are left out.
+#[derive(Debug)]
+pub struct CommitmentsExpectation {
+    blobs: NonZeroUsize,
+    nodes: NonZeroUsize,
+    fixtures: BlobFixtures,
+}
+
+impl CommitmentsExpectation {
+    pub(super) const fn new(
+        blobs: NonZeroUsize,
+        nodes: NonZeroUsize,
+        fixtures: BlobFixtures,
+    ) -> Self {
+        Self {
+            blobs,
+            nodes,
+            fixtures,
+        }
+    }
+}
+
+#[async_trait]
+impl Expectation for CommitmentsExpectation {
+    fn name(&self) -> &'static str {
+        "da_commitments"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let mut rng = ctx.rng(self.name());
+        let fixtures = self.fixtures.snapshot();
+        if fixtures.is_empty() {
+            return Err(CommitmentsError::NoFixtures.into());
+        }
+
+        let clients = reachable_validators(ctx).await;
+        if clients.is_empty() {
+            return Err(CommitmentsError::NoReachableValidators.into());
+        }
+        let encoder = Arc::new(load_encoder(ctx).await?);
+
+        let mut failures = Vec::new();
+        let mut node_checks = 0u64;
+        for fixture in &fixtures {
+            let nodes = clients
+                .choose_multiple(&mut rng, self.nodes.get())
+                .collect::<Vec<_>>();
+            let outcome = verify_blob(Arc::clone(&encoder), fixture, &nodes).await;
+            node_checks += outcome.checked;
+            failures.extend(outcome.failures);
+        }
+
+        ctx.report()
+            .increment(self.name(), "blobs_verified", fixtures.len() as u64);
+        ctx.report()
+            .increment(self.name(), "node_checks", node_checks);
+        if failures.is_empty() {
+            tracing::info!(
+                blobs = fixtures.len(),
+                node_checks,
+                "DA commitments expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        ctx.report()
+            .increment(self.name(), "failures", failures.len() as u64);
+        let count = failures.len();
+        let mut details = failures
+            .into_iter()
+            .take(MAX_REPORTED_FAILURES)
+            .map(|failure| format!("- {failure}"))
+            .collect::<Vec<_>>();
+        if count > MAX_REPORTED_FAILURES {
+            details.push(format!("- ... and {} more", count - MAX_REPORTED_FAILURES));
+        }
+        Err(CommitmentsError::Failures {
+            count,
+            blobs: fixtures.len(),
+            details: details.join("\n"),
+        }
+        .into())
+    }
+}
+
+/// Validators running from the start that answer a `consensus_info`
+/// request.
+async fn reachable_validators(ctx: &RunContext) -> Vec<(String, &ApiClient)> {
+    let config = ctx.descriptors().config();
+    let validators = ctx
+        .node_clients()
+        .targeted_clients()
+        .filter(|(target, _)| target.role() == NodeRole::Validator && !config.joins_late(*target))
+        .collect::<Vec<_>>();
+    let answers = join_all(validators.iter().map(|(_, client)| client.consensus_info())).await;
+    validators
+        .into_iter()
+        .zip(answers)
+        .filter_map(|((target, client), answer)| match answer {
+            Ok(_) => Some((target.to_string(), client)),
+            Err(err) => {
+                tracing::debug!(%target, %err, "skipping unreachable validator for DA commitments");
+                None
+            }
+        })
+        .collect()
+}
+
+/// An encoder matching the one executors use for the run's topology.
+async fn load_encoder(ctx: &RunContext) -> Result<DaEncoder, CommitmentsError> {
+    let da_params = &ctx.descriptors().config().da_params;
+    let path = da_params.global_params_path.clone();
+    let columns = usize::from(da_params.num_subnets);
+    tokio::task::spawn_blocking(move || encoder_from_file(path, columns))
+        .await
+        .expect("global parameters loading panicked")
+}
+
+fn encoder_from_file(path: String, columns: usize) -> Result<DaEncoder, CommitmentsError> {
+    global_parameters_from_file(&path)
+        .map(|parameters| DaEncoder::new(DaEncoderParams::new(columns, false, parameters)))
+        .map_err(|err| CommitmentsError::GlobalParameters {
+            path,
+            message: err.to_string(),
+        })
+}
+
+#[derive(Default)]
+struct BlobOutcome {
+    checked: u64,
+    failures: Vec<String>,
+}
+
+async fn verify_blob(
+    encoder: Arc<DaEncoder>,
+    fixture: &BlobFixture,
+    nodes: &[&(String, &ApiClient)],
+) -> BlobOutcome {
+    let blob_id = fixture.blob_id;
+    let payload = Arc::clone(&fixture.payload);
+    let encoded = tokio::task::spawn_blocking(move || encoder.encode(&payload))
+        .await
+        .expect("blob encoding panicked");
+    let expected = match encoded {
+        Ok(encoded) => encoded.row_commitments,
+        Err(err) => {
+            return BlobOutcome {
+                checked: 0,
+                failures: vec![format!(
+                    "blob {}: payload could not be encoded: {err}",
+                    hex::encode(blob_id)
+                )],
+            };
+        }
+    };
+
+    let mut outcome = BlobOutcome::default();
+    let derived = build_blob_id(&expected);
+    if derived != blob_id {
+        outcome.failures.push(format!(
+            "blob {}: publish response does not match id {} derived from the payload",
+            hex::encode(blob_id),
+            hex::encode(derived)
+        ));
+    }
+
+    let responses = join_all(
+        nodes
+            .iter()
+            .map(|(label, client)| async move { (label, client.da_commitments(blob_id).await) }),
+    )
+    .await;
+    let mut holders = 0;
+    for (label, response) in responses {
+        outcome.checked += 1;
+        match response {
+            Ok(Some(DaSharesCommitments {
+                rows_commitments, ..
+            })) => {
+                holders += 1;
+                if rows_commitments != expected {
+                    outcome.failures.push(format!(
+                        "blob {}: {label} serves commitments that differ from the payload's",
+                        hex::encode(blob_id)
+                    ));
+                }
+            }
+            Ok(None) => {}
+            Err(err) => outcome.failures.push(format!(
+                "blob {}: {label} commitments request failed: {err}",
+                hex::encode(blob_id)
+            )),
+        }
+    }
+    if holders == 0 && !nodes.is_empty() {
+        outcome.failures.push(format!(
+            "blob {}: none of the {} queried node(s) holds its commitments",
+            hex::encode(blob_id),
+            nodes.len()
+        ));
+    }
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use rand::{SeedableRng as _, rngs::StdRng};
+    use testing_framework_core::GLOBAL_PARAMS_PATH;
+
+    use super::*;
+
+    fn capacity(n: usize) -> NonZeroUsize {
+        NonZeroUsize::new(n).expect("non-zero")
+    }
+
+    #[test]
+    fn keeps_a_bounded_sample_of_recorded_blobs() {
+        let fixtures = BlobFixtures::new(capacity(4));
+        let mut rng = StdRng::seed_from_u64(3);
+        for n in 0..100u8 {
+            fixtures.record([n; 32], vec![n], &mut rng);
+        }
+
+        let kept = fixtures.snapshot();
+        assert_eq!(kept.len(), 4);
+        let ids = kept
+            .iter()
+            .map(|fixture| fixture.blob_id)
+            .collect::<HashSet<_>>();
+        assert_eq!(ids.len(), 4, "kept blobs are distinct");
+        for fixture in &kept {
+            assert_eq!(fixture.payload.as_ref(), &fixture.blob_id[..1]);
+        }
+        assert_eq!(
+            fixtures
+                .inner
+                .lock()
+                .expect("blob fixtures lock poisoned")
+                .seen,
+            100
+        );
+    }
+
+    #[test]
+    fn later_blobs_replace_kept_ones() {
+        let fixtures = BlobFixtures::new(capacity(2));
+        let mut rng = StdRng::seed_from_u64(11);
+        for n in 0..50u8 {
+            fixtures.record([n; 32], Vec::new(), &mut rng);
+        }
+
+        let kept = fixtures.snapshot();
+        assert!(
+            kept.iter().any(|fixture| fixture.blob_id[0] >= 2),
+            "the sample is not stuck on the first blobs"
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs the KZG global parameters at NOMOS_KZGRS_PARAMS_PATH"]
+    async fn blob_id_is_derived_from_the_payload() {
+        let encoder =
+            Arc::new(encoder_from_file(GLOBAL_PARAMS_PATH.to_string(), 2).expect("KZG parameters"));
+        let payload: Arc<[u8]> = vec![7u8; 1024].into();
+        let encoded = encoder.encode(&payload).expect("payload encodes");
+        let blob_id = build_blob_id(&encoded.row_commitments);
+
+        let matching = BlobFixture {
+            blob_id,
+            payload: Arc::clone(&payload),
+        };
+        let outcome = verify_blob(Arc::clone(&encoder), &matching, &[]).await;
+        assert!(outcome.failures.is_empty(), "{:?}", outcome.failures);
+
+        let mismatched = BlobFixture {
+            blob_id: [0; 32],
+            payload,
+        };
+        let outcome = verify_blob(encoder, &mismatched, &[]).await;
+        assert_eq!(outcome.failures.len(), 1, "{:?}", outcome.failures);
+        assert!(outcome.failures[0].contains(&hex::encode(blob_id)));
+    }
+}
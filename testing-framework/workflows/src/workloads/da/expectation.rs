@@ -169,7 +169,7 @@ impl Expectation for DaWorkloadExpectation {
         Ok(())
     }
 
-    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
         let state = self
             .capture_state
             .as_ref()
@@ -186,6 +186,11 @@ impl Expectation for DaWorkloadExpectation {
         };
         let required_inscriptions = minimum_required(planned_total, MIN_INCLUSION_RATIO);
         let observed_inscriptions = planned_total.saturating_sub(missing_inscriptions.len());
+        ctx.report().set_counter(
+            "channel_workload",
+            "inscriptions_included",
+            observed_inscriptions as u64,
+        );
         if observed_inscriptions < required_inscriptions {
             tracing::warn!(
                 planned = planned_total,
@@ -216,6 +221,9 @@ impl Expectation for DaWorkloadExpectation {
                 .collect::<HashSet<_>>()
         };
 
+        ctx.report()
+            .set_counter("channel_workload", "blobs_included", observed_total_blobs);
+
         let observed_blocks = state.run_blocks.load(Ordering::Relaxed).max(1);
         let expected_total_blobs = self
             .blob_rate_per_block
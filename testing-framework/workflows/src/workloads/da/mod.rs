@@ -1,4 +1,52 @@
+use nomos_core::{
+    da::BlobId,
+    header::HeaderId,
+    mantle::{AuthenticatedMantleTx as _, ops::Op},
+    sdp::SessionNumber,
+};
+use testing_framework_core::scenario::BlockRecord;
+
+mod affinity;
+mod blob_size;
+mod commitments;
 mod expectation;
+mod pruning;
+mod sampling;
+mod stress;
 mod workload;
 
+pub use affinity::ExecutorAffinity;
+pub use blob_size::{BLOB_CHUNK_BYTES, BlobSizeDistribution, DEFAULT_MAX_BLOB_CHUNKS};
+pub use commitments::CommitmentsExpectation;
+pub use pruning::PruningExpectation;
+pub use sampling::{SamplingExpectation, SamplingWorkload};
+pub use stress::DispersalStressWorkload;
 pub use workload::Workload;
+
+/// A blob published in a block seen on the block feed, stamped with when it
+/// landed in whatever unit the caller tracks (block count, wall clock).
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct LandedBlob<T> {
+    pub(crate) blob_id: BlobId,
+    pub(crate) session: SessionNumber,
+    pub(crate) block_id: HeaderId,
+    pub(crate) landed_at: T,
+}
+
+/// Collects the blobs published by the channel-blob operations of a block.
+pub(crate) fn landed_blobs<T: Copy>(record: &BlockRecord, landed_at: T) -> Vec<LandedBlob<T>> {
+    record
+        .block
+        .transactions()
+        .flat_map(|tx| tx.mantle_tx().ops.iter())
+        .filter_map(|op| match op {
+            Op::ChannelBlob(blob) => Some(LandedBlob {
+                blob_id: blob.blob,
+                session: blob.session,
+                block_id: record.header,
+                landed_at,
+            }),
+            _ => None,
+        })
+        .collect()
+}
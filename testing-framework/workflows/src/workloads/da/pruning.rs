@@ -0,0 +1,316 @@
+use std::{
+    num::NonZeroUsize,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use testing_framework_core::{
+    nodes::HistoricSamplingRequest,
+    scenario::{DynError, Expectation, NodeFeature, NodeTarget, RunContext, RunMetrics},
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::{
+    sync::broadcast,
+    time::{Instant, sleep_until},
+};
+
+use super::{LandedBlob, landed_blobs};
+
+const DEFAULT_SAMPLE_SIZE: usize = 4;
+/// Slack on top of validity plus one pruning pass before a blob must be gone.
+const EXPIRY_GRACE: Duration = Duration::from_secs(5);
+const MAX_REPORTED_FAILURES: usize = 5;
+
+#[derive(Debug, Error)]
+enum PruningExpectationError {
+    #[error("DA pruning expectation was not initialised with the topology")]
+    NotInitialised,
+    #[error("no DA blobs landed during the run; nothing to check for pruning")]
+    NoBlobs,
+    #[error(
+        "{sampleable} blob check(s) still succeeded and {unqueried} could not be queried after expiry:\n{details}"
+    )]
+    NotPruned {
+        sampleable: usize,
+        unqueried: usize,
+        details: String,
+    },
+}
+
+/// A blob check that did not show the blob as pruned. A failed query proves
+/// nothing about pruning, so it fails the check too.
+#[derive(Debug)]
+enum PruneFailure {
+    StillSampleable(String),
+    QueryFailed(String),
+}
+
+impl PruneFailure {
+    fn line(&self) -> &str {
+        match self {
+            Self::StillSampleable(line) | Self::QueryFailed(line) => line,
+        }
+    }
+}
+
+/// Checks DA garbage collection: blobs that landed during the run must stop
+/// being sampleable once `blobs_validity_duration` (plus one
+/// `old_blobs_check_interval` pass) has elapsed.
+///
+/// At evaluation time the expectation waits, up to `max_wait`, for the oldest
+/// observed blobs to expire and then queries `da_historic_sampling` on every
+/// validator, expecting it to fail. Node storage is not exposed over the API,
+/// so sampling is the observable signal for pruning.
+#[derive(Debug)]
+pub struct PruningExpectation {
+    sample_size: NonZeroUsize,
+    max_wait: Option<Duration>,
+    expiry: Option<Duration>,
+    observed: Arc<Mutex<Vec<LandedBlob<Instant>>>>,
+    capturing: bool,
+}
+
+impl Default for PruningExpectation {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::new(DEFAULT_SAMPLE_SIZE).expect("non-zero"))
+    }
+}
+
+impl PruningExpectation {
+    /// Checks up to `sample_size` expired blobs.
+    #[must_use]
+    pub fn new(sample_size: NonZeroUsize) -> Self {
+        Self {
+            sample_size,
+            max_wait: None,
+            expiry: None,
+            observed: Arc::new(Mutex::new(Vec::new())),
+            capturing: false,
+        }
+    }
+
+    /// Longest time evaluation waits for a blob to expire. Defaults to the
+    /// expiry window itself.
+    #[must_use]
+    pub const fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    fn expired_sample(&self, expiry: Duration) -> Vec<LandedBlob<Instant>> {
+        let now = Instant::now();
+        let observed = self.observed.lock().expect("pruning state lock poisoned");
+        observed
+            .iter()
+            .filter(|blob| blob.landed_at + expiry <= now)
+            .take(self.sample_size.get())
+            .copied()
+            .collect()
+    }
+
+    fn oldest_landing(&self) -> Option<Instant> {
+        self.observed
+            .lock()
+            .expect("pruning state lock poisoned")
+            .iter()
+            .map(|blob| blob.landed_at)
+            .min()
+    }
+}
+
+#[async_trait]
+impl Expectation for PruningExpectation {
+    fn name(&self) -> &'static str {
+        "da_pruning"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da", "slow"]
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        &[NodeFeature::TestingHttp]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        let da = &descriptors.config().da_params;
+        self.expiry = Some(da.blobs_validity_duration + da.old_blobs_check_interval + EXPIRY_GRACE);
+        Ok(())
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.capturing {
+            return Ok(());
+        }
+        self.capturing = true;
+
+        let mut receiver = ctx.block_feed().subscribe();
+        let observed = Arc::clone(&self.observed);
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => {
+                        let landed = landed_blobs(&record, Instant::now());
+                        if !landed.is_empty() {
+                            observed
+                                .lock()
+                                .expect("pruning state lock poisoned")
+                                .extend(landed);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "DA pruning capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let expiry = self.expiry.ok_or(PruningExpectationError::NotInitialised)?;
+        let oldest = self
+            .oldest_landing()
+            .ok_or(PruningExpectationError::NoBlobs)?;
+
+        let expires_at = oldest + expiry;
+        let deadline = Instant::now() + self.max_wait.unwrap_or(expiry);
+        if expires_at > Instant::now() {
+            tracing::info!(
+                wait_secs = expires_at
+                    .saturating_duration_since(Instant::now())
+                    .as_secs(),
+                "waiting for DA blobs to expire"
+            );
+            sleep_until(expires_at.min(deadline)).await;
+        }
+
+        let sample = self.expired_sample(expiry);
+        if sample.is_empty() {
+            return Err(format!(
+                "no DA blob expired within the wait budget (expiry window {expiry:?})"
+            )
+            .into());
+        }
+
+        let validators = ctx.node_clients().validator_clients();
+        let checks = validators.iter().enumerate().flat_map(|(index, client)| {
+            sample.iter().map(move |blob| async move {
+                let request = HistoricSamplingRequest {
+                    block_id: blob.block_id,
+                    blob_ids: vec![(blob.blob_id, blob.session)],
+                };
+                match client.da_historic_sampling(&request).await {
+                    Ok(false) => None,
+                    Ok(true) => Some(PruneFailure::StillSampleable(format!(
+                        "- {}: blob in block {:?} is still sampleable",
+                        NodeTarget::Validator(index),
+                        blob.block_id
+                    ))),
+                    Err(err) => Some(PruneFailure::QueryFailed(format!(
+                        "- {}: could not query blob in block {:?}: {err}",
+                        NodeTarget::Validator(index),
+                        blob.block_id
+                    ))),
+                }
+            })
+        });
+        let failures = join_all(checks)
+            .await
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+
+        ctx.report().increment(
+            self.name(),
+            "expired_blobs_checked",
+            (validators.len() * sample.len()) as u64,
+        );
+        pruning_verdict(&failures)?;
+        tracing::info!(blobs = sample.len(), "DA pruning expectation satisfied");
+        Ok(())
+    }
+}
+
+fn pruning_verdict(failures: &[PruneFailure]) -> Result<(), PruningExpectationError> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    let sampleable = failures
+        .iter()
+        .filter(|failure| matches!(failure, PruneFailure::StillSampleable(_)))
+        .count();
+    let mut details = failures
+        .iter()
+        .take(MAX_REPORTED_FAILURES)
+        .map(|failure| failure.line().to_owned())
+        .collect::<Vec<_>>();
+    if failures.len() > MAX_REPORTED_FAILURES {
+        details.push(format!(
+            "- ... and {} more",
+            failures.len() - MAX_REPORTED_FAILURES
+        ));
+    }
+    Err(PruningExpectationError::NotPruned {
+        sampleable,
+        unqueried: failures.len() - sampleable,
+        details: details.join("\n"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_queries_do_not_count_as_pruned() {
+        let failures = vec![PruneFailure::QueryFailed(
+            "- validator-0: could not query".to_owned(),
+        )];
+
+        assert!(matches!(
+            pruning_verdict(&failures),
+            Err(PruningExpectationError::NotPruned {
+                sampleable: 0,
+                unqueried: 1,
+                ..
+            })
+        ));
+        assert!(pruning_verdict(&[]).is_ok());
+    }
+
+    #[test]
+    fn verdict_counts_both_failures_and_truncates_details() {
+        let failures = (0..MAX_REPORTED_FAILURES + 2)
+            .map(|index| {
+                let line = format!("- validator-{index}");
+                if index % 2 == 0 {
+                    PruneFailure::StillSampleable(line)
+                } else {
+                    PruneFailure::QueryFailed(line)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let Err(PruningExpectationError::NotPruned {
+            sampleable,
+            unqueried,
+            details,
+        }) = pruning_verdict(&failures)
+        else {
+            panic!("expected the check to fail");
+        };
+        assert_eq!((sampleable, unqueried), (4, 3));
+        assert_eq!(details.lines().count(), MAX_REPORTED_FAILURES + 1);
+        assert!(details.ends_with("- ... and 2 more"), "{details}");
+    }
+}
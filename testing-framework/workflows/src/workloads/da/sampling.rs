@@ -0,0 +1,329 @@
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use futures::future::join_all;
+use nomos_core::{da::BlobId, header::HeaderId, sdp::SessionNumber};
+use rand::seq::SliceRandom as _;
+use testing_framework_core::{
+    nodes::{HistoricSamplingBatch, HistoricSamplingRequest},
+    scenario::{
+        DynError, Expectation, NodeFeature, NodeTarget, RunContext, Workload as ScenarioWorkload,
+        sample_published_blobs,
+    },
+};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+use super::{LandedBlob, landed_blobs};
+
+const DEFAULT_SAMPLE_SIZE: usize = 4;
+/// Blocks to wait after a blob lands before sampling it, so it is no longer
+/// at the tip.
+const CONFIRMATION_DEPTH: u64 = 2;
+const MAX_REPORTED_FAILURES: usize = 5;
+
+#[derive(Clone, Debug)]
+struct SamplingFailure {
+    node: String,
+    block_id: HeaderId,
+    blobs: usize,
+    reason: String,
+}
+
+#[derive(Debug, Default)]
+struct SamplingState {
+    checks: AtomicU64,
+    failures: Mutex<Vec<SamplingFailure>>,
+}
+
+/// Samples blobs that landed in blocks through `da_historic_sampling` on
+/// every validator.
+///
+/// Each block, a random subset of the blobs that are at least
+/// `CONFIRMATION_DEPTH` blocks deep is checked. Results are recorded for the
+/// attached [`SamplingExpectation`], which fails the run if any node could not
+/// sample a blob. Requests are batched per node as configured by
+/// [`HistoricSamplingBatch`].
+#[derive(Clone)]
+pub struct SamplingWorkload {
+    sample_size: NonZeroUsize,
+    batch: HistoricSamplingBatch,
+    final_sweep: Option<NonZeroUsize>,
+    state: Arc<SamplingState>,
+}
+
+impl Default for SamplingWorkload {
+    fn default() -> Self {
+        Self::new(NonZeroUsize::new(DEFAULT_SAMPLE_SIZE).expect("non-zero"))
+    }
+}
+
+impl SamplingWorkload {
+    /// Creates a workload sampling up to `sample_size` blobs per block.
+    #[must_use]
+    pub fn new(sample_size: NonZeroUsize) -> Self {
+        Self {
+            sample_size,
+            batch: HistoricSamplingBatch::default(),
+            final_sweep: None,
+            state: Arc::new(SamplingState::default()),
+        }
+    }
+
+    #[must_use]
+    /// Chunking, concurrency and retry policy for the sampling requests.
+    pub const fn with_batch(mut self, batch: HistoricSamplingBatch) -> Self {
+        self.batch = batch;
+        self
+    }
+
+    #[must_use]
+    /// Before the expectation is evaluated, sample up to `blobs` random blobs
+    /// of everything published during the run on every node, so blobs from
+    /// early blocks are checked again once the chain has moved on.
+    pub const fn with_final_sweep(mut self, blobs: NonZeroUsize) -> Self {
+        self.final_sweep = Some(blobs);
+        self
+    }
+
+    async fn verify(&self, ctx: &RunContext, sample: &[LandedBlob<u64>]) {
+        let mut by_block: HashMap<HeaderId, Vec<(BlobId, SessionNumber)>> = HashMap::new();
+        for blob in sample {
+            by_block
+                .entry(blob.block_id)
+                .or_default()
+                .push((blob.blob_id, blob.session));
+        }
+
+        let requests = by_block
+            .into_iter()
+            .map(|(block_id, blob_ids)| HistoricSamplingRequest { block_id, blob_ids })
+            .collect::<Vec<_>>();
+
+        let validators = ctx.node_clients().validator_clients();
+        let results = join_all(
+            validators
+                .iter()
+                .map(|client| client.da_historic_sampling_batch(&requests, &self.batch)),
+        )
+        .await;
+        let failures = results
+            .into_iter()
+            .enumerate()
+            .flat_map(|(index, chunks)| {
+                let node = NodeTarget::Validator(index).to_string();
+                chunks.into_iter().filter_map(move |chunk| {
+                    chunk.failure.map(|reason| SamplingFailure {
+                        node: node.clone(),
+                        block_id: chunk.block_id,
+                        blobs: chunk.blob_ids.len(),
+                        reason,
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let checked = (validators.len() * sample.len()) as u64;
+        self.state.checks.fetch_add(checked, Ordering::Relaxed);
+        ctx.report()
+            .increment(self.name(), "samples_checked", checked);
+
+        if failures.is_empty() {
+            tracing::debug!(blobs = sample.len(), "DA sampling round succeeded");
+            return;
+        }
+
+        ctx.report()
+            .increment(self.name(), "sampling_failures", failures.len() as u64);
+        for failure in &failures {
+            tracing::warn!(
+                node = %failure.node,
+                block = ?failure.block_id,
+                reason = %failure.reason,
+                "DA historic sampling failed"
+            );
+        }
+        self.state
+            .failures
+            .lock()
+            .expect("sampling failures lock poisoned")
+            .extend(failures);
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for SamplingWorkload {
+    fn name(&self) -> &'static str {
+        "da_sampling_workload"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        &[NodeFeature::TestingHttp]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(SamplingExpectation {
+            final_sweep: self.final_sweep,
+            batch: self.batch,
+            state: Arc::clone(&self.state),
+        })]
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        if ctx.node_clients().validator_clients().is_empty() {
+            return Err("da sampling workload requires at least one validator".into());
+        }
+
+        tracing::info!(
+            sample_size = self.sample_size.get(),
+            confirmation_depth = CONFIRMATION_DEPTH,
+            "starting DA sampling workload"
+        );
+
+        let mut receiver = ctx.block_feed().subscribe();
+        let mut rng = ctx.rng(self.name());
+        let mut pending = Vec::new();
+        let mut blocks = 0u64;
+        loop {
+            let record = match receiver.recv().await {
+                Ok(record) => record,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "DA sampling workload: receiver lagged");
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    return Err("block feed closed while waiting for DA blobs".into());
+                }
+            };
+
+            blocks += 1;
+            pending.extend(landed_blobs(&record, blocks));
+
+            let (ready, waiting): (Vec<_>, Vec<_>) = pending
+                .into_iter()
+                .partition(|blob: &LandedBlob<u64>| blob.landed_at + CONFIRMATION_DEPTH <= blocks);
+            pending = waiting;
+            if ready.is_empty() {
+                continue;
+            }
+
+            let sample = ready
+                .choose_multiple(&mut rng, self.sample_size.get())
+                .copied()
+                .collect::<Vec<_>>();
+            self.verify(ctx, &sample).await;
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+enum SamplingExpectationError {
+    #[error("no DA blobs were sampled; did any blob land during the run?")]
+    NoSamples,
+    #[error("historic sampling failed {count} time(s):\n{details}")]
+    Failures { count: usize, details: String },
+}
+
+/// Fails the run when any validator could not sample a blob checked by
+/// [`SamplingWorkload`], including the optional final sweep over the run's
+/// published blobs.
+#[derive(Debug)]
+pub struct SamplingExpectation {
+    final_sweep: Option<NonZeroUsize>,
+    batch: HistoricSamplingBatch,
+    state: Arc<SamplingState>,
+}
+
+impl SamplingExpectation {
+    async fn run_final_sweep(&self, ctx: &RunContext, blobs: NonZeroUsize) {
+        let mut rng = ctx.rng("da_sampling_sweep");
+        let sweep = sample_published_blobs(ctx, blobs.get(), &mut rng, &self.batch).await;
+        tracing::info!(
+            blobs = sweep.blobs,
+            checks = sweep.checks,
+            failures = sweep.failures.len(),
+            "DA sampling final sweep finished"
+        );
+
+        self.state.checks.fetch_add(sweep.checks, Ordering::Relaxed);
+        ctx.report()
+            .increment(self.name(), "sweep_samples_checked", sweep.checks);
+        if sweep.is_ok() {
+            return;
+        }
+        ctx.report()
+            .increment(self.name(), "sweep_failures", sweep.failures.len() as u64);
+        self.state
+            .failures
+            .lock()
+            .expect("sampling failures lock poisoned")
+            .extend(sweep.failures.into_iter().map(|failure| SamplingFailure {
+                node: failure.node,
+                block_id: failure.block_id,
+                blobs: failure.blobs,
+                reason: format!("final sweep: {}", failure.reason),
+            }));
+    }
+}
+
+#[async_trait]
+impl Expectation for SamplingExpectation {
+    fn name(&self) -> &'static str {
+        "da_sampling"
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if let Some(blobs) = self.final_sweep {
+            self.run_final_sweep(ctx, blobs).await;
+        }
+
+        let checks = self.state.checks.load(Ordering::Relaxed);
+        if checks == 0 {
+            return Err(SamplingExpectationError::NoSamples.into());
+        }
+
+        let failures = self
+            .state
+            .failures
+            .lock()
+            .expect("sampling failures lock poisoned")
+            .clone();
+        if failures.is_empty() {
+            tracing::info!(checks, "DA sampling expectation satisfied");
+            return Ok(());
+        }
+
+        let mut details = failures
+            .iter()
+            .take(MAX_REPORTED_FAILURES)
+            .map(|failure| {
+                format!(
+                    "- {}: block {:?} ({} blob(s)): {}",
+                    failure.node, failure.block_id, failure.blobs, failure.reason
+                )
+            })
+            .collect::<Vec<_>>();
+        if failures.len() > MAX_REPORTED_FAILURES {
+            details.push(format!(
+                "- ... and {} more",
+                failures.len() - MAX_REPORTED_FAILURES
+            ));
+        }
+        Err(SamplingExpectationError::Failures {
+            count: failures.len(),
+            details: details.join("\n"),
+        }
+        .into())
+    }
+}
@@ -0,0 +1,362 @@
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use executor_http_client::ExecutorHttpClient;
+use futures::future::try_join_all;
+use nomos_core::mantle::ops::channel::{ChannelId, MsgId};
+use rand::Rng;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{
+        DynError, Expectation, NodeFeature, NodeTarget, RunContext, RunMetrics,
+        Workload as ScenarioWorkload,
+    },
+    topology::generation::GeneratedTopology,
+};
+use thiserror::Error;
+use tokio::{
+    sync::Mutex,
+    time::{Instant, Interval, MissedTickBehavior, interval, sleep},
+};
+
+use super::{
+    blob_size::BlobSizeDistribution,
+    workload::{test_signer, wait_for_inscription},
+};
+use crate::{util::tx, workloads::util::submit_transaction_via_cluster};
+
+const NAME: &str = "dispersal_stress";
+const DEFAULT_CONCURRENCY: usize = 4;
+const DEFAULT_MAX_FAILURE_RATIO: f64 = 0.5;
+/// Pause after a failed publish so an unavailable executor is not hammered.
+const FAILURE_BACKOFF: Duration = Duration::from_millis(500);
+
+type RateLimiter = Arc<Mutex<Interval>>;
+
+/// Publishes blobs to every executor with several requests in flight at
+/// once, to find executor dispersal bottlenecks.
+///
+/// Each executor gets `concurrency` workers, each writing to its own channel:
+/// a worker inscribes its channel once, then publishes blobs back to back,
+/// optionally throttled by a per-executor rate limit. Workers do not wait for
+/// blobs to land and all name the inscription as parent, so only the first
+/// blob of a channel can be included; the workload measures dispersal, not
+/// inclusion. Publish latencies and failures are recorded per executor in
+/// [`RunContext::dispersal_summary`] and the run report.
+#[derive(Clone)]
+pub struct DispersalStressWorkload {
+    concurrency: NonZeroUsize,
+    rate_per_executor: Option<NonZeroU64>,
+    blob_sizes: BlobSizeDistribution,
+    max_failure_ratio: f64,
+}
+
+impl Default for DispersalStressWorkload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DispersalStressWorkload {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            concurrency: NonZeroUsize::new(DEFAULT_CONCURRENCY).expect("non-zero"),
+            rate_per_executor: None,
+            blob_sizes: BlobSizeDistribution::PowerOfTwoChunks,
+            max_failure_ratio: DEFAULT_MAX_FAILURE_RATIO,
+        }
+    }
+
+    #[must_use]
+    /// Publishes kept in flight per executor. Defaults to 4.
+    pub const fn with_concurrency(mut self, concurrency: NonZeroUsize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    #[must_use]
+    /// Cap each executor at `rate` publishes per second; unlimited by
+    /// default.
+    pub const fn with_rate_per_executor(mut self, rate: NonZeroU64) -> Self {
+        self.rate_per_executor = Some(rate);
+        self
+    }
+
+    #[must_use]
+    /// Draw blob payload sizes from `blob_sizes`.
+    pub const fn with_blob_sizes(mut self, blob_sizes: BlobSizeDistribution) -> Self {
+        self.blob_sizes = blob_sizes;
+        self
+    }
+
+    #[must_use]
+    /// Fail the run when an executor fails more than `ratio` of its publish
+    /// attempts. Defaults to 0.5 (panics outside `[0, 1]`).
+    pub fn with_max_failure_ratio(mut self, ratio: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&ratio),
+            "dispersal stress failure ratio must be within [0, 1]"
+        );
+        self.max_failure_ratio = ratio;
+        self
+    }
+
+    fn rate_limiter(&self) -> Option<RateLimiter> {
+        let rate = self.rate_per_executor?;
+        let mut ticks = interval(Duration::from_secs(1).div_f64(rate.get() as f64));
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Some(Arc::new(Mutex::new(ticks)))
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for DispersalStressWorkload {
+    fn name(&self) -> &'static str {
+        NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        &[NodeFeature::ExecutorDispersal]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(DispersalStressExpectation {
+            max_failure_ratio: self.max_failure_ratio,
+        })]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if descriptors.executors().is_empty() {
+            return Err(DispersalStressError::MissingExecutors.into());
+        }
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let executors = ctx.node_clients().executor_clients();
+        if executors.is_empty() {
+            return Err(DispersalStressError::MissingExecutors.into());
+        }
+        tracing::info!(
+            executors = executors.len(),
+            concurrency = self.concurrency.get(),
+            rate_per_executor = self.rate_per_executor.map(NonZeroU64::get),
+            blob_sizes = ?self.blob_sizes,
+            "dispersal stress workload starting"
+        );
+
+        let workers = executors
+            .iter()
+            .enumerate()
+            .flat_map(|(executor_index, executor)| {
+                let limiter = self.rate_limiter();
+                (0..self.concurrency.get()).map(move |worker| Worker {
+                    label: NodeTarget::Executor(executor_index).to_string(),
+                    executor,
+                    channel_id: stress_channel_id(executor_index, worker),
+                    limiter: limiter.clone(),
+                })
+            })
+            .map(|worker| {
+                let rng = ctx.rng(&format!("{NAME}/{}/{:?}", worker.label, worker.channel_id));
+                worker.run(ctx, self.blob_sizes, rng)
+            });
+
+        // Workers publish until the runner stops the workload.
+        try_join_all(workers).await?;
+        Ok(())
+    }
+}
+
+struct Worker<'a> {
+    label: String,
+    executor: &'a ApiClient,
+    channel_id: ChannelId,
+    limiter: Option<RateLimiter>,
+}
+
+impl Worker<'_> {
+    async fn run(
+        self,
+        ctx: &RunContext,
+        blob_sizes: BlobSizeDistribution,
+        mut rng: impl Rng + Send,
+    ) -> Result<(), DynError> {
+        let parent = self.inscribe(ctx).await?;
+        let client = ExecutorHttpClient::new(None);
+        let signer = test_signer();
+
+        for index in 0.. {
+            if let Some(limiter) = &self.limiter {
+                limiter.lock().await.tick().await;
+            }
+            let payload = blob_sizes.payload(index, &mut rng);
+            let bytes = payload.len() as u64;
+            let started = Instant::now();
+            match client
+                .publish_blob(
+                    self.executor.base_url().clone(),
+                    self.channel_id,
+                    parent,
+                    signer,
+                    payload,
+                )
+                .await
+            {
+                Ok(_) => {
+                    ctx.dispersal()
+                        .record_publish(&self.label, bytes, started.elapsed());
+                    ctx.report().increment(NAME, "blobs_published", 1);
+                    ctx.report().increment(NAME, "blob_bytes_published", bytes);
+                }
+                Err(err) => {
+                    tracing::debug!(executor = %self.label, %err, "dispersal stress publish failed");
+                    ctx.dispersal().record_failure(&self.label);
+                    ctx.report().increment(NAME, "publish_failures", 1);
+                    sleep(FAILURE_BACKOFF).await;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn inscribe(&self, ctx: &RunContext) -> Result<MsgId, DynError> {
+        let mut receiver = ctx.block_feed().subscribe();
+        let inscription = Arc::new(tx::create_inscription_transaction_with_id(self.channel_id));
+        submit_transaction_via_cluster(ctx, inscription).await?;
+        let inscription_id = wait_for_inscription(&mut receiver, self.channel_id).await?;
+        tracing::debug!(executor = %self.label, channel_id = ?self.channel_id, "dispersal stress channel inscribed");
+        Ok(inscription_id)
+    }
+}
+
+fn stress_channel_id(executor: usize, worker: usize) -> ChannelId {
+    let mut bytes = [0u8; 32];
+    bytes[..8].copy_from_slice(b"chn_strs");
+    bytes[16..24].copy_from_slice(&(executor as u64).to_be_bytes());
+    bytes[24..].copy_from_slice(&(worker as u64).to_be_bytes());
+    ChannelId::from(bytes)
+}
+
+#[derive(Debug, Error)]
+enum DispersalStressError {
+    #[error("dispersal stress workload requires at least one executor")]
+    MissingExecutors,
+    #[error("{executor} published no blobs")]
+    NoPublishes { executor: String },
+    #[error("{executor} failed {failed} of {attempts} publishes (allowed ratio {allowed})")]
+    TooManyFailures {
+        executor: String,
+        failed: u64,
+        attempts: u64,
+        allowed: f64,
+    },
+}
+
+/// Reports per-executor publish counters and latencies, failing when an
+/// executor published nothing or failed too often.
+#[derive(Clone, Copy, Debug)]
+struct DispersalStressExpectation {
+    max_failure_ratio: f64,
+}
+
+#[async_trait]
+impl Expectation for DispersalStressExpectation {
+    fn name(&self) -> &'static str {
+        "dispersal_stress_expectation"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let summary = ctx.dispersal_summary();
+        for executor in &summary {
+            let report = ctx.report();
+            let prefix = &executor.executor;
+            report.set_counter(NAME, &format!("{prefix}_published"), executor.published);
+            report.set_counter(NAME, &format!("{prefix}_failed"), executor.failed);
+            for (counter, value) in [
+                ("p50_ms", executor.p50),
+                ("p95_ms", executor.p95),
+                ("max_ms", executor.max),
+            ] {
+                if let Some(value) = value {
+                    report.set_counter(
+                        NAME,
+                        &format!("{prefix}_{counter}"),
+                        value.as_millis() as u64,
+                    );
+                }
+            }
+            tracing::info!(
+                executor = %executor.executor,
+                published = executor.published,
+                failed = executor.failed,
+                p50 = ?executor.p50,
+                p95 = ?executor.p95,
+                max = ?executor.max,
+                "dispersal stress executor summary"
+            );
+        }
+
+        for index in 0..ctx.node_clients().executor_clients().len() {
+            let label = NodeTarget::Executor(index).to_string();
+            let Some(stats) = summary.iter().find(|stats| stats.executor == label) else {
+                return Err(DispersalStressError::NoPublishes { executor: label }.into());
+            };
+            if stats.published == 0 {
+                return Err(DispersalStressError::NoPublishes { executor: label }.into());
+            }
+            if stats.failure_ratio() > self.max_failure_ratio {
+                return Err(DispersalStressError::TooManyFailures {
+                    executor: label,
+                    failed: stats.failed,
+                    attempts: stats.published + stats.failed,
+                    allowed: self.max_failure_ratio,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_ratio_accepts_bounds() {
+        let workload = DispersalStressWorkload::new().with_max_failure_ratio(0.0);
+        assert!(workload.max_failure_ratio.abs() < f64::EPSILON);
+        let workload = workload.with_max_failure_ratio(1.0);
+        assert!((workload.max_failure_ratio - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be within [0, 1]")]
+    fn failure_ratio_above_one_panics() {
+        let _ = DispersalStressWorkload::new().with_max_failure_ratio(1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be within [0, 1]")]
+    fn nan_failure_ratio_panics() {
+        let _ = DispersalStressWorkload::new().with_max_failure_ratio(f64::NAN);
+    }
+}
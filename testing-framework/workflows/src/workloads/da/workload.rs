@@ -1,4 +1,8 @@
-use std::{num::NonZeroU64, sync::Arc, time::Duration};
+use std::{
+    num::{NonZeroU64, NonZeroUsize},
+    sync::Arc,
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use executor_http_client::ExecutorHttpClient;
@@ -14,16 +18,26 @@ use nomos_core::{
         },
     },
 };
-use rand::{RngCore as _, seq::SliceRandom as _, thread_rng};
+use rand::Rng;
 use testing_framework_core::{
     nodes::ApiClient,
     scenario::{
-        BlockRecord, DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload,
+        BlockRecord, DynError, Expectation, NodeFeature, NodeTarget, RunContext, RunMetrics,
+        Workload as ScenarioWorkload,
     },
+    topology::generation::GeneratedTopology,
+};
+use tokio::{
+    sync::broadcast,
+    time::{Instant, sleep},
 };
-use tokio::{sync::broadcast, time::sleep};
 
-use super::expectation::DaWorkloadExpectation;
+use super::{
+    affinity::ExecutorAffinity,
+    blob_size::BlobSizeDistribution,
+    commitments::{BlobFixtures, CommitmentsExpectation},
+    expectation::DaWorkloadExpectation,
+};
 use crate::{
     util::tx,
     workloads::util::{find_channel_op, submit_transaction_via_cluster},
@@ -32,7 +46,6 @@ use crate::{
 const TEST_KEY_BYTES: [u8; 32] = [0u8; 32];
 const DEFAULT_BLOB_RATE_PER_BLOCK: u64 = 1;
 const DEFAULT_CHANNEL_RATE_PER_BLOCK: u64 = 1;
-const BLOB_CHUNK_OPTIONS: &[usize] = &[1, 2, 4, 8];
 const PUBLISH_RETRIES: usize = 5;
 const PUBLISH_RETRY_DELAY: Duration = Duration::from_secs(2);
 const DEFAULT_HEADROOM_PERCENT: u64 = 20;
@@ -42,6 +55,18 @@ pub struct Workload {
     blob_rate_per_block: NonZeroU64,
     channel_rate_per_block: NonZeroU64,
     headroom_percent: u64,
+    blob_sizes: BlobSizeDistribution,
+    executor_affinity: ExecutorAffinity,
+    commitment_checks: Option<CommitmentChecks>,
+}
+
+/// Settings of [`Workload::with_commitment_verification`]; the fixtures are
+/// shared with the expectation.
+#[derive(Clone)]
+struct CommitmentChecks {
+    blobs: NonZeroUsize,
+    nodes: NonZeroUsize,
+    fixtures: BlobFixtures,
 }
 
 impl Default for Workload {
@@ -67,9 +92,44 @@ impl Workload {
             blob_rate_per_block,
             channel_rate_per_block,
             headroom_percent,
+            blob_sizes: BlobSizeDistribution::PowerOfTwoChunks,
+            executor_affinity: ExecutorAffinity::Random,
+            commitment_checks: None,
         }
     }
 
+    #[must_use]
+    /// Draw blob payload sizes from `blob_sizes` instead of whole
+    /// power-of-two chunk counts.
+    pub const fn with_blob_sizes(mut self, blob_sizes: BlobSizeDistribution) -> Self {
+        self.blob_sizes = blob_sizes;
+        self
+    }
+
+    #[must_use]
+    /// Choose which executor each blob is published through.
+    pub const fn with_executor_affinity(mut self, affinity: ExecutorAffinity) -> Self {
+        self.executor_affinity = affinity;
+        self
+    }
+
+    #[must_use]
+    /// Keep a random sample of `blobs` published payloads and, at evaluation,
+    /// verify their commitments against `nodes` validators each; see
+    /// [`CommitmentsExpectation`].
+    pub fn with_commitment_verification(
+        mut self,
+        blobs: NonZeroUsize,
+        nodes: NonZeroUsize,
+    ) -> Self {
+        self.commitment_checks = Some(CommitmentChecks {
+            blobs,
+            nodes,
+            fixtures: BlobFixtures::new(blobs),
+        });
+        self
+    }
+
     #[must_use]
     pub const fn default_headroom_percent() -> u64 {
         DEFAULT_HEADROOM_PERCENT
@@ -82,12 +142,48 @@ impl ScenarioWorkload for Workload {
         "channel_workload"
     }
 
+    fn tags(&self) -> &[&'static str] {
+        &["da"]
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        &[NodeFeature::ExecutorDispersal]
+    }
+
     fn expectations(&self) -> Vec<Box<dyn Expectation>> {
-        vec![Box::new(DaWorkloadExpectation::new(
-            self.blob_rate_per_block,
-            self.channel_rate_per_block,
-            self.headroom_percent,
-        ))]
+        let mut expectations: Vec<Box<dyn Expectation>> =
+            vec![Box::new(DaWorkloadExpectation::new(
+                self.blob_rate_per_block,
+                self.channel_rate_per_block,
+                self.headroom_percent,
+            ))];
+        if let Some(checks) = &self.commitment_checks {
+            expectations.push(Box::new(CommitmentsExpectation::new(
+                checks.blobs,
+                checks.nodes,
+                checks.fixtures.clone(),
+            )));
+        }
+        expectations
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        self.executor_affinity
+            .validate(descriptors.executors().len())?;
+        Ok(())
+    }
+
+    fn plan(&self, run_metrics: &RunMetrics) -> Option<String> {
+        let channels = planned_channel_count(self.channel_rate_per_block, self.headroom_percent);
+        let blobs = planned_blob_count(self.blob_rate_per_block, run_metrics);
+        Some(format!(
+            "{blobs} blobs over {channels} channels, up to {} per channel",
+            per_channel_blob_target(blobs, channels as u64)
+        ))
     }
 
     async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
@@ -104,17 +200,33 @@ impl ScenarioWorkload for Workload {
             blob_rate_per_block = self.blob_rate_per_block.get(),
             channel_rate = self.channel_rate_per_block.get(),
             headroom_percent = self.headroom_percent,
+            blob_sizes = ?self.blob_sizes,
+            executor_affinity = ?self.executor_affinity,
+            commitment_checks = self.commitment_checks.is_some(),
             planned_channels = planned_channels.len(),
             expected_blobs,
             per_channel_target,
             "DA workload derived planned channels"
         );
 
-        try_join_all(planned_channels.into_iter().map(|channel_id| {
+        try_join_all(planned_channels.into_iter().enumerate().map(|(index, channel_id)| {
             let ctx = ctx;
+            // One RNG stream per channel keeps payloads reproducible regardless
+            // of how the concurrent flows interleave.
+            let rng = ctx.rng(&format!("{}/{index}", self.name()));
             async move {
                 tracing::info!(channel_id = ?channel_id, blobs = per_channel_target, "DA workload starting channel flow");
-                run_channel_flow(ctx, channel_id, per_channel_target).await?;
+                run_channel_flow(
+                    ctx,
+                    index,
+                    channel_id,
+                    per_channel_target,
+                    self.blob_sizes,
+                    self.executor_affinity,
+                    self.commitment_checks.as_ref().map(|checks| &checks.fixtures),
+                    rng,
+                )
+                .await?;
                 tracing::info!(channel_id = ?channel_id, "DA workload finished channel flow");
                 Ok::<(), DynError>(())
             }
@@ -128,20 +240,42 @@ impl ScenarioWorkload for Workload {
 
 async fn run_channel_flow(
     ctx: &RunContext,
+    index: usize,
     channel_id: ChannelId,
     target_blobs: u64,
+    blob_sizes: BlobSizeDistribution,
+    affinity: ExecutorAffinity,
+    fixtures: Option<&BlobFixtures>,
+    mut rng: impl Rng + Send,
 ) -> Result<(), DynError> {
     tracing::debug!(channel_id = ?channel_id, "DA: submitting inscription tx");
     let inscription_tx = Arc::new(tx::create_inscription_transaction_with_id(channel_id));
     submit_transaction_via_cluster(ctx, Arc::clone(&inscription_tx)).await?;
+    ctx.report()
+        .increment("channel_workload", "inscriptions_submitted", 1);
 
     let mut receiver = ctx.block_feed().subscribe();
     let inscription_id = wait_for_inscription(&mut receiver, channel_id).await?;
 
     let mut parent_id = inscription_id;
     for idx in 0..target_blobs {
-        let payload = random_blob_payload();
-        let published_blob_id = publish_blob(ctx, channel_id, parent_id, payload).await?;
+        let payload = blob_sizes.payload(idx, &mut rng);
+        let payload_bytes = payload.len() as u64;
+        let fixture = fixtures.map(|fixtures| (fixtures, payload.clone()));
+        let route = PublishRoute {
+            affinity,
+            channel: index,
+            blob: idx,
+        };
+        let published_blob_id =
+            publish_blob(ctx, channel_id, parent_id, payload, route, &mut rng).await?;
+        if let Some((fixtures, payload)) = fixture {
+            fixtures.record(published_blob_id, payload, &mut rng);
+        }
+        ctx.report()
+            .increment("channel_workload", "blobs_published", 1);
+        ctx.report()
+            .increment("channel_workload", "blob_bytes_published", payload_bytes);
         let (next_parent, included_blob_id) =
             wait_for_blob_with_parent(&mut receiver, channel_id, parent_id).await?;
         parent_id = next_parent;
@@ -149,6 +283,7 @@ async fn run_channel_flow(
         tracing::debug!(
             channel_id = ?channel_id,
             blob_index = idx,
+            payload_bytes,
             published_blob_id = ?published_blob_id,
             included_blob_id = ?included_blob_id,
             "DA: blob published"
@@ -157,7 +292,7 @@ async fn run_channel_flow(
     Ok(())
 }
 
-async fn wait_for_inscription(
+pub(super) async fn wait_for_inscription(
     receiver: &mut broadcast::Receiver<Arc<BlockRecord>>,
     channel_id: ChannelId,
 ) -> Result<MsgId, DynError> {
@@ -224,11 +359,23 @@ where
     }
 }
 
+/// Which executors a blob may be published through.
+#[derive(Clone, Copy)]
+struct PublishRoute {
+    affinity: ExecutorAffinity,
+    /// Index of the channel among the planned channels.
+    channel: usize,
+    /// Index of the blob within its channel.
+    blob: u64,
+}
+
 async fn publish_blob(
     ctx: &RunContext,
     channel_id: ChannelId,
     parent_msg: MsgId,
     data: Vec<u8>,
+    route: PublishRoute,
+    rng: &mut (impl Rng + Send),
 ) -> Result<BlobId, DynError> {
     let executors = ctx.node_clients().executor_clients();
     if executors.is_empty() {
@@ -239,20 +386,31 @@ async fn publish_blob(
     tracing::debug!(channel = ?channel_id, payload_bytes = data.len(), "DA: prepared blob payload");
     let client = ExecutorHttpClient::new(None);
 
-    let mut candidates: Vec<&ApiClient> = executors.iter().collect();
-    let mut last_err = None;
+    let mut last_failure = None;
     for attempt in 1..=PUBLISH_RETRIES {
-        candidates.shuffle(&mut thread_rng());
-        for executor in &candidates {
+        let candidates: Vec<(NodeTarget, &ApiClient)> = route
+            .affinity
+            .candidates(route.channel, route.blob, executors.len(), rng)
+            .into_iter()
+            .filter_map(|index| Some((NodeTarget::Executor(index), executors.get(index)?)))
+            .collect();
+        for (target, executor) in &candidates {
+            let label = target.to_string();
             let executor_url = executor.base_url().clone();
+            let started = Instant::now();
             match client
                 .publish_blob(executor_url, channel_id, parent_msg, signer, data.clone())
                 .await
             {
-                Ok(blob_id) => return Ok(blob_id),
+                Ok(blob_id) => {
+                    ctx.dispersal()
+                        .record_publish(&label, data.len() as u64, started.elapsed());
+                    return Ok(blob_id);
+                }
                 Err(err) => {
-                    tracing::debug!(attempt, executor = %executor.base_url(), %err, "DA: publish_blob failed");
-                    last_err = Some(err.into())
+                    tracing::debug!(attempt, executor = %label, %err, "DA: publish_blob failed");
+                    ctx.dispersal().record_failure(&label);
+                    last_failure = Some((*target, err));
                 }
             }
         }
@@ -262,25 +420,23 @@ async fn publish_blob(
         }
     }
 
-    Err(last_err.unwrap_or_else(|| "da workload could not publish blob".into()))
+    Err(last_failure.map_or_else(
+        || "da workload could not publish blob".into(),
+        |(target, err)| {
+            format!(
+                "da workload could not publish blob {} of channel {} through {target} ({:?} \
+                 executor affinity): {err}",
+                route.blob, route.channel, route.affinity
+            )
+            .into()
+        },
+    ))
 }
 
-fn test_signer() -> Ed25519PublicKey {
+pub(super) fn test_signer() -> Ed25519PublicKey {
     Ed25519Key::from_bytes(&TEST_KEY_BYTES).public_key()
 }
 
-fn random_blob_payload() -> Vec<u8> {
-    let mut rng = thread_rng();
-    // KZGRS encoder expects the polynomial degree to be a power of two, which
-    // effectively constrains the blob chunk count.
-    let chunks = *BLOB_CHUNK_OPTIONS
-        .choose(&mut rng)
-        .expect("non-empty chunk options");
-    let mut data = vec![0u8; 31 * chunks];
-    rng.fill_bytes(&mut data);
-    data
-}
-
 pub fn planned_channel_ids(total: usize) -> Vec<ChannelId> {
     (0..total as u64)
         .map(deterministic_channel_id)
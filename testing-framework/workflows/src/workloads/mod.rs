@@ -1,5 +1,7 @@
+pub mod blend;
 pub mod chaos;
 pub mod da;
+pub mod sdp;
 pub mod transaction;
 pub mod util;
 
@@ -0,0 +1,3 @@
+mod rotation;
+
+pub use rotation::SessionRotationWorkload;
@@ -0,0 +1,518 @@
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use key_management_system_service::keys::{Ed25519Key, ZkKey};
+use nomos_core::{
+    mantle::{AuthenticatedMantleTx as _, GenesisTx as _, SignedMantleTx, ops::Op},
+    sdp::{DeclarationMessage, ProviderId, ServiceType, SessionNumber},
+};
+use testing_framework_core::{
+    nodes::{ApiClient, session_at, wait_for_session},
+    scenario::{
+        BlockRecord, DynError, Expectation, NodeFeature, NodeTarget, RunContext, RunMetrics,
+        Workload,
+    },
+    topology::generation::{GeneratedNodeConfig, GeneratedTopology, NodeRole},
+};
+use thiserror::Error;
+use tokio::{sync::broadcast, time::timeout};
+use tracing::{debug, info, warn};
+
+use crate::{util::tx, workloads::util::submit_transaction_via_cluster};
+
+const WORKLOAD_NAME: &str = "sdp_session_rotation";
+const EXPECTATION_NAME: &str = "da_membership_rotation";
+/// Sessions between the one an SDP operation lands in and the first
+/// membership reflecting it.
+const DEFAULT_ACTIVATION_DELAY: u64 = 1;
+/// Nonce of the first withdrawal of a genesis declaration.
+const WITHDRAW_NONCE: u64 = 1;
+
+/// SDP operation one rotation step submits for every target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RotationStep {
+    Withdraw,
+    Declare,
+}
+
+/// A node's DA provider as declared in genesis, with the keys needed to
+/// withdraw and re-declare it.
+#[derive(Clone)]
+struct DaProvider {
+    target: NodeTarget,
+    peer_id: String,
+    declaration: DeclarationMessage,
+    note_key: ZkKey,
+    zk_key: ZkKey,
+    provider_key: Ed25519Key,
+}
+
+impl DaProvider {
+    fn transaction(&self, step: RotationStep) -> SignedMantleTx {
+        match step {
+            RotationStep::Withdraw => tx::create_sdp_withdraw_transaction(
+                self.declaration.id(),
+                WITHDRAW_NONCE,
+                &self.zk_key,
+            ),
+            RotationStep::Declare => tx::create_sdp_declare_transaction(
+                self.declaration.clone(),
+                &self.note_key,
+                &self.zk_key,
+                &self.provider_key,
+            ),
+        }
+    }
+
+    fn matches(&self, step: RotationStep, op: &Op) -> bool {
+        match (step, op) {
+            (RotationStep::Withdraw, Op::SDPWithdraw(withdraw)) => {
+                withdraw.declaration_id == self.declaration.id()
+            }
+            (RotationStep::Declare, Op::SDPDeclare(declaration)) => {
+                declaration.provider_id == self.declaration.provider_id
+            }
+            _ => false,
+        }
+    }
+}
+
+/// One rotation step of the workload, shared with its expectation.
+#[derive(Clone, Debug)]
+struct RotationRecord {
+    step: RotationStep,
+    targets: Vec<NodeTarget>,
+    peers: Vec<String>,
+    /// First session whose DA membership must reflect the step.
+    session: SessionNumber,
+    reached: bool,
+}
+
+type SharedRotationRecords = Arc<Mutex<Vec<RotationRecord>>>;
+
+/// Rotates DA membership through SDP transactions and checks that
+/// `da_get_membership` follows.
+///
+/// The workload withdraws the genesis DA declarations of the target nodes,
+/// waits for the inclusion, then for the session in which the new membership
+/// takes effect (the inclusion session plus the activation delay). With
+/// re-declaration enabled it then declares the same providers again and waits
+/// for that session too. The paired expectation queries every node for each
+/// of those sessions: withdrawn peers must be gone from the assignations and
+/// re-declared peers back. Sessions default to 1000 blocks; shorten them with
+/// `session_duration` on the topology so a run spans several.
+pub struct SessionRotationWorkload {
+    withdraw: Vec<NodeTarget>,
+    redeclare: bool,
+    activation_delay: u64,
+    session_duration: u64,
+    providers: Vec<DaProvider>,
+    records: SharedRotationRecords,
+}
+
+impl Default for SessionRotationWorkload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionRotationWorkload {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            withdraw: Vec::new(),
+            redeclare: false,
+            activation_delay: DEFAULT_ACTIVATION_DELAY,
+            session_duration: 0,
+            providers: Vec::new(),
+            records: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    #[must_use]
+    /// Withdraw the DA declaration of `target`.
+    pub fn withdraw(mut self, target: NodeTarget) -> Self {
+        if !self.withdraw.contains(&target) {
+            self.withdraw.push(target);
+        }
+        self
+    }
+
+    #[must_use]
+    /// Declare the withdrawn providers again once their withdrawal took
+    /// effect.
+    pub const fn with_redeclare(mut self, enabled: bool) -> Self {
+        self.redeclare = enabled;
+        self
+    }
+
+    #[must_use]
+    /// Sessions between the one an operation lands in and the first
+    /// membership reflecting it. Defaults to 1.
+    pub const fn with_activation_delay(mut self, sessions: u64) -> Self {
+        self.activation_delay = sessions;
+        self
+    }
+
+    fn steps(&self) -> Vec<RotationStep> {
+        let mut steps = vec![RotationStep::Withdraw];
+        if self.redeclare {
+            steps.push(RotationStep::Declare);
+        }
+        steps
+    }
+
+    async fn run_step(
+        &self,
+        ctx: &RunContext,
+        blocks: &mut broadcast::Receiver<Arc<BlockRecord>>,
+        step: RotationStep,
+    ) -> Result<(), DynError> {
+        for provider in &self.providers {
+            let tx = Arc::new(provider.transaction(step));
+            submit_transaction_via_cluster(ctx, tx).await?;
+            info!(target = %provider.target, ?step, "submitted SDP transaction");
+        }
+        wait_for_inclusion(blocks, &self.providers, step, ctx.run_duration()).await?;
+
+        let reference = reference_client(ctx)?;
+        let height = reference
+            .consensus_info()
+            .await
+            .map_err(|err| format!("consensus info request failed: {err}"))?
+            .height;
+        // The tip may already be past the inclusion block, which only delays
+        // the checked session.
+        let session = SessionNumber::from(
+            u64::from(session_at(height, self.session_duration)) + self.activation_delay,
+        );
+        let index = {
+            let mut records = self.records.lock().expect("rotation records lock poisoned");
+            records.push(RotationRecord {
+                step,
+                targets: self.providers.iter().map(|p| p.target).collect(),
+                peers: self.providers.iter().map(|p| p.peer_id.clone()).collect(),
+                session,
+                reached: false,
+            });
+            records.len() - 1
+        };
+        info!(
+            ?step,
+            height,
+            ?session,
+            "SDP transactions included; waiting for session"
+        );
+
+        wait_for_session(
+            reference,
+            session,
+            self.session_duration,
+            ctx.run_duration(),
+        )
+        .await?;
+        self.records.lock().expect("rotation records lock poisoned")[index].reached = true;
+        ctx.report().increment(WORKLOAD_NAME, "sessions_rotated", 1);
+        info!(?step, ?session, "session with rotated membership reached");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Workload for SessionRotationWorkload {
+    fn name(&self) -> &'static str {
+        WORKLOAD_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da", "sdp"]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        vec![Box::new(MembershipRotationExpectation {
+            records: Arc::clone(&self.records),
+            steps: self.steps().len(),
+        })]
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        if self.withdraw.is_empty() {
+            return Err(SessionRotationError::NoTargets.into());
+        }
+        self.session_duration = descriptors.config().consensus_params.session_duration;
+        self.providers = self
+            .withdraw
+            .iter()
+            .map(|target| da_provider(descriptors, *target))
+            .collect::<Result<_, _>>()?;
+
+        let needed =
+            self.session_duration * (self.activation_delay + 1) * self.steps().len() as u64;
+        if run_metrics.expected_consensus_blocks() < needed {
+            warn!(
+                expected_blocks = run_metrics.expected_consensus_blocks(),
+                needed,
+                session_duration = self.session_duration,
+                "run may end before the rotated sessions; lengthen it or shorten sessions"
+            );
+        }
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        info!(
+            targets = ?self.withdraw,
+            redeclare = self.redeclare,
+            session_duration = self.session_duration,
+            "starting SDP session rotation workload"
+        );
+        let mut blocks = ctx.block_feed().subscribe();
+        for step in self.steps() {
+            self.run_step(ctx, &mut blocks, step).await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+enum SessionRotationError {
+    #[error("session rotation needs at least one node to withdraw")]
+    NoTargets,
+    #[error("session rotation targets {target}, which is not part of the topology")]
+    UnknownTarget { target: NodeTarget },
+    #[error("genesis holds no DA declaration for {target}")]
+    MissingDeclaration { target: NodeTarget },
+    #[error("block feed closed before the SDP transactions were included")]
+    FeedClosed,
+    #[error("{step:?} transactions of {pending:?} were not included within {timeout:?}")]
+    InclusionTimeout {
+        step: RotationStep,
+        pending: Vec<NodeTarget>,
+        timeout: Duration,
+    },
+    #[error("no validator client available")]
+    NoValidator,
+}
+
+fn da_provider(
+    descriptors: &GeneratedTopology,
+    target: NodeTarget,
+) -> Result<DaProvider, SessionRotationError> {
+    let (position, node) = descriptors
+        .nodes()
+        .enumerate()
+        .find(|(_, node)| node_target(node) == target)
+        .ok_or(SessionRotationError::UnknownTarget { target })?;
+    let da = &node.general.da_config;
+    let provider_id = ProviderId(da.signer.public_key());
+    let declaration = node
+        .general
+        .consensus_config
+        .genesis_tx
+        .sdp_declarations()
+        .map(|(declaration, _)| declaration)
+        .find(|declaration| {
+            declaration.service_type == ServiceType::DataAvailability
+                && declaration.provider_id == provider_id
+        })
+        .cloned()
+        .ok_or(SessionRotationError::MissingDeclaration { target })?;
+    // Genesis DA notes follow the node order.
+    let note_key = node
+        .general
+        .consensus_config
+        .da_notes
+        .get(position)
+        .ok_or(SessionRotationError::MissingDeclaration { target })?
+        .sk
+        .clone();
+
+    Ok(DaProvider {
+        target,
+        peer_id: da.peer_id.to_string(),
+        declaration,
+        note_key,
+        zk_key: da.secret_zk_key.clone(),
+        provider_key: da.signer.clone(),
+    })
+}
+
+const fn node_target(node: &GeneratedNodeConfig) -> NodeTarget {
+    match node.role() {
+        NodeRole::Validator => NodeTarget::Validator(node.index()),
+        NodeRole::Executor => NodeTarget::Executor(node.index()),
+    }
+}
+
+fn reference_client(ctx: &RunContext) -> Result<&ApiClient, SessionRotationError> {
+    ctx.node_clients()
+        .validator_clients()
+        .first()
+        .ok_or(SessionRotationError::NoValidator)
+}
+
+/// Waits until every provider's operation for `step` appeared in a block,
+/// for at most `limit`.
+async fn wait_for_inclusion(
+    blocks: &mut broadcast::Receiver<Arc<BlockRecord>>,
+    providers: &[DaProvider],
+    step: RotationStep,
+    limit: Duration,
+) -> Result<(), SessionRotationError> {
+    let mut pending: HashSet<NodeTarget> = providers.iter().map(|p| p.target).collect();
+    let waited = timeout(
+        limit,
+        collect_inclusions(blocks, providers, step, &mut pending),
+    )
+    .await;
+    waited.unwrap_or_else(|_| {
+        let mut pending = pending.into_iter().collect::<Vec<_>>();
+        pending.sort_by_key(ToString::to_string);
+        Err(SessionRotationError::InclusionTimeout {
+            step,
+            pending,
+            timeout: limit,
+        })
+    })
+}
+
+async fn collect_inclusions(
+    blocks: &mut broadcast::Receiver<Arc<BlockRecord>>,
+    providers: &[DaProvider],
+    step: RotationStep,
+    pending: &mut HashSet<NodeTarget>,
+) -> Result<(), SessionRotationError> {
+    while !pending.is_empty() {
+        match blocks.recv().await {
+            Ok(record) => {
+                for tx in record.block.transactions() {
+                    for op in &tx.mantle_tx().ops {
+                        for provider in providers {
+                            if provider.matches(step, op) && pending.remove(&provider.target) {
+                                debug!(target = %provider.target, ?step, "SDP operation included");
+                            }
+                        }
+                    }
+                }
+            }
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                debug!(skipped, "session rotation block feed lagged");
+            }
+            Err(broadcast::error::RecvError::Closed) => {
+                return Err(SessionRotationError::FeedClosed);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that DA membership reflects every rotation step of
+/// [`SessionRotationWorkload`] from the session it takes effect in.
+struct MembershipRotationExpectation {
+    records: SharedRotationRecords,
+    steps: usize,
+}
+
+#[derive(Debug, Error)]
+enum MembershipRotationError {
+    #[error("only {completed} of {planned} rotation steps were submitted before the run ended")]
+    Incomplete { completed: usize, planned: usize },
+    #[error("DA membership did not follow the rotation:\n{0}")]
+    Violations(String),
+}
+
+#[async_trait]
+impl Expectation for MembershipRotationExpectation {
+    fn name(&self) -> &'static str {
+        EXPECTATION_NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["da", "sdp"]
+    }
+
+    fn required_features(&self) -> &[NodeFeature] {
+        &[NodeFeature::TestingHttp]
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let records = self
+            .records
+            .lock()
+            .expect("rotation records lock poisoned")
+            .clone();
+        if records.len() < self.steps {
+            return Err(MembershipRotationError::Incomplete {
+                completed: records.len(),
+                planned: self.steps,
+            }
+            .into());
+        }
+
+        let mut issues = Vec::new();
+        for record in &records {
+            if !record.reached {
+                issues.push(format!(
+                    "- {:?} of {:?}: session {:?} was not reached; lengthen the run or shorten sessions",
+                    record.step, record.targets, record.session
+                ));
+                continue;
+            }
+            for (target, client) in ctx.node_clients().targeted_clients() {
+                let membership = match client.da_get_membership(&record.session).await {
+                    Ok(membership) => membership,
+                    Err(err) => {
+                        issues.push(format!(
+                            "- {target}: membership for session {:?} unavailable: {err}",
+                            record.session
+                        ));
+                        continue;
+                    }
+                };
+                let assigned: HashSet<String> = membership
+                    .assignations
+                    .values()
+                    .flatten()
+                    .map(ToString::to_string)
+                    .collect();
+                for (peer, rotated) in record.peers.iter().zip(&record.targets) {
+                    let present = assigned.contains(peer);
+                    match (record.step, present) {
+                        (RotationStep::Withdraw, true) => issues.push(format!(
+                            "- {target}: withdrawn {rotated} still assigned in session {:?}",
+                            record.session
+                        )),
+                        (RotationStep::Declare, false) => issues.push(format!(
+                            "- {target}: re-declared {rotated} not assigned in session {:?}",
+                            record.session
+                        )),
+                        _ => {}
+                    }
+                }
+            }
+            ctx.report().set_counter(
+                EXPECTATION_NAME,
+                &format!("{:?}_session", record.step).to_lowercase(),
+                u64::from(record.session),
+            );
+        }
+
+        if issues.is_empty() {
+            info!(
+                steps = records.len(),
+                "DA membership rotation expectation satisfied"
+            );
+            Ok(())
+        } else {
+            Err(MembershipRotationError::Violations(issues.join("\n")).into())
+        }
+    }
+}
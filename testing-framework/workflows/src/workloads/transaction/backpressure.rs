@@ -0,0 +1,301 @@
+use std::{num::NonZeroU64, time::Duration};
+
+use futures::future::join_all;
+use serde_json::Value;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{NodeTarget, RunContext, RunReporter},
+};
+use tokio::time::{Instant, sleep};
+
+/// Mempool whose metrics are polled; wallet transactions are submitted to it.
+const MANTLE_POOL: &str = "mantle";
+/// Field of the mempool metrics response holding the pending transactions.
+const PENDING_ITEMS: &str = "pending_items";
+const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Adaptive submission for the transaction workload: before each
+/// transaction the mempool metrics of the submission nodes are polled, and
+/// submission pauses while any of them holds more than `max_pending`
+/// transactions.
+///
+/// Models a well-behaved client instead of one pushing a fixed rate into a
+/// saturated mempool. Time spent paused is reported as the `throttled_ms`
+/// and `throttle_pauses` counters of `tx_workload` (next to
+/// `peak_mempool_pending`), including a pause cut short by the end of the
+/// run, and the inclusion expectation is measured against the transactions
+/// actually submitted.
+#[derive(Clone, Copy, Debug)]
+pub struct MempoolBackpressure {
+    max_pending: NonZeroU64,
+    poll_interval: Duration,
+}
+
+impl MempoolBackpressure {
+    /// Pause submission while a node reports more than `max_pending`
+    /// pending transactions.
+    #[must_use]
+    pub const fn new(max_pending: NonZeroU64) -> Self {
+        Self {
+            max_pending,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+
+    /// How often the mempool is polled while submission is paused (panics on
+    /// zero).
+    #[must_use]
+    pub const fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        assert!(
+            !poll_interval.is_zero(),
+            "mempool backpressure poll interval must be non-zero"
+        );
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    #[must_use]
+    pub const fn max_pending(&self) -> NonZeroU64 {
+        self.max_pending
+    }
+
+    /// Wait until every polled node is back at or below the threshold.
+    /// Nodes whose metrics cannot be read do not hold submission back.
+    pub(super) async fn wait_for_capacity(
+        &self,
+        ctx: &RunContext,
+        targets: &[NodeTarget],
+        stats: &mut ThrottleStats,
+    ) {
+        loop {
+            let readings = pending_transactions(ctx, targets).await;
+            let pending = stats.observe(&readings);
+            if pending.is_none_or(|pending| pending <= self.max_pending.get()) {
+                break;
+            }
+            if stats.paused_since.is_none() {
+                tracing::debug!(
+                    pending,
+                    max_pending = self.max_pending.get(),
+                    "mempool saturated, pausing transaction submission"
+                );
+                stats.paused_since = Some(Instant::now());
+            }
+            sleep(self.poll_interval).await;
+        }
+        stats.resume();
+    }
+}
+
+/// Mempool metrics of one node, as read by [`pending_transactions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PendingReading {
+    Pending(u64),
+    /// The node answered without a pending count.
+    MissingField,
+    Unavailable,
+}
+
+impl PendingReading {
+    fn from_metrics(metrics: &Value) -> Self {
+        metrics
+            .get(PENDING_ITEMS)
+            .and_then(Value::as_u64)
+            .map_or(Self::MissingField, Self::Pending)
+    }
+}
+
+/// Time the transaction workload spent held back by [`MempoolBackpressure`].
+///
+/// Dropping the stats while paused, as happens when the workload is stopped
+/// at the end of the run, counts and reports the pause cut short.
+pub(super) struct ThrottleStats {
+    reporter: RunReporter,
+    workload: &'static str,
+    pauses: u64,
+    throttled: Duration,
+    peak_pending: u64,
+    paused_since: Option<Instant>,
+    warned_missing_field: bool,
+}
+
+impl ThrottleStats {
+    pub(super) const fn new(reporter: RunReporter, workload: &'static str) -> Self {
+        Self {
+            reporter,
+            workload,
+            pauses: 0,
+            throttled: Duration::ZERO,
+            peak_pending: 0,
+            paused_since: None,
+            warned_missing_field: false,
+        }
+    }
+
+    pub(super) fn report(&self) {
+        let report = &self.reporter;
+        report.set_counter(self.workload, "throttle_pauses", self.pauses);
+        report.set_counter(
+            self.workload,
+            "throttled_ms",
+            self.throttled.as_millis() as u64,
+        );
+        report.set_counter(self.workload, "peak_mempool_pending", self.peak_pending);
+    }
+
+    /// Highest pending count among the readings, warning once when nodes
+    /// answer without one since backpressure cannot act on them.
+    fn observe(&mut self, readings: &[PendingReading]) -> Option<u64> {
+        let missing = readings
+            .iter()
+            .filter(|reading| **reading == PendingReading::MissingField)
+            .count();
+        if missing > 0 && !self.warned_missing_field {
+            self.warned_missing_field = true;
+            tracing::warn!(
+                nodes = missing,
+                field = PENDING_ITEMS,
+                "mempool metrics lack the pending count; backpressure ignores these nodes"
+            );
+        }
+
+        let pending = readings
+            .iter()
+            .filter_map(|reading| match reading {
+                PendingReading::Pending(pending) => Some(*pending),
+                PendingReading::MissingField | PendingReading::Unavailable => None,
+            })
+            .max();
+        if let Some(pending) = pending {
+            self.peak_pending = self.peak_pending.max(pending);
+        }
+        pending
+    }
+
+    /// Close the pause in progress, if any.
+    fn resume(&mut self) {
+        if let Some(paused_since) = self.paused_since.take() {
+            let paused = paused_since.elapsed();
+            tracing::debug!(
+                paused_ms = paused.as_millis(),
+                "mempool drained, resuming transaction submission"
+            );
+            self.pauses += 1;
+            self.throttled += paused;
+        }
+    }
+}
+
+impl Drop for ThrottleStats {
+    fn drop(&mut self) {
+        if self.paused_since.is_some() {
+            self.resume();
+            self.report();
+        }
+    }
+}
+
+/// Pending counts of `targets`, or of every node when none are pinned.
+async fn pending_transactions(ctx: &RunContext, targets: &[NodeTarget]) -> Vec<PendingReading> {
+    let node_clients = ctx.node_clients();
+    let clients: Vec<&ApiClient> = if targets.is_empty() {
+        node_clients.all_clients().collect()
+    } else {
+        targets
+            .iter()
+            .filter_map(|target| node_clients.client(*target))
+            .collect()
+    };
+
+    join_all(clients.into_iter().map(|client| async move {
+        match client.mempool_metrics(MANTLE_POOL).await {
+            Ok(metrics) => PendingReading::from_metrics(&metrics),
+            Err(err) => {
+                tracing::debug!(url = %client.base_url(), error = %err, "mempool metrics unavailable");
+                PendingReading::Unavailable
+            }
+        }
+    }))
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn counters(reporter: &RunReporter) -> Vec<(String, u64)> {
+        reporter
+            .snapshot()
+            .workloads
+            .get("tx_workload")
+            .map(|counters| counters.clone().into_iter().collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn pending_count_is_read_from_the_metrics() {
+        assert_eq!(
+            PendingReading::from_metrics(&json!({ "pending_items": 12, "last_item": 3 })),
+            PendingReading::Pending(12)
+        );
+        assert_eq!(
+            PendingReading::from_metrics(&json!({ "last_item": 3 })),
+            PendingReading::MissingField
+        );
+    }
+
+    #[test]
+    fn observe_tracks_the_highest_pending_count() {
+        let mut stats = ThrottleStats::new(RunReporter::new(), "tx_workload");
+
+        let readings = [
+            PendingReading::Pending(4),
+            PendingReading::Unavailable,
+            PendingReading::Pending(9),
+        ];
+        assert_eq!(stats.observe(&readings), Some(9));
+        assert_eq!(stats.observe(&[PendingReading::Pending(2)]), Some(2));
+        assert_eq!(stats.peak_pending, 9);
+
+        assert_eq!(stats.observe(&[PendingReading::MissingField]), None);
+        assert!(stats.warned_missing_field);
+    }
+
+    #[test]
+    fn pause_cut_short_is_reported_on_drop() {
+        let reporter = RunReporter::new();
+        let mut stats = ThrottleStats::new(reporter.clone(), "tx_workload");
+        stats.paused_since = Some(Instant::now());
+        drop(stats);
+
+        let counters = counters(&reporter);
+        assert!(
+            counters.contains(&("throttle_pauses".to_owned(), 1)),
+            "{counters:?}"
+        );
+        assert!(
+            counters.iter().any(|(name, _)| name == "throttled_ms"),
+            "{counters:?}"
+        );
+    }
+
+    #[test]
+    fn resumed_pause_is_counted_once() {
+        let reporter = RunReporter::new();
+        let mut stats = ThrottleStats::new(reporter.clone(), "tx_workload");
+        stats.paused_since = Some(Instant::now());
+        stats.resume();
+        stats.report();
+        drop(stats);
+
+        assert!(counters(&reporter).contains(&("throttle_pauses".to_owned(), 1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "poll interval must be non-zero")]
+    fn zero_poll_interval_is_rejected() {
+        let _ = MempoolBackpressure::new(NonZeroU64::MIN).with_poll_interval(Duration::ZERO);
+    }
+}
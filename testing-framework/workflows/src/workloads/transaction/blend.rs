@@ -0,0 +1,260 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use nomos_core::mantle::{Transaction as _, TxHash};
+use testing_framework_core::scenario::{DynError, Expectation, NodeTarget, RunContext};
+use thiserror::Error;
+use tokio::{sync::broadcast, task::JoinHandle};
+
+/// Blocks a transaction is given to land before a missing inclusion counts.
+const GRACE_BLOCKS: u32 = 3;
+/// Grace period used when the topology gives no block interval hint.
+const FALLBACK_GRACE: Duration = Duration::from_secs(30);
+/// Missing transactions listed per entry node in the failure message.
+const MAX_REPORTED_MISSING: usize = 3;
+
+#[derive(Clone, Copy, Debug)]
+struct BlendSubmission {
+    tx: TxHash,
+    entry: NodeTarget,
+    at: Instant,
+}
+
+/// Transactions the workload sent down the blend path, shared with
+/// [`BlendTxInclusionExpectation`]. Clones share the same list.
+#[derive(Clone, Debug, Default)]
+pub(super) struct BlendSubmissions {
+    submissions: Arc<Mutex<Vec<BlendSubmission>>>,
+}
+
+impl BlendSubmissions {
+    pub(super) fn record(&self, tx: TxHash, entry: NodeTarget) {
+        self.submissions
+            .lock()
+            .expect("blend submissions lock poisoned")
+            .push(BlendSubmission {
+                tx,
+                entry,
+                at: Instant::now(),
+            });
+    }
+
+    fn snapshot(&self) -> Vec<BlendSubmission> {
+        self.submissions
+            .lock()
+            .expect("blend submissions lock poisoned")
+            .clone()
+    }
+}
+
+#[derive(Debug, Error)]
+enum BlendTxError {
+    #[error("transaction workload submitted no transactions through the blend path")]
+    NoSubmissions,
+    #[error("blend transaction inclusion not captured")]
+    NotCaptured,
+    #[error("{missing} of {settled} blend-path transactions were never included:\n{details}")]
+    Missing {
+        missing: usize,
+        settled: usize,
+        details: String,
+    },
+}
+
+/// Checks that every transaction the workload submitted through a core blend
+/// node was eventually included.
+///
+/// Transactions are matched by hash against the blocks observed by the block
+/// feed. Those submitted within the last few block intervals before
+/// evaluation may still be in flight and are not required.
+pub struct BlendTxInclusionExpectation {
+    submissions: BlendSubmissions,
+    included: Option<Arc<Mutex<HashSet<TxHash>>>>,
+    capture: Option<JoinHandle<()>>,
+}
+
+impl BlendTxInclusionExpectation {
+    pub const NAME: &'static str = "tx_blend_inclusion";
+
+    pub(super) const fn new(submissions: BlendSubmissions) -> Self {
+        Self {
+            submissions,
+            included: None,
+            capture: None,
+        }
+    }
+}
+
+impl Drop for BlendTxInclusionExpectation {
+    fn drop(&mut self) {
+        if let Some(capture) = self.capture.take() {
+            capture.abort();
+        }
+    }
+}
+
+/// Submissions old enough to have landed, with those never included grouped
+/// by entry node.
+fn settle(
+    submissions: &[BlendSubmission],
+    included: &HashSet<TxHash>,
+    grace: Duration,
+) -> (usize, BTreeMap<String, Vec<TxHash>>) {
+    let mut settled = 0;
+    let mut missing = BTreeMap::<String, Vec<TxHash>>::new();
+    for submission in submissions {
+        if included.contains(&submission.tx) {
+            settled += 1;
+        } else if submission.at.elapsed() >= grace {
+            settled += 1;
+            missing
+                .entry(submission.entry.to_string())
+                .or_default()
+                .push(submission.tx);
+        }
+    }
+    (settled, missing)
+}
+
+#[async_trait]
+impl Expectation for BlendTxInclusionExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["tx", "blend"]
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.included.is_some() {
+            return Ok(());
+        }
+
+        let included = Arc::new(Mutex::new(HashSet::new()));
+        let capture = Arc::clone(&included);
+        let mut receiver = ctx.block_feed().subscribe();
+        let capture = tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => {
+                        let mut included = capture.lock().expect("blend inclusion lock poisoned");
+                        included.extend(record.block.transactions().map(|tx| tx.hash()));
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "blend tx inclusion capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        self.included = Some(included);
+        self.capture = Some(capture);
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let included = self
+            .included
+            .as_ref()
+            .ok_or(BlendTxError::NotCaptured)?
+            .lock()
+            .expect("blend inclusion lock poisoned")
+            .clone();
+        let submissions = self.submissions.snapshot();
+        if submissions.is_empty() {
+            return Err(BlendTxError::NoSubmissions.into());
+        }
+
+        let grace = ctx
+            .run_metrics()
+            .block_interval_hint()
+            .map_or(FALLBACK_GRACE, |block| block * GRACE_BLOCKS);
+        let (settled, missing) = settle(&submissions, &included, grace);
+        let missing_total = missing.values().map(Vec::len).sum::<usize>();
+
+        ctx.report()
+            .set_counter("tx_workload", "blend_submitted", submissions.len() as u64);
+        ctx.report().set_counter(
+            "tx_workload",
+            "blend_included",
+            (settled - missing_total) as u64,
+        );
+        if missing.is_empty() {
+            tracing::info!(
+                submitted = submissions.len(),
+                settled = settled,
+                "every blend-path transaction was included"
+            );
+            return Ok(());
+        }
+
+        let details = missing
+            .into_iter()
+            .map(|(entry, txs)| {
+                let listed = txs
+                    .iter()
+                    .take(MAX_REPORTED_MISSING)
+                    .map(|tx| format!("{tx:?}"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let more = txs.len().saturating_sub(MAX_REPORTED_MISSING);
+                if more > 0 {
+                    format!("- via {entry}: {listed} and {more} more")
+                } else {
+                    format!("- via {entry}: {listed}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Err(BlendTxError::Missing {
+            missing: missing_total,
+            settled: settled,
+            details,
+        }
+        .into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nomos_core::mantle::ops::channel::ChannelId;
+
+    use super::*;
+    use crate::util::tx::create_inscription_transaction_with_id;
+
+    fn hash(id: u8) -> TxHash {
+        create_inscription_transaction_with_id(ChannelId::from([id; 32])).hash()
+    }
+
+    fn submission(tx: u8, entry: NodeTarget, age: Duration) -> BlendSubmission {
+        BlendSubmission {
+            tx: hash(tx),
+            entry,
+            at: Instant::now() - age,
+        }
+    }
+
+    #[test]
+    fn only_settled_missing_submissions_are_reported_per_entry() {
+        let grace = Duration::from_secs(10);
+        let old = Duration::from_secs(60);
+        let submissions = [
+            submission(1, NodeTarget::Validator(0), old),
+            submission(2, NodeTarget::Validator(0), old),
+            submission(3, NodeTarget::Executor(0), old),
+            submission(4, NodeTarget::Executor(0), Duration::ZERO),
+        ];
+        let included = HashSet::from([hash(1)]);
+
+        let (settled, missing) = settle(&submissions, &included, grace);
+
+        assert_eq!(settled, 3);
+        assert_eq!(missing["validator-0"], vec![hash(2)]);
+        assert_eq!(missing["executor-0"], vec![hash(3)]);
+    }
+}
@@ -14,7 +14,7 @@ use testing_framework_core::scenario::{DynError, Expectation, RunContext};
 use thiserror::Error;
 use tokio::sync::broadcast;
 
-use super::workload::{limited_user_count, submission_plan};
+use super::workload::{ClaimedAccounts, limited_user_count, submission_plan};
 
 const MIN_INCLUSION_RATIO: f64 = 0.5;
 
@@ -22,6 +22,8 @@ const MIN_INCLUSION_RATIO: f64 = 0.5;
 pub struct TxInclusionExpectation {
     txs_per_block: NonZeroU64,
     user_limit: Option<NonZeroUsize>,
+    relative_to_submitted: bool,
+    claimed: Option<ClaimedAccounts>,
     capture_state: Option<CaptureState>,
 }
 
@@ -55,9 +57,28 @@ impl TxInclusionExpectation {
         Self {
             txs_per_block,
             user_limit,
+            relative_to_submitted: false,
+            claimed: None,
             capture_state: None,
         }
     }
+
+    /// Measure inclusion against the transactions the workload actually
+    /// submitted instead of the planned count, for workloads that may hold
+    /// submissions back.
+    #[must_use]
+    pub const fn relative_to_submitted(mut self) -> Self {
+        self.relative_to_submitted = true;
+        self
+    }
+
+    /// Track the accounts the workload claimed instead of the first wallet
+    /// accounts, once the workload filled `claimed` in `init`.
+    #[must_use]
+    pub(super) fn tracking(mut self, claimed: ClaimedAccounts) -> Self {
+        self.claimed = Some(claimed);
+        self
+    }
 }
 
 #[async_trait]
@@ -71,13 +92,31 @@ impl Expectation for TxInclusionExpectation {
             return Ok(());
         }
 
-        let wallet_accounts = ctx.descriptors().config().wallet().accounts.clone();
+        let claimed = self
+            .claimed
+            .as_ref()
+            .map(|claimed| {
+                claimed
+                    .lock()
+                    .expect("claimed accounts lock poisoned")
+                    .clone()
+            })
+            .filter(|claimed| !claimed.is_empty());
+        let wallet_accounts = claimed.unwrap_or_else(|| {
+            ctx.descriptors()
+                .config()
+                .wallet()
+                .accounts
+                .iter()
+                .map(|account| account.secret_key.to_public_key())
+                .collect()
+        });
         if wallet_accounts.is_empty() {
             return Err(TxExpectationError::MissingAccounts.into());
         }
 
         let available = limited_user_count(self.user_limit, wallet_accounts.len());
-        let (planned, _) = submission_plan(self.txs_per_block, ctx, available)?;
+        let (planned, _) = submission_plan(self.txs_per_block, &ctx.run_metrics(), available)?;
         if planned == 0 {
             return Err(TxExpectationError::NoPlannedTransactions.into());
         }
@@ -92,7 +131,6 @@ impl Expectation for TxInclusionExpectation {
         let wallet_pks = wallet_accounts
             .into_iter()
             .take(planned)
-            .map(|account| account.secret_key.to_public_key())
             .collect::<HashSet<ZkPublicKey>>();
 
         let observed = Arc::new(AtomicU64::new(0));
@@ -100,6 +138,7 @@ impl Expectation for TxInclusionExpectation {
         let tracked_accounts: Arc<HashSet<ZkPublicKey>> = Arc::new(wallet_pks);
         let spawn_accounts: Arc<HashSet<ZkPublicKey>> = Arc::clone(&tracked_accounts);
         let spawn_observed = Arc::clone(&observed);
+        let latency = ctx.tx_latency().clone();
 
         tokio::spawn(async move {
             let mut receiver = receiver;
@@ -111,6 +150,7 @@ impl Expectation for TxInclusionExpectation {
                         if record.block.header().parent_block() == genesis_parent {
                             continue;
                         }
+                        latency.observe_block(&record);
 
                         for tx in record.block.transactions() {
                             for note in &tx.mantle_tx().ledger_tx.outputs {
@@ -142,20 +182,48 @@ impl Expectation for TxInclusionExpectation {
         Ok(())
     }
 
-    async fn evaluate(&mut self, _ctx: &RunContext) -> Result<(), DynError> {
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
         let state = self
             .capture_state
             .as_ref()
             .ok_or(TxExpectationError::NotCaptured)?;
 
         let observed = state.observed.load(Ordering::Relaxed);
-        let required = ((state.expected as f64) * MIN_INCLUSION_RATIO).ceil() as u64;
+        let expected = if self.relative_to_submitted {
+            let submitted = ctx
+                .report()
+                .snapshot()
+                .workloads
+                .get("tx_workload")
+                .and_then(|counters| counters.get("submitted"))
+                .copied()
+                .unwrap_or_default();
+            submitted.min(state.expected)
+        } else {
+            state.expected
+        };
+        let required = ((expected as f64) * MIN_INCLUSION_RATIO).ceil() as u64;
+        ctx.report()
+            .set_counter("tx_workload", "planned", state.expected);
+        ctx.report()
+            .set_counter("tx_workload", "accepted", observed);
+        if let Some(latency) = ctx.tx_latency_percentiles() {
+            for (counter, value) in [
+                ("inclusion_p50_ms", latency.p50),
+                ("inclusion_p95_ms", latency.p95),
+                ("inclusion_p99_ms", latency.p99),
+                ("inclusion_max_ms", latency.max),
+            ] {
+                ctx.report()
+                    .set_counter("tx_workload", counter, value.as_millis() as u64);
+            }
+        }
 
         if observed >= required {
             tracing::info!(
                 observed,
                 required,
-                expected = state.expected,
+                expected,
                 min_inclusion_ratio = MIN_INCLUSION_RATIO,
                 "tx inclusion expectation satisfied"
             );
@@ -164,7 +232,7 @@ impl Expectation for TxInclusionExpectation {
             tracing::warn!(
                 observed,
                 required,
-                expected = state.expected,
+                expected,
                 "tx inclusion expectation failed"
             );
             Err(TxExpectationError::InsufficientInclusions { observed, required }.into())
@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use testing_framework_core::scenario::{DynError, Expectation, RunContext};
+use thiserror::Error;
+use tokio::sync::broadcast;
+
+/// Fails the run when a percentile of transaction inclusion latency exceeds a
+/// threshold.
+///
+/// Latency is measured from the moment a node accepted a transaction
+/// submitted through the cluster to the moment the block feed observed it in
+/// a block. Transactions that never landed are not sampled; pair with
+/// [`super::TxInclusionExpectation`] to bound those.
+#[derive(Clone, Copy, Debug)]
+pub struct TxInclusionLatencyExpectation {
+    percentile: f64,
+    threshold: Duration,
+    capturing: bool,
+}
+
+#[derive(Debug, Error)]
+enum TxLatencyError {
+    #[error("no transaction inclusion was observed; cannot compute p{percentile}")]
+    NoSamples { percentile: f64 },
+    #[error(
+        "tx inclusion p{percentile} latency {observed:?} exceeds {threshold:?} ({samples} samples)"
+    )]
+    AboveThreshold {
+        percentile: f64,
+        observed: Duration,
+        threshold: Duration,
+        samples: usize,
+    },
+}
+
+impl TxInclusionLatencyExpectation {
+    pub const NAME: &'static str = "tx_inclusion_latency";
+
+    /// Require the `percentile` (`0.0..=100.0`) inclusion latency to stay at
+    /// or below `threshold`.
+    #[must_use]
+    pub const fn new(percentile: f64, threshold: Duration) -> Self {
+        Self {
+            percentile,
+            threshold,
+            capturing: false,
+        }
+    }
+
+    /// Require the p95 inclusion latency to stay at or below `threshold`.
+    #[must_use]
+    pub const fn p95(threshold: Duration) -> Self {
+        Self::new(95.0, threshold)
+    }
+}
+
+#[async_trait]
+impl Expectation for TxInclusionLatencyExpectation {
+    fn name(&self) -> &'static str {
+        Self::NAME
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["tx"]
+    }
+
+    async fn start_capture(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        if self.capturing {
+            return Ok(());
+        }
+        self.capturing = true;
+
+        let mut receiver = ctx.block_feed().subscribe();
+        let latency = ctx.tx_latency().clone();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(record) => latency.observe_block(&record),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::debug!(skipped, "tx latency capture lagged");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+        Ok(())
+    }
+
+    async fn evaluate(&mut self, ctx: &RunContext) -> Result<(), DynError> {
+        let percentile = self.percentile;
+        let latency = ctx.tx_latency();
+        let observed = latency
+            .percentile(percentile)
+            .ok_or(TxLatencyError::NoSamples { percentile })?;
+        let samples = ctx
+            .tx_latency_percentiles()
+            .map_or(0, |summary| summary.samples);
+
+        ctx.report()
+            .set_counter(Self::NAME, "observed_ms", observed.as_millis() as u64);
+        ctx.report()
+            .set_counter(Self::NAME, "pending", latency.pending() as u64);
+
+        if observed <= self.threshold {
+            tracing::info!(
+                percentile,
+                ?observed,
+                threshold = ?self.threshold,
+                samples,
+                "tx inclusion latency expectation satisfied"
+            );
+            return Ok(());
+        }
+
+        Err(TxLatencyError::AboveThreshold {
+            percentile,
+            observed,
+            threshold: self.threshold,
+            samples,
+        }
+        .into())
+    }
+}
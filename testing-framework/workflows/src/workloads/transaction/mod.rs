@@ -1,5 +1,13 @@
+mod backpressure;
+mod blend;
 mod expectation;
+mod latency;
+mod ramp;
 mod workload;
 
+pub use backpressure::MempoolBackpressure;
+pub use blend::BlendTxInclusionExpectation;
 pub use expectation::TxInclusionExpectation;
-pub use workload::Workload;
+pub use latency::TxInclusionLatencyExpectation;
+pub use ramp::RampWorkload;
+pub use workload::{SubmissionPath, Workload, claim_accounts, claim_child_accounts};
@@ -0,0 +1,346 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    num::{NonZeroU64, NonZeroUsize},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use key_management_system_service::keys::ZkPublicKey;
+use nomos_core::{header::HeaderId, mantle::AuthenticatedMantleTx as _};
+use testing_framework_core::{
+    scenario::{
+        DynError, Expectation, NodeTarget, RunContext, RunMetrics, Workload as ScenarioWorkload,
+    },
+    topology::generation::GeneratedTopology,
+};
+use tokio::{
+    sync::broadcast,
+    task::JoinHandle,
+    time::{Instant, sleep_until},
+};
+
+use super::workload::{
+    WalletInput, build_wallet_transaction, prepare_accounts, validate_submission_targets,
+};
+use crate::workloads::util::submit_transaction_via_nodes;
+
+/// Number of block windows a step's transactions may take to land before the
+/// step counts as delayed.
+const INCLUSION_GRACE_BLOCKS: u32 = 2;
+/// Fraction of a step's accepted transactions that must be included within
+/// the grace period.
+const MIN_STEP_INCLUSION_RATIO: f64 = 0.9;
+
+/// Mempool saturation workload that raises the per-block submission rate from
+/// `start_rate` to `end_rate` in `step` increments, holding each rate for one
+/// block window.
+///
+/// The first rate at which submissions are rejected or accepted transactions
+/// stop landing in blocks on time is recorded as the saturation point (counter
+/// `saturation_rate` of this workload in the run report), after which the
+/// ramp stops. Every wallet UTXO is spent at most once, so the ramp also stops
+/// when the seeded accounts run out.
+#[derive(Clone)]
+pub struct RampWorkload {
+    start_rate: NonZeroU64,
+    end_rate: NonZeroU64,
+    step: NonZeroU64,
+    user_limit: Option<NonZeroUsize>,
+    submit_via: Vec<NodeTarget>,
+    accounts: Vec<WalletInput>,
+}
+
+impl RampWorkload {
+    /// Creates a ramp from `start_rate` to `end_rate` transactions per block
+    /// (panics when `end_rate < start_rate`).
+    #[must_use]
+    pub fn new(start_rate: NonZeroU64, end_rate: NonZeroU64, step: NonZeroU64) -> Self {
+        assert!(
+            end_rate >= start_rate,
+            "ramp end rate must be at least the start rate"
+        );
+        Self {
+            start_rate,
+            end_rate,
+            step,
+            user_limit: None,
+            submit_via: Vec::new(),
+            accounts: Vec::new(),
+        }
+    }
+
+    /// Limits the number of distinct users that will submit transactions.
+    #[must_use]
+    pub const fn with_user_limit(mut self, user_limit: Option<NonZeroUsize>) -> Self {
+        self.user_limit = user_limit;
+        self
+    }
+
+    /// Submits every transaction through `targets` only, instead of a random
+    /// node of the cluster. An empty list restores the default.
+    #[must_use]
+    pub fn with_submission_targets(mut self, targets: Vec<NodeTarget>) -> Self {
+        self.submit_via = targets;
+        self
+    }
+
+    fn rates(&self) -> impl Iterator<Item = u64> {
+        (self.start_rate.get()..=self.end_rate.get()).step_by(self.step.get() as usize)
+    }
+}
+
+#[async_trait]
+impl ScenarioWorkload for RampWorkload {
+    fn name(&self) -> &'static str {
+        "tx_ramp_workload"
+    }
+
+    fn tags(&self) -> &[&'static str] {
+        &["tx", "slow"]
+    }
+
+    fn expectations(&self) -> Vec<Box<dyn Expectation>> {
+        Vec::new()
+    }
+
+    fn init(
+        &mut self,
+        descriptors: &GeneratedTopology,
+        _run_metrics: &RunMetrics,
+    ) -> Result<(), DynError> {
+        tracing::info!("initializing transaction ramp workload");
+        validate_submission_targets(descriptors, &self.submit_via)?;
+        self.accounts = prepare_accounts(descriptors, self.user_limit)?;
+        Ok(())
+    }
+
+    async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
+        let steps = self.rates().count() as u32;
+        let window = ctx
+            .run_metrics()
+            .block_interval_hint()
+            .unwrap_or_else(|| ctx.run_duration() / steps.max(1));
+        tracing::info!(
+            start_rate = self.start_rate.get(),
+            end_rate = self.end_rate.get(),
+            step = self.step.get(),
+            window_ms = window.as_millis(),
+            accounts_available = self.accounts.len(),
+            "starting transaction ramp"
+        );
+
+        let mut ramp = Ramp::new(self.name(), ctx, window, &self.submit_via);
+        ramp.run(self.rates(), &self.accounts).await
+    }
+}
+
+/// Transactions submitted while holding a single rate.
+struct Step {
+    rate: u64,
+    accepted: Vec<ZkPublicKey>,
+    rejected: u64,
+    deadline: Instant,
+}
+
+struct Ramp<'a> {
+    name: &'static str,
+    ctx: &'a RunContext,
+    window: Duration,
+    targets: &'a [NodeTarget],
+    included: Arc<Mutex<HashSet<ZkPublicKey>>>,
+    inclusion_task: JoinHandle<()>,
+    pending: VecDeque<Step>,
+    max_sustained_rate: Option<u64>,
+}
+
+impl Drop for Ramp<'_> {
+    fn drop(&mut self) {
+        // Also stops the tracker when the workload is cancelled mid-step.
+        self.inclusion_task.abort();
+    }
+}
+
+impl<'a> Ramp<'a> {
+    fn new(
+        name: &'static str,
+        ctx: &'a RunContext,
+        window: Duration,
+        targets: &'a [NodeTarget],
+    ) -> Self {
+        let included = Arc::new(Mutex::new(HashSet::new()));
+        let inclusion_task = spawn_inclusion_tracker(ctx, Arc::clone(&included));
+        Self {
+            name,
+            ctx,
+            window,
+            targets,
+            included,
+            inclusion_task,
+            pending: VecDeque::new(),
+            max_sustained_rate: None,
+        }
+    }
+
+    async fn run(
+        &mut self,
+        rates: impl Iterator<Item = u64>,
+        accounts: &[WalletInput],
+    ) -> Result<(), DynError> {
+        let mut accounts = accounts.iter();
+
+        for rate in rates {
+            if accounts.len() < rate as usize {
+                tracing::warn!(
+                    rate,
+                    remaining_accounts = accounts.len(),
+                    "transaction ramp ran out of wallet accounts"
+                );
+                break;
+            }
+
+            let step = self.submit_step(rate, accounts.by_ref()).await?;
+            self.pending.push_back(step);
+
+            if self.evaluate_due(false).await {
+                return Ok(());
+            }
+        }
+
+        if !self.evaluate_due(true).await {
+            tracing::info!(
+                max_sustained_rate = self.max_sustained_rate,
+                "transaction ramp finished without reaching saturation"
+            );
+        }
+        Ok(())
+    }
+
+    async fn submit_step(
+        &self,
+        rate: u64,
+        accounts: impl Iterator<Item = &WalletInput>,
+    ) -> Result<Step, DynError> {
+        let interval = self.window / rate as u32;
+        let started = Instant::now();
+        let mut step = Step {
+            rate,
+            accepted: Vec::new(),
+            rejected: 0,
+            deadline: started,
+        };
+
+        for (index, input) in accounts.take(rate as usize).enumerate() {
+            let signed_tx = Arc::new(build_wallet_transaction(input)?);
+            match submit_transaction_via_nodes(self.ctx, signed_tx, self.targets).await {
+                Ok(()) => {
+                    step.accepted.push(input.account.public_key());
+                    self.ctx.report().increment(self.name, "submitted", 1);
+                }
+                Err(err) => {
+                    tracing::debug!(rate, error = %err, "ramp transaction rejected");
+                    step.rejected += 1;
+                    self.ctx.report().increment(self.name, "rejected", 1);
+                }
+            }
+            sleep_until(started + interval * (index as u32 + 1)).await;
+        }
+
+        step.deadline = Instant::now() + self.window * INCLUSION_GRACE_BLOCKS;
+        tracing::info!(
+            rate,
+            accepted = step.accepted.len(),
+            rejected = step.rejected,
+            "transaction ramp step submitted"
+        );
+        Ok(step)
+    }
+
+    /// Evaluates pending steps whose grace period elapsed, or all of them
+    /// when `drain` is set. Returns `true` once saturation is detected.
+    async fn evaluate_due(&mut self, drain: bool) -> bool {
+        while let Some(step) = self.pending.front() {
+            if Instant::now() < step.deadline {
+                if !drain {
+                    return false;
+                }
+                sleep_until(step.deadline).await;
+            }
+
+            let step = self.pending.pop_front().expect("front step exists");
+            if self.evaluate(&step) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn evaluate(&mut self, step: &Step) -> bool {
+        let included = {
+            let included = self.included.lock().expect("ramp inclusion lock poisoned");
+            step.accepted
+                .iter()
+                .filter(|pk| included.contains(pk))
+                .count() as u64
+        };
+        let accepted = step.accepted.len() as u64;
+        let required = ((accepted as f64) * MIN_STEP_INCLUSION_RATIO).ceil() as u64;
+
+        if step.rejected == 0 && included >= required {
+            tracing::info!(
+                rate = step.rate,
+                accepted,
+                included,
+                "transaction ramp step sustained"
+            );
+            self.max_sustained_rate = Some(step.rate);
+            self.ctx
+                .report()
+                .set_counter(self.name, "max_sustained_rate", step.rate);
+            return false;
+        }
+
+        tracing::warn!(
+            rate = step.rate,
+            accepted,
+            rejected = step.rejected,
+            included,
+            required,
+            max_sustained_rate = self.max_sustained_rate,
+            "transaction ramp reached saturation"
+        );
+        self.ctx
+            .report()
+            .set_counter(self.name, "saturation_rate", step.rate);
+        true
+    }
+}
+
+fn spawn_inclusion_tracker(
+    ctx: &RunContext,
+    included: Arc<Mutex<HashSet<ZkPublicKey>>>,
+) -> JoinHandle<()> {
+    let mut receiver = ctx.block_feed().subscribe();
+    tokio::spawn(async move {
+        let genesis_parent = HeaderId::from([0; 32]);
+        loop {
+            match receiver.recv().await {
+                Ok(record) => {
+                    if record.block.header().parent_block() == genesis_parent {
+                        continue;
+                    }
+
+                    let mut included = included.lock().expect("ramp inclusion lock poisoned");
+                    for tx in record.block.transactions() {
+                        included
+                            .extend(tx.mantle_tx().ledger_tx.outputs.iter().map(|note| note.pk));
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "transaction ramp inclusion tracker lagged");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    })
+}
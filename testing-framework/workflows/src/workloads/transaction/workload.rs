@@ -1,36 +1,71 @@
 use std::{
     collections::{HashMap, VecDeque},
     num::{NonZeroU64, NonZeroUsize},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
 use async_trait::async_trait;
 use key_management_system_service::keys::{ZkKey, ZkPublicKey};
 use nomos_core::mantle::{
-    GenesisTx as _, Note, SignedMantleTx, Transaction as _, Utxo, tx_builder::MantleTxBuilder,
+    GenesisTx as _, Note, SignedMantleTx, Transaction as _, TxHash, Utxo,
+    tx_builder::MantleTxBuilder,
 };
 use testing_framework_config::topology::configs::wallet::WalletAccount;
 use testing_framework_core::{
-    scenario::{DynError, Expectation, RunContext, RunMetrics, Workload as ScenarioWorkload},
+    scenario::{
+        DynError, Expectation, NodeTarget, RunContext, RunMetrics, Workload as ScenarioWorkload,
+    },
     topology::generation::{GeneratedNodeConfig, GeneratedTopology},
 };
 use tokio::time::sleep;
 
-use super::expectation::TxInclusionExpectation;
-use crate::workloads::util::submit_transaction_via_cluster;
+use super::{
+    backpressure::{MempoolBackpressure, ThrottleStats},
+    blend::{BlendSubmissions, BlendTxInclusionExpectation},
+    expectation::TxInclusionExpectation,
+};
+use crate::workloads::{blend::core_blend_nodes, util::submit_transaction_via_nodes};
 
 #[derive(Clone)]
 pub struct Workload {
     txs_per_block: NonZeroU64,
     user_limit: Option<NonZeroUsize>,
+    submit_via: Vec<NodeTarget>,
+    backpressure: Option<MempoolBackpressure>,
+    /// Set on the blend submission path.
+    blend: Option<BlendSubmissions>,
     accounts: Vec<WalletInput>,
+    /// Keys of the accounts claimed in `init`, shared with
+    /// [`TxInclusionExpectation`] so it tracks the same accounts.
+    claimed: ClaimedAccounts,
+}
+
+/// Public keys of the wallet accounts a workload submits from, in order.
+pub(super) type ClaimedAccounts = Arc<Mutex<Vec<ZkPublicKey>>>;
+
+/// How the transaction workload hands transactions to the cluster.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SubmissionPath {
+    /// Submit each transaction to a random node, or a random pinned one.
+    #[default]
+    Mempool,
+    /// Enter every transaction through a core blend node (or a pinned node),
+    /// rotating the first choice and falling back to the others, and require
+    /// each one to be included, checked by
+    /// [`super::BlendTxInclusionExpectation`].
+    ///
+    /// Transactions themselves are not routed through Blend: nodes only take
+    /// them through their mempool API. What Blend carries is the block
+    /// proposal that includes them, so this path exercises Blend only as far
+    /// as block production does.
+    Blend,
 }
 
 #[derive(Clone)]
-struct WalletInput {
-    account: WalletAccount,
-    utxo: Utxo,
+pub(super) struct WalletInput {
+    pub(super) account: WalletAccount,
+    pub(super) utxo: Utxo,
 }
 
 #[async_trait]
@@ -39,11 +74,23 @@ impl ScenarioWorkload for Workload {
         "tx_workload"
     }
 
+    fn tags(&self) -> &[&'static str] {
+        &["tx"]
+    }
+
     fn expectations(&self) -> Vec<Box<dyn Expectation>> {
-        vec![Box::new(TxInclusionExpectation::new(
-            self.txs_per_block,
-            self.user_limit,
-        ))]
+        let mut expectation = TxInclusionExpectation::new(self.txs_per_block, self.user_limit)
+            .tracking(Arc::clone(&self.claimed));
+        if self.backpressure.is_some() {
+            expectation = expectation.relative_to_submitted();
+        }
+        let mut expectations: Vec<Box<dyn Expectation>> = vec![Box::new(expectation)];
+        if let Some(submissions) = &self.blend {
+            expectations.push(Box::new(BlendTxInclusionExpectation::new(
+                submissions.clone(),
+            )));
+        }
+        expectations
     }
 
     fn init(
@@ -52,50 +99,47 @@ impl ScenarioWorkload for Workload {
         _run_metrics: &RunMetrics,
     ) -> Result<(), DynError> {
         tracing::info!("initializing transaction workload");
-        let wallet_accounts = descriptors.config().wallet().accounts.clone();
-        if wallet_accounts.is_empty() {
-            return Err("transaction workload requires seeded accounts".into());
-        }
-
-        let reference_node = descriptors
-            .validators()
-            .first()
-            .or_else(|| descriptors.executors().first())
-            .ok_or("transaction workload requires at least one node in the topology")?;
-
-        let utxo_map = wallet_utxo_map(reference_node);
-        let mut accounts = wallet_accounts
-            .into_iter()
-            .filter_map(|account| {
-                utxo_map
-                    .get(&account.public_key())
-                    .copied()
-                    .map(|utxo| WalletInput { account, utxo })
-            })
-            .collect::<Vec<_>>();
-
-        apply_user_limit(&mut accounts, self.user_limit);
-
-        if accounts.is_empty() {
-            return Err(
-                "transaction workload could not match any accounts to genesis UTXOs".into(),
-            );
+        validate_submission_targets(descriptors, &self.submit_via)?;
+        if self.blend.is_some() && self.submit_via.is_empty() {
+            self.submit_via = core_blend_nodes(descriptors);
+            if self.submit_via.is_empty() {
+                return Err("blend submission path needs at least one core blend node".into());
+            }
         }
-
-        tracing::info!(
-            available_accounts = accounts.len(),
-            user_limit = self.user_limit.map(|u| u.get()),
-            "transaction workload accounts prepared"
-        );
-
-        self.accounts = accounts;
+        self.accounts = prepare_accounts(descriptors, self.user_limit)?;
+        *self.claimed.lock().expect("claimed accounts lock poisoned") = self
+            .accounts
+            .iter()
+            .map(|input| input.account.public_key())
+            .collect();
         Ok(())
     }
 
+    fn plan(&self, run_metrics: &RunMetrics) -> Option<String> {
+        let path = if self.blend.is_some() {
+            "blend entry nodes"
+        } else {
+            "the mempool"
+        };
+        Some(
+            match submission_plan(self.txs_per_block, run_metrics, self.accounts.len()) {
+                Ok((planned, interval)) => format!(
+                    "{planned} txs from {} accounts via {path}, one every {}ms",
+                    self.accounts.len(),
+                    interval.as_millis()
+                ),
+                Err(err) => format!("no transactions: {err}"),
+            },
+        )
+    }
+
     async fn start(&self, ctx: &RunContext) -> Result<(), DynError> {
         tracing::info!(
             txs_per_block = self.txs_per_block.get(),
             users = self.user_limit.map(|u| u.get()),
+            submit_via = ?self.submit_via,
+            blend = self.blend.is_some(),
+            max_pending = self.backpressure.map(|b| b.max_pending().get()),
             "starting transaction workload submission"
         );
         Submission::new(self, ctx)?.execute().await
@@ -106,11 +150,15 @@ impl Workload {
     /// Creates a workload that targets the provided transactions per block
     /// rate.
     #[must_use]
-    pub const fn new(txs_per_block: NonZeroU64) -> Self {
+    pub fn new(txs_per_block: NonZeroU64) -> Self {
         Self {
             txs_per_block,
             user_limit: None,
+            submit_via: Vec::new(),
+            backpressure: None,
+            blend: None,
             accounts: Vec::new(),
+            claimed: ClaimedAccounts::default(),
         }
     }
 
@@ -132,6 +180,33 @@ impl Workload {
         self.user_limit = user_limit;
         self
     }
+
+    /// Submits every transaction through `targets` only, instead of a random
+    /// node of the cluster. An empty list restores the default.
+    #[must_use]
+    pub fn with_submission_targets(mut self, targets: Vec<NodeTarget>) -> Self {
+        self.submit_via = targets;
+        self
+    }
+
+    /// Pauses submission while the mempool is saturated instead of holding
+    /// the configured rate regardless; `None` disables it.
+    #[must_use]
+    pub const fn with_backpressure(mut self, backpressure: Option<MempoolBackpressure>) -> Self {
+        self.backpressure = backpressure;
+        self
+    }
+
+    /// Selects how transactions are handed to the cluster, see
+    /// [`SubmissionPath`].
+    #[must_use]
+    pub fn with_submission_path(mut self, path: SubmissionPath) -> Self {
+        self.blend = match path {
+            SubmissionPath::Mempool => None,
+            SubmissionPath::Blend => Some(self.blend.unwrap_or_default()),
+        };
+        self
+    }
 }
 
 impl Default for Workload {
@@ -144,16 +219,22 @@ struct Submission<'a> {
     plan: VecDeque<WalletInput>,
     ctx: &'a RunContext,
     interval: Duration,
+    targets: &'a [NodeTarget],
+    backpressure: Option<MempoolBackpressure>,
+    blend: Option<&'a BlendSubmissions>,
 }
 
 impl<'a> Submission<'a> {
-    fn new(workload: &Workload, ctx: &'a RunContext) -> Result<Self, DynError> {
+    fn new(workload: &'a Workload, ctx: &'a RunContext) -> Result<Self, DynError> {
         if workload.accounts.is_empty() {
             return Err("transaction workload has no available accounts".into());
         }
 
-        let (planned, interval) =
-            submission_plan(workload.txs_per_block, ctx, workload.accounts.len())?;
+        let (planned, interval) = submission_plan(
+            workload.txs_per_block,
+            &ctx.run_metrics(),
+            workload.accounts.len(),
+        )?;
 
         let plan = workload
             .accounts
@@ -173,6 +254,9 @@ impl<'a> Submission<'a> {
             plan,
             ctx,
             interval,
+            targets: &workload.submit_via,
+            backpressure: workload.backpressure,
+            blend: workload.blend.as_ref(),
         })
     }
 
@@ -183,8 +267,24 @@ impl<'a> Submission<'a> {
             interval_ms = self.interval.as_millis(),
             "begin transaction submissions"
         );
+        let mut throttle = ThrottleStats::new(self.ctx.report().clone(), "tx_workload");
+        let mut sequence = 0usize;
         while let Some(input) = self.plan.pop_front() {
-            submit_wallet_transaction(self.ctx, &input).await?;
+            if let Some(backpressure) = &self.backpressure {
+                backpressure
+                    .wait_for_capacity(self.ctx, self.targets, &mut throttle)
+                    .await;
+                throttle.report();
+            }
+            match self.blend {
+                Some(submissions) => {
+                    let (tx_hash, entry) =
+                        submit_via_entries(self.ctx, &input, self.targets, sequence).await?;
+                    submissions.record(tx_hash, entry);
+                }
+                None => submit_wallet_transaction(self.ctx, &input, self.targets).await?,
+            }
+            sequence += 1;
 
             if !self.interval.is_zero() {
                 sleep(self.interval).await;
@@ -196,17 +296,162 @@ impl<'a> Submission<'a> {
     }
 }
 
-async fn submit_wallet_transaction(ctx: &RunContext, input: &WalletInput) -> Result<(), DynError> {
+/// Submits through `targets`, starting at the `sequence`-th one in rotation
+/// and moving on to the next while they refuse it. Returns the entry node
+/// that took the transaction.
+async fn submit_via_entries(
+    ctx: &RunContext,
+    input: &WalletInput,
+    targets: &[NodeTarget],
+    sequence: usize,
+) -> Result<(TxHash, NodeTarget), DynError> {
+    let signed_tx = Arc::new(build_wallet_transaction(input)?);
+    let tx_hash = signed_tx.hash();
+    let mut last_err = None;
+    for entry in entry_order(targets, sequence) {
+        match submit_transaction_via_nodes(ctx, Arc::clone(&signed_tx), &[entry]).await {
+            Ok(()) => {
+                ctx.report().increment("tx_workload", "submitted", 1);
+                return Ok((tx_hash, entry));
+            }
+            Err(err) => {
+                tracing::debug!(?tx_hash, %entry, error = %err, "blend entry node refused transaction");
+                last_err = Some(err);
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| "no blend entry node to submit to".into()))
+}
+
+/// `targets` rotated to start at the `sequence`-th one.
+fn entry_order(targets: &[NodeTarget], sequence: usize) -> impl Iterator<Item = NodeTarget> + '_ {
+    let start = sequence.checked_rem(targets.len()).unwrap_or(0);
+    targets[start..].iter().chain(&targets[..start]).copied()
+}
+
+async fn submit_wallet_transaction(
+    ctx: &RunContext,
+    input: &WalletInput,
+    targets: &[NodeTarget],
+) -> Result<(), DynError> {
     let signed_tx = Arc::new(build_wallet_transaction(input)?);
+    let tx_hash = signed_tx.hash();
     tracing::debug!(
-        tx_hash = ?signed_tx.hash(),
+        ?tx_hash,
         user = ?input.account.public_key(),
         "submitting wallet transaction"
     );
-    submit_transaction_via_cluster(ctx, signed_tx).await
+    submit_transaction_via_nodes(ctx, signed_tx, targets).await?;
+    ctx.report().increment("tx_workload", "submitted", 1);
+    Ok(())
+}
+
+/// Matches seeded wallet accounts to their genesis UTXOs, honouring the
+/// optional user limit.
+pub(super) fn prepare_accounts(
+    descriptors: &GeneratedTopology,
+    user_limit: Option<NonZeroUsize>,
+) -> Result<Vec<WalletInput>, DynError> {
+    if descriptors.config().wallet().accounts.is_empty() {
+        return Err("transaction workload requires seeded accounts".into());
+    }
+
+    let reference_node = descriptors
+        .validators()
+        .first()
+        .or_else(|| descriptors.executors().first())
+        .ok_or("transaction workload requires at least one node in the topology")?;
+
+    let utxo_map = wallet_utxo_map(reference_node);
+    let limit = user_limit.map_or(usize::MAX, NonZeroUsize::get);
+    let accounts = descriptors
+        .wallet_pool()
+        .claim_where(limit, |account| {
+            utxo_map.contains_key(&account.public_key())
+        })
+        .into_iter()
+        .map(|account| {
+            let utxo = utxo_map[&account.public_key()];
+            WalletInput { account, utxo }
+        })
+        .collect::<Vec<_>>();
+
+    if accounts.is_empty() {
+        return Err(
+            "transaction workload could not match any unclaimed accounts to genesis UTXOs".into(),
+        );
+    }
+
+    tracing::info!(
+        available_accounts = accounts.len(),
+        user_limit = user_limit.map(|u| u.get()),
+        "transaction workload accounts prepared"
+    );
+
+    Ok(accounts)
+}
+
+/// Claim up to `count` wallet accounts no other workload uses, each with the
+/// genesis note it can spend. Call from `init` or during the run; accounts
+/// without a genesis note are skipped but stay claimed.
+pub fn claim_accounts(
+    descriptors: &GeneratedTopology,
+    count: usize,
+) -> Result<Vec<(WalletAccount, Utxo)>, DynError> {
+    claimed_inputs(descriptors, descriptors.wallet_pool().claim(count))
+}
+
+/// [`claim_accounts`] restricted to the derived accounts of `user`.
+pub fn claim_child_accounts(
+    descriptors: &GeneratedTopology,
+    user: u32,
+    count: usize,
+) -> Result<Vec<(WalletAccount, Utxo)>, DynError> {
+    claimed_inputs(
+        descriptors,
+        descriptors.wallet_pool().claim_children(user, count),
+    )
+}
+
+fn claimed_inputs(
+    descriptors: &GeneratedTopology,
+    accounts: Vec<WalletAccount>,
+) -> Result<Vec<(WalletAccount, Utxo)>, DynError> {
+    let reference_node = descriptors
+        .nodes()
+        .next()
+        .ok_or("wallet accounts require at least one node in the topology")?;
+    let utxo_map = wallet_utxo_map(reference_node);
+    Ok(accounts
+        .into_iter()
+        .filter_map(|account| {
+            let utxo = utxo_map.get(&account.public_key()).copied()?;
+            Some((account, utxo))
+        })
+        .collect())
 }
 
-fn build_wallet_transaction(input: &WalletInput) -> Result<SignedMantleTx, DynError> {
+/// Fails when a pinned submission target is not part of the topology.
+pub(super) fn validate_submission_targets(
+    descriptors: &GeneratedTopology,
+    targets: &[NodeTarget],
+) -> Result<(), DynError> {
+    for target in targets {
+        let deployed = match target {
+            NodeTarget::Validator(index) => *index < descriptors.validators().len(),
+            NodeTarget::Executor(index) => *index < descriptors.executors().len(),
+        };
+        if !deployed {
+            return Err(format!(
+                "transaction submission target {target} is not part of the topology"
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+pub(super) fn build_wallet_transaction(input: &WalletInput) -> Result<SignedMantleTx, DynError> {
     let builder = MantleTxBuilder::new()
         .add_ledger_input(input.utxo)
         .add_ledger_output(Note::new(input.utxo.note.value, input.account.public_key()));
@@ -238,31 +483,23 @@ fn wallet_utxo_map(node: &GeneratedNodeConfig) -> HashMap<ZkPublicKey, Utxo> {
         .collect()
 }
 
-fn apply_user_limit<T>(items: &mut Vec<T>, user_limit: Option<NonZeroUsize>) {
-    if let Some(limit) = user_limit {
-        let allowed = limit.get().min(items.len());
-        items.truncate(allowed);
-    }
-}
-
 pub(super) fn limited_user_count(user_limit: Option<NonZeroUsize>, available: usize) -> usize {
     user_limit.map_or(available, |limit| limit.get().min(available))
 }
 
 pub(super) fn submission_plan(
     txs_per_block: NonZeroU64,
-    ctx: &RunContext,
+    run_metrics: &RunMetrics,
     available_accounts: usize,
 ) -> Result<(usize, Duration), DynError> {
     if available_accounts == 0 {
         return Err("transaction workload scheduled zero transactions".into());
     }
 
-    let run_secs = ctx.run_duration().as_secs_f64();
-    let block_secs = ctx
-        .run_metrics()
+    let run_secs = run_metrics.run_duration().as_secs_f64();
+    let block_secs = run_metrics
         .block_interval_hint()
-        .unwrap_or_else(|| ctx.run_duration())
+        .unwrap_or_else(|| run_metrics.run_duration())
         .as_secs_f64();
 
     let expected_blocks = run_secs / block_secs;
@@ -278,3 +515,27 @@ pub(super) fn submission_plan(
     let interval = Duration::from_secs_f64(run_secs / planned as f64);
     Ok((planned, interval))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_order_rotates_and_keeps_every_target() {
+        let targets = [
+            NodeTarget::Validator(0),
+            NodeTarget::Validator(1),
+            NodeTarget::Executor(0),
+        ];
+
+        assert_eq!(
+            entry_order(&targets, 4).collect::<Vec<_>>(),
+            vec![
+                NodeTarget::Validator(1),
+                NodeTarget::Executor(0),
+                NodeTarget::Validator(0),
+            ]
+        );
+        assert_eq!(entry_order(&[], 3).count(), 0);
+    }
+}
@@ -7,8 +7,11 @@ use nomos_core::{
         ops::{Op, channel::MsgId},
     },
 };
-use rand::{seq::SliceRandom as _, thread_rng};
-use testing_framework_core::scenario::{DynError, RunContext};
+use rand::seq::SliceRandom as _;
+use testing_framework_core::{
+    nodes::ApiClient,
+    scenario::{DynError, NodeTarget, RunContext},
+};
 use tracing::debug;
 
 /// Scans a block and invokes the matcher for every operation until it returns
@@ -33,7 +36,7 @@ where
 }
 
 /// Submits a transaction to the cluster, fanning out across clients until one
-/// succeeds.
+/// succeeds. Accepted transactions are tracked in [`RunContext::tx_latency`].
 pub async fn submit_transaction_via_cluster(
     ctx: &RunContext,
     tx: Arc<SignedMantleTx>,
@@ -47,21 +50,66 @@ pub async fn submit_transaction_via_cluster(
     let node_clients = ctx.node_clients();
     let mut validator_clients: Vec<_> = node_clients.validator_clients().iter().collect();
     let mut executor_clients: Vec<_> = node_clients.executor_clients().iter().collect();
-    validator_clients.shuffle(&mut thread_rng());
-    executor_clients.shuffle(&mut thread_rng());
+    // Derive the fan-out order from the transaction so seeded runs pick the
+    // same nodes.
+    let mut rng = ctx.rng(&format!("tx_submission/{tx_hash:?}"));
+    validator_clients.shuffle(&mut rng);
+    executor_clients.shuffle(&mut rng);
+
+    submit_transaction_to(
+        ctx,
+        &tx,
+        validator_clients.into_iter().chain(executor_clients),
+    )
+    .await
+}
 
-    let clients = validator_clients.into_iter().chain(executor_clients);
+/// Submits a transaction only through `targets`, trying them in order until
+/// one accepts it, so mempool gossip can be observed from known entry
+/// points. An empty `targets` falls back to
+/// [`submit_transaction_via_cluster`].
+pub async fn submit_transaction_via_nodes(
+    ctx: &RunContext,
+    tx: Arc<SignedMantleTx>,
+    targets: &[NodeTarget],
+) -> Result<(), DynError> {
+    if targets.is_empty() {
+        return submit_transaction_via_cluster(ctx, tx).await;
+    }
+
+    let node_clients = ctx.node_clients();
+    let mut clients = Vec::with_capacity(targets.len());
+    for target in targets {
+        let client = node_clients
+            .client(*target)
+            .ok_or_else(|| format!("transaction submission target {target} is not deployed"))?;
+        clients.push(client);
+    }
+    debug!(tx_hash = ?tx.hash(), ?targets, "submitting transaction via pinned nodes");
+
+    submit_transaction_to(ctx, &tx, clients).await
+}
+
+async fn submit_transaction_to<'a>(
+    ctx: &RunContext,
+    tx: &SignedMantleTx,
+    clients: impl IntoIterator<Item = &'a ApiClient>,
+) -> Result<(), DynError> {
+    let tx_hash = tx.hash();
     let mut last_err = None;
 
     for client in clients {
         let url = client.base_url().clone();
         debug!(?tx_hash, %url, "submitting transaction to client");
         match client
-            .submit_transaction(&tx)
+            .submit_transaction(tx)
             .await
             .map_err(|err| -> DynError { err.into() })
         {
-            Ok(()) => return Ok(()),
+            Ok(()) => {
+                ctx.tx_latency().record_submission(tx_hash);
+                return Ok(());
+            }
             Err(err) => {
                 debug!(?tx_hash, %url, "transaction submission failed");
                 last_err = Some(err);